@@ -0,0 +1,526 @@
+use crate::llm::{
+    ChatMessage, ChatProvider, ChatRequest, ChatResponse, FunctionCall, FunctionDefinition, MessageContent,
+    MessageRole, ToolCall, ToolDefinition, Usage,
+};
+use crate::{Result, ToolRegistry};
+use async_trait::async_trait;
+use futures_util::{future::join_all, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+
+#[cfg(feature = "mcp-inprocess")]
+use crate::mcp::McpHost;
+
+const DEFAULT_MAX_ITERATIONS: u32 = 10;
+
+type Arguments = serde_json::Map<String, serde_json::Value>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AgentEvent {
+    Thinking { text: String },
+    TextDelta { text: String },
+    ToolStart { id: String, name: String, arguments: serde_json::Value },
+    ToolFinish { id: String, name: String, output: String, is_error: bool },
+    ToolDenied { id: String, name: String, reason: String },
+    // `total` is cumulative across the run.
+    Usage { usage: Usage, total: AgentUsage },
+    Finished { outcome: AgentOutcome },
+    Error { message: String },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StopReason {
+    Completed,
+    MaxIterations,
+    TokenBudget,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentUsage {
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub total_tokens: u64,
+}
+
+impl AgentUsage {
+    fn add(&mut self, usage: &Usage) {
+        self.prompt_tokens += u64::from(usage.prompt_tokens);
+        self.completion_tokens += u64::from(usage.completion_tokens);
+        self.total_tokens += u64::from(usage.total_tokens);
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentOutcome {
+    // The full transcript, including the messages passed in.
+    pub messages: Vec<ChatMessage>,
+    pub content: Option<String>,
+    pub stop_reason: StopReason,
+    pub iterations: u32,
+    pub usage: AgentUsage,
+}
+
+pub type AgentEventStream = Pin<Box<dyn Stream<Item = AgentEvent> + Send>>;
+
+#[derive(Debug, Clone)]
+pub enum Approval {
+    Approve,
+    // Run the call with these arguments instead.
+    Edit(serde_json::Value),
+    // Skip the call; the reason is returned to the model as the tool result.
+    Deny(String),
+}
+
+#[async_trait]
+pub trait ApprovalHook: Send + Sync {
+    async fn review(&self, call: &ToolCall) -> Approval;
+}
+
+#[async_trait]
+impl<F> ApprovalHook for F
+where
+    F: Fn(&ToolCall) -> Approval + Send + Sync,
+{
+    async fn review(&self, call: &ToolCall) -> Approval {
+        self(call)
+    }
+}
+
+// Runs the chat/tool-call loop against any `ChatProvider`: ask the model, run
+// the tools it requests (concurrently, after approval), feed the results back
+// and repeat until it answers or a budget runs out.
+#[derive(Clone)]
+pub struct Agent {
+    provider: Arc<dyn ChatProvider>,
+    model: String,
+    system_prompt: Option<String>,
+    max_tokens: Option<u32>,
+    temperature: Option<f32>,
+    thinking_budget: Option<u32>,
+    max_iterations: u32,
+    token_budget: Option<u64>,
+    streaming: bool,
+    tools: Option<Arc<ToolRegistry>>,
+    #[cfg(feature = "mcp-inprocess")]
+    mcp: Option<Arc<McpHost>>,
+    approval: Option<Arc<dyn ApprovalHook>>,
+}
+
+impl Agent {
+    pub fn new(provider: impl ChatProvider + 'static, model: impl Into<String>) -> Self {
+        Self {
+            provider: Arc::new(provider),
+            model: model.into(),
+            system_prompt: None,
+            max_tokens: None,
+            temperature: None,
+            thinking_budget: None,
+            max_iterations: DEFAULT_MAX_ITERATIONS,
+            token_budget: None,
+            streaming: false,
+            tools: None,
+            #[cfg(feature = "mcp-inprocess")]
+            mcp: None,
+            approval: None,
+        }
+    }
+
+    pub fn with_system_prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.system_prompt = Some(prompt.into());
+        self
+    }
+
+    // Per-response limit passed to the provider.
+    pub fn with_max_tokens(mut self, max_tokens: u32) -> Self {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
+    pub fn with_temperature(mut self, temperature: f32) -> Self {
+        self.temperature = Some(temperature);
+        self
+    }
+
+    pub fn with_thinking(mut self, budget_tokens: u32) -> Self {
+        self.thinking_budget = Some(budget_tokens);
+        self
+    }
+
+    pub fn with_max_iterations(mut self, max_iterations: u32) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    // Stops the run once the tokens used across all model calls reach `budget`.
+    pub fn with_token_budget(mut self, budget: u64) -> Self {
+        self.token_budget = Some(budget);
+        self
+    }
+
+    // Uses `chat_stream` so text arrives as it is generated.
+    pub fn with_streaming(mut self, streaming: bool) -> Self {
+        self.streaming = streaming;
+        self
+    }
+
+    pub fn with_tools(mut self, tools: ToolRegistry) -> Self {
+        self.tools = Some(Arc::new(tools));
+        self
+    }
+
+    #[cfg(feature = "mcp-inprocess")]
+    pub fn with_mcp_host(mut self, host: McpHost) -> Self {
+        self.mcp = Some(Arc::new(host));
+        self
+    }
+
+    pub fn with_approval(mut self, hook: impl ApprovalHook + 'static) -> Self {
+        self.approval = Some(Arc::new(hook));
+        self
+    }
+
+    pub async fn run(&self, messages: Vec<ChatMessage>) -> Result<AgentOutcome> {
+        self.drive(messages, None).await
+    }
+
+    // Runs the agent on a background task, ending the stream with `Finished`
+    // or `Error`.
+    pub fn stream(&self, messages: Vec<ChatMessage>) -> AgentEventStream {
+        let (tx, rx) = unbounded_channel();
+        let agent = self.clone();
+        let task = tokio::spawn(async move {
+            let event = match agent.drive(messages, Some(&tx)).await {
+                Ok(outcome) => AgentEvent::Finished { outcome },
+                Err(e) => AgentEvent::Error { message: e.to_string() },
+            };
+            let _ = tx.send(event);
+        });
+        // Dropping the stream aborts the run, including any tool calls in flight.
+        let state = (rx, AbortOnDrop(task));
+        futures_util::stream::unfold(state, |(mut rx, task)| async move {
+            rx.recv().await.map(|event| (event, (rx, task)))
+        })
+        .boxed()
+    }
+
+    fn tool_definitions(&self) -> Vec<ToolDefinition> {
+        let mut definitions = Vec::new();
+
+        if let Some(tools) = &self.tools {
+            for spec in tools.definitions() {
+                let function = spec.to_openai_function();
+                definitions.push(ToolDefinition {
+                    tool_type: "function".to_string(),
+                    function: FunctionDefinition {
+                        name: spec.id,
+                        description: Some(spec.description),
+                        parameters: function["parameters"].clone(),
+                    },
+                });
+            }
+        }
+
+        #[cfg(feature = "mcp-inprocess")]
+        if let Some(host) = &self.mcp {
+            for tool in host.tools() {
+                if definitions.iter().any(|d| d.function.name == tool.name) {
+                    continue;
+                }
+                definitions.push(ToolDefinition {
+                    tool_type: "function".to_string(),
+                    function: FunctionDefinition {
+                        name: tool.name.to_string(),
+                        description: tool.description.as_ref().map(ToString::to_string),
+                        parameters: serde_json::Value::Object((*tool.input_schema).clone()),
+                    },
+                });
+            }
+        }
+
+        definitions
+    }
+
+    async fn drive(&self, mut messages: Vec<ChatMessage>, events: Option<&UnboundedSender<AgentEvent>>) -> Result<AgentOutcome> {
+        let emit = |event: AgentEvent| {
+            if let Some(tx) = events {
+                let _ = tx.send(event);
+            }
+        };
+
+        if let Some(prompt) = &self.system_prompt {
+            if !messages.iter().any(|m| m.role == MessageRole::System) {
+                messages.insert(0, ChatMessage::system(prompt.clone()));
+            }
+        }
+
+        let definitions = self.tool_definitions();
+        let mut usage = AgentUsage::default();
+        let mut iterations = 0;
+
+        loop {
+            if iterations >= self.max_iterations {
+                return Ok(self.outcome(messages, StopReason::MaxIterations, iterations, usage));
+            }
+            if self.token_budget.is_some_and(|budget| usage.total_tokens >= budget) {
+                return Ok(self.outcome(messages, StopReason::TokenBudget, iterations, usage));
+            }
+            iterations += 1;
+
+            let request = self.request(&messages, &definitions);
+            let response = if self.streaming {
+                self.chat_streaming(&request, &emit).await?
+            } else {
+                let response = self.provider.chat(&request).await?;
+                if let Some(text) = response.content().filter(|t| !t.is_empty()) {
+                    emit(AgentEvent::TextDelta { text: text.to_string() });
+                }
+                if let Some(thinking) = response.thinking() {
+                    emit(AgentEvent::Thinking { text: thinking.to_string() });
+                }
+                response
+            };
+
+            if let Some(response_usage) = &response.usage {
+                usage.add(response_usage);
+                emit(AgentEvent::Usage {
+                    usage: response_usage.clone(),
+                    total: usage,
+                });
+            }
+
+            let Some(choice) = response.choices.into_iter().next() else {
+                return Ok(self.outcome(messages, StopReason::Completed, iterations, usage));
+            };
+            let mut message = choice.message;
+            let calls = message.tool_calls.take().filter(|calls| !calls.is_empty());
+
+            let Some(calls) = calls else {
+                messages.push(message);
+                return Ok(self.outcome(messages, StopReason::Completed, iterations, usage));
+            };
+
+            let mut reviewed = Vec::with_capacity(calls.len());
+            for call in calls {
+                reviewed.push(self.review(call).await);
+            }
+
+            // The transcript records the calls as they were actually made.
+            message.tool_calls = Some(reviewed.iter().map(|(call, _)| call.clone()).collect());
+            if matches!(&message.content, MessageContent::Text(text) if text.is_empty()) {
+                message.content = MessageContent::Text(" ".to_string());
+            }
+            messages.push(message);
+
+            let results = join_all(reviewed.iter().map(|(call, denial)| async move {
+                if let Some(reason) = denial {
+                    emit(AgentEvent::ToolDenied {
+                        id: call.id.clone(),
+                        name: call.function.name.clone(),
+                        reason: reason.clone(),
+                    });
+                    return format!("Tool call denied: {}", reason);
+                }
+
+                let arguments = match parse_arguments(&call.function.arguments) {
+                    Ok(arguments) => arguments,
+                    // Let the model correct its arguments instead of running the tool without them.
+                    Err(e) => {
+                        let output = format!("Error: invalid tool arguments: {}", e);
+                        emit(AgentEvent::ToolFinish {
+                            id: call.id.clone(),
+                            name: call.function.name.clone(),
+                            output: output.clone(),
+                            is_error: true,
+                        });
+                        return output;
+                    }
+                };
+                emit(AgentEvent::ToolStart {
+                    id: call.id.clone(),
+                    name: call.function.name.clone(),
+                    arguments: serde_json::Value::Object(arguments.clone()),
+                });
+                let (output, is_error) = match self.execute(&call.function.name, arguments).await {
+                    Ok(output) => (output, false),
+                    Err(e) => (format!("Error: {}", e), true),
+                };
+                emit(AgentEvent::ToolFinish {
+                    id: call.id.clone(),
+                    name: call.function.name.clone(),
+                    output: output.clone(),
+                    is_error,
+                });
+                output
+            }))
+            .await;
+
+            for ((call, _), output) in reviewed.iter().zip(results) {
+                messages.push(ChatMessage::tool_result(call.id.clone(), output));
+            }
+        }
+    }
+
+    fn request(&self, messages: &[ChatMessage], definitions: &[ToolDefinition]) -> ChatRequest {
+        let mut request = ChatRequest::new(self.model.clone(), messages.to_vec());
+        request.max_tokens = self.max_tokens;
+        request.temperature = self.temperature;
+        if !definitions.is_empty() {
+            request = request.with_tools(definitions.to_vec());
+        }
+        if let Some(budget) = self.thinking_budget {
+            request = request.with_thinking(budget);
+        }
+        request
+    }
+
+    // Folds a streamed response back into a `ChatResponse`, emitting text as
+    // it arrives.
+    async fn chat_streaming(&self, request: &ChatRequest, emit: &(impl Fn(AgentEvent) + Sync)) -> Result<ChatResponse> {
+        let mut stream = self.provider.chat_stream(request).await?;
+        let mut id = String::new();
+        let mut content = String::new();
+        let mut thinking = String::new();
+        let mut calls: Vec<ToolCall> = Vec::new();
+        let mut finish_reason = None;
+        let mut usage = None;
+
+        while let Some(event) = stream.next().await {
+            let event = event?;
+            if let Some(event_id) = event.id {
+                id = event_id;
+            }
+            if let Some(delta) = event.delta {
+                if let Some(text) = delta.content.filter(|t| !t.is_empty()) {
+                    content.push_str(&text);
+                    emit(AgentEvent::TextDelta { text });
+                }
+                if let Some(text) = delta.thinking.filter(|t| !t.is_empty()) {
+                    thinking.push_str(&text);
+                    emit(AgentEvent::Thinking { text });
+                }
+                for call in delta.tool_calls.unwrap_or_default() {
+                    let index = call.index as usize;
+                    if calls.len() <= index {
+                        calls.resize_with(index + 1, || ToolCall {
+                            id: String::new(),
+                            call_type: "function".to_string(),
+                            function: FunctionCall {
+                                name: String::new(),
+                                arguments: String::new(),
+                            },
+                        });
+                    }
+                    let slot = &mut calls[index];
+                    if let Some(call_id) = call.id {
+                        slot.id = call_id;
+                    }
+                    if let Some(function) = call.function {
+                        if let Some(name) = function.name {
+                            slot.function.name.push_str(&name);
+                        }
+                        if let Some(arguments) = function.arguments {
+                            slot.function.arguments.push_str(&arguments);
+                        }
+                    }
+                }
+            }
+            finish_reason = event.finish_reason.or(finish_reason);
+            usage = event.usage.or(usage);
+        }
+
+        let mut message = ChatMessage::assistant(content);
+        message.tool_calls = (!calls.is_empty()).then_some(calls);
+        Ok(ChatResponse {
+            id,
+            model: request.model.clone(),
+            choices: vec![crate::llm::ChatChoice {
+                index: 0,
+                message,
+                finish_reason,
+            }],
+            usage,
+            thinking: (!thinking.is_empty()).then_some(thinking),
+        })
+    }
+
+    // Returns the call to run and, if denied, why.
+    async fn review(&self, mut call: ToolCall) -> (ToolCall, Option<String>) {
+        let Some(hook) = &self.approval else {
+            return (call, None);
+        };
+        match hook.review(&call).await {
+            Approval::Approve => (call, None),
+            Approval::Edit(arguments) => {
+                call.function.arguments = arguments.to_string();
+                (call, None)
+            }
+            Approval::Deny(reason) => (call, Some(reason)),
+        }
+    }
+
+    async fn execute(&self, name: &str, arguments: Arguments) -> Result<String> {
+        if let Some(tools) = self.tools.as_ref().filter(|t| t.contains(name)) {
+            let params: HashMap<String, serde_json::Value> = arguments.into_iter().collect();
+            let output = tools.execute(name, params).await?;
+            return match output.error {
+                Some(error) if !output.success => Err(crate::Error::ExecutionFailed(error)),
+                _ => Ok(match output.output {
+                    serde_json::Value::String(text) => text,
+                    value => value.to_string(),
+                }),
+            };
+        }
+
+        #[cfg(feature = "mcp-inprocess")]
+        if let Some(host) = self.mcp.as_ref().filter(|h| h.tools().iter().any(|t| t.name == name)) {
+            return host
+                .call_tool(name, Some(arguments))
+                .await
+                .map_err(|e| crate::Error::ExecutionFailed(e.to_string()));
+        }
+
+        Err(crate::Error::ToolNotFound(name.to_string()))
+    }
+
+    fn outcome(&self, messages: Vec<ChatMessage>, stop_reason: StopReason, iterations: u32, usage: AgentUsage) -> AgentOutcome {
+        let content = messages
+            .last()
+            .filter(|m| m.role == MessageRole::Assistant)
+            .and_then(|m| match &m.content {
+                MessageContent::Text(text) => Some(text.clone()),
+                MessageContent::Parts(_) => None,
+            });
+        AgentOutcome {
+            messages,
+            content,
+            stop_reason,
+            iterations,
+            usage,
+        }
+    }
+}
+
+// Models send empty arguments for tools that take none.
+fn parse_arguments(arguments: &str) -> std::result::Result<Arguments, String> {
+    if arguments.trim().is_empty() {
+        return Ok(serde_json::Map::new());
+    }
+    match serde_json::from_str(arguments) {
+        Ok(serde_json::Value::Object(map)) => Ok(map),
+        Ok(_) => Err("arguments must be a JSON object".to_string()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+struct AbortOnDrop(tokio::task::JoinHandle<()>);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
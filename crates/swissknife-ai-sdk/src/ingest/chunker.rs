@@ -0,0 +1,216 @@
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Chunk {
+    pub text: String,
+    pub heading: Option<String>,
+}
+
+impl Chunk {
+    fn plain(text: String) -> Self {
+        Self { text, heading: None }
+    }
+}
+
+pub trait Chunker: Send + Sync {
+    fn chunk(&self, text: &str) -> Vec<Chunk>;
+}
+
+#[derive(Debug, Clone)]
+pub struct FixedSizeChunker {
+    size: usize,
+    overlap: usize,
+}
+
+impl FixedSizeChunker {
+    pub fn new(size: usize, overlap: usize) -> Self {
+        let size = size.max(1);
+        Self {
+            size,
+            overlap: overlap.min(size - 1),
+        }
+    }
+}
+
+impl Default for FixedSizeChunker {
+    fn default() -> Self {
+        Self::new(1000, 200)
+    }
+}
+
+impl Chunker for FixedSizeChunker {
+    fn chunk(&self, text: &str) -> Vec<Chunk> {
+        split_fixed(text, self.size, self.overlap).into_iter().map(Chunk::plain).collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SentenceChunker {
+    max_chars: usize,
+}
+
+impl SentenceChunker {
+    pub fn new(max_chars: usize) -> Self {
+        Self {
+            max_chars: max_chars.max(1),
+        }
+    }
+}
+
+impl Default for SentenceChunker {
+    fn default() -> Self {
+        Self::new(1000)
+    }
+}
+
+impl Chunker for SentenceChunker {
+    fn chunk(&self, text: &str) -> Vec<Chunk> {
+        pack_sentences(text, self.max_chars).into_iter().map(Chunk::plain).collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MarkdownChunker {
+    max_chars: usize,
+}
+
+impl MarkdownChunker {
+    pub fn new(max_chars: usize) -> Self {
+        Self {
+            max_chars: max_chars.max(1),
+        }
+    }
+}
+
+impl Default for MarkdownChunker {
+    fn default() -> Self {
+        Self::new(2000)
+    }
+}
+
+impl Chunker for MarkdownChunker {
+    fn chunk(&self, text: &str) -> Vec<Chunk> {
+        let mut chunks = Vec::new();
+        let mut headings: Vec<(usize, String)> = Vec::new();
+        let mut section = String::new();
+        let mut in_fence = false;
+
+        let mut flush = |section: &mut String, headings: &[(usize, String)]| {
+            let body = std::mem::take(section);
+            if body.trim().is_empty() {
+                return;
+            }
+            let heading = (!headings.is_empty()).then(|| {
+                headings
+                    .iter()
+                    .map(|(_, title)| title.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" > ")
+            });
+            let pieces = if body.chars().count() > self.max_chars {
+                pack_sentences(&body, self.max_chars)
+            } else {
+                vec![body.trim().to_string()]
+            };
+            chunks.extend(pieces.into_iter().map(|text| Chunk {
+                text,
+                heading: heading.clone(),
+            }));
+        };
+
+        for line in text.lines() {
+            if line.trim_start().starts_with("```") {
+                in_fence = !in_fence;
+            }
+            if let Some((level, title)) = parse_heading(line).filter(|_| !in_fence) {
+                flush(&mut section, &headings);
+                headings.retain(|(l, _)| *l < level);
+                headings.push((level, title.to_string()));
+            }
+            section.push_str(line);
+            section.push('\n');
+        }
+        flush(&mut section, &headings);
+
+        chunks
+    }
+}
+
+fn parse_heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    let rest = &line[level..];
+    if !rest.starts_with(' ') {
+        return None;
+    }
+    Some((level, rest.trim()))
+}
+
+fn split_fixed(text: &str, size: usize, overlap: usize) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let step = size.saturating_sub(overlap).max(1);
+    let mut chunks = Vec::new();
+    let mut start = 0;
+
+    while start < chars.len() {
+        let end = (start + size).min(chars.len());
+        let chunk: String = chars[start..end].iter().collect();
+        if !chunk.trim().is_empty() {
+            chunks.push(chunk.trim().to_string());
+        }
+        if end == chars.len() {
+            break;
+        }
+        start += step;
+    }
+    chunks
+}
+
+fn split_sentences(text: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        let boundary = match c {
+            '.' | '!' | '?' => chars.peek().is_none_or(|(_, next)| next.is_whitespace()),
+            '\n' => chars.peek().is_some_and(|(_, next)| *next == '\n'),
+            _ => false,
+        };
+        if boundary {
+            let end = i + c.len_utf8();
+            sentences.push(&text[start..end]);
+            start = end;
+        }
+    }
+    sentences.push(&text[start..]);
+
+    sentences.into_iter().map(str::trim).filter(|s| !s.is_empty()).collect()
+}
+
+fn pack_sentences(text: &str, max_chars: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+
+    for sentence in split_sentences(text) {
+        let length = sentence.chars().count();
+        if length > max_chars {
+            if !current.is_empty() {
+                chunks.push(std::mem::take(&mut current));
+            }
+            chunks.extend(split_fixed(sentence, max_chars, 0));
+            continue;
+        }
+        if !current.is_empty() && current.chars().count() + 1 + length > max_chars {
+            chunks.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(sentence);
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
@@ -0,0 +1,392 @@
+mod chunker;
+
+pub use chunker::{Chunk, Chunker, FixedSizeChunker, MarkdownChunker, SentenceChunker};
+
+use crate::llm::{EmbeddingProvider, EmbeddingRequest};
+use crate::{Error, Result};
+use futures_util::stream::{self, StreamExt};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::Duration;
+use swissknife_vectordb_sdk::{DeleteOptions, QueryOptions, UpsertOptions, Vector, VectorDatabaseProvider};
+
+const DOCUMENT_ID_KEY: &str = "document_id";
+const CHUNK_INDEX_KEY: &str = "chunk_index";
+const CHUNK_COUNT_KEY: &str = "chunk_count";
+const CONTENT_HASH_KEY: &str = "content_hash";
+const TEXT_KEY: &str = "text";
+const HEADING_KEY: &str = "heading";
+const RESERVED_KEYS: [&str; 6] = [DOCUMENT_ID_KEY, CHUNK_INDEX_KEY, CHUNK_COUNT_KEY, CONTENT_HASH_KEY, TEXT_KEY, HEADING_KEY];
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone)]
+pub struct Document {
+    pub id: String,
+    pub text: String,
+    pub metadata: HashMap<String, Value>,
+}
+
+impl Document {
+    pub fn new(id: impl Into<String>, text: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            text: text.into(),
+            metadata: HashMap::new(),
+        }
+    }
+
+    pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    fn content_hash(&self) -> String {
+        let metadata: BTreeMap<&String, &Value> = self.metadata.iter().collect();
+        let mut hasher = Sha256::new();
+        hasher.update(self.text.as_bytes());
+        hasher.update(json!(metadata).to_string().as_bytes());
+        format!("{:x}", hasher.finalize())
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IngestReport {
+    pub ingested: usize,
+    pub skipped: usize,
+    pub chunks: usize,
+    pub removed_chunks: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct RetrievedChunk {
+    pub id: String,
+    pub document_id: String,
+    pub text: String,
+    pub heading: Option<String>,
+    pub score: f32,
+    pub metadata: HashMap<String, Value>,
+}
+
+impl RetrievedChunk {
+    fn from_metadata(id: String, score: f32, mut metadata: HashMap<String, Value>) -> Self {
+        let mut take = |key: &str| match metadata.remove(key) {
+            Some(Value::String(value)) => Some(value),
+            _ => None,
+        };
+        let document_id = take(DOCUMENT_ID_KEY).unwrap_or_default();
+        let text = take(TEXT_KEY).unwrap_or_default();
+        let heading = take(HEADING_KEY);
+        for key in RESERVED_KEYS {
+            metadata.remove(key);
+        }
+        Self {
+            id,
+            document_id,
+            text,
+            heading,
+            score,
+            metadata,
+        }
+    }
+}
+
+struct PendingDocument<'a> {
+    document: &'a Document,
+    hash: String,
+    chunks: Vec<Chunk>,
+    previous_count: usize,
+}
+
+#[derive(Clone)]
+pub struct IngestionPipeline {
+    embedder: Arc<dyn EmbeddingProvider>,
+    store: Arc<dyn VectorDatabaseProvider>,
+    collection: String,
+    model: String,
+    dimensions: Option<u32>,
+    namespace: Option<String>,
+    chunker: Arc<dyn Chunker>,
+    batch_size: usize,
+    concurrency: usize,
+    max_retries: u32,
+    retry_delay: Duration,
+}
+
+impl IngestionPipeline {
+    pub fn new(
+        embedder: Arc<dyn EmbeddingProvider>,
+        store: Arc<dyn VectorDatabaseProvider>,
+        collection: impl Into<String>,
+        model: impl Into<String>,
+    ) -> Self {
+        Self {
+            embedder,
+            store,
+            collection: collection.into(),
+            model: model.into(),
+            dimensions: None,
+            namespace: None,
+            chunker: Arc::new(FixedSizeChunker::default()),
+            batch_size: 64,
+            concurrency: 4,
+            max_retries: 5,
+            retry_delay: Duration::from_millis(500),
+        }
+    }
+
+    pub fn with_chunker(mut self, chunker: impl Chunker + 'static) -> Self {
+        self.chunker = Arc::new(chunker);
+        self
+    }
+
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
+    pub fn with_dimensions(mut self, dimensions: u32) -> Self {
+        self.dimensions = Some(dimensions);
+        self
+    }
+
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    pub fn with_retry(mut self, max_retries: u32, initial_delay: Duration) -> Self {
+        self.max_retries = max_retries;
+        self.retry_delay = initial_delay;
+        self
+    }
+
+    pub async fn ingest(&self, documents: &[Document]) -> Result<IngestReport> {
+        let mut report = IngestReport::default();
+        let previous = self.previous_state(documents).await?;
+
+        let mut pending = Vec::new();
+        for document in documents {
+            let hash = document.content_hash();
+            let (previous_hash, previous_count) = previous.get(&document.id).cloned().unwrap_or_default();
+            if previous_hash == hash {
+                report.skipped += 1;
+                continue;
+            }
+            pending.push(PendingDocument {
+                document,
+                hash,
+                chunks: self.chunker.chunk(&document.text),
+                previous_count,
+            });
+        }
+
+        let texts: Vec<String> = pending
+            .iter()
+            .flat_map(|p| p.chunks.iter().map(|c| c.text.clone()))
+            .collect();
+        let mut embeddings = self.embed_all(texts).await?.into_iter();
+
+        // A document without chunks still gets a placeholder at chunk 0 carrying
+        // its hash, so re-ingesting it is skipped like any unchanged document.
+        let placeholder = match pending.iter().any(|p| p.chunks.is_empty()) {
+            true => Some(self.placeholder_values().await?),
+            false => None,
+        };
+
+        let mut vectors = Vec::new();
+        let mut placeholders = 0;
+        let mut stale = Vec::new();
+        for pending in &pending {
+            let count = pending.chunks.len();
+            if let Some(values) = placeholder.as_ref().filter(|_| count == 0) {
+                vectors.push(Vector {
+                    id: chunk_id(&pending.document.id, 0),
+                    values: values.clone(),
+                    metadata: Some(chunk_metadata(pending, 0, 0, &Chunk::default())),
+                    sparse_values: None,
+                });
+                placeholders += 1;
+            }
+            for (index, chunk) in pending.chunks.iter().enumerate() {
+                let values = embeddings
+                    .next()
+                    .ok_or_else(|| Error::Provider("Embedding provider returned too few embeddings".to_string()))?;
+                vectors.push(Vector {
+                    id: chunk_id(&pending.document.id, index),
+                    values,
+                    metadata: Some(chunk_metadata(pending, index, count, chunk)),
+                    sparse_values: None,
+                });
+            }
+            stale.extend((count.max(1)..pending.previous_count).map(|index| chunk_id(&pending.document.id, index)));
+        }
+
+        if !vectors.is_empty() {
+            let options = UpsertOptions {
+                namespace: self.namespace.clone(),
+            };
+            self.store
+                .upsert(&self.collection, &vectors, &options)
+                .await
+                .map_err(store_error)?;
+        }
+        if !stale.is_empty() {
+            let ids: Vec<&str> = stale.iter().map(String::as_str).collect();
+            let options = DeleteOptions {
+                namespace: self.namespace.clone(),
+                ..Default::default()
+            };
+            self.store
+                .delete(&self.collection, &ids, &options)
+                .await
+                .map_err(store_error)?;
+        }
+
+        report.ingested = pending.len();
+        report.chunks = vectors.len() - placeholders;
+        report.removed_chunks = stale.len();
+        Ok(report)
+    }
+
+    pub async fn retrieve(&self, query: &str, top_k: u32) -> Result<Vec<RetrievedChunk>> {
+        let vector = self
+            .embed_batch(vec![query.to_string()])
+            .await?
+            .pop()
+            .ok_or_else(|| Error::Provider("Embedding provider returned no embedding".to_string()))?;
+
+        let options = QueryOptions {
+            top_k,
+            include_metadata: true,
+            namespace: self.namespace.clone(),
+            ..Default::default()
+        };
+        let results = self
+            .store
+            .query(&self.collection, &vector, &options)
+            .await
+            .map_err(store_error)?;
+
+        Ok(results
+            .into_iter()
+            .map(|r| (r.id, r.score, r.metadata.unwrap_or_default()))
+            .filter(|(_, _, metadata)| metadata.get(CHUNK_COUNT_KEY).and_then(Value::as_u64) != Some(0))
+            .map(|(id, score, metadata)| RetrievedChunk::from_metadata(id, score, metadata))
+            .collect())
+    }
+
+    // Any non-zero vector of the right size will do; some stores reject all-zero vectors.
+    async fn placeholder_values(&self) -> Result<Vec<f32>> {
+        let dimension = match self.dimensions {
+            Some(dimension) => dimension,
+            None => {
+                self.store
+                    .describe_collection(&self.collection)
+                    .await
+                    .map_err(store_error)?
+                    .dimension
+            }
+        };
+        let mut values = vec![0.0; dimension.max(1) as usize];
+        values[0] = 1.0;
+        Ok(values)
+    }
+
+    async fn previous_state(&self, documents: &[Document]) -> Result<HashMap<String, (String, usize)>> {
+        if documents.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let ids: Vec<String> = documents.iter().map(|d| chunk_id(&d.id, 0)).collect();
+        let ids: Vec<&str> = ids.iter().map(String::as_str).collect();
+        let existing = self
+            .store
+            .fetch(&self.collection, &ids, self.namespace.as_deref())
+            .await
+            .map_err(store_error)?;
+
+        Ok(existing
+            .into_iter()
+            .filter_map(|vector| {
+                let metadata = vector.metadata?;
+                let document_id = metadata.get(DOCUMENT_ID_KEY)?.as_str()?.to_string();
+                let hash = metadata.get(CONTENT_HASH_KEY)?.as_str()?.to_string();
+                let count = metadata.get(CHUNK_COUNT_KEY).and_then(|c| c.as_u64()).unwrap_or(1) as usize;
+                Some((document_id, (hash, count)))
+            })
+            .collect())
+    }
+
+    async fn embed_all(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>> {
+        let batches: Vec<Vec<String>> = texts.chunks(self.batch_size).map(|b| b.to_vec()).collect();
+        let results: Vec<Result<Vec<Vec<f32>>>> = stream::iter(batches)
+            .map(|batch| self.embed_batch(batch))
+            .buffered(self.concurrency)
+            .collect()
+            .await;
+
+        let mut embeddings = Vec::with_capacity(texts.len());
+        for result in results {
+            embeddings.extend(result?);
+        }
+        Ok(embeddings)
+    }
+
+    async fn embed_batch(&self, batch: Vec<String>) -> Result<Vec<Vec<f32>>> {
+        let expected = batch.len();
+        let mut request = EmbeddingRequest::new(self.model.clone(), batch);
+        request.dimensions = self.dimensions;
+
+        let mut attempt = 0;
+        let response = loop {
+            match self.embedder.embed(&request).await {
+                Err(Error::RateLimited) if attempt < self.max_retries => {
+                    let backoff = self.retry_delay.saturating_mul(2u32.saturating_pow(attempt));
+                    tokio::time::sleep(backoff.min(MAX_RETRY_DELAY)).await;
+                    attempt += 1;
+                }
+                result => break result?,
+            }
+        };
+
+        let mut data = response.data;
+        if data.len() != expected {
+            return Err(Error::Provider(format!(
+                "Expected {} embeddings, got {}",
+                expected,
+                data.len()
+            )));
+        }
+        data.sort_by_key(|d| d.index);
+        Ok(data.into_iter().map(|d| d.embedding).collect())
+    }
+}
+
+fn chunk_id(document_id: &str, index: usize) -> String {
+    format!("{}#{}", document_id, index)
+}
+
+fn chunk_metadata(pending: &PendingDocument<'_>, index: usize, count: usize, chunk: &Chunk) -> HashMap<String, Value> {
+    let mut metadata = pending.document.metadata.clone();
+    metadata.insert(DOCUMENT_ID_KEY.to_string(), json!(pending.document.id));
+    metadata.insert(CHUNK_INDEX_KEY.to_string(), json!(index));
+    metadata.insert(TEXT_KEY.to_string(), json!(chunk.text));
+    if let Some(heading) = &chunk.heading {
+        metadata.insert(HEADING_KEY.to_string(), json!(heading));
+    }
+    if index == 0 {
+        metadata.insert(CONTENT_HASH_KEY.to_string(), json!(pending.hash));
+        metadata.insert(CHUNK_COUNT_KEY.to_string(), json!(count));
+    }
+    metadata
+}
+
+fn store_error(error: swissknife_vectordb_sdk::Error) -> Error {
+    Error::Provider(error.to_string())
+}
@@ -9,9 +9,15 @@ pub mod llm;
 #[cfg(feature = "mcp")]
 pub mod mcp;
 
+#[cfg(feature = "agent")]
+pub mod agent;
+
 #[cfg(feature = "duckdb")]
 pub mod memory;
 
+#[cfg(feature = "ingest")]
+pub mod ingest;
+
 #[cfg(feature = "duckdb")]
 pub mod claude_history;
 
@@ -2,11 +2,12 @@ use crate::{Error, Result};
 use async_trait::async_trait;
 use futures_util::StreamExt;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 use super::{
     ChatMessage, ChatProvider, ChatRequest, ChatResponse, ChatChoice, ChatStreamEvent,
-    ChatStreamResponse, MessageContent, MessageRole, ProviderConfig, StreamDelta, Usage,
-    VisionProvider, VisionRequest, VisionResponse, ContentPart, ThinkingConfig,
+    ChatStreamResponse, FunctionCallDelta, MessageContent, MessageRole, ProviderConfig, StreamDelta,
+    ToolCallDelta, Usage, VisionProvider, VisionRequest, VisionResponse, ContentPart, ThinkingConfig,
 };
 
 const API_BASE: &str = "https://api.anthropic.com/v1";
@@ -314,84 +315,166 @@ impl ChatProvider for AnthropicClient {
             });
         }
 
+        let mut parser = AnthropicStreamParser::default();
         let stream = response.bytes_stream()
-            .filter_map(|result| async move {
-                match result {
-                    Ok(bytes) => {
-                        let text = String::from_utf8_lossy(&bytes);
-                        parse_anthropic_sse(&text)
-                    }
-                    Err(e) => Some(Err(Error::Http(e))),
-                }
-            });
+            .map(move |result| match result {
+                Ok(bytes) => parser.feed(&bytes),
+                Err(e) => vec![Err(Error::Http(e))],
+            })
+            .flat_map(futures_util::stream::iter);
 
         Ok(Box::pin(stream))
     }
 }
 
-fn parse_anthropic_sse(text: &str) -> Option<Result<ChatStreamEvent>> {
-    for line in text.lines() {
-        if let Some(data) = line.strip_prefix("data: ") {
-            if let Ok(event) = serde_json::from_str::<AnthropicStreamEvent>(data) {
-                match event.event_type.as_str() {
-                    "content_block_delta" => {
-                        if let Some(delta) = event.delta {
-                            return Some(Ok(ChatStreamEvent {
-                                id: None,
-                                delta: Some(StreamDelta {
-                                    role: None,
-                                    content: delta.text,
-                                    tool_calls: None,
-                                }),
-                                finish_reason: None,
-                                usage: None,
-                            }));
-                        }
-                    }
-                    "message_stop" => {
-                        return Some(Ok(ChatStreamEvent {
-                            id: None,
-                            delta: None,
-                            finish_reason: Some("stop".to_string()),
-                            usage: None,
-                        }));
-                    }
-                    "message_delta" => {
-                        return Some(Ok(ChatStreamEvent {
-                            id: None,
-                            delta: None,
-                            finish_reason: event.delta.and_then(|d| d.stop_reason),
-                            usage: event.usage.map(|u| Usage {
-                                prompt_tokens: 0,
-                                completion_tokens: u.output_tokens,
-                                total_tokens: u.output_tokens,
-                            }),
-                        }));
+// Events can be split across byte chunks, and a tool call's input arrives as
+// partial JSON over several deltas, so both are buffered until complete.
+#[derive(Default)]
+struct AnthropicStreamParser {
+    buffer: Vec<u8>,
+    input_tokens: u32,
+    // Content block index -> the tool call being assembled in it.
+    tool_uses: HashMap<u32, PendingToolUse>,
+    tool_calls: u32,
+}
+
+struct PendingToolUse {
+    // Position among this message's tool calls, not its content block index.
+    index: u32,
+    id: String,
+    name: String,
+    input: String,
+}
+
+impl AnthropicStreamParser {
+    fn feed(&mut self, bytes: &[u8]) -> Vec<Result<ChatStreamEvent>> {
+        self.buffer.extend_from_slice(bytes);
+        let mut events = Vec::new();
+        while let Some(end) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line);
+            if let Some(data) = line.trim_end().strip_prefix("data:") {
+                events.extend(self.event(data.trim_start()).map(Ok));
+            }
+        }
+        events
+    }
+
+    fn event(&mut self, data: &str) -> Option<ChatStreamEvent> {
+        let event = serde_json::from_str::<AnthropicStreamEvent>(data).ok()?;
+        match event.event_type.as_str() {
+            "message_start" => {
+                let message = event.message?;
+                self.input_tokens = message.usage.map_or(0, |u| u.input_tokens);
+                Some(stream_event(Some(message.id), None, None, None))
+            }
+            "content_block_start" => {
+                let block = event.content_block.filter(|b| b.block_type == "tool_use")?;
+                self.tool_uses.insert(event.index?, PendingToolUse {
+                    index: self.tool_calls,
+                    id: block.id.unwrap_or_default(),
+                    name: block.name.unwrap_or_default(),
+                    input: String::new(),
+                });
+                self.tool_calls += 1;
+                None
+            }
+            "content_block_delta" => {
+                let delta = event.delta?;
+                if let Some(partial) = delta.partial_json {
+                    if let Some(tool_use) = event.index.and_then(|i| self.tool_uses.get_mut(&i)) {
+                        tool_use.input.push_str(&partial);
                     }
-                    _ => {}
+                    return None;
                 }
+                Some(stream_event(None, Some(StreamDelta {
+                    role: None,
+                    content: delta.text,
+                    tool_calls: None,
+                    thinking: delta.thinking,
+                }), None, None))
+            }
+            "content_block_stop" => {
+                let tool_use = self.tool_uses.remove(&event.index?)?;
+                // A tool without parameters streams no input at all.
+                let arguments = if tool_use.input.is_empty() { "{}".to_string() } else { tool_use.input };
+                Some(stream_event(None, Some(StreamDelta {
+                    role: None,
+                    content: None,
+                    tool_calls: Some(vec![ToolCallDelta {
+                        index: tool_use.index,
+                        id: Some(tool_use.id),
+                        function: Some(FunctionCallDelta {
+                            name: Some(tool_use.name),
+                            arguments: Some(arguments),
+                        }),
+                    }]),
+                    thinking: None,
+                }), None, None))
+            }
+            "message_delta" => {
+                let output_tokens = event.usage.map_or(0, |u| u.output_tokens);
+                Some(stream_event(None, None, event.delta.and_then(|d| d.stop_reason), Some(Usage {
+                    prompt_tokens: self.input_tokens,
+                    completion_tokens: output_tokens,
+                    total_tokens: self.input_tokens + output_tokens,
+                })))
             }
+            "message_stop" => Some(stream_event(None, None, Some("stop".to_string()), None)),
+            _ => None,
         }
     }
-    None
+}
+
+fn stream_event(
+    id: Option<String>,
+    delta: Option<StreamDelta>,
+    finish_reason: Option<String>,
+    usage: Option<Usage>,
+) -> ChatStreamEvent {
+    ChatStreamEvent { id, delta, finish_reason, usage }
 }
 
 #[derive(Debug, Deserialize)]
 struct AnthropicStreamEvent {
     #[serde(rename = "type")]
     event_type: String,
+    index: Option<u32>,
+    message: Option<AnthropicStreamMessage>,
+    content_block: Option<AnthropicStreamBlock>,
     delta: Option<AnthropicStreamDelta>,
     usage: Option<AnthropicStreamUsage>,
 }
 
+#[derive(Debug, Deserialize)]
+struct AnthropicStreamMessage {
+    id: String,
+    usage: Option<AnthropicStreamUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicStreamBlock {
+    #[serde(rename = "type")]
+    block_type: String,
+    id: Option<String>,
+    name: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 struct AnthropicStreamDelta {
     text: Option<String>,
+    // Set on `thinking_delta` blocks.
+    thinking: Option<String>,
+    // Set on `input_json_delta` blocks.
+    partial_json: Option<String>,
     stop_reason: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 struct AnthropicStreamUsage {
+    #[serde(default)]
+    input_tokens: u32,
+    #[serde(default)]
     output_tokens: u32,
 }
 
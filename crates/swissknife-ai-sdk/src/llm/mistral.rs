@@ -102,6 +102,7 @@ fn parse_sse_events(text: &str) -> Option<Result<ChatStreamEvent>> {
                         role: c.delta.role,
                         content: c.delta.content.clone(),
                         tool_calls: None,
+                        thinking: None,
                     });
                     return Some(Ok(ChatStreamEvent {
                         id: Some(chunk.id),
@@ -116,6 +116,7 @@ fn parse_sse_events(text: &str) -> Option<Result<ChatStreamEvent>> {
                         role: c.delta.role,
                         content: c.delta.content.clone(),
                         tool_calls: c.delta.tool_calls.clone(),
+                        thinking: None,
                     });
                     return Some(Ok(ChatStreamEvent {
                         id: Some(chunk.id),
@@ -1,4 +1,4 @@
-use clap::{Parser, ValueEnum};
+use clap::{ArgAction, Parser, ValueEnum};
 
 #[derive(Debug, Clone, ValueEnum)]
 pub enum ServerMode {
@@ -38,13 +38,14 @@ pub enum ToolCategory {
 #[derive(Parser, Debug)]
 #[command(name = "swissknife-mcp")]
 #[command(author = "Swissknife")]
-#[command(version = "0.1.0")]
+#[command(version = "0.1.0", disable_version_flag = true)]
 #[command(about = "MCP server for Swissknife AI tools", long_about = None)]
 pub struct Cli {
     #[arg(short, long, value_enum, default_value = "http")]
     pub mode: ServerMode,
 
-    #[arg(short, long, default_value = "127.0.0.1")]
+    // -h is taken by --help.
+    #[arg(short = 'H', long, default_value = "127.0.0.1")]
     pub host: String,
 
     #[arg(short, long, default_value = "3000")]
@@ -56,14 +57,47 @@ pub struct Cli {
     #[arg(long, default_value = "swissknife-mcp")]
     pub name: String,
 
-    #[arg(long, default_value = "0.1.0")]
+    #[arg(long = "server-version", id = "server_version", alias = "version", default_value = "0.1.0")]
     pub version: String,
 
     #[arg(long)]
     pub cors_origins: Vec<String>,
 
+    /// Bearer token accepted by the HTTP server. Use TOKEN:cat1,cat2 to limit it to those tool categories.
+    #[arg(long = "auth-token", value_name = "TOKEN[:CATEGORIES]")]
+    pub auth_tokens: Vec<String>,
+
+    /// Validate bearer tokens as JWTs signed by a key from this JWKS URL.
+    #[cfg(feature = "mcp-auth")]
+    #[arg(long, requires = "audience", conflicts_with = "auth_tokens")]
+    pub jwks_uri: Option<String>,
+
+    /// Audience (`aud`) required in JWTs.
+    #[cfg(feature = "mcp-auth")]
+    #[arg(long)]
+    pub audience: Option<String>,
+
+    /// Issuer (`iss`) required in JWTs.
+    #[cfg(feature = "mcp-auth")]
+    #[arg(long)]
+    pub issuer: Option<String>,
+
+    /// Authorization server advertised in the protected resource metadata.
+    #[cfg(feature = "mcp-auth")]
+    #[arg(long, requires = "resource_url")]
+    pub authorization_server: Option<String>,
+
+    /// Public URL of this server, advertised as the protected resource.
+    #[cfg(feature = "mcp-auth")]
+    #[arg(long)]
+    pub resource_url: Option<String>,
+
     #[arg(short, long)]
     pub verbose: bool,
+
+    // --version is an alias of --server-version for existing scripts, so only -V prints the binary's version.
+    #[arg(short = 'V', action = ArgAction::Version, help = "Print version")]
+    print_version: Option<bool>,
 }
 
 impl Cli {
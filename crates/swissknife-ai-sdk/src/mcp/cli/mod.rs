@@ -2,4 +2,6 @@ mod args;
 mod runner;
 
 pub use args::{Cli, ServerMode, ToolCategory};
-pub use runner::run;
+pub use runner::{run, service};
+#[cfg(feature = "http")]
+pub use runner::http_server;
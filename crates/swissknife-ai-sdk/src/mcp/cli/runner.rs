@@ -1,8 +1,12 @@
-use crate::mcp::cli::args::{Cli, ServerMode, ToolCategory};
+use crate::mcp::cli::args::{Cli, ServerMode};
+use crate::mcp::{McpRouter, RouterService};
 use std::net::SocketAddr;
 
+#[cfg(feature = "search")]
+use crate::mcp::{cli::args::ToolCategory, providers::WebSearchProvider, ToolProvider};
+
 #[cfg(feature = "http")]
-use crate::mcp::server::{McpHttpServer, McpHttpServerConfig};
+use crate::mcp::server::{McpHttpServer, McpHttpServerConfig, StaticTokenAuth, ToolAccess};
 
 pub async fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     if cli.verbose {
@@ -19,304 +23,115 @@ pub async fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error + Send + Sync
 
 #[cfg(feature = "http")]
 async fn run_http(cli: Cli) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let config = McpHttpServerConfig {
-        name: cli.name.clone(),
-        version: cli.version.clone(),
-        cors_origins: if cli.cors_origins.is_empty() {
-            vec!["*".to_string()]
-        } else {
-            cli.cors_origins.clone()
-        },
-    };
-
-    let server = McpHttpServer::new(config);
-
-    register_tools(&server, &cli).await;
+    let (server, authenticated) = build_http_server(&cli);
 
     let addr: SocketAddr = cli.addr().parse()?;
+    if !authenticated && !addr.ip().is_loopback() {
+        eprintln!("warning: serving MCP on {} without authentication", addr);
+    }
     server.serve(addr).await
 }
 
-#[cfg(not(feature = "http"))]
-async fn run_http(_cli: Cli) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    Err("HTTP server requires the 'http' feature".into())
-}
-
-async fn run_stdio(cli: Cli) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    use crate::mcp::server::serve_stdio;
-
-    let service = StdioService::new(&cli);
-    serve_stdio(service).await.map_err(|e| e.into())
+// The server `serve --http` runs, before it is bound to an address.
+#[cfg(feature = "http")]
+pub fn http_server(cli: &Cli) -> McpHttpServer<RouterService> {
+    build_http_server(cli).0
 }
 
 #[cfg(feature = "http")]
-async fn register_tools(server: &McpHttpServer, cli: &Cli) {
-    if cli.has_category(ToolCategory::Search) {
-        register_search_tools(server).await;
-    }
-    if cli.has_category(ToolCategory::Devtools) {
-        register_devtools_tools(server).await;
-    }
-    if cli.has_category(ToolCategory::Communication) {
-        register_communication_tools(server).await;
-    }
-    if cli.has_category(ToolCategory::Database) {
-        register_database_tools(server).await;
+fn build_http_server(cli: &Cli) -> (McpHttpServer<RouterService>, bool) {
+    let config = McpHttpServerConfig {
+        cors_origins: cli.cors_origins.clone(),
+        ..Default::default()
+    };
+
+    let (router, categories) = tools(cli);
+    let service = RouterService::new(router);
+    let mut server = McpHttpServer::new(config, move || service.clone());
+    for (category, names) in categories {
+        server = server.with_tool_category(category, names);
     }
-    if cli.has_category(ToolCategory::Llm) {
-        register_llm_tools(server).await;
+    let mut authenticated = false;
+
+    #[cfg(feature = "mcp-auth")]
+    {
+        use crate::mcp::server::{JwtAuth, ProtectedResourceMetadata};
+
+        if let (Some(jwks_uri), Some(audience)) = (&cli.jwks_uri, &cli.audience) {
+            let mut auth = JwtAuth::new(jwks_uri.clone(), audience.clone());
+            if let Some(issuer) = &cli.issuer {
+                auth = auth.with_issuer(issuer.clone());
+            }
+            server = server.with_auth(auth);
+            authenticated = true;
+        }
+        if let (Some(resource), Some(issuer)) = (&cli.resource_url, &cli.authorization_server) {
+            server = server.with_resource_metadata(
+                ProtectedResourceMetadata::new(resource.clone(), issuer.clone())
+                    .with_resource_name(cli.name.clone()),
+            );
+        }
     }
 
-    if cli.verbose {
-        println!("Tools registered successfully");
+    // Clap rejects --auth-token alongside --jwks-uri, so only one applies.
+    if !cli.auth_tokens.is_empty() {
+        server = server.with_auth(static_token_auth(&cli.auth_tokens));
+        authenticated = true;
     }
-}
-
-#[cfg(feature = "http")]
-async fn register_search_tools(server: &McpHttpServer) {
-    server
-        .register_tool(
-            "web_search",
-            "Search the web using configured search provider",
-            serde_json::json!({
-                "type": "object",
-                "properties": {
-                    "query": {
-                        "type": "string",
-                        "description": "Search query"
-                    },
-                    "max_results": {
-                        "type": "integer",
-                        "description": "Maximum number of results",
-                        "default": 10
-                    }
-                },
-                "required": ["query"]
-            }),
-            |args| async move {
-                let query = args
-                    .get("query")
-                    .and_then(|v| v.as_str())
-                    .ok_or("Missing query parameter")?;
 
-                Ok(serde_json::json!({
-                    "query": query,
-                    "results": [],
-                    "message": "Search provider not configured"
-                }))
-            },
-        )
-        .await;
+    (server, authenticated)
 }
 
 #[cfg(feature = "http")]
-async fn register_devtools_tools(server: &McpHttpServer) {
-    server
-        .register_tool(
-            "github_get_repo",
-            "Get information about a GitHub repository",
-            serde_json::json!({
-                "type": "object",
-                "properties": {
-                    "owner": {
-                        "type": "string",
-                        "description": "Repository owner"
-                    },
-                    "repo": {
-                        "type": "string",
-                        "description": "Repository name"
-                    }
-                },
-                "required": ["owner", "repo"]
-            }),
-            |args| async move {
-                let owner = args
-                    .get("owner")
-                    .and_then(|v| v.as_str())
-                    .ok_or("Missing owner parameter")?;
-                let repo = args
-                    .get("repo")
-                    .and_then(|v| v.as_str())
-                    .ok_or("Missing repo parameter")?;
-
-                Ok(serde_json::json!({
-                    "owner": owner,
-                    "repo": repo,
-                    "message": "GitHub client not configured"
-                }))
-            },
-        )
-        .await;
+fn static_token_auth(specs: &[String]) -> StaticTokenAuth {
+    specs
+        .iter()
+        .enumerate()
+        .fold(StaticTokenAuth::new(), |auth, (i, spec)| {
+            // Bearer tokens never contain ':', so it can separate the categories.
+            let subject = format!("token-{}", i + 1);
+            match spec.split_once(':') {
+                Some((token, categories)) => auth.with_access(
+                    token,
+                    subject,
+                    ToolAccess::categories(categories.split(',').map(str::trim).filter(|c| !c.is_empty())),
+                ),
+                None => auth.with_token(spec.as_str(), subject),
+            }
+        })
 }
 
-#[cfg(feature = "http")]
-async fn register_communication_tools(server: &McpHttpServer) {
-    server
-        .register_tool(
-            "send_email",
-            "Send an email using configured email provider",
-            serde_json::json!({
-                "type": "object",
-                "properties": {
-                    "to": {
-                        "type": "string",
-                        "description": "Recipient email address"
-                    },
-                    "subject": {
-                        "type": "string",
-                        "description": "Email subject"
-                    },
-                    "body": {
-                        "type": "string",
-                        "description": "Email body"
-                    }
-                },
-                "required": ["to", "subject", "body"]
-            }),
-            |args| async move {
-                let to = args
-                    .get("to")
-                    .and_then(|v| v.as_str())
-                    .ok_or("Missing 'to' parameter")?;
-
-                Ok(serde_json::json!({
-                    "to": to,
-                    "message": "Email provider not configured"
-                }))
-            },
-        )
-        .await;
-
-    server
-        .register_tool(
-            "send_slack_message",
-            "Send a message to Slack",
-            serde_json::json!({
-                "type": "object",
-                "properties": {
-                    "channel": {
-                        "type": "string",
-                        "description": "Slack channel ID or name"
-                    },
-                    "message": {
-                        "type": "string",
-                        "description": "Message content"
-                    }
-                },
-                "required": ["channel", "message"]
-            }),
-            |args| async move {
-                let channel = args
-                    .get("channel")
-                    .and_then(|v| v.as_str())
-                    .ok_or("Missing channel parameter")?;
-
-                Ok(serde_json::json!({
-                    "channel": channel,
-                    "message": "Slack client not configured"
-                }))
-            },
-        )
-        .await;
+#[cfg(not(feature = "http"))]
+async fn run_http(_cli: Cli) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    Err("HTTP server requires the 'http' feature".into())
 }
 
-#[cfg(feature = "http")]
-async fn register_database_tools(server: &McpHttpServer) {
-    server
-        .register_tool(
-            "query_database",
-            "Execute a database query",
-            serde_json::json!({
-                "type": "object",
-                "properties": {
-                    "query": {
-                        "type": "string",
-                        "description": "SQL query to execute"
-                    },
-                    "database": {
-                        "type": "string",
-                        "description": "Database identifier"
-                    }
-                },
-                "required": ["query"]
-            }),
-            |args| async move {
-                let query = args
-                    .get("query")
-                    .and_then(|v| v.as_str())
-                    .ok_or("Missing query parameter")?;
+async fn run_stdio(cli: Cli) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    use crate::mcp::server::serve_stdio;
 
-                Ok(serde_json::json!({
-                    "query": query,
-                    "message": "Database client not configured"
-                }))
-            },
-        )
-        .await;
+    serve_stdio(service(&cli)).await
 }
 
-#[cfg(feature = "http")]
-async fn register_llm_tools(server: &McpHttpServer) {
-    server
-        .register_tool(
-            "llm_complete",
-            "Generate text completion using an LLM",
-            serde_json::json!({
-                "type": "object",
-                "properties": {
-                    "prompt": {
-                        "type": "string",
-                        "description": "The prompt to complete"
-                    },
-                    "model": {
-                        "type": "string",
-                        "description": "Model to use (e.g., gpt-4, claude-3)"
-                    },
-                    "max_tokens": {
-                        "type": "integer",
-                        "description": "Maximum tokens to generate",
-                        "default": 1000
-                    }
-                },
-                "required": ["prompt"]
-            }),
-            |args| async move {
-                let prompt = args
-                    .get("prompt")
-                    .and_then(|v| v.as_str())
-                    .ok_or("Missing prompt parameter")?;
-
-                Ok(serde_json::json!({
-                    "prompt": prompt,
-                    "message": "LLM provider not configured"
-                }))
-            },
-        )
-        .await;
+// Shared by the stdio and HTTP transports so both expose the same tools.
+pub fn service(cli: &Cli) -> RouterService {
+    RouterService::new(tools(cli).0)
 }
 
-use rmcp::{ServerHandler, model::ServerInfo};
+// Tool names by category, for the HTTP server's per-token allow-lists.
+type Categories = Vec<(&'static str, Vec<String>)>;
 
-pub struct StdioService {
-    name: String,
-    version: String,
-}
+fn tools(cli: &Cli) -> (McpRouter, Categories) {
+    #[allow(unused_mut)]
+    let mut router = McpRouter::new(cli.name.clone(), cli.version.clone());
+    #[allow(unused_mut)]
+    let mut categories = Categories::new();
 
-impl StdioService {
-    pub fn new(cli: &Cli) -> Self {
-        Self {
-            name: cli.name.clone(),
-            version: cli.version.clone(),
-        }
+    #[cfg(feature = "search")]
+    if cli.has_category(ToolCategory::Search) {
+        let provider = WebSearchProvider::new();
+        categories.push(("search", provider.tools().into_iter().map(|t| t.name).collect()));
+        router.add_tool_provider(provider);
     }
-}
 
-impl ServerHandler for StdioService {
-    fn get_info(&self) -> ServerInfo {
-        ServerInfo {
-            name: self.name.clone(),
-            version: self.version.clone(),
-            ..Default::default()
-        }
-    }
+    (router, categories)
 }
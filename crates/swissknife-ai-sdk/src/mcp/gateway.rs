@@ -0,0 +1,576 @@
+use futures_util::future::join_all;
+use rmcp::{
+    model::{
+        CallToolRequest, CallToolRequestParam, CallToolResult, ClientRequest, Implementation,
+        ListToolsResult, Meta, NumberOrString, PaginatedRequestParam, ProgressNotificationParam,
+        ProgressToken, ServerCapabilities, ServerInfo, ServerResult, Tool, ToolsCapability,
+    },
+    service::{
+        NotificationContext, Peer, PeerRequestOptions, RequestContext, RunningService, ServiceError,
+    },
+    transport::{streamable_http_client::StreamableHttpClientTransportConfig, StreamableHttpClientTransport},
+    ClientHandler, ErrorData as McpError, RoleClient, RoleServer, ServerHandler, ServiceExt,
+};
+use std::collections::HashMap;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
+use tokio::io::DuplexStream;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+const DEFAULT_RECONNECT_ATTEMPTS: u32 = 3;
+const RECONNECT_BACKOFF: Duration = Duration::from_millis(250);
+
+#[derive(Debug, thiserror::Error)]
+pub enum GatewayError {
+    #[error("Failed to connect to upstream {upstream}: {message}")]
+    Connect { upstream: String, message: String },
+
+    #[error("Upstream {upstream} timed out after {timeout:?}")]
+    Timeout { upstream: String, timeout: Duration },
+}
+
+type DuplexConnector = Arc<dyn Fn() -> DuplexStream + Send + Sync>;
+
+#[derive(Clone)]
+enum UpstreamTransport {
+    Command {
+        program: String,
+        args: Vec<String>,
+        env: Vec<(String, String)>,
+    },
+    Http {
+        url: String,
+        bearer_token: Option<String>,
+    },
+    Duplex(DuplexConnector),
+}
+
+#[derive(Clone)]
+pub struct Upstream {
+    name: String,
+    transport: UpstreamTransport,
+    timeout: Duration,
+    reconnect_attempts: u32,
+}
+
+impl Upstream {
+    fn new(name: impl Into<String>, transport: UpstreamTransport) -> Self {
+        Self {
+            name: name.into(),
+            transport,
+            timeout: DEFAULT_TIMEOUT,
+            reconnect_attempts: DEFAULT_RECONNECT_ATTEMPTS,
+        }
+    }
+
+    // A child process speaking MCP over its stdin/stdout.
+    pub fn command(name: impl Into<String>, program: impl Into<String>) -> Self {
+        Self::new(
+            name,
+            UpstreamTransport::Command {
+                program: program.into(),
+                args: Vec::new(),
+                env: Vec::new(),
+            },
+        )
+    }
+
+    // A server behind the Streamable HTTP transport.
+    pub fn http(name: impl Into<String>, url: impl Into<String>) -> Self {
+        Self::new(
+            name,
+            UpstreamTransport::Http {
+                url: url.into(),
+                bearer_token: None,
+            },
+        )
+    }
+
+    // `connect` is called for every (re)connection and must return the client
+    // end of a stream whose other end is being served.
+    pub fn duplex<F>(name: impl Into<String>, connect: F) -> Self
+    where
+        F: Fn() -> DuplexStream + Send + Sync + 'static,
+    {
+        Self::new(name, UpstreamTransport::Duplex(Arc::new(connect)))
+    }
+
+    // An in-process server; each connection gets a fresh handler from `factory`.
+    pub fn in_process<S, F>(name: impl Into<String>, factory: F) -> Self
+    where
+        S: ServerHandler + Send + 'static,
+        F: Fn() -> S + Send + Sync + 'static,
+    {
+        Self::duplex(name, move || {
+            let (server_stream, client_stream) = tokio::io::duplex(4096);
+            let server = factory();
+            tokio::spawn(async move {
+                if let Ok(running) = server.serve(server_stream).await {
+                    let _ = running.waiting().await;
+                }
+            });
+            client_stream
+        })
+    }
+
+    pub fn with_args<I, A>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = A>,
+        A: Into<String>,
+    {
+        if let UpstreamTransport::Command { args: existing, .. } = &mut self.transport {
+            existing.extend(args.into_iter().map(Into::into));
+        }
+        self
+    }
+
+    pub fn with_env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        if let UpstreamTransport::Command { env, .. } = &mut self.transport {
+            env.push((key.into(), value.into()));
+        }
+        self
+    }
+
+    pub fn with_bearer_token(mut self, token: impl Into<String>) -> Self {
+        if let UpstreamTransport::Http { bearer_token, .. } = &mut self.transport {
+            *bearer_token = Some(token.into());
+        }
+        self
+    }
+
+    // Bounds both connecting and each forwarded call.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn with_reconnect_attempts(mut self, attempts: u32) -> Self {
+        self.reconnect_attempts = attempts;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+// State shared by the gateway and the client handlers of its upstreams.
+#[derive(Default)]
+struct Shared {
+    downstream: Mutex<Vec<Peer<RoleServer>>>,
+    // Upstream progress token -> the downstream client and the token it used.
+    progress: Mutex<HashMap<ProgressToken, (Peer<RoleServer>, ProgressToken)>>,
+    next_token: AtomicU64,
+}
+
+impl Shared {
+    fn route_progress(&self, peer: Peer<RoleServer>, token: ProgressToken) -> ProgressToken {
+        let id = self.next_token.fetch_add(1, Ordering::Relaxed);
+        let upstream_token = ProgressToken(NumberOrString::String(format!("gateway-{}", id).into()));
+        self.progress
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(upstream_token.clone(), (peer, token));
+        upstream_token
+    }
+
+    fn unroute_progress(&self, token: &ProgressToken) {
+        self.progress.lock().unwrap_or_else(|e| e.into_inner()).remove(token);
+    }
+
+    async fn notify_tools_changed(&self) {
+        let peers = {
+            let mut peers = self.downstream.lock().unwrap_or_else(|e| e.into_inner());
+            peers.retain(|peer| !peer.is_transport_closed());
+            peers.clone()
+        };
+        for peer in peers {
+            let _ = peer.notify_tool_list_changed().await;
+        }
+    }
+}
+
+#[derive(Clone)]
+struct UpstreamClient {
+    upstream: Weak<UpstreamState>,
+    shared: Arc<Shared>,
+}
+
+impl ClientHandler for UpstreamClient {
+    async fn on_tool_list_changed(&self, context: NotificationContext<RoleClient>) {
+        let Some(upstream) = self.upstream.upgrade() else {
+            return;
+        };
+        if let Ok(tools) = context.peer.list_all_tools().await {
+            if upstream.set_tools(tools) {
+                self.shared.notify_tools_changed().await;
+            }
+        }
+    }
+
+    async fn on_progress(&self, params: ProgressNotificationParam, _context: NotificationContext<RoleClient>) {
+        let route = self
+            .shared
+            .progress
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&params.progress_token)
+            .cloned();
+        if let Some((peer, progress_token)) = route {
+            let _ = peer
+                .notify_progress(ProgressNotificationParam { progress_token, ..params })
+                .await;
+        }
+    }
+}
+
+struct Connection {
+    service: RunningService<RoleClient, UpstreamClient>,
+    // Declared after `service` so the child is killed once the session is gone.
+    _child: Option<tokio::process::Child>,
+}
+
+struct UpstreamState {
+    config: Upstream,
+    connection: tokio::sync::Mutex<Option<Connection>>,
+    // The last tool list seen, served while the upstream reconnects.
+    tools: Mutex<Vec<Tool>>,
+}
+
+impl UpstreamState {
+    fn connect_error(&self, error: impl std::fmt::Display) -> GatewayError {
+        GatewayError::Connect {
+            upstream: self.config.name.clone(),
+            message: error.to_string(),
+        }
+    }
+
+    async fn open(self: &Arc<Self>, shared: &Arc<Shared>) -> Result<(Connection, Vec<Tool>), GatewayError> {
+        let client = UpstreamClient {
+            upstream: Arc::downgrade(self),
+            shared: shared.clone(),
+        };
+
+        let (service, child) = match &self.config.transport {
+            UpstreamTransport::Command { program, args, env } => {
+                let mut child = tokio::process::Command::new(program)
+                    .args(args)
+                    .envs(env.iter().map(|(k, v)| (k, v)))
+                    .stdin(Stdio::piped())
+                    .stdout(Stdio::piped())
+                    .stderr(Stdio::inherit())
+                    .kill_on_drop(true)
+                    .spawn()
+                    .map_err(|e| self.connect_error(e))?;
+                let stdout = child.stdout.take().ok_or_else(|| self.connect_error("no stdout"))?;
+                let stdin = child.stdin.take().ok_or_else(|| self.connect_error("no stdin"))?;
+                let service = client.serve((stdout, stdin)).await.map_err(|e| self.connect_error(e))?;
+                (service, Some(child))
+            }
+            UpstreamTransport::Http { url, bearer_token } => {
+                let mut config = StreamableHttpClientTransportConfig::with_uri(url.as_str());
+                if let Some(token) = bearer_token {
+                    config = config.auth_header(token.clone());
+                }
+                let transport = StreamableHttpClientTransport::from_config(config);
+                let service = client.serve(transport).await.map_err(|e| self.connect_error(e))?;
+                (service, None)
+            }
+            UpstreamTransport::Duplex(connect) => {
+                let service = client.serve(connect()).await.map_err(|e| self.connect_error(e))?;
+                (service, None)
+            }
+        };
+
+        let tools = service.list_all_tools().await.map_err(|e| self.connect_error(e))?;
+        Ok((Connection { service, _child: child }, tools))
+    }
+
+    // Returns a live peer, reconnecting with exponential backoff if the
+    // previous session has gone away.
+    async fn peer(self: &Arc<Self>, shared: &Arc<Shared>) -> Result<Peer<RoleClient>, GatewayError> {
+        let mut connection = self.connection.lock().await;
+        if let Some(current) = connection.as_ref() {
+            if !current.service.is_transport_closed() {
+                return Ok(current.service.peer().clone());
+            }
+        }
+        connection.take();
+
+        let mut backoff = RECONNECT_BACKOFF;
+        let mut attempt = 0;
+        let fresh = loop {
+            let result = tokio::time::timeout(self.config.timeout, self.open(shared))
+                .await
+                .unwrap_or_else(|_| {
+                    Err(GatewayError::Timeout {
+                        upstream: self.config.name.clone(),
+                        timeout: self.config.timeout,
+                    })
+                });
+            match result {
+                Ok(fresh) => break fresh,
+                Err(_) if attempt < self.config.reconnect_attempts => {
+                    attempt += 1;
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                }
+                Err(e) => {
+                    // Stop advertising tools that can no longer be called.
+                    drop(connection);
+                    if self.set_tools(Vec::new()) {
+                        shared.notify_tools_changed().await;
+                    }
+                    return Err(e);
+                }
+            }
+        };
+
+        let (fresh, tools) = fresh;
+        let peer = fresh.service.peer().clone();
+        *connection = Some(fresh);
+        drop(connection);
+
+        if self.set_tools(tools) {
+            shared.notify_tools_changed().await;
+        }
+        Ok(peer)
+    }
+
+    // Reconnects in the background unless the session is live or a
+    // reconnect is already under way.
+    fn ensure_connected(self: &Arc<Self>, shared: &Arc<Shared>) {
+        let idle = match self.connection.try_lock() {
+            Ok(connection) => connection.as_ref().is_none_or(|c| c.service.is_transport_closed()),
+            Err(_) => false,
+        };
+        if idle {
+            let (upstream, shared) = (self.clone(), shared.clone());
+            tokio::spawn(async move {
+                let _ = upstream.peer(&shared).await;
+            });
+        }
+    }
+
+    fn tools(&self) -> Vec<Tool> {
+        self.tools.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    // Returns whether the list changed.
+    fn set_tools(&self, tools: Vec<Tool>) -> bool {
+        let mut current = self.tools.lock().unwrap_or_else(|e| e.into_inner());
+        let changed = *current != tools;
+        *current = tools;
+        changed
+    }
+
+    fn has_tool(&self, name: &str) -> bool {
+        self.tools.lock().unwrap_or_else(|e| e.into_inner()).iter().any(|t| t.name == name)
+    }
+}
+
+// Federates several upstream MCP servers behind one. Upstream tools are
+// exposed as `<upstream><separator><tool>`, calls and their progress are
+// forwarded, and upstream list changes are passed on to connected clients.
+#[derive(Clone)]
+pub struct McpGateway {
+    name: String,
+    version: String,
+    instructions: Option<String>,
+    separator: String,
+    upstreams: Vec<Arc<UpstreamState>>,
+    shared: Arc<Shared>,
+}
+
+impl McpGateway {
+    pub fn new(name: impl Into<String>, version: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            version: version.into(),
+            instructions: None,
+            separator: ".".to_string(),
+            upstreams: Vec::new(),
+            shared: Arc::default(),
+        }
+    }
+
+    pub fn with_instructions(mut self, instructions: impl Into<String>) -> Self {
+        self.instructions = Some(instructions.into());
+        self
+    }
+
+    pub fn with_separator(mut self, separator: impl Into<String>) -> Self {
+        self.separator = separator.into();
+        self
+    }
+
+    pub fn with_upstream(mut self, upstream: Upstream) -> Self {
+        self.upstreams.push(Arc::new(UpstreamState {
+            config: upstream,
+            connection: tokio::sync::Mutex::new(None),
+            tools: Mutex::default(),
+        }));
+        self
+    }
+
+    // Connects every upstream. All are attempted; the first failure is
+    // returned and that upstream is retried when next used.
+    pub async fn connect(&self) -> Result<(), GatewayError> {
+        join_all(self.upstreams.iter().map(|u| u.peer(&self.shared)))
+            .await
+            .into_iter()
+            .find_map(Result::err)
+            .map_or(Ok(()), Err)
+    }
+
+    // The merged, namespaced catalogue, served from each upstream's last
+    // known tools so listing never waits on a connection. Upstreams that have
+    // gone away are reconnected in the background, and clients are told to
+    // re-list once their tools change.
+    pub async fn tools(&self) -> Vec<Tool> {
+        self.upstreams
+            .iter()
+            .flat_map(|upstream| {
+                upstream.ensure_connected(&self.shared);
+                upstream.tools().into_iter().map(|mut tool| {
+                    tool.name = format!("{}{}{}", upstream.config.name, self.separator, tool.name).into();
+                    tool
+                })
+            })
+            .collect()
+    }
+
+    // Upstream names can prefix each other (`a` and `a_b` with `_`), so
+    // prefer the upstream that lists the tool, then the longest name.
+    fn resolve<'a>(&self, name: &'a str) -> Option<(&Arc<UpstreamState>, &'a str)> {
+        let mut candidates: Vec<_> = self
+            .upstreams
+            .iter()
+            .filter_map(|upstream| {
+                let tool = name
+                    .strip_prefix(upstream.config.name.as_str())?
+                    .strip_prefix(self.separator.as_str())?;
+                Some((upstream, tool))
+            })
+            .collect();
+        candidates.sort_by_key(|(upstream, _)| std::cmp::Reverse(upstream.config.name.len()));
+        candidates
+            .iter()
+            .find(|(upstream, tool)| upstream.has_tool(tool))
+            .or(candidates.first())
+            .copied()
+    }
+
+    async fn forward(
+        &self,
+        upstream: &Arc<UpstreamState>,
+        request: CallToolRequestParam,
+        meta: Meta,
+    ) -> Result<ServerResult, ServiceError> {
+        let options = || PeerRequestOptions {
+            timeout: Some(upstream.config.timeout),
+            meta: Some(meta.clone()),
+        };
+        let peer = upstream
+            .peer(&self.shared)
+            .await
+            .map_err(|e| ServiceError::McpError(McpError::internal_error(e.to_string(), None)))?;
+        let handle = match peer
+            .send_request_with_option(ClientRequest::CallToolRequest(CallToolRequest::new(request.clone())), options())
+            .await
+        {
+            Ok(handle) => handle,
+            // The request never left, so it is safe to retry on a new session.
+            Err(ServiceError::TransportClosed) => {
+                let peer = upstream
+                    .peer(&self.shared)
+                    .await
+                    .map_err(|e| ServiceError::McpError(McpError::internal_error(e.to_string(), None)))?;
+                peer.send_request_with_option(ClientRequest::CallToolRequest(CallToolRequest::new(request)), options())
+                    .await?
+            }
+            Err(e) => return Err(e),
+        };
+        handle.await_response().await
+    }
+}
+
+impl ServerHandler for McpGateway {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo {
+            capabilities: ServerCapabilities {
+                tools: Some(ToolsCapability { list_changed: Some(true) }),
+                ..Default::default()
+            },
+            server_info: Implementation {
+                name: self.name.clone(),
+                version: self.version.clone(),
+                ..Default::default()
+            },
+            instructions: self.instructions.clone(),
+            ..Default::default()
+        }
+    }
+
+    async fn on_initialized(&self, context: NotificationContext<RoleServer>) {
+        self.shared
+            .downstream
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(context.peer);
+    }
+
+    async fn list_tools(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, McpError> {
+        Ok(ListToolsResult::with_all_items(self.tools().await))
+    }
+
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let (upstream, tool) = self
+            .resolve(&request.name)
+            .ok_or_else(|| McpError::invalid_params(format!("Unknown tool: {}", request.name), None))?;
+        let forwarded = CallToolRequestParam {
+            name: tool.to_string().into(),
+            arguments: request.arguments,
+        };
+
+        let mut meta = Meta::new();
+        let route = context.meta.get_progress_token().map(|token| {
+            let upstream_token = self.shared.route_progress(context.peer.clone(), token);
+            meta.set_progress_token(upstream_token.clone());
+            upstream_token
+        });
+
+        let result = tokio::select! {
+            result = self.forward(upstream, forwarded, meta) => result,
+            _ = context.ct.cancelled() => Err(ServiceError::Cancelled { reason: None }),
+        };
+        if let Some(token) = route {
+            self.shared.unroute_progress(&token);
+        }
+
+        match result {
+            Ok(ServerResult::CallToolResult(result)) => Ok(result),
+            Ok(_) => Err(McpError::internal_error("Unexpected upstream response", None)),
+            Err(ServiceError::McpError(e)) => Err(e),
+            Err(ServiceError::Timeout { timeout }) => Err(McpError::internal_error(
+                GatewayError::Timeout {
+                    upstream: upstream.config.name.clone(),
+                    timeout,
+                }
+                .to_string(),
+                None,
+            )),
+            Err(e) => Err(McpError::internal_error(e.to_string(), None)),
+        }
+    }
+}
@@ -2,6 +2,9 @@ pub mod server;
 pub mod types;
 pub mod provider;
 pub mod providers;
+mod router;
+mod handler;
+mod service;
 
 #[cfg(feature = "mcp-tools")]
 pub mod tools;
@@ -9,6 +12,9 @@ pub mod tools;
 #[cfg(feature = "mcp-inprocess")]
 mod host;
 
+#[cfg(feature = "mcp-gateway")]
+mod gateway;
+
 #[cfg(feature = "cli")]
 pub mod cli;
 
@@ -31,4 +37,10 @@ pub use rmcp::tool_router;
 
 #[cfg(feature = "mcp-inprocess")]
 pub use host::McpHost;
+
+#[cfg(feature = "mcp-gateway")]
+pub use gateway::{GatewayError, McpGateway, Upstream};
 pub use provider::{ToolProvider, ResourceProvider, PromptProvider};
+pub use router::McpRouter;
+pub use handler::McpHandler;
+pub use service::{ListChangedNotifier, RouterService};
@@ -0,0 +1,455 @@
+use async_trait::async_trait;
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use rmcp::{
+    model::{ClientNotification, ClientRequest, ServerInfo, ServerResult},
+    service::{NotificationContext, RequestContext, RoleServer, Service},
+    ErrorData as McpError,
+};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tower_http::cors::{AllowOrigin, CorsLayer};
+
+const TOOL_SCOPE_PREFIX: &str = "tools:";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ToolAccess {
+    All,
+    Categories(HashSet<String>),
+    None,
+}
+
+impl ToolAccess {
+    pub fn categories<I, C>(categories: I) -> Self
+    where
+        I: IntoIterator<Item = C>,
+        C: Into<String>,
+    {
+        Self::Categories(categories.into_iter().map(Into::into).collect())
+    }
+
+    // Uncategorised tools are only visible to unrestricted principals.
+    pub fn allows(&self, category: Option<&str>) -> bool {
+        match self {
+            Self::All => true,
+            Self::Categories(allowed) => category.is_some_and(|c| allowed.contains(c)),
+            Self::None => false,
+        }
+    }
+
+    // `tools:<category>` scopes restrict access and `tools:*` lifts the
+    // restriction. Returns None when the token carries no tool scopes.
+    pub fn from_scopes<'a>(scopes: impl IntoIterator<Item = &'a str>) -> Option<Self> {
+        let mut categories = HashSet::new();
+        for scope in scopes {
+            match scope.strip_prefix(TOOL_SCOPE_PREFIX) {
+                Some("*") => return Some(Self::All),
+                Some(category) => {
+                    categories.insert(category.to_string());
+                }
+                None => {}
+            }
+        }
+        (!categories.is_empty()).then_some(Self::Categories(categories))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Principal {
+    pub subject: String,
+    pub access: ToolAccess,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AuthError {
+    #[error("Missing bearer token")]
+    MissingToken,
+
+    #[error("Invalid token: {0}")]
+    InvalidToken(String),
+
+    #[error("Authorization server unavailable: {0}")]
+    Unavailable(String),
+}
+
+#[async_trait]
+pub trait Authenticator: Send + Sync {
+    async fn authenticate(&self, token: &str) -> Result<Principal, AuthError>;
+}
+
+#[derive(Default)]
+pub struct StaticTokenAuth {
+    tokens: HashMap<String, Principal>,
+}
+
+impl StaticTokenAuth {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_token(self, token: impl Into<String>, subject: impl Into<String>) -> Self {
+        self.with_access(token, subject, ToolAccess::All)
+    }
+
+    pub fn with_access(mut self, token: impl Into<String>, subject: impl Into<String>, access: ToolAccess) -> Self {
+        self.tokens.insert(
+            token.into(),
+            Principal {
+                subject: subject.into(),
+                access,
+            },
+        );
+        self
+    }
+}
+
+#[async_trait]
+impl Authenticator for StaticTokenAuth {
+    async fn authenticate(&self, token: &str) -> Result<Principal, AuthError> {
+        self.tokens
+            .get(token)
+            .cloned()
+            .ok_or_else(|| AuthError::InvalidToken("Unknown token".to_string()))
+    }
+}
+
+#[cfg(feature = "mcp-auth")]
+pub use self::oauth::{IntrospectionAuth, JwtAuth};
+
+#[cfg(feature = "mcp-auth")]
+mod oauth {
+    use super::{AuthError, Authenticator, Principal, ToolAccess};
+    use async_trait::async_trait;
+    use swissknife_auth_sdk::introspection::TokenValidator;
+    use swissknife_auth_sdk::jwks::JwksClient;
+
+    fn auth_error(error: swissknife_auth_sdk::Error) -> AuthError {
+        match error {
+            swissknife_auth_sdk::Error::Http(e) => AuthError::Unavailable(e.to_string()),
+            swissknife_auth_sdk::Error::OAuth(e) => AuthError::Unavailable(e),
+            e => AuthError::InvalidToken(e.to_string()),
+        }
+    }
+
+    // Tokens without any `tools:` scope get `default_access`, which grants no
+    // tools; pass `ToolAccess::All` to `with_default_access` to opt in.
+    pub struct JwtAuth {
+        jwks: JwksClient,
+        audience: String,
+        issuer: Option<String>,
+        default_access: ToolAccess,
+    }
+
+    impl JwtAuth {
+        pub fn new(jwks_uri: impl Into<String>, audience: impl Into<String>) -> Self {
+            Self {
+                jwks: JwksClient::new(jwks_uri),
+                audience: audience.into(),
+                issuer: None,
+                default_access: ToolAccess::None,
+            }
+        }
+
+        pub fn with_jwks_client(mut self, jwks: JwksClient) -> Self {
+            self.jwks = jwks;
+            self
+        }
+
+        pub fn with_issuer(mut self, issuer: impl Into<String>) -> Self {
+            self.issuer = Some(issuer.into());
+            self
+        }
+
+        pub fn with_default_access(mut self, access: ToolAccess) -> Self {
+            self.default_access = access;
+            self
+        }
+    }
+
+    #[async_trait]
+    impl Authenticator for JwtAuth {
+        async fn authenticate(&self, token: &str) -> Result<Principal, AuthError> {
+            let mut decoder = self
+                .jwks
+                .decoder_for(token)
+                .await
+                .map_err(auth_error)?
+                .require_audience(&self.audience);
+            if let Some(issuer) = &self.issuer {
+                decoder = decoder.require_issuer(issuer);
+            }
+            let claims = decoder
+                .decode::<serde_json::Value>(token)
+                .map_err(auth_error)?
+                .claims;
+
+            // `scope` is the RFC 8693 space-delimited form; some providers use a `scp` array.
+            let scopes: Vec<&str> = match (claims.get("scope"), claims.get("scp")) {
+                (Some(serde_json::Value::String(scope)), _) => scope.split_whitespace().collect(),
+                (_, Some(serde_json::Value::Array(scp))) => scp.iter().filter_map(|s| s.as_str()).collect(),
+                _ => Vec::new(),
+            };
+
+            Ok(Principal {
+                subject: claims
+                    .get("sub")
+                    .and_then(|s| s.as_str())
+                    .unwrap_or_default()
+                    .to_string(),
+                access: ToolAccess::from_scopes(scopes).unwrap_or_else(|| self.default_access.clone()),
+            })
+        }
+    }
+
+    // Like `JwtAuth`, tokens without `tools:` scopes get no tools by default.
+    pub struct IntrospectionAuth {
+        validator: Box<dyn TokenValidator>,
+        audience: Option<String>,
+        default_access: ToolAccess,
+    }
+
+    impl IntrospectionAuth {
+        pub fn new(validator: impl TokenValidator + 'static) -> Self {
+            Self {
+                validator: Box::new(validator),
+                audience: None,
+                default_access: ToolAccess::None,
+            }
+        }
+
+        pub fn with_audience(mut self, audience: impl Into<String>) -> Self {
+            self.audience = Some(audience.into());
+            self
+        }
+
+        pub fn with_default_access(mut self, access: ToolAccess) -> Self {
+            self.default_access = access;
+            self
+        }
+    }
+
+    #[async_trait]
+    impl Authenticator for IntrospectionAuth {
+        async fn authenticate(&self, token: &str) -> Result<Principal, AuthError> {
+            let response = self.validator.validate(token).await.map_err(auth_error)?;
+            if !response.active || response.is_expired() {
+                return Err(AuthError::InvalidToken("Token is not active".to_string()));
+            }
+            if let Some(audience) = &self.audience {
+                if !response.audiences().contains(audience) {
+                    return Err(AuthError::InvalidToken("Token audience mismatch".to_string()));
+                }
+            }
+
+            Ok(Principal {
+                subject: response
+                    .sub
+                    .clone()
+                    .or_else(|| response.username.clone())
+                    .unwrap_or_default(),
+                access: ToolAccess::from_scopes(response.scopes()).unwrap_or_else(|| self.default_access.clone()),
+            })
+        }
+    }
+}
+
+// OAuth 2.0 Protected Resource Metadata (RFC 9728), which MCP clients use to
+// discover the authorization server for this endpoint.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProtectedResourceMetadata {
+    pub resource: String,
+    pub authorization_servers: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub scopes_supported: Vec<String>,
+    pub bearer_methods_supported: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_name: Option<String>,
+}
+
+impl ProtectedResourceMetadata {
+    pub fn new(resource: impl Into<String>, authorization_server: impl Into<String>) -> Self {
+        Self {
+            resource: resource.into(),
+            authorization_servers: vec![authorization_server.into()],
+            scopes_supported: Vec::new(),
+            bearer_methods_supported: vec!["header".to_string()],
+            resource_name: None,
+        }
+    }
+
+    pub fn with_scopes<I, S>(mut self, scopes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.scopes_supported = scopes.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn with_resource_name(mut self, name: impl Into<String>) -> Self {
+        self.resource_name = Some(name.into());
+        self
+    }
+
+    pub(crate) fn metadata_url(&self, path: &str) -> String {
+        let origin = match self.resource.find("://") {
+            Some(scheme_end) => match self.resource[scheme_end + 3..].find('/') {
+                Some(path_start) => &self.resource[..scheme_end + 3 + path_start],
+                None => self.resource.as_str(),
+            },
+            None => self.resource.as_str(),
+        };
+        format!("{}{}", origin.trim_end_matches('/'), metadata_path(path))
+    }
+}
+
+pub(crate) fn metadata_path(path: &str) -> String {
+    format!("/.well-known/oauth-protected-resource{}", path.trim_end_matches('/'))
+}
+
+pub(crate) struct AuthState {
+    pub authenticator: Arc<dyn Authenticator>,
+    pub metadata_url: Option<String>,
+}
+
+pub(crate) async fn require_auth(State(state): State<Arc<AuthState>>, mut request: Request, next: Next) -> Response {
+    let token = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split_once(' '))
+        .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("bearer"))
+        .map(|(_, token)| token.trim().to_string());
+
+    let result = match token {
+        Some(token) => state.authenticator.authenticate(&token).await,
+        None => Err(AuthError::MissingToken),
+    };
+
+    match result {
+        Ok(principal) => {
+            request.extensions_mut().insert(principal);
+            next.run(request).await
+        }
+        Err(AuthError::Unavailable(message)) => (StatusCode::SERVICE_UNAVAILABLE, message).into_response(),
+        Err(error) => {
+            let mut challenge = "Bearer".to_string();
+            if let Some(url) = &state.metadata_url {
+                challenge.push_str(&format!(" resource_metadata=\"{}\"", url));
+            }
+            if let AuthError::InvalidToken(_) = error {
+                challenge.push_str(", error=\"invalid_token\"");
+            }
+            let mut response = (StatusCode::UNAUTHORIZED, error.to_string()).into_response();
+            if let Ok(value) = HeaderValue::from_str(&challenge) {
+                response.headers_mut().insert(header::WWW_AUTHENTICATE, value);
+            }
+            response
+        }
+    }
+}
+
+// Browsers always send Origin, so rejecting unknown ones here also guards
+// against DNS rebinding; CORS alone only hides the response.
+pub(crate) async fn check_origin(State(origins): State<Arc<Vec<String>>>, request: Request, next: Next) -> Response {
+    let allowed = match request.headers().get(header::ORIGIN).and_then(|v| v.to_str().ok()) {
+        Some(origin) => origins.iter().any(|o| o == "*" || o == origin),
+        None => true,
+    };
+    if !allowed {
+        return (StatusCode::FORBIDDEN, "Origin not allowed").into_response();
+    }
+    next.run(request).await
+}
+
+pub(crate) fn cors_layer(origins: &[String]) -> CorsLayer {
+    let allow_origin = if origins.iter().any(|o| o == "*") {
+        AllowOrigin::any()
+    } else {
+        AllowOrigin::list(origins.iter().filter_map(|o| HeaderValue::from_str(o).ok()))
+    };
+
+    CorsLayer::new()
+        .allow_origin(allow_origin)
+        .allow_methods([Method::GET, Method::POST, Method::DELETE, Method::OPTIONS])
+        .allow_headers([
+            header::AUTHORIZATION,
+            header::CONTENT_TYPE,
+            header::ACCEPT,
+            header::HeaderName::from_static("mcp-session-id"),
+            header::HeaderName::from_static("mcp-protocol-version"),
+            header::HeaderName::from_static("last-event-id"),
+        ])
+        .expose_headers([
+            header::WWW_AUTHENTICATE,
+            header::HeaderName::from_static("mcp-session-id"),
+        ])
+}
+
+// Hides and rejects tools outside the caller's categories. The principal is
+// read from the HTTP request parts rmcp attaches to each JSON-RPC request, so
+// handlers served without authentication pass through untouched.
+pub(crate) struct ToolGuard<S> {
+    pub inner: S,
+    pub categories: Arc<HashMap<String, String>>,
+}
+
+impl<S> ToolGuard<S> {
+    fn allows(&self, access: &ToolAccess, tool: &str) -> bool {
+        access.allows(self.categories.get(tool).map(String::as_str))
+    }
+}
+
+fn principal(context: &RequestContext<RoleServer>) -> Option<Principal> {
+    context
+        .extensions
+        .get::<axum::http::request::Parts>()
+        .and_then(|parts| parts.extensions.get::<Principal>())
+        .cloned()
+}
+
+impl<S: Service<RoleServer>> Service<RoleServer> for ToolGuard<S> {
+    async fn handle_request(
+        &self,
+        request: ClientRequest,
+        context: RequestContext<RoleServer>,
+    ) -> Result<ServerResult, McpError> {
+        let Some(principal) = principal(&context) else {
+            return self.inner.handle_request(request, context).await;
+        };
+
+        if let ClientRequest::CallToolRequest(call) = &request {
+            if !self.allows(&principal.access, &call.params.name) {
+                return Err(McpError::invalid_params(
+                    format!("Tool {} is not available to {}", call.params.name, principal.subject),
+                    None,
+                ));
+            }
+        }
+
+        match self.inner.handle_request(request, context).await? {
+            ServerResult::ListToolsResult(mut result) => {
+                result.tools.retain(|tool| self.allows(&principal.access, &tool.name));
+                Ok(ServerResult::ListToolsResult(result))
+            }
+            result => Ok(result),
+        }
+    }
+
+    async fn handle_notification(
+        &self,
+        notification: ClientNotification,
+        context: NotificationContext<RoleServer>,
+    ) -> Result<(), McpError> {
+        self.inner.handle_notification(notification, context).await
+    }
+
+    fn get_info(&self) -> ServerInfo {
+        self.inner.get_info()
+    }
+}
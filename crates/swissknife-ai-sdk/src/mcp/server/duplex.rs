@@ -28,6 +28,9 @@ pub async fn serve_duplex<S: ServerHandler + Send + 'static>(
 
     let client_service: ClientService = ().serve(client_transport).await?;
     let peer = client_service.peer().clone();
+    // Dropping the running client would cancel it and strand the peer, so it
+    // lives until the server side closes the transport.
+    tokio::spawn(client_service.waiting());
 
     Ok(DuplexConnection {
         server_handle,
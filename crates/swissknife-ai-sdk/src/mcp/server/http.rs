@@ -1,157 +1,158 @@
-use axum::{
-    extract::{Path, State},
-    http::StatusCode,
-    response::{
-        sse::{Event, KeepAlive, Sse},
-        IntoResponse, Json,
-    },
-    routing::{get, post},
-    Router,
+use super::auth::{
+    check_origin, cors_layer, metadata_path, require_auth, AuthState, Authenticator,
+    ProtectedResourceMetadata, ToolGuard,
 };
-use futures_util::stream::{self, Stream};
-use serde::{Deserialize, Serialize};
-use std::{
-    collections::HashMap,
-    convert::Infallible,
-    net::SocketAddr,
-    sync::Arc,
-    time::Duration,
+use axum::{middleware, response::IntoResponse, routing::get, Json, Router};
+use rmcp::{
+    transport::streamable_http_server::{
+        session::local::LocalSessionManager, StreamableHttpServerConfig, StreamableHttpService,
+    },
+    ServerHandler,
 };
-use tokio::sync::{broadcast, RwLock};
+use std::{collections::HashMap, net::SocketAddr, sync::Arc, time::Duration};
 
 #[derive(Clone)]
 pub struct McpHttpServerConfig {
-    pub name: String,
-    pub version: String,
+    pub path: String,
+    // Browser origins allowed to call the endpoint; `*` allows any. Empty by
+    // default, so only clients that send no Origin header get through.
     pub cors_origins: Vec<String>,
+    pub sse_keep_alive: Option<Duration>,
+    // Stateless mode serves each POST with a fresh handler and never issues an
+    // Mcp-Session-Id, so GET streams, resumption and DELETE are unavailable.
+    pub stateful: bool,
 }
 
 impl Default for McpHttpServerConfig {
     fn default() -> Self {
         Self {
-            name: "swissknife-mcp".to_string(),
-            version: "0.1.0".to_string(),
-            cors_origins: vec!["*".to_string()],
+            path: "/mcp".to_string(),
+            cors_origins: Vec::new(),
+            sse_keep_alive: Some(Duration::from_secs(15)),
+            stateful: true,
         }
     }
 }
 
-#[derive(Clone, Serialize)]
-pub struct ToolInfo {
-    pub name: String,
-    pub description: String,
-    pub parameters: serde_json::Value,
-}
-
-#[derive(Clone, Serialize)]
-pub struct ServerInfo {
-    pub name: String,
-    pub version: String,
-    pub tools: Vec<ToolInfo>,
-}
-
-#[derive(Deserialize)]
-pub struct ToolCallRequest {
-    pub name: String,
-    pub arguments: serde_json::Value,
-}
+type ServiceFactory<S> = Arc<dyn Fn() -> S + Send + Sync>;
 
-#[derive(Serialize)]
-pub struct ToolCallResponse {
-    pub success: bool,
-    pub result: Option<serde_json::Value>,
-    pub error: Option<String>,
-}
-
-#[derive(Clone, Serialize)]
-pub struct SseMessage {
-    pub event_type: String,
-    pub data: serde_json::Value,
-}
-
-pub type ToolHandler = Arc<
-    dyn Fn(serde_json::Value) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<serde_json::Value, String>> + Send>>
-        + Send
-        + Sync,
->;
-
-pub struct McpHttpServer {
+// MCP Streamable HTTP transport: one endpoint taking JSON-RPC POSTs, GET for
+// the server-initiated SSE stream (resumable via Last-Event-ID) and DELETE to
+// end the session. Each session gets its own handler from `factory`.
+pub struct McpHttpServer<S> {
     config: McpHttpServerConfig,
-    tools: Arc<RwLock<HashMap<String, (ToolInfo, ToolHandler)>>>,
-    event_tx: broadcast::Sender<SseMessage>,
-}
-
-impl McpHttpServer {
-    pub fn new(config: McpHttpServerConfig) -> Self {
-        let (event_tx, _) = broadcast::channel(100);
+    factory: ServiceFactory<S>,
+    auth: Option<Arc<dyn Authenticator>>,
+    resource_metadata: Option<ProtectedResourceMetadata>,
+    tool_categories: HashMap<String, String>,
+}
+
+impl<S> McpHttpServer<S>
+where
+    S: ServerHandler + Send + 'static,
+{
+    pub fn new<F>(config: McpHttpServerConfig, factory: F) -> Self
+    where
+        F: Fn() -> S + Send + Sync + 'static,
+    {
         Self {
             config,
-            tools: Arc::new(RwLock::new(HashMap::new())),
-            event_tx,
+            factory: Arc::new(factory),
+            auth: None,
+            resource_metadata: None,
+            tool_categories: HashMap::new(),
         }
     }
 
-    pub async fn register_tool<F, Fut>(
-        &self,
-        name: impl Into<String>,
-        description: impl Into<String>,
-        parameters: serde_json::Value,
-        handler: F,
-    ) where
-        F: Fn(serde_json::Value) -> Fut + Send + Sync + 'static,
-        Fut: std::future::Future<Output = Result<serde_json::Value, String>> + Send + 'static,
-    {
-        let name = name.into();
-        let info = ToolInfo {
-            name: name.clone(),
-            description: description.into(),
-            parameters,
-        };
+    // Every request to the MCP endpoint must then carry a bearer token the
+    // authenticator accepts.
+    pub fn with_auth(mut self, auth: impl Authenticator + 'static) -> Self {
+        self.auth = Some(Arc::new(auth));
+        self
+    }
 
-        let handler: ToolHandler = Arc::new(move |args| Box::pin(handler(args)));
+    pub fn with_resource_metadata(mut self, metadata: ProtectedResourceMetadata) -> Self {
+        self.resource_metadata = Some(metadata);
+        self
+    }
 
-        let mut tools = self.tools.write().await;
-        tools.insert(name, (info, handler));
+    // Assigns tools to a category for per-token allow-lists.
+    pub fn with_tool_category<I, T>(mut self, category: impl Into<String>, tools: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        let category = category.into();
+        for tool in tools {
+            self.tool_categories.insert(tool.into(), category.clone());
+        }
+        self
     }
 
-    pub fn broadcast(&self, event_type: impl Into<String>, data: serde_json::Value) {
-        let _ = self.event_tx.send(SseMessage {
-            event_type: event_type.into(),
-            data,
-        });
+    pub fn config(&self) -> &McpHttpServerConfig {
+        &self.config
     }
 
-    pub async fn serve(self, addr: SocketAddr) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let state = Arc::new(AppState {
-            config: self.config,
-            tools: self.tools,
-            event_tx: self.event_tx,
-        });
+    pub fn router(&self) -> Router {
+        let factory = self.factory.clone();
+        let categories = Arc::new(self.tool_categories.clone());
+        let service = StreamableHttpService::new(
+            move || {
+                Ok(ToolGuard {
+                    inner: factory(),
+                    categories: categories.clone(),
+                })
+            },
+            Arc::new(LocalSessionManager::default()),
+            StreamableHttpServerConfig {
+                sse_keep_alive: self.config.sse_keep_alive,
+                stateful_mode: self.config.stateful,
+                ..Default::default()
+            },
+        );
+
+        let mut mcp = Router::new().nest_service(&self.config.path, service);
+        if let Some(authenticator) = &self.auth {
+            let state = Arc::new(AuthState {
+                authenticator: authenticator.clone(),
+                metadata_url: self
+                    .resource_metadata
+                    .as_ref()
+                    .map(|m| m.metadata_url(&self.config.path)),
+            });
+            mcp = mcp.layer(middleware::from_fn_with_state(state, require_auth));
+        }
+
+        let mut router = Router::new().route("/health", get(health_handler)).merge(mcp);
+        if let Some(metadata) = &self.resource_metadata {
+            let metadata = Json(metadata.clone());
+            router = router.route(&metadata_path(&self.config.path), get(move || async move { metadata }));
+        }
 
-        let app = Router::new()
-            .route("/", get(health_handler))
-            .route("/health", get(health_handler))
-            .route("/info", get(info_handler))
-            .route("/tools", get(list_tools_handler))
-            .route("/tools/:name", post(call_tool_handler))
-            .route("/call", post(call_tool_by_body_handler))
-            .route("/sse", get(sse_handler))
-            .route("/events", get(sse_handler))
-            .layer(tower_http::cors::CorsLayer::permissive())
-            .with_state(state);
+        let origins = Arc::new(self.config.cors_origins.clone());
+        router
+            .layer(middleware::from_fn_with_state(origins, check_origin))
+            .layer(cors_layer(&self.config.cors_origins))
+    }
 
+    pub async fn serve(self, addr: SocketAddr) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let app = self.router();
         let listener = tokio::net::TcpListener::bind(addr).await?;
-        println!("MCP HTTP server listening on http://{}", addr);
+        println!("MCP HTTP server listening on http://{}{}", addr, self.config.path);
 
         axum::serve(listener, app).await?;
         Ok(())
     }
 }
 
-struct AppState {
-    config: McpHttpServerConfig,
-    tools: Arc<RwLock<HashMap<String, (ToolInfo, ToolHandler)>>>,
-    event_tx: broadcast::Sender<SseMessage>,
+pub async fn serve_http<S, F>(factory: F, addr: SocketAddr) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
+where
+    S: ServerHandler + Send + 'static,
+    F: Fn() -> S + Send + Sync + 'static,
+{
+    McpHttpServer::new(McpHttpServerConfig::default(), factory)
+        .serve(addr)
+        .await
 }
 
 async fn health_handler() -> impl IntoResponse {
@@ -160,136 +161,3 @@ async fn health_handler() -> impl IntoResponse {
         "timestamp": chrono::Utc::now().to_rfc3339()
     }))
 }
-
-async fn info_handler(State(state): State<Arc<AppState>>) -> impl IntoResponse {
-    let tools = state.tools.read().await;
-    let tool_list: Vec<ToolInfo> = tools.values().map(|(info, _)| info.clone()).collect();
-
-    Json(ServerInfo {
-        name: state.config.name.clone(),
-        version: state.config.version.clone(),
-        tools: tool_list,
-    })
-}
-
-async fn list_tools_handler(State(state): State<Arc<AppState>>) -> impl IntoResponse {
-    let tools = state.tools.read().await;
-    let tool_list: Vec<ToolInfo> = tools.values().map(|(info, _)| info.clone()).collect();
-    Json(tool_list)
-}
-
-async fn call_tool_handler(
-    State(state): State<Arc<AppState>>,
-    Path(name): Path<String>,
-    Json(arguments): Json<serde_json::Value>,
-) -> impl IntoResponse {
-    let tools = state.tools.read().await;
-
-    match tools.get(&name) {
-        Some((_, handler)) => {
-            let handler = handler.clone();
-            drop(tools);
-
-            match handler(arguments).await {
-                Ok(result) => (
-                    StatusCode::OK,
-                    Json(ToolCallResponse {
-                        success: true,
-                        result: Some(result),
-                        error: None,
-                    }),
-                ),
-                Err(e) => (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(ToolCallResponse {
-                        success: false,
-                        result: None,
-                        error: Some(e),
-                    }),
-                ),
-            }
-        }
-        None => (
-            StatusCode::NOT_FOUND,
-            Json(ToolCallResponse {
-                success: false,
-                result: None,
-                error: Some(format!("Tool '{}' not found", name)),
-            }),
-        ),
-    }
-}
-
-async fn call_tool_by_body_handler(
-    State(state): State<Arc<AppState>>,
-    Json(request): Json<ToolCallRequest>,
-) -> impl IntoResponse {
-    let tools = state.tools.read().await;
-
-    match tools.get(&request.name) {
-        Some((_, handler)) => {
-            let handler = handler.clone();
-            drop(tools);
-
-            match handler(request.arguments).await {
-                Ok(result) => {
-                    let _ = state.event_tx.send(SseMessage {
-                        event_type: "tool_result".to_string(),
-                        data: serde_json::json!({
-                            "tool": request.name,
-                            "result": result
-                        }),
-                    });
-
-                    (
-                        StatusCode::OK,
-                        Json(ToolCallResponse {
-                            success: true,
-                            result: Some(result),
-                            error: None,
-                        }),
-                    )
-                }
-                Err(e) => (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(ToolCallResponse {
-                        success: false,
-                        result: None,
-                        error: Some(e),
-                    }),
-                ),
-            }
-        }
-        None => (
-            StatusCode::NOT_FOUND,
-            Json(ToolCallResponse {
-                success: false,
-                result: None,
-                error: Some(format!("Tool '{}' not found", request.name)),
-            }),
-        ),
-    }
-}
-
-async fn sse_handler(
-    State(state): State<Arc<AppState>>,
-) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
-    let mut rx = state.event_tx.subscribe();
-
-    let stream = async_stream::stream! {
-        yield Ok(Event::default().event("connected").data("Connected to MCP SSE server"));
-
-        loop {
-            match rx.recv().await {
-                Ok(msg) => {
-                    let data = serde_json::to_string(&msg.data).unwrap_or_default();
-                    yield Ok(Event::default().event(msg.event_type).data(data));
-                }
-                Err(broadcast::error::RecvError::Lagged(_)) => continue,
-                Err(broadcast::error::RecvError::Closed) => break,
-            }
-        }
-    };
-
-    Sse::new(stream).keep_alive(KeepAlive::new().interval(Duration::from_secs(30)))
-}
@@ -3,6 +3,9 @@ mod stdio;
 #[cfg(feature = "mcp-inprocess")]
 mod duplex;
 
+#[cfg(feature = "http")]
+mod auth;
+
 #[cfg(feature = "http")]
 mod http;
 
@@ -12,12 +15,12 @@ pub use stdio::serve_stdio;
 pub use duplex::{serve_duplex, DuplexConnection};
 
 #[cfg(feature = "http")]
-pub use http::{
-    McpHttpServer,
-    McpHttpServerConfig,
-    ToolInfo,
-    ServerInfo,
-    ToolCallRequest,
-    ToolCallResponse,
-    SseMessage,
+pub use auth::{
+    AuthError, Authenticator, Principal, ProtectedResourceMetadata, StaticTokenAuth, ToolAccess,
 };
+
+#[cfg(feature = "mcp-auth")]
+pub use auth::{IntrospectionAuth, JwtAuth};
+
+#[cfg(feature = "http")]
+pub use http::{serve_http, McpHttpServer, McpHttpServerConfig};
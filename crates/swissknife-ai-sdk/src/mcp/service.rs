@@ -0,0 +1,247 @@
+use super::router::McpRouter;
+use super::types::{PromptContent, ResourceContent, ToolResult};
+use rmcp::{
+    model::{
+        CallToolRequestParam, CallToolResult, GetPromptRequestParam, GetPromptResult, Implementation,
+        ListPromptsResult, ListResourcesResult, ListToolsResult, PaginatedRequestParam,
+        PromptsCapability, ReadResourceRequestParam, ReadResourceResult, ResourceContents,
+        ResourcesCapability, ServerCapabilities, ServerInfo, ToolsCapability,
+    },
+    service::{NotificationContext, Peer, RequestContext},
+    ErrorData as McpError, RoleServer, ServerHandler,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::sync::{Arc, Mutex};
+
+type Peers = Arc<Mutex<Vec<Peer<RoleServer>>>>;
+
+// Serves an `McpRouter` through rmcp, so it can be handed to `serve_stdio`,
+// `serve_duplex` or `McpHttpServer`. Clones share the router and the set of
+// connected clients, which is what the HTTP transport's per-session factory
+// needs for list_changed notifications to reach every session.
+#[derive(Clone)]
+pub struct RouterService {
+    router: Arc<McpRouter>,
+    peers: Peers,
+}
+
+impl RouterService {
+    pub fn new(router: McpRouter) -> Self {
+        Self {
+            router: Arc::new(router),
+            peers: Arc::default(),
+        }
+    }
+
+    pub fn router(&self) -> &McpRouter {
+        &self.router
+    }
+
+    pub fn notifier(&self) -> ListChangedNotifier {
+        ListChangedNotifier {
+            peers: self.peers.clone(),
+        }
+    }
+}
+
+impl From<McpRouter> for RouterService {
+    fn from(router: McpRouter) -> Self {
+        Self::new(router)
+    }
+}
+
+// Tells connected clients to re-list after a provider's tools, resources or
+// prompts change.
+#[derive(Clone)]
+pub struct ListChangedNotifier {
+    peers: Peers,
+}
+
+impl ListChangedNotifier {
+    pub async fn tools_changed(&self) {
+        for peer in self.live_peers() {
+            let _ = peer.notify_tool_list_changed().await;
+        }
+    }
+
+    pub async fn resources_changed(&self) {
+        for peer in self.live_peers() {
+            let _ = peer.notify_resource_list_changed().await;
+        }
+    }
+
+    pub async fn prompts_changed(&self) {
+        for peer in self.live_peers() {
+            let _ = peer.notify_prompt_list_changed().await;
+        }
+    }
+
+    fn live_peers(&self) -> Vec<Peer<RoleServer>> {
+        let mut peers = self.peers.lock().unwrap_or_else(|e| e.into_inner());
+        peers.retain(|peer| !peer.is_transport_closed());
+        peers.clone()
+    }
+}
+
+// The router's types mirror the MCP wire format, so they convert to rmcp's
+// model through JSON.
+fn convert<T: Serialize, U: DeserializeOwned>(value: T) -> Result<U, McpError> {
+    serde_json::to_value(value)
+        .and_then(serde_json::from_value)
+        .map_err(|e| McpError::internal_error(e.to_string(), None))
+}
+
+fn router_error(error: crate::Error) -> McpError {
+    match error {
+        crate::Error::Api { message, code: Some(code) } if code == "NOT_FOUND" => {
+            McpError::resource_not_found(message, None)
+        }
+        crate::Error::InvalidParameter(_) | crate::Error::MissingParameter(_) => {
+            McpError::invalid_params(error.to_string(), None)
+        }
+        e => McpError::internal_error(e.to_string(), None),
+    }
+}
+
+fn tool_result(result: ToolResult) -> Result<CallToolResult, McpError> {
+    let is_error = result.is_error;
+    let mut result: CallToolResult = convert(result)?;
+    result.is_error = Some(is_error);
+    Ok(result)
+}
+
+fn resource_contents(content: ResourceContent) -> ResourceContents {
+    match content.blob {
+        Some(blob) => ResourceContents::BlobResourceContents {
+            uri: content.uri,
+            mime_type: content.mime_type,
+            blob,
+            meta: None,
+        },
+        None => ResourceContents::TextResourceContents {
+            uri: content.uri,
+            mime_type: content.mime_type,
+            text: content.text.unwrap_or_default(),
+            meta: None,
+        },
+    }
+}
+
+impl ServerHandler for RouterService {
+    fn get_info(&self) -> ServerInfo {
+        let capabilities = self.router.capabilities();
+        let info = self.router.server_info();
+
+        ServerInfo {
+            capabilities: ServerCapabilities {
+                tools: capabilities.tools.map(|c| ToolsCapability { list_changed: c.list_changed }),
+                resources: capabilities.resources.map(|c| ResourcesCapability {
+                    subscribe: c.subscribe,
+                    list_changed: c.list_changed,
+                }),
+                prompts: capabilities.prompts.map(|c| PromptsCapability { list_changed: c.list_changed }),
+                ..Default::default()
+            },
+            server_info: Implementation {
+                name: info.name,
+                version: info.version,
+                ..Default::default()
+            },
+            instructions: self.router.instructions(),
+            ..Default::default()
+        }
+    }
+
+    async fn on_initialized(&self, context: NotificationContext<RoleServer>) {
+        self.peers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(context.peer);
+    }
+
+    async fn list_tools(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, McpError> {
+        let tools = self
+            .router
+            .list_tools()
+            .into_iter()
+            .map(convert)
+            .collect::<Result<_, _>>()?;
+        Ok(ListToolsResult::with_all_items(tools))
+    }
+
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let arguments = serde_json::Value::Object(request.arguments.unwrap_or_default());
+        match self.router.call_tool(&request.name, arguments).await {
+            Ok(result) => tool_result(result),
+            // Provider failures are reported to the model rather than as
+            // protocol errors, so it can recover.
+            Err(e) => Ok(CallToolResult::error(vec![rmcp::model::Content::text(e.to_string())])),
+        }
+    }
+
+    async fn list_resources(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, McpError> {
+        let resources = self
+            .router
+            .list_resources()
+            .into_iter()
+            .map(convert)
+            .collect::<Result<_, _>>()?;
+        Ok(ListResourcesResult::with_all_items(resources))
+    }
+
+    async fn read_resource(
+        &self,
+        request: ReadResourceRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, McpError> {
+        let content = self.router.read_resource(&request.uri).await.map_err(router_error)?;
+        Ok(ReadResourceResult {
+            contents: vec![resource_contents(content)],
+        })
+    }
+
+    async fn list_prompts(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListPromptsResult, McpError> {
+        let prompts = self
+            .router
+            .list_prompts()
+            .into_iter()
+            .map(convert)
+            .collect::<Result<_, _>>()?;
+        Ok(ListPromptsResult::with_all_items(prompts))
+    }
+
+    async fn get_prompt(
+        &self,
+        request: GetPromptRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<GetPromptResult, McpError> {
+        let arguments = serde_json::Value::Object(request.arguments.unwrap_or_default());
+        let content = self
+            .router
+            .get_prompt(&request.name, arguments)
+            .await
+            .map_err(|e| match e {
+                crate::Error::Api { message, code: Some(code) } if code == "NOT_FOUND" => {
+                    McpError::invalid_params(message, None)
+                }
+                e => router_error(e),
+            })?;
+        convert::<PromptContent, _>(content)
+    }
+}
@@ -5,6 +5,11 @@ use serde::Deserialize;
 #[cfg(feature = "cloud")]
 use swissknife_cloud_sdk as cloud;
 
+#[cfg(any(feature = "s3", feature = "gcs"))]
+use cloud::{ListOptions, ObjectStorageProvider, UploadOptions};
+#[cfg(feature = "s3")]
+use cloud::{PresignOptions, PresignedUrlProvider};
+
 #[derive(Clone)]
 pub struct CloudTools {
     #[cfg(feature = "s3")]
@@ -159,10 +164,21 @@ impl CloudTools {
         let client = self.s3.as_ref()
             .ok_or_else(|| "S3 client not configured".to_string())?;
 
-        let objects = client.list_objects(&req.bucket, req.prefix.as_deref(), req.max_keys).await
+        let options = ListOptions {
+            prefix: req.prefix,
+            max_keys: req.max_keys,
+            ..Default::default()
+        };
+
+        let result = client.list_objects(&req.bucket, &options).await
             .map_err(|e| e.to_string())?;
 
-        serde_json::to_string_pretty(&objects).map_err(|e| e.to_string())
+        serde_json::to_string_pretty(&serde_json::json!({
+            "objects": result.objects,
+            "common_prefixes": result.common_prefixes,
+            "is_truncated": result.is_truncated,
+            "next_continuation_token": result.next_continuation_token,
+        })).map_err(|e| e.to_string())
     }
 
     #[cfg(feature = "s3")]
@@ -190,11 +206,16 @@ impl CloudTools {
         let client = self.s3.as_ref()
             .ok_or_else(|| "S3 client not configured".to_string())?;
 
+        let options = UploadOptions {
+            content_type: req.content_type,
+            ..Default::default()
+        };
+
         client.put_object(
             &req.bucket,
             &req.key,
             req.body.as_bytes(),
-            req.content_type.as_deref(),
+            &options,
         ).await.map_err(|e| e.to_string())?;
 
         Ok(format!("Object uploaded to s3://{}/{}", req.bucket, req.key))
@@ -224,11 +245,17 @@ impl CloudTools {
         let client = self.s3.as_ref()
             .ok_or_else(|| "S3 client not configured".to_string())?;
 
-        let url = client.get_presigned_url(&req.bucket, &req.key, req.expires_in).await
+        let options = PresignOptions {
+            expires_in_seconds: req.expires_in.unwrap_or(3600),
+            content_type: None,
+        };
+
+        let presigned = client.generate_presigned_get_url(&req.bucket, &req.key, &options).await
             .map_err(|e| e.to_string())?;
 
         serde_json::to_string_pretty(&serde_json::json!({
-            "url": url
+            "url": presigned.url,
+            "expires_at": presigned.expires_at,
         })).map_err(|e| e.to_string())
     }
 
@@ -241,10 +268,20 @@ impl CloudTools {
         let client = self.gcs.as_ref()
             .ok_or_else(|| "GCS client not configured".to_string())?;
 
-        let objects = client.list_objects(&req.bucket, req.prefix.as_deref(), req.max_results).await
+        let options = ListOptions {
+            prefix: req.prefix,
+            max_keys: req.max_results,
+            ..Default::default()
+        };
+
+        let result = client.list_objects(&req.bucket, &options).await
             .map_err(|e| e.to_string())?;
 
-        serde_json::to_string_pretty(&objects).map_err(|e| e.to_string())
+        serde_json::to_string_pretty(&serde_json::json!({
+            "objects": result.objects,
+            "prefixes": result.common_prefixes,
+            "next_page_token": result.next_continuation_token,
+        })).map_err(|e| e.to_string())
     }
 
     #[cfg(feature = "gcs")]
@@ -272,11 +309,16 @@ impl CloudTools {
         let client = self.gcs.as_ref()
             .ok_or_else(|| "GCS client not configured".to_string())?;
 
-        client.upload_object(
+        let options = UploadOptions {
+            content_type: req.content_type,
+            ..Default::default()
+        };
+
+        client.put_object(
             &req.bucket,
             &req.object,
             req.data.as_bytes(),
-            req.content_type.as_deref(),
+            &options,
         ).await.map_err(|e| e.to_string())?;
 
         Ok(format!("Object uploaded to gs://{}/{}", req.bucket, req.object))
@@ -4,6 +4,8 @@ use serde::Deserialize;
 
 #[cfg(feature = "devtools")]
 use swissknife_devtools_sdk as devtools;
+#[cfg(any(feature = "github", feature = "gitlab"))]
+use swissknife_devtools_sdk::GitProvider;
 
 #[derive(Clone)]
 pub struct DevtoolsTools {
@@ -174,14 +176,15 @@ impl DevtoolsTools {
         let client = self.github.as_ref()
             .ok_or_else(|| "GitHub client not configured".to_string())?;
 
-        let issues = client.list_issues(
-            &req.owner,
-            &req.repo,
-            req.state.as_deref(),
-            req.per_page,
-        ).await.map_err(|e| e.to_string())?;
+        let options = devtools::ListOptions {
+            state: req.state,
+            per_page: req.per_page,
+            ..Default::default()
+        };
+        let issues = client.list_issues(&req.owner, &req.repo, &options).await
+            .map_err(|e| e.to_string())?;
 
-        serde_json::to_string_pretty(&issues).map_err(|e| e.to_string())
+        serde_json::to_string_pretty(&issues.items).map_err(|e| e.to_string())
     }
 
     #[cfg(feature = "github")]
@@ -193,13 +196,13 @@ impl DevtoolsTools {
         let client = self.github.as_ref()
             .ok_or_else(|| "GitHub client not configured".to_string())?;
 
-        let issue = client.create_issue(
+        let issue = client.create_issue_with_labels(
             &req.owner,
             &req.repo,
             &req.title,
             req.body.as_deref(),
-            req.labels.as_deref(),
-            req.assignees.as_deref(),
+            req.labels.as_deref().unwrap_or_default(),
+            req.assignees.as_deref().unwrap_or_default(),
         ).await.map_err(|e| e.to_string())?;
 
         serde_json::to_string_pretty(&issue).map_err(|e| e.to_string())
@@ -214,14 +217,15 @@ impl DevtoolsTools {
         let client = self.github.as_ref()
             .ok_or_else(|| "GitHub client not configured".to_string())?;
 
-        let prs = client.list_pull_requests(
-            &req.owner,
-            &req.repo,
-            req.state.as_deref(),
-            req.per_page,
-        ).await.map_err(|e| e.to_string())?;
+        let options = devtools::ListOptions {
+            state: req.state,
+            per_page: req.per_page,
+            ..Default::default()
+        };
+        let prs = client.list_pull_requests(&req.owner, &req.repo, &options).await
+            .map_err(|e| e.to_string())?;
 
-        serde_json::to_string_pretty(&prs).map_err(|e| e.to_string())
+        serde_json::to_string_pretty(&prs.items).map_err(|e| e.to_string())
     }
 
     #[cfg(feature = "github")]
@@ -254,7 +258,7 @@ impl DevtoolsTools {
         let client = self.github.as_ref()
             .ok_or_else(|| "GitHub client not configured".to_string())?;
 
-        let content = client.get_file_content(
+        let content = client.get_file(
             &req.owner,
             &req.repo,
             &req.path,
@@ -318,13 +322,15 @@ impl DevtoolsTools {
         let client = self.gitlab.as_ref()
             .ok_or_else(|| "GitLab client not configured".to_string())?;
 
-        let issues = client.list_issues(
-            &req.project_id,
-            req.state.as_deref(),
-            req.per_page,
-        ).await.map_err(|e| e.to_string())?;
+        let options = devtools::ListOptions {
+            state: req.state,
+            per_page: req.per_page,
+            ..Default::default()
+        };
+        let issues = client.list_issues("", &req.project_id, &options).await
+            .map_err(|e| e.to_string())?;
 
-        serde_json::to_string_pretty(&issues).map_err(|e| e.to_string())
+        serde_json::to_string_pretty(&issues.items).map_err(|e| e.to_string())
     }
 
     #[cfg(feature = "gitlab")]
@@ -336,11 +342,17 @@ impl DevtoolsTools {
         let client = self.gitlab.as_ref()
             .ok_or_else(|| "GitLab client not configured".to_string())?;
 
-        let issue = client.create_issue(
+        let labels: Vec<String> = req.labels.as_deref().unwrap_or_default()
+            .split(',')
+            .map(|l| l.trim().to_string())
+            .filter(|l| !l.is_empty())
+            .collect();
+        let issue = client.create_issue_with_labels(
+            "",
             &req.project_id,
             &req.title,
             req.description.as_deref(),
-            req.labels.as_deref(),
+            &labels,
         ).await.map_err(|e| e.to_string())?;
 
         serde_json::to_string_pretty(&issue).map_err(|e| e.to_string())
@@ -355,12 +367,14 @@ impl DevtoolsTools {
         let client = self.gitlab.as_ref()
             .ok_or_else(|| "GitLab client not configured".to_string())?;
 
-        let mrs = client.list_merge_requests(
-            &req.project_id,
-            req.state.as_deref(),
-            req.per_page,
-        ).await.map_err(|e| e.to_string())?;
+        let options = devtools::ListOptions {
+            state: req.state,
+            per_page: req.per_page,
+            ..Default::default()
+        };
+        let mrs = client.list_pull_requests("", &req.project_id, &options).await
+            .map_err(|e| e.to_string())?;
 
-        serde_json::to_string_pretty(&mrs).map_err(|e| e.to_string())
+        serde_json::to_string_pretty(&mrs.items).map_err(|e| e.to_string())
     }
 }
@@ -4,6 +4,8 @@ use serde::Deserialize;
 
 #[cfg(feature = "ecommerce")]
 use swissknife_ecommerce_sdk as ecommerce;
+#[cfg(any(feature = "shopify", feature = "woocommerce"))]
+use swissknife_ecommerce_sdk::EcommerceProvider;
 
 #[derive(Clone)]
 pub struct EcommerceTools {
@@ -137,10 +139,16 @@ impl EcommerceTools {
         let client = self.shopify.as_ref()
             .ok_or_else(|| "Shopify client not configured".to_string())?;
 
-        let products = client.list_products(req.limit, req.collection_id.as_deref()).await
-            .map_err(|e| e.to_string())?;
+        let filter = ecommerce::ProductFilter {
+            collection_id: req.collection_id,
+            ..Default::default()
+        };
+        let products = client.list_products(&filter, &ecommerce::ListOptions {
+            limit: req.limit,
+            ..Default::default()
+        }).await.map_err(|e| e.to_string())?;
 
-        serde_json::to_string_pretty(&products).map_err(|e| e.to_string())
+        serde_json::to_string_pretty(&products.items).map_err(|e| e.to_string())
     }
 
     #[cfg(feature = "shopify")]
@@ -167,13 +175,24 @@ impl EcommerceTools {
         let client = self.shopify.as_ref()
             .ok_or_else(|| "Shopify client not configured".to_string())?;
 
-        let product = client.create_product(
-            &req.title,
-            req.body_html.as_deref(),
-            req.vendor.as_deref(),
-            req.product_type.as_deref(),
-            req.tags.as_deref(),
-        ).await.map_err(|e| e.to_string())?;
+        let product = client.create_product(&ecommerce::Product {
+            id: String::new(),
+            title: req.title,
+            description: req.body_html,
+            handle: None,
+            status: ecommerce::ProductStatus::Draft,
+            vendor: req.vendor,
+            product_type: req.product_type,
+            tags: req.tags
+                .map(|t| t.split(',').map(|t| t.trim().to_string()).filter(|t| !t.is_empty()).collect())
+                .unwrap_or_default(),
+            variants: Vec::new(),
+            images: Vec::new(),
+            options: Vec::new(),
+            created_at: None,
+            updated_at: None,
+            extra: Default::default(),
+        }).await.map_err(|e| e.to_string())?;
 
         serde_json::to_string_pretty(&product).map_err(|e| e.to_string())
     }
@@ -187,10 +206,22 @@ impl EcommerceTools {
         let client = self.shopify.as_ref()
             .ok_or_else(|| "Shopify client not configured".to_string())?;
 
-        let orders = client.list_orders(req.limit, req.status.as_deref()).await
-            .map_err(|e| e.to_string())?;
-
-        serde_json::to_string_pretty(&orders).map_err(|e| e.to_string())
+        let status = match req.status.as_deref() {
+            None | Some("any") => None,
+            Some("open") => Some(ecommerce::OrderStatus::Open),
+            Some("closed") => Some(ecommerce::OrderStatus::Closed),
+            Some("cancelled") => Some(ecommerce::OrderStatus::Cancelled),
+            Some(other) => return Err(format!("Unknown order status: {}", other)),
+        };
+        let orders = client.list_orders(&ecommerce::OrderFilter {
+            status,
+            ..Default::default()
+        }, &ecommerce::ListOptions {
+            limit: req.limit,
+            ..Default::default()
+        }).await.map_err(|e| e.to_string())?;
+
+        serde_json::to_string_pretty(&orders.items).map_err(|e| e.to_string())
     }
 
     #[cfg(feature = "shopify")]
@@ -217,10 +248,12 @@ impl EcommerceTools {
         let client = self.shopify.as_ref()
             .ok_or_else(|| "Shopify client not configured".to_string())?;
 
-        let customers = client.list_customers(req.limit).await
-            .map_err(|e| e.to_string())?;
+        let customers = client.list_customers(&ecommerce::ListOptions {
+            limit: req.limit,
+            ..Default::default()
+        }).await.map_err(|e| e.to_string())?;
 
-        serde_json::to_string_pretty(&customers).map_err(|e| e.to_string())
+        serde_json::to_string_pretty(&customers.items).map_err(|e| e.to_string())
     }
 
     #[cfg(feature = "woocommerce")]
@@ -232,10 +265,15 @@ impl EcommerceTools {
         let client = self.woocommerce.as_ref()
             .ok_or_else(|| "WooCommerce client not configured".to_string())?;
 
-        let products = client.list_products(req.per_page, req.category.as_deref()).await
-            .map_err(|e| e.to_string())?;
+        let products = client.list_products(&ecommerce::ProductFilter {
+            collection_id: req.category,
+            ..Default::default()
+        }, &ecommerce::ListOptions {
+            limit: req.per_page,
+            ..Default::default()
+        }).await.map_err(|e| e.to_string())?;
 
-        serde_json::to_string_pretty(&products).map_err(|e| e.to_string())
+        serde_json::to_string_pretty(&products.items).map_err(|e| e.to_string())
     }
 
     #[cfg(feature = "woocommerce")]
@@ -247,7 +285,7 @@ impl EcommerceTools {
         let client = self.woocommerce.as_ref()
             .ok_or_else(|| "WooCommerce client not configured".to_string())?;
 
-        let product = client.get_product(req.product_id).await
+        let product = client.get_product(&req.product_id.to_string()).await
             .map_err(|e| e.to_string())?;
 
         serde_json::to_string_pretty(&product).map_err(|e| e.to_string())
@@ -262,13 +300,42 @@ impl EcommerceTools {
         let client = self.woocommerce.as_ref()
             .ok_or_else(|| "WooCommerce client not configured".to_string())?;
 
-        let product = client.create_product(
-            &req.name,
-            req.regular_price.as_deref(),
-            req.description.as_deref(),
-            req.short_description.as_deref(),
-            req.sku.as_deref(),
-        ).await.map_err(|e| e.to_string())?;
+        let price = match req.regular_price.as_deref() {
+            Some(price) => price.parse::<f64>().map_err(|_| format!("Invalid price: {}", price))?,
+            None => 0.0,
+        };
+        let mut extra = std::collections::HashMap::new();
+        if let Some(short_description) = req.short_description {
+            extra.insert("short_description".to_string(), serde_json::Value::String(short_description));
+        }
+
+        let product = client.create_product(&ecommerce::Product {
+            id: String::new(),
+            title: req.name,
+            description: req.description,
+            handle: None,
+            status: ecommerce::ProductStatus::Active,
+            vendor: None,
+            product_type: None,
+            tags: Vec::new(),
+            variants: vec![ecommerce::ProductVariant {
+                id: String::new(),
+                title: "Default".to_string(),
+                sku: req.sku,
+                price,
+                compare_at_price: None,
+                inventory_quantity: None,
+                weight: None,
+                weight_unit: None,
+                options: Default::default(),
+                barcode: None,
+            }],
+            images: Vec::new(),
+            options: Vec::new(),
+            created_at: None,
+            updated_at: None,
+            extra,
+        }).await.map_err(|e| e.to_string())?;
 
         serde_json::to_string_pretty(&product).map_err(|e| e.to_string())
     }
@@ -282,10 +349,24 @@ impl EcommerceTools {
         let client = self.woocommerce.as_ref()
             .ok_or_else(|| "WooCommerce client not configured".to_string())?;
 
-        let orders = client.list_orders(req.per_page, req.status.as_deref()).await
-            .map_err(|e| e.to_string())?;
-
-        serde_json::to_string_pretty(&orders).map_err(|e| e.to_string())
+        let filter = match req.status.as_deref() {
+            None | Some("any") => ecommerce::OrderFilter::default(),
+            Some(status) => {
+                let (status, financial_status, fulfillment_status) = ecommerce::woocommerce::order_states(status);
+                ecommerce::OrderFilter {
+                    status: Some(status),
+                    financial_status: Some(financial_status),
+                    fulfillment_status,
+                    ..Default::default()
+                }
+            }
+        };
+        let orders = client.list_orders(&filter, &ecommerce::ListOptions {
+            limit: req.per_page,
+            ..Default::default()
+        }).await.map_err(|e| e.to_string())?;
+
+        serde_json::to_string_pretty(&orders.items).map_err(|e| e.to_string())
     }
 
     #[cfg(feature = "woocommerce")]
@@ -297,7 +378,7 @@ impl EcommerceTools {
         let client = self.woocommerce.as_ref()
             .ok_or_else(|| "WooCommerce client not configured".to_string())?;
 
-        let order = client.get_order(req.order_id).await
+        let order = client.get_order(&req.order_id.to_string()).await
             .map_err(|e| e.to_string())?;
 
         serde_json::to_string_pretty(&order).map_err(|e| e.to_string())
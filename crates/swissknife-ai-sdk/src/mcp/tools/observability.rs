@@ -4,6 +4,12 @@ use serde::Deserialize;
 
 #[cfg(feature = "observability")]
 use swissknife_observability_sdk as obs;
+#[cfg(feature = "datadog")]
+use swissknife_observability_sdk::MetricsProvider;
+#[cfg(any(feature = "posthog", feature = "mixpanel"))]
+use swissknife_observability_sdk::AnalyticsProvider;
+#[cfg(feature = "sentry")]
+use swissknife_observability_sdk::ErrorTrackingProvider;
 
 #[derive(Clone)]
 pub struct ObservabilityTools {
@@ -152,12 +158,28 @@ impl ObservabilityTools {
         let client = self.datadog.as_ref()
             .ok_or_else(|| "Datadog client not configured".to_string())?;
 
-        client.send_metric(
-            &req.metric,
-            req.value,
-            req.metric_type.as_deref(),
-            req.tags.as_deref(),
-        ).await.map_err(|e| e.to_string())?;
+        let metric_type = match req.metric_type.as_deref() {
+            None | Some("gauge") => obs::MetricType::Gauge,
+            Some("count") | Some("counter") => obs::MetricType::Counter,
+            Some("histogram") => obs::MetricType::Histogram,
+            Some("distribution") => obs::MetricType::Distribution,
+            Some(other) => return Err(format!("Unsupported metric type: {}", other)),
+        };
+        let tags = req.tags.unwrap_or_default().into_iter()
+            .map(|tag| match tag.split_once(':') {
+                Some((k, v)) => (k.to_string(), v.to_string()),
+                None => (tag, String::new()),
+            })
+            .collect();
+        let metric = obs::Metric {
+            name: req.metric,
+            value: req.value,
+            timestamp: None,
+            tags,
+            metric_type,
+        };
+
+        client.submit_metrics(&[metric]).await.map_err(|e| e.to_string())?;
 
         Ok("Metric sent successfully".to_string())
     }
@@ -190,7 +212,15 @@ impl ObservabilityTools {
         let client = self.datadog.as_ref()
             .ok_or_else(|| "Datadog client not configured".to_string())?;
 
-        let result = client.query_metrics(&req.query, req.from_ts, req.to_ts).await
+        let query = obs::MetricQuery {
+            query: req.query,
+            time_range: obs::TimeRange {
+                start: chrono::DateTime::from_timestamp(req.from_ts, 0),
+                end: chrono::DateTime::from_timestamp(req.to_ts, 0),
+            },
+            step: None,
+        };
+        let result = client.query_metrics(&query).await
             .map_err(|e| e.to_string())?;
 
         serde_json::to_string_pretty(&result).map_err(|e| e.to_string())
@@ -205,7 +235,16 @@ impl ObservabilityTools {
         let client = self.posthog.as_ref()
             .ok_or_else(|| "PostHog client not configured".to_string())?;
 
-        client.capture(&req.distinct_id, &req.event, req.properties).await
+        let event = obs::Event {
+            name: req.event,
+            timestamp: None,
+            distinct_id: Some(req.distinct_id),
+            properties: req.properties
+                .and_then(|p| serde_json::from_value(p).ok())
+                .unwrap_or_default(),
+        };
+
+        client.track(&event).await
             .map_err(|e| e.to_string())?;
 
         Ok("Event captured successfully".to_string())
@@ -220,7 +259,13 @@ impl ObservabilityTools {
         let client = self.posthog.as_ref()
             .ok_or_else(|| "PostHog client not configured".to_string())?;
 
-        client.identify(&req.distinct_id, req.properties).await
+        let profile = obs::UserProfile {
+            distinct_id: req.distinct_id,
+            properties: serde_json::from_value(req.properties)
+                .map_err(|e| e.to_string())?,
+        };
+
+        client.identify(&profile).await
             .map_err(|e| e.to_string())?;
 
         Ok("User identified successfully".to_string())
@@ -253,11 +298,32 @@ impl ObservabilityTools {
         let client = self.sentry.as_ref()
             .ok_or_else(|| "Sentry client not configured".to_string())?;
 
-        let event_id = client.capture_message(
-            &req.message,
-            req.level.as_deref(),
-            req.tags,
-        ).await.map_err(|e| e.to_string())?;
+        let level = match req.level.as_deref() {
+            None | Some("info") => obs::ErrorLevel::Info,
+            Some("debug") => obs::ErrorLevel::Debug,
+            Some("warning") | Some("warn") => obs::ErrorLevel::Warning,
+            Some("error") => obs::ErrorLevel::Error,
+            Some("fatal") => obs::ErrorLevel::Fatal,
+            Some(other) => return Err(format!("Unsupported level: {}", other)),
+        };
+        let tags = req.tags
+            .and_then(|t| serde_json::from_value(t).ok())
+            .unwrap_or_default();
+        let event = obs::ErrorEvent {
+            id: None,
+            message: req.message,
+            level,
+            platform: None,
+            timestamp: None,
+            exception: None,
+            tags,
+            extra: Default::default(),
+            user: None,
+            contexts: Default::default(),
+        };
+
+        let event_id = client.capture_error(&event).await
+            .map_err(|e| e.to_string())?;
 
         serde_json::to_string_pretty(&serde_json::json!({
             "event_id": event_id
@@ -273,7 +339,7 @@ impl ObservabilityTools {
         let client = self.sentry.as_ref()
             .ok_or_else(|| "Sentry client not configured".to_string())?;
 
-        let issues = client.list_issues(
+        let issues = client.list_project_issues(
             &req.project_slug,
             req.query.as_deref(),
             req.limit,
@@ -291,7 +357,16 @@ impl ObservabilityTools {
         let client = self.mixpanel.as_ref()
             .ok_or_else(|| "Mixpanel client not configured".to_string())?;
 
-        client.track(&req.event, &req.distinct_id, req.properties).await
+        let event = obs::Event {
+            name: req.event,
+            timestamp: None,
+            distinct_id: Some(req.distinct_id),
+            properties: req.properties
+                .and_then(|p| serde_json::from_value(p).ok())
+                .unwrap_or_default(),
+        };
+
+        client.track(&event).await
             .map_err(|e| e.to_string())?;
 
         Ok("Event tracked successfully".to_string())
@@ -4,6 +4,8 @@ use serde::Deserialize;
 
 #[cfg(feature = "pm")]
 use swissknife_pm_sdk as pm;
+#[cfg(any(feature = "linear", feature = "jira", feature = "asana", feature = "trello", feature = "clickup"))]
+use swissknife_pm_sdk::ProjectManagementProvider;
 
 #[derive(Clone)]
 pub struct PmTools {
@@ -199,13 +201,13 @@ impl PmTools {
         let client = self.linear.as_ref()
             .ok_or_else(|| "Linear client not configured".to_string())?;
 
-        let issue = client.create_issue(
-            &req.title,
-            &req.team_id,
-            req.description.as_deref(),
-            req.priority,
-            req.assignee_id.as_deref(),
-        ).await.map_err(|e| e.to_string())?;
+        let issue = client.create_issue(&req.team_id, &pm::CreateIssue {
+            title: req.title,
+            description: req.description,
+            priority: req.priority.map(pm::linear::priority_from_value),
+            assignee_id: req.assignee_id,
+            ..Default::default()
+        }).await.map_err(|e| e.to_string())?;
 
         serde_json::to_string_pretty(&issue).map_err(|e| e.to_string())
     }
@@ -234,10 +236,11 @@ impl PmTools {
         let client = self.linear.as_ref()
             .ok_or_else(|| "Linear client not configured".to_string())?;
 
-        let issues = client.search_issues(&req.query, req.limit).await
+        let options = pm::ListOptions { per_page: req.limit, ..Default::default() };
+        let issues = client.search_issues(&req.query, &options).await
             .map_err(|e| e.to_string())?;
 
-        serde_json::to_string_pretty(&issues).map_err(|e| e.to_string())
+        serde_json::to_string_pretty(&issues.items).map_err(|e| e.to_string())
     }
 
     #[cfg(feature = "jira")]
@@ -249,14 +252,14 @@ impl PmTools {
         let client = self.jira.as_ref()
             .ok_or_else(|| "Jira client not configured".to_string())?;
 
-        let issue = client.create_issue(
-            &req.project_key,
-            &req.summary,
-            &req.issue_type,
-            req.description.as_deref(),
-            req.assignee.as_deref(),
-            req.priority.as_deref(),
-        ).await.map_err(|e| e.to_string())?;
+        let issue = client.create_issue(&req.project_key, &pm::CreateIssue {
+            title: req.summary,
+            description: req.description,
+            issue_type: Some(pm::jira::issue_type_from_name(&req.issue_type)),
+            priority: req.priority.as_deref().map(pm::jira::priority_from_name),
+            assignee_id: req.assignee,
+            ..Default::default()
+        }).await.map_err(|e| e.to_string())?;
 
         serde_json::to_string_pretty(&issue).map_err(|e| e.to_string())
     }
@@ -315,13 +318,18 @@ impl PmTools {
         let client = self.asana.as_ref()
             .ok_or_else(|| "Asana client not configured".to_string())?;
 
-        let task = client.create_task(
-            &req.project_id,
-            &req.name,
-            req.notes.as_deref(),
-            req.assignee.as_deref(),
-            req.due_on.as_deref(),
-        ).await.map_err(|e| e.to_string())?;
+        let due_date = req.due_on.as_deref()
+            .map(|d| chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d"))
+            .transpose()
+            .map_err(|e| format!("Invalid due_on: {}", e))?;
+
+        let task = client.create_issue(&req.project_id, &pm::CreateIssue {
+            title: req.name,
+            description: req.notes,
+            assignee_id: req.assignee,
+            due_date,
+            ..Default::default()
+        }).await.map_err(|e| e.to_string())?;
 
         serde_json::to_string_pretty(&task).map_err(|e| e.to_string())
     }
@@ -335,7 +343,7 @@ impl PmTools {
         let client = self.asana.as_ref()
             .ok_or_else(|| "Asana client not configured".to_string())?;
 
-        let task = client.get_task(&req.task_id).await
+        let task = client.get_issue(&req.task_id).await
             .map_err(|e| e.to_string())?;
 
         serde_json::to_string_pretty(&task).map_err(|e| e.to_string())
@@ -350,10 +358,12 @@ impl PmTools {
         let client = self.asana.as_ref()
             .ok_or_else(|| "Asana client not configured".to_string())?;
 
-        let tasks = client.list_tasks(&req.project_id, req.limit).await
-            .map_err(|e| e.to_string())?;
+        let tasks = client.list_issues(&req.project_id, &pm::IssueFilter::default(), &pm::ListOptions {
+            per_page: req.limit,
+            ..Default::default()
+        }).await.map_err(|e| e.to_string())?;
 
-        serde_json::to_string_pretty(&tasks).map_err(|e| e.to_string())
+        serde_json::to_string_pretty(&tasks.items).map_err(|e| e.to_string())
     }
 
     #[cfg(feature = "trello")]
@@ -384,7 +394,7 @@ impl PmTools {
         let client = self.trello.as_ref()
             .ok_or_else(|| "Trello client not configured".to_string())?;
 
-        let card = client.get_card(&req.card_id).await
+        let card = client.get_issue(&req.card_id).await
             .map_err(|e| e.to_string())?;
 
         serde_json::to_string_pretty(&card).map_err(|e| e.to_string())
@@ -414,13 +424,15 @@ impl PmTools {
         let client = self.clickup.as_ref()
             .ok_or_else(|| "ClickUp client not configured".to_string())?;
 
-        let task = client.create_task(
-            &req.list_id,
-            &req.name,
-            req.description.as_deref(),
-            req.priority,
-            req.due_date,
-        ).await.map_err(|e| e.to_string())?;
+        let task = client.create_issue(&req.list_id, &pm::CreateIssue {
+            title: req.name,
+            description: req.description,
+            priority: req.priority.map(pm::clickup::priority_from_value),
+            due_date: req.due_date
+                .and_then(chrono::DateTime::from_timestamp_millis)
+                .map(|d| d.date_naive()),
+            ..Default::default()
+        }).await.map_err(|e| e.to_string())?;
 
         serde_json::to_string_pretty(&task).map_err(|e| e.to_string())
     }
@@ -434,7 +446,7 @@ impl PmTools {
         let client = self.clickup.as_ref()
             .ok_or_else(|| "ClickUp client not configured".to_string())?;
 
-        let task = client.get_task(&req.task_id).await
+        let task = client.get_issue(&req.task_id).await
             .map_err(|e| e.to_string())?;
 
         serde_json::to_string_pretty(&task).map_err(|e| e.to_string())
@@ -5,6 +5,9 @@ use serde::Deserialize;
 #[cfg(feature = "vectordb")]
 use swissknife_vectordb_sdk as vectordb;
 
+#[cfg(any(feature = "pinecone", feature = "qdrant"))]
+use vectordb::{DeleteOptions, QueryOptions, UpsertOptions, VectorDatabaseProvider};
+
 #[derive(Clone)]
 pub struct VectorDbTools {
     #[cfg(feature = "pinecone")]
@@ -64,6 +67,7 @@ impl Default for VectorDbTools {
 
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct PineconeUpsertRequest {
+    pub index: String,
     pub namespace: String,
     pub vectors: Vec<PineconeVector>,
 }
@@ -78,6 +82,7 @@ pub struct PineconeVector {
 
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct PineconeQueryRequest {
+    pub index: String,
     pub namespace: String,
     pub vector: Vec<f32>,
     pub top_k: u32,
@@ -87,6 +92,7 @@ pub struct PineconeQueryRequest {
 
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct PineconeDeleteRequest {
+    pub index: String,
     pub namespace: String,
     pub ids: Vec<String>,
 }
@@ -171,18 +177,23 @@ impl VectorDbTools {
         let client = self.pinecone.as_ref()
             .ok_or_else(|| "Pinecone client not configured".to_string())?;
 
-        let vectors: Vec<_> = req.vectors.iter().map(|v| {
-            vectordb::pinecone::Vector {
-                id: v.id.clone(),
-                values: v.values.clone(),
-                metadata: v.metadata.clone(),
+        let vectors: Vec<_> = req.vectors.into_iter().map(|v| {
+            vectordb::Vector {
+                id: v.id,
+                values: v.values,
+                metadata: v.metadata.and_then(|m| serde_json::from_value(m).ok()),
+                sparse_values: None,
             }
         }).collect();
 
-        let result = client.upsert(&req.namespace, &vectors).await
+        let options = UpsertOptions {
+            namespace: Some(req.namespace),
+        };
+
+        let result = client.upsert(&req.index, &vectors, &options).await
             .map_err(|e| e.to_string())?;
 
-        serde_json::to_string_pretty(&result).map_err(|e| e.to_string())
+        Ok(format!("Upserted {} vectors", result.upserted_count))
     }
 
     #[cfg(feature = "pinecone")]
@@ -194,12 +205,15 @@ impl VectorDbTools {
         let client = self.pinecone.as_ref()
             .ok_or_else(|| "Pinecone client not configured".to_string())?;
 
-        let result = client.query(
-            &req.namespace,
-            &req.vector,
-            req.top_k,
-            req.include_metadata.unwrap_or(true),
-        ).await.map_err(|e| e.to_string())?;
+        let options = QueryOptions {
+            top_k: req.top_k,
+            include_metadata: req.include_metadata.unwrap_or(true),
+            namespace: Some(req.namespace),
+            ..Default::default()
+        };
+
+        let result = client.query(&req.index, &req.vector, &options).await
+            .map_err(|e| e.to_string())?;
 
         serde_json::to_string_pretty(&result).map_err(|e| e.to_string())
     }
@@ -213,7 +227,13 @@ impl VectorDbTools {
         let client = self.pinecone.as_ref()
             .ok_or_else(|| "Pinecone client not configured".to_string())?;
 
-        client.delete(&req.namespace, &req.ids).await
+        let ids: Vec<&str> = req.ids.iter().map(|id| id.as_str()).collect();
+        let options = DeleteOptions {
+            namespace: Some(req.namespace),
+            ..Default::default()
+        };
+
+        client.delete(&req.index, &ids, &options).await
             .map_err(|e| e.to_string())?;
 
         Ok(format!("Deleted {} vectors", req.ids.len()))
@@ -228,18 +248,19 @@ impl VectorDbTools {
         let client = self.qdrant.as_ref()
             .ok_or_else(|| "Qdrant client not configured".to_string())?;
 
-        let points: Vec<_> = req.points.iter().map(|p| {
-            vectordb::qdrant::Point {
-                id: p.id.clone(),
-                vector: p.vector.clone(),
-                payload: p.payload.clone(),
+        let points: Vec<_> = req.points.into_iter().map(|p| {
+            vectordb::Vector {
+                id: p.id,
+                values: p.vector,
+                metadata: p.payload.and_then(|m| serde_json::from_value(m).ok()),
+                sparse_values: None,
             }
         }).collect();
 
-        let result = client.upsert(&req.collection, &points).await
+        let result = client.upsert(&req.collection, &points, &UpsertOptions::default()).await
             .map_err(|e| e.to_string())?;
 
-        serde_json::to_string_pretty(&result).map_err(|e| e.to_string())
+        Ok(format!("Upserted {} points", result.upserted_count))
     }
 
     #[cfg(feature = "qdrant")]
@@ -251,7 +272,16 @@ impl VectorDbTools {
         let client = self.qdrant.as_ref()
             .ok_or_else(|| "Qdrant client not configured".to_string())?;
 
-        let result = client.search(&req.collection, &req.vector, req.limit, req.filter).await
+        let filter = req.filter.map(serde_json::from_value).transpose()
+            .map_err(|e| format!("Invalid filter: {}", e))?;
+        let options = QueryOptions {
+            top_k: req.limit,
+            include_metadata: true,
+            filter,
+            ..Default::default()
+        };
+
+        let result = client.query(&req.collection, &req.vector, &options).await
             .map_err(|e| e.to_string())?;
 
         serde_json::to_string_pretty(&result).map_err(|e| e.to_string())
@@ -266,7 +296,9 @@ impl VectorDbTools {
         let client = self.qdrant.as_ref()
             .ok_or_else(|| "Qdrant client not configured".to_string())?;
 
-        client.delete(&req.collection, &req.ids).await
+        let ids: Vec<&str> = req.ids.iter().map(|id| id.as_str()).collect();
+
+        client.delete(&req.collection, &ids, &DeleteOptions::default()).await
             .map_err(|e| e.to_string())?;
 
         Ok(format!("Deleted {} points", req.ids.len()))
@@ -346,7 +378,7 @@ impl VectorDbTools {
         let client = self.chroma.as_ref()
             .ok_or_else(|| "ChromaDB client not configured".to_string())?;
 
-        let result = client.query(&req.collection, &req.query_embeddings, req.n_results).await
+        let result = client.query_embeddings(&req.collection, &req.query_embeddings, req.n_results).await
             .map_err(|e| e.to_string())?;
 
         serde_json::to_string_pretty(&result).map_err(|e| e.to_string())
@@ -0,0 +1,568 @@
+#![cfg(feature = "agent")]
+
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use serde_json::json;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use swissknife_ai_sdk::agent::{Agent, AgentEvent, Approval, StopReason};
+use swissknife_ai_sdk::llm::{
+    ChatChoice, ChatMessage, ChatProvider, ChatRequest, ChatResponse, ChatStreamEvent, ChatStreamResponse,
+    FunctionCall, FunctionCallDelta, MessageContent, MessageRole, StreamDelta, ToolCall, ToolCallDelta, Usage,
+};
+use swissknife_ai_sdk::{Error, ParameterSchema, Result, Tool, ToolOutput, ToolRegistry, ToolSpec};
+
+// Replays canned responses and records every request it receives.
+#[derive(Clone, Default)]
+struct ScriptedProvider {
+    responses: Arc<Mutex<VecDeque<ChatResponse>>>,
+    requests: Arc<Mutex<Vec<ChatRequest>>>,
+}
+
+impl ScriptedProvider {
+    fn new(responses: Vec<ChatResponse>) -> Self {
+        Self {
+            responses: Arc::new(Mutex::new(responses.into())),
+            requests: Arc::default(),
+        }
+    }
+
+    fn next(&self, request: &ChatRequest) -> Result<ChatResponse> {
+        self.requests.lock().unwrap().push(request.clone());
+        self.responses
+            .lock()
+            .unwrap()
+            .pop_front()
+            .ok_or_else(|| Error::Provider("script exhausted".to_string()))
+    }
+}
+
+#[async_trait]
+impl ChatProvider for ScriptedProvider {
+    async fn chat(&self, request: &ChatRequest) -> Result<ChatResponse> {
+        self.next(request)
+    }
+
+    async fn chat_stream(&self, request: &ChatRequest) -> Result<ChatStreamResponse> {
+        let response = self.next(request)?;
+        let message = &response.choices[0].message;
+        let mut events = Vec::new();
+
+        for word in response.thinking.iter().flat_map(|t| t.split_inclusive(' ')) {
+            let mut event = delta(None, None);
+            event.delta.as_mut().unwrap().thinking = Some(word.to_string());
+            events.push(event);
+        }
+        if let MessageContent::Text(text) = &message.content {
+            for word in text.split_inclusive(' ') {
+                events.push(delta(Some(word.to_string()), None));
+            }
+        }
+        for (index, call) in message.tool_calls.iter().flatten().enumerate() {
+            let (head, tail) = call.function.arguments.split_at(call.function.arguments.len() / 2);
+            for (i, part) in [head, tail].into_iter().enumerate() {
+                events.push(delta(
+                    None,
+                    Some(ToolCallDelta {
+                        index: index as u32,
+                        id: (i == 0).then(|| call.id.clone()),
+                        function: Some(FunctionCallDelta {
+                            name: (i == 0).then(|| call.function.name.clone()),
+                            arguments: Some(part.to_string()),
+                        }),
+                    }),
+                ));
+            }
+        }
+        events.push(ChatStreamEvent {
+            id: Some(response.id.clone()),
+            delta: None,
+            finish_reason: Some("stop".to_string()),
+            usage: response.usage.clone(),
+        });
+
+        Ok(futures_util::stream::iter(events.into_iter().map(Ok)).boxed())
+    }
+}
+
+fn delta(content: Option<String>, call: Option<ToolCallDelta>) -> ChatStreamEvent {
+    ChatStreamEvent {
+        id: None,
+        delta: Some(StreamDelta {
+            role: None,
+            content,
+            tool_calls: call.map(|c| vec![c]),
+            thinking: None,
+        }),
+        finish_reason: None,
+        usage: None,
+    }
+}
+
+fn response(content: &str, calls: Vec<ToolCall>, tokens: u32) -> ChatResponse {
+    let mut message = ChatMessage::assistant(content);
+    message.tool_calls = (!calls.is_empty()).then_some(calls);
+    ChatResponse {
+        id: "resp".to_string(),
+        model: "test-model".to_string(),
+        choices: vec![ChatChoice {
+            index: 0,
+            message,
+            finish_reason: Some("stop".to_string()),
+        }],
+        usage: Some(Usage {
+            prompt_tokens: tokens / 2,
+            completion_tokens: tokens / 2,
+            total_tokens: tokens,
+        }),
+        thinking: None,
+    }
+}
+
+fn call(id: &str, name: &str, arguments: serde_json::Value) -> ToolCall {
+    ToolCall {
+        id: id.to_string(),
+        call_type: "function".to_string(),
+        function: FunctionCall {
+            name: name.to_string(),
+            arguments: arguments.to_string(),
+        },
+    }
+}
+
+struct EchoTool;
+
+#[async_trait]
+impl Tool for EchoTool {
+    fn definition(&self) -> ToolSpec {
+        ToolSpec::new("echo", "Echo Tool", "Returns the input message", "utility")
+            .with_param("message", ParameterSchema::string("The message to echo").required())
+    }
+
+    async fn execute(&self, params: HashMap<String, serde_json::Value>) -> Result<ToolOutput> {
+        let message = params
+            .get("message")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| Error::MissingParameter("message".to_string()))?;
+        Ok(ToolOutput::success(json!(message)))
+    }
+}
+
+struct SleepTool;
+
+#[async_trait]
+impl Tool for SleepTool {
+    fn definition(&self) -> ToolSpec {
+        ToolSpec::new("sleep", "Sleep Tool", "Waits before answering", "utility")
+    }
+
+    async fn execute(&self, _params: HashMap<String, serde_json::Value>) -> Result<ToolOutput> {
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        Ok(ToolOutput::success(json!("done")))
+    }
+}
+
+fn registry() -> ToolRegistry {
+    let mut registry = ToolRegistry::new();
+    registry.register(EchoTool).register(SleepTool);
+    registry
+}
+
+fn text(message: &ChatMessage) -> &str {
+    match &message.content {
+        MessageContent::Text(text) => text,
+        MessageContent::Parts(_) => panic!("unexpected parts"),
+    }
+}
+
+#[tokio::test]
+async fn test_agent_runs_tools_until_answer() {
+    let provider = ScriptedProvider::new(vec![
+        response("", vec![call("call_1", "echo", json!({ "message": "hello" }))], 10),
+        response("The tool said hello", vec![], 20),
+    ]);
+    let agent = Agent::new(provider.clone(), "test-model")
+        .with_system_prompt("Be brief")
+        .with_tools(registry());
+
+    let outcome = agent.run(vec![ChatMessage::user("Say hello")]).await.unwrap();
+
+    assert_eq!(outcome.stop_reason, StopReason::Completed);
+    assert_eq!(outcome.iterations, 2);
+    assert_eq!(outcome.content.as_deref(), Some("The tool said hello"));
+    assert_eq!(outcome.usage.total_tokens, 30);
+
+    let roles: Vec<MessageRole> = outcome.messages.iter().map(|m| m.role).collect();
+    assert_eq!(
+        roles,
+        vec![MessageRole::System, MessageRole::User, MessageRole::Assistant, MessageRole::Tool, MessageRole::Assistant]
+    );
+    assert_eq!(outcome.messages[3].tool_call_id.as_deref(), Some("call_1"));
+    assert_eq!(text(&outcome.messages[3]), "hello");
+
+    let requests = provider.requests.lock().unwrap();
+    let tools = requests[0].tools.as_ref().unwrap();
+    assert_eq!(tools.len(), 2);
+    let echo = tools.iter().find(|t| t.function.name == "echo").unwrap();
+    assert_eq!(echo.function.parameters["required"], json!(["message"]));
+    assert_eq!(requests[1].messages.len(), 4);
+}
+
+#[tokio::test]
+async fn test_agent_runs_tool_calls_concurrently() {
+    let provider = ScriptedProvider::new(vec![
+        response(
+            "",
+            vec![
+                call("call_1", "sleep", json!({})),
+                call("call_2", "sleep", json!({})),
+                call("call_3", "sleep", json!({})),
+            ],
+            10,
+        ),
+        response("done", vec![], 10),
+    ]);
+    let agent = Agent::new(provider, "test-model").with_tools(registry());
+
+    let start = Instant::now();
+    let outcome = agent.run(vec![ChatMessage::user("wait")]).await.unwrap();
+    assert!(start.elapsed() < Duration::from_millis(500));
+
+    let ids: Vec<_> = outcome.messages.iter().filter_map(|m| m.tool_call_id.as_deref()).collect();
+    assert_eq!(ids, vec!["call_1", "call_2", "call_3"]);
+}
+
+#[tokio::test]
+async fn test_agent_approval_can_edit_and_deny() {
+    let provider = ScriptedProvider::new(vec![
+        response(
+            "",
+            vec![
+                call("call_1", "echo", json!({ "message": "secret" })),
+                call("call_2", "sleep", json!({})),
+            ],
+            10,
+        ),
+        response("ok", vec![], 10),
+    ]);
+    let agent = Agent::new(provider, "test-model")
+        .with_tools(registry())
+        .with_approval(|call: &ToolCall| match call.function.name.as_str() {
+            "echo" => Approval::Edit(json!({ "message": "redacted" })),
+            _ => Approval::Deny("not allowed".to_string()),
+        });
+
+    let outcome = agent.run(vec![ChatMessage::user("go")]).await.unwrap();
+
+    let assistant = &outcome.messages[1];
+    let calls = assistant.tool_calls.as_ref().unwrap();
+    assert_eq!(calls[0].function.arguments, json!({ "message": "redacted" }).to_string());
+    assert_eq!(text(&outcome.messages[2]), "redacted");
+    assert!(text(&outcome.messages[3]).contains("not allowed"));
+}
+
+#[tokio::test]
+async fn test_agent_reports_tool_errors_to_model() {
+    let provider = ScriptedProvider::new(vec![
+        response("", vec![call("call_1", "missing", json!({}))], 10),
+        response("sorry", vec![], 10),
+    ]);
+    let agent = Agent::new(provider, "test-model").with_tools(registry());
+
+    let outcome = agent.run(vec![ChatMessage::user("go")]).await.unwrap();
+    assert!(text(&outcome.messages[2]).starts_with("Error:"));
+    assert_eq!(outcome.content.as_deref(), Some("sorry"));
+}
+
+#[tokio::test]
+async fn test_agent_rejects_malformed_tool_arguments() {
+    let mut malformed = call("call_1", "echo", json!({}));
+    malformed.function.arguments = "{\"message\": ".to_string();
+    let provider = ScriptedProvider::new(vec![
+        response("", vec![malformed, call("call_2", "echo", json!(["hi"]))], 10),
+        response("retrying", vec![], 10),
+    ]);
+    let agent = Agent::new(provider, "test-model").with_tools(registry());
+
+    let events: Vec<AgentEvent> = agent.stream(vec![ChatMessage::user("go")]).collect().await;
+    assert!(!events.iter().any(|e| matches!(e, AgentEvent::ToolStart { .. })));
+    assert!(events
+        .iter()
+        .any(|e| matches!(e, AgentEvent::ToolFinish { is_error: true, output, .. }
+            if output.contains("invalid tool arguments"))));
+
+    match events.last().unwrap() {
+        AgentEvent::Finished { outcome } => {
+            assert!(text(&outcome.messages[2]).starts_with("Error: invalid tool arguments"));
+            assert_eq!(text(&outcome.messages[3]), "Error: invalid tool arguments: arguments must be a JSON object");
+        }
+        other => panic!("unexpected final event: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_agent_stops_at_budgets() {
+    let looping = || {
+        ScriptedProvider::new(
+            (0..5)
+                .map(|i| response("", vec![call(&format!("call_{}", i), "echo", json!({ "message": "again" }))], 100))
+                .collect(),
+        )
+    };
+
+    let agent = Agent::new(looping(), "test-model").with_tools(registry()).with_max_iterations(3);
+    let outcome = agent.run(vec![ChatMessage::user("loop")]).await.unwrap();
+    assert_eq!(outcome.stop_reason, StopReason::MaxIterations);
+    assert_eq!(outcome.iterations, 3);
+    assert_eq!(outcome.content, None);
+
+    let agent = Agent::new(looping(), "test-model").with_tools(registry()).with_token_budget(150);
+    let outcome = agent.run(vec![ChatMessage::user("loop")]).await.unwrap();
+    assert_eq!(outcome.stop_reason, StopReason::TokenBudget);
+    assert_eq!(outcome.iterations, 2);
+    assert_eq!(outcome.usage.total_tokens, 200);
+}
+
+#[tokio::test]
+async fn test_agent_event_stream() {
+    let provider = ScriptedProvider::new(vec![
+        response("Let me check", vec![call("call_1", "echo", json!({ "message": "hi" }))], 10),
+        response("It says hi", vec![], 10),
+    ]);
+    let agent = Agent::new(provider, "test-model").with_tools(registry()).with_streaming(true);
+
+    let events: Vec<AgentEvent> = agent.stream(vec![ChatMessage::user("check")]).collect().await;
+
+    let text: String = events
+        .iter()
+        .filter_map(|e| match e {
+            AgentEvent::TextDelta { text } => Some(text.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(text, "Let me checkIt says hi");
+
+    let start = events.iter().position(|e| matches!(e, AgentEvent::ToolStart { .. })).unwrap();
+    let finish = events.iter().position(|e| matches!(e, AgentEvent::ToolFinish { .. })).unwrap();
+    assert!(start < finish);
+    match &events[start] {
+        AgentEvent::ToolStart { id, name, arguments } => {
+            assert_eq!(id, "call_1");
+            assert_eq!(name, "echo");
+            assert_eq!(arguments, &json!({ "message": "hi" }));
+        }
+        _ => unreachable!(),
+    }
+    match &events[finish] {
+        AgentEvent::ToolFinish { output, is_error, .. } => {
+            assert_eq!(output, "hi");
+            assert!(!is_error);
+        }
+        _ => unreachable!(),
+    }
+    assert_eq!(events.iter().filter(|e| matches!(e, AgentEvent::Usage { .. })).count(), 2);
+
+    match events.last().unwrap() {
+        AgentEvent::Finished { outcome } => {
+            assert_eq!(outcome.content.as_deref(), Some("It says hi"));
+            assert_eq!(outcome.usage.total_tokens, 20);
+        }
+        other => panic!("unexpected final event: {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_agent_stream_carries_thinking() {
+    let mut answer = response("Four", vec![], 10);
+    answer.thinking = Some("Two plus two".to_string());
+    let provider = ScriptedProvider::new(vec![answer]);
+    let agent = Agent::new(provider, "test-model").with_streaming(true);
+
+    let events: Vec<AgentEvent> = agent.stream(vec![ChatMessage::user("2 + 2?")]).collect().await;
+    let thinking: Vec<&str> = events
+        .iter()
+        .filter_map(|e| match e {
+            AgentEvent::Thinking { text } => Some(text.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(thinking, vec!["Two ", "plus ", "two"]);
+
+    // Thinking is forwarded as it arrives, ahead of the answer.
+    let last_thinking = events.iter().rposition(|e| matches!(e, AgentEvent::Thinking { .. })).unwrap();
+    let first_text = events.iter().position(|e| matches!(e, AgentEvent::TextDelta { .. })).unwrap();
+    assert!(last_thinking < first_text);
+}
+
+#[tokio::test]
+async fn test_agent_stream_drop_aborts_run() {
+    let provider = ScriptedProvider::new(vec![
+        response("", vec![call("call_1", "sleep", json!({}))], 10),
+        response("done", vec![], 10),
+    ]);
+    let agent = Agent::new(provider.clone(), "test-model").with_tools(registry());
+
+    let mut events = agent.stream(vec![ChatMessage::user("wait")]);
+    while let Some(event) = events.next().await {
+        if matches!(event, AgentEvent::ToolStart { .. }) {
+            break;
+        }
+    }
+    drop(events);
+
+    tokio::time::sleep(Duration::from_millis(400)).await;
+    assert_eq!(provider.requests.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_agent_stream_ends_with_error() {
+    let agent = Agent::new(ScriptedProvider::default(), "test-model");
+    let events: Vec<AgentEvent> = agent.stream(vec![ChatMessage::user("hi")]).collect().await;
+    assert!(matches!(events.as_slice(), [AgentEvent::Error { message }] if message.contains("script exhausted")));
+}
+
+#[cfg(feature = "anthropic")]
+#[tokio::test]
+async fn test_agent_streams_anthropic_tool_calls() {
+    use swissknife_ai_sdk::llm::{anthropic::AnthropicClient, ProviderConfig};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn sse(events: &[serde_json::Value]) -> ResponseTemplate {
+        let body: String = events
+            .iter()
+            .map(|e| format!("event: {}\ndata: {}\n\n", e["type"].as_str().unwrap(), e))
+            .collect();
+        ResponseTemplate::new(200)
+            .insert_header("content-type", "text/event-stream")
+            .set_body_string(body)
+    }
+
+    fn message_start(id: &str, input_tokens: u32) -> serde_json::Value {
+        json!({
+            "type": "message_start",
+            "message": { "id": id, "usage": { "input_tokens": input_tokens, "output_tokens": 1 } }
+        })
+    }
+
+    fn block_start(index: u32, block: serde_json::Value) -> serde_json::Value {
+        json!({ "type": "content_block_start", "index": index, "content_block": block })
+    }
+
+    fn block_delta(index: u32, delta: serde_json::Value) -> serde_json::Value {
+        json!({ "type": "content_block_delta", "index": index, "delta": delta })
+    }
+
+    fn message_delta(stop_reason: &str, output_tokens: u32) -> serde_json::Value {
+        json!({
+            "type": "message_delta",
+            "delta": { "stop_reason": stop_reason },
+            "usage": { "output_tokens": output_tokens }
+        })
+    }
+
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/messages"))
+        .respond_with(sse(&[
+            message_start("msg_1", 25),
+            block_start(0, json!({ "type": "text", "text": "" })),
+            block_delta(0, json!({ "type": "text_delta", "text": "Let me check" })),
+            json!({ "type": "content_block_stop", "index": 0 }),
+            block_start(1, json!({ "type": "tool_use", "id": "toolu_1", "name": "echo", "input": {} })),
+            block_delta(1, json!({ "type": "input_json_delta", "partial_json": "{\"mess" })),
+            block_delta(1, json!({ "type": "input_json_delta", "partial_json": "age\": \"hi\"}" })),
+            json!({ "type": "content_block_stop", "index": 1 }),
+            message_delta("tool_use", 15),
+            json!({ "type": "message_stop" }),
+        ]))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/messages"))
+        .respond_with(sse(&[
+            message_start("msg_2", 40),
+            block_start(0, json!({ "type": "text", "text": "" })),
+            block_delta(0, json!({ "type": "text_delta", "text": "It says hi" })),
+            json!({ "type": "content_block_stop", "index": 0 }),
+            message_delta("end_turn", 5),
+            json!({ "type": "message_stop" }),
+        ]))
+        .mount(&server)
+        .await;
+
+    let client = AnthropicClient::new(ProviderConfig::new("test-key").with_base_url(server.uri()));
+    let agent = Agent::new(client, "claude-test").with_tools(registry()).with_streaming(true);
+
+    let outcome = agent.run(vec![ChatMessage::user("What does echo say?")]).await.unwrap();
+    assert_eq!(outcome.content.as_deref(), Some("It says hi"));
+    let calls = outcome.messages[1].tool_calls.as_ref().unwrap();
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].id, "toolu_1");
+    assert_eq!(calls[0].function.name, "echo");
+    assert_eq!(outcome.messages[2].tool_call_id.as_deref(), Some("toolu_1"));
+    assert_eq!(text(&outcome.messages[2]), "hi");
+    assert_eq!(outcome.usage.prompt_tokens, 65);
+    assert_eq!(outcome.usage.total_tokens, 85);
+
+    let requests = server.received_requests().await.unwrap();
+    let second: serde_json::Value = serde_json::from_slice(&requests[1].body).unwrap();
+    assert_eq!(second["messages"][2]["content"][0]["tool_use_id"], "toolu_1");
+}
+
+#[cfg(feature = "mcp-inprocess")]
+#[tokio::test]
+async fn test_agent_calls_mcp_host_tools() {
+    use rmcp::model::{CallToolRequestParam, CallToolResult, Content, ListToolsResult, PaginatedRequestParam};
+    use rmcp::service::RequestContext;
+    use rmcp::{ErrorData as McpError, RoleServer, ServerHandler};
+    use swissknife_ai_sdk::mcp::{McpHost, ServerCapabilities, ServerInfo};
+
+    struct Weather;
+
+    impl ServerHandler for Weather {
+        fn get_info(&self) -> ServerInfo {
+            ServerInfo {
+                capabilities: ServerCapabilities::builder().enable_tools().build(),
+                ..Default::default()
+            }
+        }
+
+        async fn list_tools(
+            &self,
+            _request: Option<PaginatedRequestParam>,
+            _context: RequestContext<RoleServer>,
+        ) -> std::result::Result<ListToolsResult, McpError> {
+            let schema = json!({ "type": "object", "properties": { "city": { "type": "string" } } });
+            Ok(ListToolsResult::with_all_items(vec![rmcp::model::Tool::new(
+                "forecast",
+                "Weather forecast",
+                Arc::new(schema.as_object().unwrap().clone()),
+            )]))
+        }
+
+        async fn call_tool(
+            &self,
+            request: CallToolRequestParam,
+            _context: RequestContext<RoleServer>,
+        ) -> std::result::Result<CallToolResult, McpError> {
+            let city = request.arguments.unwrap_or_default()["city"].as_str().unwrap_or_default().to_string();
+            Ok(CallToolResult::success(vec![Content::text(format!("Sunny in {}", city))]))
+        }
+    }
+
+    let provider = ScriptedProvider::new(vec![
+        response("", vec![call("call_1", "forecast", json!({ "city": "Oslo" }))], 10),
+        response("It is sunny", vec![], 10),
+    ]);
+    let host = McpHost::new(Weather).await.unwrap();
+    let agent = Agent::new(provider.clone(), "test-model").with_tools(registry()).with_mcp_host(host);
+
+    let outcome = agent.run(vec![ChatMessage::user("Weather in Oslo?")]).await.unwrap();
+    assert_eq!(text(&outcome.messages[2]), "Sunny in Oslo");
+
+    let requests = provider.requests.lock().unwrap();
+    assert_eq!(requests[0].tools.as_ref().unwrap().len(), 3);
+}
@@ -0,0 +1,184 @@
+#![cfg(feature = "ingest")]
+
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use swissknife_ai_sdk::ingest::{
+    Chunker, Document, FixedSizeChunker, IngestionPipeline, MarkdownChunker, SentenceChunker,
+};
+use swissknife_ai_sdk::llm::{EmbeddingData, EmbeddingProvider, EmbeddingRequest, EmbeddingResponse};
+use swissknife_ai_sdk::{Error, Result};
+use swissknife_vectordb_sdk::local::LocalVectorStore;
+use swissknife_vectordb_sdk::{DistanceMetric, VectorDatabaseProvider};
+
+const DIMENSION: usize = 8;
+
+#[derive(Default)]
+struct HashEmbedder {
+    calls: AtomicUsize,
+    texts: AtomicUsize,
+    rate_limit_first: AtomicUsize,
+}
+
+fn embed_text(text: &str) -> Vec<f32> {
+    let mut values = vec![0.0; DIMENSION];
+    for word in text.split_whitespace() {
+        let bucket = word.bytes().map(|b| b as usize).sum::<usize>() % DIMENSION;
+        values[bucket] += 1.0;
+    }
+    values
+}
+
+#[async_trait]
+impl EmbeddingProvider for HashEmbedder {
+    async fn embed(&self, request: &EmbeddingRequest) -> Result<EmbeddingResponse> {
+        if self.rate_limit_first.load(Ordering::SeqCst) > 0 {
+            self.rate_limit_first.fetch_sub(1, Ordering::SeqCst);
+            return Err(Error::RateLimited);
+        }
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        self.texts.fetch_add(request.input.len(), Ordering::SeqCst);
+        Ok(EmbeddingResponse {
+            model: request.model.clone(),
+            data: request
+                .input
+                .iter()
+                .enumerate()
+                .rev()
+                .map(|(index, text)| EmbeddingData {
+                    index: index as u32,
+                    embedding: embed_text(text),
+                })
+                .collect(),
+            usage: None,
+        })
+    }
+}
+
+async fn pipeline(embedder: Arc<HashEmbedder>, store: LocalVectorStore) -> IngestionPipeline {
+    store
+        .create_collection("docs", DIMENSION as u32, DistanceMetric::Cosine)
+        .await
+        .unwrap();
+    IngestionPipeline::new(embedder, Arc::new(store), "docs", "test-model")
+        .with_chunker(SentenceChunker::new(40))
+        .with_batch_size(2)
+        .with_retry(3, Duration::from_millis(1))
+}
+
+#[test]
+fn test_fixed_size_chunker_overlaps() {
+    let chunks = FixedSizeChunker::new(4, 2).chunk("abcdefgh");
+    let texts: Vec<&str> = chunks.iter().map(|c| c.text.as_str()).collect();
+    assert_eq!(texts, vec!["abcd", "cdef", "efgh"]);
+}
+
+#[test]
+fn test_sentence_chunker_packs_sentences() {
+    let chunks = SentenceChunker::new(30).chunk("One short. Two short! A longer third sentence here? End.");
+    let texts: Vec<&str> = chunks.iter().map(|c| c.text.as_str()).collect();
+    assert_eq!(texts, vec!["One short. Two short!", "A longer third sentence here?", "End."]);
+}
+
+#[test]
+fn test_markdown_chunker_tracks_heading_path() {
+    let text = "Intro line\n# Guide\nWelcome.\n## Install\nRun it.\n```\n# not a heading\n```\n# FAQ\nAsk.";
+    let chunks = MarkdownChunker::new(500).chunk(text);
+    let headings: Vec<Option<&str>> = chunks.iter().map(|c| c.heading.as_deref()).collect();
+    assert_eq!(headings, vec![None, Some("Guide"), Some("Guide > Install"), Some("FAQ")]);
+    assert!(chunks[2].text.contains("# not a heading"));
+}
+
+#[tokio::test]
+async fn test_ingest_and_retrieve() {
+    let embedder = Arc::new(HashEmbedder::default());
+    let pipeline = pipeline(embedder.clone(), LocalVectorStore::new()).await;
+
+    let documents = vec![
+        Document::new("rust", "Rust has ownership. Borrowing is checked at compile time.").with_metadata("lang", "en"),
+        Document::new("cooking", "Boil the pasta in a big pot. Add salt to the water."),
+    ];
+    let report = pipeline.ingest(&documents).await.unwrap();
+    assert_eq!(report.ingested, 2);
+    assert_eq!(report.skipped, 0);
+    assert_eq!(report.chunks, 4);
+    assert_eq!(embedder.texts.load(Ordering::SeqCst), 4);
+    assert_eq!(embedder.calls.load(Ordering::SeqCst), 2);
+
+    let results = pipeline.retrieve("Boil the pasta in a big pot.", 1).await.unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].document_id, "cooking");
+    assert_eq!(results[0].text, "Boil the pasta in a big pot.");
+    assert!(results[0].metadata.is_empty());
+}
+
+#[tokio::test]
+async fn test_reingesting_unchanged_documents_is_noop() {
+    let embedder = Arc::new(HashEmbedder::default());
+    let store = LocalVectorStore::new();
+    let pipeline = pipeline(embedder.clone(), store.clone()).await;
+
+    let original = vec![Document::new("doc", "First sentence is here. Second sentence is here. Third sentence is here.")];
+    assert_eq!(pipeline.ingest(&original).await.unwrap().chunks, 3);
+    let embedded = embedder.texts.load(Ordering::SeqCst);
+
+    let report = pipeline.ingest(&original).await.unwrap();
+    assert_eq!(report.skipped, 1);
+    assert_eq!(report.chunks, 0);
+    assert_eq!(embedder.texts.load(Ordering::SeqCst), embedded);
+
+    let shorter = vec![Document::new("doc", "Only one sentence now.")];
+    let report = pipeline.ingest(&shorter).await.unwrap();
+    assert_eq!(report.chunks, 1);
+    assert_eq!(report.removed_chunks, 2);
+    assert_eq!(store.describe_collection("docs").await.unwrap().vector_count, Some(1));
+}
+
+#[tokio::test]
+async fn test_rate_limited_embeddings_are_retried() {
+    let embedder = Arc::new(HashEmbedder::default());
+    embedder.rate_limit_first.store(2, Ordering::SeqCst);
+    let pipeline = pipeline(embedder.clone(), LocalVectorStore::new()).await;
+
+    let report = pipeline.ingest(&[Document::new("doc", "Hello world.")]).await.unwrap();
+    assert_eq!(report.chunks, 1);
+    assert_eq!(embedder.calls.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_long_retry_sequences_do_not_overflow_backoff() {
+    let embedder = Arc::new(HashEmbedder::default());
+    embedder.rate_limit_first.store(40, Ordering::SeqCst);
+    let pipeline = pipeline(embedder.clone(), LocalVectorStore::new())
+        .await
+        .with_retry(40, Duration::ZERO);
+
+    let report = pipeline.ingest(&[Document::new("doc", "Hello world.")]).await.unwrap();
+    assert_eq!(report.chunks, 1);
+}
+
+#[tokio::test]
+async fn test_reingesting_empty_document_is_noop() {
+    let embedder = Arc::new(HashEmbedder::default());
+    let store = LocalVectorStore::new();
+    let pipeline = pipeline(embedder.clone(), store.clone()).await;
+
+    let full = vec![Document::new("doc", "First sentence is here. Second sentence is here.")];
+    assert_eq!(pipeline.ingest(&full).await.unwrap().chunks, 2);
+
+    let empty = vec![Document::new("doc", "")];
+    let report = pipeline.ingest(&empty).await.unwrap();
+    assert_eq!(report.ingested, 1);
+    assert_eq!(report.chunks, 0);
+    assert_eq!(report.removed_chunks, 1);
+
+    let report = pipeline.ingest(&empty).await.unwrap();
+    assert_eq!(report.skipped, 1);
+    assert_eq!(report.ingested, 0);
+    assert!(pipeline.retrieve("First sentence", 5).await.unwrap().is_empty());
+
+    let report = pipeline.ingest(&full).await.unwrap();
+    assert_eq!(report.chunks, 2);
+    assert_eq!(store.describe_collection("docs").await.unwrap().vector_count, Some(2));
+}
@@ -0,0 +1,111 @@
+#![cfg(all(feature = "cli", feature = "search"))]
+
+use clap::{CommandFactory, Parser};
+use serde_json::json;
+use swissknife_ai_sdk::mcp::cli::{http_server, Cli};
+
+async fn start(args: &[&str]) -> String {
+    let cli = Cli::parse_from(std::iter::once("swissknife-mcp").chain(args.iter().copied()));
+    let server = http_server(&cli);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, server.router()).await });
+    format!("http://{}/mcp", addr)
+}
+
+fn post(url: &str, session: Option<&str>, body: serde_json::Value) -> reqwest::RequestBuilder {
+    let request = reqwest::Client::new()
+        .post(url)
+        .header("Accept", "application/json, text/event-stream")
+        .json(&body);
+    match session {
+        Some(session) => request.header("Mcp-Session-Id", session),
+        None => request,
+    }
+}
+
+async fn list_tools(url: &str, token: &str) -> String {
+    let response = post(url, None, json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "initialize",
+        "params": {
+            "protocolVersion": "2025-03-26",
+            "capabilities": {},
+            "clientInfo": { "name": "test-client", "version": "1.0.0" }
+        }
+    }))
+    .bearer_auth(token)
+    .send()
+    .await
+    .unwrap();
+    assert!(response.status().is_success());
+    let session = response.headers()["mcp-session-id"].to_str().unwrap().to_string();
+
+    let response = post(url, Some(&session), json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }))
+        .bearer_auth(token)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 202);
+
+    post(url, Some(&session), json!({ "jsonrpc": "2.0", "id": 2, "method": "tools/list" }))
+        .bearer_auth(token)
+        .send()
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_cli_http_server_lists_configured_tools() {
+    let url = start(&["--tools", "search"]).await;
+
+    let body = list_tools(&url, "").await;
+    assert!(body.contains("\"web_fetch\""));
+}
+
+#[tokio::test]
+async fn test_cli_http_server_omits_unselected_categories() {
+    let url = start(&["--tools", "payments"]).await;
+
+    let body = list_tools(&url, "").await;
+    assert!(body.contains("\"tools\":[]"));
+}
+
+#[tokio::test]
+async fn test_cli_category_token_lists_only_its_category() {
+    let url = start(&[
+        "--tools", "all",
+        "--auth-token", "admin-token",
+        "--auth-token", "search-token:search",
+        "--auth-token", "payments-token:payments",
+    ])
+    .await;
+
+    assert!(list_tools(&url, "admin-token").await.contains("\"web_fetch\""));
+    assert!(list_tools(&url, "search-token").await.contains("\"web_fetch\""));
+    assert!(list_tools(&url, "payments-token").await.contains("\"tools\":[]"));
+}
+
+#[test]
+fn test_cli_accepts_old_version_flag() {
+    let cli = Cli::parse_from(["swissknife-mcp", "-H", "0.0.0.0", "--version", "2.0.0"]);
+    assert_eq!(cli.host, "0.0.0.0");
+    assert_eq!(cli.version, "2.0.0");
+
+    let cli = Cli::parse_from(["swissknife-mcp", "--server-version", "2.1.0"]);
+    assert_eq!(cli.version, "2.1.0");
+
+    let error = Cli::try_parse_from(["swissknife-mcp", "-V"]).unwrap_err();
+    assert_eq!(error.kind(), clap::error::ErrorKind::DisplayVersion);
+}
+
+#[test]
+fn test_cli_help_documents_auth_tokens() {
+    let help = Cli::command().render_long_help().to_string();
+    assert!(help.contains("--auth-token <TOKEN[:CATEGORIES]>"));
+    assert!(help.contains("limit it to those tool categories"));
+}
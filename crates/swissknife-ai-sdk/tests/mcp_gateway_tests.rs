@@ -0,0 +1,279 @@
+#![cfg(feature = "mcp-gateway")]
+
+use rmcp::model::{
+    CallToolRequestParam, ListToolsResult, PaginatedRequestParam, ProgressNotificationParam, RawContent,
+};
+use rmcp::service::{NotificationContext, RequestContext};
+use rmcp::{ClientHandler, ErrorData as McpError, RoleClient, RoleServer, ServiceExt};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use swissknife_ai_sdk::mcp::{
+    CallToolResult, Content, Implementation, McpGateway, ServerCapabilities, ServerHandler, ServerInfo, Tool,
+    Upstream,
+};
+use tokio::sync::Notify;
+
+// Echoes its name, can sleep, report progress and grow its tool list.
+#[derive(Clone)]
+struct TestServer {
+    name: &'static str,
+    tools: Arc<Mutex<Vec<&'static str>>>,
+}
+
+impl TestServer {
+    fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            tools: Arc::new(Mutex::new(vec!["echo", "slow", "progress", "grow"])),
+        }
+    }
+}
+
+impl ServerHandler for TestServer {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo {
+            capabilities: ServerCapabilities::builder().enable_tools().enable_tool_list_changed().build(),
+            server_info: Implementation {
+                name: self.name.to_string(),
+                version: "1.0.0".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    async fn list_tools(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, McpError> {
+        let schema = Arc::new(serde_json::Map::new());
+        let tools = self.tools.lock().unwrap().clone();
+        Ok(ListToolsResult::with_all_items(
+            tools.into_iter().map(|name| Tool::new(name, "test tool", schema.clone())).collect(),
+        ))
+    }
+
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        match request.name.as_ref() {
+            "slow" => tokio::time::sleep(Duration::from_secs(5)).await,
+            "progress" => {
+                let token = context.meta.get_progress_token().unwrap();
+                context
+                    .peer
+                    .notify_progress(ProgressNotificationParam {
+                        progress_token: token,
+                        progress: 1.0,
+                        total: Some(2.0),
+                        message: Some("halfway".to_string()),
+                    })
+                    .await
+                    .unwrap();
+            }
+            "grow" => {
+                self.tools.lock().unwrap().push("added");
+                context.peer.notify_tool_list_changed().await.unwrap();
+            }
+            _ => {}
+        }
+        let arguments = serde_json::Value::Object(request.arguments.unwrap_or_default());
+        Ok(CallToolResult::success(vec![Content::text(format!(
+            "{} {} {}",
+            self.name, request.name, arguments
+        ))]))
+    }
+}
+
+#[derive(Clone, Default)]
+struct Client {
+    progress: Arc<Mutex<Vec<String>>>,
+    tools_changed: Arc<Notify>,
+}
+
+impl ClientHandler for Client {
+    async fn on_progress(&self, params: ProgressNotificationParam, _context: NotificationContext<RoleClient>) {
+        self.progress.lock().unwrap().push(params.message.unwrap_or_default());
+    }
+
+    async fn on_tool_list_changed(&self, _context: NotificationContext<RoleClient>) {
+        self.tools_changed.notify_one();
+    }
+}
+
+async fn connect(gateway: McpGateway) -> (Client, rmcp::service::RunningService<RoleClient, Client>) {
+    gateway.connect().await.unwrap();
+    let (server_stream, client_stream) = tokio::io::duplex(4096);
+    tokio::spawn(async move { gateway.serve(server_stream).await.unwrap().waiting().await });
+    let client = Client::default();
+    let running = client.clone().serve(client_stream).await.unwrap();
+    (client, running)
+}
+
+fn text(result: &CallToolResult) -> String {
+    match &result.content[0].raw {
+        RawContent::Text(t) => t.text.clone(),
+        other => panic!("unexpected content: {:?}", other),
+    }
+}
+
+fn call(name: &str) -> CallToolRequestParam {
+    CallToolRequestParam {
+        name: name.to_string().into(),
+        arguments: json!({ "n": 1 }).as_object().cloned(),
+    }
+}
+
+#[tokio::test]
+async fn test_gateway_namespaces_and_forwards() {
+    let gateway = McpGateway::new("gateway", "1.0.0")
+        .with_upstream(Upstream::in_process("alpha", || TestServer::new("alpha")))
+        .with_upstream(Upstream::in_process("beta", || TestServer::new("beta")));
+    let (client, running) = connect(gateway).await;
+
+    let info = running.peer_info().unwrap();
+    assert_eq!(info.server_info.name, "gateway");
+
+    let mut names: Vec<String> = running.list_all_tools().await.unwrap().into_iter().map(|t| t.name.into()).collect();
+    names.sort();
+    assert_eq!(names.len(), 8);
+    assert!(names.contains(&"alpha.echo".to_string()));
+    assert!(names.contains(&"beta.grow".to_string()));
+
+    let result = running.call_tool(call("beta.echo")).await.unwrap();
+    assert_eq!(text(&result), "beta echo {\"n\":1}");
+
+    assert!(running.call_tool(call("gamma.echo")).await.is_err());
+
+    let result = running.call_tool(call("alpha.progress")).await.unwrap();
+    assert_eq!(text(&result), "alpha progress {\"n\":1}");
+    assert_eq!(*client.progress.lock().unwrap(), vec!["halfway".to_string()]);
+}
+
+#[tokio::test]
+async fn test_gateway_refreshes_on_upstream_list_changed() {
+    let gateway = McpGateway::new("gateway", "1.0.0")
+        .with_upstream(Upstream::in_process("alpha", || TestServer::new("alpha")));
+    let (client, running) = connect(gateway).await;
+    assert_eq!(running.list_all_tools().await.unwrap().len(), 4);
+
+    running.call_tool(call("alpha.grow")).await.unwrap();
+    tokio::time::timeout(Duration::from_secs(5), client.tools_changed.notified())
+        .await
+        .unwrap();
+
+    let tools = running.list_all_tools().await.unwrap();
+    assert!(tools.iter().any(|t| t.name == "alpha.added"));
+}
+
+#[tokio::test]
+async fn test_gateway_upstream_timeout() {
+    let gateway = McpGateway::new("gateway", "1.0.0").with_upstream(
+        Upstream::in_process("alpha", || TestServer::new("alpha")).with_timeout(Duration::from_millis(100)),
+    );
+    let (_client, running) = connect(gateway).await;
+
+    let error = running.call_tool(call("alpha.slow")).await.unwrap_err();
+    assert!(error.to_string().contains("timed out"));
+}
+
+#[tokio::test]
+async fn test_gateway_reconnects_to_upstream() {
+    let servers: Arc<Mutex<Vec<tokio::task::JoinHandle<()>>>> = Arc::default();
+    let handles = servers.clone();
+    let upstream = Upstream::duplex("alpha", move || {
+        let (server_stream, client_stream) = tokio::io::duplex(4096);
+        handles.lock().unwrap().push(tokio::spawn(async move {
+            let running = TestServer::new("alpha").serve(server_stream).await.unwrap();
+            let _ = running.waiting().await;
+        }));
+        client_stream
+    });
+    let gateway = McpGateway::new("gateway", "1.0.0").with_upstream(upstream);
+    let (_client, running) = connect(gateway).await;
+
+    running.call_tool(call("alpha.echo")).await.unwrap();
+    servers.lock().unwrap()[0].abort();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let result = running.call_tool(call("alpha.echo")).await.unwrap();
+    assert_eq!(text(&result), "alpha echo {\"n\":1}");
+    assert_eq!(servers.lock().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_gateway_lists_cached_tools_while_reconnecting() {
+    let servers: Arc<Mutex<Vec<tokio::task::JoinHandle<()>>>> = Arc::default();
+    let handles = servers.clone();
+    let upstream = Upstream::duplex("alpha", move || {
+        let (server_stream, client_stream) = tokio::io::duplex(4096);
+        let mut handles = handles.lock().unwrap();
+        // Only the first connection is served; later ones hang until the timeout.
+        if handles.is_empty() {
+            handles.push(tokio::spawn(async move {
+                let running = TestServer::new("alpha").serve(server_stream).await.unwrap();
+                let _ = running.waiting().await;
+            }));
+        } else {
+            handles.push(tokio::spawn(async move {
+                let _server_stream = server_stream;
+                std::future::pending::<()>().await
+            }));
+        }
+        client_stream
+    })
+    .with_timeout(Duration::from_secs(5))
+    .with_reconnect_attempts(0);
+    let gateway = McpGateway::new("gateway", "1.0.0").with_upstream(upstream);
+    let (_client, running) = connect(gateway).await;
+
+    servers.lock().unwrap()[0].abort();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let tools = tokio::time::timeout(Duration::from_secs(1), running.list_all_tools())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(tools.len(), 4);
+    assert!(tools.iter().any(|t| t.name == "alpha.echo"));
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(servers.lock().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_gateway_resolves_overlapping_upstream_names() {
+    let gateway = McpGateway::new("gateway", "1.0.0")
+        .with_separator("_")
+        .with_upstream(Upstream::in_process("alpha", || TestServer::new("alpha")))
+        .with_upstream(Upstream::in_process("alpha_beta", || TestServer::new("alpha_beta")));
+    let (_client, running) = connect(gateway).await;
+
+    let result = running.call_tool(call("alpha_beta_echo")).await.unwrap();
+    assert_eq!(text(&result), "alpha_beta echo {\"n\":1}");
+
+    let result = running.call_tool(call("alpha_echo")).await.unwrap();
+    assert_eq!(text(&result), "alpha echo {\"n\":1}");
+}
+
+#[cfg(feature = "http")]
+#[tokio::test]
+async fn test_gateway_http_upstream() {
+    use swissknife_ai_sdk::mcp::server::{McpHttpServer, McpHttpServerConfig};
+
+    let server = McpHttpServer::new(McpHttpServerConfig::default(), || TestServer::new("remote"));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, server.router()).await });
+
+    let gateway = McpGateway::new("gateway", "1.0.0")
+        .with_upstream(Upstream::http("remote", format!("http://{}/mcp", addr)));
+    let (_client, running) = connect(gateway).await;
+
+    let result = running.call_tool(call("remote.echo")).await.unwrap();
+    assert_eq!(text(&result), "remote echo {\"n\":1}");
+}
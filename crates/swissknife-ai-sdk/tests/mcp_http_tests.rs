@@ -0,0 +1,319 @@
+#![cfg(feature = "http")]
+
+use rmcp::model::{CallToolRequestParam, ListToolsResult, PaginatedRequestParam};
+use rmcp::service::RequestContext;
+use rmcp::{ErrorData as McpError, RoleServer};
+use serde_json::json;
+use std::sync::Arc;
+use swissknife_ai_sdk::mcp::server::{
+    McpHttpServer, McpHttpServerConfig, ProtectedResourceMetadata, StaticTokenAuth, ToolAccess,
+};
+use swissknife_ai_sdk::mcp::{
+    CallToolResult, Content, Implementation, ServerCapabilities, ServerHandler, ServerInfo, Tool,
+};
+
+#[derive(Clone)]
+struct TestServer;
+
+impl ServerHandler for TestServer {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo {
+            server_info: Implementation {
+                name: "test-server".to_string(),
+                version: "1.0.0".to_string(),
+                ..Default::default()
+            },
+            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            ..Default::default()
+        }
+    }
+
+    async fn list_tools(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, McpError> {
+        let schema = Arc::new(serde_json::Map::new());
+        Ok(ListToolsResult::with_all_items(vec![
+            Tool::new("search", "Search the web", schema.clone()),
+            Tool::new("charge", "Charge a card", schema),
+        ]))
+    }
+
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        Ok(CallToolResult::success(vec![Content::text(format!("called {}", request.name))]))
+    }
+}
+
+async fn start() -> String {
+    serve(McpHttpServer::new(McpHttpServerConfig::default(), || TestServer)).await
+}
+
+async fn start_with_auth() -> String {
+    let config = McpHttpServerConfig {
+        cors_origins: vec!["https://app.example.com".to_string()],
+        ..Default::default()
+    };
+    let auth = StaticTokenAuth::new()
+        .with_token("admin-token", "admin")
+        .with_access("search-token", "searcher", ToolAccess::categories(["search"]));
+    let server = McpHttpServer::new(config, || TestServer)
+        .with_auth(auth)
+        .with_resource_metadata(
+            ProtectedResourceMetadata::new("https://mcp.example.com/mcp", "https://auth.example.com")
+                .with_scopes(["tools:search", "tools:payments"]),
+        )
+        .with_tool_category("search", ["search"])
+        .with_tool_category("payments", ["charge"]);
+    serve(server).await
+}
+
+async fn serve(server: McpHttpServer<TestServer>) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, server.router()).await });
+    format!("http://{}/mcp", addr)
+}
+
+fn post(url: &str, session: Option<&str>, body: serde_json::Value) -> reqwest::RequestBuilder {
+    let request = reqwest::Client::new()
+        .post(url)
+        .header("Accept", "application/json, text/event-stream")
+        .json(&body);
+    match session {
+        Some(session) => request.header("Mcp-Session-Id", session),
+        None => request,
+    }
+}
+
+async fn initialize(url: &str, token: &str) -> String {
+    let response = post(url, None, json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "initialize",
+        "params": {
+            "protocolVersion": "2025-03-26",
+            "capabilities": {},
+            "clientInfo": { "name": "test-client", "version": "1.0.0" }
+        }
+    }))
+    .bearer_auth(token)
+    .send()
+    .await
+    .unwrap();
+    assert!(response.status().is_success());
+    let session = response.headers()["mcp-session-id"].to_str().unwrap().to_string();
+
+    let response = post(url, Some(&session), json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }))
+        .bearer_auth(token)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 202);
+    session
+}
+
+#[tokio::test]
+async fn test_streamable_http_session_lifecycle() {
+    let url = start().await;
+
+    let response = post(&url, None, json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "initialize",
+        "params": {
+            "protocolVersion": "2025-03-26",
+            "capabilities": {},
+            "clientInfo": { "name": "test-client", "version": "1.0.0" }
+        }
+    }))
+    .send()
+    .await
+    .unwrap();
+    assert!(response.status().is_success());
+    assert_eq!(response.headers()["content-type"], "text/event-stream");
+    let session = response.headers()["mcp-session-id"].to_str().unwrap().to_string();
+    let body = response.text().await.unwrap();
+    assert!(body.starts_with("data: "));
+    assert!(body.contains("\"protocolVersion\""));
+    assert!(body.contains("\"test-server\""));
+
+    let response = post(&url, Some(&session), json!({
+        "jsonrpc": "2.0",
+        "method": "notifications/initialized"
+    }))
+    .send()
+    .await
+    .unwrap();
+    assert_eq!(response.status(), 202);
+
+    let response = post(&url, Some(&session), json!({ "jsonrpc": "2.0", "id": 2, "method": "ping" }))
+        .send()
+        .await
+        .unwrap();
+    let body = response.text().await.unwrap();
+    assert!(body.contains("\"id\":2"));
+    assert!(body.contains("\"result\":{}"));
+
+    let response = reqwest::Client::new()
+        .delete(&url)
+        .header("Mcp-Session-Id", &session)
+        .send()
+        .await
+        .unwrap();
+    assert!(response.status().is_success());
+
+    let response = post(&url, Some(&session), json!({ "jsonrpc": "2.0", "id": 3, "method": "ping" }))
+        .send()
+        .await
+        .unwrap();
+    assert!(response.status().is_client_error());
+}
+
+#[tokio::test]
+async fn test_streamable_http_requires_initialize_first() {
+    let url = start().await;
+
+    let response = post(&url, None, json!({ "jsonrpc": "2.0", "id": 1, "method": "tools/list" }))
+        .send()
+        .await
+        .unwrap();
+    assert!(response.status().is_client_error());
+    assert!(response.headers().get("mcp-session-id").is_none());
+}
+
+#[tokio::test]
+async fn test_streamable_http_rejects_missing_token() {
+    let url = start_with_auth().await;
+
+    let response = post(&url, None, json!({ "jsonrpc": "2.0", "id": 1, "method": "ping" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 401);
+    assert_eq!(
+        response.headers()["www-authenticate"],
+        "Bearer resource_metadata=\"https://mcp.example.com/.well-known/oauth-protected-resource/mcp\""
+    );
+
+    let response = post(&url, None, json!({ "jsonrpc": "2.0", "id": 1, "method": "ping" }))
+        .bearer_auth("wrong-token")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 401);
+    let challenge = response.headers()["www-authenticate"].to_str().unwrap();
+    assert!(challenge.contains("error=\"invalid_token\""));
+
+    let health = url.replace("/mcp", "/health");
+    let response = reqwest::get(&health).await.unwrap();
+    assert!(response.status().is_success());
+}
+
+#[tokio::test]
+async fn test_streamable_http_protected_resource_metadata() {
+    let url = start_with_auth().await;
+    let metadata_url = url.replace("/mcp", "/.well-known/oauth-protected-resource/mcp");
+
+    let metadata: serde_json::Value = reqwest::get(&metadata_url).await.unwrap().json().await.unwrap();
+    assert_eq!(metadata["resource"], "https://mcp.example.com/mcp");
+    assert_eq!(metadata["authorization_servers"], json!(["https://auth.example.com"]));
+    assert_eq!(metadata["scopes_supported"], json!(["tools:search", "tools:payments"]));
+    assert_eq!(metadata["bearer_methods_supported"], json!(["header"]));
+}
+
+#[tokio::test]
+async fn test_streamable_http_token_tool_allow_list() {
+    let url = start_with_auth().await;
+
+    let session = initialize(&url, "admin-token").await;
+    let body = post(&url, Some(&session), json!({ "jsonrpc": "2.0", "id": 2, "method": "tools/list" }))
+        .bearer_auth("admin-token")
+        .send()
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    assert!(body.contains("\"search\""));
+    assert!(body.contains("\"charge\""));
+
+    let session = initialize(&url, "search-token").await;
+    let body = post(&url, Some(&session), json!({ "jsonrpc": "2.0", "id": 2, "method": "tools/list" }))
+        .bearer_auth("search-token")
+        .send()
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    assert!(body.contains("\"search\""));
+    assert!(!body.contains("\"charge\""));
+
+    let call = |name: &str| json!({
+        "jsonrpc": "2.0",
+        "id": 3,
+        "method": "tools/call",
+        "params": { "name": name, "arguments": {} }
+    });
+    let body = post(&url, Some(&session), call("search"))
+        .bearer_auth("search-token")
+        .send()
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    assert!(body.contains("called search"));
+
+    let body = post(&url, Some(&session), call("charge"))
+        .bearer_auth("search-token")
+        .send()
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    assert!(body.contains("\"error\""));
+    assert!(!body.contains("called charge"));
+}
+
+#[tokio::test]
+async fn test_streamable_http_rejects_unknown_origin() {
+    let url = start_with_auth().await;
+
+    let response = post(&url, None, json!({ "jsonrpc": "2.0", "id": 1, "method": "ping" }))
+        .bearer_auth("admin-token")
+        .header("Origin", "https://evil.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 403);
+
+    let response = reqwest::Client::new()
+        .request(reqwest::Method::OPTIONS, &url)
+        .header("Origin", "https://app.example.com")
+        .header("Access-Control-Request-Method", "POST")
+        .header("Access-Control-Request-Headers", "authorization, mcp-session-id")
+        .send()
+        .await
+        .unwrap();
+    assert!(response.status().is_success());
+    assert_eq!(response.headers()["access-control-allow-origin"], "https://app.example.com");
+}
+
+#[tokio::test]
+async fn test_streamable_http_rejects_cross_origin_by_default() {
+    let url = start().await;
+
+    let response = post(&url, None, json!({ "jsonrpc": "2.0", "id": 1, "method": "ping" }))
+        .header("Origin", "https://app.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 403);
+}
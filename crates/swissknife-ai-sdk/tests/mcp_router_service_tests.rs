@@ -0,0 +1,296 @@
+#![cfg(feature = "mcp-inprocess")]
+
+use async_trait::async_trait;
+use rmcp::model::{
+    CallToolRequestParam, CallToolResult, ErrorCode, GetPromptRequestParam, RawContent, ReadResourceRequestParam,
+    ResourceContents,
+};
+use rmcp::service::NotificationContext;
+use rmcp::{ClientHandler, RoleClient, ServiceError, ServiceExt};
+use serde_json::json;
+use std::sync::Arc;
+use swissknife_ai_sdk::mcp::server::{serve_duplex, DuplexConnection};
+use swissknife_ai_sdk::mcp::types::*;
+use swissknife_ai_sdk::mcp::{McpRouter, PromptProvider, ResourceProvider, RouterService, ToolProvider};
+use swissknife_ai_sdk::Result;
+use tokio::sync::Notify;
+
+struct EchoTools;
+
+#[async_trait]
+impl ToolProvider for EchoTools {
+    fn tools(&self) -> Vec<ToolDefinition> {
+        vec![ToolDefinition::new("echo")
+            .with_description("Echoes back the input")
+            .with_schema(json!({
+                "type": "object",
+                "properties": { "message": { "type": "string" } },
+                "required": ["message"]
+            }))]
+    }
+
+    async fn call(&self, name: &str, arguments: serde_json::Value) -> Result<ToolResult> {
+        match arguments.get("message").and_then(|v| v.as_str()) {
+            Some(message) => Ok(ToolResult::text(format!("{}: {}", name, message))),
+            None => Err(swissknife_ai_sdk::Error::MissingParameter("message".into())),
+        }
+    }
+}
+
+struct Files;
+
+#[async_trait]
+impl ResourceProvider for Files {
+    fn resources(&self) -> Vec<ResourceDefinition> {
+        vec![ResourceDefinition {
+            uri: "file:///notes.txt".to_string(),
+            name: "Notes".to_string(),
+            description: None,
+            mime_type: Some("text/plain".to_string()),
+        }]
+    }
+
+    async fn read(&self, uri: &str) -> Result<ResourceContent> {
+        Ok(ResourceContent {
+            uri: uri.to_string(),
+            mime_type: Some("text/plain".to_string()),
+            text: Some("remember the milk".to_string()),
+            blob: None,
+        })
+    }
+}
+
+struct Prompts;
+
+#[async_trait]
+impl PromptProvider for Prompts {
+    fn prompts(&self) -> Vec<PromptDefinition> {
+        vec![PromptDefinition {
+            name: "summarize".to_string(),
+            description: Some("Summarize a topic".to_string()),
+            arguments: Some(vec![PromptArgument {
+                name: "topic".to_string(),
+                description: None,
+                required: true,
+            }]),
+        }]
+    }
+
+    async fn get(&self, _name: &str, arguments: serde_json::Value) -> Result<PromptContent> {
+        Ok(PromptContent {
+            description: None,
+            messages: vec![PromptMessage {
+                role: PromptRole::User,
+                content: PromptMessageContent::Text {
+                    text: format!("Summarize {}", arguments["topic"].as_str().unwrap_or_default()),
+                },
+            }],
+        })
+    }
+}
+
+fn router() -> McpRouter {
+    McpRouter::new("router-server", "2.0.0")
+        .with_instructions("Use the echo tool")
+        .with_tool_provider(EchoTools)
+        .with_resource_provider(Files)
+        .with_prompt_provider(Prompts)
+}
+
+#[tokio::test]
+async fn test_router_service_over_duplex() {
+    let DuplexConnection { peer, .. } = serve_duplex(RouterService::new(router())).await.unwrap();
+
+    let info = peer.peer_info().unwrap();
+    assert_eq!(info.server_info.name, "router-server");
+    assert_eq!(info.instructions.as_deref(), Some("Use the echo tool"));
+    assert!(info.capabilities.resources.is_some());
+    assert_eq!(info.capabilities.tools.as_ref().unwrap().list_changed, Some(true));
+
+    let tools = peer.list_all_tools().await.unwrap();
+    assert_eq!(tools.len(), 1);
+    assert_eq!(tools[0].name, "echo");
+    assert_eq!(tools[0].input_schema["required"], json!(["message"]));
+
+    let result = peer
+        .call_tool(CallToolRequestParam {
+            name: "echo".into(),
+            arguments: json!({ "message": "hi" }).as_object().cloned(),
+        })
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(false));
+    match &result.content[0].raw {
+        RawContent::Text(t) => assert_eq!(t.text, "echo: hi"),
+        other => panic!("unexpected content: {:?}", other),
+    }
+
+    let result = peer
+        .call_tool(CallToolRequestParam {
+            name: "echo".into(),
+            arguments: None,
+        })
+        .await
+        .unwrap();
+    assert_eq!(result.is_error, Some(true));
+
+    let resources = peer.list_all_resources().await.unwrap();
+    assert_eq!(resources[0].uri, "file:///notes.txt");
+    let read = peer
+        .read_resource(ReadResourceRequestParam {
+            uri: "file:///notes.txt".to_string(),
+        })
+        .await
+        .unwrap();
+    match &read.contents[0] {
+        ResourceContents::TextResourceContents { text, .. } => assert_eq!(text, "remember the milk"),
+        other => panic!("unexpected contents: {:?}", other),
+    }
+    assert!(peer
+        .read_resource(ReadResourceRequestParam {
+            uri: "file:///missing.txt".to_string(),
+        })
+        .await
+        .is_err());
+
+    let prompts = peer.list_all_prompts().await.unwrap();
+    assert_eq!(prompts[0].name, "summarize");
+    assert_eq!(prompts[0].arguments.as_ref().unwrap()[0].required, Some(true));
+    let prompt = peer
+        .get_prompt(GetPromptRequestParam {
+            name: "summarize".to_string(),
+            arguments: json!({ "topic": "rust" }).as_object().cloned(),
+        })
+        .await
+        .unwrap();
+    match &prompt.messages[0].content {
+        rmcp::model::PromptMessageContent::Text { text } => assert_eq!(text, "Summarize rust"),
+        other => panic!("unexpected content: {:?}", other),
+    }
+}
+
+struct FailingTools;
+
+#[async_trait]
+impl ToolProvider for FailingTools {
+    fn tools(&self) -> Vec<ToolDefinition> {
+        vec![ToolDefinition::new("charge"), ToolDefinition::new("refund")]
+    }
+
+    async fn call(&self, name: &str, _arguments: serde_json::Value) -> Result<ToolResult> {
+        match name {
+            "charge" => Ok(ToolResult::error("card declined")),
+            _ => Err(swissknife_ai_sdk::Error::Api {
+                message: "upstream unavailable".into(),
+                code: None,
+            }),
+        }
+    }
+}
+
+struct BrokenFiles;
+
+#[async_trait]
+impl ResourceProvider for BrokenFiles {
+    fn resources(&self) -> Vec<ResourceDefinition> {
+        vec![ResourceDefinition {
+            uri: "db://".to_string(),
+            name: "Rows".to_string(),
+            description: None,
+            mime_type: None,
+        }]
+    }
+
+    async fn read(&self, uri: &str) -> Result<ResourceContent> {
+        match uri {
+            "db://rows" => Err(swissknife_ai_sdk::Error::InvalidParameter("missing table".into())),
+            _ => Err(swissknife_ai_sdk::Error::Api {
+                message: "connection reset".into(),
+                code: None,
+            }),
+        }
+    }
+}
+
+fn text(result: &CallToolResult) -> &str {
+    match &result.content[0].raw {
+        RawContent::Text(t) => &t.text,
+        other => panic!("unexpected content: {:?}", other),
+    }
+}
+
+fn error_code<T: std::fmt::Debug>(result: std::result::Result<T, ServiceError>) -> ErrorCode {
+    match result {
+        Err(ServiceError::McpError(e)) => e.code,
+        other => panic!("expected an MCP error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_router_service_maps_tool_and_provider_errors() {
+    let router = router().with_tool_provider(FailingTools).with_resource_provider(BrokenFiles);
+    let DuplexConnection { peer, .. } = serve_duplex(RouterService::new(router)).await.unwrap();
+
+    let call = |name: &str| CallToolRequestParam {
+        name: name.to_string().into(),
+        arguments: None,
+    };
+
+    // Tool failures reach the model as error results, never protocol errors.
+    let result = peer.call_tool(call("charge")).await.unwrap();
+    assert_eq!(result.is_error, Some(true));
+    assert_eq!(text(&result), "card declined");
+
+    let result = peer.call_tool(call("refund")).await.unwrap();
+    assert_eq!(result.is_error, Some(true));
+    assert!(text(&result).contains("upstream unavailable"));
+
+    let result = peer.call_tool(call("missing")).await.unwrap();
+    assert_eq!(result.is_error, Some(true));
+    assert_eq!(text(&result), "Tool not found: missing");
+
+    let read = |uri: &str| ReadResourceRequestParam { uri: uri.to_string() };
+    assert_eq!(error_code(peer.read_resource(read("file:///missing.txt")).await), ErrorCode::RESOURCE_NOT_FOUND);
+    assert_eq!(error_code(peer.read_resource(read("db://rows")).await), ErrorCode::INVALID_PARAMS);
+    assert_eq!(error_code(peer.read_resource(read("db://other")).await), ErrorCode::INTERNAL_ERROR);
+
+    let prompt = peer
+        .get_prompt(GetPromptRequestParam {
+            name: "missing".to_string(),
+            arguments: None,
+        })
+        .await;
+    assert_eq!(error_code(prompt), ErrorCode::INVALID_PARAMS);
+}
+
+#[derive(Clone, Default)]
+struct ListChangedClient {
+    tools_changed: Arc<Notify>,
+}
+
+impl ClientHandler for ListChangedClient {
+    async fn on_tool_list_changed(&self, _context: NotificationContext<RoleClient>) {
+        self.tools_changed.notify_one();
+    }
+}
+
+#[tokio::test]
+async fn test_router_service_notifies_list_changed() {
+    let service = RouterService::new(router());
+    let notifier = service.notifier();
+    let (server_transport, client_transport) = tokio::io::duplex(4096);
+    let server = tokio::spawn(async move { service.serve(server_transport).await.unwrap().waiting().await });
+
+    let handler = ListChangedClient::default();
+    let client = handler.clone().serve(client_transport).await.unwrap();
+    // The server registers the client once it sees notifications/initialized.
+    client.list_all_tools().await.unwrap();
+
+    notifier.tools_changed().await;
+    tokio::time::timeout(std::time::Duration::from_secs(5), handler.tools_changed.notified())
+        .await
+        .unwrap();
+
+    client.cancel().await.unwrap();
+    server.await.unwrap().unwrap();
+}
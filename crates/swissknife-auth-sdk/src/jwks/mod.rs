@@ -330,36 +330,39 @@ impl JwksClient {
     }
 }
 
+#[cfg(feature = "jwt")]
+impl JwksClient {
+    // Decoder for the key named by the token's `kid`. Callers can still add
+    // issuer and audience requirements before decoding.
+    pub async fn decoder_for(&self, token: &str) -> Result<crate::jwt::JwtDecoder> {
+        use crate::jwt::JwtDecoder;
+        use jsonwebtoken::Algorithm;
+
+        let header = crate::jwt::decode_header(token)?;
+        let kid = header.kid.ok_or_else(|| Error::Token("Token missing kid".into()))?;
+        let jwk = self.get_key(&kid).await?;
+
+        let constructor: fn(&[u8]) -> Result<JwtDecoder> = match (header.alg, jwk.kty.as_str()) {
+            (Algorithm::RS256, "RSA") => JwtDecoder::rs256,
+            (Algorithm::RS384, "RSA") => JwtDecoder::rs384,
+            (Algorithm::RS512, "RSA") => JwtDecoder::rs512,
+            (Algorithm::ES256, "EC") => JwtDecoder::es256,
+            (Algorithm::ES384, "EC") => JwtDecoder::es384,
+            (alg, kty) => {
+                return Err(Error::Token(format!(
+                    "Unsupported algorithm {:?} for key type {}",
+                    alg, kty
+                )))
+            }
+        };
+        constructor(jwk.to_pem()?.as_bytes())
+    }
+}
+
 #[cfg(feature = "jwt")]
 pub async fn verify_with_jwks<T: serde::de::DeserializeOwned>(
     token: &str,
     jwks_client: &JwksClient,
 ) -> Result<jsonwebtoken::TokenData<T>> {
-    use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
-
-    let header = decode_header(token).map_err(|e| Error::Token(e.to_string()))?;
-
-    let kid = header.kid.ok_or_else(|| Error::Token("Token missing kid".into()))?;
-    let jwk = jwks_client.get_key(&kid).await?;
-
-    let algorithm = match header.alg {
-        Algorithm::RS256 => Algorithm::RS256,
-        Algorithm::RS384 => Algorithm::RS384,
-        Algorithm::RS512 => Algorithm::RS512,
-        Algorithm::ES256 => Algorithm::ES256,
-        Algorithm::ES384 => Algorithm::ES384,
-        alg => return Err(Error::Token(format!("Unsupported algorithm: {:?}", alg))),
-    };
-
-    let pem = jwk.to_pem()?;
-    let decoding_key = if jwk.is_rsa() {
-        DecodingKey::from_rsa_pem(pem.as_bytes()).map_err(|e| Error::Token(e.to_string()))?
-    } else if jwk.is_ec() {
-        DecodingKey::from_ec_pem(pem.as_bytes()).map_err(|e| Error::Token(e.to_string()))?
-    } else {
-        return Err(Error::Token(format!("Unsupported key type: {}", jwk.kty)));
-    };
-
-    let validation = Validation::new(algorithm);
-    decode(token, &decoding_key, &validation).map_err(|e| Error::Token(e.to_string()))
+    jwks_client.decoder_for(token).await?.decode(token)
 }
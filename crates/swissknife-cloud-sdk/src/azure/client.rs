@@ -0,0 +1,296 @@
+use crate::signing::{hmac_sha256, sha256_hex, uri_encode};
+use crate::{Error, Result, UploadOptions};
+use base64::Engine;
+use chrono::Utc;
+use reqwest::{Client, Method, Response};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+pub(crate) const API_VERSION: &str = "2021-08-06";
+
+// Azure discards uncommitted blocks after seven days, so an upload that was
+// neither completed nor aborted by then can't be completed anymore.
+const UNCOMMITTED_BLOCK_LIFETIME: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+pub(crate) struct PendingUpload {
+    pub options: UploadOptions,
+    started: Instant,
+}
+
+#[derive(Clone)]
+enum Credentials {
+    SharedKey(Vec<u8>),
+    Sas(String),
+}
+
+#[derive(Clone)]
+pub struct AzureBlobClient {
+    account: String,
+    credentials: Credentials,
+    base_url: String,
+    client: Client,
+    pending_uploads: Arc<Mutex<HashMap<String, PendingUpload>>>,
+}
+
+impl AzureBlobClient {
+    pub fn new(account: &str, account_key: &str) -> Result<Self> {
+        let key = base64::engine::general_purpose::STANDARD
+            .decode(account_key)
+            .map_err(|e| Error::Auth(format!("Account key is not valid base64: {}", e)))?;
+
+        Ok(Self::from_credentials(account, Credentials::SharedKey(key)))
+    }
+
+    pub fn with_sas_token(account: &str, sas_token: &str) -> Self {
+        Self::from_credentials(account, Credentials::Sas(sas_token.trim_start_matches('?').to_string()))
+    }
+
+    fn from_credentials(account: &str, credentials: Credentials) -> Self {
+        Self {
+            account: account.to_string(),
+            credentials,
+            base_url: format!("https://{}.blob.core.windows.net", account),
+            client: Client::new(),
+            pending_uploads: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn account(&self) -> &str {
+        &self.account
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    // Identifies the account and the credential; a SAS token may be scoped to
+    // a single container, so clients with different tokens can't share copies.
+    pub(crate) fn credential_id(&self) -> String {
+        match &self.credentials {
+            Credentials::SharedKey(_) => format!("{}:shared-key", self.account),
+            Credentials::Sas(token) => format!("{}:sas:{}", self.account, &sha256_hex(token.as_bytes())[..16]),
+        }
+    }
+
+    // Also forgets uploads abandoned long enough that Azure has dropped their blocks.
+    pub(crate) fn start_upload(&self, upload_id: &str, options: &UploadOptions) {
+        let mut pending = self.pending_uploads.lock().unwrap();
+        pending.retain(|_, upload| upload.started.elapsed() < UNCOMMITTED_BLOCK_LIFETIME);
+        pending.insert(
+            upload_id.to_string(),
+            PendingUpload {
+                options: options.clone(),
+                started: Instant::now(),
+            },
+        );
+    }
+
+    pub(crate) fn finish_upload(&self, upload_id: &str) -> Option<UploadOptions> {
+        self.pending_uploads.lock().unwrap().remove(upload_id).map(|upload| upload.options)
+    }
+
+    pub(crate) fn account_key(&self) -> Option<&[u8]> {
+        match &self.credentials {
+            Credentials::SharedKey(key) => Some(key),
+            Credentials::Sas(_) => None,
+        }
+    }
+
+    pub(crate) fn sas_token(&self) -> Option<&str> {
+        match &self.credentials {
+            Credentials::SharedKey(_) => None,
+            Credentials::Sas(token) => Some(token),
+        }
+    }
+
+    pub(crate) fn resource_url(&self, container: Option<&str>, blob: Option<&str>) -> String {
+        let mut url = self.base_url.clone();
+        if let Some(container) = container {
+            url.push('/');
+            url.push_str(&uri_encode(container, true));
+            if let Some(blob) = blob {
+                url.push('/');
+                url.push_str(&uri_encode(blob, false));
+            }
+        }
+        url
+    }
+
+    pub(crate) async fn send(
+        &self,
+        method: Method,
+        container: Option<&str>,
+        blob: Option<&str>,
+        query: Vec<(String, String)>,
+        mut headers: Vec<(String, String)>,
+        body: Vec<u8>,
+    ) -> Result<Response> {
+        let url = self.resource_url(container, blob);
+
+        headers.push(("x-ms-date".to_string(), Utc::now().format("%a, %d %b %Y %H:%M:%S GMT").to_string()));
+        headers.push(("x-ms-version".to_string(), API_VERSION.to_string()));
+
+        let mut query_string = query
+            .iter()
+            .map(|(k, v)| format!("{}={}", uri_encode(k, true), uri_encode(v, true)))
+            .collect::<Vec<_>>();
+
+        match &self.credentials {
+            Credentials::SharedKey(key) => {
+                let path = url::Url::parse(&url)
+                    .map(|u| u.path().to_string())
+                    .map_err(|e| Error::Api { message: format!("Invalid URL {}: {}", url, e), code: None })?;
+                let signature = shared_key_signature(
+                    key,
+                    &self.account,
+                    method.as_str(),
+                    &path,
+                    &query,
+                    &headers,
+                    body.len(),
+                );
+                headers.push(("authorization".to_string(), format!("SharedKey {}:{}", self.account, signature)));
+            }
+            Credentials::Sas(token) => query_string.push(token.clone()),
+        }
+
+        let full_url = if query_string.is_empty() {
+            url
+        } else {
+            format!("{}?{}", url, query_string.join("&"))
+        };
+
+        let mut request = self.client.request(method, full_url);
+        for (name, value) in &headers {
+            request = request.header(name.as_str(), value.as_str());
+        }
+        if body.is_empty() {
+            request = request.header("content-length", "0");
+        } else {
+            request = request.body(body);
+        }
+
+        let response = request.send().await?;
+        if !response.status().is_success() {
+            return Err(error_from_response(response, container, blob).await);
+        }
+
+        Ok(response)
+    }
+}
+
+fn shared_key_signature(
+    key: &[u8],
+    account: &str,
+    method: &str,
+    path: &str,
+    query: &[(String, String)],
+    headers: &[(String, String)],
+    content_length: usize,
+) -> String {
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+            .unwrap_or("")
+    };
+    let content_length = if content_length == 0 { String::new() } else { content_length.to_string() };
+
+    let mut ms_headers: Vec<(String, &str)> = headers
+        .iter()
+        .filter(|(k, _)| k.to_lowercase().starts_with("x-ms-"))
+        .map(|(k, v)| (k.to_lowercase(), v.trim()))
+        .collect();
+    ms_headers.sort();
+    let canonical_headers: String = ms_headers.iter().map(|(k, v)| format!("{}:{}\n", k, v)).collect();
+
+    let mut params: Vec<(String, &str)> = query.iter().map(|(k, v)| (k.to_lowercase(), v.as_str())).collect();
+    params.sort();
+    let mut canonical_resource = format!("/{}{}", account, path);
+    for (name, value) in params {
+        canonical_resource.push_str(&format!("\n{}:{}", name, value));
+    }
+
+    let string_to_sign = format!(
+        "{}\n{}\n{}\n{}\n{}\n{}\n\n{}\n{}\n{}\n{}\n{}\n{}{}",
+        method,
+        header("content-encoding"),
+        header("content-language"),
+        content_length,
+        header("content-md5"),
+        header("content-type"),
+        header("if-modified-since"),
+        header("if-match"),
+        header("if-none-match"),
+        header("if-unmodified-since"),
+        header("range"),
+        canonical_headers,
+        canonical_resource
+    );
+
+    base64::engine::general_purpose::STANDARD.encode(hmac_sha256(key, string_to_sign.as_bytes()))
+}
+
+async fn error_from_response(response: Response, container: Option<&str>, blob: Option<&str>) -> Error {
+    let status = response.status().as_u16();
+    let header_code = response
+        .headers()
+        .get("x-ms-error-code")
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.to_string());
+    let body = response.text().await.unwrap_or_default();
+    let parsed: Option<AzureErrorBody> = quick_xml::de::from_str(&body).ok();
+
+    let code = parsed.as_ref().and_then(|e| e.code.clone()).or(header_code);
+    let message = parsed
+        .and_then(|e| e.message)
+        .unwrap_or_else(|| format!("Azure request failed with status {}", status));
+
+    match code.as_deref() {
+        Some("ContainerNotFound") => Error::BucketNotFound(container.unwrap_or_default().to_string()),
+        Some("BlobNotFound") | Some("InvalidBlockList") => Error::ObjectNotFound(blob.unwrap_or_default().to_string()),
+        Some("AuthenticationFailed") | Some("InvalidAuthenticationInfo") => Error::Auth(message),
+        Some("AuthorizationFailure") | Some("AuthorizationPermissionMismatch") | Some("InsufficientAccountPermissions") => {
+            Error::PermissionDenied(message)
+        }
+        None if status == 404 => match blob {
+            Some(blob) => Error::ObjectNotFound(blob.to_string()),
+            None => Error::BucketNotFound(container.unwrap_or_default().to_string()),
+        },
+        None if status == 403 => Error::PermissionDenied(message),
+        _ => Error::Api { message, code },
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct AzureErrorBody {
+    code: Option<String>,
+    message: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_abandoned_uploads_are_forgotten() {
+        let client = AzureBlobClient::with_sas_token("account", "sig=abc");
+        client.start_upload("abandoned", &UploadOptions::default());
+        let expired = Instant::now().checked_sub(UNCOMMITTED_BLOCK_LIFETIME + Duration::from_secs(1));
+        client.pending_uploads.lock().unwrap().get_mut("abandoned").unwrap().started = expired.unwrap();
+        client.start_upload("recent", &UploadOptions::default());
+
+        let pending = client.pending_uploads.lock().unwrap();
+        assert!(!pending.contains_key("abandoned"));
+        assert!(pending.contains_key("recent"));
+    }
+}
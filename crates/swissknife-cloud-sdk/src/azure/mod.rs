@@ -0,0 +1,4 @@
+mod client;
+mod operations;
+
+pub use client::AzureBlobClient;
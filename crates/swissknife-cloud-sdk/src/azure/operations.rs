@@ -0,0 +1,538 @@
+use crate::azure::client::API_VERSION;
+use crate::azure::AzureBlobClient;
+use crate::signing::{hmac_sha256, uri_encode};
+use crate::{
+    multipart_upload_stream, Bucket, ByteRange, ByteStream, CopyResult, DeleteError, DeleteObjectsResult, Error,
+    ListOptions, ListResult, MultipartUpload, MultipartUploadProvider, Object, ObjectStorageProvider, PresignOptions,
+    PresignedUrl, PresignedUrlProvider, Result, StreamUploadOptions, StreamingObjectProvider, UploadOptions,
+    UploadPart, UploadResult,
+};
+use async_trait::async_trait;
+use base64::Engine;
+use chrono::{DateTime, Duration, Utc};
+use futures_util::TryStreamExt;
+use quick_xml::escape::escape;
+use reqwest::header::HeaderMap;
+use reqwest::Method;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+const DEFAULT_PRESIGN_EXPIRY: u64 = 3600;
+const MAX_PRESIGN_EXPIRY: u64 = 604_800;
+// The service reads the copy source for as long as the copy runs, so the
+// source SAS has to outlive it.
+const COPY_SOURCE_EXPIRY: u64 = 86_400;
+const COPY_POLL_INITIAL: std::time::Duration = std::time::Duration::from_millis(200);
+const COPY_POLL_MAX: std::time::Duration = std::time::Duration::from_secs(5);
+
+impl AzureBlobClient {
+    fn presign_url(&self, permissions: &str, method: &str, container: &str, blob: &str, options: &PresignOptions) -> Result<PresignedUrl> {
+        let key = self
+            .account_key()
+            .ok_or_else(|| Error::Auth("Generating SAS URLs requires an account key".to_string()))?;
+
+        let expires_in = match options.expires_in_seconds {
+            0 => DEFAULT_PRESIGN_EXPIRY,
+            secs => secs.min(MAX_PRESIGN_EXPIRY),
+        };
+        let now = Utc::now();
+        let expires_at = now + Duration::seconds(expires_in as i64);
+        let expiry = expires_at.format("%Y-%m-%dT%H:%M:%SZ").to_string();
+        let protocol = if self.base_url().starts_with("https://") { "https" } else { "https,http" };
+        let content_type = options.content_type.as_deref().unwrap_or("");
+
+        let string_to_sign = [
+            permissions,
+            "",
+            &expiry,
+            &format!("/blob/{}/{}/{}", self.account(), container, blob),
+            "",
+            "",
+            protocol,
+            API_VERSION,
+            "b",
+            "",
+            "",
+            "",
+            "",
+            "",
+            "",
+            content_type,
+        ]
+        .join("\n");
+        let signature = base64::engine::general_purpose::STANDARD.encode(hmac_sha256(key, string_to_sign.as_bytes()));
+
+        let mut query = vec![
+            ("sv", API_VERSION.to_string()),
+            ("se", expiry),
+            ("sr", "b".to_string()),
+            ("sp", permissions.to_string()),
+            ("spr", protocol.to_string()),
+        ];
+        if !content_type.is_empty() {
+            query.push(("rsct", content_type.to_string()));
+        }
+        query.push(("sig", signature));
+
+        let query_string = query
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, uri_encode(v, true)))
+            .collect::<Vec<_>>()
+            .join("&");
+
+        Ok(PresignedUrl {
+            url: format!("{}?{}", self.resource_url(Some(container), Some(blob)), query_string),
+            method: method.to_string(),
+            expires_at,
+        })
+    }
+
+    async fn blob_properties(&self, container: &str, blob: &str) -> Result<HeaderMap> {
+        let response = self
+            .send(Method::HEAD, Some(container), Some(blob), Vec::new(), Vec::new(), Vec::new())
+            .await?;
+        Ok(response.headers().clone())
+    }
+
+    // The copy source is fetched by the service, so it has to carry its own
+    // authorization unless the container is public.
+    fn copy_source_url(&self, container: &str, blob: &str) -> Result<String> {
+        if let Some(token) = self.sas_token() {
+            return Ok(format!("{}?{}", self.resource_url(Some(container), Some(blob)), token));
+        }
+        let options = PresignOptions {
+            expires_in_seconds: COPY_SOURCE_EXPIRY,
+            content_type: None,
+        };
+        Ok(self.presign_url("r", "GET", container, blob, &options)?.url)
+    }
+
+    fn block_id(upload_id: &str, part_number: u32) -> String {
+        base64::engine::general_purpose::STANDARD.encode(format!("{}-{:06}", upload_id, part_number))
+    }
+}
+
+#[async_trait]
+impl ObjectStorageProvider for AzureBlobClient {
+    async fn list_buckets(&self) -> Result<Vec<Bucket>> {
+        let mut buckets = Vec::new();
+        let mut marker: Option<String> = None;
+
+        loop {
+            let mut query = vec![("comp".to_string(), "list".to_string())];
+            if let Some(m) = &marker {
+                query.push(("marker".to_string(), m.clone()));
+            }
+
+            let response = self.send(Method::GET, None, None, query, Vec::new(), Vec::new()).await?;
+            let result: ContainerEnumerationResults = parse_xml(&response.text().await?)?;
+
+            buckets.extend(result.containers.container.into_iter().map(|c| Bucket {
+                name: c.name,
+                region: None,
+                created_at: c.properties.and_then(|p| parse_http_date(p.last_modified.as_deref())),
+            }));
+
+            marker = result.next_marker.filter(|m| !m.is_empty());
+            if marker.is_none() {
+                break;
+            }
+        }
+
+        Ok(buckets)
+    }
+
+    async fn create_bucket(&self, name: &str, _region: Option<&str>) -> Result<Bucket> {
+        let query = vec![("restype".to_string(), "container".to_string())];
+        self.send(Method::PUT, Some(name), None, query, Vec::new(), Vec::new()).await?;
+
+        Ok(Bucket {
+            name: name.to_string(),
+            region: None,
+            created_at: Some(Utc::now()),
+        })
+    }
+
+    async fn delete_bucket(&self, name: &str) -> Result<()> {
+        let query = vec![("restype".to_string(), "container".to_string())];
+        self.send(Method::DELETE, Some(name), None, query, Vec::new(), Vec::new()).await?;
+        Ok(())
+    }
+
+    async fn bucket_exists(&self, name: &str) -> Result<bool> {
+        let query = vec![("restype".to_string(), "container".to_string())];
+        match self.send(Method::HEAD, Some(name), None, query, Vec::new(), Vec::new()).await {
+            Ok(_) => Ok(true),
+            Err(Error::BucketNotFound(_)) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn list_objects(&self, bucket: &str, options: &ListOptions) -> Result<ListResult> {
+        let mut query = vec![
+            ("restype".to_string(), "container".to_string()),
+            ("comp".to_string(), "list".to_string()),
+            ("include".to_string(), "metadata".to_string()),
+        ];
+        if let Some(prefix) = &options.prefix {
+            query.push(("prefix".to_string(), prefix.clone()));
+        }
+        if let Some(delimiter) = &options.delimiter {
+            query.push(("delimiter".to_string(), delimiter.clone()));
+        }
+        if let Some(max_keys) = options.max_keys {
+            query.push(("maxresults".to_string(), max_keys.to_string()));
+        }
+        if let Some(token) = &options.continuation_token {
+            query.push(("marker".to_string(), token.clone()));
+        }
+
+        let response = self.send(Method::GET, Some(bucket), None, query, Vec::new(), Vec::new()).await?;
+        let result: BlobEnumerationResults = parse_xml(&response.text().await?)?;
+
+        let mut objects = Vec::new();
+        let mut common_prefixes = Vec::new();
+        for item in result.blobs.items {
+            match item {
+                BlobItem::Blob(blob) => objects.push(Object {
+                    key: blob.name,
+                    bucket: bucket.to_string(),
+                    size: blob.properties.content_length.unwrap_or(0),
+                    content_type: blob.properties.content_type,
+                    etag: blob.properties.etag.map(|e| e.trim_matches('"').to_string()),
+                    last_modified: parse_http_date(blob.properties.last_modified.as_deref()),
+                    storage_class: blob.properties.access_tier,
+                    metadata: blob.metadata.unwrap_or_default(),
+                }),
+                BlobItem::BlobPrefix(prefix) => common_prefixes.push(prefix.name),
+            }
+        }
+
+        let next_marker = result.next_marker.filter(|m| !m.is_empty());
+        Ok(ListResult {
+            objects,
+            common_prefixes,
+            is_truncated: next_marker.is_some(),
+            next_continuation_token: next_marker,
+        })
+    }
+
+    async fn get_object(&self, bucket: &str, key: &str) -> Result<Vec<u8>> {
+        let response = self
+            .send(Method::GET, Some(bucket), Some(key), Vec::new(), Vec::new(), Vec::new())
+            .await?;
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    async fn get_object_metadata(&self, bucket: &str, key: &str) -> Result<Object> {
+        let headers = self.blob_properties(bucket, key).await?;
+        let metadata = headers
+            .iter()
+            .filter_map(|(name, value)| {
+                let name = name.as_str().strip_prefix("x-ms-meta-")?;
+                Some((name.to_string(), value.to_str().ok()?.to_string()))
+            })
+            .collect();
+
+        Ok(Object {
+            key: key.to_string(),
+            bucket: bucket.to_string(),
+            size: header_str(&headers, "content-length").and_then(|v| v.parse().ok()).unwrap_or(0),
+            content_type: header_str(&headers, "content-type"),
+            etag: header_str(&headers, "etag").map(|e| e.trim_matches('"').to_string()),
+            last_modified: parse_http_date(header_str(&headers, "last-modified").as_deref()),
+            storage_class: header_str(&headers, "x-ms-access-tier"),
+            metadata,
+        })
+    }
+
+    async fn put_object(&self, bucket: &str, key: &str, data: &[u8], options: &UploadOptions) -> Result<UploadResult> {
+        let mut headers = blob_headers(options);
+        headers.push(("x-ms-blob-type".to_string(), "BlockBlob".to_string()));
+
+        let response = self
+            .send(Method::PUT, Some(bucket), Some(key), Vec::new(), headers, data.to_vec())
+            .await?;
+
+        Ok(UploadResult {
+            key: key.to_string(),
+            bucket: bucket.to_string(),
+            etag: header_str(response.headers(), "etag").map(|e| e.trim_matches('"').to_string()),
+            version_id: header_str(response.headers(), "x-ms-version-id"),
+        })
+    }
+
+    async fn delete_object(&self, bucket: &str, key: &str) -> Result<()> {
+        self.send(Method::DELETE, Some(bucket), Some(key), Vec::new(), Vec::new(), Vec::new())
+            .await?;
+        Ok(())
+    }
+
+    async fn delete_objects(&self, bucket: &str, keys: &[&str]) -> Result<Vec<String>> {
+        Ok(self.delete_objects_detailed(bucket, keys).await?.deleted)
+    }
+
+    async fn delete_objects_detailed(&self, bucket: &str, keys: &[&str]) -> Result<DeleteObjectsResult> {
+        let mut result = DeleteObjectsResult::default();
+        for key in keys {
+            match self.delete_object(bucket, key).await {
+                Ok(()) | Err(Error::ObjectNotFound(_)) => result.deleted.push(key.to_string()),
+                Err(e) => result.errors.push(DeleteError::new(key, e)),
+            }
+        }
+        Ok(result)
+    }
+
+    async fn copy_object(&self, source_bucket: &str, source_key: &str, dest_bucket: &str, dest_key: &str) -> Result<CopyResult> {
+        let headers = vec![("x-ms-copy-source".to_string(), self.copy_source_url(source_bucket, source_key)?)];
+
+        let response = self
+            .send(Method::PUT, Some(dest_bucket), Some(dest_key), Vec::new(), headers, Vec::new())
+            .await?;
+        let mut headers = response.headers().clone();
+
+        // Cross-account and large copies run asynchronously; wait for them so
+        // the destination is readable once this returns.
+        let mut delay = COPY_POLL_INITIAL;
+        loop {
+            match header_str(&headers, "x-ms-copy-status").as_deref() {
+                Some("pending") => {
+                    tokio::time::sleep(delay).await;
+                    delay = (delay * 2).min(COPY_POLL_MAX);
+                    headers = self.blob_properties(dest_bucket, dest_key).await?;
+                }
+                Some(status @ ("failed" | "aborted")) => {
+                    let description = header_str(&headers, "x-ms-copy-status-description").unwrap_or_default();
+                    return Err(Error::Api {
+                        message: format!("Copy of {} {}: {}", source_key, status, description),
+                        code: Some(status.to_string()),
+                    });
+                }
+                _ => break,
+            }
+        }
+
+        Ok(CopyResult {
+            key: dest_key.to_string(),
+            bucket: dest_bucket.to_string(),
+            etag: header_str(&headers, "etag").map(|e| e.trim_matches('"').to_string()),
+            version_id: header_str(&headers, "x-ms-version-id"),
+        })
+    }
+
+    async fn object_exists(&self, bucket: &str, key: &str) -> Result<bool> {
+        match self.blob_properties(bucket, key).await {
+            Ok(_) => Ok(true),
+            Err(Error::ObjectNotFound(_)) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn storage_id(&self) -> Option<String> {
+        Some(format!("azure:{}:{}", self.base_url(), self.credential_id()))
+    }
+}
+
+#[async_trait]
+impl PresignedUrlProvider for AzureBlobClient {
+    async fn generate_presigned_get_url(&self, bucket: &str, key: &str, options: &PresignOptions) -> Result<PresignedUrl> {
+        self.presign_url("r", "GET", bucket, key, options)
+    }
+
+    async fn generate_presigned_put_url(&self, bucket: &str, key: &str, options: &PresignOptions) -> Result<PresignedUrl> {
+        self.presign_url("cw", "PUT", bucket, key, options)
+    }
+}
+
+#[async_trait]
+impl MultipartUploadProvider for AzureBlobClient {
+    async fn create_multipart_upload(&self, bucket: &str, key: &str, options: &UploadOptions) -> Result<MultipartUpload> {
+        let upload_id = format!("{:016x}", Utc::now().timestamp_nanos_opt().unwrap_or_default());
+        self.start_upload(&upload_id, options);
+
+        Ok(MultipartUpload {
+            upload_id,
+            key: key.to_string(),
+            bucket: bucket.to_string(),
+        })
+    }
+
+    async fn upload_part(&self, bucket: &str, key: &str, upload_id: &str, part_number: u32, data: &[u8]) -> Result<UploadPart> {
+        let block_id = Self::block_id(upload_id, part_number);
+        let query = vec![
+            ("comp".to_string(), "block".to_string()),
+            ("blockid".to_string(), block_id.clone()),
+        ];
+
+        self.send(Method::PUT, Some(bucket), Some(key), query, Vec::new(), data.to_vec())
+            .await?;
+
+        Ok(UploadPart {
+            part_number,
+            etag: block_id,
+        })
+    }
+
+    async fn complete_multipart_upload(&self, bucket: &str, key: &str, upload_id: &str, parts: &[UploadPart]) -> Result<UploadResult> {
+        let mut sorted: Vec<&UploadPart> = parts.iter().collect();
+        sorted.sort_by_key(|p| p.part_number);
+
+        let mut body = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?><BlockList>");
+        for part in sorted {
+            body.push_str(&format!("<Latest>{}</Latest>", escape(&Self::block_id(upload_id, part.part_number))));
+        }
+        body.push_str("</BlockList>");
+
+        let options = self.finish_upload(upload_id).unwrap_or_default();
+        let query = vec![("comp".to_string(), "blocklist".to_string())];
+
+        let response = self
+            .send(Method::PUT, Some(bucket), Some(key), query, blob_headers(&options), body.into_bytes())
+            .await?;
+
+        Ok(UploadResult {
+            key: key.to_string(),
+            bucket: bucket.to_string(),
+            etag: header_str(response.headers(), "etag").map(|e| e.trim_matches('"').to_string()),
+            version_id: header_str(response.headers(), "x-ms-version-id"),
+        })
+    }
+
+    async fn abort_multipart_upload(&self, _bucket: &str, _key: &str, upload_id: &str) -> Result<()> {
+        self.finish_upload(upload_id);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl StreamingObjectProvider for AzureBlobClient {
+    async fn get_object_stream(&self, bucket: &str, key: &str, range: Option<ByteRange>) -> Result<ByteStream> {
+        let headers = range
+            .map(|r| vec![("x-ms-range".to_string(), r.header_value())])
+            .unwrap_or_default();
+        let response = self
+            .send(Method::GET, Some(bucket), Some(key), Vec::new(), headers, Vec::new())
+            .await?;
+        Ok(Box::pin(response.bytes_stream().map_err(Error::from)))
+    }
+
+    async fn put_object_stream(&self, bucket: &str, key: &str, stream: ByteStream, options: &StreamUploadOptions) -> Result<UploadResult> {
+        multipart_upload_stream(self, bucket, key, stream, options).await
+    }
+}
+
+fn blob_headers(options: &UploadOptions) -> Vec<(String, String)> {
+    let mut headers = Vec::new();
+    if let Some(v) = &options.content_type {
+        headers.push(("x-ms-blob-content-type".to_string(), v.clone()));
+    }
+    if let Some(v) = &options.content_encoding {
+        headers.push(("x-ms-blob-content-encoding".to_string(), v.clone()));
+    }
+    if let Some(v) = &options.cache_control {
+        headers.push(("x-ms-blob-cache-control".to_string(), v.clone()));
+    }
+    if let Some(v) = &options.content_disposition {
+        headers.push(("x-ms-blob-content-disposition".to_string(), v.clone()));
+    }
+    if let Some(v) = &options.storage_class {
+        headers.push(("x-ms-access-tier".to_string(), v.clone()));
+    }
+    for (k, v) in &options.metadata {
+        headers.push((format!("x-ms-meta-{}", k.to_lowercase()), v.clone()));
+    }
+    headers
+}
+
+fn header_str(headers: &HeaderMap, name: &str) -> Option<String> {
+    headers.get(name).and_then(|v| v.to_str().ok()).map(|v| v.to_string())
+}
+
+fn parse_http_date(value: Option<&str>) -> Option<DateTime<Utc>> {
+    value
+        .and_then(|v| DateTime::parse_from_rfc2822(v).ok())
+        .map(|d| d.with_timezone(&Utc))
+}
+
+fn parse_xml<T: for<'de> Deserialize<'de>>(xml: &str) -> Result<T> {
+    quick_xml::de::from_str(xml.trim_start_matches('\u{feff}')).map_err(|e| Error::Api {
+        message: format!("Failed to parse Azure response: {}", e),
+        code: None,
+    })
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ContainerEnumerationResults {
+    #[serde(default)]
+    containers: ContainerList,
+    next_marker: Option<String>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ContainerList {
+    #[serde(default)]
+    container: Vec<ContainerEntry>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ContainerEntry {
+    name: String,
+    properties: Option<ContainerProperties>,
+}
+
+#[derive(Deserialize)]
+struct ContainerProperties {
+    #[serde(rename = "Last-Modified")]
+    last_modified: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct BlobEnumerationResults {
+    #[serde(default)]
+    blobs: BlobList,
+    next_marker: Option<String>,
+}
+
+#[derive(Default, Deserialize)]
+struct BlobList {
+    #[serde(rename = "$value", default)]
+    items: Vec<BlobItem>,
+}
+
+#[derive(Deserialize)]
+enum BlobItem {
+    Blob(BlobEntry),
+    BlobPrefix(BlobPrefixEntry),
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct BlobEntry {
+    name: String,
+    properties: BlobProperties,
+    metadata: Option<HashMap<String, String>>,
+}
+
+#[derive(Deserialize)]
+struct BlobPrefixEntry {
+    #[serde(rename = "Name")]
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct BlobProperties {
+    #[serde(rename = "Last-Modified")]
+    last_modified: Option<String>,
+    #[serde(rename = "Etag")]
+    etag: Option<String>,
+    #[serde(rename = "Content-Length")]
+    content_length: Option<u64>,
+    #[serde(rename = "Content-Type")]
+    content_type: Option<String>,
+    #[serde(rename = "AccessTier")]
+    access_tier: Option<String>,
+}
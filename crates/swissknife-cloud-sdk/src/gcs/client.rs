@@ -0,0 +1,238 @@
+use crate::{Error, Result};
+use chrono::{DateTime, Duration, Utc};
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use reqwest::{Client, RequestBuilder, Response};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+const DEFAULT_BASE_URL: &str = "https://storage.googleapis.com";
+const DEFAULT_TOKEN_URI: &str = "https://oauth2.googleapis.com/token";
+const STORAGE_SCOPE: &str = "https://www.googleapis.com/auth/devstorage.full_control";
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ServiceAccountKey {
+    pub client_email: String,
+    pub private_key: String,
+    #[serde(default)]
+    pub project_id: Option<String>,
+    #[serde(default)]
+    pub token_uri: Option<String>,
+}
+
+#[derive(Clone)]
+enum Credentials {
+    AccessToken(String),
+    ServiceAccount(ServiceAccountKey),
+}
+
+#[derive(Clone)]
+struct CachedToken {
+    token: String,
+    expires_at: DateTime<Utc>,
+}
+
+#[derive(Clone)]
+pub struct GcsClient {
+    credentials: Credentials,
+    project_id: Option<String>,
+    base_url: String,
+    client: Client,
+    token_cache: Arc<Mutex<Option<CachedToken>>>,
+}
+
+impl GcsClient {
+    pub fn new(access_token: &str) -> Self {
+        Self {
+            credentials: Credentials::AccessToken(access_token.to_string()),
+            project_id: None,
+            base_url: DEFAULT_BASE_URL.to_string(),
+            client: Client::new(),
+            token_cache: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    pub fn from_service_account(key: ServiceAccountKey) -> Self {
+        Self {
+            project_id: key.project_id.clone(),
+            credentials: Credentials::ServiceAccount(key),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            client: Client::new(),
+            token_cache: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    pub fn from_service_account_json(json: &str) -> Result<Self> {
+        let key: ServiceAccountKey = serde_json::from_str(json)?;
+        Ok(Self::from_service_account(key))
+    }
+
+    pub fn with_project_id(mut self, project_id: &str) -> Self {
+        self.project_id = Some(project_id.to_string());
+        self
+    }
+
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub fn project_id(&self) -> Option<&str> {
+        self.project_id.as_deref()
+    }
+
+    pub(crate) fn client(&self) -> &Client {
+        &self.client
+    }
+
+    pub(crate) fn service_account(&self) -> Option<&ServiceAccountKey> {
+        match &self.credentials {
+            Credentials::ServiceAccount(key) => Some(key),
+            Credentials::AccessToken(_) => None,
+        }
+    }
+
+    pub(crate) fn require_project(&self) -> Result<&str> {
+        self.project_id
+            .as_deref()
+            .ok_or_else(|| Error::Auth("A GCS project id is required for bucket operations".to_string()))
+    }
+
+    pub(crate) fn json_url(&self, path: &str) -> String {
+        format!("{}/storage/v1{}", self.base_url, path)
+    }
+
+    pub(crate) fn upload_url(&self, path: &str) -> String {
+        format!("{}/upload/storage/v1{}", self.base_url, path)
+    }
+
+    async fn access_token(&self) -> Result<String> {
+        let key = match &self.credentials {
+            Credentials::AccessToken(token) => return Ok(token.clone()),
+            Credentials::ServiceAccount(key) => key,
+        };
+
+        if let Some(cached) = self.token_cache.lock().unwrap().as_ref() {
+            if cached.expires_at > Utc::now() + Duration::seconds(60) {
+                return Ok(cached.token.clone());
+            }
+        }
+
+        let token_uri = key.token_uri.as_deref().unwrap_or(DEFAULT_TOKEN_URI);
+        let now = Utc::now();
+        let claims = JwtClaims {
+            iss: &key.client_email,
+            scope: STORAGE_SCOPE,
+            aud: token_uri,
+            iat: now.timestamp(),
+            exp: (now + Duration::hours(1)).timestamp(),
+        };
+        let encoding_key = EncodingKey::from_rsa_pem(key.private_key.as_bytes())
+            .map_err(|e| Error::Auth(format!("Invalid service account private key: {}", e)))?;
+        let assertion = jsonwebtoken::encode(&Header::new(Algorithm::RS256), &claims, &encoding_key)
+            .map_err(|e| Error::Auth(format!("Failed to sign JWT assertion: {}", e)))?;
+
+        let response = self
+            .client
+            .post(token_uri)
+            .form(&[
+                ("grant_type", "urn:ietf:params:oauth:grant-type:jwt-bearer"),
+                ("assertion", assertion.as_str()),
+            ])
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(Error::Auth(format!("Token exchange failed: {}", body)));
+        }
+
+        let token: TokenResponse = response.json().await?;
+        *self.token_cache.lock().unwrap() = Some(CachedToken {
+            token: token.access_token.clone(),
+            expires_at: now + Duration::seconds(token.expires_in),
+        });
+
+        Ok(token.access_token)
+    }
+
+    pub(crate) async fn send(&self, request: RequestBuilder, bucket: Option<&str>, key: Option<&str>) -> Result<Response> {
+        let token = self.access_token().await?;
+        let response = request.bearer_auth(token).send().await?;
+
+        if !response.status().is_success() {
+            return Err(error_from_response(response, bucket, key).await);
+        }
+
+        Ok(response)
+    }
+
+    pub(crate) async fn send_upload_chunk(&self, request: RequestBuilder, bucket: &str, key: &str) -> Result<Option<Response>> {
+        let token = self.access_token().await?;
+        let response = request.bearer_auth(token).send().await?;
+
+        if response.status().as_u16() == 308 {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            return Err(error_from_response(response, Some(bucket), Some(key)).await);
+        }
+
+        Ok(Some(response))
+    }
+}
+
+async fn error_from_response(response: Response, bucket: Option<&str>, key: Option<&str>) -> Error {
+    let status = response.status().as_u16();
+    let body = response.text().await.unwrap_or_default();
+    let message = serde_json::from_str::<GcsErrorResponse>(&body)
+        .ok()
+        .map(|e| e.error.message)
+        .unwrap_or_else(|| format!("GCS request failed with status {}", status));
+
+    match status {
+        401 => Error::Auth(message),
+        403 => Error::PermissionDenied(message),
+        404 => match key {
+            Some(key) => Error::ObjectNotFound(key.to_string()),
+            None => Error::BucketNotFound(bucket.unwrap_or_default().to_string()),
+        },
+        _ => Error::Api {
+            message,
+            code: Some(status.to_string()),
+        },
+    }
+}
+
+#[derive(Serialize)]
+struct JwtClaims<'a> {
+    iss: &'a str,
+    scope: &'a str,
+    aud: &'a str,
+    iat: i64,
+    exp: i64,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[serde(default = "default_expires_in")]
+    expires_in: i64,
+}
+
+fn default_expires_in() -> i64 {
+    3600
+}
+
+#[derive(Deserialize)]
+struct GcsErrorResponse {
+    error: GcsErrorBody,
+}
+
+#[derive(Deserialize)]
+struct GcsErrorBody {
+    message: String,
+}
@@ -0,0 +1,533 @@
+use crate::gcs::GcsClient;
+use crate::signing::{canonical_query_string, sha256_hex, uri_encode};
+use crate::stream::ChunkReader;
+use crate::{
+    Bucket, ByteRange, ByteStream, CopyResult, DeleteError, DeleteObjectsResult, Error, ListOptions, ListResult,
+    Object, ObjectStorageProvider, PresignOptions, PresignedUrl, PresignedUrlProvider, Result, StreamUploadOptions,
+    StreamingObjectProvider, UploadOptions, UploadResult,
+};
+use async_trait::async_trait;
+use base64::Engine;
+use chrono::{DateTime, Duration, Utc};
+use futures_util::TryStreamExt;
+use jsonwebtoken::{Algorithm, EncodingKey};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const SIGNING_ALGORITHM: &str = "GOOG4-RSA-SHA256";
+const DEFAULT_PRESIGN_EXPIRY: u64 = 3600;
+const MAX_PRESIGN_EXPIRY: u64 = 604_800;
+const MULTIPART_BOUNDARY: &str = "swissknife_gcs_boundary";
+const RESUMABLE_CHUNK_ALIGNMENT: usize = 256 * 1024;
+
+impl GcsClient {
+    fn object_path(bucket: &str, key: &str) -> String {
+        format!("/b/{}/o/{}", uri_encode(bucket, true), uri_encode(key, true))
+    }
+
+    fn presign_url(&self, method: &str, bucket: &str, key: &str, options: &PresignOptions) -> Result<PresignedUrl> {
+        let account = self
+            .service_account()
+            .ok_or_else(|| Error::Auth("Signed URLs require a service account key".to_string()))?;
+
+        let expires_in = match options.expires_in_seconds {
+            0 => DEFAULT_PRESIGN_EXPIRY,
+            secs => secs.min(MAX_PRESIGN_EXPIRY),
+        };
+        let parsed = url::Url::parse(self.base_url()).map_err(|e| Error::Api {
+            message: format!("Invalid base URL: {}", e),
+            code: None,
+        })?;
+        let host = match (parsed.host_str(), parsed.port()) {
+            (Some(host), Some(port)) => format!("{}:{}", host, port),
+            (Some(host), None) => host.to_string(),
+            (None, _) => {
+                return Err(Error::Api {
+                    message: format!("Base URL has no host: {}", self.base_url()),
+                    code: None,
+                })
+            }
+        };
+
+        let now = Utc::now();
+        let datetime = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let scope = format!("{}/auto/storage/goog4_request", now.format("%Y%m%d"));
+        let canonical_uri = format!("/{}/{}", uri_encode(bucket, true), uri_encode(key, false));
+
+        let mut headers = vec![("host".to_string(), host.clone())];
+        if let Some(content_type) = &options.content_type {
+            headers.push(("content-type".to_string(), content_type.clone()));
+        }
+        headers.sort();
+        let canonical_headers: String = headers.iter().map(|(k, v)| format!("{}:{}\n", k, v)).collect();
+        let signed_headers = headers.iter().map(|(k, _)| k.as_str()).collect::<Vec<_>>().join(";");
+
+        let mut query = vec![
+            ("X-Goog-Algorithm".to_string(), SIGNING_ALGORITHM.to_string()),
+            ("X-Goog-Credential".to_string(), format!("{}/{}", account.client_email, scope)),
+            ("X-Goog-Date".to_string(), datetime.clone()),
+            ("X-Goog-Expires".to_string(), expires_in.to_string()),
+            ("X-Goog-SignedHeaders".to_string(), signed_headers.clone()),
+        ];
+
+        let canonical_request = format!(
+            "{}\n{}\n{}\n{}\n{}\nUNSIGNED-PAYLOAD",
+            method,
+            canonical_uri,
+            canonical_query_string(&query),
+            canonical_headers,
+            signed_headers
+        );
+        let string_to_sign = format!(
+            "{}\n{}\n{}\n{}",
+            SIGNING_ALGORITHM,
+            datetime,
+            scope,
+            sha256_hex(canonical_request.as_bytes())
+        );
+
+        let encoding_key = EncodingKey::from_rsa_pem(account.private_key.as_bytes())
+            .map_err(|e| Error::Auth(format!("Invalid service account private key: {}", e)))?;
+        let signature = jsonwebtoken::crypto::sign(string_to_sign.as_bytes(), &encoding_key, Algorithm::RS256)
+            .map_err(|e| Error::Auth(format!("Failed to sign URL: {}", e)))?;
+        let signature = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .decode(signature)
+            .map_err(|e| Error::Auth(format!("Failed to decode signature: {}", e)))?;
+        query.push(("X-Goog-Signature".to_string(), hex::encode(signature)));
+
+        Ok(PresignedUrl {
+            url: format!(
+                "{}://{}{}?{}",
+                parsed.scheme(),
+                host,
+                canonical_uri,
+                canonical_query_string(&query)
+            ),
+            method: method.to_string(),
+            expires_at: now + Duration::seconds(expires_in as i64),
+        })
+    }
+}
+
+#[async_trait]
+impl ObjectStorageProvider for GcsClient {
+    async fn list_buckets(&self) -> Result<Vec<Bucket>> {
+        let project = self.require_project()?;
+        let mut buckets = Vec::new();
+        let mut page_token: Option<String> = None;
+
+        loop {
+            let mut query = vec![("project", project.to_string())];
+            if let Some(token) = &page_token {
+                query.push(("pageToken", token.clone()));
+            }
+
+            let request = self.client().get(self.json_url("/b")).query(&query);
+            let response: GcsBucketList = self.send(request, None, None).await?.json().await?;
+            buckets.extend(response.items.into_iter().map(gcs_to_bucket));
+
+            page_token = response.next_page_token;
+            if page_token.is_none() {
+                break;
+            }
+        }
+
+        Ok(buckets)
+    }
+
+    async fn create_bucket(&self, name: &str, region: Option<&str>) -> Result<Bucket> {
+        let project = self.require_project()?;
+        let body = CreateBucketRequest {
+            name,
+            location: region,
+        };
+
+        let request = self
+            .client()
+            .post(self.json_url("/b"))
+            .query(&[("project", project)])
+            .json(&body);
+        let bucket: GcsBucket = self.send(request, Some(name), None).await?.json().await?;
+
+        Ok(gcs_to_bucket(bucket))
+    }
+
+    async fn delete_bucket(&self, name: &str) -> Result<()> {
+        let request = self.client().delete(self.json_url(&format!("/b/{}", uri_encode(name, true))));
+        self.send(request, Some(name), None).await?;
+        Ok(())
+    }
+
+    async fn bucket_exists(&self, name: &str) -> Result<bool> {
+        let request = self.client().get(self.json_url(&format!("/b/{}", uri_encode(name, true))));
+        match self.send(request, Some(name), None).await {
+            Ok(_) => Ok(true),
+            Err(Error::BucketNotFound(_)) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn list_objects(&self, bucket: &str, options: &ListOptions) -> Result<ListResult> {
+        let mut query = Vec::new();
+        if let Some(prefix) = &options.prefix {
+            query.push(("prefix", prefix.clone()));
+        }
+        if let Some(delimiter) = &options.delimiter {
+            query.push(("delimiter", delimiter.clone()));
+        }
+        if let Some(max_keys) = options.max_keys {
+            query.push(("maxResults", max_keys.to_string()));
+        }
+        if let Some(token) = &options.continuation_token {
+            query.push(("pageToken", token.clone()));
+        }
+
+        let request = self
+            .client()
+            .get(self.json_url(&format!("/b/{}/o", uri_encode(bucket, true))))
+            .query(&query);
+        let response: GcsObjectList = self.send(request, Some(bucket), None).await?.json().await?;
+
+        Ok(ListResult {
+            objects: response.items.into_iter().map(|o| gcs_to_object(bucket, o)).collect(),
+            common_prefixes: response.prefixes,
+            is_truncated: response.next_page_token.is_some(),
+            next_continuation_token: response.next_page_token,
+        })
+    }
+
+    async fn get_object(&self, bucket: &str, key: &str) -> Result<Vec<u8>> {
+        let request = self
+            .client()
+            .get(self.json_url(&Self::object_path(bucket, key)))
+            .query(&[("alt", "media")]);
+        let response = self.send(request, Some(bucket), Some(key)).await?;
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    async fn get_object_metadata(&self, bucket: &str, key: &str) -> Result<Object> {
+        let request = self.client().get(self.json_url(&Self::object_path(bucket, key)));
+        let object: GcsObject = self.send(request, Some(bucket), Some(key)).await?.json().await?;
+        Ok(gcs_to_object(bucket, object))
+    }
+
+    async fn put_object(&self, bucket: &str, key: &str, data: &[u8], options: &UploadOptions) -> Result<UploadResult> {
+        let metadata = ObjectResource::new(key, options);
+        let content_type = options.content_type.as_deref().unwrap_or("application/octet-stream");
+
+        let mut body = Vec::with_capacity(data.len() + 512);
+        body.extend_from_slice(
+            format!(
+                "--{b}\r\nContent-Type: application/json; charset=UTF-8\r\n\r\n{}\r\n--{b}\r\nContent-Type: {}\r\n\r\n",
+                serde_json::to_string(&metadata)?,
+                content_type,
+                b = MULTIPART_BOUNDARY
+            )
+            .as_bytes(),
+        );
+        body.extend_from_slice(data);
+        body.extend_from_slice(format!("\r\n--{}--", MULTIPART_BOUNDARY).as_bytes());
+
+        let mut query = vec![("uploadType", "multipart".to_string())];
+        if let Some(acl) = &options.acl {
+            query.push(("predefinedAcl", acl.clone()));
+        }
+
+        let request = self
+            .client()
+            .post(self.upload_url(&format!("/b/{}/o", uri_encode(bucket, true))))
+            .query(&query)
+            .header("Content-Type", format!("multipart/related; boundary={}", MULTIPART_BOUNDARY))
+            .body(body);
+        let object: GcsObject = self.send(request, Some(bucket), Some(key)).await?.json().await?;
+
+        Ok(UploadResult {
+            key: object.name,
+            bucket: bucket.to_string(),
+            etag: object.etag,
+            version_id: object.generation,
+        })
+    }
+
+    async fn delete_object(&self, bucket: &str, key: &str) -> Result<()> {
+        let request = self.client().delete(self.json_url(&Self::object_path(bucket, key)));
+        self.send(request, Some(bucket), Some(key)).await?;
+        Ok(())
+    }
+
+    async fn delete_objects(&self, bucket: &str, keys: &[&str]) -> Result<Vec<String>> {
+        Ok(self.delete_objects_detailed(bucket, keys).await?.deleted)
+    }
+
+    async fn delete_objects_detailed(&self, bucket: &str, keys: &[&str]) -> Result<DeleteObjectsResult> {
+        let mut result = DeleteObjectsResult::default();
+        for key in keys {
+            match self.delete_object(bucket, key).await {
+                Ok(()) | Err(Error::ObjectNotFound(_)) => result.deleted.push(key.to_string()),
+                Err(e) => result.errors.push(DeleteError::new(key, e)),
+            }
+        }
+        Ok(result)
+    }
+
+    async fn copy_object(&self, source_bucket: &str, source_key: &str, dest_bucket: &str, dest_key: &str) -> Result<CopyResult> {
+        let url = self.json_url(&format!(
+            "{}/rewriteTo/b/{}/o/{}",
+            Self::object_path(source_bucket, source_key),
+            uri_encode(dest_bucket, true),
+            uri_encode(dest_key, true)
+        ));
+        let mut rewrite_token: Option<String> = None;
+
+        loop {
+            let mut request = self.client().post(&url).json(&serde_json::json!({}));
+            if let Some(token) = &rewrite_token {
+                request = request.query(&[("rewriteToken", token)]);
+            }
+
+            let response: RewriteResponse = self
+                .send(request, Some(source_bucket), Some(source_key))
+                .await?
+                .json()
+                .await?;
+
+            if response.done {
+                let resource = response.resource.ok_or_else(|| Error::Api {
+                    message: "Rewrite completed without a resource".to_string(),
+                    code: None,
+                })?;
+                return Ok(CopyResult {
+                    key: dest_key.to_string(),
+                    bucket: dest_bucket.to_string(),
+                    etag: resource.etag,
+                    version_id: resource.generation,
+                });
+            }
+
+            rewrite_token = response.rewrite_token;
+            if rewrite_token.is_none() {
+                return Err(Error::Api {
+                    message: "Rewrite did not complete and returned no rewrite token".to_string(),
+                    code: None,
+                });
+            }
+        }
+    }
+
+    async fn object_exists(&self, bucket: &str, key: &str) -> Result<bool> {
+        match self.get_object_metadata(bucket, key).await {
+            Ok(_) => Ok(true),
+            Err(Error::ObjectNotFound(_)) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn storage_id(&self) -> Option<String> {
+        self.service_account()
+            .map(|key| format!("gcs:{}:{}", self.base_url(), key.client_email))
+    }
+}
+
+#[async_trait]
+impl PresignedUrlProvider for GcsClient {
+    async fn generate_presigned_get_url(&self, bucket: &str, key: &str, options: &PresignOptions) -> Result<PresignedUrl> {
+        self.presign_url("GET", bucket, key, options)
+    }
+
+    async fn generate_presigned_put_url(&self, bucket: &str, key: &str, options: &PresignOptions) -> Result<PresignedUrl> {
+        self.presign_url("PUT", bucket, key, options)
+    }
+}
+
+#[async_trait]
+impl StreamingObjectProvider for GcsClient {
+    async fn get_object_stream(&self, bucket: &str, key: &str, range: Option<ByteRange>) -> Result<ByteStream> {
+        let mut request = self
+            .client()
+            .get(self.json_url(&Self::object_path(bucket, key)))
+            .query(&[("alt", "media")]);
+        if let Some(range) = range {
+            request = request.header("Range", range.header_value());
+        }
+        let response = self.send(request, Some(bucket), Some(key)).await?;
+        Ok(Box::pin(response.bytes_stream().map_err(Error::from)))
+    }
+
+    async fn put_object_stream(&self, bucket: &str, key: &str, stream: ByteStream, options: &StreamUploadOptions) -> Result<UploadResult> {
+        let chunk_size = options.part_size.div_ceil(RESUMABLE_CHUNK_ALIGNMENT).max(1) * RESUMABLE_CHUNK_ALIGNMENT;
+        let content_type = options.upload.content_type.as_deref().unwrap_or("application/octet-stream");
+
+        let mut query = vec![("uploadType", "resumable".to_string())];
+        if let Some(acl) = &options.upload.acl {
+            query.push(("predefinedAcl", acl.clone()));
+        }
+
+        let request = self
+            .client()
+            .post(self.upload_url(&format!("/b/{}/o", uri_encode(bucket, true))))
+            .query(&query)
+            .header("X-Upload-Content-Type", content_type)
+            .json(&ObjectResource::new(key, &options.upload));
+        let response = self.send(request, Some(bucket), Some(key)).await?;
+        let session_url = response
+            .headers()
+            .get("location")
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_string())
+            .ok_or_else(|| Error::UploadFailed("Resumable upload session returned no Location header".to_string()))?;
+
+        let mut reader = ChunkReader::new(stream, chunk_size);
+        let mut offset: u64 = 0;
+        let mut current = reader.next_chunk().await?;
+
+        loop {
+            let next = match current {
+                Some(_) => reader.next_chunk().await?,
+                None => None,
+            };
+            let chunk = current.take().unwrap_or_default();
+            let len = chunk.len() as u64;
+            let content_range = match (len, next.is_some()) {
+                (0, _) => format!("bytes */{}", offset),
+                (_, true) => format!("bytes {}-{}/*", offset, offset + len - 1),
+                (_, false) => format!("bytes {}-{}/{}", offset, offset + len - 1, offset + len),
+            };
+
+            let request = self
+                .client()
+                .put(&session_url)
+                .header("Content-Range", content_range)
+                .body(chunk);
+            match self.send_upload_chunk(request, bucket, key).await {
+                Ok(Some(response)) => {
+                    let object: GcsObject = response.json().await?;
+                    return Ok(UploadResult {
+                        key: object.name,
+                        bucket: bucket.to_string(),
+                        etag: object.etag,
+                        version_id: object.generation,
+                    });
+                }
+                Ok(None) if next.is_some() => {}
+                Ok(None) => {
+                    return Err(Error::UploadFailed(format!("Resumable upload of {} did not complete", key)));
+                }
+                Err(e) => {
+                    let _ = self.client().delete(&session_url).header("Content-Length", "0").send().await;
+                    return Err(e);
+                }
+            }
+
+            offset += len;
+            current = next;
+        }
+    }
+}
+
+fn gcs_to_bucket(bucket: GcsBucket) -> Bucket {
+    Bucket {
+        name: bucket.name,
+        region: bucket.location,
+        created_at: bucket.time_created,
+    }
+}
+
+fn gcs_to_object(bucket: &str, object: GcsObject) -> Object {
+    Object {
+        key: object.name,
+        bucket: bucket.to_string(),
+        size: object.size.and_then(|s| s.parse().ok()).unwrap_or(0),
+        content_type: object.content_type,
+        etag: object.etag,
+        last_modified: object.updated,
+        storage_class: object.storage_class,
+        metadata: object.metadata.unwrap_or_default(),
+    }
+}
+
+#[derive(Serialize)]
+struct CreateBucketRequest<'a> {
+    name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    location: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ObjectResource<'a> {
+    name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content_type: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content_encoding: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cache_control: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content_disposition: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    storage_class: Option<&'a str>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    metadata: &'a HashMap<String, String>,
+}
+
+impl<'a> ObjectResource<'a> {
+    fn new(name: &'a str, options: &'a UploadOptions) -> Self {
+        Self {
+            name,
+            content_type: options.content_type.as_deref(),
+            content_encoding: options.content_encoding.as_deref(),
+            cache_control: options.cache_control.as_deref(),
+            content_disposition: options.content_disposition.as_deref(),
+            storage_class: options.storage_class.as_deref(),
+            metadata: &options.metadata,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GcsBucketList {
+    #[serde(default)]
+    items: Vec<GcsBucket>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GcsBucket {
+    name: String,
+    location: Option<String>,
+    time_created: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GcsObjectList {
+    #[serde(default)]
+    items: Vec<GcsObject>,
+    #[serde(default)]
+    prefixes: Vec<String>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GcsObject {
+    name: String,
+    size: Option<String>,
+    content_type: Option<String>,
+    etag: Option<String>,
+    generation: Option<String>,
+    updated: Option<DateTime<Utc>>,
+    storage_class: Option<String>,
+    metadata: Option<HashMap<String, String>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RewriteResponse {
+    #[serde(default)]
+    done: bool,
+    rewrite_token: Option<String>,
+    resource: Option<GcsObject>,
+}
@@ -1,6 +1,11 @@
 mod error;
+mod stream;
+pub mod sync;
+#[cfg(any(feature = "s3", feature = "gcs", feature = "azure"))]
+mod signing;
 
 pub use error::{Error, Result};
+pub use stream::{multipart_upload_stream, reader_stream};
 
 #[cfg(feature = "s3")]
 pub mod s3;
@@ -18,9 +23,14 @@ pub mod dropbox;
 pub mod cloudflare;
 
 use async_trait::async_trait;
+use bytes::Bytes;
 use chrono::{DateTime, Utc};
+use futures_util::Stream;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::pin::Pin;
+
+pub type ByteStream = Pin<Box<dyn Stream<Item = Result<Bytes>> + Send>>;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Bucket {
@@ -66,6 +76,34 @@ pub struct CopyResult {
     pub version_id: Option<String>,
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DeleteObjectsResult {
+    pub deleted: Vec<String>,
+    pub errors: Vec<DeleteError>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteError {
+    pub key: String,
+    pub code: Option<String>,
+    pub message: String,
+}
+
+#[cfg(any(feature = "gcs", feature = "azure"))]
+impl DeleteError {
+    pub(crate) fn new(key: &str, error: Error) -> Self {
+        let code = match &error {
+            Error::Api { code, .. } => code.clone(),
+            _ => None,
+        };
+        Self {
+            key: key.to_string(),
+            code,
+            message: error.to_string(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct ListOptions {
     pub prefix: Option<String>,
@@ -119,6 +157,46 @@ pub struct UploadPart {
     pub etag: String,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteRange {
+    pub start: u64,
+    pub end: Option<u64>,
+}
+
+impl ByteRange {
+    pub fn new(start: u64, end: u64) -> Self {
+        Self { start, end: Some(end) }
+    }
+
+    pub fn from(start: u64) -> Self {
+        Self { start, end: None }
+    }
+
+    pub fn header_value(&self) -> String {
+        match self.end {
+            Some(end) => format!("bytes={}-{}", self.start, end),
+            None => format!("bytes={}-", self.start),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StreamUploadOptions {
+    pub upload: UploadOptions,
+    pub part_size: usize,
+    pub max_concurrency: usize,
+}
+
+impl Default for StreamUploadOptions {
+    fn default() -> Self {
+        Self {
+            upload: UploadOptions::default(),
+            part_size: 8 * 1024 * 1024,
+            max_concurrency: 4,
+        }
+    }
+}
+
 #[async_trait]
 pub trait ObjectStorageProvider: Send + Sync {
     async fn list_buckets(&self) -> Result<Vec<Bucket>>;
@@ -134,6 +212,28 @@ pub trait ObjectStorageProvider: Send + Sync {
     async fn delete_objects(&self, bucket: &str, keys: &[&str]) -> Result<Vec<String>>;
     async fn copy_object(&self, source_bucket: &str, source_key: &str, dest_bucket: &str, dest_key: &str) -> Result<CopyResult>;
     async fn object_exists(&self, bucket: &str, key: &str) -> Result<bool>;
+
+    // Like `delete_objects`, but also says why each remaining key failed. The
+    // default only knows which keys are missing from the deleted list.
+    async fn delete_objects_detailed(&self, bucket: &str, keys: &[&str]) -> Result<DeleteObjectsResult> {
+        let deleted = self.delete_objects(bucket, keys).await?;
+        let errors = keys
+            .iter()
+            .filter(|key| !deleted.iter().any(|d| d == *key))
+            .map(|key| DeleteError {
+                key: key.to_string(),
+                code: None,
+                message: "Object was not deleted".to_string(),
+            })
+            .collect();
+        Ok(DeleteObjectsResult { deleted, errors })
+    }
+
+    // Identifies the store and credentials behind the client. Clients with
+    // equal ids can copy between their buckets server-side; `None` if unknown.
+    fn storage_id(&self) -> Option<String> {
+        None
+    }
 }
 
 #[async_trait]
@@ -156,3 +256,9 @@ pub trait VersioningProvider: Send + Sync {
     async fn get_object_version(&self, bucket: &str, key: &str, version_id: &str) -> Result<Vec<u8>>;
     async fn delete_object_version(&self, bucket: &str, key: &str, version_id: &str) -> Result<()>;
 }
+
+#[async_trait]
+pub trait StreamingObjectProvider: ObjectStorageProvider {
+    async fn get_object_stream(&self, bucket: &str, key: &str, range: Option<ByteRange>) -> Result<ByteStream>;
+    async fn put_object_stream(&self, bucket: &str, key: &str, stream: ByteStream, options: &StreamUploadOptions) -> Result<UploadResult>;
+}
@@ -0,0 +1,218 @@
+use crate::s3::signing::{self, Credentials};
+use crate::{Error, Result};
+use chrono::Utc;
+use reqwest::{Client, Method, Response};
+use serde::Deserialize;
+
+#[derive(Clone)]
+pub struct S3Client {
+    access_key_id: String,
+    secret_access_key: String,
+    session_token: Option<String>,
+    region: String,
+    endpoint: Option<String>,
+    path_style: bool,
+    client: Client,
+}
+
+pub(crate) struct ResolvedUrl {
+    pub scheme: String,
+    pub host: String,
+    pub canonical_uri: String,
+}
+
+impl ResolvedUrl {
+    pub fn to_url(&self, query: &[(String, String)]) -> String {
+        let mut url = format!("{}://{}{}", self.scheme, self.host, self.canonical_uri);
+        if !query.is_empty() {
+            url.push('?');
+            url.push_str(&signing::canonical_query_string(query));
+        }
+        url
+    }
+}
+
+impl S3Client {
+    pub fn new(access_key_id: &str, secret_access_key: &str, region: &str) -> Self {
+        Self {
+            access_key_id: access_key_id.to_string(),
+            secret_access_key: secret_access_key.to_string(),
+            session_token: None,
+            region: region.to_string(),
+            endpoint: None,
+            path_style: false,
+            client: Client::new(),
+        }
+    }
+
+    pub fn with_session_token(mut self, token: &str) -> Self {
+        self.session_token = Some(token.to_string());
+        self
+    }
+
+    pub fn with_endpoint(mut self, endpoint: &str) -> Self {
+        self.endpoint = Some(endpoint.trim_end_matches('/').to_string());
+        self
+    }
+
+    pub fn with_path_style(mut self, path_style: bool) -> Self {
+        self.path_style = path_style;
+        self
+    }
+
+    pub fn region(&self) -> &str {
+        &self.region
+    }
+
+    pub fn endpoint(&self) -> String {
+        match &self.endpoint {
+            Some(endpoint) => endpoint.clone(),
+            None => format!("https://s3.{}.amazonaws.com", self.region),
+        }
+    }
+
+    pub fn is_path_style(&self) -> bool {
+        self.path_style
+    }
+
+    pub(crate) fn credentials(&self) -> Credentials<'_> {
+        Credentials {
+            access_key_id: &self.access_key_id,
+            secret_access_key: &self.secret_access_key,
+            session_token: self.session_token.as_deref(),
+            region: &self.region,
+            service: "s3",
+        }
+    }
+
+    pub(crate) fn resolve(&self, bucket: Option<&str>, key: Option<&str>) -> Result<ResolvedUrl> {
+        let endpoint = self.endpoint();
+        let parsed = url::Url::parse(&endpoint)
+            .map_err(|e| Error::Api { message: format!("Invalid endpoint {}: {}", endpoint, e), code: None })?;
+        let host = parsed.host_str().ok_or_else(|| Error::Api {
+            message: format!("Endpoint has no host: {}", endpoint),
+            code: None,
+        })?;
+        let authority = match parsed.port() {
+            Some(port) => format!("{}:{}", host, port),
+            None => host.to_string(),
+        };
+        let encoded_key = key.map(|k| signing::uri_encode(k, false));
+
+        let (host, canonical_uri) = match bucket {
+            Some(bucket) if !self.path_style && !bucket.contains('.') => (
+                format!("{}.{}", bucket, authority),
+                format!("/{}", encoded_key.unwrap_or_default()),
+            ),
+            Some(bucket) => {
+                let mut path = format!("/{}", signing::uri_encode(bucket, true));
+                if let Some(key) = encoded_key {
+                    path.push('/');
+                    path.push_str(&key);
+                }
+                (authority, path)
+            }
+            None => (authority, "/".to_string()),
+        };
+
+        Ok(ResolvedUrl {
+            scheme: parsed.scheme().to_string(),
+            host,
+            canonical_uri,
+        })
+    }
+
+    pub(crate) async fn send(
+        &self,
+        method: Method,
+        bucket: Option<&str>,
+        key: Option<&str>,
+        query: Vec<(String, String)>,
+        mut headers: Vec<(String, String)>,
+        body: Vec<u8>,
+    ) -> Result<Response> {
+        let resolved = self.resolve(bucket, key)?;
+        let payload_hash = signing::sha256_hex(&body);
+
+        headers.push(("host".to_string(), resolved.host.clone()));
+        signing::sign_request(
+            &self.credentials(),
+            method.as_str(),
+            &resolved.canonical_uri,
+            &query,
+            &mut headers,
+            &payload_hash,
+            Utc::now(),
+        );
+
+        let mut request = self.client.request(method, resolved.to_url(&query));
+        for (name, value) in headers.iter().filter(|(name, _)| name != "host") {
+            request = request.header(name.as_str(), value.as_str());
+        }
+        if !body.is_empty() {
+            request = request.body(body);
+        }
+
+        let response = request.send().await?;
+        if !response.status().is_success() {
+            return Err(error_from_response(response, bucket, key).await);
+        }
+
+        Ok(response)
+    }
+}
+
+async fn error_from_response(response: Response, bucket: Option<&str>, key: Option<&str>) -> Error {
+    let status = response.status();
+    let body = response.text().await.unwrap_or_default();
+    let parsed: Option<S3ErrorBody> = quick_xml::de::from_str(&body).ok();
+
+    let code = parsed.as_ref().and_then(|e| e.code.clone());
+    let message = parsed
+        .and_then(|e| e.message)
+        .unwrap_or_else(|| format!("S3 request failed with status {}", status));
+
+    to_error(status.as_u16(), code, message, bucket, key)
+}
+
+pub(crate) fn check_embedded_error(body: &str, bucket: Option<&str>, key: Option<&str>) -> Result<()> {
+    if !body.contains("<Error>") {
+        return Ok(());
+    }
+    let parsed: S3ErrorBody = quick_xml::de::from_str(body).map_err(|e| Error::Api {
+        message: e.to_string(),
+        code: None,
+    })?;
+    Err(to_error(
+        200,
+        parsed.code,
+        parsed.message.unwrap_or_else(|| "S3 returned an error".to_string()),
+        bucket,
+        key,
+    ))
+}
+
+fn to_error(status: u16, code: Option<String>, message: String, bucket: Option<&str>, key: Option<&str>) -> Error {
+    match code.as_deref() {
+        Some("NoSuchBucket") => Error::BucketNotFound(bucket.unwrap_or_default().to_string()),
+        Some("NoSuchKey") | Some("NoSuchVersion") | Some("NoSuchUpload") => {
+            Error::ObjectNotFound(key.unwrap_or_default().to_string())
+        }
+        Some("AccessDenied") | Some("AllAccessDisabled") => Error::PermissionDenied(message),
+        Some("InvalidAccessKeyId") | Some("SignatureDoesNotMatch") | Some("ExpiredToken")
+        | Some("InvalidToken") | Some("TokenRefreshRequired") => Error::Auth(message),
+        None if status == 404 => match key {
+            Some(key) => Error::ObjectNotFound(key.to_string()),
+            None => Error::BucketNotFound(bucket.unwrap_or_default().to_string()),
+        },
+        None if status == 403 => Error::PermissionDenied(message),
+        _ => Error::Api { message, code },
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct S3ErrorBody {
+    code: Option<String>,
+    message: Option<String>,
+}
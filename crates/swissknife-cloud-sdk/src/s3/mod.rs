@@ -0,0 +1,5 @@
+mod client;
+mod operations;
+mod signing;
+
+pub use client::S3Client;
@@ -0,0 +1,601 @@
+use crate::s3::client::check_embedded_error;
+use crate::s3::signing;
+use crate::s3::S3Client;
+use crate::{
+    multipart_upload_stream, Bucket, ByteRange, ByteStream, CopyResult, DeleteError, DeleteObjectsResult, Error,
+    ListOptions, ListResult, MultipartUpload, MultipartUploadProvider, Object, ObjectStorageProvider, ObjectVersion,
+    PresignOptions, PresignedUrl, PresignedUrlProvider, Result, StreamUploadOptions, StreamingObjectProvider,
+    UploadOptions, UploadPart, UploadResult, VersioningProvider,
+};
+use async_trait::async_trait;
+use base64::Engine;
+use chrono::{DateTime, Duration, Utc};
+use futures_util::TryStreamExt;
+use md5::{Digest, Md5};
+use quick_xml::escape::escape;
+use reqwest::header::HeaderMap;
+use reqwest::Method;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+const MAX_DELETE_BATCH: usize = 1000;
+const DEFAULT_PRESIGN_EXPIRY: u64 = 3600;
+const MAX_PRESIGN_EXPIRY: u64 = 604_800;
+const MIN_PART_SIZE: usize = 5 * 1024 * 1024;
+const MAX_PART_SIZE: usize = 5 * 1024 * 1024 * 1024;
+
+impl S3Client {
+    fn presign_url(&self, method: Method, bucket: &str, key: &str, options: &PresignOptions) -> Result<PresignedUrl> {
+        let expires_in = match options.expires_in_seconds {
+            0 => DEFAULT_PRESIGN_EXPIRY,
+            secs => secs.min(MAX_PRESIGN_EXPIRY),
+        };
+        let resolved = self.resolve(Some(bucket), Some(key))?;
+
+        let mut headers = vec![("host".to_string(), resolved.host.clone())];
+        if let Some(content_type) = &options.content_type {
+            headers.push(("content-type".to_string(), content_type.clone()));
+        }
+
+        let now = Utc::now();
+        let mut query = Vec::new();
+        signing::presign(
+            &self.credentials(),
+            method.as_str(),
+            &resolved.canonical_uri,
+            &mut query,
+            &headers,
+            expires_in,
+            now,
+        );
+
+        Ok(PresignedUrl {
+            url: resolved.to_url(&query),
+            method: method.to_string(),
+            expires_at: now + Duration::seconds(expires_in as i64),
+        })
+    }
+
+    async fn head_object(&self, bucket: &str, key: &str, version_id: Option<&str>) -> Result<HeaderMap> {
+        let query = version_id
+            .map(|v| vec![("versionId".to_string(), v.to_string())])
+            .unwrap_or_default();
+        let response = self.send(Method::HEAD, Some(bucket), Some(key), query, Vec::new(), Vec::new()).await?;
+        Ok(response.headers().clone())
+    }
+
+    async fn get_object_bytes(&self, bucket: &str, key: &str, query: Vec<(String, String)>) -> Result<Vec<u8>> {
+        let response = self.send(Method::GET, Some(bucket), Some(key), query, Vec::new(), Vec::new()).await?;
+        Ok(response.bytes().await?.to_vec())
+    }
+}
+
+#[async_trait]
+impl ObjectStorageProvider for S3Client {
+    async fn list_buckets(&self) -> Result<Vec<Bucket>> {
+        let response = self.send(Method::GET, None, None, Vec::new(), Vec::new(), Vec::new()).await?;
+        let result: ListAllMyBucketsResult = parse_xml(&response.text().await?)?;
+
+        Ok(result
+            .buckets
+            .bucket
+            .into_iter()
+            .map(|b| Bucket {
+                name: b.name,
+                region: None,
+                created_at: b.creation_date,
+            })
+            .collect())
+    }
+
+    async fn create_bucket(&self, name: &str, region: Option<&str>) -> Result<Bucket> {
+        let region = region.unwrap_or(self.region());
+        let body = if region == "us-east-1" {
+            Vec::new()
+        } else {
+            format!(
+                "<CreateBucketConfiguration xmlns=\"http://s3.amazonaws.com/doc/2006-03-01/\"><LocationConstraint>{}</LocationConstraint></CreateBucketConfiguration>",
+                escape(region)
+            )
+            .into_bytes()
+        };
+
+        self.send(Method::PUT, Some(name), None, Vec::new(), Vec::new(), body).await?;
+
+        Ok(Bucket {
+            name: name.to_string(),
+            region: Some(region.to_string()),
+            created_at: Some(Utc::now()),
+        })
+    }
+
+    async fn delete_bucket(&self, name: &str) -> Result<()> {
+        self.send(Method::DELETE, Some(name), None, Vec::new(), Vec::new(), Vec::new()).await?;
+        Ok(())
+    }
+
+    async fn bucket_exists(&self, name: &str) -> Result<bool> {
+        match self.send(Method::HEAD, Some(name), None, Vec::new(), Vec::new(), Vec::new()).await {
+            Ok(_) => Ok(true),
+            Err(Error::BucketNotFound(_)) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn list_objects(&self, bucket: &str, options: &ListOptions) -> Result<ListResult> {
+        let mut query = vec![("list-type".to_string(), "2".to_string())];
+        if let Some(prefix) = &options.prefix {
+            query.push(("prefix".to_string(), prefix.clone()));
+        }
+        if let Some(delimiter) = &options.delimiter {
+            query.push(("delimiter".to_string(), delimiter.clone()));
+        }
+        if let Some(max_keys) = options.max_keys {
+            query.push(("max-keys".to_string(), max_keys.to_string()));
+        }
+        if let Some(token) = &options.continuation_token {
+            query.push(("continuation-token".to_string(), token.clone()));
+        }
+
+        let response = self.send(Method::GET, Some(bucket), None, query, Vec::new(), Vec::new()).await?;
+        let result: ListBucketResult = parse_xml(&response.text().await?)?;
+
+        Ok(ListResult {
+            objects: result
+                .contents
+                .into_iter()
+                .map(|c| Object {
+                    key: c.key,
+                    bucket: bucket.to_string(),
+                    size: c.size,
+                    content_type: None,
+                    etag: c.etag.map(|e| trim_etag(&e)),
+                    last_modified: c.last_modified,
+                    storage_class: c.storage_class,
+                    metadata: HashMap::new(),
+                })
+                .collect(),
+            common_prefixes: result.common_prefixes.into_iter().map(|p| p.prefix).collect(),
+            is_truncated: result.is_truncated,
+            next_continuation_token: result.next_continuation_token,
+        })
+    }
+
+    async fn get_object(&self, bucket: &str, key: &str) -> Result<Vec<u8>> {
+        self.get_object_bytes(bucket, key, Vec::new()).await
+    }
+
+    async fn get_object_metadata(&self, bucket: &str, key: &str) -> Result<Object> {
+        let headers = self.head_object(bucket, key, None).await?;
+        Ok(object_from_headers(bucket, key, &headers))
+    }
+
+    async fn put_object(&self, bucket: &str, key: &str, data: &[u8], options: &UploadOptions) -> Result<UploadResult> {
+        let response = self
+            .send(Method::PUT, Some(bucket), Some(key), Vec::new(), upload_headers(options), data.to_vec())
+            .await?;
+
+        Ok(UploadResult {
+            key: key.to_string(),
+            bucket: bucket.to_string(),
+            etag: header_str(response.headers(), "etag").map(|e| trim_etag(&e)),
+            version_id: header_str(response.headers(), "x-amz-version-id"),
+        })
+    }
+
+    async fn delete_object(&self, bucket: &str, key: &str) -> Result<()> {
+        self.send(Method::DELETE, Some(bucket), Some(key), Vec::new(), Vec::new(), Vec::new()).await?;
+        Ok(())
+    }
+
+    async fn delete_objects(&self, bucket: &str, keys: &[&str]) -> Result<Vec<String>> {
+        Ok(self.delete_objects_detailed(bucket, keys).await?.deleted)
+    }
+
+    async fn delete_objects_detailed(&self, bucket: &str, keys: &[&str]) -> Result<DeleteObjectsResult> {
+        let mut deleted = DeleteObjectsResult::default();
+
+        for chunk in keys.chunks(MAX_DELETE_BATCH) {
+            let mut body = String::from("<Delete xmlns=\"http://s3.amazonaws.com/doc/2006-03-01/\"><Quiet>false</Quiet>");
+            for key in chunk {
+                body.push_str(&format!("<Object><Key>{}</Key></Object>", escape(key)));
+            }
+            body.push_str("</Delete>");
+
+            let content_md5 = base64::engine::general_purpose::STANDARD.encode(Md5::digest(body.as_bytes()));
+            let headers = vec![
+                ("content-md5".to_string(), content_md5),
+                ("content-type".to_string(), "application/xml".to_string()),
+            ];
+            let query = vec![("delete".to_string(), String::new())];
+
+            let response = self.send(Method::POST, Some(bucket), None, query, headers, body.into_bytes()).await?;
+            let result: DeleteResult = parse_xml(&response.text().await?)?;
+            deleted.deleted.extend(result.deleted.into_iter().map(|d| d.key));
+            // Per-key failures come back in a 200 response as <Error> entries.
+            deleted.errors.extend(result.error.into_iter().map(|e| DeleteError {
+                key: e.key,
+                message: e.message.unwrap_or_else(|| "S3 failed to delete the object".to_string()),
+                code: e.code,
+            }));
+        }
+
+        Ok(deleted)
+    }
+
+    async fn copy_object(&self, source_bucket: &str, source_key: &str, dest_bucket: &str, dest_key: &str) -> Result<CopyResult> {
+        let copy_source = format!(
+            "/{}/{}",
+            signing::uri_encode(source_bucket, true),
+            signing::uri_encode(source_key, false)
+        );
+        let headers = vec![("x-amz-copy-source".to_string(), copy_source)];
+
+        let response = self
+            .send(Method::PUT, Some(dest_bucket), Some(dest_key), Vec::new(), headers, Vec::new())
+            .await?;
+        let version_id = header_str(response.headers(), "x-amz-version-id");
+        let body = response.text().await?;
+        check_embedded_error(&body, Some(dest_bucket), Some(dest_key))?;
+        let result: CopyObjectResult = parse_xml(&body)?;
+
+        Ok(CopyResult {
+            key: dest_key.to_string(),
+            bucket: dest_bucket.to_string(),
+            etag: result.etag.map(|e| trim_etag(&e)),
+            version_id,
+        })
+    }
+
+    async fn object_exists(&self, bucket: &str, key: &str) -> Result<bool> {
+        match self.head_object(bucket, key, None).await {
+            Ok(_) => Ok(true),
+            Err(Error::ObjectNotFound(_)) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn storage_id(&self) -> Option<String> {
+        Some(format!("s3:{}:{}:{}", self.endpoint(), self.region(), self.credentials().access_key_id))
+    }
+}
+
+#[async_trait]
+impl PresignedUrlProvider for S3Client {
+    async fn generate_presigned_get_url(&self, bucket: &str, key: &str, options: &PresignOptions) -> Result<PresignedUrl> {
+        self.presign_url(Method::GET, bucket, key, options)
+    }
+
+    async fn generate_presigned_put_url(&self, bucket: &str, key: &str, options: &PresignOptions) -> Result<PresignedUrl> {
+        self.presign_url(Method::PUT, bucket, key, options)
+    }
+}
+
+#[async_trait]
+impl MultipartUploadProvider for S3Client {
+    async fn create_multipart_upload(&self, bucket: &str, key: &str, options: &UploadOptions) -> Result<MultipartUpload> {
+        let query = vec![("uploads".to_string(), String::new())];
+        let response = self
+            .send(Method::POST, Some(bucket), Some(key), query, upload_headers(options), Vec::new())
+            .await?;
+        let result: InitiateMultipartUploadResult = parse_xml(&response.text().await?)?;
+
+        Ok(MultipartUpload {
+            upload_id: result.upload_id,
+            key: key.to_string(),
+            bucket: bucket.to_string(),
+        })
+    }
+
+    async fn upload_part(&self, bucket: &str, key: &str, upload_id: &str, part_number: u32, data: &[u8]) -> Result<UploadPart> {
+        let query = vec![
+            ("partNumber".to_string(), part_number.to_string()),
+            ("uploadId".to_string(), upload_id.to_string()),
+        ];
+        let response = self
+            .send(Method::PUT, Some(bucket), Some(key), query, Vec::new(), data.to_vec())
+            .await?;
+
+        let etag = header_str(response.headers(), "etag")
+            .ok_or_else(|| Error::UploadFailed(format!("No ETag returned for part {}", part_number)))?;
+
+        Ok(UploadPart { part_number, etag })
+    }
+
+    async fn complete_multipart_upload(&self, bucket: &str, key: &str, upload_id: &str, parts: &[UploadPart]) -> Result<UploadResult> {
+        let mut sorted: Vec<&UploadPart> = parts.iter().collect();
+        sorted.sort_by_key(|p| p.part_number);
+
+        let mut body = String::from("<CompleteMultipartUpload xmlns=\"http://s3.amazonaws.com/doc/2006-03-01/\">");
+        for part in sorted {
+            body.push_str(&format!(
+                "<Part><PartNumber>{}</PartNumber><ETag>{}</ETag></Part>",
+                part.part_number,
+                escape(quote_etag(&part.etag).as_str())
+            ));
+        }
+        body.push_str("</CompleteMultipartUpload>");
+
+        let query = vec![("uploadId".to_string(), upload_id.to_string())];
+        let headers = vec![("content-type".to_string(), "application/xml".to_string())];
+        let response = self
+            .send(Method::POST, Some(bucket), Some(key), query, headers, body.into_bytes())
+            .await?;
+        let version_id = header_str(response.headers(), "x-amz-version-id");
+        let text = response.text().await?;
+        check_embedded_error(&text, Some(bucket), Some(key))?;
+        let result: CompleteMultipartUploadResult = parse_xml(&text)?;
+
+        Ok(UploadResult {
+            key: key.to_string(),
+            bucket: bucket.to_string(),
+            etag: result.etag.map(|e| trim_etag(&e)),
+            version_id,
+        })
+    }
+
+    async fn abort_multipart_upload(&self, bucket: &str, key: &str, upload_id: &str) -> Result<()> {
+        let query = vec![("uploadId".to_string(), upload_id.to_string())];
+        self.send(Method::DELETE, Some(bucket), Some(key), query, Vec::new(), Vec::new()).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl StreamingObjectProvider for S3Client {
+    async fn get_object_stream(&self, bucket: &str, key: &str, range: Option<ByteRange>) -> Result<ByteStream> {
+        let headers = range
+            .map(|r| vec![("range".to_string(), r.header_value())])
+            .unwrap_or_default();
+        let response = self.send(Method::GET, Some(bucket), Some(key), Vec::new(), headers, Vec::new()).await?;
+        Ok(Box::pin(response.bytes_stream().map_err(Error::from)))
+    }
+
+    async fn put_object_stream(&self, bucket: &str, key: &str, stream: ByteStream, options: &StreamUploadOptions) -> Result<UploadResult> {
+        let options = StreamUploadOptions {
+            part_size: options.part_size.clamp(MIN_PART_SIZE, MAX_PART_SIZE),
+            ..options.clone()
+        };
+        multipart_upload_stream(self, bucket, key, stream, &options).await
+    }
+}
+
+#[async_trait]
+impl VersioningProvider for S3Client {
+    async fn list_object_versions(&self, bucket: &str, key: &str) -> Result<Vec<ObjectVersion>> {
+        let mut versions = Vec::new();
+        let mut key_marker: Option<String> = None;
+        let mut version_marker: Option<String> = None;
+
+        loop {
+            let mut query = vec![
+                ("versions".to_string(), String::new()),
+                ("prefix".to_string(), key.to_string()),
+            ];
+            if let Some(marker) = &key_marker {
+                query.push(("key-marker".to_string(), marker.clone()));
+            }
+            if let Some(marker) = &version_marker {
+                query.push(("version-id-marker".to_string(), marker.clone()));
+            }
+
+            let response = self.send(Method::GET, Some(bucket), None, query, Vec::new(), Vec::new()).await?;
+            let result: ListVersionsResult = parse_xml(&response.text().await?)?;
+
+            versions.extend(result.version.into_iter().filter(|v| v.key == key).map(|v| ObjectVersion {
+                key: v.key,
+                version_id: v.version_id,
+                is_latest: v.is_latest,
+                last_modified: v.last_modified,
+                size: v.size,
+            }));
+
+            if !result.is_truncated {
+                break;
+            }
+            key_marker = result.next_key_marker;
+            version_marker = result.next_version_id_marker;
+            if key_marker.is_none() && version_marker.is_none() {
+                break;
+            }
+        }
+
+        Ok(versions)
+    }
+
+    async fn get_object_version(&self, bucket: &str, key: &str, version_id: &str) -> Result<Vec<u8>> {
+        let query = vec![("versionId".to_string(), version_id.to_string())];
+        self.get_object_bytes(bucket, key, query).await
+    }
+
+    async fn delete_object_version(&self, bucket: &str, key: &str, version_id: &str) -> Result<()> {
+        let query = vec![("versionId".to_string(), version_id.to_string())];
+        self.send(Method::DELETE, Some(bucket), Some(key), query, Vec::new(), Vec::new()).await?;
+        Ok(())
+    }
+}
+
+fn upload_headers(options: &UploadOptions) -> Vec<(String, String)> {
+    let mut headers = Vec::new();
+    if let Some(v) = &options.content_type {
+        headers.push(("content-type".to_string(), v.clone()));
+    }
+    if let Some(v) = &options.content_encoding {
+        headers.push(("content-encoding".to_string(), v.clone()));
+    }
+    if let Some(v) = &options.cache_control {
+        headers.push(("cache-control".to_string(), v.clone()));
+    }
+    if let Some(v) = &options.content_disposition {
+        headers.push(("content-disposition".to_string(), v.clone()));
+    }
+    if let Some(v) = &options.storage_class {
+        headers.push(("x-amz-storage-class".to_string(), v.clone()));
+    }
+    if let Some(v) = &options.acl {
+        headers.push(("x-amz-acl".to_string(), v.clone()));
+    }
+    for (k, v) in &options.metadata {
+        headers.push((format!("x-amz-meta-{}", k.to_lowercase()), v.clone()));
+    }
+    headers
+}
+
+fn object_from_headers(bucket: &str, key: &str, headers: &HeaderMap) -> Object {
+    let metadata = headers
+        .iter()
+        .filter_map(|(name, value)| {
+            let name = name.as_str().strip_prefix("x-amz-meta-")?;
+            Some((name.to_string(), value.to_str().ok()?.to_string()))
+        })
+        .collect();
+
+    Object {
+        key: key.to_string(),
+        bucket: bucket.to_string(),
+        size: header_str(headers, "content-length")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(0),
+        content_type: header_str(headers, "content-type"),
+        etag: header_str(headers, "etag").map(|e| trim_etag(&e)),
+        last_modified: header_str(headers, "last-modified")
+            .and_then(|v| DateTime::parse_from_rfc2822(&v).ok())
+            .map(|d| d.with_timezone(&Utc)),
+        storage_class: header_str(headers, "x-amz-storage-class"),
+        metadata,
+    }
+}
+
+fn header_str(headers: &HeaderMap, name: &str) -> Option<String> {
+    headers.get(name).and_then(|v| v.to_str().ok()).map(|v| v.to_string())
+}
+
+fn trim_etag(etag: &str) -> String {
+    etag.trim_matches('"').to_string()
+}
+
+fn quote_etag(etag: &str) -> String {
+    format!("\"{}\"", trim_etag(etag))
+}
+
+fn parse_xml<T: for<'de> Deserialize<'de>>(xml: &str) -> Result<T> {
+    quick_xml::de::from_str(xml).map_err(|e| Error::Api {
+        message: format!("Failed to parse S3 response: {}", e),
+        code: None,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ListAllMyBucketsResult {
+    #[serde(default)]
+    buckets: BucketList,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct BucketList {
+    #[serde(default)]
+    bucket: Vec<BucketEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct BucketEntry {
+    name: String,
+    creation_date: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ListBucketResult {
+    #[serde(default)]
+    contents: Vec<ObjectEntry>,
+    #[serde(default)]
+    common_prefixes: Vec<CommonPrefix>,
+    #[serde(default)]
+    is_truncated: bool,
+    next_continuation_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ObjectEntry {
+    key: String,
+    #[serde(default)]
+    size: u64,
+    #[serde(rename = "ETag")]
+    etag: Option<String>,
+    last_modified: Option<DateTime<Utc>>,
+    storage_class: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct CommonPrefix {
+    prefix: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct DeleteResult {
+    #[serde(default)]
+    deleted: Vec<DeletedEntry>,
+    #[serde(default)]
+    error: Vec<DeleteErrorEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct DeletedEntry {
+    key: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct DeleteErrorEntry {
+    key: String,
+    code: Option<String>,
+    message: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CopyObjectResult {
+    #[serde(rename = "ETag")]
+    etag: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct InitiateMultipartUploadResult {
+    upload_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct CompleteMultipartUploadResult {
+    #[serde(rename = "ETag")]
+    etag: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ListVersionsResult {
+    #[serde(default)]
+    version: Vec<VersionEntry>,
+    #[serde(default)]
+    is_truncated: bool,
+    next_key_marker: Option<String>,
+    next_version_id_marker: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct VersionEntry {
+    key: String,
+    version_id: String,
+    #[serde(default)]
+    is_latest: bool,
+    last_modified: DateTime<Utc>,
+    #[serde(default)]
+    size: u64,
+}
@@ -0,0 +1,101 @@
+use crate::{
+    ByteStream, Error, MultipartUploadProvider, ObjectStorageProvider, Result, StreamUploadOptions, UploadPart,
+    UploadResult,
+};
+use bytes::{Bytes, BytesMut};
+use futures_util::{stream, StreamExt, TryStreamExt};
+use tokio::io::AsyncRead;
+use tokio_util::io::ReaderStream;
+
+pub fn reader_stream<R>(reader: R) -> ByteStream
+where
+    R: AsyncRead + Send + 'static,
+{
+    Box::pin(ReaderStream::new(reader).map_err(Error::from))
+}
+
+pub(crate) struct ChunkReader {
+    stream: ByteStream,
+    buffer: BytesMut,
+    chunk_size: usize,
+    finished: bool,
+}
+
+impl ChunkReader {
+    pub(crate) fn new(stream: ByteStream, chunk_size: usize) -> Self {
+        Self {
+            stream,
+            buffer: BytesMut::new(),
+            chunk_size: chunk_size.max(1),
+            finished: false,
+        }
+    }
+
+    pub(crate) async fn next_chunk(&mut self) -> Result<Option<Bytes>> {
+        while !self.finished && self.buffer.len() < self.chunk_size {
+            match self.stream.next().await {
+                Some(bytes) => self.buffer.extend_from_slice(&bytes?),
+                None => self.finished = true,
+            }
+        }
+
+        if self.buffer.is_empty() {
+            return Ok(None);
+        }
+        let len = self.buffer.len().min(self.chunk_size);
+        Ok(Some(self.buffer.split_to(len).freeze()))
+    }
+}
+
+pub async fn multipart_upload_stream<P>(
+    provider: &P,
+    bucket: &str,
+    key: &str,
+    stream: ByteStream,
+    options: &StreamUploadOptions,
+) -> Result<UploadResult>
+where
+    P: ObjectStorageProvider + MultipartUploadProvider + ?Sized,
+{
+    let mut reader = ChunkReader::new(stream, options.part_size);
+
+    let first = reader.next_chunk().await?.unwrap_or_default();
+    let second = if first.len() < options.part_size {
+        None
+    } else {
+        reader.next_chunk().await?
+    };
+    let Some(second) = second else {
+        return provider.put_object(bucket, key, &first, &options.upload).await;
+    };
+
+    let upload = provider.create_multipart_upload(bucket, key, &options.upload).await?;
+    let upload_id = upload.upload_id.as_str();
+
+    let remaining = stream::try_unfold(reader, |mut reader| async move {
+        Ok::<_, Error>(reader.next_chunk().await?.map(|chunk| (chunk, reader)))
+    });
+    let uploaded: Result<Vec<UploadPart>> = stream::iter([Ok::<_, Error>(first), Ok(second)])
+        .chain(remaining)
+        .enumerate()
+        .map(|(index, chunk)| async move {
+            let chunk = chunk?;
+            provider.upload_part(bucket, key, upload_id, index as u32 + 1, &chunk).await
+        })
+        .buffer_unordered(options.max_concurrency.max(1))
+        .try_collect()
+        .await;
+
+    let result = match uploaded {
+        Ok(mut parts) => {
+            parts.sort_by_key(|p| p.part_number);
+            provider.complete_multipart_upload(bucket, key, upload_id, &parts).await
+        }
+        Err(e) => Err(e),
+    };
+
+    if result.is_err() {
+        let _ = provider.abort_multipart_upload(bucket, key, upload_id).await;
+    }
+    result
+}
@@ -0,0 +1,328 @@
+use crate::{
+    DeleteError, ListOptions, Object, Result, StreamUploadOptions, StreamingObjectProvider, UploadOptions,
+};
+use futures_util::{stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncLocation {
+    pub bucket: String,
+    pub prefix: String,
+}
+
+impl SyncLocation {
+    pub fn new(bucket: &str, prefix: &str) -> Self {
+        Self {
+            bucket: bucket.to_string(),
+            prefix: prefix.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncActionKind {
+    Create,
+    Update,
+    Delete,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncAction {
+    pub kind: SyncActionKind,
+    pub source_key: Option<String>,
+    pub dest_key: String,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncProgress {
+    pub action: SyncAction,
+    pub completed: usize,
+    pub total: usize,
+    pub bytes_transferred: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransferError {
+    pub action: SyncAction,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncReport {
+    pub dry_run: bool,
+    pub actions: Vec<SyncAction>,
+    pub unchanged: usize,
+    pub bytes_transferred: u64,
+    pub failed_transfers: Vec<TransferError>,
+    pub failed_deletes: Vec<DeleteError>,
+}
+
+impl SyncReport {
+    pub fn count(&self, kind: SyncActionKind) -> usize {
+        self.actions.iter().filter(|a| a.kind == kind).count()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SyncOptions {
+    pub delete: bool,
+    pub dry_run: bool,
+    pub max_concurrency: usize,
+}
+
+impl Default for SyncOptions {
+    fn default() -> Self {
+        Self {
+            delete: false,
+            dry_run: false,
+            max_concurrency: 8,
+        }
+    }
+}
+
+type ProgressCallback<'a> = Box<dyn Fn(&SyncProgress) + Send + Sync + 'a>;
+
+pub struct ObjectSync<'a> {
+    source: &'a dyn StreamingObjectProvider,
+    source_location: SyncLocation,
+    dest: &'a dyn StreamingObjectProvider,
+    dest_location: SyncLocation,
+    options: SyncOptions,
+    progress: Option<ProgressCallback<'a>>,
+}
+
+impl<'a> ObjectSync<'a> {
+    pub fn new(
+        source: &'a dyn StreamingObjectProvider,
+        source_location: SyncLocation,
+        dest: &'a dyn StreamingObjectProvider,
+        dest_location: SyncLocation,
+    ) -> Self {
+        Self {
+            source,
+            source_location,
+            dest,
+            dest_location,
+            options: SyncOptions::default(),
+            progress: None,
+        }
+    }
+
+    pub fn with_options(mut self, options: SyncOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn with_delete(mut self, delete: bool) -> Self {
+        self.options.delete = delete;
+        self
+    }
+
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.options.dry_run = dry_run;
+        self
+    }
+
+    pub fn with_max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.options.max_concurrency = max_concurrency;
+        self
+    }
+
+    pub fn with_progress<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&SyncProgress) + Send + Sync + 'a,
+    {
+        self.progress = Some(Box::new(callback));
+        self
+    }
+
+    // Server-side copies and ETag comparisons only work within one store.
+    fn same_provider(&self) -> bool {
+        match (self.source.storage_id(), self.dest.storage_id()) {
+            (Some(source), Some(dest)) => source == dest,
+            _ => false,
+        }
+    }
+
+    pub async fn plan(&self) -> Result<SyncReport> {
+        let source_objects = list_all(self.source, &self.source_location).await?;
+        let mut dest_objects: HashMap<String, Object> = list_all(self.dest, &self.dest_location)
+            .await?
+            .into_iter()
+            .map(|o| (relative_key(&o.key, &self.dest_location.prefix).to_string(), o))
+            .collect();
+
+        let same_provider = self.same_provider();
+        let mut report = SyncReport {
+            dry_run: self.options.dry_run,
+            ..Default::default()
+        };
+
+        for source in source_objects {
+            let relative = relative_key(&source.key, &self.source_location.prefix).to_string();
+            let kind = match dest_objects.remove(&relative) {
+                None => SyncActionKind::Create,
+                Some(dest) if needs_update(&source, &dest, same_provider) => SyncActionKind::Update,
+                Some(_) => {
+                    report.unchanged += 1;
+                    continue;
+                }
+            };
+            report.actions.push(SyncAction {
+                kind,
+                dest_key: format!("{}{}", self.dest_location.prefix, relative),
+                source_key: Some(source.key),
+                size: source.size,
+            });
+        }
+
+        if self.options.delete {
+            let mut extraneous: Vec<Object> = dest_objects.into_values().collect();
+            extraneous.sort_by(|a, b| a.key.cmp(&b.key));
+            report.actions.extend(extraneous.into_iter().map(|o| SyncAction {
+                kind: SyncActionKind::Delete,
+                source_key: None,
+                dest_key: o.key,
+                size: o.size,
+            }));
+        }
+
+        Ok(report)
+    }
+
+    pub async fn run(&self) -> Result<SyncReport> {
+        let mut report = self.plan().await?;
+        if self.options.dry_run {
+            return Ok(report);
+        }
+
+        let total = report.actions.len();
+        let completed = AtomicUsize::new(0);
+        let transferred = AtomicU64::new(0);
+        let finish = |action: &SyncAction, bytes: u64| {
+            let completed = completed.fetch_add(1, Ordering::SeqCst) + 1;
+            let bytes_transferred = transferred.fetch_add(bytes, Ordering::SeqCst) + bytes;
+            if let Some(callback) = &self.progress {
+                callback(&SyncProgress {
+                    action: action.clone(),
+                    completed,
+                    total,
+                    bytes_transferred,
+                });
+            }
+        };
+
+        let (deletes, transfers): (Vec<&SyncAction>, Vec<&SyncAction>) = report
+            .actions
+            .iter()
+            .partition(|a| a.kind == SyncActionKind::Delete);
+
+        // A failed object doesn't stop the others; it is reported instead.
+        report.failed_transfers = stream::iter(transfers)
+            .map(|action| async {
+                match self.transfer(action).await {
+                    Ok(()) => {
+                        finish(action, action.size);
+                        None
+                    }
+                    Err(e) => Some(TransferError {
+                        action: action.clone(),
+                        message: e.to_string(),
+                    }),
+                }
+            })
+            .buffer_unordered(self.options.max_concurrency.max(1))
+            .filter_map(std::future::ready)
+            .collect()
+            .await;
+
+        if !deletes.is_empty() {
+            let keys: Vec<&str> = deletes.iter().map(|a| a.dest_key.as_str()).collect();
+            let result = self.dest.delete_objects_detailed(&self.dest_location.bucket, &keys).await?;
+            for action in deletes.iter().filter(|a| result.deleted.contains(&a.dest_key)) {
+                finish(action, 0);
+            }
+            report.failed_deletes = result.errors;
+        }
+
+        report.bytes_transferred = transferred.load(Ordering::SeqCst);
+        Ok(report)
+    }
+
+    async fn transfer(&self, action: &SyncAction) -> Result<()> {
+        let source_key = action.source_key.as_deref().unwrap_or_default();
+
+        if self.same_provider() {
+            self.source
+                .copy_object(
+                    &self.source_location.bucket,
+                    source_key,
+                    &self.dest_location.bucket,
+                    &action.dest_key,
+                )
+                .await?;
+            return Ok(());
+        }
+
+        let metadata = self.source.get_object_metadata(&self.source_location.bucket, source_key).await?;
+        let stream = self
+            .source
+            .get_object_stream(&self.source_location.bucket, source_key, None)
+            .await?;
+        let options = StreamUploadOptions {
+            upload: UploadOptions {
+                content_type: metadata.content_type,
+                metadata: metadata.metadata,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        self.dest
+            .put_object_stream(&self.dest_location.bucket, &action.dest_key, stream, &options)
+            .await?;
+        Ok(())
+    }
+}
+
+async fn list_all(provider: &dyn StreamingObjectProvider, location: &SyncLocation) -> Result<Vec<Object>> {
+    let mut objects = Vec::new();
+    let mut options = ListOptions {
+        prefix: Some(location.prefix.clone()).filter(|p| !p.is_empty()),
+        ..Default::default()
+    };
+
+    loop {
+        let result = provider.list_objects(&location.bucket, &options).await?;
+        objects.extend(result.objects);
+
+        match result.next_continuation_token {
+            Some(token) if result.is_truncated => options.continuation_token = Some(token),
+            _ => break,
+        }
+    }
+
+    Ok(objects)
+}
+
+fn relative_key<'k>(key: &'k str, prefix: &str) -> &'k str {
+    key.strip_prefix(prefix).unwrap_or(key)
+}
+
+fn needs_update(source: &Object, dest: &Object, same_provider: bool) -> bool {
+    if source.size != dest.size {
+        return true;
+    }
+    if same_provider {
+        if let (Some(a), Some(b)) = (&source.etag, &dest.etag) {
+            return a != b;
+        }
+    }
+    match (source.last_modified, dest.last_modified) {
+        (Some(source), Some(dest)) => source > dest,
+        _ => false,
+    }
+}
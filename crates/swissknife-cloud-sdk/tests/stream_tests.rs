@@ -0,0 +1,248 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures_util::stream;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use swissknife_cloud_sdk::{
+    multipart_upload_stream, reader_stream, Bucket, ByteRange, ByteStream, CopyResult, Error, ListOptions, ListResult,
+    MultipartUpload, MultipartUploadProvider, Object, ObjectStorageProvider, Result, StreamUploadOptions,
+    UploadOptions, UploadPart, UploadResult,
+};
+
+#[derive(Default)]
+struct MemoryStorage {
+    objects: Mutex<HashMap<String, Vec<u8>>>,
+    parts: Mutex<HashMap<u32, Vec<u8>>>,
+    single_puts: Mutex<u32>,
+    aborted: Mutex<bool>,
+    fail_part: Option<u32>,
+}
+
+fn unsupported<T>() -> Result<T> {
+    Err(Error::Api {
+        message: "unsupported".to_string(),
+        code: None,
+    })
+}
+
+#[async_trait]
+impl ObjectStorageProvider for MemoryStorage {
+    async fn list_buckets(&self) -> Result<Vec<Bucket>> {
+        unsupported()
+    }
+
+    async fn create_bucket(&self, _name: &str, _region: Option<&str>) -> Result<Bucket> {
+        unsupported()
+    }
+
+    async fn delete_bucket(&self, _name: &str) -> Result<()> {
+        unsupported()
+    }
+
+    async fn bucket_exists(&self, _name: &str) -> Result<bool> {
+        unsupported()
+    }
+
+    async fn list_objects(&self, _bucket: &str, _options: &ListOptions) -> Result<ListResult> {
+        unsupported()
+    }
+
+    async fn get_object(&self, _bucket: &str, key: &str) -> Result<Vec<u8>> {
+        self.objects
+            .lock()
+            .unwrap()
+            .get(key)
+            .cloned()
+            .ok_or_else(|| Error::ObjectNotFound(key.to_string()))
+    }
+
+    async fn get_object_metadata(&self, _bucket: &str, _key: &str) -> Result<Object> {
+        unsupported()
+    }
+
+    async fn put_object(&self, bucket: &str, key: &str, data: &[u8], _options: &UploadOptions) -> Result<UploadResult> {
+        *self.single_puts.lock().unwrap() += 1;
+        self.objects.lock().unwrap().insert(key.to_string(), data.to_vec());
+        Ok(UploadResult {
+            key: key.to_string(),
+            bucket: bucket.to_string(),
+            etag: None,
+            version_id: None,
+        })
+    }
+
+    async fn delete_object(&self, _bucket: &str, _key: &str) -> Result<()> {
+        unsupported()
+    }
+
+    async fn delete_objects(&self, _bucket: &str, _keys: &[&str]) -> Result<Vec<String>> {
+        unsupported()
+    }
+
+    async fn copy_object(&self, _source_bucket: &str, _source_key: &str, _dest_bucket: &str, _dest_key: &str) -> Result<CopyResult> {
+        unsupported()
+    }
+
+    async fn object_exists(&self, _bucket: &str, key: &str) -> Result<bool> {
+        Ok(self.objects.lock().unwrap().contains_key(key))
+    }
+}
+
+#[async_trait]
+impl MultipartUploadProvider for MemoryStorage {
+    async fn create_multipart_upload(&self, bucket: &str, key: &str, _options: &UploadOptions) -> Result<MultipartUpload> {
+        Ok(MultipartUpload {
+            upload_id: "upload-1".to_string(),
+            key: key.to_string(),
+            bucket: bucket.to_string(),
+        })
+    }
+
+    async fn upload_part(&self, _bucket: &str, _key: &str, _upload_id: &str, part_number: u32, data: &[u8]) -> Result<UploadPart> {
+        if self.fail_part == Some(part_number) {
+            return Err(Error::UploadFailed(format!("part {}", part_number)));
+        }
+        self.parts.lock().unwrap().insert(part_number, data.to_vec());
+        Ok(UploadPart {
+            part_number,
+            etag: format!("etag-{}", part_number),
+        })
+    }
+
+    async fn complete_multipart_upload(&self, bucket: &str, key: &str, _upload_id: &str, parts: &[UploadPart]) -> Result<UploadResult> {
+        let stored = self.parts.lock().unwrap();
+        let mut data = Vec::new();
+        for (index, part) in parts.iter().enumerate() {
+            assert_eq!(part.part_number, index as u32 + 1);
+            data.extend_from_slice(&stored[&part.part_number]);
+        }
+        self.objects.lock().unwrap().insert(key.to_string(), data);
+        Ok(UploadResult {
+            key: key.to_string(),
+            bucket: bucket.to_string(),
+            etag: Some(format!("etag-{}", parts.len())),
+            version_id: None,
+        })
+    }
+
+    async fn abort_multipart_upload(&self, _bucket: &str, _key: &str, _upload_id: &str) -> Result<()> {
+        *self.aborted.lock().unwrap() = true;
+        Ok(())
+    }
+}
+
+fn chunked_stream(data: &[u8], chunk: usize) -> ByteStream {
+    let chunks: Vec<Result<Bytes>> = data.chunks(chunk).map(|c| Ok(Bytes::copy_from_slice(c))).collect();
+    Box::pin(stream::iter(chunks))
+}
+
+fn options(part_size: usize) -> StreamUploadOptions {
+    StreamUploadOptions {
+        part_size,
+        max_concurrency: 3,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_byte_range_header_value() {
+    assert_eq!(ByteRange::new(0, 99).header_value(), "bytes=0-99");
+    assert_eq!(ByteRange::from(500).header_value(), "bytes=500-");
+}
+
+#[test]
+fn test_stream_upload_options_default() {
+    let options = StreamUploadOptions::default();
+    assert_eq!(options.part_size, 8 * 1024 * 1024);
+    assert_eq!(options.max_concurrency, 4);
+}
+
+#[tokio::test]
+async fn test_small_stream_uses_single_put() {
+    let storage = MemoryStorage::default();
+    let data = b"hello streaming world".to_vec();
+
+    multipart_upload_stream(&storage, "bucket", "small.txt", chunked_stream(&data, 4), &options(64))
+        .await
+        .unwrap();
+
+    assert_eq!(*storage.single_puts.lock().unwrap(), 1);
+    assert!(storage.parts.lock().unwrap().is_empty());
+    assert_eq!(storage.get_object("bucket", "small.txt").await.unwrap(), data);
+}
+
+#[tokio::test]
+async fn test_exact_part_size_stream_uses_single_put() {
+    let storage = MemoryStorage::default();
+    let data = vec![7u8; 64];
+
+    multipart_upload_stream(&storage, "bucket", "exact.bin", chunked_stream(&data, 10), &options(64))
+        .await
+        .unwrap();
+
+    assert_eq!(*storage.single_puts.lock().unwrap(), 1);
+    assert!(storage.parts.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_large_stream_switches_to_multipart() {
+    let storage = MemoryStorage::default();
+    let data: Vec<u8> = (0..1000u32).map(|i| (i % 251) as u8).collect();
+
+    let result = multipart_upload_stream(&storage, "bucket", "large.bin", chunked_stream(&data, 37), &options(100))
+        .await
+        .unwrap();
+
+    assert_eq!(result.etag.as_deref(), Some("etag-10"));
+    assert_eq!(*storage.single_puts.lock().unwrap(), 0);
+    {
+        let parts = storage.parts.lock().unwrap();
+        assert_eq!(parts.len(), 10);
+        assert!(parts.values().all(|p| p.len() == 100));
+    }
+    assert_eq!(storage.get_object("bucket", "large.bin").await.unwrap(), data);
+}
+
+#[tokio::test]
+async fn test_failed_part_aborts_upload() {
+    let storage = MemoryStorage {
+        fail_part: Some(3),
+        ..Default::default()
+    };
+    let data = vec![1u8; 500];
+
+    let result = multipart_upload_stream(&storage, "bucket", "fail.bin", chunked_stream(&data, 50), &options(100)).await;
+
+    assert!(matches!(result, Err(Error::UploadFailed(_))));
+    assert!(*storage.aborted.lock().unwrap());
+    assert!(!storage.object_exists("bucket", "fail.bin").await.unwrap());
+}
+
+#[tokio::test]
+async fn test_stream_error_aborts_upload() {
+    let storage = MemoryStorage::default();
+    let chunks: Vec<Result<Bytes>> = vec![
+        Ok(Bytes::from(vec![0u8; 100])),
+        Ok(Bytes::from(vec![0u8; 100])),
+        Err(Error::Io(std::io::Error::other("connection reset"))),
+    ];
+
+    let result = multipart_upload_stream(&storage, "bucket", "broken.bin", Box::pin(stream::iter(chunks)), &options(100)).await;
+
+    assert!(matches!(result, Err(Error::Io(_))));
+    assert!(*storage.aborted.lock().unwrap());
+}
+
+#[tokio::test]
+async fn test_reader_stream_upload() {
+    let storage = MemoryStorage::default();
+    let data = vec![42u8; 250];
+    let reader = std::io::Cursor::new(data.clone());
+
+    multipart_upload_stream(&storage, "bucket", "reader.bin", reader_stream(reader), &options(100))
+        .await
+        .unwrap();
+
+    assert_eq!(storage.parts.lock().unwrap().len(), 3);
+    assert_eq!(storage.get_object("bucket", "reader.bin").await.unwrap(), data);
+}
@@ -0,0 +1,479 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+use chrono::{Duration, Utc};
+use futures_util::{stream, TryStreamExt};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use swissknife_cloud_sdk::sync::{ObjectSync, SyncActionKind, SyncLocation, SyncProgress};
+use swissknife_cloud_sdk::{
+    Bucket, ByteRange, ByteStream, CopyResult, DeleteError, DeleteObjectsResult, Error, ListOptions, ListResult,
+    Object, ObjectStorageProvider, Result, StreamUploadOptions, StreamingObjectProvider, UploadOptions, UploadResult,
+};
+
+const PAGE_SIZE: usize = 2;
+
+type ObjectMap = BTreeMap<(String, String), (Vec<u8>, Object)>;
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+struct MemoryStorage {
+    id: usize,
+    objects: Mutex<ObjectMap>,
+    copies: Mutex<u32>,
+    puts: Mutex<u32>,
+    full_reads: Mutex<u32>,
+}
+
+impl Default for MemoryStorage {
+    fn default() -> Self {
+        Self {
+            id: NEXT_ID.fetch_add(1, Ordering::SeqCst),
+            objects: Mutex::default(),
+            copies: Mutex::default(),
+            puts: Mutex::default(),
+            full_reads: Mutex::default(),
+        }
+    }
+}
+
+impl MemoryStorage {
+    fn insert(&self, bucket: &str, key: &str, data: &[u8], age_minutes: i64) {
+        let object = Object {
+            key: key.to_string(),
+            bucket: bucket.to_string(),
+            size: data.len() as u64,
+            content_type: Some("text/plain".to_string()),
+            etag: Some(format!("{:x}", data.iter().map(|b| *b as u64).sum::<u64>())),
+            last_modified: Some(Utc::now() - Duration::minutes(age_minutes)),
+            storage_class: None,
+            metadata: Default::default(),
+        };
+        self.objects
+            .lock()
+            .unwrap()
+            .insert((bucket.to_string(), key.to_string()), (data.to_vec(), object));
+    }
+
+    fn keys(&self, bucket: &str) -> Vec<String> {
+        self.objects
+            .lock()
+            .unwrap()
+            .keys()
+            .filter(|(b, _)| b == bucket)
+            .map(|(_, k)| k.clone())
+            .collect()
+    }
+
+    fn data(&self, bucket: &str, key: &str) -> Option<Vec<u8>> {
+        self.objects
+            .lock()
+            .unwrap()
+            .get(&(bucket.to_string(), key.to_string()))
+            .map(|(d, _)| d.clone())
+    }
+}
+
+#[async_trait]
+impl ObjectStorageProvider for MemoryStorage {
+    async fn list_buckets(&self) -> Result<Vec<Bucket>> {
+        Ok(Vec::new())
+    }
+
+    async fn create_bucket(&self, name: &str, region: Option<&str>) -> Result<Bucket> {
+        Ok(Bucket {
+            name: name.to_string(),
+            region: region.map(|r| r.to_string()),
+            created_at: None,
+        })
+    }
+
+    async fn delete_bucket(&self, _name: &str) -> Result<()> {
+        Ok(())
+    }
+
+    async fn bucket_exists(&self, _name: &str) -> Result<bool> {
+        Ok(true)
+    }
+
+    async fn list_objects(&self, bucket: &str, options: &ListOptions) -> Result<ListResult> {
+        let prefix = options.prefix.clone().unwrap_or_default();
+        let start: usize = options
+            .continuation_token
+            .as_deref()
+            .map(|t| t.parse().unwrap())
+            .unwrap_or(0);
+        let matching: Vec<Object> = self
+            .objects
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|((b, k), _)| b == bucket && k.starts_with(&prefix))
+            .map(|(_, (_, o))| o.clone())
+            .collect();
+
+        let end = (start + PAGE_SIZE).min(matching.len());
+        let is_truncated = end < matching.len();
+        Ok(ListResult {
+            objects: matching[start..end].to_vec(),
+            common_prefixes: Vec::new(),
+            is_truncated,
+            next_continuation_token: is_truncated.then(|| end.to_string()),
+        })
+    }
+
+    async fn get_object(&self, bucket: &str, key: &str) -> Result<Vec<u8>> {
+        *self.full_reads.lock().unwrap() += 1;
+        self.data(bucket, key).ok_or_else(|| Error::ObjectNotFound(key.to_string()))
+    }
+
+    async fn get_object_metadata(&self, bucket: &str, key: &str) -> Result<Object> {
+        self.objects
+            .lock()
+            .unwrap()
+            .get(&(bucket.to_string(), key.to_string()))
+            .map(|(_, o)| o.clone())
+            .ok_or_else(|| Error::ObjectNotFound(key.to_string()))
+    }
+
+    async fn put_object(&self, bucket: &str, key: &str, data: &[u8], _options: &UploadOptions) -> Result<UploadResult> {
+        *self.puts.lock().unwrap() += 1;
+        self.insert(bucket, key, data, 0);
+        Ok(UploadResult {
+            key: key.to_string(),
+            bucket: bucket.to_string(),
+            etag: None,
+            version_id: None,
+        })
+    }
+
+    async fn delete_object(&self, bucket: &str, key: &str) -> Result<()> {
+        self.objects.lock().unwrap().remove(&(bucket.to_string(), key.to_string()));
+        Ok(())
+    }
+
+    async fn delete_objects(&self, bucket: &str, keys: &[&str]) -> Result<Vec<String>> {
+        Ok(self.delete_objects_detailed(bucket, keys).await?.deleted)
+    }
+
+    async fn delete_objects_detailed(&self, bucket: &str, keys: &[&str]) -> Result<DeleteObjectsResult> {
+        let mut result = DeleteObjectsResult::default();
+        for key in keys {
+            if key.contains("locked") {
+                result.errors.push(DeleteError {
+                    key: key.to_string(),
+                    code: Some("AccessDenied".to_string()),
+                    message: "Access Denied".to_string(),
+                });
+                continue;
+            }
+            self.delete_object(bucket, key).await?;
+            result.deleted.push(key.to_string());
+        }
+        Ok(result)
+    }
+
+    async fn copy_object(&self, source_bucket: &str, source_key: &str, dest_bucket: &str, dest_key: &str) -> Result<CopyResult> {
+        *self.copies.lock().unwrap() += 1;
+        let data = self.get_object(source_bucket, source_key).await?;
+        self.insert(dest_bucket, dest_key, &data, 0);
+        Ok(CopyResult {
+            key: dest_key.to_string(),
+            bucket: dest_bucket.to_string(),
+            etag: None,
+            version_id: None,
+        })
+    }
+
+    async fn object_exists(&self, bucket: &str, key: &str) -> Result<bool> {
+        Ok(self.data(bucket, key).is_some())
+    }
+
+    fn storage_id(&self) -> Option<String> {
+        Some(format!("memory:{}", self.id))
+    }
+}
+
+#[async_trait]
+impl StreamingObjectProvider for MemoryStorage {
+    async fn get_object_stream(&self, bucket: &str, key: &str, _range: Option<ByteRange>) -> Result<ByteStream> {
+        let data = self.data(bucket, key).ok_or_else(|| Error::ObjectNotFound(key.to_string()))?;
+        let chunks: Vec<Result<Bytes>> = data.chunks(2).map(|c| Ok(Bytes::copy_from_slice(c))).collect();
+        Ok(Box::pin(stream::iter(chunks)))
+    }
+
+    async fn put_object_stream(&self, bucket: &str, key: &str, stream: ByteStream, options: &StreamUploadOptions) -> Result<UploadResult> {
+        if key.contains("broken") {
+            return Err(Error::UploadFailed(format!("{} was rejected", key)));
+        }
+        let data: Vec<u8> = stream.map_ok(|b| b.to_vec()).try_concat().await?;
+        self.put_object(bucket, key, &data, &options.upload).await
+    }
+}
+
+fn seeded_source() -> MemoryStorage {
+    let source = MemoryStorage::default();
+    source.insert("src", "photos/a.jpg", b"aaaa", 10);
+    source.insert("src", "photos/b.jpg", b"bbbb", 10);
+    source.insert("src", "photos/nested/c.jpg", b"cccc", 10);
+    source.insert("src", "other/d.jpg", b"dddd", 10);
+    source
+}
+
+#[tokio::test]
+async fn test_sync_copies_new_objects_across_providers() {
+    let source = seeded_source();
+    let dest = MemoryStorage::default();
+
+    let report = ObjectSync::new(
+        &source,
+        SyncLocation::new("src", "photos/"),
+        &dest,
+        SyncLocation::new("dst", "backup/"),
+    )
+    .run()
+    .await
+    .unwrap();
+
+    assert_eq!(report.count(SyncActionKind::Create), 3);
+    assert_eq!(report.bytes_transferred, 12);
+    assert_eq!(dest.keys("dst"), vec!["backup/a.jpg", "backup/b.jpg", "backup/nested/c.jpg"]);
+    assert_eq!(dest.data("dst", "backup/nested/c.jpg").unwrap(), b"cccc");
+    assert_eq!(*dest.puts.lock().unwrap(), 3);
+    assert_eq!(*source.copies.lock().unwrap(), 0);
+}
+
+#[tokio::test]
+async fn test_sync_skips_unchanged_and_updates_changed() {
+    let source = seeded_source();
+    let dest = MemoryStorage::default();
+    dest.insert("dst", "backup/a.jpg", b"aaaa", 0);
+    dest.insert("dst", "backup/b.jpg", b"bb", 0);
+    dest.insert("dst", "backup/nested/c.jpg", b"cccc", 20);
+
+    let report = ObjectSync::new(
+        &source,
+        SyncLocation::new("src", "photos/"),
+        &dest,
+        SyncLocation::new("dst", "backup/"),
+    )
+    .run()
+    .await
+    .unwrap();
+
+    assert_eq!(report.unchanged, 1);
+    assert_eq!(report.count(SyncActionKind::Update), 2);
+    assert_eq!(dest.data("dst", "backup/b.jpg").unwrap(), b"bbbb");
+}
+
+#[tokio::test]
+async fn test_sync_same_provider_uses_server_side_copy() {
+    let storage = seeded_source();
+
+    let report = ObjectSync::new(
+        &storage,
+        SyncLocation::new("src", "photos/"),
+        &storage,
+        SyncLocation::new("mirror", "photos/"),
+    )
+    .run()
+    .await
+    .unwrap();
+
+    assert_eq!(report.count(SyncActionKind::Create), 3);
+    assert_eq!(*storage.copies.lock().unwrap(), 3);
+    assert_eq!(*storage.puts.lock().unwrap(), 0);
+    assert_eq!(storage.keys("mirror").len(), 3);
+}
+
+#[tokio::test]
+async fn test_sync_delete_removes_extraneous_objects() {
+    let source = seeded_source();
+    let dest = MemoryStorage::default();
+    dest.insert("dst", "backup/stale.jpg", b"old", 0);
+    dest.insert("dst", "elsewhere/keep.jpg", b"keep", 0);
+
+    let report = ObjectSync::new(
+        &source,
+        SyncLocation::new("src", "photos/"),
+        &dest,
+        SyncLocation::new("dst", "backup/"),
+    )
+    .with_delete(true)
+    .run()
+    .await
+    .unwrap();
+
+    assert_eq!(report.count(SyncActionKind::Delete), 1);
+    assert!(dest.data("dst", "backup/stale.jpg").is_none());
+    assert!(dest.data("dst", "elsewhere/keep.jpg").is_some());
+}
+
+#[tokio::test]
+async fn test_sync_without_delete_keeps_extraneous_objects() {
+    let source = seeded_source();
+    let dest = MemoryStorage::default();
+    dest.insert("dst", "backup/stale.jpg", b"old", 0);
+
+    let report = ObjectSync::new(
+        &source,
+        SyncLocation::new("src", "photos/"),
+        &dest,
+        SyncLocation::new("dst", "backup/"),
+    )
+    .run()
+    .await
+    .unwrap();
+
+    assert_eq!(report.count(SyncActionKind::Delete), 0);
+    assert!(dest.data("dst", "backup/stale.jpg").is_some());
+}
+
+#[tokio::test]
+async fn test_sync_dry_run_reports_without_changes() {
+    let source = seeded_source();
+    let dest = MemoryStorage::default();
+    dest.insert("dst", "backup/stale.jpg", b"old", 0);
+
+    let report = ObjectSync::new(
+        &source,
+        SyncLocation::new("src", "photos/"),
+        &dest,
+        SyncLocation::new("dst", "backup/"),
+    )
+    .with_delete(true)
+    .with_dry_run(true)
+    .run()
+    .await
+    .unwrap();
+
+    assert!(report.dry_run);
+    assert_eq!(report.count(SyncActionKind::Create), 3);
+    assert_eq!(report.count(SyncActionKind::Delete), 1);
+    assert_eq!(report.bytes_transferred, 0);
+    assert_eq!(dest.keys("dst"), vec!["backup/stale.jpg"]);
+}
+
+#[tokio::test]
+async fn test_sync_reports_progress() {
+    let source = seeded_source();
+    let dest = MemoryStorage::default();
+    let events: Arc<Mutex<Vec<SyncProgress>>> = Arc::new(Mutex::new(Vec::new()));
+    let recorded = events.clone();
+
+    ObjectSync::new(
+        &source,
+        SyncLocation::new("src", "photos/"),
+        &dest,
+        SyncLocation::new("dst", "backup/"),
+    )
+    .with_max_concurrency(2)
+    .with_progress(move |progress| recorded.lock().unwrap().push(progress.clone()))
+    .run()
+    .await
+    .unwrap();
+
+    let events = events.lock().unwrap();
+    assert_eq!(events.len(), 3);
+    assert!(events.iter().all(|e| e.total == 3));
+    let last = events.iter().max_by_key(|e| e.completed).unwrap();
+    assert_eq!(last.completed, 3);
+    assert_eq!(last.bytes_transferred, 12);
+}
+
+#[tokio::test]
+async fn test_sync_streams_objects_between_providers() {
+    let source = seeded_source();
+    let dest = MemoryStorage::default();
+
+    ObjectSync::new(
+        &source,
+        SyncLocation::new("src", "photos/"),
+        &dest,
+        SyncLocation::new("dst", "backup/"),
+    )
+    .run()
+    .await
+    .unwrap();
+
+    assert_eq!(dest.data("dst", "backup/a.jpg").unwrap(), b"aaaa");
+    assert_eq!(*source.full_reads.lock().unwrap(), 0);
+}
+
+#[tokio::test]
+async fn test_sync_reports_failed_deletes() {
+    let source = seeded_source();
+    let dest = MemoryStorage::default();
+    dest.insert("dst", "backup/stale.jpg", b"old", 0);
+    dest.insert("dst", "backup/locked.jpg", b"old", 0);
+    let events: Arc<Mutex<Vec<SyncProgress>>> = Arc::new(Mutex::new(Vec::new()));
+    let recorded = events.clone();
+
+    let report = ObjectSync::new(
+        &source,
+        SyncLocation::new("src", "photos/"),
+        &dest,
+        SyncLocation::new("dst", "backup/"),
+    )
+    .with_delete(true)
+    .with_progress(move |progress| recorded.lock().unwrap().push(progress.clone()))
+    .run()
+    .await
+    .unwrap();
+
+    assert_eq!(report.count(SyncActionKind::Delete), 2);
+    assert_eq!(report.failed_deletes.len(), 1);
+    assert_eq!(report.failed_deletes[0].key, "backup/locked.jpg");
+    assert_eq!(report.failed_deletes[0].code.as_deref(), Some("AccessDenied"));
+    assert!(dest.data("dst", "backup/locked.jpg").is_some());
+    assert!(dest.data("dst", "backup/stale.jpg").is_none());
+    assert_eq!(events.lock().unwrap().len(), 4);
+}
+
+#[tokio::test]
+async fn test_sync_reports_failed_transfers_and_continues() {
+    let source = seeded_source();
+    source.insert("src", "photos/broken.jpg", b"eeee", 10);
+    let dest = MemoryStorage::default();
+    let events: Arc<Mutex<Vec<SyncProgress>>> = Arc::new(Mutex::new(Vec::new()));
+    let recorded = events.clone();
+
+    let report = ObjectSync::new(
+        &source,
+        SyncLocation::new("src", "photos/"),
+        &dest,
+        SyncLocation::new("dst", "backup/"),
+    )
+    .with_max_concurrency(1)
+    .with_progress(move |progress| recorded.lock().unwrap().push(progress.clone()))
+    .run()
+    .await
+    .unwrap();
+
+    assert_eq!(report.count(SyncActionKind::Create), 4);
+    assert_eq!(report.failed_transfers.len(), 1);
+    assert_eq!(report.failed_transfers[0].action.dest_key, "backup/broken.jpg");
+    assert_eq!(report.failed_transfers[0].message, "Upload failed: backup/broken.jpg was rejected");
+    assert_eq!(dest.keys("dst"), vec!["backup/a.jpg", "backup/b.jpg", "backup/nested/c.jpg"]);
+    assert_eq!(report.bytes_transferred, 12);
+    assert_eq!(events.lock().unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn test_sync_separate_stores_never_copy_server_side() {
+    let source = seeded_source();
+    let dest = MemoryStorage::default();
+
+    ObjectSync::new(
+        &source,
+        SyncLocation::new("src", "photos/"),
+        &dest,
+        SyncLocation::new("src", "photos/"),
+    )
+    .run()
+    .await
+    .unwrap();
+
+    assert_eq!(*source.copies.lock().unwrap(), 0);
+    assert_eq!(*dest.puts.lock().unwrap(), 3);
+}
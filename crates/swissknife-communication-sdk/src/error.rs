@@ -19,6 +19,21 @@ pub enum Error {
 
     #[error("Configuration error: {0}")]
     Config(String),
+
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+
+    #[error("{provider} does not support {feature}")]
+    Unsupported { provider: &'static str, feature: &'static str },
+
+    #[error("SMTP error: {code} - {message}")]
+    Smtp { code: u16, message: String },
+
+    #[error("Timeout: {0}")]
+    Timeout(String),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
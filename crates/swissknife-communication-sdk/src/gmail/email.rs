@@ -1,10 +1,13 @@
 use crate::{Error, Result};
 use crate::gmail::GmailClient;
 use async_trait::async_trait;
-use serde::{Deserialize, Serialize};
+use base64::Engine;
+use serde::Deserialize;
 
 #[cfg(feature = "email")]
-use crate::email::{Email, EmailResponse, EmailSender};
+use crate::email::{Email, EmailFeature, EmailResponse, EmailSender};
+#[cfg(feature = "email")]
+use crate::mime::{MimeAttachment, MimeMessage};
 
 impl GmailClient {
     pub async fn send_raw(&self, user_id: &str, raw_message: &str) -> Result<GmailMessage> {
@@ -22,7 +25,7 @@ impl GmailClient {
             let text = response.text().await.unwrap_or_default();
             return Err(Error::Api {
                 message: text,
-                code: Some(status.to_string()),
+                code: status.as_u16() as i32,
             });
         }
 
@@ -51,7 +54,7 @@ impl GmailClient {
             let text = response.text().await.unwrap_or_default();
             return Err(Error::Api {
                 message: text,
-                code: Some(status.to_string()),
+                code: status.as_u16() as i32,
             });
         }
 
@@ -72,7 +75,7 @@ impl GmailClient {
             let text = response.text().await.unwrap_or_default();
             return Err(Error::Api {
                 message: text,
-                code: Some(status.to_string()),
+                code: status.as_u16() as i32,
             });
         }
 
@@ -92,7 +95,7 @@ impl GmailClient {
             let text = response.text().await.unwrap_or_default();
             return Err(Error::Api {
                 message: text,
-                code: Some(status.to_string()),
+                code: status.as_u16() as i32,
             });
         }
 
@@ -111,7 +114,7 @@ impl GmailClient {
             let text = response.text().await.unwrap_or_default();
             return Err(Error::Api {
                 message: text,
-                code: Some(status.to_string()),
+                code: status.as_u16() as i32,
             });
         }
 
@@ -120,37 +123,6 @@ impl GmailClient {
     }
 }
 
-use base64::Engine;
-
-fn build_raw_email(from: &str, to: &[&str], subject: &str, text: Option<&str>, html: Option<&str>) -> String {
-    let boundary = "boundary_swissknife";
-    let to_header = to.join(", ");
-
-    let mut raw = format!(
-        "From: {}\r\nTo: {}\r\nSubject: {}\r\nMIME-Version: 1.0\r\n",
-        from, to_header, subject
-    );
-
-    if text.is_some() && html.is_some() {
-        raw.push_str(&format!("Content-Type: multipart/alternative; boundary=\"{}\"\r\n\r\n", boundary));
-        if let Some(t) = text {
-            raw.push_str(&format!("--{}\r\nContent-Type: text/plain; charset=\"UTF-8\"\r\n\r\n{}\r\n", boundary, t));
-        }
-        if let Some(h) = html {
-            raw.push_str(&format!("--{}\r\nContent-Type: text/html; charset=\"UTF-8\"\r\n\r\n{}\r\n", boundary, h));
-        }
-        raw.push_str(&format!("--{}--", boundary));
-    } else if let Some(h) = html {
-        raw.push_str("Content-Type: text/html; charset=\"UTF-8\"\r\n\r\n");
-        raw.push_str(h);
-    } else if let Some(t) = text {
-        raw.push_str("Content-Type: text/plain; charset=\"UTF-8\"\r\n\r\n");
-        raw.push_str(t);
-    }
-
-    raw
-}
-
 #[derive(Debug, Clone, Deserialize)]
 pub struct GmailMessage {
     pub id: String,
@@ -217,21 +189,32 @@ struct GmailLabelsResponse {
 #[async_trait]
 impl EmailSender for GmailClient {
     async fn send_email(&self, email: &Email) -> Result<EmailResponse> {
-        let from = if let Some(ref name) = email.from.name {
-            format!("{} <{}>", name, email.from.email)
-        } else {
-            email.from.email.clone()
-        };
-
-        let to: Vec<&str> = email.to.iter().map(|a| a.email.as_str()).collect();
-
-        let raw = build_raw_email(
-            &from,
-            &to,
-            &email.subject,
-            email.text.as_deref(),
-            email.html.as_deref(),
-        );
+        email.check_supported("Gmail", &[EmailFeature::Tags, EmailFeature::Metadata, EmailFeature::Template])?;
+
+        let addresses = |list: &[crate::email::EmailAddress]| list.iter().map(ToString::to_string).collect();
+        let raw = MimeMessage {
+            from: email.from.to_string(),
+            to: addresses(&email.to),
+            cc: addresses(&email.cc),
+            bcc: addresses(&email.bcc),
+            reply_to: email.reply_to.as_ref().map(ToString::to_string),
+            subject: &email.subject,
+            text: email.text.as_deref(),
+            html: email.html.as_deref(),
+            headers: email.headers.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect(),
+            attachments: email
+                .attachments
+                .iter()
+                .map(|a| MimeAttachment {
+                    filename: &a.filename,
+                    content_type: &a.content_type,
+                    data: &a.content,
+                    content_id: a.content_id.as_deref(),
+                })
+                .collect(),
+            ..Default::default()
+        }
+        .build()?;
 
         let message = self.send_raw("me", &raw).await?;
 
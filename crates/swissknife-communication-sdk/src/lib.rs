@@ -45,6 +45,9 @@ pub mod outlook;
 #[cfg(feature = "smtp")]
 pub mod smtp;
 
+#[cfg(any(feature = "smtp", all(feature = "gmail", feature = "email")))]
+mod mime;
+
 pub use error::{Error, Result};
 
 #[cfg(feature = "sms")]
@@ -113,6 +116,7 @@ pub mod whatsapp {
 #[cfg(feature = "email")]
 pub mod email {
     use async_trait::async_trait;
+    use std::collections::HashMap;
 
     #[derive(Debug, Clone)]
     pub struct EmailAddress {
@@ -136,6 +140,57 @@ pub mod email {
         }
     }
 
+    impl std::fmt::Display for EmailAddress {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match &self.name {
+                Some(name) => write!(f, "{} <{}>", name, self.email),
+                None => f.write_str(&self.email),
+            }
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct Attachment {
+        pub filename: String,
+        pub content_type: String,
+        pub content: Vec<u8>,
+        // Set for inline parts, which the HTML body references as `cid:<content_id>`.
+        pub content_id: Option<String>,
+    }
+
+    impl Attachment {
+        pub fn new(filename: impl Into<String>, content_type: impl Into<String>, content: Vec<u8>) -> Self {
+            Self {
+                filename: filename.into(),
+                content_type: content_type.into(),
+                content,
+                content_id: None,
+            }
+        }
+
+        pub fn inline(
+            filename: impl Into<String>,
+            content_type: impl Into<String>,
+            content: Vec<u8>,
+            content_id: impl Into<String>,
+        ) -> Self {
+            Self {
+                content_id: Some(content_id.into()),
+                ..Self::new(filename, content_type, content)
+            }
+        }
+
+        pub fn is_inline(&self) -> bool {
+            self.content_id.is_some()
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct EmailTemplate {
+        pub id: String,
+        pub variables: serde_json::Value,
+    }
+
     #[derive(Debug, Clone)]
     pub struct Email {
         pub from: EmailAddress,
@@ -143,6 +198,14 @@ pub mod email {
         pub subject: String,
         pub text: Option<String>,
         pub html: Option<String>,
+        pub cc: Vec<EmailAddress>,
+        pub bcc: Vec<EmailAddress>,
+        pub reply_to: Option<EmailAddress>,
+        pub headers: HashMap<String, String>,
+        pub attachments: Vec<Attachment>,
+        pub tags: Vec<String>,
+        pub metadata: HashMap<String, String>,
+        pub template: Option<EmailTemplate>,
     }
 
     impl Email {
@@ -153,6 +216,14 @@ pub mod email {
                 subject: subject.into(),
                 text: None,
                 html: None,
+                cc: Vec::new(),
+                bcc: Vec::new(),
+                reply_to: None,
+                headers: HashMap::new(),
+                attachments: Vec::new(),
+                tags: Vec::new(),
+                metadata: HashMap::new(),
+                template: None,
             }
         }
 
@@ -170,6 +241,104 @@ pub mod email {
             self.to.push(to);
             self
         }
+
+        pub fn cc(mut self, cc: EmailAddress) -> Self {
+            self.cc.push(cc);
+            self
+        }
+
+        pub fn bcc(mut self, bcc: EmailAddress) -> Self {
+            self.bcc.push(bcc);
+            self
+        }
+
+        pub fn reply_to(mut self, reply_to: EmailAddress) -> Self {
+            self.reply_to = Some(reply_to);
+            self
+        }
+
+        pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+            self.headers.insert(name.into(), value.into());
+            self
+        }
+
+        pub fn attach(mut self, attachment: Attachment) -> Self {
+            self.attachments.push(attachment);
+            self
+        }
+
+        pub fn tag(mut self, tag: impl Into<String>) -> Self {
+            self.tags.push(tag.into());
+            self
+        }
+
+        pub fn metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+            self.metadata.insert(key.into(), value.into());
+            self
+        }
+
+        pub fn template(mut self, id: impl Into<String>, variables: serde_json::Value) -> Self {
+            self.template = Some(EmailTemplate {
+                id: id.into(),
+                variables,
+            });
+            self
+        }
+
+        // Rejects the email if it uses any feature the provider cannot honour,
+        // instead of silently dropping it.
+        pub fn check_supported(&self, provider: &'static str, unsupported: &[EmailFeature]) -> crate::Result<()> {
+            match unsupported.iter().find(|feature| feature.is_used_by(self)) {
+                Some(feature) => Err(crate::Error::Unsupported {
+                    provider,
+                    feature: feature.as_str(),
+                }),
+                None => Ok(()),
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum EmailFeature {
+        Cc,
+        Bcc,
+        ReplyTo,
+        Headers,
+        Attachments,
+        InlineAttachments,
+        Tags,
+        Metadata,
+        Template,
+    }
+
+    impl EmailFeature {
+        pub fn as_str(&self) -> &'static str {
+            match self {
+                EmailFeature::Cc => "cc",
+                EmailFeature::Bcc => "bcc",
+                EmailFeature::ReplyTo => "reply_to",
+                EmailFeature::Headers => "custom headers",
+                EmailFeature::Attachments => "attachments",
+                EmailFeature::InlineAttachments => "inline attachments",
+                EmailFeature::Tags => "tags",
+                EmailFeature::Metadata => "metadata",
+                EmailFeature::Template => "templates",
+            }
+        }
+
+        fn is_used_by(&self, email: &Email) -> bool {
+            match self {
+                EmailFeature::Cc => !email.cc.is_empty(),
+                EmailFeature::Bcc => !email.bcc.is_empty(),
+                EmailFeature::ReplyTo => email.reply_to.is_some(),
+                EmailFeature::Headers => !email.headers.is_empty(),
+                EmailFeature::Attachments => email.attachments.iter().any(|a| !a.is_inline()),
+                EmailFeature::InlineAttachments => email.attachments.iter().any(Attachment::is_inline),
+                EmailFeature::Tags => !email.tags.is_empty(),
+                EmailFeature::Metadata => !email.metadata.is_empty(),
+                EmailFeature::Template => email.template.is_some(),
+            }
+        }
     }
 
     #[derive(Debug, Clone)]
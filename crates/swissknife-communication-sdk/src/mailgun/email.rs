@@ -2,6 +2,7 @@ use crate::{Error, Result};
 use crate::mailgun::MailgunClient;
 use async_trait::async_trait;
 use serde::Deserialize;
+use std::collections::HashMap;
 
 #[cfg(feature = "email")]
 use crate::email::{Email, EmailResponse, EmailSender};
@@ -28,6 +29,33 @@ impl MailgunClient {
         if let Some(reply_to) = message.reply_to {
             form = form.text("h:Reply-To", reply_to);
         }
+        for (name, value) in message.headers {
+            form = form.text(format!("h:{}", name), value);
+        }
+        for attachment in message.attachments {
+            // Inline parts are referenced from HTML as `cid:<file name>`.
+            let (field, file_name) = match attachment.content_id {
+                Some(content_id) => ("inline", content_id),
+                None => ("attachment", attachment.filename),
+            };
+            let part = reqwest::multipart::Part::bytes(attachment.data)
+                .file_name(file_name)
+                .mime_str(&attachment.content_type)
+                .map_err(|e| Error::InvalidRequest(format!("Invalid attachment content type: {}", e)))?;
+            form = form.part(field, part);
+        }
+        for tag in message.tags {
+            form = form.text("o:tag", tag);
+        }
+        for (key, value) in message.variables {
+            form = form.text(format!("v:{}", key), value);
+        }
+        if let Some(template) = message.template {
+            form = form.text("template", template);
+        }
+        if let Some(variables) = message.template_variables {
+            form = form.text("t:variables", variables.to_string());
+        }
 
         let response = self.client()
             .post(format!("{}/{}/messages", self.base_url(), self.domain()))
@@ -41,7 +69,7 @@ impl MailgunClient {
             let text = response.text().await.unwrap_or_default();
             return Err(Error::Api {
                 message: text,
-                code: Some(status.to_string()),
+                code: status.as_u16() as i32,
             });
         }
 
@@ -76,7 +104,7 @@ impl MailgunClient {
             let text = response.text().await.unwrap_or_default();
             return Err(Error::Api {
                 message: text,
-                code: Some(status.to_string()),
+                code: status.as_u16() as i32,
             });
         }
 
@@ -97,7 +125,7 @@ impl MailgunClient {
             let text = response.text().await.unwrap_or_default();
             return Err(Error::Api {
                 message: text,
-                code: Some(status.to_string()),
+                code: status.as_u16() as i32,
             });
         }
 
@@ -116,6 +144,20 @@ pub struct MailgunMessage {
     pub cc: Vec<String>,
     pub bcc: Vec<String>,
     pub reply_to: Option<String>,
+    pub headers: Vec<(String, String)>,
+    pub attachments: Vec<MailgunAttachment>,
+    pub tags: Vec<String>,
+    pub variables: HashMap<String, String>,
+    pub template: Option<String>,
+    pub template_variables: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MailgunAttachment {
+    pub filename: String,
+    pub content_type: String,
+    pub data: Vec<u8>,
+    pub content_id: Option<String>,
 }
 
 impl MailgunMessage {
@@ -157,6 +199,37 @@ impl MailgunMessage {
         self.reply_to = Some(reply_to.to_string());
         self
     }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn attach(mut self, filename: &str, content_type: &str, data: Vec<u8>) -> Self {
+        self.attachments.push(MailgunAttachment {
+            filename: filename.to_string(),
+            content_type: content_type.to_string(),
+            data,
+            content_id: None,
+        });
+        self
+    }
+
+    pub fn tag(mut self, tag: &str) -> Self {
+        self.tags.push(tag.to_string());
+        self
+    }
+
+    pub fn variable(mut self, key: &str, value: &str) -> Self {
+        self.variables.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    pub fn template(mut self, name: &str, variables: serde_json::Value) -> Self {
+        self.template = Some(name.to_string());
+        self.template_variables = Some(variables);
+        self
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -210,27 +283,34 @@ pub struct ValidationResponse {
 #[async_trait]
 impl EmailSender for MailgunClient {
     async fn send_email(&self, email: &Email) -> Result<EmailResponse> {
-        let from = if let Some(ref name) = email.from.name {
-            format!("{} <{}>", name, email.from.email)
-        } else {
-            email.from.email.clone()
+        let addresses = |list: &[crate::email::EmailAddress]| -> Vec<String> {
+            list.iter().map(ToString::to_string).collect()
         };
 
-        let to: Vec<String> = email.to.iter().map(|a| {
-            if let Some(ref name) = a.name {
-                format!("{} <{}>", name, a.email)
-            } else {
-                a.email.clone()
-            }
-        }).collect();
-
-        let mut message = MailgunMessage {
-            from,
-            to,
+        let message = MailgunMessage {
+            from: email.from.to_string(),
+            to: addresses(&email.to),
             subject: email.subject.clone(),
             text: email.text.clone(),
             html: email.html.clone(),
-            ..Default::default()
+            cc: addresses(&email.cc),
+            bcc: addresses(&email.bcc),
+            reply_to: email.reply_to.as_ref().map(ToString::to_string),
+            headers: email.headers.iter().map(|(k, v)| (k.clone(), v.clone())).collect(),
+            attachments: email
+                .attachments
+                .iter()
+                .map(|a| MailgunAttachment {
+                    filename: a.filename.clone(),
+                    content_type: a.content_type.clone(),
+                    data: a.content.clone(),
+                    content_id: a.content_id.clone(),
+                })
+                .collect(),
+            tags: email.tags.clone(),
+            variables: email.metadata.clone(),
+            template: email.template.as_ref().map(|t| t.id.clone()),
+            template_variables: email.template.as_ref().map(|t| t.variables.clone()),
         };
 
         let response = self.send_message(message).await?;
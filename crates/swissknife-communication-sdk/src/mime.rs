@@ -0,0 +1,259 @@
+use crate::{Error, Result};
+use base64::Engine;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const MAX_LINE: usize = 998;
+
+// Headers the builder writes itself; custom headers may not override them.
+const RESERVED_HEADERS: &[&str] = &[
+    "from",
+    "to",
+    "cc",
+    "bcc",
+    "reply-to",
+    "subject",
+    "date",
+    "message-id",
+    "mime-version",
+    "content-type",
+    "content-transfer-encoding",
+];
+
+pub(crate) struct MimeAttachment<'a> {
+    pub filename: &'a str,
+    pub content_type: &'a str,
+    pub data: &'a [u8],
+    pub content_id: Option<&'a str>,
+}
+
+#[derive(Default)]
+pub(crate) struct MimeMessage<'a> {
+    pub from: String,
+    pub to: Vec<String>,
+    pub cc: Vec<String>,
+    // Only written when non-empty. SMTP keeps Bcc in the envelope; the Gmail
+    // API reads it from the header and strips it before delivery.
+    pub bcc: Vec<String>,
+    pub reply_to: Option<String>,
+    pub subject: &'a str,
+    pub text: Option<&'a str>,
+    pub html: Option<&'a str>,
+    pub headers: Vec<(&'a str, &'a str)>,
+    pub attachments: Vec<MimeAttachment<'a>>,
+    pub message_id: Option<String>,
+    pub date: Option<String>,
+}
+
+impl MimeMessage<'_> {
+    // Builds an RFC 5322 message: multipart/mixed for regular attachments,
+    // multipart/related for inline ones and multipart/alternative for text + HTML.
+    pub fn build(&self) -> Result<String> {
+        let seed = unique_id();
+        let mut out = String::new();
+        header(&mut out, "From", &encode_address(&self.from)?);
+        header(&mut out, "To", &encode_addresses(&self.to)?);
+        if !self.cc.is_empty() {
+            header(&mut out, "Cc", &encode_addresses(&self.cc)?);
+        }
+        if !self.bcc.is_empty() {
+            header(&mut out, "Bcc", &encode_addresses(&self.bcc)?);
+        }
+        if let Some(reply_to) = &self.reply_to {
+            header(&mut out, "Reply-To", &encode_address(reply_to)?);
+        }
+        header(&mut out, "Subject", &encode_word(self.subject));
+        if let Some(date) = &self.date {
+            header(&mut out, "Date", date);
+        }
+        if let Some(message_id) = &self.message_id {
+            header(&mut out, "Message-ID", message_id);
+        }
+        for (name, value) in &self.headers {
+            check_header(name, value)?;
+            header(&mut out, name, &encode_word(value));
+        }
+        header(&mut out, "MIME-Version", "1.0");
+
+        let (inline, regular): (Vec<_>, Vec<_>) = self.attachments.iter().partition(|a| a.content_id.is_some());
+        if regular.is_empty() {
+            self.related(&mut out, &inline, &seed);
+        } else {
+            let boundary = format!("=_mixed_{}", seed);
+            multipart(&mut out, "mixed", &boundary);
+            out.push_str(&format!("--{}\r\n", boundary));
+            self.related(&mut out, &inline, &seed);
+            for attachment in regular {
+                out.push_str(&format!("\r\n--{}\r\n", boundary));
+                attachment_part(&mut out, attachment, "attachment");
+            }
+            out.push_str(&format!("\r\n--{}--\r\n", boundary));
+        }
+        Ok(out)
+    }
+
+    fn related(&self, out: &mut String, inline: &[&MimeAttachment], seed: &str) {
+        if inline.is_empty() {
+            return self.alternative(out, seed);
+        }
+        let boundary = format!("=_rel_{}", seed);
+        multipart(out, "related", &boundary);
+        out.push_str(&format!("--{}\r\n", boundary));
+        self.alternative(out, seed);
+        for attachment in inline {
+            out.push_str(&format!("\r\n--{}\r\n", boundary));
+            attachment_part(out, attachment, "inline");
+        }
+        out.push_str(&format!("\r\n--{}--\r\n", boundary));
+    }
+
+    fn alternative(&self, out: &mut String, seed: &str) {
+        match (self.text, self.html) {
+            (Some(text), Some(html)) => {
+                let boundary = format!("=_alt_{}", seed);
+                multipart(out, "alternative", &boundary);
+                out.push_str(&format!("--{}\r\n", boundary));
+                text_part(out, "text/plain", text);
+                out.push_str(&format!("\r\n--{}\r\n", boundary));
+                text_part(out, "text/html", html);
+                out.push_str(&format!("\r\n--{}--\r\n", boundary));
+            }
+            (None, Some(html)) => text_part(out, "text/html", html),
+            (text, None) => text_part(out, "text/plain", text.unwrap_or_default()),
+        }
+    }
+}
+
+pub(crate) fn unique_id() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    format!(
+        "{:x}.{:x}.{:x}",
+        nanos,
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    )
+}
+
+// Bare address for the SMTP envelope, from either `a@b` or `Name <a@b>`.
+#[cfg(feature = "smtp")]
+pub(crate) fn mailbox(address: &str) -> &str {
+    match (address.rfind('<'), address.rfind('>')) {
+        (Some(start), Some(end)) if start < end => address[start + 1..end].trim(),
+        _ => address.trim(),
+    }
+}
+
+// Accepts `a@b` or `Name <a@b>`. Line breaks would end a header or SMTP
+// command early, and stray angle brackets would smuggle a second address into
+// the envelope.
+pub(crate) fn check_address(address: &str) -> Result<()> {
+    let invalid = || Error::InvalidRequest(format!("Invalid address: {:?}", address));
+    if address.contains(['\r', '\n']) {
+        return Err(invalid());
+    }
+    let address = address.trim();
+    let mailbox = match address.split_once('<') {
+        Some((_, rest)) => rest.strip_suffix('>').ok_or_else(invalid)?,
+        None => address,
+    };
+    if mailbox.trim().is_empty() || mailbox.contains(['<', '>']) {
+        return Err(invalid());
+    }
+    Ok(())
+}
+
+fn check_header(name: &str, value: &str) -> Result<()> {
+    let valid_name = !name.is_empty() && name.bytes().all(|b| b.is_ascii_graphic() && b != b':');
+    if !valid_name || value.contains(['\r', '\n']) {
+        return Err(Error::InvalidRequest(format!("Invalid header: {}", name)));
+    }
+    if RESERVED_HEADERS.contains(&name.to_ascii_lowercase().as_str()) {
+        return Err(Error::InvalidRequest(format!("Header {} cannot be overridden", name)));
+    }
+    Ok(())
+}
+
+fn multipart(out: &mut String, subtype: &str, boundary: &str) {
+    header(out, "Content-Type", &format!("multipart/{}; boundary=\"{}\"", subtype, boundary));
+    out.push_str("\r\n");
+}
+
+fn text_part(out: &mut String, content_type: &str, content: &str) {
+    header(out, "Content-Type", &format!("{}; charset=\"UTF-8\"", content_type));
+    let content = normalize_newlines(content);
+    if content.is_ascii() && content.split("\r\n").all(|line| line.len() <= MAX_LINE) {
+        header(out, "Content-Transfer-Encoding", "7bit");
+        out.push_str("\r\n");
+        out.push_str(&content);
+    } else {
+        header(out, "Content-Transfer-Encoding", "base64");
+        out.push_str("\r\n");
+        out.push_str(&wrap_base64(content.as_bytes()));
+    }
+}
+
+fn attachment_part(out: &mut String, attachment: &MimeAttachment, disposition: &str) {
+    let filename = encode_word(attachment.filename).replace('"', "");
+    header(out, "Content-Type", &format!("{}; name=\"{}\"", attachment.content_type, filename));
+    header(out, "Content-Disposition", &format!("{}; filename=\"{}\"", disposition, filename));
+    if let Some(content_id) = attachment.content_id {
+        header(out, "Content-ID", &format!("<{}>", content_id.trim_matches(['<', '>'])));
+    }
+    header(out, "Content-Transfer-Encoding", "base64");
+    out.push_str("\r\n");
+    out.push_str(&wrap_base64(attachment.data));
+}
+
+fn header(out: &mut String, name: &str, value: &str) {
+    out.push_str(name);
+    out.push_str(": ");
+    out.push_str(value);
+    out.push_str("\r\n");
+}
+
+fn normalize_newlines(text: &str) -> String {
+    text.replace("\r\n", "\n").replace('\r', "\n").replace('\n', "\r\n")
+}
+
+fn wrap_base64(data: &[u8]) -> String {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(data);
+    let mut out = String::with_capacity(encoded.len() + encoded.len() / 76 * 2 + 2);
+    for chunk in encoded.as_bytes().chunks(76) {
+        out.push_str(std::str::from_utf8(chunk).unwrap_or_default());
+        out.push_str("\r\n");
+    }
+    out
+}
+
+// RFC 2047 encoded-word for header text that isn't plain ASCII.
+fn encode_word(value: &str) -> String {
+    if value.is_ascii() && !value.contains(['\r', '\n']) {
+        value.to_string()
+    } else {
+        format!("=?UTF-8?B?{}?=", base64::engine::general_purpose::STANDARD.encode(value))
+    }
+}
+
+fn encode_address(address: &str) -> Result<String> {
+    check_address(address)?;
+    Ok(match address.rsplit_once('<') {
+        Some((name, addr)) if !name.trim().is_empty() => {
+            let name = name.trim().trim_matches('"');
+            let name = if name.is_ascii() {
+                format!("\"{}\"", name.replace(['\\', '"'], ""))
+            } else {
+                encode_word(name)
+            };
+            format!("{} <{}", name, addr.trim())
+        }
+        _ => address.trim().to_string(),
+    })
+}
+
+fn encode_addresses(addresses: &[String]) -> Result<String> {
+    Ok(addresses.iter().map(|a| encode_address(a)).collect::<Result<Vec<_>>>()?.join(", "))
+}
@@ -1,10 +1,11 @@
 use crate::{Error, Result};
 use crate::outlook::OutlookClient;
 use async_trait::async_trait;
+use base64::Engine;
 use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "email")]
-use crate::email::{Email, EmailAddress, EmailResponse, EmailSender};
+use crate::email::{Email, EmailAddress, EmailFeature, EmailResponse, EmailSender};
 
 impl OutlookClient {
     pub async fn send_mail(&self, message: OutlookMailMessage, save_to_sent: bool) -> Result<()> {
@@ -25,7 +26,7 @@ impl OutlookClient {
             let text = response.text().await.unwrap_or_default();
             return Err(Error::Api {
                 message: text,
-                code: Some(status.to_string()),
+                code: status.as_u16() as i32,
             });
         }
 
@@ -51,7 +52,7 @@ impl OutlookClient {
             let text = response.text().await.unwrap_or_default();
             return Err(Error::Api {
                 message: text,
-                code: Some(status.to_string()),
+                code: status.as_u16() as i32,
             });
         }
 
@@ -71,7 +72,7 @@ impl OutlookClient {
             let text = response.text().await.unwrap_or_default();
             return Err(Error::Api {
                 message: text,
-                code: Some(status.to_string()),
+                code: status.as_u16() as i32,
             });
         }
 
@@ -91,7 +92,7 @@ impl OutlookClient {
             let text = response.text().await.unwrap_or_default();
             return Err(Error::Api {
                 message: text,
-                code: Some(status.to_string()),
+                code: status.as_u16() as i32,
             });
         }
 
@@ -110,7 +111,7 @@ impl OutlookClient {
             let text = response.text().await.unwrap_or_default();
             return Err(Error::Api {
                 message: text,
-                code: Some(status.to_string()),
+                code: status.as_u16() as i32,
             });
         }
 
@@ -131,6 +132,60 @@ pub struct OutlookMailMessage {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "bccRecipients")]
     pub bcc_recipients: Option<Vec<OutlookRecipient>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "replyTo")]
+    pub reply_to: Option<Vec<OutlookRecipient>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "internetMessageHeaders")]
+    pub internet_message_headers: Option<Vec<OutlookHeader>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attachments: Option<Vec<OutlookAttachment>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub categories: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OutlookHeader {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OutlookAttachment {
+    #[serde(rename = "@odata.type")]
+    pub odata_type: String,
+    pub name: String,
+    #[serde(rename = "contentType")]
+    pub content_type: String,
+    // Base64-encoded file content.
+    #[serde(rename = "contentBytes")]
+    pub content_bytes: String,
+    #[serde(rename = "isInline")]
+    pub is_inline: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "contentId")]
+    pub content_id: Option<String>,
+}
+
+impl OutlookAttachment {
+    pub fn file(name: &str, content_type: &str, data: &[u8]) -> Self {
+        Self {
+            odata_type: "#microsoft.graph.fileAttachment".to_string(),
+            name: name.to_string(),
+            content_type: content_type.to_string(),
+            content_bytes: base64::engine::general_purpose::STANDARD.encode(data),
+            is_inline: false,
+            content_id: None,
+        }
+    }
+
+    pub fn inline(name: &str, content_type: &str, data: &[u8], content_id: &str) -> Self {
+        Self {
+            is_inline: true,
+            content_id: Some(content_id.to_string()),
+            ..Self::file(name, content_type, data)
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -169,6 +224,10 @@ impl OutlookMailMessage {
             }],
             cc_recipients: None,
             bcc_recipients: None,
+            reply_to: None,
+            internet_message_headers: None,
+            attachments: None,
+            categories: None,
         }
     }
 
@@ -217,6 +276,35 @@ impl OutlookMailMessage {
         });
         self
     }
+
+    pub fn reply_to(mut self, reply_to: &str) -> Self {
+        self.reply_to.get_or_insert_with(Vec::new).push(OutlookRecipient {
+            email_address: OutlookEmailAddress {
+                address: reply_to.to_string(),
+                name: None,
+            },
+        });
+        self
+    }
+
+    // Graph only accepts custom headers whose names start with `X-`.
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.internet_message_headers.get_or_insert_with(Vec::new).push(OutlookHeader {
+            name: name.to_string(),
+            value: value.to_string(),
+        });
+        self
+    }
+
+    pub fn attach(mut self, attachment: OutlookAttachment) -> Self {
+        self.attachments.get_or_insert_with(Vec::new).push(attachment);
+        self
+    }
+
+    pub fn category(mut self, category: &str) -> Self {
+        self.categories.get_or_insert_with(Vec::new).push(category.to_string());
+        self
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -267,27 +355,59 @@ pub struct OutlookFolder {
 #[async_trait]
 impl EmailSender for OutlookClient {
     async fn send_email(&self, email: &Email) -> Result<EmailResponse> {
-        let to_recipients: Vec<OutlookRecipient> = email.to.iter().map(|a| {
-            OutlookRecipient {
-                email_address: OutlookEmailAddress {
-                    address: a.email.clone(),
-                    name: a.name.clone(),
-                },
-            }
-        }).collect();
+        email.check_supported("Outlook", &[EmailFeature::Metadata, EmailFeature::Template])?;
+        if let Some(name) = email.headers.keys().find(|name| !name.to_ascii_lowercase().starts_with("x-")) {
+            return Err(Error::InvalidRequest(format!(
+                "Outlook only accepts custom headers starting with X-, got {}",
+                name
+            )));
+        }
+
+        let recipients = |list: &[EmailAddress]| -> Vec<OutlookRecipient> {
+            list.iter()
+                .map(|a| OutlookRecipient {
+                    email_address: OutlookEmailAddress {
+                        address: a.email.clone(),
+                        name: a.name.clone(),
+                    },
+                })
+                .collect()
+        };
+        let non_empty = |list: Vec<OutlookRecipient>| (!list.is_empty()).then_some(list);
 
         let content_type = if email.html.is_some() { "HTML" } else { "Text" };
         let content = email.html.clone().or(email.text.clone()).unwrap_or_default();
 
+        let headers: Vec<OutlookHeader> = email
+            .headers
+            .iter()
+            .map(|(name, value)| OutlookHeader {
+                name: name.clone(),
+                value: value.clone(),
+            })
+            .collect();
+        let attachments: Vec<OutlookAttachment> = email
+            .attachments
+            .iter()
+            .map(|a| match &a.content_id {
+                Some(content_id) => OutlookAttachment::inline(&a.filename, &a.content_type, &a.content, content_id),
+                None => OutlookAttachment::file(&a.filename, &a.content_type, &a.content),
+            })
+            .collect();
+
         let message = OutlookMailMessage {
             subject: email.subject.clone(),
             body: OutlookBody {
                 content_type: content_type.to_string(),
                 content,
             },
-            to_recipients,
-            cc_recipients: None,
-            bcc_recipients: None,
+            to_recipients: recipients(&email.to),
+            cc_recipients: non_empty(recipients(&email.cc)),
+            bcc_recipients: non_empty(recipients(&email.bcc)),
+            reply_to: non_empty(recipients(email.reply_to.as_slice())),
+            internet_message_headers: (!headers.is_empty()).then_some(headers),
+            attachments: (!attachments.is_empty()).then_some(attachments),
+            categories: (!email.tags.is_empty()).then(|| email.tags.clone()),
         };
 
         self.send_mail(message, true).await?;
@@ -1,10 +1,12 @@
 use crate::{Error, Result};
 use crate::resend::ResendClient;
 use async_trait::async_trait;
+use base64::Engine;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[cfg(feature = "email")]
-use crate::email::{Email, EmailResponse, EmailSender};
+use crate::email::{Email, EmailFeature, EmailResponse, EmailSender};
 
 impl ResendClient {
     pub async fn send(&self, email: ResendEmail) -> Result<ResendResponse> {
@@ -20,7 +22,7 @@ impl ResendClient {
             let text = response.text().await.unwrap_or_default();
             return Err(Error::Api {
                 message: text,
-                code: Some(status.to_string()),
+                code: status.as_u16() as i32,
             });
         }
 
@@ -40,7 +42,7 @@ impl ResendClient {
             let text = response.text().await.unwrap_or_default();
             return Err(Error::Api {
                 message: text,
-                code: Some(status.to_string()),
+                code: status.as_u16() as i32,
             });
         }
 
@@ -60,7 +62,7 @@ impl ResendClient {
             let text = response.text().await.unwrap_or_default();
             return Err(Error::Api {
                 message: text,
-                code: Some(status.to_string()),
+                code: status.as_u16() as i32,
             });
         }
 
@@ -86,7 +88,7 @@ impl ResendClient {
             let text = response.text().await.unwrap_or_default();
             return Err(Error::Api {
                 message: text,
-                code: Some(status.to_string()),
+                code: status.as_u16() as i32,
             });
         }
 
@@ -111,9 +113,24 @@ pub struct ResendEmail {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reply_to: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub headers: Option<HashMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attachments: Option<Vec<ResendAttachment>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub tags: Option<Vec<ResendTag>>,
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct ResendAttachment {
+    pub filename: String,
+    // Base64-encoded file content.
+    pub content: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_id: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct ResendTag {
     pub name: String,
@@ -131,6 +148,8 @@ impl ResendEmail {
             cc: None,
             bcc: None,
             reply_to: None,
+            headers: None,
+            attachments: None,
             tags: None,
         }
     }
@@ -165,6 +184,21 @@ impl ResendEmail {
         self
     }
 
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.get_or_insert_with(HashMap::new).insert(name.to_string(), value.to_string());
+        self
+    }
+
+    pub fn attach(mut self, filename: &str, content_type: &str, data: &[u8]) -> Self {
+        self.attachments.get_or_insert_with(Vec::new).push(ResendAttachment {
+            filename: filename.to_string(),
+            content: base64::engine::general_purpose::STANDARD.encode(data),
+            content_type: Some(content_type.to_string()),
+            content_id: None,
+        });
+        self
+    }
+
     pub fn tag(mut self, name: &str, value: &str) -> Self {
         self.tags.get_or_insert_with(Vec::new).push(ResendTag {
             name: name.to_string(),
@@ -212,30 +246,44 @@ pub struct ApiKeyResponse {
 #[async_trait]
 impl EmailSender for ResendClient {
     async fn send_email(&self, email: &Email) -> Result<EmailResponse> {
-        let from = if let Some(ref name) = email.from.name {
-            format!("{} <{}>", name, email.from.email)
-        } else {
-            email.from.email.clone()
-        };
+        // Resend tags are name/value pairs, so they carry `metadata`; bare tags
+        // have no equivalent.
+        email.check_supported("Resend", &[EmailFeature::Tags, EmailFeature::Template])?;
 
-        let to: Vec<String> = email.to.iter().map(|a| {
-            if let Some(ref name) = a.name {
-                format!("{} <{}>", name, a.email)
-            } else {
-                a.email.clone()
-            }
-        }).collect();
+        let addresses = |list: &[crate::email::EmailAddress]| -> Option<Vec<String>> {
+            (!list.is_empty()).then(|| list.iter().map(ToString::to_string).collect())
+        };
+        let attachments: Vec<ResendAttachment> = email
+            .attachments
+            .iter()
+            .map(|a| ResendAttachment {
+                filename: a.filename.clone(),
+                content: base64::engine::general_purpose::STANDARD.encode(&a.content),
+                content_type: Some(a.content_type.clone()),
+                content_id: a.content_id.clone(),
+            })
+            .collect();
+        let tags: Vec<ResendTag> = email
+            .metadata
+            .iter()
+            .map(|(name, value)| ResendTag {
+                name: name.clone(),
+                value: value.clone(),
+            })
+            .collect();
 
         let resend_email = ResendEmail {
-            from,
-            to,
+            from: email.from.to_string(),
+            to: email.to.iter().map(ToString::to_string).collect(),
             subject: email.subject.clone(),
             html: email.html.clone(),
             text: email.text.clone(),
-            cc: None,
-            bcc: None,
-            reply_to: None,
-            tags: None,
+            cc: addresses(&email.cc),
+            bcc: addresses(&email.bcc),
+            reply_to: email.reply_to.as_ref().map(ToString::to_string),
+            headers: (!email.headers.is_empty()).then(|| email.headers.clone()),
+            attachments: (!attachments.is_empty()).then_some(attachments),
+            tags: (!tags.is_empty()).then_some(tags),
         };
 
         let response = self.send(resend_email).await?;
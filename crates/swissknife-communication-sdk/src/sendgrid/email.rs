@@ -1,5 +1,7 @@
 use async_trait::async_trait;
+use base64::Engine;
 use serde::Serialize;
+use std::collections::HashMap;
 
 use crate::email::{Email, EmailResponse, EmailSender};
 use crate::{Error, Result};
@@ -11,12 +13,42 @@ struct SendGridEmail {
     personalizations: Vec<Personalization>,
     from: EmailAddr,
     subject: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
     content: Vec<Content>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reply_to: Option<EmailAddr>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    headers: HashMap<String, String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    attachments: Vec<SendGridAttachment>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    categories: Vec<String>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    custom_args: HashMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    template_id: Option<String>,
 }
 
 #[derive(Serialize)]
 struct Personalization {
     to: Vec<EmailAddr>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    cc: Vec<EmailAddr>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    bcc: Vec<EmailAddr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dynamic_template_data: Option<serde_json::Value>,
+}
+
+#[derive(Serialize)]
+struct SendGridAttachment {
+    content: String,
+    #[serde(rename = "type")]
+    content_type: String,
+    filename: String,
+    disposition: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content_id: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -61,19 +93,41 @@ impl EmailSender for SendGridClient {
             });
         }
 
-        if content.is_empty() {
+        // A template supplies its own body.
+        if content.is_empty() && email.template.is_none() {
             return Err(Error::Config(
                 "Email must have either text or html content".into(),
             ));
         }
 
+        let attachments = email
+            .attachments
+            .iter()
+            .map(|a| SendGridAttachment {
+                content: base64::engine::general_purpose::STANDARD.encode(&a.content),
+                content_type: a.content_type.clone(),
+                filename: a.filename.clone(),
+                disposition: if a.is_inline() { "inline" } else { "attachment" },
+                content_id: a.content_id.clone(),
+            })
+            .collect();
+
         let sg_email = SendGridEmail {
             personalizations: vec![Personalization {
                 to: email.to.iter().map(EmailAddr::from).collect(),
+                cc: email.cc.iter().map(EmailAddr::from).collect(),
+                bcc: email.bcc.iter().map(EmailAddr::from).collect(),
+                dynamic_template_data: email.template.as_ref().map(|t| t.variables.clone()),
             }],
             from: EmailAddr::from(&email.from),
             subject: email.subject.clone(),
             content,
+            reply_to: email.reply_to.as_ref().map(EmailAddr::from),
+            headers: email.headers.clone(),
+            attachments,
+            categories: email.tags.clone(),
+            custom_args: email.metadata.clone(),
+            template_id: email.template.as_ref().map(|t| t.id.clone()),
         };
 
         let response = self
@@ -1,16 +1,37 @@
+use super::transport::{connect_tcp, upgrade_tls, Connection, Io};
+use crate::mime::{check_address, mailbox, unique_id, MimeAttachment, MimeMessage};
 use crate::{Error, Result};
-use async_trait::async_trait;
-use base64::Engine;
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
 
 #[cfg(feature = "email")]
-use crate::email::{Email, EmailResponse, EmailSender};
+use crate::email::{Email, EmailFeature, EmailResponse, EmailSender};
+#[cfg(feature = "email")]
+use async_trait::async_trait;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+const DEFAULT_POOL_SIZE: usize = 4;
+const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TlsMode {
+    None,
+    StartTls,
+    Implicit,
+}
 
 pub struct SmtpClient {
     host: String,
     port: u16,
     username: Option<String>,
     password: Option<String>,
-    use_tls: bool,
+    tls: TlsMode,
+    hello_name: String,
+    timeout: Duration,
+    pool_size: usize,
+    idle_timeout: Duration,
+    pool: Mutex<Vec<(Connection, Instant)>>,
 }
 
 impl SmtpClient {
@@ -20,7 +41,12 @@ impl SmtpClient {
             port,
             username: None,
             password: None,
-            use_tls: true,
+            tls: default_tls(port),
+            hello_name: "localhost".to_string(),
+            timeout: DEFAULT_TIMEOUT,
+            pool_size: DEFAULT_POOL_SIZE,
+            idle_timeout: DEFAULT_IDLE_TIMEOUT,
+            pool: Mutex::new(Vec::new()),
         }
     }
 
@@ -31,7 +57,29 @@ impl SmtpClient {
     }
 
     pub fn with_tls(mut self, use_tls: bool) -> Self {
-        self.use_tls = use_tls;
+        self.tls = if use_tls { default_tls(self.port) } else { TlsMode::None };
+        self
+    }
+
+    pub fn with_tls_mode(mut self, tls: TlsMode) -> Self {
+        self.tls = tls;
+        self
+    }
+
+    pub fn with_hello_name(mut self, hello_name: &str) -> Self {
+        self.hello_name = hello_name.to_string();
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    // Number of idle connections kept open between sends; 0 disables pooling.
+    pub fn with_pool(mut self, pool_size: usize, idle_timeout: Duration) -> Self {
+        self.pool_size = pool_size;
+        self.idle_timeout = idle_timeout;
         self
     }
 
@@ -55,64 +103,239 @@ impl SmtpClient {
             .with_auth(username, password)
     }
 
-    fn build_raw_email(&self, email: &SmtpEmail) -> String {
-        let boundary = "boundary_swissknife_smtp";
-        let to_header = email.to.join(", ");
+    pub fn host(&self) -> &str {
+        &self.host
+    }
 
-        let mut raw = format!(
-            "From: {}\r\nTo: {}\r\nSubject: {}\r\nMIME-Version: 1.0\r\n",
-            email.from, to_header, email.subject
-        );
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    pub fn username(&self) -> Option<&str> {
+        self.username.as_deref()
+    }
 
-        if !email.cc.is_empty() {
-            raw.push_str(&format!("Cc: {}\r\n", email.cc.join(", ")));
+    pub fn password(&self) -> Option<&str> {
+        self.password.as_deref()
+    }
+
+    pub fn use_tls(&self) -> bool {
+        self.tls != TlsMode::None
+    }
+
+    pub fn tls_mode(&self) -> TlsMode {
+        self.tls
+    }
+
+    pub async fn send(&self, email: &SmtpEmail) -> Result<SmtpResponse> {
+        if email.to.is_empty() && email.cc.is_empty() && email.bcc.is_empty() {
+            return Err(Error::InvalidRequest("Email has no recipients".into()));
+        }
+        for address in std::iter::once(&email.from).chain(&email.to).chain(&email.cc).chain(&email.bcc) {
+            check_address(address)?;
         }
 
-        if let Some(ref reply_to) = email.reply_to {
-            raw.push_str(&format!("Reply-To: {}\r\n", reply_to));
+        let message_id = self.message_id(mailbox(&email.from));
+        let message = MimeMessage {
+            from: email.from.clone(),
+            to: email.to.clone(),
+            cc: email.cc.clone(),
+            reply_to: email.reply_to.clone(),
+            subject: &email.subject,
+            text: email.text.as_deref(),
+            html: email.html.as_deref(),
+            headers: email.headers.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect(),
+            attachments: email
+                .attachments
+                .iter()
+                .map(|a| MimeAttachment {
+                    filename: &a.filename,
+                    content_type: &a.content_type,
+                    data: &a.data,
+                    content_id: a.content_id.as_deref(),
+                })
+                .collect(),
+            message_id: Some(message_id.clone()),
+            date: Some(chrono::Utc::now().to_rfc2822()),
+            ..Default::default()
+        }
+        .build()?;
+
+        // A connection that failed mid-transaction is dropped rather than pooled.
+        let mut conn = self.checkout().await?;
+        let response = self.transaction(&mut conn, email, message_id, &message).await?;
+        self.checkin(conn).await;
+
+        if response.accepted.is_empty() {
+            let failures: Vec<String> = response
+                .rejected
+                .iter()
+                .map(|f| format!("{} ({} {})", f.address, f.code, f.message))
+                .collect();
+            return Err(Error::Smtp {
+                code: response.rejected.first().map(|f| f.code).unwrap_or(550),
+                message: format!("All recipients were rejected: {}", failures.join("; ")),
+            });
+        }
+        Ok(response)
+    }
+
+    // Sends QUIT on every pooled connection.
+    pub async fn close(&self) {
+        let idle = std::mem::take(&mut *self.pool.lock().await);
+        for (mut conn, _) in idle {
+            let _ = conn.command("QUIT").await;
         }
+    }
 
-        if email.text.is_some() && email.html.is_some() {
-            raw.push_str(&format!("Content-Type: multipart/alternative; boundary=\"{}\"\r\n\r\n", boundary));
-            if let Some(ref t) = email.text {
-                raw.push_str(&format!("--{}\r\nContent-Type: text/plain; charset=\"UTF-8\"\r\n\r\n{}\r\n", boundary, t));
+    async fn transaction(
+        &self,
+        conn: &mut Connection,
+        email: &SmtpEmail,
+        message_id: String,
+        message: &str,
+    ) -> Result<SmtpResponse> {
+        conn.command(&format!("MAIL FROM:<{}>", mailbox(&email.from)))
+            .await?
+            .expect(250)?;
+
+        let mut seen = HashSet::new();
+        let mut accepted = Vec::new();
+        let mut rejected = Vec::new();
+        for recipient in email.to.iter().chain(&email.cc).chain(&email.bcc) {
+            let address = mailbox(recipient);
+            if !seen.insert(address.to_ascii_lowercase()) {
+                continue;
             }
-            if let Some(ref h) = email.html {
-                raw.push_str(&format!("--{}\r\nContent-Type: text/html; charset=\"UTF-8\"\r\n\r\n{}\r\n", boundary, h));
+            let reply = conn.command(&format!("RCPT TO:<{}>", address)).await?;
+            if reply.is_positive() {
+                accepted.push(address.to_string());
+            } else {
+                rejected.push(RecipientFailure {
+                    address: address.to_string(),
+                    code: reply.code,
+                    message: reply.message(),
+                });
             }
-            raw.push_str(&format!("--{}--", boundary));
-        } else if let Some(ref h) = email.html {
-            raw.push_str("Content-Type: text/html; charset=\"UTF-8\"\r\n\r\n");
-            raw.push_str(h);
-        } else if let Some(ref t) = email.text {
-            raw.push_str("Content-Type: text/plain; charset=\"UTF-8\"\r\n\r\n");
-            raw.push_str(t);
         }
 
-        raw
+        if accepted.is_empty() {
+            conn.command("RSET").await?.expect(250)?;
+            return Ok(SmtpResponse {
+                message_id,
+                accepted,
+                rejected,
+                server_reply: String::new(),
+            });
+        }
+
+        let reply = conn.data(message).await?.expect(250)?;
+        Ok(SmtpResponse {
+            message_id,
+            accepted,
+            rejected,
+            server_reply: reply.message(),
+        })
     }
 
-    pub fn host(&self) -> &str {
-        &self.host
+    async fn checkout(&self) -> Result<Connection> {
+        loop {
+            let idle = self.pool.lock().await.pop();
+            match idle {
+                Some((mut conn, since)) if since.elapsed() < self.idle_timeout => {
+                    // RSET doubles as a liveness check for connections the server may have dropped.
+                    if matches!(conn.command("RSET").await, Ok(reply) if reply.code == 250) {
+                        return Ok(conn);
+                    }
+                }
+                Some(_) => continue,
+                None => return self.connect().await,
+            }
+        }
     }
 
-    pub fn port(&self) -> u16 {
-        self.port
+    async fn checkin(&self, conn: Connection) {
+        let mut pool = self.pool.lock().await;
+        if pool.len() < self.pool_size {
+            pool.push((conn, Instant::now()));
+        }
     }
 
-    pub fn username(&self) -> Option<&str> {
-        self.username.as_deref()
+    async fn connect(&self) -> Result<Connection> {
+        let tcp = connect_tcp(&self.host, self.port, self.timeout).await?;
+        let mut conn = match self.tls {
+            TlsMode::StartTls => {
+                let mut plain = Connection::new(tcp, self.timeout);
+                plain.read_reply().await?.expect(220)?;
+                plain.ehlo(&self.hello_name).await?;
+                if !plain.capabilities.supports("STARTTLS") {
+                    return Err(Error::Config(format!("{} does not offer STARTTLS", self.host)));
+                }
+                plain.command("STARTTLS").await?.expect(220)?;
+                let stream = upgrade_tls(plain.into_inner(), &self.host, self.timeout).await?;
+                Connection::new(stream, self.timeout)
+            }
+            TlsMode::Implicit => {
+                let stream = upgrade_tls(tcp, &self.host, self.timeout).await?;
+                let mut conn = Connection::new(stream, self.timeout);
+                conn.read_reply().await?.expect(220)?;
+                conn
+            }
+            TlsMode::None => {
+                let mut conn = Connection::new(Box::new(tcp) as Box<dyn Io>, self.timeout);
+                conn.read_reply().await?.expect(220)?;
+                conn
+            }
+        };
+        conn.ehlo(&self.hello_name).await?;
+
+        if let (Some(username), Some(password)) = (&self.username, &self.password) {
+            conn.authenticate(username, password).await?;
+        }
+        Ok(conn)
     }
 
-    pub fn password(&self) -> Option<&str> {
-        self.password.as_deref()
+    fn message_id(&self, from: &str) -> String {
+        let domain = from
+            .rsplit_once('@')
+            .map(|(_, domain)| domain)
+            .unwrap_or(&self.hello_name);
+        format!("<{}@{}>", unique_id(), domain)
     }
+}
 
-    pub fn use_tls(&self) -> bool {
-        self.use_tls
+// Port 465 is SMTPS; everything else negotiates TLS with STARTTLS.
+fn default_tls(port: u16) -> TlsMode {
+    if port == 465 {
+        TlsMode::Implicit
+    } else {
+        TlsMode::StartTls
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct SmtpResponse {
+    pub message_id: String,
+    pub accepted: Vec<String>,
+    pub rejected: Vec<RecipientFailure>,
+    pub server_reply: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct RecipientFailure {
+    pub address: String,
+    pub code: u16,
+    pub message: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct SmtpAttachment {
+    pub filename: String,
+    pub content_type: String,
+    pub data: Vec<u8>,
+    pub content_id: Option<String>,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct SmtpEmail {
     pub from: String,
@@ -123,6 +346,8 @@ pub struct SmtpEmail {
     pub cc: Vec<String>,
     pub bcc: Vec<String>,
     pub reply_to: Option<String>,
+    pub headers: Vec<(String, String)>,
+    pub attachments: Vec<SmtpAttachment>,
 }
 
 impl SmtpEmail {
@@ -164,37 +389,66 @@ impl SmtpEmail {
         self.reply_to = Some(reply_to.to_string());
         self
     }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn attach(mut self, filename: &str, content_type: &str, data: Vec<u8>) -> Self {
+        self.attachments.push(SmtpAttachment {
+            filename: filename.to_string(),
+            content_type: content_type.to_string(),
+            data,
+            content_id: None,
+        });
+        self
+    }
+
+    pub fn attach_inline(mut self, filename: &str, content_type: &str, data: Vec<u8>, content_id: &str) -> Self {
+        self.attachments.push(SmtpAttachment {
+            filename: filename.to_string(),
+            content_type: content_type.to_string(),
+            data,
+            content_id: Some(content_id.to_string()),
+        });
+        self
+    }
 }
 
 #[cfg(feature = "email")]
 #[async_trait]
 impl EmailSender for SmtpClient {
     async fn send_email(&self, email: &Email) -> Result<EmailResponse> {
-        let from = if let Some(ref name) = email.from.name {
-            format!("{} <{}>", name, email.from.email)
-        } else {
-            email.from.email.clone()
-        };
+        email.check_supported("SMTP", &[EmailFeature::Tags, EmailFeature::Metadata, EmailFeature::Template])?;
 
-        let to: Vec<String> = email.to.iter().map(|a| {
-            if let Some(ref name) = a.name {
-                format!("{} <{}>", name, a.email)
-            } else {
-                a.email.clone()
-            }
-        }).collect();
-
-        let _smtp_email = SmtpEmail {
-            from,
-            to,
+        let addresses = |list: &[crate::email::EmailAddress]| list.iter().map(ToString::to_string).collect();
+        let smtp_email = SmtpEmail {
+            from: email.from.to_string(),
+            to: addresses(&email.to),
             subject: email.subject.clone(),
             text: email.text.clone(),
             html: email.html.clone(),
-            ..Default::default()
+            cc: addresses(&email.cc),
+            bcc: addresses(&email.bcc),
+            reply_to: email.reply_to.as_ref().map(ToString::to_string),
+            headers: email.headers.iter().map(|(k, v)| (k.clone(), v.clone())).collect(),
+            attachments: email
+                .attachments
+                .iter()
+                .map(|a| SmtpAttachment {
+                    filename: a.filename.clone(),
+                    content_type: a.content_type.clone(),
+                    data: a.content.clone(),
+                    content_id: a.content_id.clone(),
+                })
+                .collect(),
         };
 
-        Err(Error::InvalidRequest(
-            "SMTP sending requires an async SMTP client. Use lettre crate or similar in your runtime.".to_string()
-        ))
+        let response = self.send(&smtp_email).await?;
+        Ok(EmailResponse {
+            message_id: Some(response.message_id),
+            status: if response.rejected.is_empty() { "sent" } else { "partially_sent" }.to_string(),
+        })
     }
 }
@@ -1,3 +1,4 @@
 mod client;
+mod transport;
 
-pub use client::SmtpClient;
+pub use client::{RecipientFailure, SmtpAttachment, SmtpClient, SmtpEmail, SmtpResponse, TlsMode};
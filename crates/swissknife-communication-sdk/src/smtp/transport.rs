@@ -0,0 +1,207 @@
+use crate::{Error, Result};
+use base64::Engine;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufStream};
+use tokio::net::TcpStream;
+use tokio::time::timeout;
+use tokio_rustls::rustls::pki_types::ServerName;
+use tokio_rustls::rustls::{ClientConfig, RootCertStore};
+use tokio_rustls::TlsConnector;
+
+pub(crate) trait Io: AsyncRead + AsyncWrite + Unpin + Send {}
+
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> Io for T {}
+
+#[derive(Debug)]
+pub(crate) struct Reply {
+    pub code: u16,
+    pub lines: Vec<String>,
+}
+
+impl Reply {
+    pub fn message(&self) -> String {
+        self.lines.join(" ")
+    }
+
+    pub fn is_positive(&self) -> bool {
+        (200..400).contains(&self.code)
+    }
+
+    pub fn expect(self, code: u16) -> Result<Self> {
+        if self.code == code {
+            Ok(self)
+        } else {
+            Err(self.into_error())
+        }
+    }
+
+    pub fn into_error(self) -> Error {
+        let message = self.message();
+        match self.code {
+            530 | 534 | 535 | 538 => Error::Auth(message),
+            code => Error::Smtp { code, message },
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Capabilities {
+    extensions: HashSet<String>,
+    auth: HashSet<String>,
+}
+
+impl Capabilities {
+    fn parse(reply: &Reply) -> Self {
+        let mut caps = Self::default();
+        // The first line is the server greeting, not an extension.
+        for line in reply.lines.iter().skip(1) {
+            let mut words = line.split_whitespace();
+            let Some(keyword) = words.next() else { continue };
+            let keyword = keyword.to_ascii_uppercase();
+            if keyword == "AUTH" {
+                caps.auth.extend(words.map(|m| m.to_ascii_uppercase()));
+            }
+            caps.extensions.insert(keyword);
+        }
+        caps
+    }
+
+    pub fn supports(&self, extension: &str) -> bool {
+        self.extensions.contains(extension)
+    }
+
+    pub fn supports_auth(&self, mechanism: &str) -> bool {
+        self.auth.contains(mechanism)
+    }
+}
+
+pub(crate) struct Connection<S = Box<dyn Io>> {
+    stream: BufStream<S>,
+    timeout: Duration,
+    pub capabilities: Capabilities,
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> Connection<S> {
+    pub fn new(stream: S, timeout: Duration) -> Self {
+        Self {
+            stream: BufStream::new(stream),
+            timeout,
+            capabilities: Capabilities::default(),
+        }
+    }
+
+    // Anything still buffered is discarded; RFC 3207 forbids the server from
+    // pipelining past its STARTTLS reply.
+    pub fn into_inner(self) -> S {
+        self.stream.into_inner()
+    }
+
+    pub async fn read_reply(&mut self) -> Result<Reply> {
+        let mut lines = Vec::new();
+        loop {
+            let mut buf = Vec::new();
+            let read = timeout(self.timeout, self.stream.read_until(b'\n', &mut buf))
+                .await
+                .map_err(|_| Error::Timeout("Timed out waiting for SMTP reply".into()))??;
+            if read == 0 {
+                return Err(Error::Smtp {
+                    code: 0,
+                    message: "Connection closed by server".into(),
+                });
+            }
+
+            let line = String::from_utf8_lossy(&buf);
+            let line = line.trim_end_matches(['\r', '\n']);
+            let code = line
+                .get(..3)
+                .and_then(|c| c.parse::<u16>().ok())
+                .ok_or_else(|| Error::Smtp {
+                    code: 0,
+                    message: format!("Malformed SMTP reply: {}", line),
+                })?;
+            lines.push(line.get(4..).unwrap_or_default().to_string());
+            if line.as_bytes().get(3) != Some(&b'-') {
+                return Ok(Reply { code, lines });
+            }
+        }
+    }
+
+    pub async fn write(&mut self, data: &[u8]) -> Result<()> {
+        timeout(self.timeout, async {
+            self.stream.write_all(data).await?;
+            self.stream.flush().await
+        })
+        .await
+        .map_err(|_| Error::Timeout("Timed out writing to SMTP server".into()))??;
+        Ok(())
+    }
+
+    pub async fn command(&mut self, command: &str) -> Result<Reply> {
+        self.write(format!("{}\r\n", command).as_bytes()).await?;
+        self.read_reply().await
+    }
+
+    pub async fn ehlo(&mut self, hello_name: &str) -> Result<()> {
+        let reply = self.command(&format!("EHLO {}", hello_name)).await?.expect(250)?;
+        self.capabilities = Capabilities::parse(&reply);
+        Ok(())
+    }
+
+    pub async fn authenticate(&mut self, username: &str, password: &str) -> Result<()> {
+        let b64 = base64::engine::general_purpose::STANDARD;
+        if self.capabilities.supports_auth("PLAIN") {
+            let token = b64.encode(format!("\0{}\0{}", username, password));
+            self.command(&format!("AUTH PLAIN {}", token)).await?.expect(235)?;
+        } else if self.capabilities.supports_auth("LOGIN") {
+            self.command("AUTH LOGIN").await?.expect(334)?;
+            self.command(&b64.encode(username)).await?.expect(334)?;
+            self.command(&b64.encode(password)).await?.expect(235)?;
+        } else {
+            return Err(Error::Auth("Server offers neither AUTH PLAIN nor AUTH LOGIN".into()));
+        }
+        Ok(())
+    }
+
+    // Sends the message body terminated by `<CRLF>.<CRLF>`, dot-stuffing any
+    // line that starts with a period.
+    pub async fn data(&mut self, message: &str) -> Result<Reply> {
+        self.command("DATA").await?.expect(354)?;
+        let mut body = String::with_capacity(message.len() + 64);
+        for line in message.split("\r\n") {
+            if line.starts_with('.') {
+                body.push('.');
+            }
+            body.push_str(line);
+            body.push_str("\r\n");
+        }
+        body.push_str(".\r\n");
+        self.write(body.as_bytes()).await?;
+        self.read_reply().await
+    }
+}
+
+pub(crate) async fn connect_tcp(host: &str, port: u16, limit: Duration) -> Result<TcpStream> {
+    timeout(limit, TcpStream::connect((host, port)))
+        .await
+        .map_err(|_| Error::Timeout(format!("Timed out connecting to {}:{}", host, port)))?
+        .map_err(Error::from)
+}
+
+pub(crate) async fn upgrade_tls<S: Io + 'static>(stream: S, host: &str, limit: Duration) -> Result<Box<dyn Io>> {
+    let roots = RootCertStore::from_iter(webpki_roots::TLS_SERVER_ROOTS.iter().cloned());
+    let config = ClientConfig::builder_with_provider(Arc::new(
+        tokio_rustls::rustls::crypto::ring::default_provider(),
+    ))
+    .with_safe_default_protocol_versions()
+    .map_err(|e| Error::Config(e.to_string()))?
+    .with_root_certificates(roots)
+    .with_no_client_auth();
+    let server_name =
+        ServerName::try_from(host.to_string()).map_err(|_| Error::Config(format!("Invalid TLS server name: {}", host)))?;
+    let stream = timeout(limit, TlsConnector::from(Arc::new(config)).connect(server_name, stream))
+        .await
+        .map_err(|_| Error::Timeout(format!("Timed out negotiating TLS with {}", host)))??;
+    Ok(Box::new(stream))
+}
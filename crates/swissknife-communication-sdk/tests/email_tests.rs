@@ -1,6 +1,7 @@
 #![cfg(feature = "email")]
 
-use swissknife_communication_sdk::email::{Email, EmailAddress, EmailResponse};
+use swissknife_communication_sdk::email::{Attachment, Email, EmailAddress, EmailFeature, EmailResponse};
+use swissknife_communication_sdk::Error;
 
 #[test]
 fn test_email_address_new() {
@@ -105,6 +106,42 @@ fn test_email_chaining() {
     assert!(email.html.is_some());
 }
 
+#[test]
+fn test_email_with_recipients_and_extras() {
+    let from = EmailAddress::new("sender@test.com");
+    let to = EmailAddress::new("recipient@test.com");
+    let email = Email::new(from, to, "Extras")
+        .cc(EmailAddress::new("cc@test.com"))
+        .bcc(EmailAddress::new("bcc@test.com"))
+        .reply_to(EmailAddress::with_name("support@test.com", "Support"))
+        .header("X-Campaign", "spring")
+        .attach(Attachment::new("report.csv", "text/csv", b"a,b".to_vec()))
+        .attach(Attachment::inline("logo.png", "image/png", vec![0x89], "logo"))
+        .tag("billing")
+        .metadata("account", "42");
+
+    assert_eq!(email.cc.len(), 1);
+    assert_eq!(email.bcc.len(), 1);
+    assert_eq!(email.reply_to.unwrap().to_string(), "Support <support@test.com>");
+    assert_eq!(email.headers.get("X-Campaign"), Some(&"spring".to_string()));
+    assert!(!email.attachments[0].is_inline());
+    assert!(email.attachments[1].is_inline());
+    assert_eq!(email.tags, vec!["billing"]);
+    assert_eq!(email.metadata.get("account"), Some(&"42".to_string()));
+}
+
+#[test]
+fn test_email_check_supported() {
+    let from = EmailAddress::new("sender@test.com");
+    let to = EmailAddress::new("recipient@test.com");
+    let email = Email::new(from, to, "Template")
+        .template("welcome", serde_json::json!({ "name": "Ann" }));
+
+    assert!(email.check_supported("Test", &[EmailFeature::Tags]).is_ok());
+    let err = email.check_supported("Test", &[EmailFeature::Tags, EmailFeature::Template]).unwrap_err();
+    assert!(matches!(err, Error::Unsupported { provider: "Test", feature: "templates" }));
+}
+
 #[test]
 fn test_email_clone() {
     let from = EmailAddress::new("sender@test.com");
@@ -0,0 +1,179 @@
+#![cfg(feature = "smtp")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use swissknife_communication_sdk::smtp::{SmtpClient, SmtpEmail, TlsMode};
+use swissknife_communication_sdk::Error;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+
+type Log = Arc<std::sync::Mutex<Vec<String>>>;
+
+// Minimal SMTP sink: offers AUTH LOGIN only, rejects nobody@ and records
+// every command and message body it receives.
+async fn sink() -> (u16, Arc<AtomicUsize>, Log, Log) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let connections = Arc::new(AtomicUsize::new(0));
+    let commands: Log = Default::default();
+    let messages: Log = Default::default();
+    let (conns, cmds, msgs) = (connections.clone(), commands.clone(), messages.clone());
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            conns.fetch_add(1, Ordering::SeqCst);
+            let (cmds, msgs) = (cmds.clone(), msgs.clone());
+            tokio::spawn(async move {
+                let (read, mut write) = stream.into_split();
+                let mut lines = BufReader::new(read).lines();
+                write.write_all(b"220 sink ready\r\n").await.unwrap();
+                let mut auth_steps = 0;
+                while let Ok(Some(line)) = lines.next_line().await {
+                    cmds.lock().unwrap().push(line.clone());
+                    let reply = match line.as_str() {
+                        _ if auth_steps == 2 => {
+                            auth_steps = 1;
+                            "334 UGFzc3dvcmQ6\r\n"
+                        }
+                        _ if auth_steps == 1 => {
+                            auth_steps = 0;
+                            "235 2.7.0 Authenticated\r\n"
+                        }
+                        "AUTH LOGIN" => {
+                            auth_steps = 2;
+                            "334 VXNlcm5hbWU6\r\n"
+                        }
+                        l if l.starts_with("EHLO") => "250-sink\r\n250-8BITMIME\r\n250 AUTH LOGIN\r\n",
+                        l if l.starts_with("RCPT TO:<nobody@") => "550 5.1.1 No such user\r\n",
+                        l if l.starts_with("MAIL FROM") || l.starts_with("RCPT TO") || l == "RSET" => "250 OK\r\n",
+                        "DATA" => {
+                            write.write_all(b"354 Go ahead\r\n").await.unwrap();
+                            let mut data = String::new();
+                            while let Ok(Some(line)) = lines.next_line().await {
+                                if line == "." {
+                                    break;
+                                }
+                                data.push_str(&line);
+                                data.push_str("\r\n");
+                            }
+                            msgs.lock().unwrap().push(data);
+                            "250 2.0.0 Ok: queued as ABC123\r\n"
+                        }
+                        "QUIT" => "221 Bye\r\n",
+                        _ => "502 Command not implemented\r\n",
+                    };
+                    write.write_all(reply.as_bytes()).await.unwrap();
+                }
+            });
+        }
+    });
+    (port, connections, commands, messages)
+}
+
+#[tokio::test]
+async fn test_send_reports_rejected_recipients_and_reuses_connection() {
+    let (port, connections, commands, messages) = sink().await;
+    let client = SmtpClient::new("127.0.0.1", port)
+        .with_tls(false)
+        .with_auth("user", "secret");
+    let email = SmtpEmail::new("Billing <billing@example.com>", "ann@example.com", "Invoice")
+        .cc("nobody@example.com")
+        .bcc("audit@example.com")
+        .text("Total: 10\n.end");
+
+    let response = client.send(&email).await.unwrap();
+    assert_eq!(response.accepted, ["ann@example.com", "audit@example.com"]);
+    assert_eq!(response.rejected[0].address, "nobody@example.com");
+    assert_eq!(response.rejected[0].code, 550);
+    assert!(response.server_reply.contains("ABC123"));
+
+    let only_rejected = SmtpEmail::new("billing@example.com", "nobody@example.com", "Invoice");
+    assert!(matches!(client.send(&only_rejected).await, Err(Error::Smtp { code: 550, .. })));
+    client.send(&email).await.unwrap();
+    client.close().await;
+
+    assert_eq!(connections.load(Ordering::SeqCst), 1);
+    let commands = commands.lock().unwrap();
+    assert_eq!(commands[1..4], ["AUTH LOGIN", "dXNlcg==", "c2VjcmV0"]);
+    assert_eq!(commands.last().map(String::as_str), Some("QUIT"));
+    let messages = messages.lock().unwrap();
+    assert_eq!(messages.len(), 2);
+    assert!(messages[0].contains("Cc: nobody@example.com\r\n"));
+    assert!(!messages[0].contains("audit@example.com"));
+    assert!(messages[0].contains("Total: 10\r\n..end"));
+}
+
+#[tokio::test]
+async fn test_send_builds_mime_with_attachment() {
+    let (port, _, _, messages) = sink().await;
+    let client = SmtpClient::new("127.0.0.1", port)
+        .with_tls(false)
+        .with_auth("user", "secret");
+    let email = SmtpEmail::new("Zoë <billing@example.com>", "ann@example.com", "Invoice ✓")
+        .reply_to("support@example.com")
+        .text("Hi")
+        .html("<p>Hi</p>")
+        .header("X-Invoice", "42")
+        .attach("invoice.pdf", "application/pdf", b"%PDF-1.4".to_vec())
+        .attach_inline("logo.png", "image/png", vec![0x89], "logo");
+
+    let response = client.send(&email).await.unwrap();
+
+    let messages = messages.lock().unwrap();
+    let message = &messages[0];
+    assert!(message.starts_with("From: =?UTF-8?B?Wm/Dqw==?= <billing@example.com>\r\nTo: ann@example.com\r\n"));
+    assert!(message.contains("Reply-To: support@example.com\r\n"));
+    assert!(message.contains("Subject: =?UTF-8?B?SW52b2ljZSDinJM=?=\r\n"));
+    assert!(message.contains(&format!("Message-ID: {}\r\n", response.message_id)));
+    assert!(message.contains("X-Invoice: 42\r\n"));
+    assert!(message.contains("multipart/mixed; boundary=\"=_mixed_"));
+    assert!(message.contains("multipart/related; boundary=\"=_rel_"));
+    assert!(message.contains("multipart/alternative; boundary=\"=_alt_"));
+    assert!(message.contains("Content-Disposition: inline; filename=\"logo.png\"\r\nContent-ID: <logo>\r\n"));
+    assert!(message.contains("filename=\"invoice.pdf\"\r\nContent-Transfer-Encoding: base64\r\n\r\nJVBERi0xLjQ=\r\n"));
+}
+
+#[tokio::test]
+async fn test_send_rejects_addresses_that_could_inject_commands() {
+    let (port, connections, _, _) = sink().await;
+    let client = SmtpClient::new("127.0.0.1", port).with_tls(false);
+    let injected = [
+        "ann@example.com>\r\nRCPT TO:<evil@example.com",
+        "ann@example.com\nDATA",
+        "ann@example.com>",
+        "<ann@example.com",
+        "Ann <ann@example.com> <evil@example.com>",
+        "Ann <>",
+    ];
+
+    for address in injected {
+        let to = SmtpEmail::new("billing@example.com", address, "Invoice");
+        assert!(matches!(client.send(&to).await, Err(Error::InvalidRequest(_))), "{:?}", address);
+        let from = SmtpEmail::new(address, "ann@example.com", "Invoice");
+        assert!(matches!(client.send(&from).await, Err(Error::InvalidRequest(_))), "{:?}", address);
+    }
+    let reply_to =
+        SmtpEmail::new("billing@example.com", "ann@example.com", "Invoice").reply_to("a@b.com\r\nBcc: evil@example.com");
+    assert!(matches!(client.send(&reply_to).await, Err(Error::InvalidRequest(_))));
+
+    assert_eq!(connections.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn test_tls_handshake_times_out() {
+    // Accepts the connection but never answers the TLS ClientHello.
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+        let mut open = Vec::new();
+        while let Ok((stream, _)) = listener.accept().await {
+            open.push(stream);
+        }
+    });
+
+    let client = SmtpClient::new("localhost", port)
+        .with_tls_mode(TlsMode::Implicit)
+        .with_timeout(Duration::from_millis(100));
+    let email = SmtpEmail::new("billing@example.com", "ann@example.com", "Invoice");
+    assert!(matches!(client.send(&email).await, Err(Error::Timeout(_))));
+}
@@ -0,0 +1,123 @@
+use crate::github::GitHubClient;
+use crate::{CiCdProvider, Result, Workflow, WorkflowRun};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::Method;
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+
+#[async_trait]
+impl CiCdProvider for GitHubClient {
+    async fn list_workflows(&self, owner: &str, repo: &str) -> Result<Vec<Workflow>> {
+        let response: WorkflowsResponse = self
+            .json(
+                self.request(Method::GET, &format!("/repos/{}/{}/actions/workflows", owner, repo))
+                    .query(&[("per_page", "100")]),
+            )
+            .await?;
+        Ok(response
+            .workflows
+            .into_iter()
+            .map(|w| Workflow {
+                id: w.id.to_string(),
+                name: w.name,
+                path: w.path,
+                state: w.state,
+            })
+            .collect())
+    }
+
+    async fn list_workflow_runs(&self, owner: &str, repo: &str, workflow_id: &str) -> Result<Vec<WorkflowRun>> {
+        let response: RunsResponse = self
+            .json(
+                self.request(
+                    Method::GET,
+                    &format!("/repos/{}/{}/actions/workflows/{}/runs", owner, repo, workflow_id),
+                )
+                .query(&[("per_page", "100")]),
+            )
+            .await?;
+        Ok(response
+            .workflow_runs
+            .into_iter()
+            .map(|r| WorkflowRun {
+                id: r.id.to_string(),
+                workflow_id: r.workflow_id.to_string(),
+                name: r.name.unwrap_or_default(),
+                status: r.status.unwrap_or_default(),
+                conclusion: r.conclusion,
+                branch: r.head_branch.unwrap_or_default(),
+                sha: r.head_sha,
+                created_at: r.created_at,
+                updated_at: r.updated_at,
+            })
+            .collect())
+    }
+
+    async fn trigger_workflow(
+        &self,
+        owner: &str,
+        repo: &str,
+        workflow_id: &str,
+        ref_: &str,
+        inputs: Option<HashMap<String, String>>,
+    ) -> Result<()> {
+        let mut payload = json!({ "ref": ref_ });
+        if let Some(inputs) = inputs {
+            payload["inputs"] = json!(inputs);
+        }
+        self.send(
+            self.request(
+                Method::POST,
+                &format!("/repos/{}/{}/actions/workflows/{}/dispatches", owner, repo, workflow_id),
+            )
+            .json(&payload),
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn cancel_workflow_run(&self, owner: &str, repo: &str, run_id: &str) -> Result<()> {
+        self.send(self.request(Method::POST, &format!("/repos/{}/{}/actions/runs/{}/cancel", owner, repo, run_id)))
+            .await?;
+        Ok(())
+    }
+
+    async fn rerun_workflow(&self, owner: &str, repo: &str, run_id: &str) -> Result<()> {
+        self.send(self.request(Method::POST, &format!("/repos/{}/{}/actions/runs/{}/rerun", owner, repo, run_id)))
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct WorkflowsResponse {
+    workflows: Vec<GhWorkflow>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GhWorkflow {
+    id: u64,
+    name: String,
+    path: String,
+    state: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RunsResponse {
+    workflow_runs: Vec<GhWorkflowRun>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GhWorkflowRun {
+    id: u64,
+    workflow_id: u64,
+    name: Option<String>,
+    status: Option<String>,
+    conclusion: Option<String>,
+    head_branch: Option<String>,
+    head_sha: String,
+    created_at: DateTime<Utc>,
+    updated_at: Option<DateTime<Utc>>,
+}
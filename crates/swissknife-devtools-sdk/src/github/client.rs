@@ -0,0 +1,124 @@
+use crate::rest::{check_cursor, next_link};
+use crate::{Error, ListOptions, ListResult, Result};
+use reqwest::{Client, Method, RequestBuilder, Response};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+const GITHUB_API_URL: &str = "https://api.github.com";
+const MAX_PER_PAGE: u32 = 100;
+const JSON_MEDIA_TYPE: &str = "application/vnd.github+json";
+
+#[derive(Clone)]
+pub struct GitHubClient {
+    token: String,
+    base_url: String,
+    client: Client,
+}
+
+impl GitHubClient {
+    pub fn new(token: &str) -> Self {
+        Self {
+            token: token.to_string(),
+            base_url: GITHUB_API_URL.to_string(),
+            client: Client::new(),
+        }
+    }
+
+    // GitHub Enterprise Server serves the REST API under `https://<host>/api/v3`.
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    pub(crate) fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        self.request_as(method, path, JSON_MEDIA_TYPE)
+    }
+
+    // `RequestBuilder::header` appends, so the media type has to be chosen up front.
+    pub(crate) fn request_as(&self, method: Method, path: &str, accept: &str) -> RequestBuilder {
+        self.authorize(self.client.request(method, format!("{}{}", self.base_url, path)), accept)
+    }
+
+    fn authorize(&self, request: RequestBuilder, accept: &str) -> RequestBuilder {
+        request
+            .bearer_auth(&self.token)
+            .header("Accept", accept)
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .header("User-Agent", "swissknife-devtools-sdk")
+    }
+
+    pub(crate) async fn send(&self, request: RequestBuilder) -> Result<Response> {
+        let response = request.send().await?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        let rate_limited = status.as_u16() == 429
+            || (status.as_u16() == 403
+                && response.headers().get("x-ratelimit-remaining").is_some_and(|v| v == "0"));
+        let text = response.text().await.unwrap_or_default();
+        let message = serde_json::from_str::<Value>(&text)
+            .ok()
+            .and_then(|body| body["message"].as_str().map(String::from))
+            .unwrap_or(text);
+
+        Err(match status.as_u16() {
+            _ if rate_limited => Error::RateLimited,
+            401 | 403 => Error::Auth(message),
+            404 => Error::NotFound(message),
+            405 | 409 | 422 => Error::Validation(message),
+            code => Error::Api {
+                message,
+                status: Some(code),
+            },
+        })
+    }
+
+    pub(crate) async fn json<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T> {
+        Ok(self.send(request).await?.json().await?)
+    }
+
+    pub(crate) async fn list<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        mut query: Vec<(&str, String)>,
+        options: &ListOptions,
+    ) -> Result<ListResult<T>> {
+        let request = match &options.cursor {
+            Some(cursor) => self.authorize(self.client.get(check_cursor(cursor, &self.base_url)?), JSON_MEDIA_TYPE),
+            None => {
+                query.push(("per_page", options.per_page.unwrap_or(30).min(MAX_PER_PAGE).to_string()));
+                if let Some(page) = options.page {
+                    query.push(("page", page.to_string()));
+                }
+                self.request(Method::GET, path).query(&query)
+            }
+        };
+
+        let response = self.send(request).await?;
+        let next_cursor = next_link(response.headers());
+        Ok(ListResult {
+            items: response.json().await?,
+            total: None,
+            has_more: next_cursor.is_some(),
+            next_cursor,
+        })
+    }
+
+    pub(crate) async fn list_all<T: DeserializeOwned>(&self, path: &str) -> Result<Vec<T>> {
+        let mut options = ListOptions {
+            per_page: Some(MAX_PER_PAGE),
+            ..Default::default()
+        };
+        let mut items = Vec::new();
+        loop {
+            let page = self.list(path, Vec::new(), &options).await?;
+            items.extend(page.items);
+            match page.next_cursor {
+                Some(cursor) => options.cursor = Some(cursor),
+                None => return Ok(items),
+            }
+        }
+    }
+}
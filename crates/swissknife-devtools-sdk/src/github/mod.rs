@@ -0,0 +1,6 @@
+mod client;
+mod repos;
+mod actions;
+
+pub use client::GitHubClient;
+pub use repos::CodeSearchItem;
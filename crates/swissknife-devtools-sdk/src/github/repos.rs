@@ -0,0 +1,515 @@
+use crate::github::GitHubClient;
+use crate::rest::encode_path;
+use crate::{
+    Branch, Comment, Commit, Error, FileContent, GitProvider, Issue, IssueState, ListOptions, ListResult, PullRequest,
+    PullRequestState, Repository, Result,
+};
+use async_trait::async_trait;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+impl GitHubClient {
+    pub async fn create_issue_with_labels(
+        &self,
+        owner: &str,
+        repo: &str,
+        title: &str,
+        body: Option<&str>,
+        labels: &[String],
+        assignees: &[String],
+    ) -> Result<Issue> {
+        let mut payload = json!({ "title": title, "body": body });
+        if !labels.is_empty() {
+            payload["labels"] = json!(labels);
+        }
+        if !assignees.is_empty() {
+            payload["assignees"] = json!(assignees);
+        }
+        let issue: GhIssue = self
+            .json(self.request(Method::POST, &format!("/repos/{}/{}/issues", owner, repo)).json(&payload))
+            .await?;
+        Ok(issue.into())
+    }
+
+    pub async fn search_code(&self, query: &str, per_page: Option<u32>) -> Result<Vec<CodeSearchItem>> {
+        let results: SearchResults<GhCodeResult> = self.search("/search/code", query, per_page).await?;
+        Ok(results
+            .items
+            .into_iter()
+            .map(|item| CodeSearchItem {
+                name: item.name,
+                path: item.path,
+                sha: item.sha,
+                url: item.html_url,
+                repository: item.repository.full_name,
+            })
+            .collect())
+    }
+
+    pub async fn search_repositories(&self, query: &str, per_page: Option<u32>) -> Result<Vec<Repository>> {
+        let results: SearchResults<GhRepository> = self.search("/search/repositories", query, per_page).await?;
+        Ok(results.items.into_iter().map(Into::into).collect())
+    }
+
+    async fn search<T: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &str,
+        per_page: Option<u32>,
+    ) -> Result<SearchResults<T>> {
+        let per_page = per_page.unwrap_or(30).min(100).to_string();
+        self.json(self.request(Method::GET, path).query(&[("q", query), ("per_page", &per_page)]))
+            .await
+    }
+}
+
+#[async_trait]
+impl GitProvider for GitHubClient {
+    async fn get_repository(&self, owner: &str, repo: &str) -> Result<Repository> {
+        let repository: GhRepository = self
+            .json(self.request(Method::GET, &format!("/repos/{}/{}", owner, repo)))
+            .await?;
+        Ok(repository.into())
+    }
+
+    async fn list_branches(&self, owner: &str, repo: &str) -> Result<Vec<Branch>> {
+        let branches: Vec<GhBranch> = self.list_all(&format!("/repos/{}/{}/branches", owner, repo)).await?;
+        Ok(branches
+            .into_iter()
+            .map(|b| Branch {
+                name: b.name,
+                sha: b.commit.sha,
+                protected: b.protected,
+            })
+            .collect())
+    }
+
+    async fn get_file(&self, owner: &str, repo: &str, path: &str, ref_: Option<&str>) -> Result<FileContent> {
+        let url = format!("/repos/{}/{}/contents/{}", owner, repo, encode_path(path, true));
+        let mut request = self.request(Method::GET, &url);
+        if let Some(ref_) = ref_ {
+            request = request.query(&[("ref", ref_)]);
+        }
+        let file: GhContent = match self.json(request).await {
+            Ok(file) => file,
+            Err(Error::Json(_)) => return Err(Error::Validation(format!("{} is not a file", path))),
+            Err(e) => return Err(e),
+        };
+
+        let bytes = match file.encoding.as_deref() {
+            Some("base64") => base64::engine::general_purpose::STANDARD
+                .decode(file.content.unwrap_or_default().replace('\n', ""))
+                .map_err(|e| Error::Validation(format!("Invalid base64 content: {}", e)))?,
+            // Files between 1 and 100 MB come back without inline content.
+            _ => {
+                let mut request = self.request_as(Method::GET, &url, "application/vnd.github.raw+json");
+                if let Some(ref_) = ref_ {
+                    request = request.query(&[("ref", ref_)]);
+                }
+                self.send(request).await?.bytes().await?.to_vec()
+            }
+        };
+
+        let (content, encoding) = match String::from_utf8(bytes) {
+            Ok(text) => (text, "utf-8"),
+            Err(e) => (base64::engine::general_purpose::STANDARD.encode(e.into_bytes()), "base64"),
+        };
+        Ok(FileContent {
+            path: file.path,
+            content,
+            encoding: encoding.to_string(),
+            sha: file.sha,
+            size: file.size,
+        })
+    }
+
+    async fn list_commits(&self, owner: &str, repo: &str, options: &ListOptions) -> Result<ListResult<Commit>> {
+        let page = self
+            .list::<GhCommit>(&format!("/repos/{}/{}/commits", owner, repo), Vec::new(), options)
+            .await?;
+        Ok(map_items(page, Into::into))
+    }
+
+    async fn list_issues(&self, owner: &str, repo: &str, options: &ListOptions) -> Result<ListResult<Issue>> {
+        let query = vec![("state", options.state.clone().unwrap_or_else(|| "open".to_string()))];
+        let mut page = self
+            .list::<GhIssue>(&format!("/repos/{}/{}/issues", owner, repo), query, options)
+            .await?;
+        // The issues endpoint also returns pull requests.
+        page.items.retain(|issue| issue.pull_request.is_none());
+        Ok(map_items(page, Into::into))
+    }
+
+    async fn get_issue(&self, owner: &str, repo: &str, number: u64) -> Result<Issue> {
+        let issue: GhIssue = self
+            .json(self.request(Method::GET, &format!("/repos/{}/{}/issues/{}", owner, repo, number)))
+            .await?;
+        Ok(issue.into())
+    }
+
+    async fn create_issue(&self, owner: &str, repo: &str, title: &str, body: Option<&str>) -> Result<Issue> {
+        self.create_issue_with_labels(owner, repo, title, body, &[], &[]).await
+    }
+
+    async fn update_issue(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        title: Option<&str>,
+        body: Option<&str>,
+        state: Option<IssueState>,
+    ) -> Result<Issue> {
+        let mut payload = json!({});
+        if let Some(title) = title {
+            payload["title"] = json!(title);
+        }
+        if let Some(body) = body {
+            payload["body"] = json!(body);
+        }
+        if let Some(state) = state {
+            payload["state"] = json!(state);
+        }
+        let issue: GhIssue = self
+            .json(
+                self.request(Method::PATCH, &format!("/repos/{}/{}/issues/{}", owner, repo, number))
+                    .json(&payload),
+            )
+            .await?;
+        Ok(issue.into())
+    }
+
+    async fn list_pull_requests(
+        &self,
+        owner: &str,
+        repo: &str,
+        options: &ListOptions,
+    ) -> Result<ListResult<PullRequest>> {
+        // GitHub has no `merged` filter; merged pulls are a subset of closed ones.
+        let merged_only = options.state.as_deref() == Some("merged");
+        let state = if merged_only {
+            "closed".to_string()
+        } else {
+            options.state.clone().unwrap_or_else(|| "open".to_string())
+        };
+        let page = self
+            .list::<GhPullRequest>(&format!("/repos/{}/{}/pulls", owner, repo), vec![("state", state)], options)
+            .await?;
+        let mut page = map_items(page, PullRequest::from);
+        if merged_only {
+            page.items.retain(|pr| pr.state == PullRequestState::Merged);
+        }
+        Ok(page)
+    }
+
+    async fn get_pull_request(&self, owner: &str, repo: &str, number: u64) -> Result<PullRequest> {
+        let pr: GhPullRequest = self
+            .json(self.request(Method::GET, &format!("/repos/{}/{}/pulls/{}", owner, repo, number)))
+            .await?;
+        Ok(pr.into())
+    }
+
+    async fn create_pull_request(
+        &self,
+        owner: &str,
+        repo: &str,
+        title: &str,
+        head: &str,
+        base: &str,
+        body: Option<&str>,
+    ) -> Result<PullRequest> {
+        let payload = json!({ "title": title, "head": head, "base": base, "body": body });
+        let pr: GhPullRequest = self
+            .json(self.request(Method::POST, &format!("/repos/{}/{}/pulls", owner, repo)).json(&payload))
+            .await?;
+        Ok(pr.into())
+    }
+
+    async fn merge_pull_request(&self, owner: &str, repo: &str, number: u64) -> Result<()> {
+        self.send(
+            self.request(Method::PUT, &format!("/repos/{}/{}/pulls/{}/merge", owner, repo, number))
+                .json(&json!({})),
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn list_comments(&self, owner: &str, repo: &str, issue_number: u64) -> Result<Vec<Comment>> {
+        let comments: Vec<GhComment> = self
+            .list_all(&format!("/repos/{}/{}/issues/{}/comments", owner, repo, issue_number))
+            .await?;
+        Ok(comments.into_iter().map(Into::into).collect())
+    }
+
+    async fn create_comment(&self, owner: &str, repo: &str, issue_number: u64, body: &str) -> Result<Comment> {
+        let comment: GhComment = self
+            .json(
+                self.request(Method::POST, &format!("/repos/{}/{}/issues/{}/comments", owner, repo, issue_number))
+                    .json(&json!({ "body": body })),
+            )
+            .await?;
+        Ok(comment.into())
+    }
+}
+
+fn map_items<T, U>(page: ListResult<T>, f: impl FnMut(T) -> U) -> ListResult<U> {
+    ListResult {
+        items: page.items.into_iter().map(f).collect(),
+        total: page.total,
+        has_more: page.has_more,
+        next_cursor: page.next_cursor,
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CodeSearchItem {
+    pub name: String,
+    pub path: String,
+    pub sha: String,
+    pub url: String,
+    pub repository: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResults<T> {
+    items: Vec<T>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GhCodeResult {
+    name: String,
+    path: String,
+    sha: String,
+    html_url: String,
+    repository: GhRepoRef,
+}
+
+#[derive(Debug, Deserialize)]
+struct GhRepoRef {
+    full_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GhUser {
+    login: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GhRepository {
+    id: u64,
+    name: String,
+    full_name: String,
+    description: Option<String>,
+    html_url: String,
+    clone_url: String,
+    default_branch: Option<String>,
+    private: bool,
+    owner: GhUser,
+    created_at: Option<DateTime<Utc>>,
+    updated_at: Option<DateTime<Utc>>,
+    language: Option<String>,
+    stargazers_count: Option<u32>,
+    forks_count: Option<u32>,
+}
+
+impl From<GhRepository> for Repository {
+    fn from(r: GhRepository) -> Self {
+        Repository {
+            id: r.id.to_string(),
+            name: r.name,
+            full_name: r.full_name,
+            description: r.description,
+            url: r.html_url,
+            clone_url: r.clone_url,
+            default_branch: r.default_branch.unwrap_or_else(|| "main".to_string()),
+            is_private: r.private,
+            owner: r.owner.login,
+            created_at: r.created_at,
+            updated_at: r.updated_at,
+            language: r.language,
+            stars: r.stargazers_count,
+            forks: r.forks_count,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GhBranch {
+    name: String,
+    commit: GhSha,
+    #[serde(default)]
+    protected: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct GhSha {
+    sha: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GhContent {
+    path: String,
+    sha: String,
+    size: u64,
+    content: Option<String>,
+    encoding: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GhCommit {
+    sha: String,
+    html_url: String,
+    commit: GhCommitDetail,
+    author: Option<GhUser>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GhCommitDetail {
+    message: String,
+    author: Option<GhCommitAuthor>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GhCommitAuthor {
+    name: Option<String>,
+    email: Option<String>,
+    date: Option<DateTime<Utc>>,
+}
+
+impl From<GhCommit> for Commit {
+    fn from(c: GhCommit) -> Self {
+        let author = c.commit.author;
+        Commit {
+            sha: c.sha,
+            message: c.commit.message,
+            author: author
+                .as_ref()
+                .and_then(|a| a.name.clone())
+                .or_else(|| c.author.map(|u| u.login))
+                .unwrap_or_default(),
+            author_email: author.as_ref().and_then(|a| a.email.clone()),
+            date: author.and_then(|a| a.date).unwrap_or_else(Utc::now),
+            url: c.html_url,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GhLabel {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GhIssue {
+    id: u64,
+    number: u64,
+    title: String,
+    body: Option<String>,
+    state: IssueState,
+    user: GhUser,
+    #[serde(default)]
+    assignees: Vec<GhUser>,
+    #[serde(default)]
+    labels: Vec<GhLabel>,
+    created_at: DateTime<Utc>,
+    updated_at: Option<DateTime<Utc>>,
+    closed_at: Option<DateTime<Utc>>,
+    pull_request: Option<serde_json::Value>,
+}
+
+impl From<GhIssue> for Issue {
+    fn from(i: GhIssue) -> Self {
+        Issue {
+            id: i.id.to_string(),
+            number: i.number,
+            title: i.title,
+            body: i.body,
+            state: i.state,
+            author: i.user.login,
+            assignees: i.assignees.into_iter().map(|u| u.login).collect(),
+            labels: i.labels.into_iter().map(|l| l.name).collect(),
+            created_at: i.created_at,
+            updated_at: i.updated_at,
+            closed_at: i.closed_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GhRef {
+    #[serde(rename = "ref")]
+    ref_: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GhPullRequest {
+    id: u64,
+    number: u64,
+    title: String,
+    body: Option<String>,
+    state: String,
+    user: GhUser,
+    head: GhRef,
+    base: GhRef,
+    #[serde(default)]
+    draft: bool,
+    mergeable: Option<bool>,
+    created_at: DateTime<Utc>,
+    updated_at: Option<DateTime<Utc>>,
+    merged_at: Option<DateTime<Utc>>,
+    additions: Option<u32>,
+    deletions: Option<u32>,
+    changed_files: Option<u32>,
+}
+
+impl From<GhPullRequest> for PullRequest {
+    fn from(pr: GhPullRequest) -> Self {
+        let state = match (pr.state.as_str(), pr.merged_at) {
+            (_, Some(_)) => PullRequestState::Merged,
+            ("closed", None) => PullRequestState::Closed,
+            _ => PullRequestState::Open,
+        };
+        PullRequest {
+            id: pr.id.to_string(),
+            number: pr.number,
+            title: pr.title,
+            body: pr.body,
+            state,
+            author: pr.user.login,
+            head_branch: pr.head.ref_,
+            base_branch: pr.base.ref_,
+            is_draft: pr.draft,
+            mergeable: pr.mergeable,
+            created_at: pr.created_at,
+            updated_at: pr.updated_at,
+            merged_at: pr.merged_at,
+            additions: pr.additions,
+            deletions: pr.deletions,
+            changed_files: pr.changed_files,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GhComment {
+    id: u64,
+    body: Option<String>,
+    user: GhUser,
+    created_at: DateTime<Utc>,
+    updated_at: Option<DateTime<Utc>>,
+}
+
+impl From<GhComment> for Comment {
+    fn from(c: GhComment) -> Self {
+        Comment {
+            id: c.id.to_string(),
+            body: c.body.unwrap_or_default(),
+            author: c.user.login,
+            created_at: c.created_at,
+            updated_at: c.updated_at,
+        }
+    }
+}
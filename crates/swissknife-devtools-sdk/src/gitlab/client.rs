@@ -0,0 +1,133 @@
+use crate::rest::{check_cursor, encode_path, next_link};
+use crate::{Error, ListOptions, ListResult, Result};
+use reqwest::{Client, Method, RequestBuilder, Response};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+const GITLAB_API_URL: &str = "https://gitlab.com/api/v4";
+const MAX_PER_PAGE: u32 = 100;
+
+#[derive(Clone)]
+pub struct GitLabClient {
+    token: String,
+    base_url: String,
+    client: Client,
+}
+
+impl GitLabClient {
+    pub fn new(token: &str) -> Self {
+        Self {
+            token: token.to_string(),
+            base_url: GITLAB_API_URL.to_string(),
+            client: Client::new(),
+        }
+    }
+
+    // Self-managed instances serve the API under `https://<host>/api/v4`.
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    pub(crate) fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        self.client
+            .request(method, format!("{}{}", self.base_url, path))
+            .header("PRIVATE-TOKEN", &self.token)
+    }
+
+    pub(crate) async fn send(&self, request: RequestBuilder) -> Result<Response> {
+        let response = request.send().await?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        let text = response.text().await.unwrap_or_default();
+        let message = serde_json::from_str::<Value>(&text)
+            .ok()
+            .and_then(|body| match body.get("message").or_else(|| body.get("error")) {
+                Some(Value::String(message)) => Some(message.clone()),
+                Some(other) => Some(other.to_string()),
+                None => None,
+            })
+            .unwrap_or(text);
+
+        Err(match status.as_u16() {
+            401 | 403 => Error::Auth(message),
+            404 => Error::NotFound(message),
+            400 | 405 | 406 | 409 | 422 => Error::Validation(message),
+            429 => Error::RateLimited,
+            code => Error::Api {
+                message,
+                status: Some(code),
+            },
+        })
+    }
+
+    pub(crate) async fn json<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T> {
+        Ok(self.send(request).await?.json().await?)
+    }
+
+    pub(crate) async fn list<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        mut query: Vec<(&str, String)>,
+        options: &ListOptions,
+    ) -> Result<ListResult<T>> {
+        let request = match &options.cursor {
+            Some(cursor) => self
+                .client
+                .get(check_cursor(cursor, &self.base_url)?)
+                .header("PRIVATE-TOKEN", &self.token),
+            None => {
+                query.push(("per_page", options.per_page.unwrap_or(20).min(MAX_PER_PAGE).to_string()));
+                if let Some(page) = options.page {
+                    query.push(("page", page.to_string()));
+                }
+                self.request(Method::GET, path).query(&query)
+            }
+        };
+
+        let response = self.send(request).await?;
+        let next_cursor = next_link(response.headers());
+        // Offset pagination reports a total; keyset pagination and very large
+        // collections don't.
+        let total = response
+            .headers()
+            .get("x-total")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse().ok());
+        Ok(ListResult {
+            items: response.json().await?,
+            total,
+            has_more: next_cursor.is_some(),
+            next_cursor,
+        })
+    }
+
+    pub(crate) async fn list_all<T: DeserializeOwned>(&self, path: &str) -> Result<Vec<T>> {
+        let mut options = ListOptions {
+            per_page: Some(MAX_PER_PAGE),
+            ..Default::default()
+        };
+        let mut items = Vec::new();
+        loop {
+            let page = self.list(path, Vec::new(), &options).await?;
+            items.extend(page.items);
+            match page.next_cursor {
+                Some(cursor) => options.cursor = Some(cursor),
+                None => return Ok(items),
+            }
+        }
+    }
+}
+
+// GitLab addresses projects by numeric ID or URL-encoded `namespace/path`. An
+// empty owner lets `repo` carry a numeric ID or a full path on its own.
+pub(crate) fn project(owner: &str, repo: &str) -> String {
+    if owner.is_empty() {
+        encode_path(repo, false)
+    } else {
+        encode_path(&format!("{}/{}", owner, repo), false)
+    }
+}
@@ -0,0 +1,5 @@
+mod client;
+mod projects;
+mod pipelines;
+
+pub use client::GitLabClient;
@@ -0,0 +1,142 @@
+use crate::gitlab::client::project;
+use crate::gitlab::GitLabClient;
+use crate::{CiCdProvider, Result, Workflow, WorkflowRun};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::Method;
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+
+const DEFAULT_CI_CONFIG: &str = ".gitlab-ci.yml";
+
+// A GitLab project has a single CI configuration, so it is exposed as one
+// workflow whose id is the config path and whose runs are the project's pipelines.
+#[async_trait]
+impl CiCdProvider for GitLabClient {
+    async fn list_workflows(&self, owner: &str, repo: &str) -> Result<Vec<Workflow>> {
+        let settings: GlCiSettings = self
+            .json(self.request(Method::GET, &format!("/projects/{}", project(owner, repo))))
+            .await?;
+        let path = settings
+            .ci_config_path
+            .filter(|p| !p.is_empty())
+            .unwrap_or_else(|| DEFAULT_CI_CONFIG.to_string());
+        Ok(vec![Workflow {
+            id: path.clone(),
+            name: "GitLab CI/CD".to_string(),
+            path,
+            state: if settings.jobs_enabled.unwrap_or(true) { "active" } else { "disabled" }.to_string(),
+        }])
+    }
+
+    async fn list_workflow_runs(&self, owner: &str, repo: &str, workflow_id: &str) -> Result<Vec<WorkflowRun>> {
+        let pipelines: Vec<GlPipeline> = self
+            .json(
+                self.request(Method::GET, &format!("/projects/{}/pipelines", project(owner, repo)))
+                    .query(&[("per_page", "100")]),
+            )
+            .await?;
+        Ok(pipelines
+            .into_iter()
+            .map(|p| {
+                let (status, conclusion) = normalize_status(&p.status);
+                WorkflowRun {
+                    id: p.id.to_string(),
+                    workflow_id: workflow_id.to_string(),
+                    name: p.name.unwrap_or_else(|| format!("Pipeline #{}", p.iid.unwrap_or(p.id))),
+                    status: status.to_string(),
+                    conclusion: conclusion.map(String::from),
+                    branch: p.ref_,
+                    sha: p.sha,
+                    created_at: p.created_at,
+                    updated_at: p.updated_at,
+                }
+            })
+            .collect())
+    }
+
+    async fn trigger_workflow(
+        &self,
+        owner: &str,
+        repo: &str,
+        _workflow_id: &str,
+        ref_: &str,
+        inputs: Option<HashMap<String, String>>,
+    ) -> Result<()> {
+        let variables: Vec<_> = inputs
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(key, value)| json!({ "key": key, "value": value }))
+            .collect();
+        self.send(
+            self.request(Method::POST, &format!("/projects/{}/pipeline", project(owner, repo)))
+                .json(&json!({ "ref": ref_, "variables": variables })),
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn cancel_workflow_run(&self, owner: &str, repo: &str, run_id: &str) -> Result<()> {
+        self.send(self.request(
+            Method::POST,
+            &format!("/projects/{}/pipelines/{}/cancel", project(owner, repo), run_id),
+        ))
+        .await?;
+        Ok(())
+    }
+
+    async fn rerun_workflow(&self, owner: &str, repo: &str, run_id: &str) -> Result<()> {
+        self.send(self.request(
+            Method::POST,
+            &format!("/projects/{}/pipelines/{}/retry", project(owner, repo), run_id),
+        ))
+        .await?;
+        Ok(())
+    }
+}
+
+// Maps pipeline statuses onto the GitHub Actions status/conclusion pair.
+fn normalize_status(status: &str) -> (&'static str, Option<&'static str>) {
+    match status {
+        "running" => ("in_progress", None),
+        "success" => ("completed", Some("success")),
+        "failed" => ("completed", Some("failure")),
+        "canceled" => ("completed", Some("cancelled")),
+        "skipped" => ("completed", Some("skipped")),
+        "manual" | "scheduled" => ("waiting", None),
+        _ => ("queued", None),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GlCiSettings {
+    ci_config_path: Option<String>,
+    jobs_enabled: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GlPipeline {
+    id: u64,
+    iid: Option<u64>,
+    name: Option<String>,
+    status: String,
+    #[serde(rename = "ref")]
+    ref_: String,
+    sha: String,
+    created_at: DateTime<Utc>,
+    updated_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_status() {
+        assert_eq!(normalize_status("running"), ("in_progress", None));
+        assert_eq!(normalize_status("failed"), ("completed", Some("failure")));
+        assert_eq!(normalize_status("canceled"), ("completed", Some("cancelled")));
+        assert_eq!(normalize_status("waiting_for_resource"), ("queued", None));
+    }
+}
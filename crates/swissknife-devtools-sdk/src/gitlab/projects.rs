@@ -0,0 +1,458 @@
+use crate::gitlab::client::project;
+use crate::gitlab::GitLabClient;
+use crate::rest::encode_path;
+use crate::{
+    Branch, Comment, Commit, Error, FileContent, GitProvider, Issue, IssueState, ListOptions, ListResult, PullRequest,
+    PullRequestState, Repository, Result,
+};
+use async_trait::async_trait;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use reqwest::Method;
+use serde::Deserialize;
+use serde_json::json;
+
+impl GitLabClient {
+    pub async fn get_project(&self, project_id: &str) -> Result<Repository> {
+        self.get_repository("", project_id).await
+    }
+
+    pub async fn create_issue_with_labels(
+        &self,
+        owner: &str,
+        repo: &str,
+        title: &str,
+        description: Option<&str>,
+        labels: &[String],
+    ) -> Result<Issue> {
+        let mut payload = json!({ "title": title, "description": description });
+        if !labels.is_empty() {
+            payload["labels"] = json!(labels.join(","));
+        }
+        let issue: GlIssue = self
+            .json(
+                self.request(Method::POST, &format!("/projects/{}/issues", project(owner, repo)))
+                    .json(&payload),
+            )
+            .await?;
+        Ok(issue.into())
+    }
+}
+
+#[async_trait]
+impl GitProvider for GitLabClient {
+    async fn get_repository(&self, owner: &str, repo: &str) -> Result<Repository> {
+        let project: GlProject = self
+            .json(self.request(Method::GET, &format!("/projects/{}", project(owner, repo))))
+            .await?;
+        Ok(project.into())
+    }
+
+    async fn list_branches(&self, owner: &str, repo: &str) -> Result<Vec<Branch>> {
+        let branches: Vec<GlBranch> = self
+            .list_all(&format!("/projects/{}/repository/branches", project(owner, repo)))
+            .await?;
+        Ok(branches
+            .into_iter()
+            .map(|b| Branch {
+                name: b.name,
+                sha: b.commit.id,
+                protected: b.protected,
+            })
+            .collect())
+    }
+
+    async fn get_file(&self, owner: &str, repo: &str, path: &str, ref_: Option<&str>) -> Result<FileContent> {
+        let file: GlFile = self
+            .json(
+                self.request(
+                    Method::GET,
+                    &format!("/projects/{}/repository/files/{}", project(owner, repo), encode_path(path, false)),
+                )
+                .query(&[("ref", ref_.unwrap_or("HEAD"))]),
+            )
+            .await?;
+
+        let bytes = match file.encoding.as_str() {
+            "base64" => base64::engine::general_purpose::STANDARD
+                .decode(file.content.replace('\n', ""))
+                .map_err(|e| Error::Validation(format!("Invalid base64 content: {}", e)))?,
+            _ => file.content.into_bytes(),
+        };
+        let (content, encoding) = match String::from_utf8(bytes) {
+            Ok(text) => (text, "utf-8"),
+            Err(e) => (base64::engine::general_purpose::STANDARD.encode(e.into_bytes()), "base64"),
+        };
+        Ok(FileContent {
+            path: file.file_path,
+            content,
+            encoding: encoding.to_string(),
+            sha: file.blob_id,
+            size: file.size,
+        })
+    }
+
+    async fn list_commits(&self, owner: &str, repo: &str, options: &ListOptions) -> Result<ListResult<Commit>> {
+        let page = self
+            .list::<GlCommit>(
+                &format!("/projects/{}/repository/commits", project(owner, repo)),
+                Vec::new(),
+                options,
+            )
+            .await?;
+        Ok(map_items(page, Into::into))
+    }
+
+    async fn list_issues(&self, owner: &str, repo: &str, options: &ListOptions) -> Result<ListResult<Issue>> {
+        let state = match options.state.as_deref() {
+            None | Some("open") => "opened",
+            Some(state) => state,
+        };
+        let page = self
+            .list::<GlIssue>(
+                &format!("/projects/{}/issues", project(owner, repo)),
+                vec![("state", state.to_string())],
+                options,
+            )
+            .await?;
+        Ok(map_items(page, Into::into))
+    }
+
+    async fn get_issue(&self, owner: &str, repo: &str, number: u64) -> Result<Issue> {
+        let issue: GlIssue = self
+            .json(self.request(Method::GET, &format!("/projects/{}/issues/{}", project(owner, repo), number)))
+            .await?;
+        Ok(issue.into())
+    }
+
+    async fn create_issue(&self, owner: &str, repo: &str, title: &str, body: Option<&str>) -> Result<Issue> {
+        self.create_issue_with_labels(owner, repo, title, body, &[]).await
+    }
+
+    async fn update_issue(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        title: Option<&str>,
+        body: Option<&str>,
+        state: Option<IssueState>,
+    ) -> Result<Issue> {
+        let mut payload = json!({});
+        if let Some(title) = title {
+            payload["title"] = json!(title);
+        }
+        if let Some(body) = body {
+            payload["description"] = json!(body);
+        }
+        if let Some(state) = state {
+            payload["state_event"] = json!(match state {
+                IssueState::Open => "reopen",
+                IssueState::Closed => "close",
+            });
+        }
+        let issue: GlIssue = self
+            .json(
+                self.request(Method::PUT, &format!("/projects/{}/issues/{}", project(owner, repo), number))
+                    .json(&payload),
+            )
+            .await?;
+        Ok(issue.into())
+    }
+
+    async fn list_pull_requests(
+        &self,
+        owner: &str,
+        repo: &str,
+        options: &ListOptions,
+    ) -> Result<ListResult<PullRequest>> {
+        let state = match options.state.as_deref() {
+            None | Some("open") => "opened",
+            Some(state) => state,
+        };
+        let page = self
+            .list::<GlMergeRequest>(
+                &format!("/projects/{}/merge_requests", project(owner, repo)),
+                vec![("state", state.to_string())],
+                options,
+            )
+            .await?;
+        Ok(map_items(page, Into::into))
+    }
+
+    async fn get_pull_request(&self, owner: &str, repo: &str, number: u64) -> Result<PullRequest> {
+        let mr: GlMergeRequest = self
+            .json(self.request(
+                Method::GET,
+                &format!("/projects/{}/merge_requests/{}", project(owner, repo), number),
+            ))
+            .await?;
+        Ok(mr.into())
+    }
+
+    async fn create_pull_request(
+        &self,
+        owner: &str,
+        repo: &str,
+        title: &str,
+        head: &str,
+        base: &str,
+        body: Option<&str>,
+    ) -> Result<PullRequest> {
+        let payload = json!({
+            "title": title,
+            "source_branch": head,
+            "target_branch": base,
+            "description": body,
+        });
+        let mr: GlMergeRequest = self
+            .json(
+                self.request(Method::POST, &format!("/projects/{}/merge_requests", project(owner, repo)))
+                    .json(&payload),
+            )
+            .await?;
+        Ok(mr.into())
+    }
+
+    async fn merge_pull_request(&self, owner: &str, repo: &str, number: u64) -> Result<()> {
+        self.send(self.request(
+            Method::PUT,
+            &format!("/projects/{}/merge_requests/{}/merge", project(owner, repo), number),
+        ))
+        .await?;
+        Ok(())
+    }
+
+    async fn list_comments(&self, owner: &str, repo: &str, issue_number: u64) -> Result<Vec<Comment>> {
+        let notes: Vec<GlNote> = self
+            .list_all(&format!("/projects/{}/issues/{}/notes", project(owner, repo), issue_number))
+            .await?;
+        // System notes record label changes, assignments and the like.
+        Ok(notes.into_iter().filter(|n| !n.system).map(Into::into).collect())
+    }
+
+    async fn create_comment(&self, owner: &str, repo: &str, issue_number: u64, body: &str) -> Result<Comment> {
+        let note: GlNote = self
+            .json(
+                self.request(
+                    Method::POST,
+                    &format!("/projects/{}/issues/{}/notes", project(owner, repo), issue_number),
+                )
+                .json(&json!({ "body": body })),
+            )
+            .await?;
+        Ok(note.into())
+    }
+}
+
+fn map_items<T, U>(page: ListResult<T>, f: impl FnMut(T) -> U) -> ListResult<U> {
+    ListResult {
+        items: page.items.into_iter().map(f).collect(),
+        total: page.total,
+        has_more: page.has_more,
+        next_cursor: page.next_cursor,
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GlUser {
+    username: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GlNamespace {
+    full_path: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GlProject {
+    id: u64,
+    name: String,
+    path_with_namespace: String,
+    description: Option<String>,
+    web_url: String,
+    http_url_to_repo: String,
+    default_branch: Option<String>,
+    visibility: Option<String>,
+    namespace: GlNamespace,
+    created_at: Option<DateTime<Utc>>,
+    last_activity_at: Option<DateTime<Utc>>,
+    star_count: Option<u32>,
+    forks_count: Option<u32>,
+}
+
+impl From<GlProject> for Repository {
+    fn from(p: GlProject) -> Self {
+        Repository {
+            id: p.id.to_string(),
+            name: p.name,
+            full_name: p.path_with_namespace,
+            description: p.description,
+            url: p.web_url,
+            clone_url: p.http_url_to_repo,
+            default_branch: p.default_branch.unwrap_or_else(|| "main".to_string()),
+            is_private: p.visibility.as_deref() != Some("public"),
+            owner: p.namespace.full_path,
+            created_at: p.created_at,
+            updated_at: p.last_activity_at,
+            language: None,
+            stars: p.star_count,
+            forks: p.forks_count,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GlBranch {
+    name: String,
+    commit: GlCommitRef,
+    #[serde(default)]
+    protected: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct GlCommitRef {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GlFile {
+    file_path: String,
+    size: u64,
+    encoding: String,
+    content: String,
+    blob_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GlCommit {
+    id: String,
+    message: String,
+    author_name: String,
+    author_email: Option<String>,
+    authored_date: Option<DateTime<Utc>>,
+    web_url: String,
+}
+
+impl From<GlCommit> for Commit {
+    fn from(c: GlCommit) -> Self {
+        Commit {
+            sha: c.id,
+            message: c.message,
+            author: c.author_name,
+            author_email: c.author_email,
+            date: c.authored_date.unwrap_or_else(Utc::now),
+            url: c.web_url,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GlIssue {
+    id: u64,
+    iid: u64,
+    title: String,
+    description: Option<String>,
+    state: String,
+    author: GlUser,
+    #[serde(default)]
+    assignees: Vec<GlUser>,
+    #[serde(default)]
+    labels: Vec<String>,
+    created_at: DateTime<Utc>,
+    updated_at: Option<DateTime<Utc>>,
+    closed_at: Option<DateTime<Utc>>,
+}
+
+impl From<GlIssue> for Issue {
+    fn from(i: GlIssue) -> Self {
+        Issue {
+            id: i.id.to_string(),
+            number: i.iid,
+            title: i.title,
+            body: i.description,
+            state: if i.state == "closed" { IssueState::Closed } else { IssueState::Open },
+            author: i.author.username,
+            assignees: i.assignees.into_iter().map(|u| u.username).collect(),
+            labels: i.labels,
+            created_at: i.created_at,
+            updated_at: i.updated_at,
+            closed_at: i.closed_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GlMergeRequest {
+    id: u64,
+    iid: u64,
+    title: String,
+    description: Option<String>,
+    state: String,
+    author: GlUser,
+    source_branch: String,
+    target_branch: String,
+    #[serde(default)]
+    draft: bool,
+    detailed_merge_status: Option<String>,
+    created_at: DateTime<Utc>,
+    updated_at: Option<DateTime<Utc>>,
+    merged_at: Option<DateTime<Utc>>,
+}
+
+impl From<GlMergeRequest> for PullRequest {
+    fn from(mr: GlMergeRequest) -> Self {
+        let state = match mr.state.as_str() {
+            "merged" => PullRequestState::Merged,
+            "closed" | "locked" => PullRequestState::Closed,
+            _ => PullRequestState::Open,
+        };
+        // Statuses still being computed say nothing about mergeability yet.
+        let mergeable = match mr.detailed_merge_status.as_deref() {
+            Some("mergeable") => Some(true),
+            None | Some("checking") | Some("unchecked") | Some("preparing") | Some("approvals_syncing") => None,
+            Some(_) => Some(false),
+        };
+        PullRequest {
+            id: mr.id.to_string(),
+            number: mr.iid,
+            title: mr.title,
+            body: mr.description,
+            state,
+            author: mr.author.username,
+            head_branch: mr.source_branch,
+            base_branch: mr.target_branch,
+            is_draft: mr.draft,
+            mergeable,
+            created_at: mr.created_at,
+            updated_at: mr.updated_at,
+            merged_at: mr.merged_at,
+            additions: None,
+            deletions: None,
+            changed_files: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GlNote {
+    id: u64,
+    body: String,
+    author: GlUser,
+    #[serde(default)]
+    system: bool,
+    created_at: DateTime<Utc>,
+    updated_at: Option<DateTime<Utc>>,
+}
+
+impl From<GlNote> for Comment {
+    fn from(n: GlNote) -> Self {
+        Comment {
+            id: n.id.to_string(),
+            body: n.body,
+            author: n.author.username,
+            created_at: n.created_at,
+            updated_at: n.updated_at,
+        }
+    }
+}
@@ -2,6 +2,9 @@ mod error;
 
 pub use error::{Error, Result};
 
+#[cfg(any(feature = "github", feature = "gitlab"))]
+mod rest;
+
 #[cfg(feature = "github")]
 pub mod github;
 
@@ -148,6 +151,7 @@ pub struct ListOptions {
     pub page: Option<u32>,
     pub per_page: Option<u32>,
     pub state: Option<String>,
+    pub cursor: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -155,6 +159,7 @@ pub struct ListResult<T> {
     pub items: Vec<T>,
     pub total: Option<u64>,
     pub has_more: bool,
+    pub next_cursor: Option<String>,
 }
 
 #[async_trait]
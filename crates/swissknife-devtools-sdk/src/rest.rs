@@ -0,0 +1,76 @@
+use crate::{Error, Result};
+use reqwest::header::HeaderMap;
+
+// RFC 8288 `Link: <url>; rel="next", <url>; rel="last"`. Both GitHub and
+// GitLab (offset and keyset) advertise the next page this way, so the URL
+// itself serves as the opaque cursor.
+pub(crate) fn next_link(headers: &HeaderMap) -> Option<String> {
+    let link = headers.get("link")?.to_str().ok()?;
+    link.split(',').find_map(|part| {
+        let (url, params) = part.split_once(';')?;
+        params
+            .split(';')
+            .any(|p| {
+                let p = p.trim();
+                p == "rel=\"next\"" || p == "rel=next"
+            })
+            .then(|| url.trim().trim_start_matches('<').trim_end_matches('>').to_string())
+    })
+}
+
+// Cursors are full URLs; refuse to send credentials anywhere but the API host.
+pub(crate) fn check_cursor<'a>(cursor: &'a str, base_url: &str) -> Result<&'a str> {
+    if cursor.starts_with(&format!("{}/", base_url)) {
+        Ok(cursor)
+    } else {
+        Err(Error::Validation(format!("Cursor does not belong to {}", base_url)))
+    }
+}
+
+// Percent-encodes everything outside the RFC 3986 unreserved set, optionally
+// keeping `/` so nested paths stay readable.
+pub(crate) fn encode_path(path: &str, keep_slash: bool) -> String {
+    let mut encoded = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => encoded.push(byte as char),
+            b'/' if keep_slash => encoded.push('/'),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_link() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "link",
+            r#"<https://api.github.com/repositories/1/issues?page=1>; rel="prev", <https://api.github.com/repositories/1/issues?page=3>; rel="next", <https://api.github.com/repositories/1/issues?page=9>; rel="last""#
+                .parse()
+                .unwrap(),
+        );
+        assert_eq!(next_link(&headers).as_deref(), Some("https://api.github.com/repositories/1/issues?page=3"));
+
+        headers.insert(
+            "link",
+            r#"<https://gitlab.example.com/api/v4/projects?id_after=42&pagination=keyset&per_page=20>; rel="next""#
+                .parse()
+                .unwrap(),
+        );
+        let cursor = next_link(&headers).unwrap();
+        assert!(check_cursor(&cursor, "https://gitlab.example.com/api/v4").is_ok());
+        assert!(check_cursor(&cursor, "https://gitlab.com/api/v4").is_err());
+        assert_eq!(next_link(&HeaderMap::new()), None);
+    }
+
+    #[test]
+    fn test_encode_path() {
+        assert_eq!(encode_path("group/sub project/app", false), "group%2Fsub%20project%2Fapp");
+        assert_eq!(encode_path("docs/a#b.md", true), "docs/a%23b.md");
+    }
+}
@@ -0,0 +1,189 @@
+#![cfg(feature = "github")]
+
+use serde_json::json;
+use swissknife_devtools_sdk::github::GitHubClient;
+use swissknife_devtools_sdk::{CiCdProvider, GitProvider, IssueState, ListOptions};
+use wiremock::matchers::{header, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn client(server: &MockServer) -> GitHubClient {
+    GitHubClient::new("ghp_test").with_base_url(&server.uri())
+}
+
+fn issue(number: u64, pull_request: bool) -> serde_json::Value {
+    let mut issue = json!({
+        "id": 1000 + number,
+        "number": number,
+        "title": format!("Item {}", number),
+        "body": null,
+        "state": "open",
+        "user": { "login": "octocat" },
+        "labels": [{ "name": "bug" }],
+        "created_at": "2024-03-05T12:00:00Z",
+        "updated_at": null,
+        "closed_at": null,
+    });
+    if pull_request {
+        issue["pull_request"] = json!({ "url": format!("https://api.github.com/repos/o/r/pulls/{}", number) });
+    }
+    issue
+}
+
+#[tokio::test]
+async fn test_get_file_decodes_wrapped_base64() {
+    let server = MockServer::start().await;
+    // GitHub wraps base64 content at 60 characters.
+    let encoded = "IyBTd2lzc2tuaWZlCgpBIHRvb2xraXQgZm9yIGJ1aWxkaW5nIGFnZW50cyB3\naXRoIGEgbG9uZyBsaW5lLgo=\n";
+    Mock::given(method("GET"))
+        .and(path("/repos/o/r/contents/docs/READ%20ME.md"))
+        .and(query_param("ref", "main"))
+        .and(header("authorization", "Bearer ghp_test"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "type": "file",
+            "path": "docs/READ ME.md",
+            "sha": "abc123",
+            "size": 62,
+            "encoding": "base64",
+            "content": encoded,
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let file = client(&server).get_file("o", "r", "docs/READ ME.md", Some("main")).await.unwrap();
+
+    assert_eq!(file.path, "docs/READ ME.md");
+    assert_eq!(file.encoding, "utf-8");
+    assert_eq!(file.content, "# Swissknife\n\nA toolkit for building agents with a long line.\n");
+    assert_eq!(file.sha, "abc123");
+}
+
+#[tokio::test]
+async fn test_get_file_reencodes_binary_content() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/repos/o/r/contents/logo.png"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "path": "logo.png",
+            "sha": "def456",
+            "size": 4,
+            "encoding": "base64",
+            "content": "iVBO\nRw==\n",
+        })))
+        .mount(&server)
+        .await;
+
+    let file = client(&server).get_file("o", "r", "logo.png", None).await.unwrap();
+
+    assert_eq!(file.encoding, "base64");
+    assert_eq!(file.content, "iVBORw==");
+}
+
+#[tokio::test]
+async fn test_get_file_fetches_raw_content_for_large_files() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/repos/o/r/contents/big.txt"))
+        .and(header("accept", "application/vnd.github.raw+json"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("large body"))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/repos/o/r/contents/big.txt"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "path": "big.txt",
+            "sha": "789",
+            "size": 2_000_000,
+            "encoding": "none",
+            "content": "",
+        })))
+        .mount(&server)
+        .await;
+
+    let file = client(&server).get_file("o", "r", "big.txt", None).await.unwrap();
+
+    assert_eq!(file.content, "large body");
+    assert_eq!(file.size, 2_000_000);
+}
+
+#[tokio::test]
+async fn test_list_issues_skips_pull_requests() {
+    let server = MockServer::start().await;
+    let next = format!("{}/repositories/1/issues?page=2", server.uri());
+    Mock::given(method("GET"))
+        .and(path("/repos/o/r/issues"))
+        .and(query_param("state", "all"))
+        .and(query_param("per_page", "3"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("link", format!("<{}>; rel=\"next\"", next).as_str())
+                .set_body_json(json!([issue(1, false), issue(2, true), issue(3, false)])),
+        )
+        .mount(&server)
+        .await;
+
+    let options = ListOptions {
+        per_page: Some(3),
+        state: Some("all".to_string()),
+        ..Default::default()
+    };
+    let page = client(&server).list_issues("o", "r", &options).await.unwrap();
+
+    let numbers: Vec<u64> = page.items.iter().map(|i| i.number).collect();
+    assert_eq!(numbers, vec![1, 3]);
+    assert_eq!(page.items[0].state, IssueState::Open);
+    assert_eq!(page.items[0].labels, vec!["bug"]);
+    assert!(page.has_more);
+    assert_eq!(page.next_cursor.as_deref(), Some(next.as_str()));
+}
+
+#[tokio::test]
+async fn test_list_workflow_runs_maps_status_and_conclusion() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/repos/o/r/actions/workflows/ci.yml/runs"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "total_count": 2,
+            "workflow_runs": [
+                {
+                    "id": 30433642,
+                    "workflow_id": 159038,
+                    "name": "CI",
+                    "status": "completed",
+                    "conclusion": "failure",
+                    "head_branch": "main",
+                    "head_sha": "acb5820ced9479c074f688cc328bf03f341a511d",
+                    "created_at": "2024-03-05T12:00:00Z",
+                    "updated_at": "2024-03-05T12:04:00Z",
+                },
+                {
+                    "id": 30433643,
+                    "workflow_id": 159038,
+                    "name": null,
+                    "status": "in_progress",
+                    "conclusion": null,
+                    "head_branch": null,
+                    "head_sha": "0f1e2d",
+                    "created_at": "2024-03-05T12:10:00Z",
+                    "updated_at": null,
+                },
+            ],
+        })))
+        .mount(&server)
+        .await;
+
+    let runs = client(&server).list_workflow_runs("o", "r", "ci.yml").await.unwrap();
+
+    assert_eq!(runs.len(), 2);
+    assert_eq!(runs[0].id, "30433642");
+    assert_eq!(runs[0].workflow_id, "159038");
+    assert_eq!(runs[0].status, "completed");
+    assert_eq!(runs[0].conclusion.as_deref(), Some("failure"));
+    assert_eq!(runs[0].branch, "main");
+    assert_eq!(runs[1].status, "in_progress");
+    assert_eq!(runs[1].conclusion, None);
+    assert_eq!(runs[1].name, "");
+    assert_eq!(runs[1].branch, "");
+    assert!(runs[1].updated_at.is_none());
+}
@@ -0,0 +1,1364 @@
+use crate::{
+    page_number, Address, Collection, CollectionProvider, Customer, EcommerceProvider, Error, FinancialStatus,
+    FulfillmentStatus, InventoryItem, InventoryProvider, LineItem, ListOptions, ListResult, Order, OrderFilter,
+    OrderStatus, Product, ProductFilter, ProductImage, ProductOption, ProductStatus, ProductVariant, Result,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::Method;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+const API_BASE: &str = "https://api.bigcommerce.com/stores";
+const DEFAULT_LIMIT: u32 = 50;
+const MAX_LIMIT: u32 = 250;
+const ORDER_STATUS_IDS: std::ops::RangeInclusive<u32> = 0..=14;
+
+#[derive(Clone)]
+pub struct BigCommerceClient {
+    store_hash: String,
+    access_token: String,
+    http: reqwest::Client,
+}
+
+impl BigCommerceClient {
+    pub fn new(store_hash: impl Into<String>, access_token: impl Into<String>) -> Self {
+        Self {
+            store_hash: store_hash.into(),
+            access_token: access_token.into(),
+            http: reqwest::Client::new(),
+        }
+    }
+
+    async fn send<T: DeserializeOwned>(&self, method: Method, path: &str, query: &[(&str, String)], body: Option<Value>) -> Result<T> {
+        let mut request = self
+            .http
+            .request(method, format!("{}/{}{}", API_BASE, self.store_hash, path))
+            .header("X-Auth-Token", &self.access_token)
+            .header("Accept", "application/json")
+            .query(query);
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+
+        let response = request.send().await?;
+        let status = response.status().as_u16();
+        let text = response.text().await?;
+
+        if !(200..300).contains(&status) {
+            let message = error_message(&text);
+            return Err(match status {
+                400 | 409 | 422 => Error::Validation(message),
+                401 | 403 => Error::Auth(message),
+                404 => Error::NotFound(message),
+                429 => Error::RateLimited,
+                _ => Error::Api {
+                    message,
+                    code: Some(status.to_string()),
+                },
+            });
+        }
+
+        if text.trim().is_empty() {
+            return Ok(serde_json::from_value(Value::Null)?);
+        }
+        Ok(serde_json::from_str(&text)?)
+    }
+
+    async fn v3_page<T: DeserializeOwned>(&self, path: &str, mut query: Vec<(&str, String)>, options: &ListOptions) -> Result<ListResult<T>> {
+        let page = page_number(options);
+        query.push(("page", page.to_string()));
+        query.push(("limit", options.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT).to_string()));
+        let response: V3Response<Vec<T>> = self.send(Method::GET, path, &query, None).await?;
+        let has_more = response
+            .meta
+            .and_then(|m| m.pagination)
+            .is_some_and(|p| p.current_page < p.total_pages);
+        Ok(ListResult {
+            items: response.data,
+            has_more,
+            next_cursor: has_more.then(|| (page + 1).to_string()),
+        })
+    }
+
+    async fn fetch_product(&self, id: &str) -> Result<BcProduct> {
+        let response: V3Response<BcProduct> = self
+            .send(
+                Method::GET,
+                &format!("/v3/catalog/products/{}", id),
+                &[("include", "variants,images,options".to_string())],
+                None,
+            )
+            .await?;
+        Ok(response.data)
+    }
+
+    async fn brand_id(&self, name: &str) -> Result<Option<i64>> {
+        let response: V3Response<Vec<BcBrand>> = self
+            .send(Method::GET, "/v3/catalog/brands", &[("name", name.to_string())], None)
+            .await?;
+        Ok(response.data.into_iter().next().map(|b| b.id))
+    }
+
+    async fn order_details(&self, order: BcOrder) -> Result<Order> {
+        let products: Option<Vec<BcOrderProduct>> = self
+            .send(
+                Method::GET,
+                &format!("/v2/orders/{}/products", order.id),
+                &[("limit", MAX_LIMIT.to_string())],
+                None,
+            )
+            .await?;
+        let shipping: Option<Vec<BcAddress>> = self
+            .send(Method::GET, &format!("/v2/orders/{}/shipping_addresses", order.id), &[], None)
+            .await?;
+        Ok(to_order(
+            order,
+            products.unwrap_or_default(),
+            shipping.unwrap_or_default().into_iter().next(),
+        ))
+    }
+
+    async fn fetch_customer(&self, id: &str) -> Result<Customer> {
+        let response: V3Response<Vec<BcCustomer>> = self
+            .send(
+                Method::GET,
+                "/v3/customers",
+                &[("id:in", id.to_string()), ("include", "addresses".to_string())],
+                None,
+            )
+            .await?;
+        response
+            .data
+            .into_iter()
+            .next()
+            .map(to_customer)
+            .ok_or_else(|| Error::NotFound(format!("Customer {} not found", id)))
+    }
+
+    async fn inventory_levels(&self, variant_id: &str) -> Result<BcInventoryItem> {
+        let response: V3Response<Vec<BcInventoryItem>> = self
+            .send(
+                Method::GET,
+                "/v3/inventory/items",
+                &[("variant_id:in", variant_id.to_string())],
+                None,
+            )
+            .await?;
+        response
+            .data
+            .into_iter()
+            .next()
+            .ok_or_else(|| Error::NotFound(format!("Inventory for variant {} not found", variant_id)))
+    }
+}
+
+#[async_trait]
+impl EcommerceProvider for BigCommerceClient {
+    async fn list_products(&self, filter: &ProductFilter, options: &ListOptions) -> Result<ListResult<Product>> {
+        let mut query = vec![("include", "variants,images,options".to_string())];
+        match filter.status {
+            Some(ProductStatus::Active) => query.push(("is_visible", "true".to_string())),
+            Some(ProductStatus::Draft) => query.push(("is_visible", "false".to_string())),
+            Some(ProductStatus::Archived) => query.push(("availability", "disabled".to_string())),
+            None => {}
+        }
+        if let Some(vendor) = &filter.vendor {
+            match self.brand_id(vendor).await? {
+                Some(id) => query.push(("brand_id", id.to_string())),
+                None => {
+                    return Ok(ListResult {
+                        items: Vec::new(),
+                        has_more: false,
+                        next_cursor: None,
+                    })
+                }
+            }
+        }
+        if let Some(product_type) = &filter.product_type {
+            query.push(("type", product_type.clone()));
+        }
+        if let Some(category) = &filter.collection_id {
+            query.push(("categories:in", category.clone()));
+        }
+        if !filter.ids.is_empty() {
+            query.push(("id:in", filter.ids.join(",")));
+        }
+
+        let page: ListResult<BcProduct> = self.v3_page("/v3/catalog/products", query, options).await?;
+        Ok(ListResult {
+            items: page.items.into_iter().map(to_product).collect(),
+            has_more: page.has_more,
+            next_cursor: page.next_cursor,
+        })
+    }
+
+    async fn get_product(&self, id: &str) -> Result<Product> {
+        Ok(to_product(self.fetch_product(id).await?))
+    }
+
+    async fn create_product(&self, product: &Product) -> Result<Product> {
+        let mut input = product_input(product);
+        if !product.images.is_empty() {
+            let images: Vec<Value> = product
+                .images
+                .iter()
+                .enumerate()
+                .map(|(i, image)| {
+                    json!({
+                        "image_url": image.src,
+                        "description": image.alt.clone().unwrap_or_default(),
+                        "is_thumbnail": i == 0,
+                        "sort_order": image.position.unwrap_or(i as i32),
+                    })
+                })
+                .collect();
+            input.insert("images".to_string(), json!(images));
+        }
+        if !product.options.is_empty() {
+            let variants: Vec<Value> = product.variants.iter().map(variant_input).collect();
+            input.insert("variants".to_string(), json!(variants));
+        }
+
+        let created: V3Response<BcProduct> = self
+            .send(Method::POST, "/v3/catalog/products", &[], Some(Value::Object(input)))
+            .await?;
+        self.get_product(&created.data.id.to_string()).await
+    }
+
+    async fn update_product(&self, id: &str, product: &Product) -> Result<Product> {
+        let input = product_input(product);
+        self.send::<Value>(Method::PUT, &format!("/v3/catalog/products/{}", id), &[], Some(Value::Object(input)))
+            .await?;
+
+        if !product.options.is_empty() {
+            let (existing, new): (Vec<&ProductVariant>, Vec<&ProductVariant>) =
+                product.variants.iter().partition(|v| !v.id.is_empty());
+            if !existing.is_empty() {
+                let updates: Vec<Value> = existing
+                    .into_iter()
+                    .map(|v| {
+                        let mut input = variant_input(v);
+                        input["id"] = json!(v.id.parse::<i64>().unwrap_or_default());
+                        if let Value::Object(map) = &mut input {
+                            map.remove("option_values");
+                        }
+                        input
+                    })
+                    .collect();
+                self.send::<Value>(Method::PUT, "/v3/catalog/variants", &[], Some(json!(updates)))
+                    .await?;
+            }
+            for variant in new {
+                self.send::<Value>(
+                    Method::POST,
+                    &format!("/v3/catalog/products/{}/variants", id),
+                    &[],
+                    Some(variant_input(variant)),
+                )
+                .await?;
+            }
+        }
+
+        self.get_product(id).await
+    }
+
+    async fn delete_product(&self, id: &str) -> Result<()> {
+        self.send::<Value>(Method::DELETE, &format!("/v3/catalog/products/{}", id), &[], None)
+            .await?;
+        Ok(())
+    }
+
+    async fn list_orders(&self, filter: &OrderFilter, options: &ListOptions) -> Result<ListResult<Order>> {
+        let page = page_number(options);
+        let limit = options.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+        let mut query = vec![
+            ("page", page.to_string()),
+            ("limit", limit.to_string()),
+            ("sort", "date_created:desc".to_string()),
+        ];
+
+        if filter.status.is_some() || filter.financial_status.is_some() || filter.fulfillment_status.is_some() {
+            let ids = status_ids_for(filter);
+            match ids.as_slice() {
+                [] => {
+                    return Ok(ListResult {
+                        items: Vec::new(),
+                        has_more: false,
+                        next_cursor: None,
+                    })
+                }
+                [id] => query.push(("status_id", id.to_string())),
+                _ => {}
+            }
+        }
+        if let Some(min) = filter.created_at_min {
+            query.push(("min_date_created", min.to_rfc3339()));
+        }
+        if let Some(max) = filter.created_at_max {
+            query.push(("max_date_created", max.to_rfc3339()));
+        }
+
+        let orders: Option<Vec<BcOrder>> = self.send(Method::GET, "/v2/orders", &query, None).await?;
+        let orders = orders.unwrap_or_default();
+        let has_more = orders.len() as u32 == limit;
+        Ok(ListResult {
+            items: orders
+                .into_iter()
+                .map(|o| to_order(o, Vec::new(), None))
+                .filter(|o| order_matches(o, filter))
+                .collect(),
+            has_more,
+            next_cursor: has_more.then(|| (page + 1).to_string()),
+        })
+    }
+
+    async fn get_order(&self, id: &str) -> Result<Order> {
+        let order: BcOrder = self.send(Method::GET, &format!("/v2/orders/{}", id), &[], None).await?;
+        self.order_details(order).await
+    }
+
+    async fn create_order(&self, order: &Order) -> Result<Order> {
+        let products: Vec<Value> = order
+            .line_items
+            .iter()
+            .map(|item| {
+                Ok(match &item.product_id {
+                    Some(product_id) => {
+                        let mut product = json!({ "product_id": numeric_id(product_id)?, "quantity": item.quantity });
+                        if let Some(variant_id) = &item.variant_id {
+                            product["variant_id"] = json!(numeric_id(variant_id)?);
+                        }
+                        product
+                    }
+                    None => json!({
+                        "name": item.title,
+                        "quantity": item.quantity,
+                        "sku": item.sku,
+                        "price_ex_tax": item.price,
+                        "price_inc_tax": item.price,
+                    }),
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        let mut billing = order.billing_address.as_ref().map(address_input).unwrap_or_else(|| json!({}));
+        if let Some(email) = &order.email {
+            billing["email"] = json!(email);
+        }
+        if let Some(phone) = &order.phone {
+            billing["phone"] = json!(phone);
+        }
+
+        let mut body = Map::new();
+        body.insert("status_id".to_string(), json!(status_id_for(order.status, order.financial_status)));
+        body.insert("billing_address".to_string(), billing);
+        body.insert("products".to_string(), json!(products));
+        if let Some(shipping) = &order.shipping_address {
+            body.insert("shipping_addresses".to_string(), json!([address_input(shipping)]));
+        }
+        if let Some(customer) = order.customer.as_ref().filter(|c| !c.id.is_empty()) {
+            body.insert("customer_id".to_string(), json!(numeric_id(&customer.id)?));
+        }
+        if let Some(note) = &order.note {
+            body.insert("customer_message".to_string(), json!(note));
+        }
+        if !order.currency.is_empty() {
+            body.insert("default_currency_code".to_string(), json!(order.currency));
+        }
+
+        let created: BcOrder = self.send(Method::POST, "/v2/orders", &[], Some(Value::Object(body))).await?;
+        self.order_details(created).await
+    }
+
+    async fn update_order(&self, id: &str, order: &Order) -> Result<Order> {
+        let mut body = Map::new();
+        if let Some(note) = &order.note {
+            body.insert("customer_message".to_string(), json!(note));
+        }
+        let mut billing = order.billing_address.as_ref().map(address_input).unwrap_or_else(|| json!({}));
+        if let Some(email) = &order.email {
+            billing["email"] = json!(email);
+        }
+        if let Some(phone) = &order.phone {
+            billing["phone"] = json!(phone);
+        }
+        if billing.as_object().is_some_and(|b| !b.is_empty()) {
+            body.insert("billing_address".to_string(), billing);
+        }
+        let updated: BcOrder = self
+            .send(Method::PUT, &format!("/v2/orders/{}", id), &[], Some(Value::Object(body)))
+            .await?;
+        self.order_details(updated).await
+    }
+
+    async fn cancel_order(&self, id: &str) -> Result<Order> {
+        let cancelled: BcOrder = self
+            .send(Method::PUT, &format!("/v2/orders/{}", id), &[], Some(json!({ "status_id": 5 })))
+            .await?;
+        self.order_details(cancelled).await
+    }
+
+    async fn list_customers(&self, options: &ListOptions) -> Result<ListResult<Customer>> {
+        let page: ListResult<BcCustomer> = self
+            .v3_page("/v3/customers", vec![("include", "addresses".to_string())], options)
+            .await?;
+        Ok(ListResult {
+            items: page.items.into_iter().map(to_customer).collect(),
+            has_more: page.has_more,
+            next_cursor: page.next_cursor,
+        })
+    }
+
+    async fn get_customer(&self, id: &str) -> Result<Customer> {
+        self.fetch_customer(id).await
+    }
+
+    async fn create_customer(&self, customer: &Customer) -> Result<Customer> {
+        let mut input = customer_input(customer);
+        if !customer.addresses.is_empty() {
+            let addresses: Vec<Value> = customer.addresses.iter().map(customer_address_input).collect();
+            input.insert("addresses".to_string(), json!(addresses));
+        }
+        let created: V3Response<Vec<BcCustomer>> = self
+            .send(Method::POST, "/v3/customers", &[], Some(json!([input])))
+            .await?;
+        let id = created
+            .data
+            .into_iter()
+            .next()
+            .map(|c| c.id.to_string())
+            .ok_or_else(|| Error::Api {
+                message: "BigCommerce did not return the created customer".to_string(),
+                code: None,
+            })?;
+        self.fetch_customer(&id).await
+    }
+
+    async fn update_customer(&self, id: &str, customer: &Customer) -> Result<Customer> {
+        let mut input = customer_input(customer);
+        input.insert("id".to_string(), json!(numeric_id(id)?));
+        self.send::<Value>(Method::PUT, "/v3/customers", &[], Some(json!([input])))
+            .await?;
+        self.fetch_customer(id).await
+    }
+
+    async fn delete_customer(&self, id: &str) -> Result<()> {
+        self.send::<Value>(Method::DELETE, "/v3/customers", &[("id:in", id.to_string())], None)
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl InventoryProvider for BigCommerceClient {
+    async fn get_inventory(&self, item_id: &str, location_id: Option<&str>) -> Result<InventoryItem> {
+        let item = self.inventory_levels(item_id).await?;
+        let sku = item.identity.sku.filter(|s| !s.is_empty());
+        match location_id {
+            Some(location) => item
+                .locations
+                .into_iter()
+                .find(|l| l.location_id.to_string() == location)
+                .map(|l| InventoryItem {
+                    id: item_id.to_string(),
+                    sku,
+                    inventory_quantity: l.available_to_sell,
+                    location_id: Some(l.location_id.to_string()),
+                })
+                .ok_or_else(|| Error::NotFound(format!("Variant {} is not stocked at location {}", item_id, location))),
+            None => Ok(InventoryItem {
+                id: item_id.to_string(),
+                sku,
+                inventory_quantity: item.locations.iter().map(|l| l.available_to_sell).sum(),
+                location_id: None,
+            }),
+        }
+    }
+
+    async fn adjust_inventory(&self, item_id: &str, location_id: &str, adjustment: i32) -> Result<InventoryItem> {
+        let body = json!({
+            "reason": "Adjusted via API",
+            "items": [{
+                "location_id": numeric_id(location_id)?,
+                "variant_id": numeric_id(item_id)?,
+                "quantity": adjustment,
+            }],
+        });
+        self.send::<Value>(Method::POST, "/v3/inventory/adjustments/relative", &[], Some(body))
+            .await?;
+        self.get_inventory(item_id, Some(location_id)).await
+    }
+
+    async fn set_inventory(&self, item_id: &str, location_id: &str, quantity: i32) -> Result<InventoryItem> {
+        let body = json!({
+            "reason": "Set via API",
+            "items": [{
+                "location_id": numeric_id(location_id)?,
+                "variant_id": numeric_id(item_id)?,
+                "quantity": quantity,
+            }],
+        });
+        self.send::<Value>(Method::PUT, "/v3/inventory/adjustments/absolute", &[], Some(body))
+            .await?;
+        self.get_inventory(item_id, Some(location_id)).await
+    }
+}
+
+#[async_trait]
+impl CollectionProvider for BigCommerceClient {
+    async fn list_collections(&self, options: &ListOptions) -> Result<ListResult<Collection>> {
+        let page: ListResult<BcCategory> = self.v3_page("/v3/catalog/categories", Vec::new(), options).await?;
+        Ok(ListResult {
+            items: page.items.into_iter().map(to_collection).collect(),
+            has_more: page.has_more,
+            next_cursor: page.next_cursor,
+        })
+    }
+
+    async fn get_collection(&self, id: &str) -> Result<Collection> {
+        let response: V3Response<BcCategory> = self
+            .send(Method::GET, &format!("/v3/catalog/categories/{}", id), &[], None)
+            .await?;
+        Ok(to_collection(response.data))
+    }
+
+    async fn create_collection(&self, title: &str, description: Option<&str>) -> Result<Collection> {
+        let response: V3Response<BcCategory> = self
+            .send(
+                Method::POST,
+                "/v3/catalog/categories",
+                &[],
+                Some(json!({ "name": title, "description": description.unwrap_or_default(), "parent_id": 0 })),
+            )
+            .await?;
+        Ok(to_collection(response.data))
+    }
+
+    async fn add_products_to_collection(&self, collection_id: &str, product_ids: &[&str]) -> Result<()> {
+        if product_ids.is_empty() {
+            return Ok(());
+        }
+        let category_id = numeric_id(collection_id)?;
+        let assignments: Vec<Value> = product_ids
+            .iter()
+            .map(|id| Ok(json!({ "product_id": numeric_id(id)?, "category_id": category_id })))
+            .collect::<Result<_>>()?;
+        self.send::<Value>(
+            Method::PUT,
+            "/v3/catalog/products/category-assignments",
+            &[],
+            Some(json!(assignments)),
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn remove_products_from_collection(&self, collection_id: &str, product_ids: &[&str]) -> Result<()> {
+        if product_ids.is_empty() {
+            return Ok(());
+        }
+        self.send::<Value>(
+            Method::DELETE,
+            "/v3/catalog/products/category-assignments",
+            &[
+                ("product_id:in", product_ids.join(",")),
+                ("category_id:in", collection_id.to_string()),
+            ],
+            None,
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+pub fn order_states(status_id: u32) -> (OrderStatus, FinancialStatus, Option<FulfillmentStatus>) {
+    use FinancialStatus as F;
+    use FulfillmentStatus as S;
+    use OrderStatus as O;
+
+    match status_id {
+        2 => (O::Closed, F::Paid, Some(S::Fulfilled)),
+        3 => (O::Open, F::Paid, Some(S::Partial)),
+        4 => (O::Closed, F::Refunded, None),
+        5 => (O::Cancelled, F::Voided, None),
+        6 => (O::Cancelled, F::Failed, None),
+        8 | 9 | 11 => (O::Open, F::Paid, Some(S::Unfulfilled)),
+        10 => (O::Closed, F::Paid, Some(S::Fulfilled)),
+        12 => (O::Open, F::Authorized, Some(S::Unfulfilled)),
+        13 => (O::Open, F::Paid, None),
+        14 => (O::Open, F::PartiallyRefunded, None),
+        _ => (O::Open, F::Pending, Some(S::Unfulfilled)),
+    }
+}
+
+fn status_ids_for(filter: &OrderFilter) -> Vec<u32> {
+    ORDER_STATUS_IDS
+        .filter(|id| {
+            let (status, financial, fulfillment) = order_states(*id);
+            filter.status.is_none_or(|s| s == status)
+                && filter.financial_status.is_none_or(|f| match f {
+                    FinancialStatus::Authorized => financial == FinancialStatus::Authorized || financial == FinancialStatus::Paid,
+                    f => f == financial,
+                })
+                && filter.fulfillment_status.is_none_or(|f| Some(f) == fulfillment)
+        })
+        .collect()
+}
+
+fn status_id_for(status: OrderStatus, financial: FinancialStatus) -> u32 {
+    match (status, financial) {
+        (_, FinancialStatus::Failed) => 6,
+        (OrderStatus::Cancelled, _) | (_, FinancialStatus::Voided) => 5,
+        (_, FinancialStatus::Refunded) => 4,
+        (_, FinancialStatus::PartiallyRefunded) => 14,
+        (OrderStatus::Closed, _) => 10,
+        (_, FinancialStatus::Paid) | (_, FinancialStatus::Authorized) => 11,
+        _ => 7,
+    }
+}
+
+fn order_matches(order: &Order, filter: &OrderFilter) -> bool {
+    filter.status.is_none_or(|s| s == order.status)
+        && filter.financial_status.is_none_or(|f| f == order.financial_status)
+        && filter.fulfillment_status.is_none_or(|f| Some(f) == order.fulfillment_status)
+}
+
+fn error_message(text: &str) -> String {
+    match serde_json::from_str::<Value>(text) {
+        Ok(Value::Array(errors)) => errors
+            .iter()
+            .filter_map(|e| e["message"].as_str())
+            .collect::<Vec<_>>()
+            .join("; "),
+        Ok(Value::Object(error)) => {
+            let title = error.get("title").and_then(Value::as_str).unwrap_or("Request failed");
+            match error.get("errors").and_then(Value::as_object) {
+                Some(details) if !details.is_empty() => {
+                    let details: Vec<String> = details
+                        .iter()
+                        .map(|(field, message)| format!("{}: {}", field, message.as_str().unwrap_or_default()))
+                        .collect();
+                    format!("{} ({})", title, details.join("; "))
+                }
+                _ => title.to_string(),
+            }
+        }
+        _ => text.to_string(),
+    }
+}
+
+fn numeric_id(id: &str) -> Result<i64> {
+    id.parse()
+        .map_err(|_| Error::Validation(format!("Invalid BigCommerce id: {}", id)))
+}
+
+fn non_empty(value: String) -> Option<String> {
+    (!value.is_empty()).then_some(value)
+}
+
+fn amount(value: &str) -> f64 {
+    value.parse().unwrap_or_default()
+}
+
+fn parse_rfc2822(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc2822(value).ok().map(|d| d.with_timezone(&Utc))
+}
+
+fn product_input(product: &Product) -> Map<String, Value> {
+    let mut input = Map::new();
+    input.insert("name".to_string(), json!(product.title));
+    input.insert(
+        "type".to_string(),
+        json!(product.product_type.as_deref().unwrap_or("physical")),
+    );
+    if let Some(description) = &product.description {
+        input.insert("description".to_string(), json!(description));
+    }
+    let (visible, availability) = match product.status {
+        ProductStatus::Active => (true, "available"),
+        ProductStatus::Draft => (false, "available"),
+        ProductStatus::Archived => (false, "disabled"),
+    };
+    input.insert("is_visible".to_string(), json!(visible));
+    input.insert("availability".to_string(), json!(availability));
+    input.insert("search_keywords".to_string(), json!(product.tags.join(",")));
+    if let Some(vendor) = &product.vendor {
+        input.insert("brand_name".to_string(), json!(vendor));
+    }
+    if let Some(handle) = &product.handle {
+        input.insert(
+            "custom_url".to_string(),
+            json!({ "url": format!("/{}/", handle.trim_matches('/')), "is_customized": true }),
+        );
+    }
+
+    let first = product.variants.first();
+    let base_price = first
+        .map(|v| v.compare_at_price.filter(|c| *c > v.price).unwrap_or(v.price))
+        .unwrap_or_default();
+    input.insert("price".to_string(), json!(base_price));
+    if let Some(variant) = first.filter(|v| v.compare_at_price.is_some_and(|c| c > v.price)) {
+        input.insert("sale_price".to_string(), json!(variant.price));
+    }
+    input.insert(
+        "weight".to_string(),
+        json!(first.and_then(|v| v.weight).unwrap_or_default()),
+    );
+    if product.options.is_empty() {
+        if let Some(variant) = first {
+            if let Some(sku) = &variant.sku {
+                input.insert("sku".to_string(), json!(sku));
+            }
+            if let Some(barcode) = &variant.barcode {
+                input.insert("upc".to_string(), json!(barcode));
+            }
+            if let Some(quantity) = variant.inventory_quantity {
+                input.insert("inventory_tracking".to_string(), json!("product"));
+                input.insert("inventory_level".to_string(), json!(quantity));
+            }
+        }
+    } else if product.variants.iter().any(|v| v.inventory_quantity.is_some()) {
+        input.insert("inventory_tracking".to_string(), json!("variant"));
+    }
+    input
+}
+
+fn variant_input(variant: &ProductVariant) -> Value {
+    let mut input = Map::new();
+    match variant.compare_at_price.filter(|c| *c > variant.price) {
+        Some(regular) => {
+            input.insert("price".to_string(), json!(regular));
+            input.insert("sale_price".to_string(), json!(variant.price));
+        }
+        None => {
+            input.insert("price".to_string(), json!(variant.price));
+        }
+    }
+    if let Some(sku) = &variant.sku {
+        input.insert("sku".to_string(), json!(sku));
+    }
+    if let Some(weight) = variant.weight {
+        input.insert("weight".to_string(), json!(weight));
+    }
+    if let Some(barcode) = &variant.barcode {
+        input.insert("upc".to_string(), json!(barcode));
+    }
+    if let Some(quantity) = variant.inventory_quantity {
+        input.insert("inventory_level".to_string(), json!(quantity));
+    }
+    let option_values: Vec<Value> = variant
+        .options
+        .iter()
+        .map(|(name, label)| json!({ "option_display_name": name, "label": label }))
+        .collect();
+    input.insert("option_values".to_string(), json!(option_values));
+    Value::Object(input)
+}
+
+fn address_input(address: &Address) -> Value {
+    json!({
+        "first_name": address.first_name.clone().unwrap_or_default(),
+        "last_name": address.last_name.clone().unwrap_or_default(),
+        "company": address.company.clone().unwrap_or_default(),
+        "street_1": address.address1.clone().unwrap_or_default(),
+        "street_2": address.address2.clone().unwrap_or_default(),
+        "city": address.city.clone().unwrap_or_default(),
+        "state": address.province.clone().or_else(|| address.province_code.clone()).unwrap_or_default(),
+        "zip": address.zip.clone().unwrap_or_default(),
+        "country": address.country.clone().unwrap_or_default(),
+        "country_iso2": address.country_code.clone().unwrap_or_default(),
+        "phone": address.phone.clone().unwrap_or_default(),
+    })
+}
+
+fn customer_input(customer: &Customer) -> Map<String, Value> {
+    let mut input = Map::new();
+    input.insert("email".to_string(), json!(customer.email.clone().unwrap_or_default()));
+    input.insert("first_name".to_string(), json!(customer.first_name.clone().unwrap_or_default()));
+    input.insert("last_name".to_string(), json!(customer.last_name.clone().unwrap_or_default()));
+    if let Some(phone) = &customer.phone {
+        input.insert("phone".to_string(), json!(phone));
+    }
+    input
+}
+
+fn customer_address_input(address: &Address) -> Value {
+    json!({
+        "first_name": address.first_name.clone().unwrap_or_default(),
+        "last_name": address.last_name.clone().unwrap_or_default(),
+        "company": address.company.clone().unwrap_or_default(),
+        "address1": address.address1.clone().unwrap_or_default(),
+        "address2": address.address2.clone().unwrap_or_default(),
+        "city": address.city.clone().unwrap_or_default(),
+        "state_or_province": address.province.clone().or_else(|| address.province_code.clone()).unwrap_or_default(),
+        "postal_code": address.zip.clone().unwrap_or_default(),
+        "country_code": address.country_code.clone().unwrap_or_default(),
+        "phone": address.phone.clone().unwrap_or_default(),
+    })
+}
+
+fn to_product(product: BcProduct) -> Product {
+    let mut extra = HashMap::new();
+    if product.brand_id != 0 {
+        extra.insert("brand_id".to_string(), json!(product.brand_id.to_string()));
+    }
+    if !product.categories.is_empty() {
+        extra.insert(
+            "category_ids".to_string(),
+            json!(product.categories.iter().map(|c| c.to_string()).collect::<Vec<_>>()),
+        );
+    }
+    extra.insert("availability".to_string(), json!(product.availability));
+
+    let status = if product.availability == "disabled" {
+        ProductStatus::Archived
+    } else if product.is_visible {
+        ProductStatus::Active
+    } else {
+        ProductStatus::Draft
+    };
+
+    let mut images = product.images;
+    images.sort_by_key(|i| i.sort_order);
+
+    let product_price = product.calculated_price.unwrap_or(product.price);
+    Product {
+        id: product.id.to_string(),
+        title: product.name,
+        description: non_empty(product.description),
+        handle: product
+            .custom_url
+            .map(|u| u.url.trim_matches('/').to_string())
+            .and_then(non_empty),
+        status,
+        vendor: None,
+        product_type: non_empty(product.product_type),
+        tags: product
+            .search_keywords
+            .split(',')
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .collect(),
+        variants: product
+            .variants
+            .into_iter()
+            .map(|variant| {
+                let base = variant.price.unwrap_or(product.price);
+                let price = variant.calculated_price.unwrap_or(product_price);
+                let compare_at_price = if price < base {
+                    Some(base)
+                } else {
+                    variant.retail_price.filter(|r| *r > price)
+                };
+                let title = variant
+                    .option_values
+                    .iter()
+                    .map(|o| o.label.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" / ");
+                ProductVariant {
+                    id: variant.id.to_string(),
+                    title: if title.is_empty() { "Default".to_string() } else { title },
+                    sku: non_empty(variant.sku),
+                    price,
+                    compare_at_price,
+                    inventory_quantity: Some(variant.inventory_level),
+                    weight: variant.calculated_weight.or(variant.weight),
+                    weight_unit: None,
+                    options: variant
+                        .option_values
+                        .into_iter()
+                        .map(|o| (o.option_display_name, o.label))
+                        .collect(),
+                    barcode: variant.upc.and_then(non_empty),
+                }
+            })
+            .collect(),
+        images: images
+            .into_iter()
+            .map(|image| ProductImage {
+                id: image.id.to_string(),
+                src: image.url_standard,
+                alt: non_empty(image.description),
+                position: Some(image.sort_order),
+            })
+            .collect(),
+        options: product
+            .options
+            .into_iter()
+            .map(|o| ProductOption {
+                name: o.display_name,
+                values: o.option_values.into_iter().map(|v| v.label).collect(),
+            })
+            .collect(),
+        created_at: product.date_created,
+        updated_at: product.date_modified,
+        extra,
+    }
+}
+
+fn to_address(address: BcAddress) -> Address {
+    Address {
+        first_name: non_empty(address.first_name),
+        last_name: non_empty(address.last_name),
+        company: non_empty(address.company),
+        address1: non_empty(address.street_1),
+        address2: non_empty(address.street_2),
+        city: non_empty(address.city),
+        province: non_empty(address.state),
+        province_code: None,
+        country: non_empty(address.country),
+        country_code: non_empty(address.country_iso2),
+        zip: non_empty(address.zip),
+        phone: non_empty(address.phone),
+    }
+}
+
+fn to_order(order: BcOrder, products: Vec<BcOrderProduct>, shipping: Option<BcAddress>) -> Order {
+    let (status, mut financial_status, fulfillment_status) = order_states(order.status_id);
+    if financial_status == FinancialStatus::Paid && order.payment_status == "authorized" {
+        financial_status = FinancialStatus::Authorized;
+    }
+
+    let mut extra = HashMap::new();
+    extra.insert("bigcommerce_status".to_string(), json!(order.status));
+    extra.insert("status_id".to_string(), json!(order.status_id));
+    extra.insert("items_total".to_string(), json!(order.items_total));
+    extra.insert("items_shipped".to_string(), json!(order.items_shipped));
+    if !order.payment_method.is_empty() {
+        extra.insert("payment_method".to_string(), json!(order.payment_method));
+    }
+
+    let email = non_empty(order.billing_address.email.clone());
+    let phone = non_empty(order.billing_address.phone.clone());
+    let customer = (order.customer_id != 0).then(|| Customer {
+        id: order.customer_id.to_string(),
+        email: email.clone(),
+        first_name: non_empty(order.billing_address.first_name.clone()),
+        last_name: non_empty(order.billing_address.last_name.clone()),
+        phone: phone.clone(),
+        orders_count: None,
+        total_spent: None,
+        tags: Vec::new(),
+        addresses: Vec::new(),
+        created_at: None,
+        updated_at: None,
+    });
+
+    Order {
+        id: order.id.to_string(),
+        order_number: Some(order.id.to_string()),
+        email,
+        phone,
+        status,
+        financial_status,
+        fulfillment_status,
+        currency: order.currency_code,
+        subtotal_price: amount(&order.subtotal_ex_tax),
+        total_tax: amount(&order.total_tax),
+        total_discounts: amount(&order.discount_amount) + amount(&order.coupon_discount),
+        total_price: amount(&order.total_inc_tax),
+        line_items: products
+            .into_iter()
+            .map(|item| LineItem {
+                id: item.id.to_string(),
+                product_id: (item.product_id != 0).then(|| item.product_id.to_string()),
+                variant_id: (item.variant_id != 0).then(|| item.variant_id.to_string()),
+                title: item.name,
+                quantity: item.quantity,
+                price: amount(&item.price_ex_tax),
+                sku: non_empty(item.sku),
+                total_discount: Some(item.applied_discounts.iter().map(|d| amount(&d.amount)).sum())
+                    .filter(|d: &f64| *d > 0.0),
+            })
+            .collect(),
+        shipping_address: shipping.map(to_address),
+        billing_address: Some(to_address(order.billing_address)),
+        customer,
+        note: non_empty(order.customer_message),
+        created_at: parse_rfc2822(&order.date_created).unwrap_or_default(),
+        updated_at: parse_rfc2822(&order.date_modified),
+        extra,
+    }
+}
+
+fn to_customer(customer: BcCustomer) -> Customer {
+    Customer {
+        id: customer.id.to_string(),
+        email: non_empty(customer.email),
+        first_name: non_empty(customer.first_name),
+        last_name: non_empty(customer.last_name),
+        phone: non_empty(customer.phone),
+        orders_count: None,
+        total_spent: None,
+        tags: Vec::new(),
+        addresses: customer
+            .addresses
+            .into_iter()
+            .map(|a| Address {
+                first_name: non_empty(a.first_name),
+                last_name: non_empty(a.last_name),
+                company: non_empty(a.company),
+                address1: non_empty(a.address1),
+                address2: non_empty(a.address2),
+                city: non_empty(a.city),
+                province: non_empty(a.state_or_province),
+                province_code: None,
+                country: non_empty(a.country),
+                country_code: non_empty(a.country_code),
+                zip: non_empty(a.postal_code),
+                phone: non_empty(a.phone),
+            })
+            .collect(),
+        created_at: customer.date_created,
+        updated_at: customer.date_modified,
+    }
+}
+
+fn to_collection(category: BcCategory) -> Collection {
+    Collection {
+        id: category.id.to_string(),
+        title: category.name,
+        description: non_empty(category.description),
+        handle: category
+            .custom_url
+            .map(|u| u.url.trim_matches('/').to_string())
+            .and_then(non_empty),
+        image: non_empty(category.image_url).map(|src| ProductImage {
+            id: String::new(),
+            src,
+            alt: None,
+            position: None,
+        }),
+        products_count: None,
+    }
+}
+
+fn string_or_number<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<String, D::Error> {
+    Ok(match Option::<Value>::deserialize(deserializer)? {
+        Some(Value::String(s)) => s,
+        Some(Value::Number(n)) => n.to_string(),
+        _ => String::new(),
+    })
+}
+
+#[derive(Debug, Deserialize)]
+struct V3Response<T> {
+    data: T,
+    meta: Option<V3Meta>,
+}
+
+#[derive(Debug, Deserialize)]
+struct V3Meta {
+    pagination: Option<V3Pagination>,
+}
+
+#[derive(Debug, Deserialize)]
+struct V3Pagination {
+    current_page: u32,
+    total_pages: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct BcBrand {
+    id: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct BcCustomUrl {
+    url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BcProduct {
+    id: i64,
+    name: String,
+    #[serde(rename = "type", default)]
+    product_type: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    price: f64,
+    calculated_price: Option<f64>,
+    #[serde(default)]
+    is_visible: bool,
+    #[serde(default)]
+    availability: String,
+    #[serde(default)]
+    brand_id: i64,
+    #[serde(default)]
+    categories: Vec<i64>,
+    #[serde(default)]
+    search_keywords: String,
+    custom_url: Option<BcCustomUrl>,
+    date_created: Option<DateTime<Utc>>,
+    date_modified: Option<DateTime<Utc>>,
+    #[serde(default)]
+    variants: Vec<BcVariant>,
+    #[serde(default)]
+    images: Vec<BcImage>,
+    #[serde(default)]
+    options: Vec<BcOption>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BcVariant {
+    id: i64,
+    #[serde(default)]
+    sku: String,
+    price: Option<f64>,
+    calculated_price: Option<f64>,
+    retail_price: Option<f64>,
+    #[serde(default)]
+    inventory_level: i32,
+    weight: Option<f64>,
+    calculated_weight: Option<f64>,
+    upc: Option<String>,
+    #[serde(default)]
+    option_values: Vec<BcVariantOptionValue>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BcVariantOptionValue {
+    option_display_name: String,
+    label: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BcImage {
+    id: i64,
+    #[serde(default)]
+    url_standard: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    sort_order: i32,
+}
+
+#[derive(Debug, Deserialize)]
+struct BcOption {
+    display_name: String,
+    #[serde(default)]
+    option_values: Vec<BcOptionValue>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BcOptionValue {
+    label: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+struct BcAddress {
+    first_name: String,
+    last_name: String,
+    company: String,
+    street_1: String,
+    street_2: String,
+    city: String,
+    state: String,
+    #[serde(deserialize_with = "string_or_number")]
+    zip: String,
+    country: String,
+    country_iso2: String,
+    phone: String,
+    email: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BcOrder {
+    id: i64,
+    #[serde(default)]
+    status_id: u32,
+    #[serde(default)]
+    status: String,
+    #[serde(default)]
+    customer_id: i64,
+    #[serde(default)]
+    date_created: String,
+    #[serde(default)]
+    date_modified: String,
+    #[serde(default, deserialize_with = "string_or_number")]
+    subtotal_ex_tax: String,
+    #[serde(default, deserialize_with = "string_or_number")]
+    total_tax: String,
+    #[serde(default, deserialize_with = "string_or_number")]
+    discount_amount: String,
+    #[serde(default, deserialize_with = "string_or_number")]
+    coupon_discount: String,
+    #[serde(default, deserialize_with = "string_or_number")]
+    total_inc_tax: String,
+    #[serde(default)]
+    currency_code: String,
+    #[serde(default)]
+    payment_status: String,
+    #[serde(default)]
+    payment_method: String,
+    #[serde(default)]
+    billing_address: BcAddress,
+    #[serde(default)]
+    customer_message: String,
+    #[serde(default)]
+    items_total: u32,
+    #[serde(default)]
+    items_shipped: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct BcOrderProduct {
+    id: i64,
+    #[serde(default)]
+    product_id: i64,
+    #[serde(default)]
+    variant_id: i64,
+    #[serde(default)]
+    name: String,
+    quantity: u32,
+    #[serde(default, deserialize_with = "string_or_number")]
+    price_ex_tax: String,
+    #[serde(default)]
+    sku: String,
+    #[serde(default)]
+    applied_discounts: Vec<BcDiscount>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BcDiscount {
+    #[serde(default, deserialize_with = "string_or_number")]
+    amount: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BcCustomer {
+    id: i64,
+    #[serde(default)]
+    email: String,
+    #[serde(default)]
+    first_name: String,
+    #[serde(default)]
+    last_name: String,
+    #[serde(default)]
+    phone: String,
+    date_created: Option<DateTime<Utc>>,
+    date_modified: Option<DateTime<Utc>>,
+    #[serde(default)]
+    addresses: Vec<BcCustomerAddress>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct BcCustomerAddress {
+    first_name: String,
+    last_name: String,
+    company: String,
+    address1: String,
+    address2: String,
+    city: String,
+    state_or_province: String,
+    postal_code: String,
+    country: String,
+    country_code: String,
+    phone: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BcInventoryItem {
+    identity: BcInventoryIdentity,
+    #[serde(default)]
+    locations: Vec<BcInventoryLocation>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BcInventoryIdentity {
+    sku: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BcInventoryLocation {
+    location_id: i64,
+    #[serde(default)]
+    available_to_sell: i32,
+}
+
+#[derive(Debug, Deserialize)]
+struct BcCategory {
+    id: i64,
+    name: String,
+    #[serde(default)]
+    description: String,
+    custom_url: Option<BcCustomUrl>,
+    #[serde(default)]
+    image_url: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_order_status_mapping() {
+        assert_eq!(
+            order_states(3),
+            (OrderStatus::Open, FinancialStatus::Paid, Some(FulfillmentStatus::Partial))
+        );
+        assert_eq!(order_states(5), (OrderStatus::Cancelled, FinancialStatus::Voided, None));
+        assert_eq!(order_states(6), (OrderStatus::Cancelled, FinancialStatus::Failed, None));
+        assert_eq!(status_id_for(OrderStatus::Cancelled, FinancialStatus::Failed), 6);
+        assert_eq!(status_id_for(OrderStatus::Open, FinancialStatus::Voided), 5);
+
+        let unfulfilled_paid = OrderFilter {
+            financial_status: Some(FinancialStatus::Paid),
+            fulfillment_status: Some(FulfillmentStatus::Unfulfilled),
+            ..Default::default()
+        };
+        assert_eq!(status_ids_for(&unfulfilled_paid), vec![8, 9, 11]);
+
+        let order: BcOrder = serde_json::from_value(json!({
+            "id": 118,
+            "status_id": 11,
+            "status": "Awaiting Fulfillment",
+            "customer_id": 4,
+            "date_created": "Tue, 05 Mar 2024 12:00:00 +0000",
+            "subtotal_ex_tax": "40.0000",
+            "total_tax": "3.2000",
+            "discount_amount": "0.0000",
+            "coupon_discount": "5.0000",
+            "total_inc_tax": "38.2000",
+            "currency_code": "USD",
+            "payment_status": "authorized",
+            "billing_address": { "first_name": "Ada", "email": "ada@example.com", "zip": 94107 }
+        }))
+        .unwrap();
+        let products = vec![BcOrderProduct {
+            id: 1,
+            product_id: 77,
+            variant_id: 0,
+            name: "Mug".to_string(),
+            quantity: 2,
+            price_ex_tax: "20.0000".to_string(),
+            sku: String::new(),
+            applied_discounts: vec![BcDiscount { amount: "5".to_string() }],
+        }];
+
+        let order = to_order(order, products, None);
+        assert_eq!(order.financial_status, FinancialStatus::Authorized);
+        assert_eq!(order.fulfillment_status, Some(FulfillmentStatus::Unfulfilled));
+        assert_eq!(order.total_discounts, 5.0);
+        assert_eq!(order.line_items[0].total_discount, Some(5.0));
+        assert_eq!(order.customer.unwrap().id, "4");
+        assert_eq!(order.billing_address.unwrap().zip.as_deref(), Some("94107"));
+        assert_eq!(order.created_at.to_rfc3339(), "2024-03-05T12:00:00+00:00");
+    }
+
+    #[test]
+    fn test_error_messages() {
+        assert_eq!(
+            error_message(r#"{"status":422,"title":"JSON data is missing or invalid","errors":{"name":"required"}}"#),
+            "JSON data is missing or invalid (name: required)"
+        );
+        assert_eq!(error_message(r#"[{"status":400,"message":"The field 'products' is invalid."}]"#), "The field 'products' is invalid.");
+    }
+}
@@ -11,6 +11,9 @@ pub mod woocommerce;
 #[cfg(feature = "bigcommerce")]
 pub mod bigcommerce;
 
+#[cfg(feature = "webhooks")]
+pub mod webhooks;
+
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -114,6 +117,7 @@ pub enum FinancialStatus {
     PartiallyRefunded,
     Refunded,
     Voided,
+    Failed,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -217,6 +221,17 @@ pub struct OrderFilter {
     pub created_at_max: Option<DateTime<Utc>>,
 }
 
+#[cfg(any(feature = "woocommerce", feature = "bigcommerce"))]
+pub(crate) fn page_number(options: &ListOptions) -> u32 {
+    options
+        .cursor
+        .as_deref()
+        .and_then(|c| c.parse().ok())
+        .or(options.page)
+        .unwrap_or(1)
+        .max(1)
+}
+
 #[async_trait]
 pub trait EcommerceProvider: Send + Sync {
     async fn list_products(&self, filter: &ProductFilter, options: &ListOptions) -> Result<ListResult<Product>>;
@@ -0,0 +1,1520 @@
+mod queries;
+mod throttle;
+
+use crate::{
+    Address, Collection, CollectionProvider, Customer, EcommerceProvider, Error, FinancialStatus, FulfillmentStatus,
+    InventoryItem, InventoryProvider, LineItem, ListOptions, ListResult, Order, OrderFilter, OrderStatus, Product,
+    ProductFilter, ProductImage, ProductOption, ProductStatus, ProductVariant, Result,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use throttle::{LeakyBucket, QueryCost};
+
+pub const DEFAULT_API_VERSION: &str = "2025-01";
+const DEFAULT_PAGE_SIZE: u32 = 50;
+const MAX_PAGE_SIZE: u32 = 250;
+
+#[derive(Clone)]
+pub struct ShopifyClient {
+    shop: String,
+    access_token: String,
+    api_version: String,
+    max_retries: u32,
+    bucket: Arc<Mutex<LeakyBucket>>,
+    http: reqwest::Client,
+}
+
+// Returns reserved points to the bucket when a request finishes, however it
+// ends.
+struct Reservation<'a> {
+    bucket: &'a Mutex<LeakyBucket>,
+    cost: f64,
+}
+
+impl Drop for Reservation<'_> {
+    fn drop(&mut self) {
+        self.bucket.lock().unwrap_or_else(|e| e.into_inner()).release(self.cost);
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Location {
+    pub id: String,
+    pub name: String,
+    pub active: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectionRule {
+    pub column: String,
+    pub relation: String,
+    pub condition: String,
+}
+
+impl ShopifyClient {
+    pub fn new(shop: impl Into<String>, access_token: impl Into<String>) -> Self {
+        Self {
+            shop: normalize_shop(&shop.into()),
+            access_token: access_token.into(),
+            api_version: DEFAULT_API_VERSION.to_string(),
+            max_retries: 3,
+            bucket: Arc::new(Mutex::new(LeakyBucket::new())),
+            http: reqwest::Client::new(),
+        }
+    }
+
+    pub fn with_api_version(mut self, version: impl Into<String>) -> Self {
+        self.api_version = version.into();
+        self
+    }
+
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    fn endpoint(&self) -> String {
+        format!("https://{}/admin/api/{}/graphql.json", self.shop, self.api_version)
+    }
+
+    fn lock_bucket(&self) -> std::sync::MutexGuard<'_, LeakyBucket> {
+        self.bucket.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    // Reserves the cost Shopify last requested for the same query text before
+    // sending it.
+    pub async fn graphql(&self, query: &str, variables: Value) -> Result<Value> {
+        self.execute(query, variables, None).await
+    }
+
+    // Like `graphql`, but reserves `estimated_cost` points of the shop's rate
+    // limit, for queries whose cost depends on their variables.
+    pub async fn graphql_with_cost(&self, query: &str, variables: Value, estimated_cost: f64) -> Result<Value> {
+        self.execute(query, variables, Some(estimated_cost)).await
+    }
+
+    async fn execute(&self, query: &str, variables: Value, estimated_cost: Option<f64>) -> Result<Value> {
+        let body = json!({ "query": query, "variables": variables });
+        let mut attempt = 0;
+
+        loop {
+            let (held, delay) = {
+                let mut bucket = self.lock_bucket();
+                let cost = estimated_cost.unwrap_or_else(|| bucket.estimate(query));
+                bucket.reserve(cost, Instant::now())
+            };
+            let _reservation = Reservation {
+                bucket: &self.bucket,
+                cost: held,
+            };
+            if !delay.is_zero() {
+                tokio::time::sleep(delay).await;
+            }
+
+            let response = self
+                .http
+                .post(self.endpoint())
+                .header("X-Shopify-Access-Token", &self.access_token)
+                .json(&body)
+                .send()
+                .await?;
+
+            let status = response.status().as_u16();
+            let retry_after = response
+                .headers()
+                .get("Retry-After")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse::<f64>().ok());
+            let text = response.text().await?;
+
+            if status == 429 {
+                if attempt < self.max_retries {
+                    attempt += 1;
+                    tokio::time::sleep(Duration::from_secs_f64(retry_after.unwrap_or(1.0))).await;
+                    continue;
+                }
+                return Err(Error::RateLimited);
+            }
+
+            if !(200..300).contains(&status) {
+                let message = serde_json::from_str::<Value>(&text)
+                    .ok()
+                    .and_then(|v| v.get("errors").cloned())
+                    .map(|e| e.as_str().map(str::to_string).unwrap_or_else(|| e.to_string()))
+                    .unwrap_or(text);
+                return Err(match status {
+                    401 | 403 => Error::Auth(message),
+                    404 => Error::NotFound(message),
+                    _ => Error::Api {
+                        message,
+                        code: Some(status.to_string()),
+                    },
+                });
+            }
+
+            let response: GraphQlResponse = serde_json::from_str(&text)?;
+            if let Some(cost) = response.extensions.and_then(|e| e.cost) {
+                self.lock_bucket().record(query, &cost);
+            }
+
+            if !response.errors.is_empty() {
+                if response.errors.iter().any(|e| e.code() == Some("THROTTLED")) {
+                    if attempt < self.max_retries {
+                        attempt += 1;
+                        continue;
+                    }
+                    return Err(Error::RateLimited);
+                }
+                return Err(graphql_error(response.errors));
+            }
+
+            return Ok(response.data.unwrap_or(Value::Null));
+        }
+    }
+
+    async fn query<T: DeserializeOwned>(&self, query: &str, variables: Value, field: &str) -> Result<T> {
+        let mut data = self.graphql(query, variables).await?;
+        let value = data.get_mut(field).map(Value::take).unwrap_or(Value::Null);
+        if value.is_null() {
+            return Err(Error::NotFound(format!("Shopify {} not found", field)));
+        }
+        Ok(serde_json::from_value(value)?)
+    }
+
+    async fn mutate(&self, query: &str, variables: Value, field: &str) -> Result<Value> {
+        let mut data = self.graphql(query, variables).await?;
+        let payload = data.get_mut(field).map(Value::take).unwrap_or(Value::Null);
+        check_user_errors(&payload)?;
+        Ok(payload)
+    }
+
+    async fn create_variants(&self, product_id: &str, variants: &[Value], strategy: Option<&str>) -> Result<()> {
+        if variants.is_empty() {
+            return Ok(());
+        }
+        self.mutate(
+            queries::VARIANTS_BULK_CREATE,
+            json!({ "productId": product_id, "variants": variants, "strategy": strategy }),
+            "productVariantsBulkCreate",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn update_variants(&self, product_id: &str, variants: &[Value]) -> Result<()> {
+        if variants.is_empty() {
+            return Ok(());
+        }
+        self.mutate(
+            queries::VARIANTS_BULK_UPDATE,
+            json!({ "productId": product_id, "variants": variants }),
+            "productVariantsBulkUpdate",
+        )
+        .await?;
+        Ok(())
+    }
+
+    pub async fn list_locations(&self) -> Result<Vec<Location>> {
+        let mut locations = Vec::new();
+        let mut after: Option<String> = None;
+        loop {
+            let page: Connection<ShopifyLocation> = self
+                .query(queries::LOCATIONS, json!({ "first": MAX_PAGE_SIZE, "after": after }), "locations")
+                .await?;
+            locations.extend(page.nodes.into_iter().map(|l| Location {
+                id: legacy_id(&l.id),
+                name: l.name,
+                active: l.is_active,
+            }));
+            if !page.page_info.has_next_page {
+                return Ok(locations);
+            }
+            after = page.page_info.end_cursor;
+        }
+    }
+
+    pub async fn inventory_levels(&self, item_id: &str) -> Result<Vec<InventoryItem>> {
+        let item: ShopifyInventoryItem = self
+            .query(queries::INVENTORY_ITEM, json!({ "id": gid("InventoryItem", item_id) }), "inventoryItem")
+            .await?;
+        let id = legacy_id(&item.id);
+        Ok(item
+            .inventory_levels
+            .nodes
+            .into_iter()
+            .map(|level| InventoryItem {
+                id: id.clone(),
+                sku: item.sku.clone().filter(|s| !s.is_empty()),
+                inventory_quantity: level.available(),
+                location_id: Some(legacy_id(&level.location.id)),
+            })
+            .collect())
+    }
+
+    pub async fn create_smart_collection(
+        &self,
+        title: &str,
+        description: Option<&str>,
+        rules: &[CollectionRule],
+        disjunctive: bool,
+    ) -> Result<Collection> {
+        let input = json!({
+            "title": title,
+            "descriptionHtml": description,
+            "ruleSet": { "appliedDisjunctively": disjunctive, "rules": rules },
+        });
+        let payload = self.mutate(queries::COLLECTION_CREATE, json!({ "input": input }), "collectionCreate").await?;
+        Ok(to_collection(serde_json::from_value(payload["collection"].clone())?))
+    }
+}
+
+#[async_trait]
+impl EcommerceProvider for ShopifyClient {
+    async fn list_products(&self, filter: &ProductFilter, options: &ListOptions) -> Result<ListResult<Product>> {
+        let first = page_size(options);
+        let page: Connection<ShopifyProduct> = match &filter.collection_id {
+            Some(collection_id) => {
+                let collection: ShopifyCollectionProducts = self
+                    .query(
+                        queries::COLLECTION_PRODUCTS,
+                        json!({ "id": gid("Collection", collection_id), "first": first, "after": options.cursor }),
+                        "collection",
+                    )
+                    .await?;
+                collection.products
+            }
+            None => {
+                let query = build_product_query(filter);
+                self.query(
+                    queries::PRODUCTS,
+                    json!({ "first": first, "after": options.cursor, "query": query }),
+                    "products",
+                )
+                .await?
+            }
+        };
+        Ok(page.into_list(to_product))
+    }
+
+    async fn get_product(&self, id: &str) -> Result<Product> {
+        let product: ShopifyProduct = self
+            .query(queries::PRODUCT, json!({ "id": gid("Product", id) }), "product")
+            .await?;
+        Ok(to_product(product))
+    }
+
+    async fn create_product(&self, product: &Product) -> Result<Product> {
+        let mut input = product_input(product);
+        if !product.options.is_empty() {
+            let options: Vec<Value> = product
+                .options
+                .iter()
+                .map(|o| json!({ "name": o.name, "values": o.values.iter().map(|v| json!({ "name": v })).collect::<Vec<_>>() }))
+                .collect();
+            input.insert("productOptions".to_string(), json!(options));
+        }
+        let media: Vec<Value> = product
+            .images
+            .iter()
+            .map(|i| json!({ "originalSource": i.src, "alt": i.alt, "mediaContentType": "IMAGE" }))
+            .collect();
+
+        let payload = self
+            .mutate(
+                queries::PRODUCT_CREATE,
+                json!({ "product": input, "media": media }),
+                "productCreate",
+            )
+            .await?;
+        let created: ShopifyCreatedProduct = serde_json::from_value(payload["product"].clone())?;
+
+        if product.options.is_empty() {
+            if let (Some(variant), Some(default)) = (product.variants.first(), created.variants.nodes.first()) {
+                let mut input = variant_input(variant, &product.options);
+                input.insert("id".to_string(), json!(default.id));
+                self.update_variants(&created.id, &[Value::Object(input)]).await?;
+            }
+        } else {
+            let variants: Vec<Value> = product
+                .variants
+                .iter()
+                .map(|v| Value::Object(variant_input(v, &product.options)))
+                .collect();
+            self.create_variants(&created.id, &variants, Some("REMOVE_STANDALONE_VARIANT"))
+                .await?;
+        }
+
+        self.get_product(&created.id).await
+    }
+
+    async fn update_product(&self, id: &str, product: &Product) -> Result<Product> {
+        let product_id = gid("Product", id);
+        let mut input = product_input(product);
+        input.insert("id".to_string(), json!(product_id));
+        self.mutate(queries::PRODUCT_UPDATE, json!({ "product": input }), "productUpdate")
+            .await?;
+
+        let (existing, new): (Vec<&ProductVariant>, Vec<&ProductVariant>) =
+            product.variants.iter().partition(|v| !v.id.is_empty());
+        let existing: Vec<Value> = existing
+            .into_iter()
+            .map(|v| {
+                let mut input = variant_input(v, &product.options);
+                input.insert("id".to_string(), json!(gid("ProductVariant", &v.id)));
+                Value::Object(input)
+            })
+            .collect();
+        let new: Vec<Value> = new
+            .into_iter()
+            .map(|v| Value::Object(variant_input(v, &product.options)))
+            .collect();
+        self.update_variants(&product_id, &existing).await?;
+        self.create_variants(&product_id, &new, None).await?;
+
+        self.get_product(id).await
+    }
+
+    async fn delete_product(&self, id: &str) -> Result<()> {
+        self.mutate(
+            queries::PRODUCT_DELETE,
+            json!({ "input": { "id": gid("Product", id) } }),
+            "productDelete",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn list_orders(&self, filter: &OrderFilter, options: &ListOptions) -> Result<ListResult<Order>> {
+        let page: Connection<ShopifyOrder> = self
+            .query(
+                queries::ORDERS,
+                json!({ "first": page_size(options), "after": options.cursor, "query": build_order_query(filter) }),
+                "orders",
+            )
+            .await?;
+        Ok(page.into_list(to_order))
+    }
+
+    async fn get_order(&self, id: &str) -> Result<Order> {
+        let order: ShopifyOrder = self.query(queries::ORDER, json!({ "id": gid("Order", id) }), "order").await?;
+        Ok(to_order(order))
+    }
+
+    async fn create_order(&self, order: &Order) -> Result<Order> {
+        let line_items: Vec<Value> = order
+            .line_items
+            .iter()
+            .map(|item| match &item.variant_id {
+                Some(variant_id) => json!({
+                    "variantId": gid("ProductVariant", variant_id),
+                    "quantity": item.quantity,
+                }),
+                None => json!({
+                    "title": item.title,
+                    "quantity": item.quantity,
+                    "sku": item.sku,
+                    "priceSet": { "shopMoney": { "amount": item.price.to_string(), "currencyCode": order.currency } },
+                }),
+            })
+            .collect();
+
+        let mut input = Map::new();
+        input.insert("lineItems".to_string(), json!(line_items));
+        input.insert("currency".to_string(), json!(order.currency));
+        input.insert("financialStatus".to_string(), json!(financial_status_value(order.financial_status)));
+        if let Some(email) = &order.email {
+            input.insert("email".to_string(), json!(email));
+        }
+        if let Some(phone) = &order.phone {
+            input.insert("phone".to_string(), json!(phone));
+        }
+        if let Some(note) = &order.note {
+            input.insert("note".to_string(), json!(note));
+        }
+        if let Some(address) = &order.shipping_address {
+            input.insert("shippingAddress".to_string(), address_input(address));
+        }
+        if let Some(address) = &order.billing_address {
+            input.insert("billingAddress".to_string(), address_input(address));
+        }
+        if let Some(customer) = order.customer.as_ref().filter(|c| !c.id.is_empty()) {
+            input.insert(
+                "customer".to_string(),
+                json!({ "toAssociate": { "id": gid("Customer", &customer.id) } }),
+            );
+        }
+
+        let payload = self.mutate(queries::ORDER_CREATE, json!({ "order": input }), "orderCreate").await?;
+        let id = created_id(&payload, "order")?;
+        self.get_order(&id).await
+    }
+
+    async fn update_order(&self, id: &str, order: &Order) -> Result<Order> {
+        let mut input = Map::new();
+        input.insert("id".to_string(), json!(gid("Order", id)));
+        if let Some(email) = &order.email {
+            input.insert("email".to_string(), json!(email));
+        }
+        if let Some(note) = &order.note {
+            input.insert("note".to_string(), json!(note));
+        }
+        if let Some(address) = &order.shipping_address {
+            input.insert("shippingAddress".to_string(), address_input(address));
+        }
+        self.mutate(queries::ORDER_UPDATE, json!({ "input": input }), "orderUpdate")
+            .await?;
+        self.get_order(id).await
+    }
+
+    async fn cancel_order(&self, id: &str) -> Result<Order> {
+        self.mutate(queries::ORDER_CANCEL, json!({ "orderId": gid("Order", id) }), "orderCancel")
+            .await?;
+        self.get_order(id).await
+    }
+
+    async fn list_customers(&self, options: &ListOptions) -> Result<ListResult<Customer>> {
+        let page: Connection<ShopifyCustomer> = self
+            .query(
+                queries::CUSTOMERS,
+                json!({ "first": page_size(options), "after": options.cursor }),
+                "customers",
+            )
+            .await?;
+        Ok(page.into_list(to_customer))
+    }
+
+    async fn get_customer(&self, id: &str) -> Result<Customer> {
+        let customer: ShopifyCustomer = self
+            .query(queries::CUSTOMER, json!({ "id": gid("Customer", id) }), "customer")
+            .await?;
+        Ok(to_customer(customer))
+    }
+
+    async fn create_customer(&self, customer: &Customer) -> Result<Customer> {
+        let input = customer_input(customer);
+        let payload = self
+            .mutate(queries::CUSTOMER_CREATE, json!({ "input": input }), "customerCreate")
+            .await?;
+        let id = created_id(&payload, "customer")?;
+        self.get_customer(&id).await
+    }
+
+    async fn update_customer(&self, id: &str, customer: &Customer) -> Result<Customer> {
+        let mut input = customer_input(customer);
+        input.insert("id".to_string(), json!(gid("Customer", id)));
+        self.mutate(queries::CUSTOMER_UPDATE, json!({ "input": input }), "customerUpdate")
+            .await?;
+        self.get_customer(id).await
+    }
+
+    async fn delete_customer(&self, id: &str) -> Result<()> {
+        self.mutate(
+            queries::CUSTOMER_DELETE,
+            json!({ "input": { "id": gid("Customer", id) } }),
+            "customerDelete",
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl InventoryProvider for ShopifyClient {
+    async fn get_inventory(&self, item_id: &str, location_id: Option<&str>) -> Result<InventoryItem> {
+        let levels = self.inventory_levels(item_id).await?;
+        match location_id {
+            Some(location) => {
+                let location = legacy_id(location);
+                levels
+                    .into_iter()
+                    .find(|l| l.location_id.as_deref() == Some(location.as_str()))
+                    .ok_or_else(|| Error::NotFound(format!("Inventory item {} is not stocked at location {}", item_id, location)))
+            }
+            None => {
+                let sku = levels.first().and_then(|l| l.sku.clone());
+                Ok(InventoryItem {
+                    id: legacy_id(item_id),
+                    sku,
+                    inventory_quantity: levels.iter().map(|l| l.inventory_quantity).sum(),
+                    location_id: None,
+                })
+            }
+        }
+    }
+
+    async fn adjust_inventory(&self, item_id: &str, location_id: &str, adjustment: i32) -> Result<InventoryItem> {
+        let input = json!({
+            "reason": "correction",
+            "name": "available",
+            "changes": [{
+                "delta": adjustment,
+                "inventoryItemId": gid("InventoryItem", item_id),
+                "locationId": gid("Location", location_id),
+            }],
+        });
+        self.mutate(queries::INVENTORY_ADJUST, json!({ "input": input }), "inventoryAdjustQuantities")
+            .await?;
+        self.get_inventory(item_id, Some(location_id)).await
+    }
+
+    async fn set_inventory(&self, item_id: &str, location_id: &str, quantity: i32) -> Result<InventoryItem> {
+        let input = json!({
+            "reason": "correction",
+            "name": "available",
+            "ignoreCompareQuantity": true,
+            "quantities": [{
+                "quantity": quantity,
+                "inventoryItemId": gid("InventoryItem", item_id),
+                "locationId": gid("Location", location_id),
+            }],
+        });
+        self.mutate(queries::INVENTORY_SET, json!({ "input": input }), "inventorySetQuantities")
+            .await?;
+        self.get_inventory(item_id, Some(location_id)).await
+    }
+}
+
+#[async_trait]
+impl CollectionProvider for ShopifyClient {
+    async fn list_collections(&self, options: &ListOptions) -> Result<ListResult<Collection>> {
+        let page: Connection<ShopifyCollection> = self
+            .query(
+                queries::COLLECTIONS,
+                json!({ "first": page_size(options), "after": options.cursor }),
+                "collections",
+            )
+            .await?;
+        Ok(page.into_list(to_collection))
+    }
+
+    async fn get_collection(&self, id: &str) -> Result<Collection> {
+        let collection: ShopifyCollection = self
+            .query(queries::COLLECTION, json!({ "id": gid("Collection", id) }), "collection")
+            .await?;
+        Ok(to_collection(collection))
+    }
+
+    async fn create_collection(&self, title: &str, description: Option<&str>) -> Result<Collection> {
+        let input = json!({ "title": title, "descriptionHtml": description });
+        let payload = self.mutate(queries::COLLECTION_CREATE, json!({ "input": input }), "collectionCreate").await?;
+        Ok(to_collection(serde_json::from_value(payload["collection"].clone())?))
+    }
+
+    async fn add_products_to_collection(&self, collection_id: &str, product_ids: &[&str]) -> Result<()> {
+        let product_ids: Vec<String> = product_ids.iter().map(|id| gid("Product", id)).collect();
+        self.mutate(
+            queries::COLLECTION_ADD_PRODUCTS,
+            json!({ "id": gid("Collection", collection_id), "productIds": product_ids }),
+            "collectionAddProducts",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn remove_products_from_collection(&self, collection_id: &str, product_ids: &[&str]) -> Result<()> {
+        let product_ids: Vec<String> = product_ids.iter().map(|id| gid("Product", id)).collect();
+        self.mutate(
+            queries::COLLECTION_REMOVE_PRODUCTS,
+            json!({ "id": gid("Collection", collection_id), "productIds": product_ids }),
+            "collectionRemoveProducts",
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+fn normalize_shop(shop: &str) -> String {
+    let shop = shop
+        .trim()
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .trim_end_matches('/');
+    if shop.contains('.') {
+        shop.to_string()
+    } else {
+        format!("{}.myshopify.com", shop)
+    }
+}
+
+pub fn gid(resource: &str, id: &str) -> String {
+    if id.starts_with("gid://") {
+        id.to_string()
+    } else {
+        format!("gid://shopify/{}/{}", resource, id)
+    }
+}
+
+pub fn legacy_id(gid: &str) -> String {
+    let id = gid.split('?').next().unwrap_or(gid);
+    id.rsplit('/').next().unwrap_or(id).to_string()
+}
+
+fn page_size(options: &ListOptions) -> u32 {
+    options.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE)
+}
+
+fn search_value(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn build_product_query(filter: &ProductFilter) -> Option<String> {
+    let mut terms = Vec::new();
+    if let Some(status) = filter.status {
+        let status = match status {
+            ProductStatus::Active => "active",
+            ProductStatus::Draft => "draft",
+            ProductStatus::Archived => "archived",
+        };
+        terms.push(format!("status:{}", status));
+    }
+    if let Some(vendor) = &filter.vendor {
+        terms.push(format!("vendor:{}", search_value(vendor)));
+    }
+    if let Some(product_type) = &filter.product_type {
+        terms.push(format!("product_type:{}", search_value(product_type)));
+    }
+    if !filter.ids.is_empty() {
+        let ids: Vec<String> = filter.ids.iter().map(|id| format!("id:{}", legacy_id(id))).collect();
+        terms.push(format!("({})", ids.join(" OR ")));
+    }
+    (!terms.is_empty()).then(|| terms.join(" AND "))
+}
+
+fn build_order_query(filter: &OrderFilter) -> Option<String> {
+    let mut terms = Vec::new();
+    if let Some(status) = filter.status {
+        let status = match status {
+            OrderStatus::Open => "open",
+            OrderStatus::Closed => "closed",
+            OrderStatus::Cancelled => "cancelled",
+        };
+        terms.push(format!("status:{}", status));
+    }
+    if let Some(status) = filter.financial_status {
+        let status = serde_json::to_value(status).ok().and_then(|v| v.as_str().map(str::to_string));
+        if let Some(status) = status {
+            terms.push(format!("financial_status:{}", status));
+        }
+    }
+    if let Some(status) = filter.fulfillment_status {
+        let status = match status {
+            FulfillmentStatus::Unfulfilled => "unshipped",
+            FulfillmentStatus::Partial => "partial",
+            FulfillmentStatus::Fulfilled => "shipped",
+        };
+        terms.push(format!("fulfillment_status:{}", status));
+    }
+    if let Some(min) = filter.created_at_min {
+        terms.push(format!("created_at:>='{}'", min.to_rfc3339()));
+    }
+    if let Some(max) = filter.created_at_max {
+        terms.push(format!("created_at:<='{}'", max.to_rfc3339()));
+    }
+    (!terms.is_empty()).then(|| terms.join(" AND "))
+}
+
+fn check_user_errors(payload: &Value) -> Result<()> {
+    let Some(object) = payload.as_object() else {
+        return Ok(());
+    };
+    let messages: Vec<String> = object
+        .iter()
+        .filter(|(key, _)| *key == "userErrors" || key.ends_with("UserErrors"))
+        .filter_map(|(_, errors)| errors.as_array())
+        .flatten()
+        .map(|error| {
+            let message = error["message"].as_str().unwrap_or("Unknown error");
+            match error["field"].as_array() {
+                Some(field) if !field.is_empty() => {
+                    let field: Vec<&str> = field.iter().filter_map(Value::as_str).collect();
+                    format!("{}: {}", field.join("."), message)
+                }
+                _ => message.to_string(),
+            }
+        })
+        .collect();
+    if messages.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::Validation(messages.join("; ")))
+    }
+}
+
+fn created_id(payload: &Value, field: &str) -> Result<String> {
+    payload[field]["id"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| Error::Api {
+            message: format!("Shopify did not return the created {}", field),
+            code: None,
+        })
+}
+
+fn graphql_error(errors: Vec<GraphQlError>) -> Error {
+    let code = errors.iter().find_map(|e| e.code().map(str::to_string));
+    let message = errors.into_iter().map(|e| e.message).collect::<Vec<_>>().join("; ");
+    match code.as_deref() {
+        Some("ACCESS_DENIED") | Some("UNAUTHORIZED") => Error::Auth(message),
+        Some("NOT_FOUND") => Error::NotFound(message),
+        _ => Error::Api { message, code },
+    }
+}
+
+fn product_input(product: &Product) -> Map<String, Value> {
+    let mut input = Map::new();
+    input.insert("title".to_string(), json!(product.title));
+    if let Some(description) = &product.description {
+        input.insert("descriptionHtml".to_string(), json!(description));
+    }
+    if let Some(handle) = &product.handle {
+        input.insert("handle".to_string(), json!(handle));
+    }
+    let status = match product.status {
+        ProductStatus::Active => "ACTIVE",
+        ProductStatus::Draft => "DRAFT",
+        ProductStatus::Archived => "ARCHIVED",
+    };
+    input.insert("status".to_string(), json!(status));
+    if let Some(vendor) = &product.vendor {
+        input.insert("vendor".to_string(), json!(vendor));
+    }
+    if let Some(product_type) = &product.product_type {
+        input.insert("productType".to_string(), json!(product_type));
+    }
+    input.insert("tags".to_string(), json!(product.tags));
+    input
+}
+
+fn variant_input(variant: &ProductVariant, options: &[ProductOption]) -> Map<String, Value> {
+    let mut input = Map::new();
+    input.insert("price".to_string(), json!(variant.price.to_string()));
+    if let Some(compare_at) = variant.compare_at_price {
+        input.insert("compareAtPrice".to_string(), json!(compare_at.to_string()));
+    }
+    if let Some(barcode) = &variant.barcode {
+        input.insert("barcode".to_string(), json!(barcode));
+    }
+
+    let option_values: Vec<Value> = options
+        .iter()
+        .filter_map(|o| {
+            variant
+                .options
+                .get(&o.name)
+                .map(|value| json!({ "optionName": o.name, "name": value }))
+        })
+        .collect();
+    if !option_values.is_empty() {
+        input.insert("optionValues".to_string(), json!(option_values));
+    }
+
+    let mut inventory_item = Map::new();
+    if let Some(sku) = &variant.sku {
+        inventory_item.insert("sku".to_string(), json!(sku));
+    }
+    if let Some(weight) = variant.weight {
+        let unit = weight_unit(variant.weight_unit.as_deref().unwrap_or("kg"));
+        inventory_item.insert(
+            "measurement".to_string(),
+            json!({ "weight": { "value": weight, "unit": unit } }),
+        );
+    }
+    if !inventory_item.is_empty() {
+        input.insert("inventoryItem".to_string(), Value::Object(inventory_item));
+    }
+    input
+}
+
+fn weight_unit(unit: &str) -> &'static str {
+    match unit.to_lowercase().as_str() {
+        "g" | "gram" | "grams" => "GRAMS",
+        "oz" | "ounce" | "ounces" => "OUNCES",
+        "lb" | "lbs" | "pound" | "pounds" => "POUNDS",
+        _ => "KILOGRAMS",
+    }
+}
+
+fn weight_unit_name(unit: &str) -> &'static str {
+    match unit {
+        "GRAMS" => "g",
+        "OUNCES" => "oz",
+        "POUNDS" => "lb",
+        _ => "kg",
+    }
+}
+
+fn address_input(address: &Address) -> Value {
+    json!({
+        "firstName": address.first_name,
+        "lastName": address.last_name,
+        "company": address.company,
+        "address1": address.address1,
+        "address2": address.address2,
+        "city": address.city,
+        "provinceCode": address.province_code,
+        "countryCode": address.country_code,
+        "zip": address.zip,
+        "phone": address.phone,
+    })
+}
+
+fn customer_input(customer: &Customer) -> Map<String, Value> {
+    let mut input = Map::new();
+    if let Some(email) = &customer.email {
+        input.insert("email".to_string(), json!(email));
+    }
+    if let Some(first_name) = &customer.first_name {
+        input.insert("firstName".to_string(), json!(first_name));
+    }
+    if let Some(last_name) = &customer.last_name {
+        input.insert("lastName".to_string(), json!(last_name));
+    }
+    if let Some(phone) = &customer.phone {
+        input.insert("phone".to_string(), json!(phone));
+    }
+    if !customer.tags.is_empty() {
+        input.insert("tags".to_string(), json!(customer.tags));
+    }
+    if !customer.addresses.is_empty() {
+        let addresses: Vec<Value> = customer.addresses.iter().map(address_input).collect();
+        input.insert("addresses".to_string(), json!(addresses));
+    }
+    input
+}
+
+fn financial_status_value(status: FinancialStatus) -> &'static str {
+    match status {
+        FinancialStatus::Pending => "PENDING",
+        FinancialStatus::Authorized => "AUTHORIZED",
+        FinancialStatus::PartiallyPaid => "PARTIALLY_PAID",
+        FinancialStatus::Paid => "PAID",
+        FinancialStatus::PartiallyRefunded => "PARTIALLY_REFUNDED",
+        FinancialStatus::Refunded => "REFUNDED",
+        // Shopify has no failed payment state; an order that never got paid is
+        // the closest match.
+        FinancialStatus::Voided | FinancialStatus::Failed => "VOIDED",
+    }
+}
+
+fn to_product(product: ShopifyProduct) -> Product {
+    let mut extra = HashMap::new();
+    extra.insert("admin_graphql_api_id".to_string(), json!(product.id));
+    let inventory_items: Map<String, Value> = product
+        .variants
+        .nodes
+        .iter()
+        .filter_map(|v| {
+            v.inventory_item
+                .as_ref()
+                .map(|item| (legacy_id(&v.id), json!(legacy_id(&item.id))))
+        })
+        .collect();
+    if !inventory_items.is_empty() {
+        extra.insert("inventory_item_ids".to_string(), Value::Object(inventory_items));
+    }
+
+    Product {
+        id: legacy_id(&product.id),
+        title: product.title,
+        description: product.description_html.filter(|d| !d.is_empty()),
+        handle: product.handle,
+        status: match product.status.as_str() {
+            "ACTIVE" | "UNLISTED" => ProductStatus::Active,
+            "ARCHIVED" => ProductStatus::Archived,
+            _ => ProductStatus::Draft,
+        },
+        vendor: product.vendor.filter(|v| !v.is_empty()),
+        product_type: product.product_type.filter(|t| !t.is_empty()),
+        tags: product.tags,
+        variants: product.variants.nodes.into_iter().map(to_variant).collect(),
+        images: product
+            .media
+            .nodes
+            .into_iter()
+            .filter_map(|m| Some((m.id?, m.alt, m.image?)))
+            .enumerate()
+            .map(|(position, (id, alt, image))| ProductImage {
+                id: legacy_id(&id),
+                src: image.url,
+                alt: alt.filter(|a| !a.is_empty()),
+                position: Some(position as i32 + 1),
+            })
+            .collect(),
+        options: product
+            .options
+            .into_iter()
+            .map(|o| ProductOption {
+                name: o.name,
+                values: o.option_values.into_iter().map(|v| v.name).collect(),
+            })
+            .collect(),
+        created_at: product.created_at,
+        updated_at: product.updated_at,
+        extra,
+    }
+}
+
+fn to_variant(variant: ShopifyVariant) -> ProductVariant {
+    let weight = variant
+        .inventory_item
+        .and_then(|item| item.measurement)
+        .and_then(|m| m.weight);
+    ProductVariant {
+        id: legacy_id(&variant.id),
+        title: variant.title,
+        sku: variant.sku.filter(|s| !s.is_empty()),
+        price: variant.price,
+        compare_at_price: variant.compare_at_price,
+        inventory_quantity: variant.inventory_quantity,
+        weight: weight.as_ref().map(|w| w.value),
+        weight_unit: weight.as_ref().map(|w| weight_unit_name(&w.unit).to_string()),
+        options: variant
+            .selected_options
+            .into_iter()
+            .map(|o| (o.name, o.value))
+            .collect(),
+        barcode: variant.barcode.filter(|b| !b.is_empty()),
+    }
+}
+
+fn to_order(order: ShopifyOrder) -> Order {
+    let mut extra = HashMap::new();
+    extra.insert("admin_graphql_api_id".to_string(), json!(order.id));
+    if !order.tags.is_empty() {
+        extra.insert("tags".to_string(), json!(order.tags));
+    }
+
+    let status = if order.cancelled_at.is_some() {
+        OrderStatus::Cancelled
+    } else if order.closed {
+        OrderStatus::Closed
+    } else {
+        OrderStatus::Open
+    };
+
+    Order {
+        id: legacy_id(&order.id),
+        order_number: order.name,
+        email: order.email,
+        phone: order.phone,
+        status,
+        financial_status: match order.display_financial_status.as_deref() {
+            Some("AUTHORIZED") => FinancialStatus::Authorized,
+            Some("PARTIALLY_PAID") => FinancialStatus::PartiallyPaid,
+            Some("PAID") => FinancialStatus::Paid,
+            Some("PARTIALLY_REFUNDED") => FinancialStatus::PartiallyRefunded,
+            Some("REFUNDED") => FinancialStatus::Refunded,
+            Some("VOIDED") | Some("EXPIRED") => FinancialStatus::Voided,
+            _ => FinancialStatus::Pending,
+        },
+        fulfillment_status: order.display_fulfillment_status.as_deref().map(|s| match s {
+            "FULFILLED" => FulfillmentStatus::Fulfilled,
+            "PARTIALLY_FULFILLED" => FulfillmentStatus::Partial,
+            _ => FulfillmentStatus::Unfulfilled,
+        }),
+        currency: order.currency_code,
+        subtotal_price: order.subtotal_price_set.map(|m| m.shop_money.amount).unwrap_or_default(),
+        total_tax: order.total_tax_set.map(|m| m.shop_money.amount).unwrap_or_default(),
+        total_discounts: order.total_discounts_set.map(|m| m.shop_money.amount).unwrap_or_default(),
+        total_price: order.total_price_set.shop_money.amount,
+        line_items: order
+            .line_items
+            .nodes
+            .into_iter()
+            .map(|item| LineItem {
+                id: legacy_id(&item.id),
+                product_id: item.product.map(|p| legacy_id(&p.id)),
+                variant_id: item.variant.map(|v| legacy_id(&v.id)),
+                title: item.title,
+                quantity: item.quantity,
+                price: item.original_unit_price_set.shop_money.amount,
+                sku: item.sku.filter(|s| !s.is_empty()),
+                total_discount: item.total_discount_set.map(|m| m.shop_money.amount),
+            })
+            .collect(),
+        shipping_address: order.shipping_address.map(to_address),
+        billing_address: order.billing_address.map(to_address),
+        customer: order.customer.map(to_customer),
+        note: order.note,
+        created_at: order.created_at,
+        updated_at: order.updated_at,
+        extra,
+    }
+}
+
+fn to_address(address: ShopifyAddress) -> Address {
+    Address {
+        first_name: address.first_name,
+        last_name: address.last_name,
+        company: address.company,
+        address1: address.address1,
+        address2: address.address2,
+        city: address.city,
+        province: address.province,
+        province_code: address.province_code,
+        country: address.country,
+        country_code: address.country_code_v2,
+        zip: address.zip,
+        phone: address.phone,
+    }
+}
+
+fn to_customer(customer: ShopifyCustomer) -> Customer {
+    Customer {
+        id: legacy_id(&customer.id),
+        email: customer.email,
+        first_name: customer.first_name,
+        last_name: customer.last_name,
+        phone: customer.phone,
+        orders_count: customer.number_of_orders,
+        total_spent: customer.amount_spent.map(|m| m.amount),
+        tags: customer.tags,
+        addresses: customer.addresses.into_iter().map(to_address).collect(),
+        created_at: customer.created_at,
+        updated_at: customer.updated_at,
+    }
+}
+
+fn to_collection(collection: ShopifyCollection) -> Collection {
+    Collection {
+        id: legacy_id(&collection.id),
+        title: collection.title,
+        description: collection.description_html.filter(|d| !d.is_empty()),
+        handle: collection.handle,
+        image: collection.image.map(|image| ProductImage {
+            id: image.id.as_deref().map(legacy_id).unwrap_or_default(),
+            src: image.url,
+            alt: image.alt_text,
+            position: None,
+        }),
+        products_count: collection.products_count.map(|c| c.count),
+    }
+}
+
+fn money<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<f64, D::Error> {
+    Ok(optional_money(deserializer)?.unwrap_or_default())
+}
+
+fn optional_money<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Option<f64>, D::Error> {
+    Ok(match Option::<Value>::deserialize(deserializer)? {
+        Some(Value::String(s)) => s.parse().ok(),
+        Some(Value::Number(n)) => n.as_f64(),
+        _ => None,
+    })
+}
+
+fn optional_count<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Option<u32>, D::Error> {
+    Ok(match Option::<Value>::deserialize(deserializer)? {
+        Some(Value::String(s)) => s.parse().ok(),
+        Some(Value::Number(n)) => n.as_u64().map(|n| n as u32),
+        _ => None,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlResponse {
+    data: Option<Value>,
+    #[serde(default)]
+    errors: Vec<GraphQlError>,
+    extensions: Option<GraphQlExtensions>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlError {
+    message: String,
+    extensions: Option<Value>,
+}
+
+impl GraphQlError {
+    fn code(&self) -> Option<&str> {
+        self.extensions.as_ref()?.get("code")?.as_str()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlExtensions {
+    cost: Option<QueryCost>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PageInfo {
+    has_next_page: bool,
+    end_cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Connection<T> {
+    nodes: Vec<T>,
+    page_info: PageInfo,
+}
+
+impl<T> Connection<T> {
+    fn into_list<U>(self, f: impl FnMut(T) -> U) -> ListResult<U> {
+        ListResult {
+            items: self.nodes.into_iter().map(f).collect(),
+            has_more: self.page_info.has_next_page,
+            next_cursor: self.page_info.end_cursor.filter(|_| self.page_info.has_next_page),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Nodes<T> {
+    #[serde(default = "Vec::new")]
+    nodes: Vec<T>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ShopifyId {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ShopifyMoneyBag {
+    #[serde(rename = "shopMoney")]
+    shop_money: ShopifyMoney,
+}
+
+#[derive(Debug, Deserialize)]
+struct ShopifyMoney {
+    #[serde(deserialize_with = "money")]
+    amount: f64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ShopifyProduct {
+    id: String,
+    title: String,
+    description_html: Option<String>,
+    handle: Option<String>,
+    status: String,
+    vendor: Option<String>,
+    product_type: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    created_at: Option<DateTime<Utc>>,
+    updated_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    options: Vec<ShopifyOption>,
+    variants: Nodes<ShopifyVariant>,
+    media: Nodes<ShopifyMedia>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ShopifyOption {
+    name: String,
+    #[serde(default)]
+    option_values: Vec<ShopifyOptionValue>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ShopifyOptionValue {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ShopifyVariant {
+    id: String,
+    title: String,
+    sku: Option<String>,
+    #[serde(deserialize_with = "money")]
+    price: f64,
+    #[serde(default, deserialize_with = "optional_money")]
+    compare_at_price: Option<f64>,
+    inventory_quantity: Option<i32>,
+    barcode: Option<String>,
+    #[serde(default)]
+    selected_options: Vec<ShopifySelectedOption>,
+    inventory_item: Option<ShopifyVariantInventoryItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ShopifySelectedOption {
+    name: String,
+    value: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ShopifyVariantInventoryItem {
+    id: String,
+    measurement: Option<ShopifyMeasurement>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ShopifyMeasurement {
+    weight: Option<ShopifyWeight>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ShopifyWeight {
+    unit: String,
+    value: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct ShopifyMedia {
+    id: Option<String>,
+    alt: Option<String>,
+    image: Option<ShopifyImageUrl>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ShopifyImageUrl {
+    url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ShopifyCreatedProduct {
+    id: String,
+    variants: Nodes<ShopifyId>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ShopifyCollectionProducts {
+    products: Connection<ShopifyProduct>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ShopifyOrder {
+    id: String,
+    name: Option<String>,
+    email: Option<String>,
+    phone: Option<String>,
+    #[serde(default)]
+    closed: bool,
+    cancelled_at: Option<DateTime<Utc>>,
+    display_financial_status: Option<String>,
+    display_fulfillment_status: Option<String>,
+    currency_code: String,
+    note: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    created_at: DateTime<Utc>,
+    updated_at: Option<DateTime<Utc>>,
+    subtotal_price_set: Option<ShopifyMoneyBag>,
+    total_tax_set: Option<ShopifyMoneyBag>,
+    total_discounts_set: Option<ShopifyMoneyBag>,
+    total_price_set: ShopifyMoneyBag,
+    line_items: Nodes<ShopifyLineItem>,
+    shipping_address: Option<ShopifyAddress>,
+    billing_address: Option<ShopifyAddress>,
+    customer: Option<ShopifyCustomer>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ShopifyLineItem {
+    id: String,
+    title: String,
+    quantity: u32,
+    sku: Option<String>,
+    original_unit_price_set: ShopifyMoneyBag,
+    total_discount_set: Option<ShopifyMoneyBag>,
+    product: Option<ShopifyId>,
+    variant: Option<ShopifyId>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ShopifyAddress {
+    first_name: Option<String>,
+    last_name: Option<String>,
+    company: Option<String>,
+    address1: Option<String>,
+    address2: Option<String>,
+    city: Option<String>,
+    province: Option<String>,
+    province_code: Option<String>,
+    country: Option<String>,
+    country_code_v2: Option<String>,
+    zip: Option<String>,
+    phone: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ShopifyCustomer {
+    id: String,
+    email: Option<String>,
+    first_name: Option<String>,
+    last_name: Option<String>,
+    phone: Option<String>,
+    #[serde(default, deserialize_with = "optional_count")]
+    number_of_orders: Option<u32>,
+    amount_spent: Option<ShopifyMoney>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    addresses: Vec<ShopifyAddress>,
+    created_at: Option<DateTime<Utc>>,
+    updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ShopifyInventoryItem {
+    id: String,
+    sku: Option<String>,
+    inventory_levels: Nodes<ShopifyInventoryLevel>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ShopifyInventoryLevel {
+    location: ShopifyId,
+    #[serde(default)]
+    quantities: Vec<ShopifyQuantity>,
+}
+
+impl ShopifyInventoryLevel {
+    fn available(&self) -> i32 {
+        self.quantities
+            .iter()
+            .find(|q| q.name == "available")
+            .map(|q| q.quantity)
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ShopifyQuantity {
+    name: String,
+    quantity: i32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ShopifyLocation {
+    id: String,
+    name: String,
+    #[serde(default)]
+    is_active: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ShopifyCollection {
+    id: String,
+    title: String,
+    description_html: Option<String>,
+    handle: Option<String>,
+    image: Option<ShopifyCollectionImage>,
+    products_count: Option<ShopifyCount>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ShopifyCollectionImage {
+    id: Option<String>,
+    url: String,
+    alt_text: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ShopifyCount {
+    count: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ids_and_search_queries() {
+        assert_eq!(gid("Product", "42"), "gid://shopify/Product/42");
+        assert_eq!(gid("Product", "gid://shopify/Product/42"), "gid://shopify/Product/42");
+        assert_eq!(legacy_id("gid://shopify/MediaImage/7?v=1"), "7");
+        assert_eq!(normalize_shop("https://acme/"), "acme.myshopify.com");
+
+        let filter = ProductFilter {
+            status: Some(ProductStatus::Active),
+            vendor: Some("Acme \"Co\"".to_string()),
+            ids: vec!["1".to_string(), "gid://shopify/Product/2".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            build_product_query(&filter).unwrap(),
+            r#"status:active AND vendor:"Acme \"Co\"" AND (id:1 OR id:2)"#
+        );
+
+        let orders = OrderFilter {
+            financial_status: Some(FinancialStatus::PartiallyPaid),
+            fulfillment_status: Some(FulfillmentStatus::Unfulfilled),
+            ..Default::default()
+        };
+        assert_eq!(
+            build_order_query(&orders).unwrap(),
+            "financial_status:partially_paid AND fulfillment_status:unshipped"
+        );
+    }
+
+    #[test]
+    fn test_product_maps_variants_and_options() {
+        let product: ShopifyProduct = serde_json::from_value(json!({
+            "id": "gid://shopify/Product/10",
+            "title": "Tee",
+            "descriptionHtml": "<p>Soft</p>",
+            "handle": "tee",
+            "status": "ACTIVE",
+            "vendor": "Acme",
+            "productType": "",
+            "tags": ["summer"],
+            "options": [{ "name": "Size", "optionValues": [{ "name": "S" }, { "name": "M" }] }],
+            "variants": { "nodes": [{
+                "id": "gid://shopify/ProductVariant/11",
+                "title": "S",
+                "sku": "TEE-S",
+                "price": "19.99",
+                "compareAtPrice": null,
+                "inventoryQuantity": 4,
+                "barcode": "",
+                "selectedOptions": [{ "name": "Size", "value": "S" }],
+                "inventoryItem": { "id": "gid://shopify/InventoryItem/12", "measurement": { "weight": { "unit": "GRAMS", "value": 180.0 } } }
+            }] },
+            "media": { "nodes": [{}, { "id": "gid://shopify/MediaImage/13", "alt": "", "image": { "url": "https://cdn/tee.png" } }] }
+        }))
+        .unwrap();
+
+        let product = to_product(product);
+        assert_eq!(product.id, "10");
+        assert_eq!(product.status, ProductStatus::Active);
+        assert_eq!(product.product_type, None);
+        assert_eq!(product.options[0].values, vec!["S", "M"]);
+        let variant = &product.variants[0];
+        assert_eq!(variant.price, 19.99);
+        assert_eq!(variant.options["Size"], "S");
+        assert_eq!(variant.weight_unit.as_deref(), Some("g"));
+        assert_eq!(variant.barcode, None);
+        assert_eq!(product.images.len(), 1);
+        assert_eq!(product.images[0].position, Some(1));
+        assert_eq!(product.extra["inventory_item_ids"]["11"], "12");
+
+        let input = variant_input(&product.variants[0], &product.options);
+        assert_eq!(input["optionValues"], json!([{ "optionName": "Size", "name": "S" }]));
+        assert_eq!(input["inventoryItem"]["measurement"]["weight"]["unit"], "GRAMS");
+    }
+
+    #[test]
+    fn test_user_errors_become_validation_errors() {
+        let payload = json!({
+            "product": null,
+            "userErrors": [{ "field": ["product", "title"], "message": "can't be blank" }]
+        });
+        match check_user_errors(&payload) {
+            Err(Error::Validation(message)) => assert_eq!(message, "product.title: can't be blank"),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(check_user_errors(&json!({ "job": { "id": "1" }, "orderCancelUserErrors": [] })).is_ok());
+    }
+}
@@ -0,0 +1,243 @@
+macro_rules! product_fields {
+    () => {
+        r#"
+fragment ProductFields on Product {
+  id title descriptionHtml handle status vendor productType tags createdAt updatedAt
+  options { name optionValues { name } }
+  variants(first: 100) {
+    nodes {
+      id title sku price compareAtPrice inventoryQuantity barcode
+      selectedOptions { name value }
+      inventoryItem { id measurement { weight { unit value } } }
+    }
+  }
+  media(first: 50) { nodes { ... on MediaImage { id alt image { url } } } }
+}"#
+    };
+}
+
+macro_rules! address_fields {
+    () => {
+        r#"
+fragment AddressFields on MailingAddress {
+  firstName lastName company address1 address2 city province provinceCode country countryCodeV2 zip phone
+}"#
+    };
+}
+
+macro_rules! customer_fields {
+    () => {
+        r#"
+fragment CustomerFields on Customer {
+  id email firstName lastName phone numberOfOrders amountSpent { amount } tags createdAt updatedAt
+  addresses { ...AddressFields }
+}"#
+    };
+}
+
+macro_rules! order_fields {
+    () => {
+        r#"
+fragment OrderFields on Order {
+  id name email phone closed cancelledAt displayFinancialStatus displayFulfillmentStatus currencyCode note tags
+  createdAt updatedAt
+  subtotalPriceSet { shopMoney { amount } }
+  totalTaxSet { shopMoney { amount } }
+  totalDiscountsSet { shopMoney { amount } }
+  totalPriceSet { shopMoney { amount } }
+  lineItems(first: 250) {
+    nodes {
+      id title quantity sku
+      originalUnitPriceSet { shopMoney { amount } }
+      totalDiscountSet { shopMoney { amount } }
+      product { id }
+      variant { id }
+    }
+  }
+  shippingAddress { ...AddressFields }
+  billingAddress { ...AddressFields }
+  customer { ...CustomerFields }
+}"#
+    };
+}
+
+macro_rules! collection_fields {
+    () => {
+        r#"
+fragment CollectionFields on Collection {
+  id title descriptionHtml handle
+  image { id url altText }
+  productsCount { count }
+  ruleSet { appliedDisjunctively }
+}"#
+    };
+}
+
+macro_rules! page_info {
+    () => {
+        "pageInfo { hasNextPage endCursor }"
+    };
+}
+
+pub(crate) const PRODUCTS: &str = concat!(
+    "query Products($first: Int!, $after: String, $query: String) {
+  products(first: $first, after: $after, query: $query) { nodes { ...ProductFields } ",
+    page_info!(),
+    " }
+}",
+    product_fields!()
+);
+
+pub(crate) const COLLECTION_PRODUCTS: &str = concat!(
+    "query CollectionProducts($id: ID!, $first: Int!, $after: String) {
+  collection(id: $id) { products(first: $first, after: $after) { nodes { ...ProductFields } ",
+    page_info!(),
+    " } }
+}",
+    product_fields!()
+);
+
+pub(crate) const PRODUCT: &str = concat!(
+    "query Product($id: ID!) { product(id: $id) { ...ProductFields } }",
+    product_fields!()
+);
+
+pub(crate) const PRODUCT_CREATE: &str = "mutation ProductCreate($product: ProductCreateInput!, $media: [CreateMediaInput!]) {
+  productCreate(product: $product, media: $media) {
+    product { id variants(first: 1) { nodes { id } } }
+    userErrors { field message }
+  }
+}";
+
+pub(crate) const PRODUCT_UPDATE: &str = "mutation ProductUpdate($product: ProductUpdateInput!) {
+  productUpdate(product: $product) { product { id } userErrors { field message } }
+}";
+
+pub(crate) const PRODUCT_DELETE: &str = "mutation ProductDelete($input: ProductDeleteInput!) {
+  productDelete(input: $input) { deletedProductId userErrors { field message } }
+}";
+
+pub(crate) const VARIANTS_BULK_CREATE: &str = "mutation VariantsBulkCreate($productId: ID!, $variants: [ProductVariantsBulkInput!]!, $strategy: ProductVariantsBulkCreateStrategy) {
+  productVariantsBulkCreate(productId: $productId, variants: $variants, strategy: $strategy) {
+    productVariants { id }
+    userErrors { field message }
+  }
+}";
+
+pub(crate) const VARIANTS_BULK_UPDATE: &str = "mutation VariantsBulkUpdate($productId: ID!, $variants: [ProductVariantsBulkInput!]!) {
+  productVariantsBulkUpdate(productId: $productId, variants: $variants) {
+    productVariants { id }
+    userErrors { field message }
+  }
+}";
+
+pub(crate) const ORDERS: &str = concat!(
+    "query Orders($first: Int!, $after: String, $query: String) {
+  orders(first: $first, after: $after, query: $query, sortKey: CREATED_AT, reverse: true) { nodes { ...OrderFields } ",
+    page_info!(),
+    " }
+}",
+    order_fields!(),
+    customer_fields!(),
+    address_fields!()
+);
+
+pub(crate) const ORDER: &str = concat!(
+    "query Order($id: ID!) { order(id: $id) { ...OrderFields } }",
+    order_fields!(),
+    customer_fields!(),
+    address_fields!()
+);
+
+pub(crate) const ORDER_CREATE: &str = "mutation OrderCreate($order: OrderCreateOrderInput!) {
+  orderCreate(order: $order) { order { id } userErrors { field message } }
+}";
+
+pub(crate) const ORDER_UPDATE: &str = "mutation OrderUpdate($input: OrderInput!) {
+  orderUpdate(input: $input) { order { id } userErrors { field message } }
+}";
+
+pub(crate) const ORDER_CANCEL: &str = "mutation OrderCancel($orderId: ID!) {
+  orderCancel(orderId: $orderId, reason: OTHER, refund: false, restock: true, notifyCustomer: false) {
+    job { id }
+    orderCancelUserErrors { field message }
+  }
+}";
+
+pub(crate) const CUSTOMERS: &str = concat!(
+    "query Customers($first: Int!, $after: String) {
+  customers(first: $first, after: $after) { nodes { ...CustomerFields } ",
+    page_info!(),
+    " }
+}",
+    customer_fields!(),
+    address_fields!()
+);
+
+pub(crate) const CUSTOMER: &str = concat!(
+    "query Customer($id: ID!) { customer(id: $id) { ...CustomerFields } }",
+    customer_fields!(),
+    address_fields!()
+);
+
+pub(crate) const CUSTOMER_CREATE: &str = "mutation CustomerCreate($input: CustomerInput!) {
+  customerCreate(input: $input) { customer { id } userErrors { field message } }
+}";
+
+pub(crate) const CUSTOMER_UPDATE: &str = "mutation CustomerUpdate($input: CustomerInput!) {
+  customerUpdate(input: $input) { customer { id } userErrors { field message } }
+}";
+
+pub(crate) const CUSTOMER_DELETE: &str = "mutation CustomerDelete($input: CustomerDeleteInput!) {
+  customerDelete(input: $input) { deletedCustomerId userErrors { field message } }
+}";
+
+pub(crate) const INVENTORY_ITEM: &str = "query InventoryItem($id: ID!) {
+  inventoryItem(id: $id) {
+    id sku
+    inventoryLevels(first: 250) {
+      nodes { location { id } quantities(names: [\"available\"]) { name quantity } }
+    }
+  }
+}";
+
+pub(crate) const INVENTORY_ADJUST: &str = "mutation InventoryAdjust($input: InventoryAdjustQuantitiesInput!) {
+  inventoryAdjustQuantities(input: $input) { inventoryAdjustmentGroup { id } userErrors { field message } }
+}";
+
+pub(crate) const INVENTORY_SET: &str = "mutation InventorySet($input: InventorySetQuantitiesInput!) {
+  inventorySetQuantities(input: $input) { inventoryAdjustmentGroup { id } userErrors { field message } }
+}";
+
+pub(crate) const LOCATIONS: &str = "query Locations($first: Int!, $after: String) {
+  locations(first: $first, after: $after) { nodes { id name isActive } pageInfo { hasNextPage endCursor } }
+}";
+
+pub(crate) const COLLECTIONS: &str = concat!(
+    "query Collections($first: Int!, $after: String) {
+  collections(first: $first, after: $after) { nodes { ...CollectionFields } ",
+    page_info!(),
+    " }
+}",
+    collection_fields!()
+);
+
+pub(crate) const COLLECTION: &str = concat!(
+    "query Collection($id: ID!) { collection(id: $id) { ...CollectionFields } }",
+    collection_fields!()
+);
+
+pub(crate) const COLLECTION_CREATE: &str = concat!(
+    "mutation CollectionCreate($input: CollectionInput!) {
+  collectionCreate(input: $input) { collection { ...CollectionFields } userErrors { field message } }
+}",
+    collection_fields!()
+);
+
+pub(crate) const COLLECTION_ADD_PRODUCTS: &str = "mutation CollectionAddProducts($id: ID!, $productIds: [ID!]!) {
+  collectionAddProducts(id: $id, productIds: $productIds) { collection { id } userErrors { field message } }
+}";
+
+pub(crate) const COLLECTION_REMOVE_PRODUCTS: &str = "mutation CollectionRemoveProducts($id: ID!, $productIds: [ID!]!) {
+  collectionRemoveProducts(id: $id, productIds: $productIds) { job { id } userErrors { field message } }
+}";
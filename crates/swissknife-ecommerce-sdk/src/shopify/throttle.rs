@@ -0,0 +1,158 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+// Estimate for a query that has never been sent.
+const DEFAULT_QUERY_COST: f64 = 10.0;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct QueryCost {
+    pub requested_query_cost: Option<f64>,
+    pub actual_query_cost: Option<f64>,
+    pub throttle_status: Option<ThrottleStatus>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ThrottleStatus {
+    pub maximum_available: f64,
+    pub currently_available: f64,
+    pub restore_rate: f64,
+}
+
+// Tracks the shop's query cost bucket. Every request reserves its estimated
+// cost before it is sent and keeps it until the response arrives, so
+// concurrent callers queue behind each other instead of all spending the
+// same points.
+#[derive(Debug)]
+pub(crate) struct LeakyBucket {
+    status: Option<ThrottleStatus>,
+    updated_at: Instant,
+    reserved: f64,
+    costs: HashMap<String, f64>,
+}
+
+impl LeakyBucket {
+    pub fn new() -> Self {
+        Self {
+            status: None,
+            updated_at: Instant::now(),
+            reserved: 0.0,
+            costs: HashMap::new(),
+        }
+    }
+
+    // The cost Shopify last requested for this query text.
+    pub fn estimate(&self, query: &str) -> f64 {
+        self.costs.get(query).copied().unwrap_or(DEFAULT_QUERY_COST)
+    }
+
+    // Reserves `cost` points and returns the amount held along with how long
+    // to wait before sending. Release the amount once the request finishes.
+    pub fn reserve(&mut self, cost: f64, now: Instant) -> (f64, Duration) {
+        let cost = match self.status {
+            Some(status) => cost.clamp(0.0, status.maximum_available),
+            None => cost.max(0.0),
+        };
+        self.reserved += cost;
+        let (Some(status), Some(available)) = (self.status, self.available_at(now)) else {
+            return (cost, Duration::ZERO);
+        };
+        let deficit = self.reserved - available;
+        if deficit <= 0.0 || status.restore_rate <= 0.0 {
+            return (cost, Duration::ZERO);
+        }
+        (cost, Duration::from_secs_f64(deficit / status.restore_rate))
+    }
+
+    pub fn release(&mut self, cost: f64) {
+        self.reserved = (self.reserved - cost).max(0.0);
+    }
+
+    pub fn record(&mut self, query: &str, cost: &QueryCost) {
+        if let Some(requested) = cost.requested_query_cost.or(cost.actual_query_cost) {
+            self.costs.insert(query.to_string(), requested);
+        }
+        if let Some(status) = cost.throttle_status {
+            self.status = Some(status);
+            self.updated_at = Instant::now();
+        }
+    }
+
+    pub fn available_at(&self, now: Instant) -> Option<f64> {
+        self.status.map(|status| {
+            let elapsed = now.saturating_duration_since(self.updated_at).as_secs_f64();
+            (status.currently_available + status.restore_rate * elapsed).min(status.maximum_available)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cost(requested: f64, available: f64) -> QueryCost {
+        QueryCost {
+            requested_query_cost: Some(requested),
+            actual_query_cost: None,
+            throttle_status: Some(ThrottleStatus {
+                maximum_available: 1000.0,
+                currently_available: available,
+                restore_rate: 50.0,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_bucket_waits_for_restore() {
+        let mut bucket = LeakyBucket::new();
+        assert_eq!(bucket.reserve(100.0, Instant::now()), (100.0, Duration::ZERO));
+        bucket.release(100.0);
+
+        bucket.record("q", &cost(100.0, 900.0));
+        let (held, delay) = bucket.reserve(100.0, bucket.updated_at);
+        assert_eq!(delay, Duration::ZERO);
+        bucket.release(held);
+
+        bucket.record("q", &cost(100.0, 0.0));
+        let (held, delay) = bucket.reserve(100.0, bucket.updated_at);
+        assert_eq!(delay, Duration::from_secs(2));
+        bucket.release(held);
+        let later = bucket.updated_at + Duration::from_secs(1);
+        assert_eq!(bucket.reserve(100.0, later).1, Duration::from_secs(1));
+        assert_eq!(bucket.available_at(bucket.updated_at + Duration::from_secs(60)), Some(1000.0));
+    }
+
+    #[test]
+    fn test_concurrent_reservations_queue() {
+        let mut bucket = LeakyBucket::new();
+        bucket.record("q", &cost(100.0, 150.0));
+        let now = bucket.updated_at;
+
+        assert_eq!(bucket.reserve(100.0, now), (100.0, Duration::ZERO));
+        assert_eq!(bucket.reserve(100.0, now).1, Duration::from_secs(1));
+        assert_eq!(bucket.reserve(100.0, now).1, Duration::from_secs(3));
+
+        bucket.release(300.0);
+        assert_eq!(bucket.reserve(100.0, now).1, Duration::ZERO);
+    }
+
+    #[test]
+    fn test_estimates_are_per_query() {
+        let mut bucket = LeakyBucket::new();
+        assert_eq!(bucket.estimate("products"), DEFAULT_QUERY_COST);
+
+        bucket.record("products", &cost(252.0, 1000.0));
+        bucket.record("shop", &cost(1.0, 1000.0));
+        assert_eq!(bucket.estimate("products"), 252.0);
+        assert_eq!(bucket.estimate("shop"), 1.0);
+    }
+
+    #[test]
+    fn test_reservation_is_capped_at_bucket_size() {
+        let mut bucket = LeakyBucket::new();
+        bucket.record("q", &cost(1.0, 1000.0));
+        assert_eq!(bucket.reserve(5000.0, bucket.updated_at), (1000.0, Duration::ZERO));
+    }
+}
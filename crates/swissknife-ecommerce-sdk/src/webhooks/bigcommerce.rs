@@ -0,0 +1,161 @@
+use super::{decode_base64, header, optional_header, verify_hmac_sha256, EcommerceEvent, WebhookEvent, WebhookSource};
+use crate::{Error, Result};
+use chrono::Utc;
+use reqwest::header::HeaderMap;
+use serde::Deserialize;
+use serde_json::Value;
+use std::time::Duration;
+
+const ID_HEADER: &str = "webhook-id";
+const TIMESTAMP_HEADER: &str = "webhook-timestamp";
+const SIGNATURE_HEADER: &str = "webhook-signature";
+const DEFAULT_TOLERANCE: Duration = Duration::from_secs(300);
+
+pub struct BigCommerceWebhook {
+    secret: Vec<u8>,
+    tolerance: Duration,
+}
+
+impl BigCommerceWebhook {
+    pub fn new(secret: impl AsRef<str>) -> Self {
+        let secret = secret.as_ref();
+        let secret = match secret.strip_prefix("whsec_") {
+            Some(encoded) => decode_base64(encoded).unwrap_or_else(|_| secret.as_bytes().to_vec()),
+            None => secret.as_bytes().to_vec(),
+        };
+        Self {
+            secret,
+            tolerance: DEFAULT_TOLERANCE,
+        }
+    }
+
+    pub fn with_tolerance(mut self, tolerance: Duration) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+}
+
+impl WebhookSource for BigCommerceWebhook {
+    type Event = WebhookEvent;
+    type Error = Error;
+
+    fn provider(&self) -> &'static str {
+        "bigcommerce"
+    }
+
+    fn verify(&self, headers: &HeaderMap, body: &[u8]) -> Result<()> {
+        let id = header(headers, ID_HEADER)?;
+        let timestamp = header(headers, TIMESTAMP_HEADER)?;
+        let sent_at: i64 = timestamp
+            .parse()
+            .map_err(|_| Error::WebhookVerification("Invalid webhook timestamp".into()))?;
+        if Utc::now().timestamp().abs_diff(sent_at) > self.tolerance.as_secs() {
+            return Err(Error::WebhookVerification("Timestamp outside the tolerance window".into()));
+        }
+
+        let signatures = header(headers, SIGNATURE_HEADER)?;
+        let valid = signatures
+            .split(' ')
+            .filter_map(|s| s.strip_prefix("v1,"))
+            .filter_map(|s| decode_base64(s).ok())
+            .any(|signature| {
+                verify_hmac_sha256(
+                    &self.secret,
+                    &[id.as_bytes(), b".", timestamp.as_bytes(), b".", body],
+                    &signature,
+                )
+                .is_ok()
+            });
+        if valid {
+            Ok(())
+        } else {
+            Err(Error::WebhookVerification("Signature mismatch".into()))
+        }
+    }
+
+    fn parse(&self, headers: &HeaderMap, body: &[u8]) -> Result<Vec<WebhookEvent>> {
+        let payload: Value = serde_json::from_slice(body)?;
+        let notification: Notification = serde_json::from_value(payload.clone())?;
+        let id = match &notification.data.id {
+            Value::Number(n) => n.to_string(),
+            Value::String(s) => s.clone(),
+            _ => String::new(),
+        };
+
+        let event = match notification.scope.as_str() {
+            "store/product/deleted" => EcommerceEvent::ProductDeleted { id },
+            "store/customer/deleted" => EcommerceEvent::CustomerDeleted { id },
+            _ if !id.is_empty() => EcommerceEvent::ResourceChanged {
+                resource: notification.data.resource_type,
+                id,
+            },
+            _ => EcommerceEvent::Unknown(payload),
+        };
+
+        Ok(vec![WebhookEvent {
+            id: optional_header(headers, ID_HEADER).or(notification.hash),
+            provider: self.provider().to_string(),
+            topic: notification.scope,
+            received_at: Utc::now(),
+            event,
+        }])
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Notification {
+    scope: String,
+    #[serde(default)]
+    data: NotificationData,
+    hash: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct NotificationData {
+    #[serde(rename = "type", default)]
+    resource_type: String,
+    #[serde(default)]
+    id: Value,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::{engine::general_purpose::STANDARD, Engine};
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256;
+
+    fn signed_headers(secret: &[u8], id: &str, timestamp: i64, body: &[u8]) -> HeaderMap {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret).unwrap();
+        mac.update(format!("{}.{}.", id, timestamp).as_bytes());
+        mac.update(body);
+        let signature = STANDARD.encode(mac.finalize().into_bytes());
+
+        let mut headers = HeaderMap::new();
+        headers.insert(ID_HEADER, id.parse().unwrap());
+        headers.insert(TIMESTAMP_HEADER, timestamp.to_string().parse().unwrap());
+        headers.insert(SIGNATURE_HEADER, format!("v1,bm90LWl0 v1,{}", signature).parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_signature_and_tolerance() {
+        let body = br#"{"scope":"store/order/statusUpdated","store_id":"1025646","data":{"type":"order","id":250,"status":{"previous_status_id":0,"new_status_id":11}},"hash":"dd70c0976e06b67aaf671e73f49dcb79230ebf9d","created_at":1561482670,"producer":"stores/abc123"}"#;
+        let webhook = BigCommerceWebhook::new("client-secret");
+
+        let headers = signed_headers(b"client-secret", "msg_1", Utc::now().timestamp(), body);
+        let event = webhook.receive(&headers, body).unwrap().remove(0);
+        assert_eq!(event.topic, "store/order/statusUpdated");
+        assert_eq!(event.id.as_deref(), Some("msg_1"));
+        assert!(matches!(
+            event.event,
+            EcommerceEvent::ResourceChanged { ref resource, ref id } if resource == "order" && id == "250"
+        ));
+
+        let stale = signed_headers(b"client-secret", "msg_1", Utc::now().timestamp() - 600, body);
+        assert!(matches!(webhook.verify(&stale, body), Err(Error::WebhookVerification(_))));
+
+        let encoded = BigCommerceWebhook::new(format!("whsec_{}", STANDARD.encode("client-secret")));
+        assert!(encoded.verify(&headers, body).is_ok());
+    }
+}
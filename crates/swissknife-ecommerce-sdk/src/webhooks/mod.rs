@@ -0,0 +1,73 @@
+#[cfg(feature = "shopify")]
+mod shopify;
+#[cfg(feature = "shopify")]
+pub use shopify::ShopifyWebhook;
+
+#[cfg(feature = "woocommerce")]
+mod woocommerce;
+#[cfg(feature = "woocommerce")]
+pub use woocommerce::WooCommerceWebhook;
+
+#[cfg(feature = "bigcommerce")]
+mod bigcommerce;
+#[cfg(feature = "bigcommerce")]
+pub use bigcommerce::BigCommerceWebhook;
+
+pub use swissknife_webhook_sdk::{
+    decode_base64, header, optional_header, verify_hmac_sha256, EventId, VerificationError, WebhookError, WebhookSource,
+};
+#[cfg(feature = "webhook-router")]
+pub use swissknife_webhook_sdk::WebhookHandler;
+
+#[cfg(feature = "webhook-router")]
+pub type WebhookRouter = swissknife_webhook_sdk::WebhookRouter<WebhookEvent, Error>;
+
+use crate::{Customer, Error, Order, Product};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookEvent {
+    pub id: Option<String>,
+    pub provider: String,
+    pub topic: String,
+    pub received_at: DateTime<Utc>,
+    pub event: EcommerceEvent,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum EcommerceEvent {
+    OrderCreated(Order),
+    OrderUpdated(Order),
+    OrderPaid(Order),
+    OrderFulfilled(Order),
+    OrderCancelled(Order),
+    OrderDeleted { id: String },
+    ProductCreated(Product),
+    ProductUpdated(Product),
+    ProductDeleted { id: String },
+    CustomerCreated(Customer),
+    CustomerUpdated(Customer),
+    CustomerDeleted { id: String },
+    ResourceChanged { resource: String, id: String },
+    Unknown(serde_json::Value),
+}
+
+impl EventId for WebhookEvent {
+    fn event_id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+}
+
+impl From<VerificationError> for Error {
+    fn from(e: VerificationError) -> Self {
+        Error::WebhookVerification(e.0)
+    }
+}
+
+impl WebhookError for Error {
+    fn is_verification(&self) -> bool {
+        matches!(self, Error::WebhookVerification(_))
+    }
+}
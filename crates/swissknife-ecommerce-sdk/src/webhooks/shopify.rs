@@ -0,0 +1,425 @@
+use super::{decode_base64, header, optional_header, verify_hmac_sha256, EcommerceEvent, WebhookEvent, WebhookSource};
+use crate::{
+    Address, Customer, Error, FinancialStatus, FulfillmentStatus, LineItem, Order, OrderStatus, Product, ProductImage,
+    ProductOption, ProductStatus, ProductVariant, Result,
+};
+use chrono::{DateTime, Utc};
+use reqwest::header::HeaderMap;
+use serde::{Deserialize, Deserializer};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+const SIGNATURE_HEADER: &str = "x-shopify-hmac-sha256";
+const TOPIC_HEADER: &str = "x-shopify-topic";
+const WEBHOOK_ID_HEADER: &str = "x-shopify-webhook-id";
+
+pub struct ShopifyWebhook {
+    secret: String,
+}
+
+impl ShopifyWebhook {
+    pub fn new(secret: impl Into<String>) -> Self {
+        Self { secret: secret.into() }
+    }
+}
+
+impl WebhookSource for ShopifyWebhook {
+    type Event = WebhookEvent;
+    type Error = Error;
+
+    fn provider(&self) -> &'static str {
+        "shopify"
+    }
+
+    fn verify(&self, headers: &HeaderMap, body: &[u8]) -> Result<()> {
+        let signature = decode_base64(header(headers, SIGNATURE_HEADER)?)?;
+        verify_hmac_sha256(self.secret.as_bytes(), &[body], &signature).map_err(Error::from)
+    }
+
+    fn parse(&self, headers: &HeaderMap, body: &[u8]) -> Result<Vec<WebhookEvent>> {
+        let topic = header(headers, TOPIC_HEADER)?.to_string();
+        let payload: Value = serde_json::from_slice(body)?;
+        let event = match topic.as_str() {
+            "orders/create" => EcommerceEvent::OrderCreated(to_order(serde_json::from_value(payload)?)),
+            "orders/updated" | "orders/edited" => EcommerceEvent::OrderUpdated(to_order(serde_json::from_value(payload)?)),
+            "orders/paid" => EcommerceEvent::OrderPaid(to_order(serde_json::from_value(payload)?)),
+            "orders/fulfilled" => EcommerceEvent::OrderFulfilled(to_order(serde_json::from_value(payload)?)),
+            "orders/cancelled" => EcommerceEvent::OrderCancelled(to_order(serde_json::from_value(payload)?)),
+            "orders/delete" => EcommerceEvent::OrderDeleted { id: resource_id(&payload) },
+            "products/create" => EcommerceEvent::ProductCreated(to_product(serde_json::from_value(payload)?)),
+            "products/update" => EcommerceEvent::ProductUpdated(to_product(serde_json::from_value(payload)?)),
+            "products/delete" => EcommerceEvent::ProductDeleted { id: resource_id(&payload) },
+            "customers/create" => EcommerceEvent::CustomerCreated(to_customer(serde_json::from_value(payload)?)),
+            "customers/update" => EcommerceEvent::CustomerUpdated(to_customer(serde_json::from_value(payload)?)),
+            "customers/delete" => EcommerceEvent::CustomerDeleted { id: resource_id(&payload) },
+            _ => EcommerceEvent::Unknown(payload),
+        };
+
+        Ok(vec![WebhookEvent {
+            id: optional_header(headers, WEBHOOK_ID_HEADER),
+            provider: self.provider().to_string(),
+            topic,
+            received_at: Utc::now(),
+            event,
+        }])
+    }
+}
+
+fn resource_id(payload: &Value) -> String {
+    match &payload["id"] {
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => s.clone(),
+        _ => String::new(),
+    }
+}
+
+fn to_order(order: RestOrder) -> Order {
+    let mut extra = HashMap::new();
+    if let Some(gid) = &order.admin_graphql_api_id {
+        extra.insert("admin_graphql_api_id".to_string(), json!(gid));
+    }
+    if !order.tags.is_empty() {
+        extra.insert("tags".to_string(), json!(split_tags(&order.tags)));
+    }
+
+    let status = if order.cancelled_at.is_some() {
+        OrderStatus::Cancelled
+    } else if order.closed_at.is_some() {
+        OrderStatus::Closed
+    } else {
+        OrderStatus::Open
+    };
+
+    Order {
+        id: order.id.to_string(),
+        order_number: order.name,
+        email: order.email.filter(|e| !e.is_empty()),
+        phone: order.phone.filter(|p| !p.is_empty()),
+        status,
+        financial_status: match order.financial_status.as_deref() {
+            Some("authorized") => FinancialStatus::Authorized,
+            Some("partially_paid") => FinancialStatus::PartiallyPaid,
+            Some("paid") => FinancialStatus::Paid,
+            Some("partially_refunded") => FinancialStatus::PartiallyRefunded,
+            Some("refunded") => FinancialStatus::Refunded,
+            Some("voided") | Some("expired") => FinancialStatus::Voided,
+            _ => FinancialStatus::Pending,
+        },
+        fulfillment_status: Some(match order.fulfillment_status.as_deref() {
+            Some("fulfilled") => FulfillmentStatus::Fulfilled,
+            Some("partial") => FulfillmentStatus::Partial,
+            _ => FulfillmentStatus::Unfulfilled,
+        }),
+        currency: order.currency,
+        subtotal_price: order.subtotal_price,
+        total_tax: order.total_tax,
+        total_discounts: order.total_discounts,
+        total_price: order.total_price,
+        line_items: order
+            .line_items
+            .into_iter()
+            .map(|item| LineItem {
+                id: item.id.to_string(),
+                product_id: item.product_id.map(|id| id.to_string()),
+                variant_id: item.variant_id.map(|id| id.to_string()),
+                title: item.title,
+                quantity: item.quantity,
+                price: item.price,
+                sku: item.sku.filter(|s| !s.is_empty()),
+                total_discount: item.total_discount,
+            })
+            .collect(),
+        shipping_address: order.shipping_address.map(to_address),
+        billing_address: order.billing_address.map(to_address),
+        customer: order.customer.map(to_customer),
+        note: order.note.filter(|n| !n.is_empty()),
+        created_at: order.created_at,
+        updated_at: order.updated_at,
+        extra,
+    }
+}
+
+fn to_product(product: RestProduct) -> Product {
+    let mut extra = HashMap::new();
+    if let Some(gid) = &product.admin_graphql_api_id {
+        extra.insert("admin_graphql_api_id".to_string(), json!(gid));
+    }
+
+    let option_names: Vec<String> = product.options.iter().map(|o| o.name.clone()).collect();
+    Product {
+        id: product.id.to_string(),
+        title: product.title,
+        description: product.body_html.filter(|d| !d.is_empty()),
+        handle: product.handle,
+        status: match product.status.as_deref() {
+            Some("active") => ProductStatus::Active,
+            Some("archived") => ProductStatus::Archived,
+            _ => ProductStatus::Draft,
+        },
+        vendor: product.vendor.filter(|v| !v.is_empty()),
+        product_type: product.product_type.filter(|t| !t.is_empty()),
+        tags: split_tags(&product.tags),
+        variants: product
+            .variants
+            .into_iter()
+            .map(|variant| ProductVariant {
+                id: variant.id.to_string(),
+                title: variant.title,
+                sku: variant.sku.filter(|s| !s.is_empty()),
+                price: variant.price,
+                compare_at_price: variant.compare_at_price,
+                inventory_quantity: variant.inventory_quantity,
+                weight: variant.weight,
+                weight_unit: variant.weight_unit,
+                options: option_names
+                    .iter()
+                    .cloned()
+                    .zip([variant.option1, variant.option2, variant.option3])
+                    .filter_map(|(name, value)| Some((name, value?)))
+                    .collect(),
+                barcode: variant.barcode.filter(|b| !b.is_empty()),
+            })
+            .collect(),
+        images: product
+            .images
+            .into_iter()
+            .map(|image| ProductImage {
+                id: image.id.to_string(),
+                src: image.src,
+                alt: image.alt.filter(|a| !a.is_empty()),
+                position: image.position,
+            })
+            .collect(),
+        options: product
+            .options
+            .into_iter()
+            .map(|o| ProductOption {
+                name: o.name,
+                values: o.values,
+            })
+            .collect(),
+        created_at: product.created_at,
+        updated_at: product.updated_at,
+        extra,
+    }
+}
+
+fn to_address(address: RestAddress) -> Address {
+    Address {
+        first_name: address.first_name,
+        last_name: address.last_name,
+        company: address.company,
+        address1: address.address1,
+        address2: address.address2,
+        city: address.city,
+        province: address.province,
+        province_code: address.province_code,
+        country: address.country,
+        country_code: address.country_code,
+        zip: address.zip,
+        phone: address.phone,
+    }
+}
+
+fn to_customer(customer: RestCustomer) -> Customer {
+    Customer {
+        id: customer.id.to_string(),
+        email: customer.email,
+        first_name: customer.first_name,
+        last_name: customer.last_name,
+        phone: customer.phone,
+        orders_count: customer.orders_count,
+        total_spent: customer.total_spent,
+        tags: split_tags(&customer.tags),
+        addresses: customer.addresses.into_iter().map(to_address).collect(),
+        created_at: customer.created_at,
+        updated_at: customer.updated_at,
+    }
+}
+
+fn split_tags(tags: &str) -> Vec<String> {
+    tags.split(',')
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+fn money<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<f64, D::Error> {
+    Ok(optional_money(deserializer)?.unwrap_or_default())
+}
+
+fn optional_money<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Option<f64>, D::Error> {
+    Ok(match Option::<Value>::deserialize(deserializer)? {
+        Some(Value::String(s)) => s.parse().ok(),
+        Some(Value::Number(n)) => n.as_f64(),
+        _ => None,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+struct RestOrder {
+    id: i64,
+    admin_graphql_api_id: Option<String>,
+    name: Option<String>,
+    email: Option<String>,
+    phone: Option<String>,
+    cancelled_at: Option<DateTime<Utc>>,
+    closed_at: Option<DateTime<Utc>>,
+    financial_status: Option<String>,
+    fulfillment_status: Option<String>,
+    #[serde(default)]
+    currency: String,
+    #[serde(default, deserialize_with = "money")]
+    subtotal_price: f64,
+    #[serde(default, deserialize_with = "money")]
+    total_tax: f64,
+    #[serde(default, deserialize_with = "money")]
+    total_discounts: f64,
+    #[serde(default, deserialize_with = "money")]
+    total_price: f64,
+    #[serde(default)]
+    line_items: Vec<RestLineItem>,
+    shipping_address: Option<RestAddress>,
+    billing_address: Option<RestAddress>,
+    customer: Option<RestCustomer>,
+    note: Option<String>,
+    #[serde(default)]
+    tags: String,
+    created_at: DateTime<Utc>,
+    updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RestLineItem {
+    id: i64,
+    product_id: Option<i64>,
+    variant_id: Option<i64>,
+    #[serde(default)]
+    title: String,
+    quantity: u32,
+    #[serde(default, deserialize_with = "money")]
+    price: f64,
+    sku: Option<String>,
+    #[serde(default, deserialize_with = "optional_money")]
+    total_discount: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RestAddress {
+    first_name: Option<String>,
+    last_name: Option<String>,
+    company: Option<String>,
+    address1: Option<String>,
+    address2: Option<String>,
+    city: Option<String>,
+    province: Option<String>,
+    province_code: Option<String>,
+    country: Option<String>,
+    country_code: Option<String>,
+    zip: Option<String>,
+    phone: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RestCustomer {
+    id: i64,
+    email: Option<String>,
+    first_name: Option<String>,
+    last_name: Option<String>,
+    phone: Option<String>,
+    orders_count: Option<u32>,
+    #[serde(default, deserialize_with = "optional_money")]
+    total_spent: Option<f64>,
+    #[serde(default)]
+    tags: String,
+    #[serde(default)]
+    addresses: Vec<RestAddress>,
+    created_at: Option<DateTime<Utc>>,
+    updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RestProduct {
+    id: i64,
+    admin_graphql_api_id: Option<String>,
+    title: String,
+    body_html: Option<String>,
+    handle: Option<String>,
+    status: Option<String>,
+    vendor: Option<String>,
+    product_type: Option<String>,
+    #[serde(default)]
+    tags: String,
+    #[serde(default)]
+    variants: Vec<RestVariant>,
+    #[serde(default)]
+    images: Vec<RestImage>,
+    #[serde(default)]
+    options: Vec<RestOption>,
+    created_at: Option<DateTime<Utc>>,
+    updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RestVariant {
+    id: i64,
+    #[serde(default)]
+    title: String,
+    sku: Option<String>,
+    #[serde(default, deserialize_with = "money")]
+    price: f64,
+    #[serde(default, deserialize_with = "optional_money")]
+    compare_at_price: Option<f64>,
+    inventory_quantity: Option<i32>,
+    weight: Option<f64>,
+    weight_unit: Option<String>,
+    option1: Option<String>,
+    option2: Option<String>,
+    option3: Option<String>,
+    barcode: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RestImage {
+    id: i64,
+    src: String,
+    alt: Option<String>,
+    position: Option<i32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RestOption {
+    name: String,
+    #[serde(default)]
+    values: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_and_parse_order() {
+        let body = br##"{"id":820982911946154508,"name":"#9999","email":"jon@example.com","financial_status":"paid","fulfillment_status":null,"currency":"USD","total_price":"403.00","subtotal_price":"393.00","total_tax":"0.00","total_discounts":"5.00","line_items":[{"id":866550311766439020,"product_id":632910392,"variant_id":808950810,"title":"IPod Nano - 8GB","quantity":1,"price":"199.00","sku":"IPOD2008PINK","total_discount":"0.00"}],"tags":"vip, wholesale","created_at":"2021-12-31T19:00:00-05:00"}"##;
+        let mut headers = HeaderMap::new();
+        headers.insert(TOPIC_HEADER, "orders/paid".parse().unwrap());
+        headers.insert(SIGNATURE_HEADER, "X50rDfiGad+njy8PVoXJGfZAsLvI5wiswaGQ75FPIWo=".parse().unwrap());
+
+        let webhook = ShopifyWebhook::new("hush");
+        let event = webhook.receive(&headers, body).unwrap().remove(0);
+        match event.event {
+            EcommerceEvent::OrderPaid(order) => {
+                assert_eq!(order.financial_status, FinancialStatus::Paid);
+                assert_eq!(order.fulfillment_status, Some(FulfillmentStatus::Unfulfilled));
+                assert_eq!(order.line_items[0].variant_id.as_deref(), Some("808950810"));
+                assert_eq!(order.extra["tags"], json!(["vip", "wholesale"]));
+            }
+            other => panic!("unexpected event {:?}", other),
+        }
+
+        assert!(matches!(
+            ShopifyWebhook::new("other").receive(&headers, body),
+            Err(Error::WebhookVerification(_))
+        ));
+    }
+}
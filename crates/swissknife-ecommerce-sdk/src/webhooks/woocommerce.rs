@@ -0,0 +1,105 @@
+use super::{decode_base64, header, optional_header, verify_hmac_sha256, EcommerceEvent, WebhookEvent, WebhookSource};
+use crate::woocommerce::{customer_from_value, order_from_value, product_from_value};
+use crate::{Error, Result};
+use chrono::Utc;
+use reqwest::header::HeaderMap;
+use serde_json::Value;
+
+const SIGNATURE_HEADER: &str = "x-wc-webhook-signature";
+const TOPIC_HEADER: &str = "x-wc-webhook-topic";
+const DELIVERY_ID_HEADER: &str = "x-wc-webhook-delivery-id";
+
+pub struct WooCommerceWebhook {
+    secret: String,
+}
+
+impl WooCommerceWebhook {
+    pub fn new(secret: impl Into<String>) -> Self {
+        Self { secret: secret.into() }
+    }
+}
+
+impl WebhookSource for WooCommerceWebhook {
+    type Event = WebhookEvent;
+    type Error = Error;
+
+    fn provider(&self) -> &'static str {
+        "woocommerce"
+    }
+
+    fn verify(&self, headers: &HeaderMap, body: &[u8]) -> Result<()> {
+        // WooCommerce pings a new delivery URL with an unsigned `webhook_id=<id>` form body.
+        if !headers.contains_key(SIGNATURE_HEADER) && is_ping(body) {
+            return Ok(());
+        }
+        let signature = decode_base64(header(headers, SIGNATURE_HEADER)?)?;
+        verify_hmac_sha256(self.secret.as_bytes(), &[body], &signature).map_err(Error::from)
+    }
+
+    fn parse(&self, headers: &HeaderMap, body: &[u8]) -> Result<Vec<WebhookEvent>> {
+        let topic = optional_header(headers, TOPIC_HEADER).unwrap_or_else(|| "ping".to_string());
+        let event = if is_ping(body) {
+            EcommerceEvent::Unknown(Value::String(String::from_utf8_lossy(body).into_owned()))
+        } else {
+            let payload: Value = serde_json::from_slice(body)?;
+            match topic.as_str() {
+                "order.created" => EcommerceEvent::OrderCreated(order_from_value(payload)?),
+                "order.updated" | "order.restored" => EcommerceEvent::OrderUpdated(order_from_value(payload)?),
+                "order.deleted" => EcommerceEvent::OrderDeleted { id: resource_id(&payload) },
+                "product.created" => EcommerceEvent::ProductCreated(product_from_value(payload)?),
+                "product.updated" | "product.restored" => EcommerceEvent::ProductUpdated(product_from_value(payload)?),
+                "product.deleted" => EcommerceEvent::ProductDeleted { id: resource_id(&payload) },
+                "customer.created" => EcommerceEvent::CustomerCreated(customer_from_value(payload)?),
+                "customer.updated" => EcommerceEvent::CustomerUpdated(customer_from_value(payload)?),
+                "customer.deleted" => EcommerceEvent::CustomerDeleted { id: resource_id(&payload) },
+                _ => EcommerceEvent::Unknown(payload),
+            }
+        };
+
+        Ok(vec![WebhookEvent {
+            id: optional_header(headers, DELIVERY_ID_HEADER),
+            provider: self.provider().to_string(),
+            topic,
+            received_at: Utc::now(),
+            event,
+        }])
+    }
+}
+
+fn is_ping(body: &[u8]) -> bool {
+    body.starts_with(b"webhook_id=")
+}
+
+fn resource_id(payload: &Value) -> String {
+    match &payload["id"] {
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => s.clone(),
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OrderStatus;
+
+    #[test]
+    fn test_verify_and_parse_order() {
+        let body = br#"{"id":727,"number":"727","status":"cancelled","currency":"EUR","total":"29.35","line_items":[]}"#;
+        let mut headers = HeaderMap::new();
+        headers.insert(TOPIC_HEADER, "order.updated".parse().unwrap());
+        headers.insert(SIGNATURE_HEADER, "G5n43cdgRkAwKMoNGX84EsqNLIgLQapgQMnvBZcWqA8=".parse().unwrap());
+
+        let event = WooCommerceWebhook::new("wc-secret").receive(&headers, body).unwrap().remove(0);
+        match event.event {
+            EcommerceEvent::OrderUpdated(order) => {
+                assert_eq!(order.status, OrderStatus::Cancelled);
+                assert_eq!(order.total_price, 29.35);
+            }
+            other => panic!("unexpected event {:?}", other),
+        }
+
+        let ping = WooCommerceWebhook::new("wc-secret").receive(&HeaderMap::new(), b"webhook_id=15").unwrap().remove(0);
+        assert_eq!(ping.topic, "ping");
+    }
+}
@@ -0,0 +1,1290 @@
+use crate::{
+    page_number, Address, Collection, CollectionProvider, Customer, EcommerceProvider, Error, FinancialStatus,
+    FulfillmentStatus, InventoryItem, InventoryProvider, LineItem, ListOptions, ListResult, Order, OrderFilter,
+    OrderStatus, Product, ProductFilter, ProductImage, ProductOption, ProductStatus, ProductVariant, Result,
+};
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use hmac::{Hmac, Mac};
+use reqwest::Method;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer};
+use serde_json::{json, Map, Value};
+use sha2::Sha256;
+use std::collections::HashMap;
+
+const DEFAULT_PER_PAGE: u32 = 20;
+const MAX_PER_PAGE: u32 = 100;
+const ORDER_STATUSES: &[&str] = &[
+    "pending",
+    "processing",
+    "on-hold",
+    "completed",
+    "cancelled",
+    "refunded",
+    "failed",
+    "checkout-draft",
+];
+
+#[derive(Clone)]
+pub struct WooCommerceClient {
+    store_url: String,
+    consumer_key: String,
+    consumer_secret: String,
+    http: reqwest::Client,
+}
+
+impl WooCommerceClient {
+    pub fn new(store_url: impl Into<String>, consumer_key: impl Into<String>, consumer_secret: impl Into<String>) -> Self {
+        Self {
+            store_url: store_url.into().trim_end_matches('/').to_string(),
+            consumer_key: consumer_key.into(),
+            consumer_secret: consumer_secret.into(),
+            http: reqwest::Client::new(),
+        }
+    }
+
+    fn api_url(&self, path: &str) -> String {
+        format!("{}/wp-json/wc/v3{}", self.store_url, path)
+    }
+
+    fn oauth_params(&self, method: &Method, url: &str, query: &[(&str, String)]) -> Vec<(String, String)> {
+        let mut params: Vec<(String, String)> = query.iter().map(|(k, v)| (k.to_string(), v.clone())).collect();
+        params.push(("oauth_consumer_key".to_string(), self.consumer_key.clone()));
+        params.push(("oauth_nonce".to_string(), uuid::Uuid::new_v4().simple().to_string()));
+        params.push(("oauth_signature_method".to_string(), "HMAC-SHA256".to_string()));
+        params.push(("oauth_timestamp".to_string(), Utc::now().timestamp().to_string()));
+        let signature = oauth_signature(method.as_str(), url, &params, &self.consumer_secret);
+        params.push(("oauth_signature".to_string(), signature));
+        params
+    }
+
+    async fn request<T: DeserializeOwned>(&self, method: Method, path: &str, query: &[(&str, String)], body: Option<Value>) -> Result<(T, Option<u32>)> {
+        let url = self.api_url(path);
+        let mut request = if url.starts_with("https://") {
+            self.http
+                .request(method.clone(), &url)
+                .basic_auth(&self.consumer_key, Some(&self.consumer_secret))
+                .query(query)
+        } else {
+            let params = self.oauth_params(&method, &url, query);
+            self.http.request(method.clone(), &url).query(&params)
+        };
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+
+        let response = request.send().await?;
+        let status = response.status().as_u16();
+        let total_pages = response
+            .headers()
+            .get("X-WP-TotalPages")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse().ok());
+        let text = response.text().await?;
+
+        if !(200..300).contains(&status) {
+            let error = serde_json::from_str::<WooError>(&text).ok();
+            let code = error.as_ref().map(|e| e.code.clone());
+            let message = error.map(|e| e.message).unwrap_or(text);
+            return Err(match status {
+                400 => Error::Validation(message),
+                401 | 403 => Error::Auth(message),
+                404 => Error::NotFound(message),
+                429 => Error::RateLimited,
+                _ => Error::Api { message, code },
+            });
+        }
+
+        Ok((serde_json::from_str(&text)?, total_pages))
+    }
+
+    async fn send<T: DeserializeOwned>(&self, method: Method, path: &str, query: &[(&str, String)], body: Option<Value>) -> Result<T> {
+        Ok(self.request(method, path, query, body).await?.0)
+    }
+
+    async fn list_page<T: DeserializeOwned>(&self, path: &str, mut query: Vec<(&str, String)>, options: &ListOptions) -> Result<ListResult<T>> {
+        let page = page_number(options);
+        query.push(("page", page.to_string()));
+        query.push(("per_page", options.limit.unwrap_or(DEFAULT_PER_PAGE).clamp(1, MAX_PER_PAGE).to_string()));
+        let (items, total_pages) = self.request(Method::GET, path, &query, None).await?;
+        let has_more = total_pages.is_some_and(|total| page < total);
+        Ok(ListResult {
+            items,
+            has_more,
+            next_cursor: has_more.then(|| (page + 1).to_string()),
+        })
+    }
+
+    async fn fetch_product(&self, id: &str) -> Result<WooProduct> {
+        self.send(Method::GET, &format!("/products/{}", id), &[], None).await
+    }
+
+    async fn fetch_variations(&self, product_id: i64) -> Result<Vec<WooVariation>> {
+        let mut variations = Vec::new();
+        let mut options = ListOptions {
+            limit: Some(MAX_PER_PAGE),
+            ..Default::default()
+        };
+        loop {
+            let page: ListResult<WooVariation> = self
+                .list_page(&format!("/products/{}/variations", product_id), Vec::new(), &options)
+                .await?;
+            variations.extend(page.items);
+            if !page.has_more {
+                return Ok(variations);
+            }
+            options.cursor = page.next_cursor;
+        }
+    }
+
+    async fn tag_ids(&self, names: &[String]) -> Result<Vec<Value>> {
+        let mut ids = Vec::new();
+        for name in names {
+            let existing: Vec<WooTerm> = self
+                .send(Method::GET, "/products/tags", &[("search", name.clone())], None)
+                .await?;
+            let tag = match existing.into_iter().find(|t| t.name.eq_ignore_ascii_case(name)) {
+                Some(tag) => tag,
+                None => {
+                    self.send(Method::POST, "/products/tags", &[], Some(json!({ "name": name })))
+                        .await?
+                }
+            };
+            ids.push(json!({ "id": tag.id }));
+        }
+        Ok(ids)
+    }
+
+    async fn save_product(&self, method: Method, path: &str, product: &Product) -> Result<WooProduct> {
+        let mut input = product_input(product);
+        input.insert("tags".to_string(), json!(self.tag_ids(&product.tags).await?));
+        self.send(method, path, &[], Some(Value::Object(input))).await
+    }
+
+    async fn save_variations(&self, product_id: i64, variants: &[ProductVariant]) -> Result<()> {
+        let (update, create): (Vec<&ProductVariant>, Vec<&ProductVariant>) =
+            variants.iter().partition(|v| !v.id.is_empty());
+        if update.is_empty() && create.is_empty() {
+            return Ok(());
+        }
+        let body = json!({
+            "create": create.into_iter().map(variation_input).collect::<Vec<_>>(),
+            "update": update
+                .into_iter()
+                .map(|v| {
+                    let mut input = variation_input(v);
+                    if let Value::Object(map) = &mut input {
+                        map.insert("id".to_string(), json!(v.id.parse::<i64>().unwrap_or_default()));
+                    }
+                    input
+                })
+                .collect::<Vec<_>>(),
+        });
+        self.send::<Value>(Method::POST, &format!("/products/{}/variations/batch", product_id), &[], Some(body))
+            .await?;
+        Ok(())
+    }
+
+    async fn load_product(&self, product: WooProduct) -> Result<Product> {
+        let variations = if product.product_type == "variable" {
+            self.fetch_variations(product.id).await?
+        } else {
+            Vec::new()
+        };
+        Ok(to_product(product, variations))
+    }
+
+    async fn set_product_categories(&self, collection_id: &str, product_ids: &[&str], add: bool) -> Result<()> {
+        if product_ids.is_empty() {
+            return Ok(());
+        }
+        let category: i64 = collection_id
+            .parse()
+            .map_err(|_| Error::Validation(format!("Invalid WooCommerce category id: {}", collection_id)))?;
+        let include = product_ids.join(",");
+        let products: Vec<WooProduct> = self
+            .send(
+                Method::GET,
+                "/products",
+                &[("include", include), ("per_page", MAX_PER_PAGE.to_string())],
+                None,
+            )
+            .await?;
+
+        let update: Vec<Value> = products
+            .into_iter()
+            .map(|p| {
+                let mut categories: Vec<i64> = p.categories.iter().map(|c| c.id).filter(|id| *id != category).collect();
+                if add {
+                    categories.push(category);
+                }
+                json!({
+                    "id": p.id,
+                    "categories": categories.into_iter().map(|id| json!({ "id": id })).collect::<Vec<_>>(),
+                })
+            })
+            .collect();
+        self.send::<Value>(Method::POST, "/products/batch", &[], Some(json!({ "update": update })))
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl EcommerceProvider for WooCommerceClient {
+    async fn list_products(&self, filter: &ProductFilter, options: &ListOptions) -> Result<ListResult<Product>> {
+        let mut query = Vec::new();
+        if let Some(status) = filter.status {
+            query.push(("status", product_status_value(status).to_string()));
+        }
+        if let Some(product_type) = &filter.product_type {
+            query.push(("type", product_type.clone()));
+        }
+        if let Some(category) = &filter.collection_id {
+            query.push(("category", category.clone()));
+        }
+        if !filter.ids.is_empty() {
+            query.push(("include", filter.ids.join(",")));
+        }
+
+        let page: ListResult<WooProduct> = self.list_page("/products", query, options).await?;
+        Ok(ListResult {
+            items: page.items.into_iter().map(|p| to_product(p, Vec::new())).collect(),
+            has_more: page.has_more,
+            next_cursor: page.next_cursor,
+        })
+    }
+
+    async fn get_product(&self, id: &str) -> Result<Product> {
+        let product = self.fetch_product(id).await?;
+        self.load_product(product).await
+    }
+
+    async fn create_product(&self, product: &Product) -> Result<Product> {
+        let created = self.save_product(Method::POST, "/products", product).await?;
+        if created.product_type == "variable" {
+            self.save_variations(created.id, &product.variants).await?;
+        }
+        self.get_product(&created.id.to_string()).await
+    }
+
+    async fn update_product(&self, id: &str, product: &Product) -> Result<Product> {
+        let updated = self
+            .save_product(Method::PUT, &format!("/products/{}", id), product)
+            .await?;
+        if updated.product_type == "variable" {
+            let variants: Vec<ProductVariant> = product
+                .variants
+                .iter()
+                .filter(|v| v.id != id)
+                .cloned()
+                .collect();
+            self.save_variations(updated.id, &variants).await?;
+        }
+        self.load_product(updated).await
+    }
+
+    async fn delete_product(&self, id: &str) -> Result<()> {
+        self.send::<Value>(Method::DELETE, &format!("/products/{}", id), &[("force", "true".to_string())], None)
+            .await?;
+        Ok(())
+    }
+
+    async fn list_orders(&self, filter: &OrderFilter, options: &ListOptions) -> Result<ListResult<Order>> {
+        let mut query = vec![("dates_are_gmt", "true".to_string())];
+        if filter.status.is_some() || filter.financial_status.is_some() || filter.fulfillment_status.is_some() {
+            let statuses = statuses_for(filter);
+            if statuses.is_empty() {
+                return Ok(ListResult {
+                    items: Vec::new(),
+                    has_more: false,
+                    next_cursor: None,
+                });
+            }
+            query.push(("status", statuses.join(",")));
+        }
+        if let Some(min) = filter.created_at_min {
+            query.push(("after", min.to_rfc3339()));
+        }
+        if let Some(max) = filter.created_at_max {
+            query.push(("before", max.to_rfc3339()));
+        }
+
+        let page: ListResult<WooOrder> = self.list_page("/orders", query, options).await?;
+        Ok(ListResult {
+            items: page
+                .items
+                .into_iter()
+                .map(to_order)
+                .filter(|o| order_matches(o, filter))
+                .collect(),
+            has_more: page.has_more,
+            next_cursor: page.next_cursor,
+        })
+    }
+
+    async fn get_order(&self, id: &str) -> Result<Order> {
+        let order: WooOrder = self.send(Method::GET, &format!("/orders/{}", id), &[], None).await?;
+        Ok(to_order(order))
+    }
+
+    async fn create_order(&self, order: &Order) -> Result<Order> {
+        let line_items: Vec<Value> = order
+            .line_items
+            .iter()
+            .map(|item| {
+                let mut input = Map::new();
+                input.insert("quantity".to_string(), json!(item.quantity));
+                match &item.product_id {
+                    Some(product_id) => {
+                        input.insert("product_id".to_string(), json!(numeric_id(product_id)?));
+                        if let Some(variant_id) = item.variant_id.as_deref().filter(|v| *v != product_id) {
+                            input.insert("variation_id".to_string(), json!(numeric_id(variant_id)?));
+                        }
+                    }
+                    None => {
+                        let total = item.price * item.quantity as f64 - item.total_discount.unwrap_or_default();
+                        input.insert("name".to_string(), json!(item.title));
+                        input.insert("subtotal".to_string(), json!((item.price * item.quantity as f64).to_string()));
+                        input.insert("total".to_string(), json!(total.to_string()));
+                    }
+                }
+                Ok(Value::Object(input))
+            })
+            .collect::<Result<_>>()?;
+
+        let (status, set_paid) = order_status_value(order.status, order.financial_status);
+
+        let mut body = Map::new();
+        body.insert("status".to_string(), json!(status));
+        body.insert("set_paid".to_string(), json!(set_paid));
+        body.insert("currency".to_string(), json!(order.currency));
+        body.insert("line_items".to_string(), json!(line_items));
+        let mut billing = order.billing_address.as_ref().map(address_input).unwrap_or_else(|| json!({}));
+        if let Some(email) = &order.email {
+            billing["email"] = json!(email);
+        }
+        if let Some(phone) = &order.phone {
+            billing["phone"] = json!(phone);
+        }
+        body.insert("billing".to_string(), billing);
+        if let Some(shipping) = &order.shipping_address {
+            body.insert("shipping".to_string(), address_input(shipping));
+        }
+        if let Some(note) = &order.note {
+            body.insert("customer_note".to_string(), json!(note));
+        }
+        if let Some(customer) = order.customer.as_ref().filter(|c| !c.id.is_empty()) {
+            body.insert("customer_id".to_string(), json!(numeric_id(&customer.id)?));
+        }
+
+        let created: WooOrder = self.send(Method::POST, "/orders", &[], Some(Value::Object(body))).await?;
+        Ok(to_order(created))
+    }
+
+    async fn update_order(&self, id: &str, order: &Order) -> Result<Order> {
+        let mut body = Map::new();
+        if let Some(note) = &order.note {
+            body.insert("customer_note".to_string(), json!(note));
+        }
+        let mut billing = order.billing_address.as_ref().map(address_input).unwrap_or_else(|| json!({}));
+        if let Some(email) = &order.email {
+            billing["email"] = json!(email);
+        }
+        if let Some(phone) = &order.phone {
+            billing["phone"] = json!(phone);
+        }
+        if billing.as_object().is_some_and(|b| !b.is_empty()) {
+            body.insert("billing".to_string(), billing);
+        }
+        if let Some(shipping) = &order.shipping_address {
+            body.insert("shipping".to_string(), address_input(shipping));
+        }
+        let updated: WooOrder = self
+            .send(Method::PUT, &format!("/orders/{}", id), &[], Some(Value::Object(body)))
+            .await?;
+        Ok(to_order(updated))
+    }
+
+    async fn cancel_order(&self, id: &str) -> Result<Order> {
+        let cancelled: WooOrder = self
+            .send(Method::PUT, &format!("/orders/{}", id), &[], Some(json!({ "status": "cancelled" })))
+            .await?;
+        Ok(to_order(cancelled))
+    }
+
+    async fn list_customers(&self, options: &ListOptions) -> Result<ListResult<Customer>> {
+        let page: ListResult<WooCustomer> = self
+            .list_page("/customers", vec![("role", "all".to_string())], options)
+            .await?;
+        Ok(ListResult {
+            items: page.items.into_iter().map(to_customer).collect(),
+            has_more: page.has_more,
+            next_cursor: page.next_cursor,
+        })
+    }
+
+    async fn get_customer(&self, id: &str) -> Result<Customer> {
+        let customer: WooCustomer = self.send(Method::GET, &format!("/customers/{}", id), &[], None).await?;
+        Ok(to_customer(customer))
+    }
+
+    async fn create_customer(&self, customer: &Customer) -> Result<Customer> {
+        let created: WooCustomer = self
+            .send(Method::POST, "/customers", &[], Some(customer_input(customer)))
+            .await?;
+        Ok(to_customer(created))
+    }
+
+    async fn update_customer(&self, id: &str, customer: &Customer) -> Result<Customer> {
+        let updated: WooCustomer = self
+            .send(Method::PUT, &format!("/customers/{}", id), &[], Some(customer_input(customer)))
+            .await?;
+        Ok(to_customer(updated))
+    }
+
+    async fn delete_customer(&self, id: &str) -> Result<()> {
+        self.send::<Value>(Method::DELETE, &format!("/customers/{}", id), &[("force", "true".to_string())], None)
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl InventoryProvider for WooCommerceClient {
+    async fn get_inventory(&self, item_id: &str, _location_id: Option<&str>) -> Result<InventoryItem> {
+        let product = self.fetch_product(item_id).await?;
+        Ok(to_inventory(&product))
+    }
+
+    async fn adjust_inventory(&self, item_id: &str, location_id: &str, adjustment: i32) -> Result<InventoryItem> {
+        let current = self.get_inventory(item_id, Some(location_id)).await?;
+        self.set_inventory(item_id, location_id, current.inventory_quantity + adjustment)
+            .await
+    }
+
+    async fn set_inventory(&self, item_id: &str, _location_id: &str, quantity: i32) -> Result<InventoryItem> {
+        let product = self.fetch_product(item_id).await?;
+        let path = if product.parent_id != 0 {
+            format!("/products/{}/variations/{}", product.parent_id, product.id)
+        } else {
+            format!("/products/{}", product.id)
+        };
+        let updated: WooProduct = self
+            .send(
+                Method::PUT,
+                &path,
+                &[],
+                Some(json!({ "manage_stock": true, "stock_quantity": quantity })),
+            )
+            .await?;
+        Ok(to_inventory(&updated))
+    }
+}
+
+#[async_trait]
+impl CollectionProvider for WooCommerceClient {
+    async fn list_collections(&self, options: &ListOptions) -> Result<ListResult<Collection>> {
+        let page: ListResult<WooCategory> = self.list_page("/products/categories", Vec::new(), options).await?;
+        Ok(ListResult {
+            items: page.items.into_iter().map(to_collection).collect(),
+            has_more: page.has_more,
+            next_cursor: page.next_cursor,
+        })
+    }
+
+    async fn get_collection(&self, id: &str) -> Result<Collection> {
+        let category: WooCategory = self
+            .send(Method::GET, &format!("/products/categories/{}", id), &[], None)
+            .await?;
+        Ok(to_collection(category))
+    }
+
+    async fn create_collection(&self, title: &str, description: Option<&str>) -> Result<Collection> {
+        let category: WooCategory = self
+            .send(
+                Method::POST,
+                "/products/categories",
+                &[],
+                Some(json!({ "name": title, "description": description.unwrap_or_default() })),
+            )
+            .await?;
+        Ok(to_collection(category))
+    }
+
+    async fn add_products_to_collection(&self, collection_id: &str, product_ids: &[&str]) -> Result<()> {
+        self.set_product_categories(collection_id, product_ids, true).await
+    }
+
+    async fn remove_products_from_collection(&self, collection_id: &str, product_ids: &[&str]) -> Result<()> {
+        self.set_product_categories(collection_id, product_ids, false).await
+    }
+}
+
+pub fn oauth_signature(method: &str, url: &str, params: &[(String, String)], consumer_secret: &str) -> String {
+    let mut encoded: Vec<(String, String)> = params
+        .iter()
+        .map(|(k, v)| (oauth_encode(k), oauth_encode(v)))
+        .collect();
+    encoded.sort();
+    let normalized = encoded
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<_>>()
+        .join("&");
+    let base = format!(
+        "{}&{}&{}",
+        method.to_uppercase(),
+        oauth_encode(url),
+        oauth_encode(&normalized)
+    );
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(format!("{}&", consumer_secret).as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(base.as_bytes());
+    STANDARD.encode(mac.finalize().into_bytes())
+}
+
+fn oauth_encode(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => out.push(byte as char),
+            _ => out.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    out
+}
+
+// The inverse of `order_states`: the WooCommerce status to write, and whether to mark the order paid.
+fn order_status_value(status: OrderStatus, financial_status: FinancialStatus) -> (&'static str, bool) {
+    match (status, financial_status) {
+        (OrderStatus::Cancelled, _) => ("cancelled", false),
+        (OrderStatus::Closed, FinancialStatus::Refunded) => ("refunded", false),
+        (OrderStatus::Closed, _) => ("completed", true),
+        (_, FinancialStatus::Paid) => ("processing", true),
+        (_, FinancialStatus::Authorized) => ("on-hold", false),
+        (_, FinancialStatus::Failed) => ("failed", false),
+        (_, FinancialStatus::Voided) => ("cancelled", false),
+        _ => ("pending", false),
+    }
+}
+
+pub fn order_states(status: &str) -> (OrderStatus, FinancialStatus, Option<FulfillmentStatus>) {
+    match status {
+        "on-hold" => (OrderStatus::Open, FinancialStatus::Authorized, Some(FulfillmentStatus::Unfulfilled)),
+        "processing" => (OrderStatus::Open, FinancialStatus::Paid, Some(FulfillmentStatus::Unfulfilled)),
+        "completed" => (OrderStatus::Closed, FinancialStatus::Paid, Some(FulfillmentStatus::Fulfilled)),
+        "cancelled" | "trash" => (OrderStatus::Cancelled, FinancialStatus::Voided, None),
+        "refunded" => (OrderStatus::Closed, FinancialStatus::Refunded, None),
+        "failed" => (OrderStatus::Open, FinancialStatus::Failed, Some(FulfillmentStatus::Unfulfilled)),
+        _ => (OrderStatus::Open, FinancialStatus::Pending, Some(FulfillmentStatus::Unfulfilled)),
+    }
+}
+
+fn statuses_for(filter: &OrderFilter) -> Vec<&'static str> {
+    ORDER_STATUSES
+        .iter()
+        .copied()
+        .filter(|status| {
+            let (order_status, financial, fulfillment) = order_states(status);
+            filter.status.is_none_or(|s| s == order_status)
+                && filter.financial_status.is_none_or(|f| match f {
+                    FinancialStatus::PartiallyRefunded => financial == FinancialStatus::Paid,
+                    f => f == financial,
+                })
+                && filter.fulfillment_status.is_none_or(|f| Some(f) == fulfillment)
+        })
+        .collect()
+}
+
+fn order_matches(order: &Order, filter: &OrderFilter) -> bool {
+    filter.status.is_none_or(|s| s == order.status)
+        && filter.financial_status.is_none_or(|f| f == order.financial_status)
+        && filter.fulfillment_status.is_none_or(|f| Some(f) == order.fulfillment_status)
+}
+
+fn product_status_value(status: ProductStatus) -> &'static str {
+    match status {
+        ProductStatus::Active => "publish",
+        ProductStatus::Draft => "draft",
+        ProductStatus::Archived => "private",
+    }
+}
+
+fn numeric_id(id: &str) -> Result<i64> {
+    id.parse()
+        .map_err(|_| Error::Validation(format!("Invalid WooCommerce id: {}", id)))
+}
+
+fn amount(value: &str) -> f64 {
+    value.parse().unwrap_or_default()
+}
+
+fn optional_amount(value: &str) -> Option<f64> {
+    value.parse().ok()
+}
+
+fn non_empty(value: String) -> Option<String> {
+    (!value.is_empty()).then_some(value)
+}
+
+fn parse_gmt(value: Option<String>) -> Option<DateTime<Utc>> {
+    value
+        .and_then(|v| NaiveDateTime::parse_from_str(&v, "%Y-%m-%dT%H:%M:%S").ok())
+        .map(|d| d.and_utc())
+}
+
+fn price_fields(map: &mut Map<String, Value>, variant: &ProductVariant) {
+    match variant.compare_at_price.filter(|c| *c > variant.price) {
+        Some(regular) => {
+            map.insert("regular_price".to_string(), json!(regular.to_string()));
+            map.insert("sale_price".to_string(), json!(variant.price.to_string()));
+        }
+        None => {
+            map.insert("regular_price".to_string(), json!(variant.price.to_string()));
+            map.insert("sale_price".to_string(), json!(""));
+        }
+    }
+    if let Some(sku) = &variant.sku {
+        map.insert("sku".to_string(), json!(sku));
+    }
+    if let Some(weight) = variant.weight {
+        map.insert("weight".to_string(), json!(weight.to_string()));
+    }
+    if let Some(quantity) = variant.inventory_quantity {
+        map.insert("manage_stock".to_string(), json!(true));
+        map.insert("stock_quantity".to_string(), json!(quantity));
+    }
+    if let Some(barcode) = &variant.barcode {
+        map.insert("global_unique_id".to_string(), json!(barcode));
+    }
+}
+
+fn product_input(product: &Product) -> Map<String, Value> {
+    let mut input = Map::new();
+    input.insert("name".to_string(), json!(product.title));
+    input.insert("status".to_string(), json!(product_status_value(product.status)));
+    let product_type = if product.options.is_empty() {
+        product.product_type.as_deref().unwrap_or("simple")
+    } else {
+        "variable"
+    };
+    input.insert("type".to_string(), json!(product_type));
+    if let Some(handle) = &product.handle {
+        input.insert("slug".to_string(), json!(handle));
+    }
+    if let Some(description) = &product.description {
+        input.insert("description".to_string(), json!(description));
+    }
+    if let Some(short) = product.extra.get("short_description") {
+        input.insert("short_description".to_string(), short.clone());
+    }
+    if !product.images.is_empty() {
+        let images: Vec<Value> = product
+            .images
+            .iter()
+            .map(|i| json!({ "src": i.src, "alt": i.alt.clone().unwrap_or_default() }))
+            .collect();
+        input.insert("images".to_string(), json!(images));
+    }
+    if !product.options.is_empty() {
+        let attributes: Vec<Value> = product
+            .options
+            .iter()
+            .map(|o| json!({ "name": o.name, "options": o.values, "visible": true, "variation": true }))
+            .collect();
+        input.insert("attributes".to_string(), json!(attributes));
+    } else if let Some(variant) = product.variants.first() {
+        price_fields(&mut input, variant);
+    }
+    input
+}
+
+fn variation_input(variant: &ProductVariant) -> Value {
+    let mut input = Map::new();
+    price_fields(&mut input, variant);
+    let attributes: Vec<Value> = variant
+        .options
+        .iter()
+        .map(|(name, option)| json!({ "name": name, "option": option }))
+        .collect();
+    input.insert("attributes".to_string(), json!(attributes));
+    Value::Object(input)
+}
+
+fn address_input(address: &Address) -> Value {
+    json!({
+        "first_name": address.first_name.clone().unwrap_or_default(),
+        "last_name": address.last_name.clone().unwrap_or_default(),
+        "company": address.company.clone().unwrap_or_default(),
+        "address_1": address.address1.clone().unwrap_or_default(),
+        "address_2": address.address2.clone().unwrap_or_default(),
+        "city": address.city.clone().unwrap_or_default(),
+        "state": address.province_code.clone().or_else(|| address.province.clone()).unwrap_or_default(),
+        "postcode": address.zip.clone().unwrap_or_default(),
+        "country": address.country_code.clone().or_else(|| address.country.clone()).unwrap_or_default(),
+        "phone": address.phone.clone().unwrap_or_default(),
+    })
+}
+
+fn customer_input(customer: &Customer) -> Value {
+    let mut input = Map::new();
+    if let Some(email) = &customer.email {
+        input.insert("email".to_string(), json!(email));
+    }
+    if let Some(first_name) = &customer.first_name {
+        input.insert("first_name".to_string(), json!(first_name));
+    }
+    if let Some(last_name) = &customer.last_name {
+        input.insert("last_name".to_string(), json!(last_name));
+    }
+    let mut addresses = customer.addresses.iter().map(address_input);
+    if let Some(mut billing) = addresses.next() {
+        if let Some(email) = &customer.email {
+            billing["email"] = json!(email);
+        }
+        if let Some(phone) = &customer.phone {
+            billing["phone"] = json!(phone);
+        }
+        input.insert("billing".to_string(), billing);
+    } else if let Some(phone) = &customer.phone {
+        input.insert("billing".to_string(), json!({ "phone": phone }));
+    }
+    if let Some(shipping) = addresses.next() {
+        input.insert("shipping".to_string(), shipping);
+    }
+    Value::Object(input)
+}
+
+fn to_product(product: WooProduct, variations: Vec<WooVariation>) -> Product {
+    let mut extra = HashMap::new();
+    if let Some(permalink) = &product.permalink {
+        extra.insert("permalink".to_string(), json!(permalink));
+    }
+    if !product.short_description.is_empty() {
+        extra.insert("short_description".to_string(), json!(product.short_description));
+    }
+    if !product.variations.is_empty() {
+        extra.insert("variation_ids".to_string(), json!(product.variations));
+    }
+    if !product.categories.is_empty() {
+        extra.insert(
+            "category_ids".to_string(),
+            json!(product.categories.iter().map(|c| c.id.to_string()).collect::<Vec<_>>()),
+        );
+    }
+
+    let variants = if product.product_type == "variable" {
+        variations.into_iter().map(to_variant).collect()
+    } else {
+        vec![ProductVariant {
+            id: product.id.to_string(),
+            title: "Default".to_string(),
+            sku: non_empty(product.sku.clone()),
+            price: amount(&product.price),
+            compare_at_price: optional_amount(&product.regular_price).filter(|r| *r > amount(&product.price)),
+            inventory_quantity: product.stock_quantity,
+            weight: optional_amount(&product.weight),
+            weight_unit: None,
+            options: HashMap::new(),
+            barcode: product.global_unique_id.clone().and_then(non_empty),
+        }]
+    };
+
+    Product {
+        id: product.id.to_string(),
+        title: product.name,
+        description: non_empty(product.description),
+        handle: non_empty(product.slug),
+        status: match product.status.as_str() {
+            "publish" => ProductStatus::Active,
+            "private" | "trash" => ProductStatus::Archived,
+            _ => ProductStatus::Draft,
+        },
+        vendor: None,
+        product_type: non_empty(product.product_type),
+        tags: product.tags.into_iter().map(|t| t.name).collect(),
+        variants,
+        images: product
+            .images
+            .into_iter()
+            .enumerate()
+            .map(|(position, image)| ProductImage {
+                id: image.id.to_string(),
+                src: image.src,
+                alt: image.alt.and_then(non_empty),
+                position: Some(position as i32 + 1),
+            })
+            .collect(),
+        options: product
+            .attributes
+            .into_iter()
+            .map(|a| ProductOption {
+                name: a.name,
+                values: a.options,
+            })
+            .collect(),
+        created_at: parse_gmt(product.date_created_gmt),
+        updated_at: parse_gmt(product.date_modified_gmt),
+        extra,
+    }
+}
+
+fn to_variant(variation: WooVariation) -> ProductVariant {
+    let title = variation
+        .attributes
+        .iter()
+        .map(|a| a.option.as_str())
+        .collect::<Vec<_>>()
+        .join(" / ");
+    ProductVariant {
+        id: variation.id.to_string(),
+        title,
+        sku: non_empty(variation.sku),
+        price: amount(&variation.price),
+        compare_at_price: optional_amount(&variation.regular_price).filter(|r| *r > amount(&variation.price)),
+        inventory_quantity: variation.stock_quantity,
+        weight: optional_amount(&variation.weight),
+        weight_unit: None,
+        options: variation.attributes.into_iter().map(|a| (a.name, a.option)).collect(),
+        barcode: variation.global_unique_id.and_then(non_empty),
+    }
+}
+
+fn to_inventory(product: &WooProduct) -> InventoryItem {
+    InventoryItem {
+        id: product.id.to_string(),
+        sku: non_empty(product.sku.clone()),
+        inventory_quantity: product.stock_quantity.unwrap_or_default(),
+        location_id: None,
+    }
+}
+
+fn to_address(address: WooAddress) -> Option<Address> {
+    let address = Address {
+        first_name: non_empty(address.first_name),
+        last_name: non_empty(address.last_name),
+        company: non_empty(address.company),
+        address1: non_empty(address.address_1),
+        address2: non_empty(address.address_2),
+        city: non_empty(address.city),
+        province: None,
+        province_code: non_empty(address.state),
+        country: None,
+        country_code: non_empty(address.country),
+        zip: non_empty(address.postcode),
+        phone: address.phone.and_then(non_empty),
+    };
+    (address.address1.is_some() || address.first_name.is_some() || address.last_name.is_some()).then_some(address)
+}
+
+fn to_order(order: WooOrder) -> Order {
+    let (status, mut financial_status, fulfillment_status) = order_states(&order.status);
+    if financial_status == FinancialStatus::Paid && !order.refunds.is_empty() {
+        financial_status = FinancialStatus::PartiallyRefunded;
+    }
+
+    let mut extra = HashMap::new();
+    extra.insert("woocommerce_status".to_string(), json!(order.status));
+    extra.insert("shipping_total".to_string(), json!(amount(&order.shipping_total)));
+    if let Some(method) = order.payment_method_title.as_ref().filter(|m| !m.is_empty()) {
+        extra.insert("payment_method".to_string(), json!(method));
+    }
+
+    let email = order.billing.email.clone().and_then(non_empty);
+    let phone = order.billing.phone.clone().and_then(non_empty);
+    let customer = (order.customer_id != 0).then(|| Customer {
+        id: order.customer_id.to_string(),
+        email: email.clone(),
+        first_name: non_empty(order.billing.first_name.clone()),
+        last_name: non_empty(order.billing.last_name.clone()),
+        phone: phone.clone(),
+        orders_count: None,
+        total_spent: None,
+        tags: Vec::new(),
+        addresses: Vec::new(),
+        created_at: None,
+        updated_at: None,
+    });
+
+    let subtotal = order.line_items.iter().map(|i| amount(&i.subtotal)).sum();
+    Order {
+        id: order.id.to_string(),
+        order_number: Some(order.number),
+        email,
+        phone,
+        status,
+        financial_status,
+        fulfillment_status,
+        currency: order.currency,
+        subtotal_price: subtotal,
+        total_tax: amount(&order.total_tax),
+        total_discounts: amount(&order.discount_total),
+        total_price: amount(&order.total),
+        line_items: order
+            .line_items
+            .into_iter()
+            .map(|item| LineItem {
+                id: item.id.to_string(),
+                product_id: (item.product_id != 0).then(|| item.product_id.to_string()),
+                variant_id: (item.variation_id != 0).then(|| item.variation_id.to_string()),
+                title: item.name,
+                quantity: item.quantity,
+                price: item.price,
+                sku: item.sku.and_then(non_empty),
+                total_discount: Some(amount(&item.subtotal) - amount(&item.total)).filter(|d| *d > 0.0),
+            })
+            .collect(),
+        shipping_address: to_address(order.shipping),
+        billing_address: to_address(order.billing),
+        customer,
+        note: non_empty(order.customer_note),
+        created_at: parse_gmt(order.date_created_gmt).unwrap_or_default(),
+        updated_at: parse_gmt(order.date_modified_gmt),
+        extra,
+    }
+}
+
+fn to_customer(customer: WooCustomer) -> Customer {
+    let phone = customer.billing.phone.clone().and_then(non_empty);
+    Customer {
+        id: customer.id.to_string(),
+        email: non_empty(customer.email),
+        first_name: non_empty(customer.first_name),
+        last_name: non_empty(customer.last_name),
+        phone,
+        orders_count: customer.orders_count,
+        total_spent: customer.total_spent.as_deref().and_then(optional_amount),
+        tags: Vec::new(),
+        addresses: [to_address(customer.billing), to_address(customer.shipping)]
+            .into_iter()
+            .flatten()
+            .collect(),
+        created_at: parse_gmt(customer.date_created_gmt),
+        updated_at: parse_gmt(customer.date_modified_gmt),
+    }
+}
+
+fn to_collection(category: WooCategory) -> Collection {
+    Collection {
+        id: category.id.to_string(),
+        title: category.name,
+        description: non_empty(category.description),
+        handle: non_empty(category.slug),
+        image: category.image.map(|image| ProductImage {
+            id: image.id.to_string(),
+            src: image.src,
+            alt: image.alt.and_then(non_empty),
+            position: None,
+        }),
+        products_count: Some(category.count),
+    }
+}
+
+#[cfg(feature = "webhooks")]
+pub(crate) fn order_from_value(value: Value) -> Result<Order> {
+    Ok(to_order(serde_json::from_value(value)?))
+}
+
+#[cfg(feature = "webhooks")]
+pub(crate) fn product_from_value(value: Value) -> Result<Product> {
+    Ok(to_product(serde_json::from_value(value)?, Vec::new()))
+}
+
+#[cfg(feature = "webhooks")]
+pub(crate) fn customer_from_value(value: Value) -> Result<Customer> {
+    Ok(to_customer(serde_json::from_value(value)?))
+}
+
+fn string_or_number<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<String, D::Error> {
+    Ok(match Option::<Value>::deserialize(deserializer)? {
+        Some(Value::String(s)) => s,
+        Some(Value::Number(n)) => n.to_string(),
+        _ => String::new(),
+    })
+}
+
+fn number<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<f64, D::Error> {
+    Ok(amount(&string_or_number(deserializer)?))
+}
+
+#[derive(Debug, Deserialize)]
+struct WooError {
+    code: String,
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct WooTerm {
+    id: i64,
+    #[serde(default)]
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct WooImage {
+    #[serde(default)]
+    id: i64,
+    src: String,
+    alt: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WooAttribute {
+    name: String,
+    #[serde(default)]
+    options: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WooProduct {
+    id: i64,
+    #[serde(default)]
+    parent_id: i64,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    slug: String,
+    #[serde(default)]
+    status: String,
+    #[serde(rename = "type", default)]
+    product_type: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    short_description: String,
+    #[serde(default)]
+    sku: String,
+    #[serde(default, deserialize_with = "string_or_number")]
+    price: String,
+    #[serde(default, deserialize_with = "string_or_number")]
+    regular_price: String,
+    #[serde(default, deserialize_with = "string_or_number")]
+    weight: String,
+    stock_quantity: Option<i32>,
+    global_unique_id: Option<String>,
+    permalink: Option<String>,
+    #[serde(default)]
+    categories: Vec<WooTerm>,
+    #[serde(default)]
+    tags: Vec<WooTerm>,
+    #[serde(default)]
+    images: Vec<WooImage>,
+    #[serde(default)]
+    attributes: Vec<WooAttribute>,
+    #[serde(default)]
+    variations: Vec<i64>,
+    date_created_gmt: Option<String>,
+    date_modified_gmt: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WooVariation {
+    id: i64,
+    #[serde(default)]
+    sku: String,
+    #[serde(default, deserialize_with = "string_or_number")]
+    price: String,
+    #[serde(default, deserialize_with = "string_or_number")]
+    regular_price: String,
+    #[serde(default, deserialize_with = "string_or_number")]
+    weight: String,
+    stock_quantity: Option<i32>,
+    global_unique_id: Option<String>,
+    #[serde(default)]
+    attributes: Vec<WooVariationAttribute>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WooVariationAttribute {
+    name: String,
+    option: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+struct WooAddress {
+    first_name: String,
+    last_name: String,
+    company: String,
+    address_1: String,
+    address_2: String,
+    city: String,
+    state: String,
+    postcode: String,
+    country: String,
+    email: Option<String>,
+    phone: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WooLineItem {
+    id: i64,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    product_id: i64,
+    #[serde(default)]
+    variation_id: i64,
+    quantity: u32,
+    #[serde(default, deserialize_with = "number")]
+    price: f64,
+    sku: Option<String>,
+    #[serde(default, deserialize_with = "string_or_number")]
+    subtotal: String,
+    #[serde(default, deserialize_with = "string_or_number")]
+    total: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct WooOrder {
+    id: i64,
+    #[serde(default, deserialize_with = "string_or_number")]
+    number: String,
+    status: String,
+    #[serde(default)]
+    currency: String,
+    #[serde(default)]
+    customer_id: i64,
+    #[serde(default)]
+    billing: WooAddress,
+    #[serde(default)]
+    shipping: WooAddress,
+    #[serde(default)]
+    line_items: Vec<WooLineItem>,
+    #[serde(default, deserialize_with = "string_or_number")]
+    total: String,
+    #[serde(default, deserialize_with = "string_or_number")]
+    total_tax: String,
+    #[serde(default, deserialize_with = "string_or_number")]
+    discount_total: String,
+    #[serde(default, deserialize_with = "string_or_number")]
+    shipping_total: String,
+    #[serde(default)]
+    customer_note: String,
+    payment_method_title: Option<String>,
+    #[serde(default)]
+    refunds: Vec<Value>,
+    date_created_gmt: Option<String>,
+    date_modified_gmt: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WooCustomer {
+    id: i64,
+    #[serde(default)]
+    email: String,
+    #[serde(default)]
+    first_name: String,
+    #[serde(default)]
+    last_name: String,
+    #[serde(default)]
+    billing: WooAddress,
+    #[serde(default)]
+    shipping: WooAddress,
+    orders_count: Option<u32>,
+    total_spent: Option<String>,
+    date_created_gmt: Option<String>,
+    date_modified_gmt: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WooCategory {
+    id: i64,
+    name: String,
+    #[serde(default)]
+    slug: String,
+    #[serde(default)]
+    description: String,
+    image: Option<WooImage>,
+    #[serde(default)]
+    count: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_oauth_signature() {
+        let params = vec![
+            ("per_page".to_string(), "10".to_string()),
+            ("oauth_consumer_key".to_string(), "ck_test".to_string()),
+            ("oauth_nonce".to_string(), "abc 123".to_string()),
+            ("oauth_signature_method".to_string(), "HMAC-SHA256".to_string()),
+            ("oauth_timestamp".to_string(), "1700000000".to_string()),
+        ];
+        let signature = oauth_signature("get", "http://shop.test/wp-json/wc/v3/products", &params, "cs_secret");
+        assert_eq!(signature, "h6Q97UZNPYRXsBbyBJZpCB6RVFq4LAhX+XJDGZVzG1U=");
+        assert_eq!(oauth_encode("a b/c~"), "a%20b%2Fc~");
+    }
+
+    #[test]
+    fn test_order_status_mapping() {
+        let paid = OrderFilter {
+            financial_status: Some(FinancialStatus::Paid),
+            ..Default::default()
+        };
+        assert_eq!(statuses_for(&paid), vec!["processing", "completed"]);
+
+        let partial = OrderFilter {
+            fulfillment_status: Some(FulfillmentStatus::Partial),
+            ..Default::default()
+        };
+        assert!(statuses_for(&partial).is_empty());
+
+        assert_eq!(order_states("failed").1, FinancialStatus::Failed);
+        let failed = OrderFilter {
+            financial_status: Some(FinancialStatus::Failed),
+            ..Default::default()
+        };
+        assert_eq!(statuses_for(&failed), vec!["failed"]);
+
+        let authorized = OrderFilter {
+            financial_status: Some(FinancialStatus::Authorized),
+            ..Default::default()
+        };
+        assert_eq!(statuses_for(&authorized), vec!["on-hold"]);
+
+        for (status, financial) in [
+            (OrderStatus::Open, FinancialStatus::Pending),
+            (OrderStatus::Open, FinancialStatus::Authorized),
+            (OrderStatus::Open, FinancialStatus::Paid),
+            (OrderStatus::Open, FinancialStatus::Failed),
+            (OrderStatus::Closed, FinancialStatus::Paid),
+            (OrderStatus::Closed, FinancialStatus::Refunded),
+            (OrderStatus::Cancelled, FinancialStatus::Voided),
+        ] {
+            let (value, _) = order_status_value(status, financial);
+            let (read_status, read_financial, _) = order_states(value);
+            assert_eq!((read_status, read_financial), (status, financial), "{}", value);
+        }
+
+        let order: WooOrder = serde_json::from_value(json!({
+            "id": 727,
+            "number": "727",
+            "status": "completed",
+            "currency": "USD",
+            "customer_id": 0,
+            "billing": { "first_name": "Ada", "email": "ada@example.com", "address_1": "1 Main St" },
+            "line_items": [{ "id": 1, "name": "Tee", "product_id": 9, "variation_id": 0, "quantity": 2, "price": 10, "subtotal": "20.00", "total": "18.00" }],
+            "total": "18.00",
+            "refunds": [{ "id": 5, "total": "-2.00" }],
+            "date_created_gmt": "2024-03-05T12:00:00"
+        }))
+        .unwrap();
+        let order = to_order(order);
+        assert_eq!(order.status, OrderStatus::Closed);
+        assert_eq!(order.financial_status, FinancialStatus::PartiallyRefunded);
+        assert_eq!(order.fulfillment_status, Some(FulfillmentStatus::Fulfilled));
+        assert_eq!(order.subtotal_price, 20.0);
+        assert_eq!(order.line_items[0].total_discount, Some(2.0));
+        assert_eq!(order.line_items[0].variant_id, None);
+        assert!(order.customer.is_none());
+        assert_eq!(order.email.as_deref(), Some("ada@example.com"));
+        assert_eq!(order.created_at.to_rfc3339(), "2024-03-05T12:00:00+00:00");
+    }
+}
@@ -0,0 +1,114 @@
+use crate::{AnalyticsProvider, Error, Event, Result, UserProfile};
+use async_trait::async_trait;
+use reqwest::{Client, Response};
+use serde_json::{json, Value};
+
+const AMPLITUDE_API_URL: &str = "https://api2.amplitude.com";
+pub const AMPLITUDE_EU_API_URL: &str = "https://api.eu.amplitude.com";
+
+const MAX_BATCH_SIZE: usize = 2000;
+
+#[derive(Clone)]
+pub struct AmplitudeClient {
+    api_key: String,
+    base_url: String,
+    client: Client,
+}
+
+impl AmplitudeClient {
+    pub fn new(api_key: &str) -> Self {
+        Self {
+            api_key: api_key.to_string(),
+            base_url: AMPLITUDE_API_URL.to_string(),
+            client: Client::new(),
+        }
+    }
+
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    async fn post_form(&self, path: &str, field: &str, payload: &Value) -> Result<()> {
+        let response = self
+            .client
+            .post(format!("{}{}", self.base_url, path))
+            .form(&[("api_key", self.api_key.as_str()), (field, &payload.to_string())])
+            .send()
+            .await?;
+        read(response).await
+    }
+}
+
+#[async_trait]
+impl AnalyticsProvider for AmplitudeClient {
+    async fn track(&self, event: &Event) -> Result<()> {
+        self.track_batch(std::slice::from_ref(event)).await
+    }
+
+    async fn track_batch(&self, events: &[Event]) -> Result<()> {
+        for chunk in events.chunks(MAX_BATCH_SIZE) {
+            let events = chunk.iter().map(to_event).collect::<Result<Vec<_>>>()?;
+            let response = self
+                .client
+                .post(format!("{}/2/httpapi", self.base_url))
+                .json(&json!({ "api_key": self.api_key, "events": events }))
+                .send()
+                .await?;
+            read(response).await?;
+        }
+        Ok(())
+    }
+
+    async fn identify(&self, profile: &UserProfile) -> Result<()> {
+        let identification = json!([{
+            "user_id": profile.distinct_id,
+            "user_properties": { "$set": profile.properties },
+        }]);
+        self.post_form("/identify", "identification", &identification).await
+    }
+
+    // Amplitude has no alias event; the User Mapping API links the alias to the
+    // global user instead.
+    async fn alias(&self, distinct_id: &str, alias: &str) -> Result<()> {
+        let mapping = json!([{ "user_id": alias, "global_user_id": distinct_id }]);
+        self.post_form("/usermap", "mapping", &mapping).await
+    }
+}
+
+fn to_event(event: &Event) -> Result<Value> {
+    let user_id = event
+        .distinct_id
+        .as_deref()
+        .ok_or_else(|| Error::InvalidRequest(format!("Event {} has no distinct_id", event.name)))?;
+    let mut payload = json!({
+        "user_id": user_id,
+        "event_type": event.name,
+        "event_properties": event.properties,
+    });
+    if let Some(timestamp) = event.timestamp {
+        payload["time"] = json!(timestamp.timestamp_millis());
+    }
+    Ok(payload)
+}
+
+async fn read(response: Response) -> Result<()> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(());
+    }
+
+    let text = response.text().await.unwrap_or_default();
+    let message = serde_json::from_str::<Value>(&text)
+        .ok()
+        .and_then(|body| body["error"].as_str().map(String::from))
+        .unwrap_or(text);
+    Err(match status.as_u16() {
+        401 | 403 => Error::Auth(message),
+        429 => Error::RateLimited,
+        _ => Error::Api {
+            message,
+            code: Some(status.to_string()),
+        },
+    })
+}
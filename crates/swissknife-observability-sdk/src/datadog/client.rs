@@ -0,0 +1,196 @@
+use crate::{Error, Result, TimeRange};
+use reqwest::{Client, RequestBuilder, Response};
+use serde::Serialize;
+use serde_json::Value;
+
+const DEFAULT_AGENT_URL: &str = "http://localhost:8126";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DatadogSite {
+    #[default]
+    Us1,
+    Us3,
+    Us5,
+    Eu1,
+    Ap1,
+    Us1Fed,
+}
+
+impl DatadogSite {
+    pub fn domain(&self) -> &'static str {
+        match self {
+            DatadogSite::Us1 => "datadoghq.com",
+            DatadogSite::Us3 => "us3.datadoghq.com",
+            DatadogSite::Us5 => "us5.datadoghq.com",
+            DatadogSite::Eu1 => "datadoghq.eu",
+            DatadogSite::Ap1 => "ap1.datadoghq.com",
+            DatadogSite::Us1Fed => "ddog-gov.com",
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct DatadogClient {
+    api_key: String,
+    app_key: String,
+    api_url: String,
+    logs_url: String,
+    app_url: String,
+    agent_url: String,
+    client: Client,
+}
+
+impl DatadogClient {
+    pub fn new(api_key: &str, app_key: &str) -> Self {
+        Self {
+            api_key: api_key.to_string(),
+            app_key: app_key.to_string(),
+            api_url: String::new(),
+            logs_url: String::new(),
+            app_url: String::new(),
+            agent_url: DEFAULT_AGENT_URL.to_string(),
+            client: Client::new(),
+        }
+        .with_site(DatadogSite::default())
+    }
+
+    pub fn with_site(mut self, site: DatadogSite) -> Self {
+        let domain = site.domain();
+        self.api_url = format!("https://api.{}", domain);
+        self.logs_url = format!("https://http-intake.logs.{}", domain);
+        self.app_url = format!("https://app.{}", domain);
+        self
+    }
+
+    // A custom base URL (proxy, mock server) serves both the API and log intake.
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        let base_url = base_url.trim_end_matches('/').to_string();
+        self.app_url = base_url.replacen("://api.", "://app.", 1);
+        self.logs_url = base_url.clone();
+        self.api_url = base_url;
+        self
+    }
+
+    pub fn with_agent_url(mut self, agent_url: &str) -> Self {
+        self.agent_url = agent_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    pub(crate) fn client(&self) -> &Client {
+        &self.client
+    }
+
+    pub(crate) fn api_url(&self) -> &str {
+        &self.api_url
+    }
+
+    pub(crate) fn logs_url(&self) -> &str {
+        &self.logs_url
+    }
+
+    pub(crate) fn app_url(&self) -> &str {
+        &self.app_url
+    }
+
+    pub(crate) fn agent_url(&self) -> &str {
+        &self.agent_url
+    }
+
+    pub(crate) async fn send(&self, request: RequestBuilder) -> Result<Value> {
+        let response = request
+            .header("DD-API-KEY", &self.api_key)
+            .header("DD-APPLICATION-KEY", &self.app_key)
+            .send()
+            .await?;
+        read(response).await
+    }
+
+    pub async fn send_event(
+        &self,
+        title: &str,
+        text: &str,
+        alert_type: Option<&str>,
+        tags: Option<&[String]>,
+    ) -> Result<()> {
+        let event = EventRequest {
+            title,
+            text,
+            alert_type,
+            tags: tags.unwrap_or_default(),
+        };
+        self.send(self.client.post(format!("{}/api/v1/events", self.api_url)).json(&event))
+            .await?;
+        Ok(())
+    }
+}
+
+pub(crate) async fn read(response: Response) -> Result<Value> {
+    let status = response.status();
+    let text = response.text().await?;
+
+    if !status.is_success() {
+        let message = serde_json::from_str::<Value>(&text)
+            .ok()
+            .and_then(|body| error_message(&body))
+            .unwrap_or(text);
+        return Err(match status.as_u16() {
+            401 | 403 => Error::Auth(message),
+            429 => Error::RateLimited,
+            _ => Error::Api {
+                message,
+                code: Some(status.to_string()),
+            },
+        });
+    }
+
+    if text.trim().is_empty() {
+        return Ok(Value::Null);
+    }
+    Ok(serde_json::from_str(&text)?)
+}
+
+// v1 endpoints return `errors` as strings, v2 endpoints as JSON:API error objects.
+fn error_message(body: &Value) -> Option<String> {
+    let errors: Vec<String> = body["errors"]
+        .as_array()?
+        .iter()
+        .filter_map(|e| match e {
+            Value::String(s) => Some(s.clone()),
+            e => e["detail"].as_str().or_else(|| e["title"].as_str()).map(String::from),
+        })
+        .collect();
+    (!errors.is_empty()).then(|| errors.join("; "))
+}
+
+pub(crate) fn time_bounds(time_range: &TimeRange, default_from: &str) -> (String, String) {
+    (
+        time_range.start.map(|t| t.to_rfc3339()).unwrap_or_else(|| default_from.to_string()),
+        time_range.end.map(|t| t.to_rfc3339()).unwrap_or_else(|| "now".to_string()),
+    )
+}
+
+pub(crate) fn format_tags<'a>(tags: impl IntoIterator<Item = (&'a String, &'a String)>) -> Vec<String> {
+    let mut tags: Vec<String> = tags
+        .into_iter()
+        .map(|(k, v)| if v.is_empty() { k.clone() } else { format!("{}:{}", k, v) })
+        .collect();
+    tags.sort();
+    tags
+}
+
+pub(crate) fn parse_tag(tag: &str) -> (String, String) {
+    match tag.split_once(':') {
+        Some((k, v)) => (k.to_string(), v.to_string()),
+        None => (tag.to_string(), String::new()),
+    }
+}
+
+#[derive(Serialize)]
+struct EventRequest<'a> {
+    title: &'a str,
+    text: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    alert_type: Option<&'a str>,
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    tags: &'a [String],
+}
@@ -0,0 +1,86 @@
+use crate::datadog::DatadogClient;
+use crate::{Dashboard, DashboardProvider, Result, Widget};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::Value;
+
+#[async_trait]
+impl DashboardProvider for DatadogClient {
+    async fn list_dashboards(&self) -> Result<Vec<Dashboard>> {
+        let response = self
+            .send(self.client().get(format!("{}/api/v1/dashboard", self.api_url())))
+            .await?;
+        let response: DashboardList = serde_json::from_value(response)?;
+        Ok(response.dashboards.into_iter().map(|d| self.to_dashboard(d)).collect())
+    }
+
+    async fn get_dashboard(&self, id: &str) -> Result<Dashboard> {
+        let response = self
+            .send(self.client().get(format!("{}/api/v1/dashboard/{}", self.api_url(), id)))
+            .await?;
+        Ok(self.to_dashboard(serde_json::from_value(response)?))
+    }
+}
+
+impl DatadogClient {
+    fn to_dashboard(&self, dashboard: DatadogDashboard) -> Dashboard {
+        let mut widgets = Vec::new();
+        flatten_widgets(&dashboard.widgets, &mut widgets);
+        Dashboard {
+            id: dashboard.id,
+            title: dashboard.title,
+            description: dashboard.description.filter(|d| !d.is_empty()),
+            url: dashboard.url.map(|url| format!("{}{}", self.app_url(), url)),
+            widgets,
+        }
+    }
+}
+
+// Group widgets nest their children under `definition.widgets`.
+fn flatten_widgets(widgets: &[Value], out: &mut Vec<Widget>) {
+    for widget in widgets {
+        let definition = &widget["definition"];
+        let widget_type = definition["type"].as_str().unwrap_or_default();
+        if let Some(children) = definition["widgets"].as_array() {
+            flatten_widgets(children, out);
+            continue;
+        }
+        out.push(Widget {
+            id: widget["id"].as_i64().map(|id| id.to_string()).unwrap_or_default(),
+            title: definition["title"].as_str().unwrap_or_default().to_string(),
+            widget_type: widget_type.to_string(),
+            query: first_query(&definition["requests"]),
+        });
+    }
+}
+
+// Widget requests are either a list or keyed by axis, and carry their query as
+// a legacy `q` string or a list of `queries`.
+fn first_query(requests: &Value) -> Option<String> {
+    match requests {
+        Value::Array(items) => items.iter().find_map(first_query),
+        Value::Object(map) => map
+            .get("q")
+            .or_else(|| map.get("query"))
+            .and_then(Value::as_str)
+            .map(String::from)
+            .or_else(|| map.values().find_map(first_query)),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct DashboardList {
+    #[serde(default)]
+    dashboards: Vec<DatadogDashboard>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DatadogDashboard {
+    id: String,
+    title: String,
+    description: Option<String>,
+    url: Option<String>,
+    #[serde(default)]
+    widgets: Vec<Value>,
+}
@@ -0,0 +1,120 @@
+use crate::datadog::client::time_bounds;
+use crate::datadog::DatadogClient;
+use crate::{LogEntry, LogLevel, LoggingProvider, Result, TimeRange};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+const MAX_LOGS_PER_REQUEST: usize = 1000;
+
+#[async_trait]
+impl LoggingProvider for DatadogClient {
+    async fn send_logs(&self, logs: &[LogEntry]) -> Result<()> {
+        for chunk in logs.chunks(MAX_LOGS_PER_REQUEST) {
+            let payload: Vec<Value> = chunk.iter().map(to_intake).collect();
+            self.send(self.client().post(format!("{}/api/v2/logs", self.logs_url())).json(&payload))
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn query_logs(&self, query: &str, time_range: &TimeRange, limit: u32) -> Result<Vec<LogEntry>> {
+        let (from, to) = time_bounds(time_range, "now-15m");
+        let body = json!({
+            "filter": { "query": query, "from": from, "to": to },
+            "page": { "limit": limit.min(MAX_LOGS_PER_REQUEST as u32) },
+            "sort": "-timestamp",
+        });
+        let response = self
+            .send(self.client().post(format!("{}/api/v2/logs/events/search", self.api_url())).json(&body))
+            .await?;
+        let response: LogsResponse = serde_json::from_value(response)?;
+        Ok(response.data.into_iter().map(|log| from_search(log.attributes)).collect())
+    }
+}
+
+fn to_intake(entry: &LogEntry) -> Value {
+    let mut log: Map<String, Value> = entry.attributes.clone().into_iter().collect();
+    log.insert("message".to_string(), json!(entry.message));
+    log.insert("status".to_string(), json!(status(entry.level)));
+    if let Some(timestamp) = entry.timestamp {
+        log.insert("timestamp".to_string(), json!(timestamp.timestamp_millis()));
+    }
+    if let Some(service) = &entry.service {
+        log.insert("service".to_string(), json!(service));
+    }
+    if let Some(source) = &entry.source {
+        log.insert("ddsource".to_string(), json!(source));
+    }
+    Value::Object(log)
+}
+
+fn status(level: LogLevel) -> &'static str {
+    match level {
+        LogLevel::Debug => "debug",
+        LogLevel::Info => "info",
+        LogLevel::Warn => "warning",
+        LogLevel::Error => "error",
+        LogLevel::Fatal => "critical",
+    }
+}
+
+fn level(status: &str) -> LogLevel {
+    match status.to_ascii_lowercase().as_str() {
+        "debug" | "trace" => LogLevel::Debug,
+        "warn" | "warning" => LogLevel::Warn,
+        "error" | "err" => LogLevel::Error,
+        "critical" | "crit" | "alert" | "emergency" | "emerg" | "fatal" => LogLevel::Fatal,
+        _ => LogLevel::Info,
+    }
+}
+
+fn from_search(log: LogAttributes) -> LogEntry {
+    let source = log
+        .tags
+        .iter()
+        .find_map(|t| t.strip_prefix("source:"))
+        .map(String::from);
+    let mut attributes = log.attributes;
+    if let Some(host) = log.host {
+        attributes.entry("host".to_string()).or_insert(json!(host));
+    }
+    if !log.tags.is_empty() {
+        attributes.insert("tags".to_string(), json!(log.tags));
+    }
+
+    LogEntry {
+        message: log.message.unwrap_or_default(),
+        level: level(log.status.as_deref().unwrap_or("info")),
+        timestamp: log.timestamp,
+        attributes,
+        service: log.service,
+        source,
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct LogsResponse {
+    #[serde(default)]
+    data: Vec<LogEvent>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LogEvent {
+    attributes: LogAttributes,
+}
+
+#[derive(Debug, Deserialize)]
+struct LogAttributes {
+    message: Option<String>,
+    status: Option<String>,
+    timestamp: Option<DateTime<Utc>>,
+    service: Option<String>,
+    host: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    attributes: HashMap<String, Value>,
+}
@@ -0,0 +1,200 @@
+use crate::datadog::client::{format_tags, parse_tag};
+use crate::datadog::DatadogClient;
+use crate::{Error, Metric, MetricQuery, MetricSeries, MetricType, MetricsProvider, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[async_trait]
+impl MetricsProvider for DatadogClient {
+    async fn submit_metrics(&self, metrics: &[Metric]) -> Result<()> {
+        let (distributions, series): (Vec<&Metric>, Vec<&Metric>) = metrics
+            .iter()
+            .partition(|m| matches!(m.metric_type, MetricType::Histogram | MetricType::Distribution));
+
+        if !series.is_empty() {
+            let payload = SeriesPayload { series: series.into_iter().map(to_series).collect() };
+            self.send(self.client().post(format!("{}/api/v2/series", self.api_url())).json(&payload))
+                .await?;
+        }
+
+        // Histograms are aggregated client-side by the agent; through the API the
+        // closest equivalent is a distribution, which keeps the raw values.
+        if !distributions.is_empty() {
+            let series: Vec<_> = distributions
+                .into_iter()
+                .map(|m| {
+                    json!({
+                        "metric": m.name,
+                        "points": [[timestamp(m), [m.value]]],
+                        "tags": format_tags(&m.tags),
+                    })
+                })
+                .collect();
+            self.send(
+                self.client()
+                    .post(format!("{}/api/v1/distribution_points", self.api_url()))
+                    .json(&json!({ "series": series })),
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn query_metrics(&self, query: &MetricQuery) -> Result<Vec<MetricSeries>> {
+        if query.query.trim().is_empty() {
+            return Err(Error::InvalidQuery("Metric query is empty".to_string()));
+        }
+
+        let to = query.time_range.end.unwrap_or_else(Utc::now);
+        let from = query.time_range.start.unwrap_or(to - Duration::hours(1));
+        let mut attributes = json!({
+            "from": from.timestamp_millis(),
+            "to": to.timestamp_millis(),
+            "queries": [{ "data_source": "metrics", "query": query.query, "name": "query1" }],
+            "formulas": [{ "formula": "query1" }],
+        });
+        if let Some(step) = query.step {
+            attributes["interval"] = json!(u64::from(step) * 1000);
+        }
+
+        let body = json!({ "data": { "type": "timeseries_request", "attributes": attributes } });
+        let response = self
+            .send(self.client().post(format!("{}/api/v2/query/timeseries", self.api_url())).json(&body))
+            .await?;
+        let response: TimeseriesResponse = serde_json::from_value(response)?;
+        Ok(to_metric_series(&query.query, response.data.attributes))
+    }
+}
+
+fn timestamp(metric: &Metric) -> i64 {
+    metric.timestamp.unwrap_or_else(Utc::now).timestamp()
+}
+
+fn to_series(metric: &Metric) -> Series {
+    Series {
+        metric: metric.name.clone(),
+        metric_type: match metric.metric_type {
+            MetricType::Counter => 1,
+            _ => 3,
+        },
+        points: vec![Point { timestamp: timestamp(metric), value: metric.value }],
+        tags: format_tags(&metric.tags),
+    }
+}
+
+fn to_metric_series(query: &str, attributes: TimeseriesAttributes) -> Vec<MetricSeries> {
+    attributes
+        .series
+        .into_iter()
+        .zip(attributes.values)
+        .map(|(series, values)| MetricSeries {
+            metric: query.to_string(),
+            tags: series.group_tags.iter().map(|t| parse_tag(t)).collect(),
+            points: attributes
+                .times
+                .iter()
+                .zip(values)
+                .filter_map(|(t, v)| Some((DateTime::from_timestamp_millis(*t)?, v?)))
+                .collect(),
+        })
+        .collect()
+}
+
+#[derive(Debug, Serialize)]
+struct SeriesPayload {
+    series: Vec<Series>,
+}
+
+#[derive(Debug, Serialize)]
+struct Series {
+    metric: String,
+    #[serde(rename = "type")]
+    metric_type: u8,
+    points: Vec<Point>,
+    tags: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct Point {
+    timestamp: i64,
+    value: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct TimeseriesResponse {
+    data: TimeseriesData,
+}
+
+#[derive(Debug, Deserialize)]
+struct TimeseriesData {
+    attributes: TimeseriesAttributes,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct TimeseriesAttributes {
+    #[serde(default)]
+    series: Vec<TimeseriesSeries>,
+    #[serde(default)]
+    times: Vec<i64>,
+    #[serde(default)]
+    values: Vec<Vec<Option<f64>>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TimeseriesSeries {
+    #[serde(default)]
+    group_tags: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_series_payload() {
+        let metric = Metric {
+            name: "jobs.processed".to_string(),
+            value: 3.0,
+            timestamp: DateTime::from_timestamp(1_700_000_000, 0),
+            tags: HashMap::from([
+                ("env".to_string(), "prod".to_string()),
+                ("canary".to_string(), String::new()),
+            ]),
+            metric_type: MetricType::Counter,
+        };
+        let payload = serde_json::to_value(to_series(&metric)).unwrap();
+        assert_eq!(
+            payload,
+            json!({
+                "metric": "jobs.processed",
+                "type": 1,
+                "points": [{ "timestamp": 1_700_000_000, "value": 3.0 }],
+                "tags": ["canary", "env:prod"],
+            })
+        );
+    }
+
+    #[test]
+    fn test_timeseries_response() {
+        let response: TimeseriesResponse = serde_json::from_value(json!({
+            "data": {
+                "type": "timeseries_response",
+                "attributes": {
+                    "series": [{ "group_tags": ["host:a"], "query_index": 0 }, { "group_tags": ["host:b"], "query_index": 0 }],
+                    "times": [1_700_000_000_000i64, 1_700_000_060_000i64],
+                    "values": [[1.5, null], [2.0, 4.0]]
+                }
+            }
+        }))
+        .unwrap();
+
+        let series = to_metric_series("avg:system.load.1{*} by {host}", response.data.attributes);
+        assert_eq!(series.len(), 2);
+        assert_eq!(series[0].tags["host"], "a");
+        assert_eq!(series[0].points.len(), 1);
+        assert_eq!(series[1].points[1].1, 4.0);
+    }
+}
@@ -0,0 +1,8 @@
+mod client;
+mod metrics;
+mod logs;
+mod traces;
+mod monitors;
+mod dashboards;
+
+pub use client::{DatadogClient, DatadogSite};
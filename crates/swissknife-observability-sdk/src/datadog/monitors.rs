@@ -0,0 +1,91 @@
+use crate::datadog::DatadogClient;
+use crate::{Alert, AlertSeverity, AlertStatus, AlertingProvider, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+use serde_json::json;
+
+#[async_trait]
+impl AlertingProvider for DatadogClient {
+    async fn list_alerts(&self) -> Result<Vec<Alert>> {
+        let response = self
+            .send(self.client().get(format!("{}/api/v1/monitor", self.api_url())))
+            .await?;
+        let monitors: Vec<Monitor> = serde_json::from_value(response)?;
+        Ok(monitors.into_iter().map(to_alert).collect())
+    }
+
+    async fn get_alert(&self, id: &str) -> Result<Alert> {
+        let response = self
+            .send(self.client().get(format!("{}/api/v1/monitor/{}", self.api_url(), id)))
+            .await?;
+        Ok(to_alert(serde_json::from_value(response)?))
+    }
+
+    // A duration of zero mutes the monitor until it is explicitly unmuted.
+    async fn mute_alert(&self, id: &str, duration_minutes: u32) -> Result<()> {
+        let body = if duration_minutes == 0 {
+            json!({})
+        } else {
+            json!({ "end": (Utc::now() + Duration::minutes(duration_minutes.into())).timestamp() })
+        };
+        self.send(
+            self.client()
+                .post(format!("{}/api/v1/monitor/{}/mute", self.api_url(), id))
+                .json(&body),
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn unmute_alert(&self, id: &str) -> Result<()> {
+        self.send(
+            self.client()
+                .post(format!("{}/api/v1/monitor/{}/unmute", self.api_url(), id))
+                .json(&json!({ "all_scopes": true })),
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+fn to_alert(monitor: Monitor) -> Alert {
+    let status = match monitor.overall_state.as_deref() {
+        Some("OK") => AlertStatus::Ok,
+        Some("Warn") => AlertStatus::Warn,
+        Some("Alert") => AlertStatus::Critical,
+        Some("No Data") => AlertStatus::NoData,
+        _ => AlertStatus::Unknown,
+    };
+    let severity = match monitor.priority {
+        Some(1) => AlertSeverity::Critical,
+        Some(2) => AlertSeverity::High,
+        Some(4) | Some(5) => AlertSeverity::Low,
+        _ => AlertSeverity::Medium,
+    };
+    let (triggered_at, resolved_at) = match status {
+        AlertStatus::Ok => (None, monitor.overall_state_modified),
+        AlertStatus::Unknown => (None, None),
+        _ => (monitor.overall_state_modified, None),
+    };
+
+    Alert {
+        id: monitor.id.to_string(),
+        name: monitor.name,
+        status,
+        severity,
+        message: monitor.message.filter(|m| !m.is_empty()),
+        triggered_at,
+        resolved_at,
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Monitor {
+    id: i64,
+    name: String,
+    message: Option<String>,
+    overall_state: Option<String>,
+    overall_state_modified: Option<DateTime<Utc>>,
+    priority: Option<i64>,
+}
@@ -0,0 +1,229 @@
+use crate::datadog::client::{parse_tag, read, time_bounds};
+use crate::datadog::DatadogClient;
+use crate::{Error, Result, Span, SpanStatus, TimeRange, Trace, TracingProvider};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+const MAX_SPANS_PER_REQUEST: u32 = 1000;
+const SPAN_RETENTION: &str = "now-15d";
+
+// Spans are submitted through the Datadog agent's trace intake; the public API
+// only exposes span search.
+#[async_trait]
+impl TracingProvider for DatadogClient {
+    async fn send_traces(&self, traces: &[Trace]) -> Result<()> {
+        let payload: Vec<Vec<Value>> = traces
+            .iter()
+            .map(|trace| trace.spans.iter().map(|span| to_agent_span(&trace.trace_id, span)).collect())
+            .collect::<Result<_>>()?;
+        let response = self
+            .client()
+            .put(format!("{}/v0.4/traces", self.agent_url()))
+            .header("X-Datadog-Trace-Count", traces.len().to_string())
+            .json(&payload)
+            .send()
+            .await?;
+        read(response).await?;
+        Ok(())
+    }
+
+    async fn get_trace(&self, trace_id: &str) -> Result<Trace> {
+        let range = TimeRange::default();
+        let spans = self
+            .search_spans(&format!("trace_id:{}", trace_id), &range, SPAN_RETENTION, MAX_SPANS_PER_REQUEST)
+            .await?;
+        group_traces(spans).into_iter().next().ok_or_else(|| Error::Api {
+            message: format!("Trace {} not found", trace_id),
+            code: Some("404".to_string()),
+        })
+    }
+
+    async fn search_traces(&self, query: &str, time_range: &TimeRange, limit: u32) -> Result<Vec<Trace>> {
+        let spans = self
+            .search_spans(query, time_range, "now-15m", MAX_SPANS_PER_REQUEST)
+            .await?;
+        let mut traces = group_traces(spans);
+        traces.truncate(limit as usize);
+        Ok(traces)
+    }
+}
+
+impl DatadogClient {
+    async fn search_spans(
+        &self,
+        query: &str,
+        time_range: &TimeRange,
+        default_from: &str,
+        limit: u32,
+    ) -> Result<Vec<SpanAttributes>> {
+        let (from, to) = time_bounds(time_range, default_from);
+        let body = json!({
+            "data": {
+                "type": "search_request",
+                "attributes": {
+                    "filter": { "query": query, "from": from, "to": to },
+                    "page": { "limit": limit },
+                    "sort": "-timestamp",
+                },
+            },
+        });
+        let response = self
+            .send(self.client().post(format!("{}/api/v2/spans/events/search", self.api_url())).json(&body))
+            .await?;
+        let response: SpansResponse = serde_json::from_value(response)?;
+        Ok(response.data.into_iter().map(|s| s.attributes).collect())
+    }
+}
+
+fn group_traces(spans: Vec<SpanAttributes>) -> Vec<Trace> {
+    let mut traces: Vec<Trace> = Vec::new();
+    for span in spans {
+        let trace_id = span.trace_id.clone();
+        let span = to_span(span);
+        match traces.iter_mut().find(|t| t.trace_id == trace_id) {
+            Some(trace) => trace.spans.push(span),
+            None => traces.push(Trace { trace_id, spans: vec![span] }),
+        }
+    }
+    for trace in &mut traces {
+        trace.spans.sort_by_key(|s| s.start_time);
+    }
+    traces
+}
+
+fn to_span(span: SpanAttributes) -> Span {
+    let start = span.start_timestamp.unwrap_or_else(Utc::now);
+    let duration_ms = match (span.start_timestamp, span.end_timestamp) {
+        (Some(start), Some(end)) => (end - start).num_microseconds().unwrap_or_default() as f64 / 1000.0,
+        _ => span.custom["duration"].as_f64().map(|ns| ns / 1_000_000.0).unwrap_or_default(),
+    };
+    let is_error = span.custom["error"].as_i64().is_some_and(|e| e != 0)
+        || span.custom["error"].as_bool() == Some(true)
+        || span.attributes["status"].as_str() == Some("error");
+    let operation_name = span.custom["operation_name"]
+        .as_str()
+        .or_else(|| span.attributes["operation_name"].as_str())
+        .map(String::from)
+        .or_else(|| span.resource_name.clone())
+        .unwrap_or_default();
+
+    let mut tags: HashMap<String, String> = span.tags.iter().map(|t| parse_tag(t)).collect();
+    if let Some(resource) = span.resource_name {
+        tags.insert("resource.name".to_string(), resource);
+    }
+
+    Span {
+        span_id: span.span_id,
+        parent_id: span.parent_id.filter(|p| !p.is_empty() && p != "0"),
+        operation_name,
+        service: span.service.unwrap_or_default(),
+        start_time: start,
+        duration_ms,
+        status: if is_error { SpanStatus::Error } else { SpanStatus::Ok },
+        tags,
+        logs: Vec::new(),
+    }
+}
+
+fn to_agent_span(trace_id: &str, span: &Span) -> Result<Value> {
+    let resource = span.tags.get("resource.name").unwrap_or(&span.operation_name);
+    let meta: HashMap<&String, &String> = span.tags.iter().filter(|(k, _)| *k != "resource.name").collect();
+    Ok(json!({
+        "trace_id": agent_id(trace_id)?,
+        "span_id": agent_id(&span.span_id)?,
+        "parent_id": span.parent_id.as_deref().map(agent_id).transpose()?.unwrap_or(0),
+        "name": span.operation_name,
+        "resource": resource,
+        "service": span.service,
+        "start": span.start_time.timestamp_nanos_opt().unwrap_or_default(),
+        "duration": (span.duration_ms * 1_000_000.0) as i64,
+        "error": i32::from(span.status == SpanStatus::Error),
+        "meta": meta,
+    }))
+}
+
+// The agent takes 64-bit integer IDs. Span and trace IDs are hex (up to 128
+// bits for W3C trace IDs), so keep the low 64 bits.
+fn agent_id(id: &str) -> Result<u64> {
+    if id.is_empty() || !id.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(Error::InvalidRequest(format!("Invalid trace or span ID: {:?}", id)));
+    }
+    let low = &id[id.len().saturating_sub(16)..];
+    u64::from_str_radix(low, 16).map_err(|_| Error::InvalidRequest(format!("Invalid trace or span ID: {:?}", id)))
+}
+
+#[derive(Debug, Deserialize)]
+struct SpansResponse {
+    #[serde(default)]
+    data: Vec<SpanEvent>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpanEvent {
+    attributes: SpanAttributes,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpanAttributes {
+    trace_id: String,
+    span_id: String,
+    parent_id: Option<String>,
+    service: Option<String>,
+    resource_name: Option<String>,
+    start_timestamp: Option<DateTime<Utc>>,
+    end_timestamp: Option<DateTime<Utc>>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    custom: Value,
+    #[serde(default)]
+    attributes: Value,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_agent_id_parses_hex() {
+        assert_eq!(agent_id("00f067aa0ba902b7").unwrap(), 0x00f0_67aa_0ba9_02b7);
+        assert_eq!(agent_id("1234567890").unwrap(), 0x12_3456_7890);
+        assert_eq!(agent_id("4bf92f3577b34da6a3ce929d0e0e4736").unwrap(), 0xa3ce_929d_0e0e_4736);
+        assert_eq!(agent_id("FFFFFFFFFFFFFFFF").unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn test_agent_id_rejects_invalid_ids() {
+        assert!(agent_id("").is_err());
+        assert!(agent_id("0x1f").is_err());
+        assert!(agent_id("not-an-id").is_err());
+        assert!(agent_id("é00f067aa0ba902b7").is_err());
+        assert!(agent_id("00f067aa0ba902b7é").is_err());
+    }
+
+    #[test]
+    fn test_agent_span_ids() {
+        let span = Span {
+            span_id: "00000000000000ff".to_string(),
+            parent_id: Some("10".to_string()),
+            operation_name: "GET /users".to_string(),
+            service: "api".to_string(),
+            start_time: Utc::now(),
+            duration_ms: 1.5,
+            status: SpanStatus::Ok,
+            tags: HashMap::new(),
+            logs: Vec::new(),
+        };
+        let value = to_agent_span("4bf92f3577b34da6a3ce929d0e0e4736", &span).unwrap();
+        assert_eq!(value["trace_id"], 0xa3ce_929d_0e0e_4736u64);
+        assert_eq!(value["span_id"], 255);
+        assert_eq!(value["parent_id"], 16);
+
+        let root = Span { parent_id: None, ..span.clone() };
+        assert_eq!(to_agent_span("1", &root).unwrap()["parent_id"], 0);
+        assert!(to_agent_span("trace-1", &root).is_err());
+    }
+}
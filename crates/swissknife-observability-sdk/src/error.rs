@@ -17,8 +17,23 @@ pub enum Error {
     #[error("Invalid query: {0}")]
     InvalidQuery(String),
 
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+
+    #[error("Configuration error: {0}")]
+    Config(String),
+
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Export queue is full")]
+    QueueFull,
+
+    #[error("Exporter has shut down")]
+    ExporterClosed,
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,557 @@
+use crate::{
+    AnalyticsProvider, Error, Event, LogEntry, LoggingProvider, Metric, MetricsProvider, Result, Trace, TracingProvider,
+};
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::{mpsc, oneshot};
+use tokio::time::MissedTickBehavior;
+
+// `tokio::time::interval` panics on a zero period.
+const MIN_FLUSH_INTERVAL: Duration = Duration::from_millis(10);
+
+#[async_trait]
+pub trait BatchSink<T>: Send + Sync {
+    async fn export(&self, batch: &[T]) -> Result<()>;
+}
+
+#[async_trait]
+impl<P: AnalyticsProvider> BatchSink<Event> for P {
+    async fn export(&self, batch: &[Event]) -> Result<()> {
+        self.track_batch(batch).await
+    }
+}
+
+#[async_trait]
+impl<P: MetricsProvider> BatchSink<Metric> for P {
+    async fn export(&self, batch: &[Metric]) -> Result<()> {
+        self.submit_metrics(batch).await
+    }
+}
+
+#[async_trait]
+impl<P: LoggingProvider> BatchSink<LogEntry> for P {
+    async fn export(&self, batch: &[LogEntry]) -> Result<()> {
+        self.send_logs(batch).await
+    }
+}
+
+#[async_trait]
+impl<P: TracingProvider> BatchSink<Trace> for P {
+    async fn export(&self, batch: &[Trace]) -> Result<()> {
+        self.send_traces(batch).await
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ExporterConfig {
+    pub capacity: usize,
+    pub max_batch_size: usize,
+    pub flush_interval: Duration,
+    pub max_retries: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    // Batches that still fail with a transient error after every retry,
+    // including whatever is left at shutdown, are appended here and replayed on
+    // the next start. Batches the provider rejects outright are dropped, and a
+    // full queue is not spilled; `try_send` reports it to the caller instead.
+    pub spill_path: Option<PathBuf>,
+}
+
+impl Default for ExporterConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 10_000,
+            max_batch_size: 500,
+            flush_interval: Duration::from_secs(5),
+            max_retries: 5,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            spill_path: None,
+        }
+    }
+}
+
+impl ExporterConfig {
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    pub fn with_max_batch_size(mut self, max_batch_size: usize) -> Self {
+        self.max_batch_size = max_batch_size.max(1);
+        self
+    }
+
+    pub fn with_flush_interval(mut self, flush_interval: Duration) -> Self {
+        self.flush_interval = flush_interval.max(MIN_FLUSH_INTERVAL);
+        self
+    }
+
+    pub fn with_retries(mut self, max_retries: u32, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        self.max_retries = max_retries;
+        self.initial_backoff = initial_backoff;
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    pub fn with_spill_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.spill_path = Some(path.into());
+        self
+    }
+}
+
+enum Command<T> {
+    Item(T),
+    Flush(oneshot::Sender<Result<()>>),
+    Shutdown(oneshot::Sender<Result<()>>),
+}
+
+pub struct BufferedExporter<T> {
+    sender: mpsc::Sender<Command<T>>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl<T> BufferedExporter<T>
+where
+    T: Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+    // Spawns the flush worker on the current Tokio runtime. Anything left in the
+    // spill file from a previous run is replayed before new items.
+    pub fn start<P: BatchSink<T> + 'static>(provider: P, mut config: ExporterConfig) -> Self {
+        // The fields are public, so apply the same bounds as the builders.
+        config.capacity = config.capacity.max(1);
+        config.max_batch_size = config.max_batch_size.max(1);
+        config.flush_interval = config.flush_interval.max(MIN_FLUSH_INTERVAL);
+        let (sender, receiver) = mpsc::channel(config.capacity);
+        let dropped = Arc::new(AtomicU64::new(0));
+        let worker = Worker {
+            provider,
+            buffer: Vec::with_capacity(config.max_batch_size),
+            config,
+            dropped: dropped.clone(),
+        };
+        tokio::spawn(worker.run(receiver));
+        Self { sender, dropped }
+    }
+
+    pub async fn send(&self, item: T) -> Result<()> {
+        self.sender
+            .send(Command::Item(item))
+            .await
+            .map_err(|_| Error::ExporterClosed)
+    }
+
+    // Fails with `QueueFull` rather than waiting when `capacity` items are
+    // already queued. Rejected items are counted in `dropped`.
+    pub fn try_send(&self, item: T) -> Result<()> {
+        self.sender.try_send(Command::Item(item)).map_err(|e| {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+            match e {
+                mpsc::error::TrySendError::Full(_) => Error::QueueFull,
+                mpsc::error::TrySendError::Closed(_) => Error::ExporterClosed,
+            }
+        })
+    }
+
+    pub async fn flush(&self) -> Result<()> {
+        self.request(Command::Flush).await
+    }
+
+    pub async fn shutdown(&self) -> Result<()> {
+        self.request(Command::Shutdown).await
+    }
+
+    // Items `try_send` turned away, batches the provider rejected, and those
+    // that could neither be exported nor spilled to disk.
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    async fn request(&self, command: impl FnOnce(oneshot::Sender<Result<()>>) -> Command<T>) -> Result<()> {
+        let (ack, done) = oneshot::channel();
+        self.sender.send(command(ack)).await.map_err(|_| Error::ExporterClosed)?;
+        done.await.map_err(|_| Error::ExporterClosed)?
+    }
+}
+
+struct Worker<P, T> {
+    provider: P,
+    config: ExporterConfig,
+    buffer: Vec<T>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl<P, T> Worker<P, T>
+where
+    P: BatchSink<T>,
+    T: Serialize + DeserializeOwned + Send + Sync,
+{
+    async fn run(mut self, mut receiver: mpsc::Receiver<Command<T>>) {
+        self.replay().await;
+        let _ = self.flush().await;
+
+        let mut ticker = tokio::time::interval(self.config.flush_interval);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            tokio::select! {
+                command = receiver.recv() => match command {
+                    Some(Command::Item(item)) => {
+                        self.buffer.push(item);
+                        if self.buffer.len() >= self.config.max_batch_size {
+                            let _ = self.flush().await;
+                        }
+                    }
+                    Some(Command::Flush(ack)) => {
+                        let _ = ack.send(self.flush().await);
+                    }
+                    Some(Command::Shutdown(ack)) => {
+                        self.drain(receiver, ack).await;
+                        return;
+                    }
+                    None => {
+                        let _ = self.flush().await;
+                        return;
+                    }
+                },
+                _ = ticker.tick() => {
+                    let _ = self.flush().await;
+                }
+            }
+        }
+    }
+
+    // Stops accepting new items, then exports everything already queued.
+    async fn drain(&mut self, mut receiver: mpsc::Receiver<Command<T>>, ack: oneshot::Sender<Result<()>>) {
+        receiver.close();
+        let mut acks = vec![ack];
+        while let Some(command) = receiver.recv().await {
+            match command {
+                Command::Item(item) => self.buffer.push(item),
+                Command::Flush(ack) | Command::Shutdown(ack) => acks.push(ack),
+            }
+        }
+
+        let result = self.flush().await;
+        let failed = result.is_err();
+        let mut acks = acks.into_iter();
+        if let Some(first) = acks.next() {
+            let _ = first.send(result);
+        }
+        for ack in acks {
+            let _ = ack.send(if failed { Err(Error::ExporterClosed) } else { Ok(()) });
+        }
+    }
+
+    async fn flush(&mut self) -> Result<()> {
+        let mut result = Ok(());
+        while !self.buffer.is_empty() {
+            let size = self.buffer.len().min(self.config.max_batch_size);
+            let batch: Vec<T> = self.buffer.drain(..size).collect();
+            match self.export(&batch).await {
+                Ok(()) => {}
+                Err(e) if is_retryable(&e) => {
+                    // The provider is unreachable; don't spend retries on the rest.
+                    let mut unsent = batch;
+                    unsent.append(&mut self.buffer);
+                    self.spill(unsent).await;
+                    return Err(e);
+                }
+                Err(e) => {
+                    // The provider rejected the batch, so replaying it would fail again.
+                    // Drop it and carry on with the rest.
+                    self.dropped.fetch_add(batch.len() as u64, Ordering::Relaxed);
+                    if result.is_ok() {
+                        result = Err(e);
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    async fn export(&self, batch: &[T]) -> Result<()> {
+        let mut backoff = self.config.initial_backoff;
+        let mut attempt = 0;
+        loop {
+            match self.provider.export(batch).await {
+                Err(e) if attempt < self.config.max_retries && is_retryable(&e) => {
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(self.config.max_backoff);
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn spill(&self, items: Vec<T>) {
+        let count = items.len() as u64;
+        let written = match &self.config.spill_path {
+            Some(path) => append_lines(path, &items).await.is_ok(),
+            None => false,
+        };
+        if !written {
+            self.dropped.fetch_add(count, Ordering::Relaxed);
+        }
+    }
+
+    async fn replay(&mut self) {
+        let Some(path) = &self.config.spill_path else {
+            return;
+        };
+        let Ok(contents) = tokio::fs::read_to_string(path).await else {
+            return;
+        };
+        // Only take ownership of the spilled items once the file is gone, so a
+        // failed removal can't lead to them being replayed twice.
+        if tokio::fs::remove_file(path).await.is_err() {
+            return;
+        }
+        // A crash mid-write can leave a truncated last line; skip anything unparseable.
+        self.buffer.extend(contents.lines().filter_map(|line| serde_json::from_str(line).ok()));
+    }
+}
+
+fn is_retryable(error: &Error) -> bool {
+    match error {
+        Error::Http(_) | Error::RateLimited => true,
+        Error::Api { code: Some(code), .. } => code.starts_with('5'),
+        _ => false,
+    }
+}
+
+async fn append_lines<T: Serialize>(path: &Path, items: &[T]) -> Result<()> {
+    let mut lines = Vec::new();
+    for item in items {
+        serde_json::to_writer(&mut lines, item)?;
+        lines.push(b'\n');
+    }
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await?;
+    file.write_all(&lines).await?;
+    file.sync_data().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::UserProfile;
+    use std::collections::HashMap;
+    use std::sync::atomic::AtomicBool;
+    use std::sync::Mutex;
+
+    #[derive(Clone, Default)]
+    struct RecordingProvider {
+        batches: Arc<Mutex<Vec<Vec<String>>>>,
+        unreachable: Arc<AtomicBool>,
+        invalid: Arc<AtomicBool>,
+        attempts: Arc<AtomicU64>,
+    }
+
+    #[async_trait]
+    impl AnalyticsProvider for RecordingProvider {
+        async fn track(&self, event: &Event) -> Result<()> {
+            self.track_batch(std::slice::from_ref(event)).await
+        }
+
+        async fn track_batch(&self, events: &[Event]) -> Result<()> {
+            self.attempts.fetch_add(1, Ordering::SeqCst);
+            if self.invalid.load(Ordering::SeqCst) {
+                return Err(Error::InvalidRequest("bad event".into()));
+            }
+            if self.unreachable.load(Ordering::SeqCst) {
+                return Err(Error::RateLimited);
+            }
+            self.batches.lock().unwrap().push(events.iter().map(|e| e.name.clone()).collect());
+            Ok(())
+        }
+
+        async fn identify(&self, _profile: &UserProfile) -> Result<()> {
+            Ok(())
+        }
+
+        async fn alias(&self, _distinct_id: &str, _alias: &str) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    fn event(name: &str) -> Event {
+        Event {
+            name: name.to_string(),
+            timestamp: None,
+            distinct_id: Some("user-1".to_string()),
+            properties: HashMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_flushes_by_size_and_drains_on_shutdown() {
+        let provider = RecordingProvider::default();
+        let config = ExporterConfig::default()
+            .with_max_batch_size(2)
+            .with_flush_interval(Duration::from_secs(3600));
+        let exporter = BufferedExporter::start(provider.clone(), config);
+
+        for name in ["a", "b", "c"] {
+            exporter.send(event(name)).await.unwrap();
+        }
+        exporter.shutdown().await.unwrap();
+
+        assert_eq!(*provider.batches.lock().unwrap(), vec![vec!["a", "b"], vec!["c"]]);
+        assert!(matches!(exporter.send(event("d")).await, Err(Error::ExporterClosed)));
+    }
+
+    #[tokio::test]
+    async fn test_spills_when_unreachable_and_replays_on_restart() {
+        let path = std::env::temp_dir().join(format!("swissknife-exporter-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let config = ExporterConfig::default()
+            .with_flush_interval(Duration::from_secs(3600))
+            .with_retries(2, Duration::from_millis(1), Duration::from_millis(2))
+            .with_spill_path(&path);
+
+        let provider = RecordingProvider::default();
+        provider.unreachable.store(true, Ordering::SeqCst);
+        let exporter = BufferedExporter::start(provider.clone(), config.clone());
+        exporter.send(event("a")).await.unwrap();
+        exporter.send(event("b")).await.unwrap();
+        assert!(matches!(exporter.shutdown().await, Err(Error::RateLimited)));
+        assert_eq!(exporter.dropped(), 0);
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 2);
+
+        provider.unreachable.store(false, Ordering::SeqCst);
+        let exporter = BufferedExporter::start(provider.clone(), config);
+        exporter.send(event("c")).await.unwrap();
+        exporter.shutdown().await.unwrap();
+
+        assert_eq!(*provider.batches.lock().unwrap(), vec![vec!["a", "b"], vec!["c"]]);
+        assert!(!path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_flush_exports_buffered_items() {
+        let provider = RecordingProvider::default();
+        let config = ExporterConfig::default().with_flush_interval(Duration::from_secs(3600));
+        let exporter = BufferedExporter::start(provider.clone(), config);
+
+        exporter.send(event("a")).await.unwrap();
+        exporter.try_send(event("b")).unwrap();
+        exporter.flush().await.unwrap();
+
+        assert_eq!(*provider.batches.lock().unwrap(), vec![vec!["a", "b"]]);
+    }
+
+    #[tokio::test]
+    async fn test_zero_flush_interval_is_clamped() {
+        assert_eq!(
+            ExporterConfig::default().with_flush_interval(Duration::ZERO).flush_interval,
+            MIN_FLUSH_INTERVAL
+        );
+
+        let provider = RecordingProvider::default();
+        let config = ExporterConfig {
+            flush_interval: Duration::ZERO,
+            ..Default::default()
+        };
+        let exporter = BufferedExporter::start(provider.clone(), config);
+        exporter.send(event("a")).await.unwrap();
+        tokio::time::sleep(MIN_FLUSH_INTERVAL * 10).await;
+
+        assert_eq!(*provider.batches.lock().unwrap(), vec![vec!["a"]]);
+    }
+
+    #[tokio::test]
+    async fn test_try_send_reports_full_queue() {
+        let provider = RecordingProvider::default();
+        let exporter = BufferedExporter::start(provider.clone(), ExporterConfig::default().with_capacity(1));
+
+        // The worker hasn't been polled yet, so the first item fills the queue.
+        exporter.try_send(event("a")).unwrap();
+        assert!(matches!(exporter.try_send(event("b")), Err(Error::QueueFull)));
+        assert_eq!(exporter.dropped(), 1);
+
+        exporter.shutdown().await.unwrap();
+        assert_eq!(*provider.batches.lock().unwrap(), vec![vec!["a"]]);
+    }
+
+    #[tokio::test]
+    async fn test_counts_dropped_items_without_spill_path() {
+        let provider = RecordingProvider::default();
+        provider.unreachable.store(true, Ordering::SeqCst);
+        let config = ExporterConfig::default()
+            .with_flush_interval(Duration::from_secs(3600))
+            .with_retries(1, Duration::from_millis(1), Duration::from_millis(1));
+        let exporter = BufferedExporter::start(provider.clone(), config);
+
+        for name in ["a", "b", "c"] {
+            exporter.send(event(name)).await.unwrap();
+        }
+        assert!(matches!(exporter.flush().await, Err(Error::RateLimited)));
+        assert_eq!(exporter.dropped(), 3);
+        assert_eq!(provider.attempts.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_does_not_retry_rejected_batches() {
+        let provider = RecordingProvider::default();
+        provider.invalid.store(true, Ordering::SeqCst);
+        let config = ExporterConfig::default()
+            .with_flush_interval(Duration::from_secs(3600))
+            .with_retries(5, Duration::from_millis(1), Duration::from_millis(1));
+        let exporter = BufferedExporter::start(provider.clone(), config);
+
+        exporter.send(event("a")).await.unwrap();
+        assert!(matches!(exporter.flush().await, Err(Error::InvalidRequest(_))));
+        assert_eq!(provider.attempts.load(Ordering::SeqCst), 1);
+        assert_eq!(exporter.dropped(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_rejected_batches_are_not_spilled() {
+        let path = std::env::temp_dir().join(format!("swissknife-exporter-rejected-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let provider = RecordingProvider::default();
+        provider.invalid.store(true, Ordering::SeqCst);
+        let config = ExporterConfig::default()
+            .with_max_batch_size(1)
+            .with_flush_interval(Duration::from_secs(3600))
+            .with_spill_path(&path);
+        let exporter = BufferedExporter::start(provider.clone(), config);
+
+        exporter.send(event("a")).await.unwrap();
+        exporter.send(event("b")).await.unwrap();
+        exporter.shutdown().await.unwrap();
+
+        assert_eq!(provider.attempts.load(Ordering::SeqCst), 2);
+        assert_eq!(exporter.dropped(), 2);
+        assert!(!path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_zero_capacity_and_batch_size_are_clamped() {
+        let provider = RecordingProvider::default();
+        let config = ExporterConfig {
+            capacity: 0,
+            max_batch_size: 0,
+            flush_interval: Duration::from_secs(3600),
+            ..Default::default()
+        };
+        let exporter = BufferedExporter::start(provider.clone(), config);
+
+        exporter.send(event("a")).await.unwrap();
+        exporter.send(event("b")).await.unwrap();
+        exporter.shutdown().await.unwrap();
+
+        assert_eq!(*provider.batches.lock().unwrap(), vec![vec!["a"], vec!["b"]]);
+    }
+}
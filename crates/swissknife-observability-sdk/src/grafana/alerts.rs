@@ -0,0 +1,165 @@
+use crate::grafana::GrafanaClient;
+use crate::{Alert, AlertSeverity, AlertStatus, AlertingProvider, Error, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use reqwest::Method;
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+
+const RULE_UID_LABEL: &str = "__alert_rule_uid__";
+const SILENCES_PATH: &str = "/api/alertmanager/grafana/api/v2/silences";
+
+#[async_trait]
+impl AlertingProvider for GrafanaClient {
+    async fn list_alerts(&self) -> Result<Vec<Alert>> {
+        let response = self
+            .send(self.request(Method::GET, "/api/prometheus/grafana/api/v1/rules"))
+            .await?;
+        let response: RulesResponse = serde_json::from_value(response)?;
+        Ok(response
+            .data
+            .groups
+            .into_iter()
+            .flat_map(|group| group.rules)
+            .filter(|rule| rule.rule_type.as_deref() != Some("recording"))
+            .map(to_alert)
+            .collect())
+    }
+
+    async fn get_alert(&self, id: &str) -> Result<Alert> {
+        self.list_alerts()
+            .await?
+            .into_iter()
+            .find(|alert| alert.id == id)
+            .ok_or_else(|| Error::Api {
+                message: format!("Alert rule {} not found", id),
+                code: Some("404".to_string()),
+            })
+    }
+
+    // Silences always need an end, so a zero duration mutes for a year.
+    async fn mute_alert(&self, id: &str, duration_minutes: u32) -> Result<()> {
+        let starts_at = Utc::now();
+        let duration = match duration_minutes {
+            0 => Duration::days(365),
+            minutes => Duration::minutes(minutes.into()),
+        };
+        let silence = json!({
+            "matchers": [{ "name": RULE_UID_LABEL, "value": id, "isRegex": false, "isEqual": true }],
+            "startsAt": starts_at.to_rfc3339(),
+            "endsAt": (starts_at + duration).to_rfc3339(),
+            "createdBy": "swissknife-observability-sdk",
+            "comment": format!("Muted alert rule {}", id),
+        });
+        self.send(self.request(Method::POST, SILENCES_PATH).json(&silence))
+            .await?;
+        Ok(())
+    }
+
+    async fn unmute_alert(&self, id: &str) -> Result<()> {
+        let filter = format!("{}=\"{}\"", RULE_UID_LABEL, id);
+        let response = self
+            .send(self.request(Method::GET, SILENCES_PATH).query(&[("filter", filter)]))
+            .await?;
+        let silences: Vec<Silence> = serde_json::from_value(response)?;
+        for silence in silences.into_iter().filter(|s| s.status.state != "expired") {
+            self.send(self.request(
+                Method::DELETE,
+                &format!("/api/alertmanager/grafana/api/v2/silence/{}", silence.id),
+            ))
+            .await?;
+        }
+        Ok(())
+    }
+}
+
+fn to_alert(rule: Rule) -> Alert {
+    let status = match (rule.health.as_deref(), rule.state.as_deref()) {
+        (Some("nodata"), _) => AlertStatus::NoData,
+        (Some("error"), _) => AlertStatus::Unknown,
+        (_, Some("firing")) => AlertStatus::Critical,
+        (_, Some("pending")) => AlertStatus::Warn,
+        (_, Some("inactive")) => AlertStatus::Ok,
+        _ => AlertStatus::Unknown,
+    };
+    let severity = match rule.labels.get("severity").map(|s| s.to_ascii_lowercase()).as_deref() {
+        Some("critical") | Some("page") => AlertSeverity::Critical,
+        Some("high") | Some("error") => AlertSeverity::High,
+        Some("low") | Some("info") => AlertSeverity::Low,
+        _ => AlertSeverity::Medium,
+    };
+    let triggered_at = rule.alerts.iter().filter_map(|a| a.active_at).min();
+    let id = rule
+        .uid
+        .clone()
+        .or_else(|| rule.alerts.iter().find_map(|a| a.labels.get(RULE_UID_LABEL).cloned()))
+        .unwrap_or_else(|| rule.name.clone());
+    let message = rule
+        .annotations
+        .get("summary")
+        .or_else(|| rule.annotations.get("description"))
+        .cloned();
+
+    Alert {
+        id,
+        name: rule.name,
+        status,
+        severity,
+        message,
+        triggered_at: triggered_at.filter(|_| matches!(status, AlertStatus::Critical | AlertStatus::Warn)),
+        resolved_at: None,
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RulesResponse {
+    data: RulesData,
+}
+
+#[derive(Debug, Deserialize)]
+struct RulesData {
+    #[serde(default)]
+    groups: Vec<RuleGroup>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RuleGroup {
+    #[serde(default)]
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Rule {
+    uid: Option<String>,
+    name: String,
+    state: Option<String>,
+    health: Option<String>,
+    #[serde(rename = "type")]
+    rule_type: Option<String>,
+    #[serde(default)]
+    labels: HashMap<String, String>,
+    #[serde(default)]
+    annotations: HashMap<String, String>,
+    #[serde(default)]
+    alerts: Vec<RuleAlert>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RuleAlert {
+    #[serde(default)]
+    labels: HashMap<String, String>,
+    active_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Silence {
+    id: String,
+    status: SilenceStatus,
+}
+
+#[derive(Debug, Deserialize)]
+struct SilenceStatus {
+    state: String,
+}
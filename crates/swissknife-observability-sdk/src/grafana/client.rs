@@ -0,0 +1,56 @@
+use crate::{Error, Result};
+use reqwest::{Client, Method, RequestBuilder};
+use serde_json::Value;
+
+#[derive(Clone)]
+pub struct GrafanaClient {
+    base_url: String,
+    api_token: String,
+    client: Client,
+}
+
+impl GrafanaClient {
+    pub fn new(base_url: &str, api_token: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_token: api_token.to_string(),
+            client: Client::new(),
+        }
+    }
+
+    pub(crate) fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub(crate) fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        self.client
+            .request(method, format!("{}{}", self.base_url, path))
+            .bearer_auth(&self.api_token)
+    }
+
+    pub(crate) async fn send(&self, request: RequestBuilder) -> Result<Value> {
+        let response = request.send().await?;
+        let status = response.status();
+        let text = response.text().await?;
+
+        if !status.is_success() {
+            let message = serde_json::from_str::<Value>(&text)
+                .ok()
+                .and_then(|body| body["message"].as_str().or_else(|| body["error"].as_str()).map(String::from))
+                .unwrap_or(text);
+            return Err(match status.as_u16() {
+                401 | 403 => Error::Auth(message),
+                429 => Error::RateLimited,
+                _ => Error::Api {
+                    message,
+                    code: Some(status.to_string()),
+                },
+            });
+        }
+
+        if text.trim().is_empty() {
+            return Ok(Value::Null);
+        }
+        Ok(serde_json::from_str(&text)?)
+    }
+}
@@ -0,0 +1,121 @@
+use crate::grafana::GrafanaClient;
+use crate::{Dashboard, DashboardProvider, Result, Widget};
+use async_trait::async_trait;
+use reqwest::Method;
+use serde::Deserialize;
+use serde_json::Value;
+
+#[async_trait]
+impl DashboardProvider for GrafanaClient {
+    async fn list_dashboards(&self) -> Result<Vec<Dashboard>> {
+        let response = self
+            .send(self.request(Method::GET, "/api/search").query(&[("type", "dash-db")]))
+            .await?;
+        let hits: Vec<SearchHit> = serde_json::from_value(response)?;
+        Ok(hits
+            .into_iter()
+            .map(|hit| Dashboard {
+                id: hit.uid,
+                title: hit.title,
+                description: None,
+                url: hit.url.map(|url| format!("{}{}", self.base_url(), url)),
+                widgets: Vec::new(),
+            })
+            .collect())
+    }
+
+    async fn get_dashboard(&self, id: &str) -> Result<Dashboard> {
+        let response = self
+            .send(self.request(Method::GET, &format!("/api/dashboards/uid/{}", id)))
+            .await?;
+        let response: DashboardResponse = serde_json::from_value(response)?;
+        let mut widgets = Vec::new();
+        flatten_panels(&response.dashboard.panels, &mut widgets);
+
+        Ok(Dashboard {
+            id: response.dashboard.uid,
+            title: response.dashboard.title,
+            description: response.dashboard.description.filter(|d| !d.is_empty()),
+            url: response.meta.url.map(|url| format!("{}{}", self.base_url(), url)),
+            widgets,
+        })
+    }
+}
+
+// Collapsed rows carry their panels inline instead of at the top level.
+fn flatten_panels(panels: &[Value], out: &mut Vec<Widget>) {
+    for panel in panels {
+        if panel["type"] == "row" {
+            if let Some(children) = panel["panels"].as_array() {
+                flatten_panels(children, out);
+            }
+            continue;
+        }
+        let query = panel["targets"].as_array().and_then(|targets| {
+            targets.iter().find_map(|t| {
+                ["expr", "query", "rawSql"]
+                    .iter()
+                    .find_map(|key| t[key].as_str().filter(|q| !q.is_empty()))
+                    .map(String::from)
+            })
+        });
+        out.push(Widget {
+            id: panel["id"].as_i64().map(|id| id.to_string()).unwrap_or_default(),
+            title: panel["title"].as_str().unwrap_or_default().to_string(),
+            widget_type: panel["type"].as_str().unwrap_or_default().to_string(),
+            query,
+        });
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchHit {
+    uid: String,
+    title: String,
+    url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DashboardResponse {
+    dashboard: GrafanaDashboard,
+    meta: DashboardMeta,
+}
+
+#[derive(Debug, Deserialize)]
+struct GrafanaDashboard {
+    uid: String,
+    title: String,
+    description: Option<String>,
+    #[serde(default)]
+    panels: Vec<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DashboardMeta {
+    url: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_flatten_collapsed_rows() {
+        let panels = vec![
+            json!({ "id": 1, "type": "timeseries", "title": "Requests", "targets": [{ "refId": "A", "expr": "sum(rate(http_requests_total[5m]))" }] }),
+            json!({ "id": 2, "type": "row", "title": "Database", "collapsed": true, "panels": [
+                { "id": 3, "type": "table", "title": "Slow queries", "targets": [{ "refId": "A", "rawSql": "SELECT 1" }] }
+            ] }),
+            json!({ "id": 4, "type": "text", "title": "Notes" }),
+        ];
+        let mut widgets = Vec::new();
+        flatten_panels(&panels, &mut widgets);
+
+        let ids: Vec<&str> = widgets.iter().map(|w| w.id.as_str()).collect();
+        assert_eq!(ids, ["1", "3", "4"]);
+        assert_eq!(widgets[0].query.as_deref(), Some("sum(rate(http_requests_total[5m]))"));
+        assert_eq!(widgets[1].query.as_deref(), Some("SELECT 1"));
+        assert_eq!(widgets[2].query, None);
+    }
+}
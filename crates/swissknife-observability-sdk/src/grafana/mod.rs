@@ -0,0 +1,5 @@
+mod client;
+mod dashboards;
+mod alerts;
+
+pub use client::GrafanaClient;
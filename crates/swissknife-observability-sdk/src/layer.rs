@@ -0,0 +1,402 @@
+use crate::exporter::BufferedExporter;
+use crate::{LogEntry, LogLevel, Span, SpanLog, SpanStatus, Trace};
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Level, Subscriber};
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+// The exporters' own HTTP stack emits events while shipping a batch; exporting
+// those would feed back into the next batch forever.
+const DEFAULT_IGNORED_TARGETS: &[&str] = &["hyper", "h2", "reqwest", "rustls", "tower"];
+
+pub struct TelemetryLayer {
+    service: String,
+    traces: Option<Arc<BufferedExporter<Trace>>>,
+    logs: Option<Arc<BufferedExporter<LogEntry>>>,
+    ignored_targets: Vec<String>,
+}
+
+impl TelemetryLayer {
+    pub fn new(service: impl Into<String>) -> Self {
+        Self {
+            service: service.into(),
+            traces: None,
+            logs: None,
+            ignored_targets: DEFAULT_IGNORED_TARGETS.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    pub fn with_traces(mut self, exporter: Arc<BufferedExporter<Trace>>) -> Self {
+        self.traces = Some(exporter);
+        self
+    }
+
+    pub fn with_logs(mut self, exporter: Arc<BufferedExporter<LogEntry>>) -> Self {
+        self.logs = Some(exporter);
+        self
+    }
+
+    pub fn with_ignored_target(mut self, target: impl Into<String>) -> Self {
+        self.ignored_targets.push(target.into());
+        self
+    }
+
+    fn is_ignored(&self, target: &str) -> bool {
+        self.ignored_targets.iter().any(|t| {
+            target
+                .strip_prefix(t.as_str())
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+        })
+    }
+}
+
+impl<S> Layer<S> for TelemetryLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        if self.traces.is_none() || self.is_ignored(attrs.metadata().target()) {
+            return;
+        }
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+
+        let parent = span.parent().and_then(|parent| {
+            parent
+                .extensions()
+                .get::<SpanData>()
+                .map(|data| (data.trace_id.clone(), data.span_id.clone()))
+        });
+        let (trace_id, parent_id) = match parent {
+            Some((trace_id, parent_id)) => (trace_id, Some(parent_id)),
+            None => (uuid::Uuid::new_v4().simple().to_string(), None),
+        };
+
+        let mut fields = Fields::default();
+        attrs.record(&mut fields);
+        fields.0.insert("target".to_string(), json!(attrs.metadata().target()));
+
+        span.extensions_mut().insert(SpanData {
+            trace_id,
+            span_id: format!("{:016x}", uuid::Uuid::new_v4().as_u64_pair().1),
+            parent_id,
+            name: attrs.metadata().name(),
+            started_at: Utc::now(),
+            started: Instant::now(),
+            fields,
+            error: false,
+            logs: Vec::new(),
+        });
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            if let Some(data) = span.extensions_mut().get_mut::<SpanData>() {
+                values.record(&mut data.fields);
+            }
+        }
+    }
+
+    fn on_event(&self, event: &tracing::Event<'_>, ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        if self.is_ignored(metadata.target()) {
+            return;
+        }
+
+        let mut fields = Fields::default();
+        event.record(&mut fields);
+        let level = log_level(metadata.level());
+        let timestamp = Utc::now();
+
+        let mut ids = None;
+        if let Some(span) = ctx.event_span(event) {
+            if let Some(data) = span.extensions_mut().get_mut::<SpanData>() {
+                data.error |= level == LogLevel::Error;
+                data.logs.push(SpanLog {
+                    timestamp,
+                    fields: fields.0.clone(),
+                });
+                ids = Some((data.trace_id.clone(), data.span_id.clone()));
+            }
+        }
+
+        if let Some(logs) = &self.logs {
+            let mut attributes = fields.0;
+            let message = match attributes.remove("message") {
+                Some(Value::String(message)) => message,
+                Some(other) => other.to_string(),
+                None => metadata.name().to_string(),
+            };
+            attributes.insert("target".to_string(), json!(metadata.target()));
+            if let Some((trace_id, span_id)) = ids {
+                attributes.insert("trace_id".to_string(), json!(trace_id));
+                attributes.insert("span_id".to_string(), json!(span_id));
+            }
+            // Never block the instrumented code; the exporter counts anything a
+            // full queue turns away in `dropped()`.
+            let _ = logs.try_send(LogEntry {
+                message,
+                level,
+                timestamp: Some(timestamp),
+                attributes,
+                service: Some(self.service.clone()),
+                source: Some(metadata.target().to_string()),
+            });
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(traces) = &self.traces else {
+            return;
+        };
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let Some(data) = span.extensions_mut().remove::<SpanData>() else {
+            return;
+        };
+
+        // Each span ships as soon as it closes, as a one-span chunk of its
+        // trace, so a long-lived root span never holds its children back.
+        let trace_id = data.trace_id.clone();
+        let spans = vec![data.finish(&self.service)];
+        let _ = traces.try_send(Trace { trace_id, spans });
+    }
+}
+
+struct SpanData {
+    trace_id: String,
+    span_id: String,
+    parent_id: Option<String>,
+    name: &'static str,
+    started_at: DateTime<Utc>,
+    started: Instant,
+    fields: Fields,
+    error: bool,
+    logs: Vec<SpanLog>,
+}
+
+impl SpanData {
+    fn finish(self, service: &str) -> Span {
+        let mut fields = self.fields.0;
+        let status = match fields.remove("otel.status_code").as_ref().and_then(Value::as_str) {
+            Some(code) if code.eq_ignore_ascii_case("error") => SpanStatus::Error,
+            Some(code) if code.eq_ignore_ascii_case("ok") => SpanStatus::Ok,
+            _ if self.error || is_set(fields.get("error")) || is_set(fields.get("exception.message")) => {
+                SpanStatus::Error
+            }
+            _ => SpanStatus::Unset,
+        };
+        let operation_name = match fields.remove("otel.name") {
+            Some(Value::String(name)) => name,
+            _ => self.name.to_string(),
+        };
+        let tags = fields
+            .into_iter()
+            .map(|(k, v)| match v {
+                Value::String(s) => (k, s),
+                other => (k, other.to_string()),
+            })
+            .collect();
+
+        Span {
+            span_id: self.span_id,
+            parent_id: self.parent_id,
+            operation_name,
+            service: service.to_string(),
+            start_time: self.started_at,
+            duration_ms: self.started.elapsed().as_secs_f64() * 1000.0,
+            status,
+            tags,
+            logs: self.logs,
+        }
+    }
+}
+
+// `error = false` or an empty message doesn't mark a span as failed.
+fn is_set(value: Option<&Value>) -> bool {
+    match value {
+        None | Some(Value::Null) => false,
+        Some(Value::Bool(flag)) => *flag,
+        Some(Value::String(text)) => !text.is_empty(),
+        Some(_) => true,
+    }
+}
+
+fn log_level(level: &Level) -> LogLevel {
+    match *level {
+        Level::TRACE | Level::DEBUG => LogLevel::Debug,
+        Level::INFO => LogLevel::Info,
+        Level::WARN => LogLevel::Warn,
+        Level::ERROR => LogLevel::Error,
+    }
+}
+
+#[derive(Default)]
+struct Fields(HashMap<String, Value>);
+
+impl Visit for Fields {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().to_string(), json!(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), json!(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), json!(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), json!(value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), json!(value));
+    }
+
+    fn record_error(&mut self, field: &Field, value: &(dyn std::error::Error + 'static)) {
+        self.0.insert(field.name().to_string(), json!(value.to_string()));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.insert(field.name().to_string(), json!(format!("{:?}", value)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exporter::ExporterConfig;
+    use crate::{TimeRange, TracingProvider};
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+    use std::time::Duration;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[derive(Clone, Default)]
+    struct RecordingTracer {
+        traces: Arc<Mutex<Vec<Trace>>>,
+    }
+
+    #[async_trait]
+    impl TracingProvider for RecordingTracer {
+        async fn send_traces(&self, traces: &[Trace]) -> crate::Result<()> {
+            self.traces.lock().unwrap().extend_from_slice(traces);
+            Ok(())
+        }
+
+        async fn get_trace(&self, _trace_id: &str) -> crate::Result<Trace> {
+            unimplemented!()
+        }
+
+        async fn search_traces(&self, _query: &str, _time_range: &TimeRange, _limit: u32) -> crate::Result<Vec<Trace>> {
+            unimplemented!()
+        }
+    }
+
+    fn exporter(tracer: &RecordingTracer, capacity: usize) -> Arc<BufferedExporter<Trace>> {
+        let config = ExporterConfig::default()
+            .with_capacity(capacity)
+            .with_flush_interval(Duration::from_secs(3600));
+        Arc::new(BufferedExporter::start(tracer.clone(), config))
+    }
+
+    fn exported(tracer: &RecordingTracer) -> Vec<Span> {
+        let traces = tracer.traces.lock().unwrap();
+        traces.iter().flat_map(|t| t.spans.clone()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_nested_spans_export_as_they_close() {
+        let tracer = RecordingTracer::default();
+        let traces = exporter(&tracer, 100);
+        let subscriber = tracing_subscriber::registry().with(TelemetryLayer::new("api").with_traces(traces.clone()));
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let root = tracing::info_span!("serve");
+        let child = tracing::info_span!(parent: &root, "request", path = "/users");
+        let grandchild = tracing::info_span!(parent: &child, "query");
+        drop(grandchild);
+        drop(child);
+
+        // The root is still open, but its finished children are already out.
+        traces.flush().await.unwrap();
+        let spans = exported(&tracer);
+        assert_eq!(spans.iter().map(|s| s.operation_name.as_str()).collect::<Vec<_>>(), ["query", "request"]);
+        assert_eq!(spans[0].parent_id.as_ref(), Some(&spans[1].span_id));
+        assert_eq!(spans[1].tags["path"], "/users");
+
+        drop(root);
+        traces.flush().await.unwrap();
+        let spans = exported(&tracer);
+        assert_eq!(spans.len(), 3);
+        assert_eq!(spans[1].parent_id.as_ref(), Some(&spans[2].span_id));
+        assert_eq!(spans[2].parent_id, None);
+        let trace_ids: Vec<_> = tracer.traces.lock().unwrap().iter().map(|t| t.trace_id.clone()).collect();
+        assert!(trace_ids.iter().all(|id| *id == trace_ids[0]));
+    }
+
+    #[tokio::test]
+    async fn test_counts_spans_dropped_by_a_full_queue() {
+        let tracer = RecordingTracer::default();
+        let traces = exporter(&tracer, 1);
+        let subscriber = tracing_subscriber::registry().with(TelemetryLayer::new("api").with_traces(traces.clone()));
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        // The worker can't run until this task yields, so only one span fits.
+        for _ in 0..3 {
+            drop(tracing::info_span!("work"));
+        }
+        assert_eq!(traces.dropped(), 2);
+
+        traces.flush().await.unwrap();
+        assert_eq!(exported(&tracer).len(), 1);
+    }
+
+    fn span_data(fields: &[(&str, Value)]) -> SpanData {
+        SpanData {
+            trace_id: "4bf92f3577b34da6a3ce929d0e0e4736".to_string(),
+            span_id: "00f067aa0ba902b7".to_string(),
+            parent_id: None,
+            name: "handle_request",
+            started_at: Utc::now(),
+            started: Instant::now(),
+            fields: Fields(fields.iter().map(|(k, v)| (k.to_string(), v.clone())).collect()),
+            error: false,
+            logs: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_span_status_and_name_from_fields() {
+        let span = span_data(&[("http.status_code", json!(200)), ("otel.name", json!("GET /users"))]).finish("api");
+        assert_eq!(span.status, SpanStatus::Unset);
+        assert_eq!(span.operation_name, "GET /users");
+        assert_eq!(span.tags["http.status_code"], "200");
+        assert!(!span.tags.contains_key("otel.name"));
+
+        let span = span_data(&[("error", json!("connection reset"))]).finish("api");
+        assert_eq!(span.status, SpanStatus::Error);
+        assert_eq!(span.operation_name, "handle_request");
+
+        let span = span_data(&[("error", json!(true)), ("otel.status_code", json!("OK"))]).finish("api");
+        assert_eq!(span.status, SpanStatus::Ok);
+
+        let span = span_data(&[("error", json!(false)), ("exception.message", json!(""))]).finish("api");
+        assert_eq!(span.status, SpanStatus::Unset);
+        assert_eq!(span.tags["error"], "false");
+
+        let span = span_data(&[("error", json!(true))]).finish("api");
+        assert_eq!(span.status, SpanStatus::Error);
+    }
+}
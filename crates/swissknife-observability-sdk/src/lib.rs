@@ -23,6 +23,12 @@ pub mod amplitude;
 #[cfg(feature = "incidentio")]
 pub mod incidentio;
 
+#[cfg(feature = "exporter")]
+pub mod exporter;
+
+#[cfg(feature = "tracing-layer")]
+pub mod layer;
+
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
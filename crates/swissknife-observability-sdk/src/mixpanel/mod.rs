@@ -0,0 +1,145 @@
+use crate::{AnalyticsProvider, Error, Event, Result, UserProfile};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde_json::{json, Map, Value};
+
+const MIXPANEL_API_URL: &str = "https://api.mixpanel.com";
+pub const MIXPANEL_EU_API_URL: &str = "https://api-eu.mixpanel.com";
+
+const MAX_TRACK_BATCH_SIZE: usize = 50;
+const MAX_ENGAGE_BATCH_SIZE: usize = 2000;
+
+#[derive(Clone)]
+pub struct MixpanelClient {
+    token: String,
+    base_url: String,
+    client: Client,
+}
+
+impl MixpanelClient {
+    pub fn new(token: &str) -> Self {
+        Self {
+            token: token.to_string(),
+            base_url: MIXPANEL_API_URL.to_string(),
+            client: Client::new(),
+        }
+    }
+
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    pub async fn set_profile(&self, distinct_id: &str, properties: Value) -> Result<()> {
+        self.set_profiles(&[(distinct_id, properties)]).await
+    }
+
+    pub async fn set_profiles(&self, profiles: &[(&str, Value)]) -> Result<()> {
+        for chunk in profiles.chunks(MAX_ENGAGE_BATCH_SIZE) {
+            let updates: Vec<Value> = chunk
+                .iter()
+                .map(|(distinct_id, properties)| {
+                    json!({ "$token": self.token, "$distinct_id": distinct_id, "$set": properties })
+                })
+                .collect();
+            self.post("/engage", &updates).await?;
+        }
+        Ok(())
+    }
+
+    async fn post(&self, path: &str, payload: &[Value]) -> Result<()> {
+        let response = self
+            .client
+            .post(format!("{}{}?verbose=1", self.base_url, path))
+            .json(payload)
+            .send()
+            .await?;
+        let status = response.status();
+        let text = response.text().await?;
+
+        if !status.is_success() {
+            return Err(match status.as_u16() {
+                401 | 403 => Error::Auth(text),
+                429 => Error::RateLimited,
+                _ => Error::Api {
+                    message: text,
+                    code: Some(status.to_string()),
+                },
+            });
+        }
+
+        // Rejected payloads still come back as 200 with `status: 0` in verbose mode.
+        let body: Value = serde_json::from_str(&text)?;
+        if body["status"].as_i64() != Some(1) {
+            return Err(Error::Api {
+                message: body["error"].as_str().unwrap_or(&text).to_string(),
+                code: None,
+            });
+        }
+        Ok(())
+    }
+
+    fn to_track(&self, event: &Event) -> Value {
+        let mut properties: Map<String, Value> = event.properties.clone().into_iter().collect();
+        properties.insert("token".to_string(), json!(self.token));
+        if let Some(distinct_id) = &event.distinct_id {
+            properties.insert("distinct_id".to_string(), json!(distinct_id));
+        }
+        if let Some(timestamp) = event.timestamp {
+            properties.insert("time".to_string(), json!(timestamp.timestamp_millis()));
+        }
+        json!({ "event": event.name, "properties": properties })
+    }
+}
+
+#[async_trait]
+impl AnalyticsProvider for MixpanelClient {
+    async fn track(&self, event: &Event) -> Result<()> {
+        self.track_batch(std::slice::from_ref(event)).await
+    }
+
+    async fn track_batch(&self, events: &[Event]) -> Result<()> {
+        for chunk in events.chunks(MAX_TRACK_BATCH_SIZE) {
+            let payload: Vec<Value> = chunk.iter().map(|e| self.to_track(e)).collect();
+            self.post("/track", &payload).await?;
+        }
+        Ok(())
+    }
+
+    async fn identify(&self, profile: &UserProfile) -> Result<()> {
+        self.set_profile(&profile.distinct_id, json!(profile.properties)).await
+    }
+
+    async fn alias(&self, distinct_id: &str, alias: &str) -> Result<()> {
+        let payload = json!({
+            "event": "$create_alias",
+            "properties": { "token": self.token, "distinct_id": distinct_id, "alias": alias },
+        });
+        self.post("/track", &[payload]).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::DateTime;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_track_payload() {
+        let client = MixpanelClient::new("tok");
+        let event = Event {
+            name: "Signed Up".to_string(),
+            timestamp: DateTime::from_timestamp(1_700_000_000, 0),
+            distinct_id: Some("user-1".to_string()),
+            properties: HashMap::from([("plan".to_string(), json!("pro"))]),
+        };
+        assert_eq!(
+            client.to_track(&event),
+            json!({
+                "event": "Signed Up",
+                "properties": { "token": "tok", "distinct_id": "user-1", "time": 1_700_000_000_000i64, "plan": "pro" },
+            })
+        );
+    }
+}
@@ -0,0 +1,133 @@
+use crate::{AnalyticsProvider, Error, Event, Result, UserProfile};
+use async_trait::async_trait;
+use reqwest::{Client, Response};
+use serde_json::{json, Value};
+
+const POSTHOG_US_HOST: &str = "https://us.i.posthog.com";
+pub const POSTHOG_EU_HOST: &str = "https://eu.i.posthog.com";
+
+// The batch endpoint caps request bodies at 20MB; chunking by event count keeps
+// typical payloads well below that.
+const MAX_BATCH_SIZE: usize = 1000;
+
+#[derive(Clone)]
+pub struct PostHogClient {
+    api_key: String,
+    host: String,
+    client: Client,
+}
+
+impl PostHogClient {
+    pub fn new(api_key: &str) -> Self {
+        Self {
+            api_key: api_key.to_string(),
+            host: POSTHOG_US_HOST.to_string(),
+            client: Client::new(),
+        }
+    }
+
+    pub fn with_host(mut self, host: &str) -> Self {
+        self.host = host.trim_end_matches('/').to_string();
+        self
+    }
+
+    pub async fn get_feature_flag(&self, distinct_id: &str, key: &str) -> Result<Option<Value>> {
+        let response = self
+            .client
+            .post(format!("{}/decide/?v=3", self.host))
+            .json(&json!({ "api_key": self.api_key, "distinct_id": distinct_id }))
+            .send()
+            .await?;
+        let response = read(response).await?;
+        Ok(response["featureFlags"].get(key).cloned())
+    }
+
+    async fn capture(&self, event: Value) -> Result<()> {
+        let mut event = event;
+        event["api_key"] = json!(self.api_key);
+        let response = self
+            .client
+            .post(format!("{}/capture/", self.host))
+            .json(&event)
+            .send()
+            .await?;
+        read(response).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AnalyticsProvider for PostHogClient {
+    async fn track(&self, event: &Event) -> Result<()> {
+        self.capture(to_capture(event)?).await
+    }
+
+    async fn track_batch(&self, events: &[Event]) -> Result<()> {
+        for chunk in events.chunks(MAX_BATCH_SIZE) {
+            let batch = chunk.iter().map(to_capture).collect::<Result<Vec<_>>>()?;
+            let response = self
+                .client
+                .post(format!("{}/batch/", self.host))
+                .json(&json!({ "api_key": self.api_key, "batch": batch }))
+                .send()
+                .await?;
+            read(response).await?;
+        }
+        Ok(())
+    }
+
+    async fn identify(&self, profile: &UserProfile) -> Result<()> {
+        self.capture(json!({
+            "event": "$identify",
+            "distinct_id": profile.distinct_id,
+            "properties": { "$set": profile.properties },
+        }))
+        .await
+    }
+
+    async fn alias(&self, distinct_id: &str, alias: &str) -> Result<()> {
+        self.capture(json!({
+            "event": "$create_alias",
+            "distinct_id": distinct_id,
+            "properties": { "distinct_id": distinct_id, "alias": alias },
+        }))
+        .await
+    }
+}
+
+fn to_capture(event: &Event) -> Result<Value> {
+    let distinct_id = event
+        .distinct_id
+        .as_deref()
+        .ok_or_else(|| Error::InvalidRequest(format!("Event {} has no distinct_id", event.name)))?;
+    let mut capture = json!({
+        "event": event.name,
+        "distinct_id": distinct_id,
+        "properties": event.properties,
+    });
+    if let Some(timestamp) = event.timestamp {
+        capture["timestamp"] = json!(timestamp.to_rfc3339());
+    }
+    Ok(capture)
+}
+
+async fn read(response: Response) -> Result<Value> {
+    let status = response.status();
+    let text = response.text().await?;
+
+    if !status.is_success() {
+        return Err(match status.as_u16() {
+            401 | 403 => Error::Auth(text),
+            429 => Error::RateLimited,
+            _ => Error::Api {
+                message: text,
+                code: Some(status.to_string()),
+            },
+        });
+    }
+
+    if text.trim().is_empty() {
+        return Ok(Value::Null);
+    }
+    Ok(serde_json::from_str(&text)?)
+}
@@ -0,0 +1,81 @@
+use crate::sentry::SentryClient;
+use crate::{ErrorEvent, ErrorLevel, ErrorTrackingProvider, Result};
+use async_trait::async_trait;
+use chrono::Utc;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+#[async_trait]
+impl ErrorTrackingProvider for SentryClient {
+    async fn capture_error(&self, error: &ErrorEvent) -> Result<String> {
+        let event_id = error
+            .id
+            .clone()
+            .unwrap_or_else(|| uuid::Uuid::new_v4().simple().to_string());
+        let event = self.to_event(&event_id, error)?;
+        self.send_envelope(&event_id, &event).await?;
+        Ok(event_id)
+    }
+
+    async fn capture_message(&self, message: &str, level: ErrorLevel) -> Result<String> {
+        self.capture_error(&ErrorEvent {
+            id: None,
+            message: message.to_string(),
+            level,
+            platform: None,
+            timestamp: None,
+            exception: None,
+            tags: HashMap::new(),
+            extra: HashMap::new(),
+            user: None,
+            contexts: HashMap::new(),
+        })
+        .await
+    }
+
+    async fn list_issues(&self, query: Option<&str>, limit: u32) -> Result<Vec<ErrorEvent>> {
+        self.list_organization_issues(query, limit).await
+    }
+}
+
+impl SentryClient {
+    fn to_event(&self, event_id: &str, error: &ErrorEvent) -> Result<Value> {
+        let mut event = Map::new();
+        event.insert("event_id".to_string(), json!(event_id));
+        event.insert("timestamp".to_string(), json!(error.timestamp.unwrap_or_else(Utc::now).to_rfc3339()));
+        event.insert("platform".to_string(), json!(error.platform.as_deref().unwrap_or("other")));
+        event.insert("level".to_string(), serde_json::to_value(error.level)?);
+        event.insert("message".to_string(), json!({ "formatted": error.message }));
+        event.insert(
+            "sdk".to_string(),
+            json!({ "name": "swissknife-observability-sdk", "version": env!("CARGO_PKG_VERSION") }),
+        );
+
+        if let Some(exception) = &error.exception {
+            let mut value = json!({ "type": exception.exception_type, "value": exception.value });
+            if let Some(frames) = &exception.stacktrace {
+                value["stacktrace"] = json!({ "frames": frames });
+            }
+            event.insert("exception".to_string(), json!({ "values": [value] }));
+        }
+        if !error.tags.is_empty() {
+            event.insert("tags".to_string(), json!(error.tags));
+        }
+        if !error.extra.is_empty() {
+            event.insert("extra".to_string(), json!(error.extra));
+        }
+        if !error.contexts.is_empty() {
+            event.insert("contexts".to_string(), json!(error.contexts));
+        }
+        if let Some(user) = &error.user {
+            event.insert("user".to_string(), serde_json::to_value(user)?);
+        }
+        if let Some(environment) = self.environment() {
+            event.insert("environment".to_string(), json!(environment));
+        }
+        if let Some(release) = self.release() {
+            event.insert("release".to_string(), json!(release));
+        }
+        Ok(Value::Object(event))
+    }
+}
@@ -0,0 +1,107 @@
+use crate::sentry::client::read;
+use crate::sentry::SentryClient;
+use crate::{ErrorEvent, ErrorLevel, ExceptionInfo, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+const MAX_ISSUES_PER_PAGE: u32 = 100;
+
+impl SentryClient {
+    pub(crate) async fn list_organization_issues(&self, query: Option<&str>, limit: u32) -> Result<Vec<ErrorEvent>> {
+        let path = format!("/organizations/{}/issues/", self.organization()?);
+        let mut params = vec![
+            ("project", self.dsn().project_id().to_string()),
+            ("limit", limit.clamp(1, MAX_ISSUES_PER_PAGE).to_string()),
+        ];
+        if let Some(query) = query {
+            params.push(("query", query.to_string()));
+        }
+
+        let response = read(self.api_get(&path)?.query(&params).send().await?).await?;
+        let issues: Vec<Issue> = serde_json::from_value(response)?;
+        Ok(issues.into_iter().take(limit as usize).map(to_error_event).collect())
+    }
+
+    pub async fn list_project_issues(
+        &self,
+        project_slug: &str,
+        query: Option<&str>,
+        limit: Option<u32>,
+    ) -> Result<Vec<Issue>> {
+        let path = format!("/projects/{}/{}/issues/", self.organization()?, project_slug);
+        let mut request = self.api_get(&path)?;
+        if let Some(query) = query {
+            request = request.query(&[("query", query)]);
+        }
+
+        let response = read(request.send().await?).await?;
+        let mut issues: Vec<Issue> = serde_json::from_value(response)?;
+        if let Some(limit) = limit {
+            issues.truncate(limit as usize);
+        }
+        Ok(issues)
+    }
+}
+
+fn to_error_event(issue: Issue) -> ErrorEvent {
+    let exception = issue.metadata["type"].as_str().map(|exception_type| ExceptionInfo {
+        exception_type: exception_type.to_string(),
+        value: issue.metadata["value"].as_str().unwrap_or_default().to_string(),
+        stacktrace: None,
+    });
+    let level = match issue.level.as_deref() {
+        Some("debug") => ErrorLevel::Debug,
+        Some("info") => ErrorLevel::Info,
+        Some("warning") => ErrorLevel::Warning,
+        Some("fatal") => ErrorLevel::Fatal,
+        _ => ErrorLevel::Error,
+    };
+
+    let mut tags = HashMap::from([("short_id".to_string(), issue.short_id)]);
+    if let Some(status) = issue.status {
+        tags.insert("status".to_string(), status);
+    }
+    if let Some(culprit) = issue.culprit.filter(|c| !c.is_empty()) {
+        tags.insert("culprit".to_string(), culprit);
+    }
+    let extra = HashMap::from([
+        ("count".to_string(), json!(issue.count)),
+        ("user_count".to_string(), json!(issue.user_count)),
+        ("permalink".to_string(), json!(issue.permalink)),
+        ("first_seen".to_string(), json!(issue.first_seen)),
+    ]);
+
+    ErrorEvent {
+        id: Some(issue.id),
+        message: issue.title,
+        level,
+        platform: issue.platform,
+        timestamp: issue.last_seen,
+        exception,
+        tags,
+        extra,
+        user: None,
+        contexts: HashMap::new(),
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Issue {
+    pub id: String,
+    pub short_id: String,
+    pub title: String,
+    pub culprit: Option<String>,
+    pub level: Option<String>,
+    pub status: Option<String>,
+    pub platform: Option<String>,
+    pub permalink: Option<String>,
+    pub count: Option<String>,
+    pub user_count: Option<u64>,
+    pub first_seen: Option<DateTime<Utc>>,
+    pub last_seen: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub metadata: Value,
+}
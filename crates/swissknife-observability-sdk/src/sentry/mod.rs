@@ -0,0 +1,6 @@
+mod client;
+mod events;
+mod issues;
+
+pub use client::{Dsn, SentryClient};
+pub use issues::Issue;
@@ -0,0 +1,272 @@
+#[cfg(any(feature = "posthog", feature = "amplitude"))]
+use swissknife_observability_sdk::Event;
+#[cfg(any(feature = "posthog", feature = "amplitude", feature = "sentry", feature = "grafana"))]
+use {serde_json::Value, wiremock::MockServer};
+
+#[cfg(any(feature = "posthog", feature = "amplitude"))]
+fn events(count: usize) -> Vec<Event> {
+    (0..count)
+        .map(|i| Event {
+            name: format!("event-{}", i),
+            timestamp: None,
+            distinct_id: Some("user-1".to_string()),
+            properties: Default::default(),
+        })
+        .collect()
+}
+
+#[cfg(any(feature = "posthog", feature = "amplitude"))]
+async fn bodies(server: &MockServer) -> Vec<Value> {
+    server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .map(|r| serde_json::from_slice(&r.body).unwrap())
+        .collect()
+}
+
+#[cfg(feature = "posthog")]
+mod posthog {
+    use super::*;
+    use swissknife_observability_sdk::posthog::PostHogClient;
+    use swissknife_observability_sdk::AnalyticsProvider;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_posthog_track_batch_chunks_by_1000() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/batch/"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("{\"status\": 1}"))
+            .expect(3)
+            .mount(&server)
+            .await;
+
+        let client = PostHogClient::new("phc_key").with_host(&server.uri());
+        client.track_batch(&events(2001)).await.unwrap();
+
+        let bodies = bodies(&server).await;
+        let sizes: Vec<usize> = bodies.iter().map(|b| b["batch"].as_array().unwrap().len()).collect();
+        assert_eq!(sizes, vec![1000, 1000, 1]);
+        assert_eq!(bodies[0]["api_key"], "phc_key");
+        assert_eq!(bodies[1]["batch"][0]["event"], "event-1000");
+        assert_eq!(bodies[2]["batch"][0]["distinct_id"], "user-1");
+    }
+}
+
+#[cfg(feature = "amplitude")]
+mod amplitude {
+    use super::*;
+    use swissknife_observability_sdk::amplitude::AmplitudeClient;
+    use swissknife_observability_sdk::AnalyticsProvider;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_amplitude_track_batch_chunks_by_2000() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/2/httpapi"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let client = AmplitudeClient::new("amp_key").with_base_url(&server.uri());
+        client.track_batch(&events(2500)).await.unwrap();
+
+        let bodies = bodies(&server).await;
+        let sizes: Vec<usize> = bodies.iter().map(|b| b["events"].as_array().unwrap().len()).collect();
+        assert_eq!(sizes, vec![2000, 500]);
+        assert_eq!(bodies[1]["events"][0]["event_type"], "event-2000");
+        assert_eq!(bodies[1]["events"][0]["user_id"], "user-1");
+    }
+}
+
+#[cfg(feature = "sentry")]
+mod sentry {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use serde_json::json;
+    use std::collections::HashMap;
+    use swissknife_observability_sdk::sentry::SentryClient;
+    use swissknife_observability_sdk::{ErrorEvent, ErrorLevel, ErrorTrackingProvider, ExceptionInfo, StackFrame};
+    use wiremock::matchers::{header, method, path, query_param};
+    use wiremock::{Mock, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_sentry_capture_error_sends_event_envelope() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/42/envelope/"))
+            .and(header("content-type", "application/x-sentry-envelope"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "id": "ignored" })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let dsn = format!("{}/42", server.uri().replace("http://", "http://public@"));
+        let client = SentryClient::new(&dsn).unwrap().with_environment("prod").with_release("1.2.3");
+        let error = ErrorEvent {
+            id: Some("fc6d8c0c43fc4630ad850ee518f1b9d0".to_string()),
+            message: "connection reset".to_string(),
+            level: ErrorLevel::Warning,
+            platform: Some("rust".to_string()),
+            timestamp: None,
+            exception: Some(ExceptionInfo {
+                exception_type: "IoError".to_string(),
+                value: "connection reset by peer".to_string(),
+                stacktrace: Some(vec![StackFrame {
+                    filename: Some("src/main.rs".to_string()),
+                    function: Some("main".to_string()),
+                    lineno: Some(7),
+                    colno: None,
+                    context_line: None,
+                }]),
+            }),
+            tags: HashMap::from([("region".to_string(), "eu".to_string())]),
+            extra: HashMap::new(),
+            user: None,
+            contexts: HashMap::new(),
+        };
+        let id = client.capture_error(&error).await.unwrap();
+        assert_eq!(id, "fc6d8c0c43fc4630ad850ee518f1b9d0");
+
+        let requests = server.received_requests().await.unwrap();
+        let auth = requests[0].headers.get("x-sentry-auth").unwrap().to_str().unwrap();
+        assert!(auth.contains("sentry_key=public"));
+        let body = std::str::from_utf8(&requests[0].body).unwrap();
+        let event: Value = serde_json::from_str(body.lines().nth(2).unwrap()).unwrap();
+        assert_eq!(event["event_id"], "fc6d8c0c43fc4630ad850ee518f1b9d0");
+        assert_eq!(event["level"], "warning");
+        assert_eq!(event["platform"], "rust");
+        assert_eq!(event["message"]["formatted"], "connection reset");
+        assert_eq!(event["exception"]["values"][0]["type"], "IoError");
+        assert_eq!(event["exception"]["values"][0]["stacktrace"]["frames"][0]["lineno"], 7);
+        assert_eq!(event["tags"]["region"], "eu");
+        assert_eq!(event["environment"], "prod");
+        assert_eq!(event["release"], "1.2.3");
+        assert!(event.get("extra").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_sentry_list_issues_maps_to_error_events() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/organizations/acme/issues/"))
+            .and(query_param("project", "42"))
+            .and(query_param("limit", "100"))
+            .and(query_param("query", "is:unresolved"))
+            .and(header("authorization", "Bearer sntrys_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+                "id": "1001",
+                "shortId": "API-1",
+                "title": "ZeroDivisionError: division by zero",
+                "culprit": "app.views in divide",
+                "level": "fatal",
+                "status": "unresolved",
+                "platform": "python",
+                "permalink": "https://acme.sentry.io/issues/1001/",
+                "count": "12",
+                "userCount": 3,
+                "firstSeen": "2024-03-01T08:00:00Z",
+                "lastSeen": "2024-03-05T12:00:00Z",
+                "metadata": { "type": "ZeroDivisionError", "value": "division by zero" },
+            }])))
+            .mount(&server)
+            .await;
+
+        let client = SentryClient::new("https://public@o1.ingest.sentry.io/42")
+            .unwrap()
+            .with_api_url(&server.uri())
+            .with_organization("acme")
+            .with_auth_token("sntrys_token");
+        let issues = client.list_issues(Some("is:unresolved"), 500).await.unwrap();
+
+        assert_eq!(issues.len(), 1);
+        let issue = &issues[0];
+        assert_eq!(issue.id.as_deref(), Some("1001"));
+        assert_eq!(issue.message, "ZeroDivisionError: division by zero");
+        assert_eq!(issue.level, ErrorLevel::Fatal);
+        assert_eq!(issue.platform.as_deref(), Some("python"));
+        assert_eq!(issue.timestamp, Some(Utc.with_ymd_and_hms(2024, 3, 5, 12, 0, 0).unwrap()));
+        let exception = issue.exception.as_ref().unwrap();
+        assert_eq!(exception.exception_type, "ZeroDivisionError");
+        assert_eq!(exception.value, "division by zero");
+        assert_eq!(issue.tags["short_id"], "API-1");
+        assert_eq!(issue.tags["status"], "unresolved");
+        assert_eq!(issue.tags["culprit"], "app.views in divide");
+        assert_eq!(issue.extra["count"], "12");
+        assert_eq!(issue.extra["user_count"], 3);
+    }
+}
+
+#[cfg(feature = "grafana")]
+mod grafana {
+    use super::*;
+    use serde_json::json;
+    use swissknife_observability_sdk::grafana::GrafanaClient;
+    use swissknife_observability_sdk::{AlertSeverity, AlertStatus, AlertingProvider};
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, ResponseTemplate};
+
+    fn rule(uid: &str, state: &str, health: &str, severity: &str) -> Value {
+        json!({
+            "uid": uid,
+            "name": format!("{} rule", uid),
+            "state": state,
+            "health": health,
+            "type": "alerting",
+            "labels": { "severity": severity },
+            "annotations": { "summary": format!("{} summary", uid) },
+            "alerts": [{ "labels": {}, "activeAt": "2024-03-05T12:00:00Z" }],
+        })
+    }
+
+    #[tokio::test]
+    async fn test_grafana_list_alerts_maps_rule_state_and_severity() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/prometheus/grafana/api/v1/rules"))
+            .and(header("authorization", "Bearer glsa_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "status": "success",
+                "data": {
+                    "groups": [
+                        { "rules": [rule("firing", "firing", "ok", "Critical"), rule("pending", "pending", "ok", "high")] },
+                        {
+                            "rules": [
+                                rule("quiet", "inactive", "ok", "info"),
+                                rule("empty", "inactive", "nodata", ""),
+                                rule("broken", "inactive", "error", "page"),
+                                { "name": "cpu:rate5m", "type": "recording" },
+                            ]
+                        },
+                    ]
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let alerts = GrafanaClient::new(&server.uri(), "glsa_token").list_alerts().await.unwrap();
+
+        let mapped: Vec<(&str, AlertStatus, AlertSeverity)> =
+            alerts.iter().map(|a| (a.id.as_str(), a.status, a.severity)).collect();
+        assert_eq!(
+            mapped,
+            vec![
+                ("firing", AlertStatus::Critical, AlertSeverity::Critical),
+                ("pending", AlertStatus::Warn, AlertSeverity::High),
+                ("quiet", AlertStatus::Ok, AlertSeverity::Low),
+                ("empty", AlertStatus::NoData, AlertSeverity::Medium),
+                ("broken", AlertStatus::Unknown, AlertSeverity::Critical),
+            ]
+        );
+        assert_eq!(alerts[0].name, "firing rule");
+        assert_eq!(alerts[0].message.as_deref(), Some("firing summary"));
+        assert!(alerts[0].triggered_at.is_some());
+        assert!(alerts[2].triggered_at.is_none());
+    }
+}
@@ -366,6 +366,8 @@ pub struct NotificationItem {
 #[serde(rename_all = "camelCase")]
 pub struct NotificationRequestItem {
     pub psp_reference: String,
+    #[serde(default)]
+    pub original_reference: Option<String>,
     pub event_code: String,
     pub event_date: String,
     pub merchant_account_code: String,
@@ -373,4 +375,6 @@ pub struct NotificationRequestItem {
     pub amount: AdyenAmount,
     pub success: String,
     pub reason: Option<String>,
+    #[serde(default)]
+    pub additional_data: Option<std::collections::HashMap<String, String>>,
 }
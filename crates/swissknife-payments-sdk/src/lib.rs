@@ -17,6 +17,9 @@ pub mod braintree;
 #[cfg(feature = "adyen")]
 pub mod adyen;
 
+#[cfg(feature = "webhooks")]
+pub mod webhooks;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -66,6 +69,30 @@ impl Currency {
         }
     }
 
+    pub fn from_code(code: &str) -> Option<Self> {
+        Some(match code.to_ascii_lowercase().as_str() {
+            "usd" => Currency::USD,
+            "eur" => Currency::EUR,
+            "gbp" => Currency::GBP,
+            "cad" => Currency::CAD,
+            "aud" => Currency::AUD,
+            "jpy" => Currency::JPY,
+            "cny" => Currency::CNY,
+            "inr" => Currency::INR,
+            "brl" => Currency::BRL,
+            "mxn" => Currency::MXN,
+            "chf" => Currency::CHF,
+            "sek" => Currency::SEK,
+            "nok" => Currency::NOK,
+            "dkk" => Currency::DKK,
+            "nzd" => Currency::NZD,
+            "sgd" => Currency::SGD,
+            "hkd" => Currency::HKD,
+            "krw" => Currency::KRW,
+            _ => return None,
+        })
+    }
+
     pub fn zero_decimal(&self) -> bool {
         matches!(self, Currency::JPY | Currency::KRW)
     }
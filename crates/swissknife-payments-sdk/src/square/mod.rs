@@ -204,12 +204,7 @@ impl PaymentProvider for SquareClient {
             id: refund.id,
             payment_id: payment_id.to_string(),
             amount: amt,
-            status: match refund.status.as_str() {
-                "COMPLETED" => RefundStatus::Succeeded,
-                "PENDING" => RefundStatus::Pending,
-                "REJECTED" | "FAILED" => RefundStatus::Failed,
-                _ => RefundStatus::Pending,
-            },
+            status: refund_status(&refund.status),
             reason: reason.map(String::from),
             created_at: chrono::Utc::now(),
         })
@@ -222,7 +217,16 @@ impl PaymentProvider for SquareClient {
     }
 }
 
-fn payment_to_result(payment: SquarePayment, amount: Money, customer_id: Option<String>) -> PaymentResult {
+pub(crate) fn refund_status(status: &str) -> RefundStatus {
+    match status {
+        "COMPLETED" => RefundStatus::Succeeded,
+        "PENDING" => RefundStatus::Pending,
+        "REJECTED" | "FAILED" => RefundStatus::Failed,
+        _ => RefundStatus::Pending,
+    }
+}
+
+pub(crate) fn payment_to_result(payment: SquarePayment, amount: Money, customer_id: Option<String>) -> PaymentResult {
     PaymentResult {
         id: payment.id,
         status: match payment.status.as_str() {
@@ -208,13 +208,7 @@ impl PaymentProvider for StripeClient {
             id: refund.id,
             payment_id: payment_id.to_string(),
             amount: amount.unwrap_or_else(|| Money::new(refund.amount, Currency::USD)),
-            status: match refund.status.as_str() {
-                "succeeded" => RefundStatus::Succeeded,
-                "pending" => RefundStatus::Pending,
-                "failed" => RefundStatus::Failed,
-                "canceled" => RefundStatus::Canceled,
-                _ => RefundStatus::Pending,
-            },
+            status: refund_status(&refund.status),
             reason: reason.map(String::from),
             created_at: chrono::Utc::now(),
         })
@@ -227,6 +221,16 @@ impl PaymentProvider for StripeClient {
     }
 }
 
+pub(crate) fn refund_status(status: &str) -> RefundStatus {
+    match status {
+        "succeeded" => RefundStatus::Succeeded,
+        "pending" => RefundStatus::Pending,
+        "failed" => RefundStatus::Failed,
+        "canceled" => RefundStatus::Canceled,
+        _ => RefundStatus::Pending,
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct StripeError {
     error: StripeErrorDetail,
@@ -256,10 +260,14 @@ pub struct PaymentIntent {
     pub client_secret: Option<String>,
     pub payment_method: Option<String>,
     pub created: i64,
+    #[serde(default)]
+    pub customer: Option<String>,
+    #[serde(default)]
+    pub metadata: std::collections::HashMap<String, String>,
 }
 
 impl PaymentIntent {
-    fn into_result(self, amount: Money, customer_id: Option<String>) -> PaymentResult {
+    pub(crate) fn into_result(self, amount: Money, customer_id: Option<String>) -> PaymentResult {
         PaymentResult {
             id: self.id,
             status: match self.status.as_str() {
@@ -276,7 +284,7 @@ impl PaymentIntent {
             payment_method_id: self.payment_method,
             error_message: None,
             created_at: chrono::DateTime::from_timestamp(self.created, 0).unwrap_or_else(chrono::Utc::now),
-            metadata: std::collections::HashMap::new(),
+            metadata: self.metadata,
         }
     }
 }
@@ -286,6 +294,14 @@ pub struct Refund {
     pub id: String,
     pub amount: i64,
     pub status: String,
+    #[serde(default)]
+    pub payment_intent: Option<String>,
+    #[serde(default)]
+    pub currency: Option<String>,
+    #[serde(default)]
+    pub reason: Option<String>,
+    #[serde(default)]
+    pub created: i64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -0,0 +1,157 @@
+use super::{currency, decode_base64, verify_hmac_sha256, PaymentEvent, WebhookEvent, WebhookSource};
+use crate::adyen::{AdyenWebhookNotification, NotificationRequestItem};
+use crate::{Error, Money, PaymentResult, PaymentStatus, RefundResult, RefundStatus, Result};
+use chrono::{DateTime, Utc};
+use reqwest::header::HeaderMap;
+use std::collections::HashMap;
+
+pub struct AdyenWebhook {
+    hmac_key: String,
+}
+
+impl AdyenWebhook {
+    pub fn new(hmac_key: impl Into<String>) -> Self {
+        Self { hmac_key: hmac_key.into() }
+    }
+}
+
+impl WebhookSource for AdyenWebhook {
+    type Event = WebhookEvent;
+    type Error = Error;
+
+    fn provider(&self) -> &'static str {
+        "adyen"
+    }
+
+    // Adyen signs each notification item rather than the request body.
+    fn verify(&self, _headers: &HeaderMap, body: &[u8]) -> Result<()> {
+        let key = hex::decode(&self.hmac_key).map_err(|_| Error::WebhookVerification("Invalid HMAC key".into()))?;
+        let notification: AdyenWebhookNotification = serde_json::from_slice(body)?;
+        if notification.notification_items.is_empty() {
+            return Err(Error::WebhookVerification("Notification has no items".into()));
+        }
+        for item in &notification.notification_items {
+            let item = &item.notification_request_item;
+            let signature = item
+                .additional_data
+                .as_ref()
+                .and_then(|d| d.get("hmacSignature"))
+                .ok_or_else(|| Error::WebhookVerification(format!("Item {} is not signed", item.psp_reference)))?;
+            let signature = decode_base64(signature)?;
+            verify_hmac_sha256(&key, &[signing_string(item).as_bytes()], &signature)?;
+        }
+        Ok(())
+    }
+
+    fn parse(&self, _headers: &HeaderMap, body: &[u8]) -> Result<Vec<WebhookEvent>> {
+        let notification: AdyenWebhookNotification = serde_json::from_slice(body)?;
+        notification
+            .notification_items
+            .into_iter()
+            .map(|item| to_event(item.notification_request_item))
+            .collect()
+    }
+
+    fn acknowledgement(&self) -> &'static str {
+        "[accepted]"
+    }
+}
+
+fn signing_string(item: &NotificationRequestItem) -> String {
+    [
+        item.psp_reference.as_str(),
+        item.original_reference.as_deref().unwrap_or_default(),
+        item.merchant_account_code.as_str(),
+        item.merchant_reference.as_str(),
+        &item.amount.value.to_string(),
+        item.amount.currency.as_str(),
+        item.event_code.as_str(),
+        item.success.as_str(),
+    ]
+    .join(":")
+}
+
+fn to_event(item: NotificationRequestItem) -> Result<WebhookEvent> {
+    let success = item.success == "true";
+    let created_at = DateTime::parse_from_rfc3339(&item.event_date)
+        .map(|d| d.with_timezone(&Utc))
+        .unwrap_or_else(|_| Utc::now());
+    let amount = Money::new(item.amount.value, currency(&item.amount.currency)?);
+    let payment_id = item.original_reference.clone().unwrap_or_else(|| item.psp_reference.clone());
+
+    let payment = |status: PaymentStatus| PaymentResult {
+        id: payment_id.clone(),
+        status,
+        amount: amount.clone(),
+        customer_id: None,
+        payment_method_id: None,
+        error_message: if success { None } else { item.reason.clone() },
+        created_at,
+        metadata: HashMap::from([("merchant_reference".to_string(), item.merchant_reference.clone())]),
+    };
+    let refund = |status: RefundStatus| RefundResult {
+        id: item.psp_reference.clone(),
+        payment_id: payment_id.clone(),
+        amount: amount.clone(),
+        status,
+        reason: item.reason.clone().filter(|r| !r.is_empty()),
+        created_at,
+    };
+
+    let event = match (item.event_code.as_str(), success) {
+        ("AUTHORISATION", true) | ("CAPTURE", true) => PaymentEvent::PaymentSucceeded(payment(PaymentStatus::Succeeded)),
+        ("AUTHORISATION", false) | ("CAPTURE", false) | ("CAPTURE_FAILED", _) => {
+            PaymentEvent::PaymentFailed(payment(PaymentStatus::Failed))
+        }
+        ("CANCELLATION", true) | ("CANCEL_OR_REFUND", true) => PaymentEvent::PaymentCanceled(payment(PaymentStatus::Canceled)),
+        ("REFUND", true) => PaymentEvent::RefundUpdated(refund(RefundStatus::Succeeded)),
+        ("REFUND", false) | ("REFUND_FAILED", _) | ("REFUNDED_REVERSED", _) => {
+            PaymentEvent::RefundUpdated(refund(RefundStatus::Failed))
+        }
+        _ => PaymentEvent::Unknown(serde_json::to_value(&item)?),
+    };
+
+    Ok(WebhookEvent {
+        id: format!("{}:{}", item.psp_reference, item.event_code),
+        provider: "adyen".to_string(),
+        event_type: item.event_code,
+        created_at,
+        event,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_items_and_parse_refund() {
+        let body = br#"{"live":"false","notificationItems":[{"NotificationRequestItem":{"additionalData":{"hmacSignature":"s89+Y2Jr7NlhvyMEjDH5WLAwpgTWi2dAsvRegohWMh0="},"amount":{"currency":"EUR","value":1130},"eventCode":"REFUND","eventDate":"2024-01-15T10:00:00+01:00","merchantAccountCode":"TestMerchant","merchantReference":"order-42","originalReference":"9913333333333333","pspReference":"9914444444444444","reason":"","success":"true"}}]}"#;
+        let key = "44782DEF547AAA06C910C43932B1EB0C71FC68D9D0C057550C48EC2ACF6BA056";
+        let webhook = AdyenWebhook::new(key);
+
+        let events = webhook.receive(&HeaderMap::new(), body).unwrap();
+        assert_eq!(events[0].id, "9914444444444444:REFUND");
+        match &events[0].event {
+            PaymentEvent::RefundUpdated(refund) => {
+                assert_eq!(refund.payment_id, "9913333333333333");
+                assert_eq!(refund.status, RefundStatus::Succeeded);
+                assert_eq!(refund.amount.amount, 1130);
+                assert_eq!(refund.reason, None);
+            }
+            other => panic!("unexpected event {:?}", other),
+        }
+
+        let tampered = String::from_utf8_lossy(body).replace("1130", "11300");
+        assert!(matches!(
+            webhook.verify(&HeaderMap::new(), tampered.as_bytes()),
+            Err(Error::WebhookVerification(_))
+        ));
+
+        let empty = br#"{"live":"false","notificationItems":[]}"#;
+        assert!(matches!(
+            webhook.verify(&HeaderMap::new(), empty),
+            Err(Error::WebhookVerification(_))
+        ));
+    }
+}
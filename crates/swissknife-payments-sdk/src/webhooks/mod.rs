@@ -0,0 +1,72 @@
+#[cfg(feature = "stripe")]
+mod stripe;
+#[cfg(feature = "stripe")]
+pub use stripe::StripeWebhook;
+
+#[cfg(feature = "square")]
+mod square;
+#[cfg(feature = "square")]
+pub use square::SquareWebhook;
+
+#[cfg(feature = "adyen")]
+mod adyen;
+#[cfg(feature = "adyen")]
+pub use adyen::AdyenWebhook;
+
+pub use swissknife_webhook_sdk::{
+    decode_base64, header, optional_header, verify_hmac_sha256, EventId, VerificationError, WebhookError, WebhookSource,
+};
+#[cfg(feature = "webhook-router")]
+pub use swissknife_webhook_sdk::WebhookHandler;
+
+#[cfg(feature = "webhook-router")]
+pub type WebhookRouter = swissknife_webhook_sdk::WebhookRouter<WebhookEvent, Error>;
+
+use crate::{Error, PaymentResult, RefundResult};
+#[cfg(any(feature = "stripe", feature = "square", feature = "adyen"))]
+use crate::{Currency, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookEvent {
+    pub id: String,
+    pub provider: String,
+    pub event_type: String,
+    pub created_at: DateTime<Utc>,
+    pub event: PaymentEvent,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum PaymentEvent {
+    PaymentSucceeded(PaymentResult),
+    PaymentFailed(PaymentResult),
+    PaymentCanceled(PaymentResult),
+    PaymentUpdated(PaymentResult),
+    RefundUpdated(RefundResult),
+    Unknown(serde_json::Value),
+}
+
+impl EventId for WebhookEvent {
+    fn event_id(&self) -> Option<&str> {
+        Some(&self.id)
+    }
+}
+
+impl From<VerificationError> for Error {
+    fn from(e: VerificationError) -> Self {
+        Error::WebhookVerification(e.0)
+    }
+}
+
+impl WebhookError for Error {
+    fn is_verification(&self) -> bool {
+        matches!(self, Error::WebhookVerification(_))
+    }
+}
+
+#[cfg(any(feature = "stripe", feature = "square", feature = "adyen"))]
+pub(crate) fn currency(code: &str) -> Result<Currency> {
+    Currency::from_code(code).ok_or_else(|| Error::InvalidRequest(format!("Unsupported currency: {}", code)))
+}
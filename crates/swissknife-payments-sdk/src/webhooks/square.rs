@@ -0,0 +1,139 @@
+use super::{currency, decode_base64, header, verify_hmac_sha256, PaymentEvent, WebhookEvent, WebhookSource};
+use crate::square::{payment_to_result, refund_status, SquarePayment, SquareRefund};
+use crate::{Currency, Error, Money, PaymentStatus, RefundResult, Result};
+use chrono::{DateTime, Utc};
+use reqwest::header::HeaderMap;
+use serde::Deserialize;
+
+const SIGNATURE_HEADER: &str = "x-square-hmacsha256-signature";
+
+pub struct SquareWebhook {
+    signature_key: String,
+    notification_url: String,
+}
+
+impl SquareWebhook {
+    pub fn new(signature_key: impl Into<String>, notification_url: impl Into<String>) -> Self {
+        Self {
+            signature_key: signature_key.into(),
+            notification_url: notification_url.into(),
+        }
+    }
+}
+
+impl WebhookSource for SquareWebhook {
+    type Event = WebhookEvent;
+    type Error = Error;
+
+    fn provider(&self) -> &'static str {
+        "square"
+    }
+
+    fn verify(&self, headers: &HeaderMap, body: &[u8]) -> Result<()> {
+        let signature = decode_base64(header(headers, SIGNATURE_HEADER)?)?;
+        verify_hmac_sha256(
+            self.signature_key.as_bytes(),
+            &[self.notification_url.as_bytes(), body],
+            &signature,
+        )
+        .map_err(Error::from)
+    }
+
+    fn parse(&self, _headers: &HeaderMap, body: &[u8]) -> Result<Vec<WebhookEvent>> {
+        let notification: Notification = serde_json::from_slice(body)?;
+        let created_at = parse_time(notification.created_at.as_deref());
+        let object = notification.data.object;
+
+        let event = match notification.event_type.as_str() {
+            "payment.created" | "payment.updated" => {
+                let payment: SquarePayment = serde_json::from_value(object["payment"].clone())?;
+                let amount = to_money(payment.amount_money.as_ref().map(|m| (m.amount, m.currency.as_str())))?;
+                let customer_id = payment.customer_id.clone();
+                let payment_created_at = payment.created_at.clone();
+                let mut result = payment_to_result(payment, amount, customer_id);
+                result.created_at = parse_time(payment_created_at.as_deref());
+                match result.status {
+                    PaymentStatus::Succeeded => PaymentEvent::PaymentSucceeded(result),
+                    PaymentStatus::Failed => PaymentEvent::PaymentFailed(result),
+                    PaymentStatus::Canceled => PaymentEvent::PaymentCanceled(result),
+                    _ => PaymentEvent::PaymentUpdated(result),
+                }
+            }
+            "refund.created" | "refund.updated" => {
+                let refund: SquareRefund = serde_json::from_value(object["refund"].clone())?;
+                PaymentEvent::RefundUpdated(RefundResult {
+                    amount: to_money(refund.amount_money.as_ref().map(|m| (m.amount, m.currency.as_str())))?,
+                    id: refund.id,
+                    payment_id: refund.payment_id.unwrap_or_default(),
+                    status: refund_status(&refund.status),
+                    reason: refund.reason,
+                    created_at,
+                })
+            }
+            _ => PaymentEvent::Unknown(object),
+        };
+
+        Ok(vec![WebhookEvent {
+            id: notification.event_id,
+            provider: self.provider().to_string(),
+            event_type: notification.event_type,
+            created_at,
+            event,
+        }])
+    }
+}
+
+fn to_money(money: Option<(i64, &str)>) -> Result<Money> {
+    match money {
+        Some((amount, code)) => Ok(Money::new(amount, currency(code)?)),
+        None => Ok(Money::new(0, Currency::USD)),
+    }
+}
+
+fn parse_time(value: Option<&str>) -> DateTime<Utc> {
+    value
+        .and_then(|v| DateTime::parse_from_rfc3339(v).ok())
+        .map(|d| d.with_timezone(&Utc))
+        .unwrap_or_else(Utc::now)
+}
+
+#[derive(Debug, Deserialize)]
+struct Notification {
+    #[serde(rename = "type")]
+    event_type: String,
+    event_id: String,
+    created_at: Option<String>,
+    #[serde(default)]
+    data: NotificationData,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct NotificationData {
+    #[serde(default)]
+    object: serde_json::Value,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signature_covers_notification_url() {
+        let body = br#"{"merchant_id":"6SSW7HV8K2ST5","type":"payment.updated","event_id":"13b867cf-db3d-4b1c-90b6-2f32a9d78124","created_at":"2020-02-06T21:27:34.308Z","data":{"type":"payment","id":"hYy9pRFVxpDsO1FB05SunFWUe9JZY","object":{"payment":{"id":"hYy9pRFVxpDsO1FB05SunFWUe9JZY","status":"COMPLETED","amount_money":{"amount":100,"currency":"CAD"},"created_at":"2020-02-06T21:27:30.792Z"}}}}"#;
+        let webhook = SquareWebhook::new("sq-key", "https://example.com/webhooks/square");
+
+        let mut headers = HeaderMap::new();
+        headers.insert(SIGNATURE_HEADER, "21eAiulysCW/QzQWDhvDL3BHV3/+yeTp0njnWY3wVAY=".parse().unwrap());
+        let events = webhook.receive(&headers, body).unwrap();
+        match &events[0].event {
+            PaymentEvent::PaymentSucceeded(result) => {
+                assert_eq!(result.amount.currency, Currency::CAD);
+                assert_eq!(result.created_at.to_rfc3339(), "2020-02-06T21:27:30.792+00:00");
+            }
+            other => panic!("unexpected event {:?}", other),
+        }
+
+        let moved = SquareWebhook::new("sq-key", "https://example.com/other");
+        assert!(matches!(moved.verify(&headers, body), Err(Error::WebhookVerification(_))));
+    }
+}
@@ -0,0 +1,157 @@
+use super::{currency, header, verify_hmac_sha256, PaymentEvent, WebhookEvent, WebhookSource};
+use crate::stripe::{refund_status, PaymentIntent, Refund, StripeWebhookEvent};
+use crate::{Error, Money, PaymentStatus, RefundResult, Result};
+use chrono::{DateTime, Utc};
+use reqwest::header::HeaderMap;
+use std::time::Duration;
+
+const SIGNATURE_HEADER: &str = "stripe-signature";
+const DEFAULT_TOLERANCE: Duration = Duration::from_secs(300);
+
+pub struct StripeWebhook {
+    secret: String,
+    tolerance: Duration,
+}
+
+impl StripeWebhook {
+    pub fn new(secret: impl Into<String>) -> Self {
+        Self {
+            secret: secret.into(),
+            tolerance: DEFAULT_TOLERANCE,
+        }
+    }
+
+    pub fn with_tolerance(mut self, tolerance: Duration) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+}
+
+impl WebhookSource for StripeWebhook {
+    type Event = WebhookEvent;
+    type Error = Error;
+
+    fn provider(&self) -> &'static str {
+        "stripe"
+    }
+
+    fn verify(&self, headers: &HeaderMap, body: &[u8]) -> Result<()> {
+        let signature = header(headers, SIGNATURE_HEADER)?;
+        let mut timestamp = None;
+        let mut signatures = Vec::new();
+        for part in signature.split(',') {
+            if let Some(t) = part.strip_prefix("t=") {
+                timestamp = Some(t);
+            } else if let Some(s) = part.strip_prefix("v1=") {
+                signatures.push(s);
+            }
+        }
+
+        let timestamp = timestamp.ok_or_else(|| Error::WebhookVerification("Missing timestamp".into()))?;
+        let sent_at: i64 = timestamp
+            .parse()
+            .map_err(|_| Error::WebhookVerification("Invalid timestamp".into()))?;
+        if Utc::now().timestamp().abs_diff(sent_at) > self.tolerance.as_secs() {
+            return Err(Error::WebhookVerification("Timestamp outside the tolerance window".into()));
+        }
+
+        let valid = signatures
+            .into_iter()
+            .filter_map(|s| hex::decode(s).ok())
+            .any(|s| verify_hmac_sha256(self.secret.as_bytes(), &[timestamp.as_bytes(), b".", body], &s).is_ok());
+        if valid {
+            Ok(())
+        } else {
+            Err(Error::WebhookVerification("No matching v1 signature".into()))
+        }
+    }
+
+    fn parse(&self, _headers: &HeaderMap, body: &[u8]) -> Result<Vec<WebhookEvent>> {
+        let event: StripeWebhookEvent = serde_json::from_slice(body)?;
+        let object = event.data.get("object").cloned().unwrap_or_default();
+
+        let payment = |status: Option<PaymentStatus>| -> Result<_> {
+            let error_message = object["last_payment_error"]["message"].as_str().map(String::from);
+            let intent: PaymentIntent = serde_json::from_value(object.clone())?;
+            let amount = Money::new(intent.amount, currency(&intent.currency)?);
+            let customer_id = intent.customer.clone();
+            let mut result = intent.into_result(amount, customer_id);
+            if let Some(status) = status {
+                result.status = status;
+            }
+            result.error_message = error_message;
+            Ok(result)
+        };
+
+        let parsed = match event.event_type.as_str() {
+            "payment_intent.succeeded" => PaymentEvent::PaymentSucceeded(payment(None)?),
+            "payment_intent.payment_failed" => PaymentEvent::PaymentFailed(payment(Some(PaymentStatus::Failed))?),
+            "payment_intent.canceled" => PaymentEvent::PaymentCanceled(payment(None)?),
+            t if t.starts_with("payment_intent.") => PaymentEvent::PaymentUpdated(payment(None)?),
+            "refund.created" | "refund.updated" | "refund.failed" | "charge.refund.updated" => {
+                let refund: Refund = serde_json::from_value(object.clone())?;
+                PaymentEvent::RefundUpdated(RefundResult {
+                    id: refund.id,
+                    payment_id: refund.payment_intent.unwrap_or_default(),
+                    amount: Money::new(refund.amount, currency(refund.currency.as_deref().unwrap_or("usd"))?),
+                    status: refund_status(&refund.status),
+                    reason: refund.reason,
+                    created_at: DateTime::from_timestamp(refund.created, 0).unwrap_or_else(Utc::now),
+                })
+            }
+            _ => PaymentEvent::Unknown(object),
+        };
+
+        Ok(vec![WebhookEvent {
+            id: event.id,
+            provider: self.provider().to_string(),
+            event_type: event.event_type,
+            created_at: DateTime::from_timestamp(event.created, 0).unwrap_or_else(Utc::now),
+            event: parsed,
+        }])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Currency;
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256;
+
+    fn sign(secret: &str, timestamp: i64, body: &[u8]) -> HeaderMap {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(format!("{}.", timestamp).as_bytes());
+        mac.update(body);
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            SIGNATURE_HEADER,
+            format!("t={},v1={},v0=6ffbb59b", timestamp, hex::encode(mac.finalize().into_bytes()))
+                .parse()
+                .unwrap(),
+        );
+        headers
+    }
+
+    #[test]
+    fn test_verify_and_parse_payment_intent() {
+        let body = br#"{"id":"evt_1","type":"payment_intent.payment_failed","created":1700000000,"data":{"object":{"id":"pi_1","amount":2000,"currency":"eur","status":"requires_payment_method","payment_method":null,"client_secret":null,"created":1700000000,"customer":"cus_9","last_payment_error":{"message":"Your card was declined."}}}}"#;
+        let webhook = StripeWebhook::new("whsec_test");
+
+        let events = webhook.receive(&sign("whsec_test", Utc::now().timestamp(), body), body).unwrap();
+        match &events[0].event {
+            PaymentEvent::PaymentFailed(result) => {
+                assert_eq!(result.status, PaymentStatus::Failed);
+                assert_eq!(result.amount.currency, Currency::EUR);
+                assert_eq!(result.customer_id.as_deref(), Some("cus_9"));
+                assert_eq!(result.error_message.as_deref(), Some("Your card was declined."));
+            }
+            other => panic!("unexpected event {:?}", other),
+        }
+
+        let stale = sign("whsec_test", Utc::now().timestamp() - 3600, body);
+        assert!(matches!(webhook.verify(&stale, body), Err(Error::WebhookVerification(_))));
+        let forged = sign("whsec_other", Utc::now().timestamp(), body);
+        assert!(matches!(webhook.verify(&forged, body), Err(Error::WebhookVerification(_))));
+    }
+}
@@ -0,0 +1,732 @@
+use crate::{
+    state_type_from_name, Comment, CreateIssue, Error, Issue, IssueFilter, IssueType, Label, ListOptions, ListResult,
+    Project, ProjectManagementProvider, ProjectStatus, Result, Sprint, SprintProvider, UpdateIssue, User,
+    WorkflowState,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use reqwest::{Method, RequestBuilder};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use std::collections::{HashMap, HashSet};
+
+const API_BASE: &str = "https://app.asana.com/api/1.0";
+const DEFAULT_PAGE_SIZE: u32 = 50;
+const MAX_PAGE_SIZE: u32 = 100;
+const PROJECT_FIELDS: &str = "name,notes,permalink_url,owner,archived,completed,created_at,modified_at";
+const TASK_FIELDS: &str = "name,notes,completed,completed_at,created_at,modified_at,due_on,assignee,created_by,parent,\
+    tags.name,memberships.project,memberships.section.name,permalink_url,resource_subtype";
+const STORY_FIELDS: &str = "text,created_by,created_at,resource_subtype,target";
+
+#[derive(Clone)]
+pub struct AsanaClient {
+    access_token: String,
+    workspace: Option<String>,
+    team: Option<String>,
+    http: reqwest::Client,
+}
+
+impl AsanaClient {
+    pub fn new(access_token: impl Into<String>) -> Self {
+        Self {
+            access_token: access_token.into(),
+            workspace: None,
+            team: None,
+            http: reqwest::Client::new(),
+        }
+    }
+
+    pub fn with_workspace(mut self, workspace: impl Into<String>) -> Self {
+        self.workspace = Some(workspace.into());
+        self
+    }
+
+    pub fn with_team(mut self, team: impl Into<String>) -> Self {
+        self.team = Some(team.into());
+        self
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        self.http
+            .request(method, format!("{}{}", API_BASE, path))
+            .bearer_auth(&self.access_token)
+    }
+
+    async fn send<T: DeserializeOwned>(&self, method: Method, path: &str, data: Option<Value>) -> Result<AsanaResponse<T>> {
+        let mut request = self.request(method, path);
+        if let Some(data) = data {
+            request = request.json(&json!({ "data": data }));
+        }
+
+        let response = request.send().await?;
+        let status = response.status().as_u16();
+        let text = response.text().await?;
+
+        if !(200..300).contains(&status) {
+            let message = serde_json::from_str::<AsanaErrorResponse>(&text)
+                .ok()
+                .and_then(|e| e.errors.into_iter().next())
+                .map(|e| e.message)
+                .unwrap_or(text);
+            return Err(match status {
+                400 => Error::Validation(message),
+                401 | 403 => Error::Auth(message),
+                404 => Error::NotFound(message),
+                429 => Error::RateLimited,
+                _ => Error::Api {
+                    message,
+                    code: Some(status.to_string()),
+                },
+            });
+        }
+
+        Ok(serde_json::from_str(&text)?)
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        Ok(self.send(Method::GET, path, None).await?.data)
+    }
+
+    async fn workspace(&self) -> Result<String> {
+        if let Some(workspace) = &self.workspace {
+            return Ok(workspace.clone());
+        }
+        let workspaces: Vec<AsanaRef> = self.get("/workspaces?limit=1").await?;
+        workspaces
+            .into_iter()
+            .next()
+            .map(|w| w.gid)
+            .ok_or_else(|| Error::NotFound("No Asana workspace available".to_string()))
+    }
+
+    async fn tag_ids(&self, names: &[String]) -> Result<HashMap<String, String>> {
+        if names.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let workspace = self.workspace().await?;
+        let tags: Vec<AsanaTag> = self
+            .get(&format!("/tags?workspace={}&limit={}&opt_fields=name", workspace, MAX_PAGE_SIZE))
+            .await?;
+        let by_name: HashMap<String, String> = tags.into_iter().map(|t| (t.name.to_lowercase(), t.gid)).collect();
+
+        names
+            .iter()
+            .map(|name| {
+                by_name
+                    .get(&name.to_lowercase())
+                    .map(|gid| (name.clone(), gid.clone()))
+                    .ok_or_else(|| Error::Validation(format!("Unknown Asana tag: {}", name)))
+            })
+            .collect()
+    }
+
+    async fn task_page(&self, path: &str, options: &ListOptions) -> Result<AsanaResponse<Vec<AsanaTask>>> {
+        let mut path = format!(
+            "{}limit={}&opt_fields={}",
+            path,
+            options.per_page.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE),
+            TASK_FIELDS
+        );
+        if let Some(offset) = &options.cursor {
+            path.push_str(&format!("&offset={}", offset));
+        }
+        self.send(Method::GET, &path, None).await
+    }
+
+    async fn move_to_section(&self, task: &AsanaTask, status: &str) -> Result<()> {
+        let project = task
+            .memberships
+            .iter()
+            .find_map(|m| m.project.as_ref())
+            .ok_or_else(|| Error::Validation("Task does not belong to a project".to_string()))?;
+        let sections: Vec<AsanaRef> = self
+            .get(&format!("/projects/{}/sections?opt_fields=name", project.gid))
+            .await?;
+        let section = sections
+            .into_iter()
+            .find(|s| s.name.as_deref().is_some_and(|n| n.eq_ignore_ascii_case(status)))
+            .ok_or_else(|| Error::Validation(format!("Unknown Asana section: {}", status)))?;
+        self.send::<Value>(
+            Method::POST,
+            &format!("/sections/{}/addTask", section.gid),
+            Some(json!({ "task": task.gid })),
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn set_tags(&self, task: &AsanaTask, labels: &[String]) -> Result<()> {
+        let current: HashMap<String, String> = task
+            .tags
+            .iter()
+            .filter_map(|t| Some((t.name.clone()?.to_lowercase(), t.gid.clone())))
+            .collect();
+        let wanted: HashSet<String> = labels.iter().map(|l| l.to_lowercase()).collect();
+
+        let missing: Vec<String> = labels.iter().filter(|l| !current.contains_key(&l.to_lowercase())).cloned().collect();
+        for gid in self.tag_ids(&missing).await?.into_values() {
+            self.send::<Value>(Method::POST, &format!("/tasks/{}/addTag", task.gid), Some(json!({ "tag": gid })))
+                .await?;
+        }
+        for (name, gid) in &current {
+            if !wanted.contains(name) {
+                self.send::<Value>(Method::POST, &format!("/tasks/{}/removeTag", task.gid), Some(json!({ "tag": gid })))
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn fetch_task(&self, id: &str) -> Result<AsanaTask> {
+        self.get(&format!("/tasks/{}?opt_fields={}", id, TASK_FIELDS)).await
+    }
+}
+
+#[async_trait]
+impl ProjectManagementProvider for AsanaClient {
+    async fn list_projects(&self, options: &ListOptions) -> Result<ListResult<Project>> {
+        let workspace = self.workspace().await?;
+        let mut path = format!(
+            "/projects?workspace={}&limit={}&opt_fields={}",
+            workspace,
+            options.per_page.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE),
+            PROJECT_FIELDS
+        );
+        if let Some(offset) = &options.cursor {
+            path.push_str(&format!("&offset={}", offset));
+        }
+        let response: AsanaResponse<Vec<AsanaProject>> = self.send(Method::GET, &path, None).await?;
+        Ok(response.into_list_result(|projects| projects.into_iter().map(to_project).collect()))
+    }
+
+    async fn get_project(&self, id: &str) -> Result<Project> {
+        let project: AsanaProject = self.get(&format!("/projects/{}?opt_fields={}", id, PROJECT_FIELDS)).await?;
+        Ok(to_project(project))
+    }
+
+    async fn create_project(&self, name: &str, description: Option<&str>) -> Result<Project> {
+        let mut data = json!({ "name": name, "workspace": self.workspace().await? });
+        if let Some(team) = &self.team {
+            data["team"] = json!(team);
+        }
+        if let Some(description) = description {
+            data["notes"] = json!(description);
+        }
+        let response: AsanaResponse<AsanaProject> = self
+            .send(Method::POST, &format!("/projects?opt_fields={}", PROJECT_FIELDS), Some(data))
+            .await?;
+        Ok(to_project(response.data))
+    }
+
+    async fn update_project(&self, id: &str, name: Option<&str>, description: Option<&str>) -> Result<Project> {
+        let mut data = Map::new();
+        if let Some(name) = name {
+            data.insert("name".to_string(), json!(name));
+        }
+        if let Some(description) = description {
+            data.insert("notes".to_string(), json!(description));
+        }
+        let response: AsanaResponse<AsanaProject> = self
+            .send(Method::PUT, &format!("/projects/{}?opt_fields={}", id, PROJECT_FIELDS), Some(Value::Object(data)))
+            .await?;
+        Ok(to_project(response.data))
+    }
+
+    async fn archive_project(&self, id: &str) -> Result<()> {
+        self.send::<Value>(Method::PUT, &format!("/projects/{}", id), Some(json!({ "archived": true })))
+            .await?;
+        Ok(())
+    }
+
+    async fn list_issues(&self, project_id: &str, filter: &IssueFilter, options: &ListOptions) -> Result<ListResult<Issue>> {
+        let response = self.task_page(&format!("/projects/{}/tasks?", project_id), options).await?;
+        Ok(response.into_list_result(|tasks| {
+            tasks
+                .into_iter()
+                .map(|t| to_issue(t, Some(project_id)))
+                .filter(|issue| matches_filter(issue, filter))
+                .collect()
+        }))
+    }
+
+    async fn get_issue(&self, id: &str) -> Result<Issue> {
+        Ok(to_issue(self.fetch_task(id).await?, None))
+    }
+
+    async fn create_issue(&self, project_id: &str, issue: &CreateIssue) -> Result<Issue> {
+        let mut data = Map::new();
+        data.insert("name".to_string(), json!(issue.title));
+        if let Some(description) = &issue.description {
+            data.insert("notes".to_string(), json!(description));
+        }
+        if let Some(assignee) = &issue.assignee_id {
+            data.insert("assignee".to_string(), json!(assignee));
+        }
+        if let Some(due) = issue.due_date {
+            data.insert("due_on".to_string(), json!(due.format("%Y-%m-%d").to_string()));
+        }
+        let tags: Vec<String> = self.tag_ids(&issue.labels).await?.into_values().collect();
+        if !tags.is_empty() {
+            data.insert("tags".to_string(), json!(tags));
+        }
+
+        let path = match &issue.parent_id {
+            Some(parent) => format!("/tasks/{}/subtasks?opt_fields={}", parent, TASK_FIELDS),
+            None => {
+                data.insert("projects".to_string(), json!([project_id]));
+                format!("/tasks?opt_fields={}", TASK_FIELDS)
+            }
+        };
+        let response: AsanaResponse<AsanaTask> = self.send(Method::POST, &path, Some(Value::Object(data))).await?;
+        Ok(to_issue(response.data, Some(project_id)))
+    }
+
+    async fn update_issue(&self, id: &str, update: &UpdateIssue) -> Result<Issue> {
+        let mut data = Map::new();
+        if let Some(title) = &update.title {
+            data.insert("name".to_string(), json!(title));
+        }
+        if let Some(description) = &update.description {
+            data.insert("notes".to_string(), json!(description));
+        }
+        if let Some(assignee) = &update.assignee_id {
+            data.insert("assignee".to_string(), json!(assignee));
+        }
+        if let Some(due) = update.due_date {
+            data.insert("due_on".to_string(), json!(due.format("%Y-%m-%d").to_string()));
+        }
+        if !data.is_empty() {
+            self.send::<Value>(Method::PUT, &format!("/tasks/{}", id), Some(Value::Object(data)))
+                .await?;
+        }
+
+        if update.status.is_some() || update.labels.is_some() {
+            let task = self.fetch_task(id).await?;
+            if let Some(status) = &update.status {
+                self.move_to_section(&task, status).await?;
+            }
+            if let Some(labels) = &update.labels {
+                self.set_tags(&task, labels).await?;
+            }
+        }
+        self.get_issue(id).await
+    }
+
+    async fn delete_issue(&self, id: &str) -> Result<()> {
+        self.send::<Value>(Method::DELETE, &format!("/tasks/{}", id), None).await?;
+        Ok(())
+    }
+
+    // Typeahead works on every plan (the task search API needs Premium) but only
+    // returns the best `count` matches, at most 100, and cannot be paged, so
+    // `has_more` is always false.
+    async fn search_issues(&self, query: &str, options: &ListOptions) -> Result<ListResult<Issue>> {
+        let workspace = self.workspace().await?;
+        let path = format!(
+            "/workspaces/{}/typeahead?resource_type=task&query={}&count={}&opt_fields={}",
+            workspace,
+            encode(query),
+            options.per_page.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE),
+            TASK_FIELDS
+        );
+        let tasks: Vec<AsanaTask> = self.get(&path).await?;
+        Ok(ListResult {
+            total: None,
+            items: tasks.into_iter().map(|t| to_issue(t, None)).collect(),
+            has_more: false,
+            next_cursor: None,
+        })
+    }
+
+    async fn list_labels(&self, _project_id: &str) -> Result<Vec<Label>> {
+        let workspace = self.workspace().await?;
+        let tags: Vec<AsanaTag> = self
+            .get(&format!("/tags?workspace={}&limit={}&opt_fields=name,color,notes", workspace, MAX_PAGE_SIZE))
+            .await?;
+        Ok(tags.into_iter().map(to_label).collect())
+    }
+
+    async fn create_label(&self, _project_id: &str, name: &str, color: Option<&str>) -> Result<Label> {
+        let mut data = json!({ "name": name, "workspace": self.workspace().await? });
+        if let Some(color) = color {
+            data["color"] = json!(color);
+        }
+        let response: AsanaResponse<AsanaTag> = self
+            .send(Method::POST, "/tags?opt_fields=name,color,notes", Some(data))
+            .await?;
+        Ok(to_label(response.data))
+    }
+
+    async fn list_workflow_states(&self, project_id: &str) -> Result<Vec<WorkflowState>> {
+        let sections: Vec<AsanaRef> = self
+            .get(&format!("/projects/{}/sections?opt_fields=name", project_id))
+            .await?;
+        Ok(sections
+            .into_iter()
+            .enumerate()
+            .map(|(position, section)| {
+                let name = section.name.unwrap_or_default();
+                WorkflowState {
+                    id: section.gid,
+                    state_type: state_type_from_name(&name),
+                    name,
+                    color: None,
+                    position: Some(position as i32),
+                }
+            })
+            .collect())
+    }
+
+    async fn list_comments(&self, issue_id: &str) -> Result<Vec<Comment>> {
+        let stories: Vec<AsanaStory> = self
+            .get(&format!("/tasks/{}/stories?opt_fields={}", issue_id, STORY_FIELDS))
+            .await?;
+        Ok(stories
+            .into_iter()
+            .filter(|s| s.resource_subtype.as_deref() == Some("comment_added"))
+            .map(|s| to_comment(s, issue_id))
+            .collect())
+    }
+
+    async fn create_comment(&self, issue_id: &str, body: &str) -> Result<Comment> {
+        let response: AsanaResponse<AsanaStory> = self
+            .send(
+                Method::POST,
+                &format!("/tasks/{}/stories?opt_fields={}", issue_id, STORY_FIELDS),
+                Some(json!({ "text": body })),
+            )
+            .await?;
+        Ok(to_comment(response.data, issue_id))
+    }
+
+    async fn update_comment(&self, comment_id: &str, body: &str) -> Result<Comment> {
+        let response: AsanaResponse<AsanaStory> = self
+            .send(
+                Method::PUT,
+                &format!("/stories/{}?opt_fields={}", comment_id, STORY_FIELDS),
+                Some(json!({ "text": body })),
+            )
+            .await?;
+        Ok(to_comment(response.data, ""))
+    }
+
+    async fn delete_comment(&self, comment_id: &str) -> Result<()> {
+        self.send::<Value>(Method::DELETE, &format!("/stories/{}", comment_id), None).await?;
+        Ok(())
+    }
+
+    async fn list_users(&self) -> Result<Vec<User>> {
+        let workspace = self.workspace().await?;
+        let users: Vec<AsanaUser> = self
+            .get(&format!("/users?workspace={}&opt_fields=name,email,photo.image_60x60", workspace))
+            .await?;
+        Ok(users.into_iter().map(to_user).collect())
+    }
+
+    async fn get_current_user(&self) -> Result<User> {
+        let user: AsanaUser = self.get("/users/me?opt_fields=name,email,photo.image_60x60").await?;
+        Ok(to_user(user))
+    }
+}
+
+#[async_trait]
+impl SprintProvider for AsanaClient {
+    async fn list_sprints(&self, _project_id: &str) -> Result<Vec<Sprint>> {
+        Err(sprints_unsupported())
+    }
+
+    async fn get_active_sprint(&self, _project_id: &str) -> Result<Option<Sprint>> {
+        Err(sprints_unsupported())
+    }
+
+    async fn create_sprint(&self, _project_id: &str, _name: &str, _goal: Option<&str>, _start: Option<NaiveDate>, _end: Option<NaiveDate>) -> Result<Sprint> {
+        Err(sprints_unsupported())
+    }
+
+    async fn start_sprint(&self, _sprint_id: &str) -> Result<Sprint> {
+        Err(sprints_unsupported())
+    }
+
+    async fn complete_sprint(&self, _sprint_id: &str) -> Result<Sprint> {
+        Err(sprints_unsupported())
+    }
+}
+
+fn sprints_unsupported() -> Error {
+    Error::Unsupported("Asana has no sprints; use projects or sections instead".to_string())
+}
+
+fn matches_filter(issue: &Issue, filter: &IssueFilter) -> bool {
+    filter.assignee_id.as_ref().is_none_or(|a| issue.assignee_id.as_ref() == Some(a))
+        && filter.status.as_ref().is_none_or(|s| issue.status.eq_ignore_ascii_case(s))
+        && filter
+            .labels
+            .iter()
+            .all(|label| issue.labels.iter().any(|l| l.eq_ignore_ascii_case(label)))
+        && filter.issue_type.is_none_or(|t| issue.issue_type == t)
+        && filter.search.as_ref().is_none_or(|query| {
+            let query = query.to_lowercase();
+            issue.title.to_lowercase().contains(&query)
+                || issue.description.as_ref().is_some_and(|d| d.to_lowercase().contains(&query))
+        })
+}
+
+fn encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+fn to_project(project: AsanaProject) -> Project {
+    let status = if project.archived.unwrap_or(false) {
+        ProjectStatus::Archived
+    } else if project.completed.unwrap_or(false) {
+        ProjectStatus::Completed
+    } else {
+        ProjectStatus::Active
+    };
+    Project {
+        id: project.gid,
+        name: project.name,
+        key: None,
+        description: project.notes.filter(|n| !n.is_empty()),
+        url: project.permalink_url,
+        lead_id: project.owner.map(|o| o.gid),
+        status: Some(status),
+        created_at: project.created_at,
+        updated_at: project.modified_at,
+        extra: HashMap::new(),
+    }
+}
+
+fn to_issue(task: AsanaTask, project_id: Option<&str>) -> Issue {
+    let membership = task
+        .memberships
+        .iter()
+        .find(|m| project_id.is_none_or(|p| m.project.as_ref().is_some_and(|project| project.gid == p)))
+        .or(task.memberships.first());
+    let section = membership.and_then(|m| m.section.as_ref()).and_then(|s| s.name.clone());
+    let project = project_id
+        .map(str::to_string)
+        .or_else(|| membership.and_then(|m| m.project.as_ref()).map(|p| p.gid.clone()))
+        .unwrap_or_default();
+    let status = section.unwrap_or_else(|| if task.completed { "Completed" } else { "Open" }.to_string());
+
+    let mut extra = HashMap::new();
+    extra.insert("completed".to_string(), json!(task.completed));
+    if let Some(url) = &task.permalink_url {
+        extra.insert("url".to_string(), json!(url));
+    }
+
+    Issue {
+        id: task.gid,
+        key: None,
+        title: task.name,
+        description: task.notes.filter(|n| !n.is_empty()),
+        issue_type: match (&task.parent, task.resource_subtype.as_deref()) {
+            (Some(_), _) => IssueType::Subtask,
+            (None, Some("milestone")) => IssueType::Epic,
+            _ => IssueType::Task,
+        },
+        status,
+        priority: None,
+        assignee_id: task.assignee.map(|a| a.gid),
+        reporter_id: task.created_by.map(|c| c.gid),
+        project_id: project,
+        parent_id: task.parent.map(|p| p.gid),
+        labels: task.tags.into_iter().filter_map(|t| t.name).collect(),
+        estimate: None,
+        due_date: task.due_on.and_then(|d| NaiveDate::parse_from_str(&d, "%Y-%m-%d").ok()),
+        created_at: task.created_at.unwrap_or_default(),
+        updated_at: task.modified_at,
+        completed_at: task.completed_at,
+        extra,
+    }
+}
+
+fn to_label(tag: AsanaTag) -> Label {
+    Label {
+        id: tag.gid,
+        name: tag.name,
+        color: tag.color,
+        description: tag.notes.filter(|n| !n.is_empty()),
+    }
+}
+
+fn to_comment(story: AsanaStory, issue_id: &str) -> Comment {
+    Comment {
+        id: story.gid,
+        body: story.text.unwrap_or_default(),
+        author_id: story.created_by.map(|c| c.gid).unwrap_or_default(),
+        issue_id: story.target.map(|t| t.gid).unwrap_or_else(|| issue_id.to_string()),
+        created_at: story.created_at.unwrap_or_default(),
+        updated_at: None,
+    }
+}
+
+fn to_user(user: AsanaUser) -> User {
+    User {
+        id: user.gid,
+        name: user.name.unwrap_or_default(),
+        email: user.email,
+        avatar_url: user.photo.and_then(|p| p.image_60x60),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct AsanaResponse<T> {
+    data: T,
+    next_page: Option<AsanaNextPage>,
+}
+
+impl<T> AsanaResponse<T> {
+    fn into_list_result<U>(self, map: impl FnOnce(T) -> Vec<U>) -> ListResult<U> {
+        let next_cursor = self.next_page.map(|p| p.offset);
+        ListResult {
+            items: map(self.data),
+            total: None,
+            has_more: next_cursor.is_some(),
+            next_cursor,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct AsanaNextPage {
+    offset: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct AsanaErrorResponse {
+    #[serde(default)]
+    errors: Vec<AsanaError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AsanaError {
+    message: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct AsanaRef {
+    gid: String,
+    name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AsanaProject {
+    gid: String,
+    name: String,
+    notes: Option<String>,
+    permalink_url: Option<String>,
+    owner: Option<AsanaRef>,
+    archived: Option<bool>,
+    completed: Option<bool>,
+    created_at: Option<DateTime<Utc>>,
+    modified_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AsanaMembership {
+    project: Option<AsanaRef>,
+    section: Option<AsanaRef>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AsanaTask {
+    gid: String,
+    #[serde(default)]
+    name: String,
+    notes: Option<String>,
+    #[serde(default)]
+    completed: bool,
+    completed_at: Option<DateTime<Utc>>,
+    created_at: Option<DateTime<Utc>>,
+    modified_at: Option<DateTime<Utc>>,
+    due_on: Option<String>,
+    assignee: Option<AsanaRef>,
+    created_by: Option<AsanaRef>,
+    parent: Option<AsanaRef>,
+    #[serde(default)]
+    tags: Vec<AsanaRef>,
+    #[serde(default)]
+    memberships: Vec<AsanaMembership>,
+    permalink_url: Option<String>,
+    resource_subtype: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AsanaTag {
+    gid: String,
+    name: String,
+    color: Option<String>,
+    notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AsanaStory {
+    gid: String,
+    text: Option<String>,
+    created_by: Option<AsanaRef>,
+    created_at: Option<DateTime<Utc>>,
+    resource_subtype: Option<String>,
+    target: Option<AsanaRef>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AsanaUser {
+    gid: String,
+    name: Option<String>,
+    email: Option<String>,
+    photo: Option<AsanaPhoto>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AsanaPhoto {
+    image_60x60: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_issue_uses_section_for_status() {
+        let task: AsanaTask = serde_json::from_value(json!({
+            "gid": "t1",
+            "name": "Write docs",
+            "notes": "",
+            "completed": false,
+            "created_at": "2024-02-01T09:00:00.000Z",
+            "due_on": "2024-02-10",
+            "assignee": { "gid": "u1" },
+            "tags": [{ "gid": "g1", "name": "docs" }],
+            "memberships": [
+                { "project": { "gid": "other" }, "section": { "gid": "s0", "name": "Inbox" } },
+                { "project": { "gid": "p1" }, "section": { "gid": "s1", "name": "In Progress" } }
+            ]
+        }))
+        .unwrap();
+
+        let issue = to_issue(task, Some("p1"));
+        assert_eq!(issue.status, "In Progress");
+        assert_eq!(issue.project_id, "p1");
+        assert_eq!(issue.description, None);
+        assert_eq!(issue.labels, vec!["docs"]);
+        assert_eq!(issue.due_date, NaiveDate::from_ymd_opt(2024, 2, 10));
+
+        let filter = IssueFilter {
+            labels: vec!["DOCS".into()],
+            status: Some("in progress".into()),
+            ..Default::default()
+        };
+        assert!(matches_filter(&issue, &filter));
+        assert!(!matches_filter(&issue, &IssueFilter { assignee_id: Some("u2".into()), ..Default::default() }));
+        assert_eq!(state_type_from_name("In Progress"), crate::StateType::Started);
+    }
+}
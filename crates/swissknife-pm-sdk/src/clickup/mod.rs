@@ -0,0 +1,855 @@
+use crate::{
+    state_type_from_name, Comment, CreateIssue, Error, Issue, IssueFilter, IssueType, Label, ListOptions, ListResult,
+    Priority, Project, ProjectManagementProvider, ProjectStatus, Result, Sprint, SprintProvider, StateType,
+    UpdateIssue, User, WorkflowState,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use reqwest::{Method, RequestBuilder};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer};
+use serde_json::{json, Map, Value};
+use std::collections::{HashMap, HashSet};
+
+const API_BASE: &str = "https://api.clickup.com/api/v2";
+// ClickUp pages are a fixed 100 tasks.
+const TASK_PAGE_SIZE: u32 = 100;
+
+#[derive(Clone)]
+pub struct ClickUpClient {
+    api_token: String,
+    team_id: Option<String>,
+    space_id: Option<String>,
+    http: reqwest::Client,
+}
+
+impl ClickUpClient {
+    pub fn new(api_token: impl Into<String>) -> Self {
+        Self {
+            api_token: api_token.into(),
+            team_id: None,
+            space_id: None,
+            http: reqwest::Client::new(),
+        }
+    }
+
+    pub fn with_team(mut self, team_id: impl Into<String>) -> Self {
+        self.team_id = Some(team_id.into());
+        self
+    }
+
+    pub fn with_space(mut self, space_id: impl Into<String>) -> Self {
+        self.space_id = Some(space_id.into());
+        self
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        self.http
+            .request(method, format!("{}{}", API_BASE, path))
+            .header("Authorization", &self.api_token)
+    }
+
+    async fn send<T: DeserializeOwned>(&self, method: Method, path: &str, query: &[(&str, String)], body: Option<Value>) -> Result<T> {
+        let mut request = self.request(method, path).query(query);
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+
+        let response = request.send().await?;
+        let status = response.status().as_u16();
+        let text = response.text().await?;
+
+        if !(200..300).contains(&status) {
+            let error = serde_json::from_str::<ClickUpErrorResponse>(&text).ok();
+            let code = error.as_ref().and_then(|e| e.ecode.clone());
+            let message = error.and_then(|e| e.err).unwrap_or(text);
+            return Err(match status {
+                400 => Error::Validation(message),
+                401 | 403 => Error::Auth(message),
+                404 => Error::NotFound(message),
+                429 => Error::RateLimited,
+                _ => Error::Api {
+                    message,
+                    code: code.or(Some(status.to_string())),
+                },
+            });
+        }
+
+        if text.trim().is_empty() {
+            return Ok(serde_json::from_value(Value::Null)?);
+        }
+        Ok(serde_json::from_str(&text)?)
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str, query: &[(&str, String)]) -> Result<T> {
+        self.send(Method::GET, path, query, None).await
+    }
+
+    async fn team(&self) -> Result<String> {
+        if let Some(team) = &self.team_id {
+            return Ok(team.clone());
+        }
+        let teams: ClickUpTeams = self.get("/team", &[]).await?;
+        teams
+            .teams
+            .into_iter()
+            .next()
+            .map(|t| t.id)
+            .ok_or_else(|| Error::NotFound("No ClickUp workspace available".to_string()))
+    }
+
+    async fn space(&self) -> Result<String> {
+        if let Some(space) = &self.space_id {
+            return Ok(space.clone());
+        }
+        let team = self.team().await?;
+        let spaces: ClickUpSpaces = self.get(&format!("/team/{}/space", team), &[("archived", "false".to_string())]).await?;
+        spaces
+            .spaces
+            .into_iter()
+            .next()
+            .map(|s| s.id)
+            .ok_or_else(|| Error::NotFound("No ClickUp space available".to_string()))
+    }
+
+    async fn fetch_list(&self, list_id: &str) -> Result<ClickUpList> {
+        self.get(&format!("/list/{}", list_id), &[]).await
+    }
+
+    async fn fetch_task(&self, id: &str) -> Result<ClickUpTask> {
+        self.get(&format!("/task/{}", id), &[("include_markdown_description", "true".to_string())])
+            .await
+    }
+
+    async fn task_page(&self, path: &str, mut query: Vec<(&str, String)>, options: &ListOptions) -> Result<(Vec<ClickUpTask>, u32, bool)> {
+        let page: u32 = options
+            .cursor
+            .as_deref()
+            .and_then(|c| c.parse().ok())
+            .or(options.page)
+            .unwrap_or(0);
+        query.push(("page", page.to_string()));
+        query.push(("subtasks", "true".to_string()));
+        query.push(("include_closed", "true".to_string()));
+        query.push(("include_markdown_description", "true".to_string()));
+        let response: ClickUpTasks = self.get(path, &query).await?;
+        let has_more = !response.last_page.unwrap_or(response.tasks.is_empty());
+        Ok((response.tasks, page, has_more))
+    }
+}
+
+#[async_trait]
+impl ProjectManagementProvider for ClickUpClient {
+    async fn list_projects(&self, _options: &ListOptions) -> Result<ListResult<Project>> {
+        let team = self.team().await?;
+        let spaces: ClickUpSpaces = self.get(&format!("/team/{}/space", team), &[("archived", "false".to_string())]).await?;
+
+        let mut projects = Vec::new();
+        for space in spaces.spaces {
+            let folders: ClickUpFolders = self
+                .get(&format!("/space/{}/folder", space.id), &[("archived", "false".to_string())])
+                .await?;
+            for folder in folders.folders {
+                projects.extend(folder.lists.into_iter().map(|list| to_project(list, &space, Some(&folder.name))));
+            }
+            let lists: ClickUpLists = self
+                .get(&format!("/space/{}/list", space.id), &[("archived", "false".to_string())])
+                .await?;
+            projects.extend(lists.lists.into_iter().map(|list| to_project(list, &space, None)));
+        }
+
+        Ok(ListResult {
+            total: Some(projects.len() as u64),
+            items: projects,
+            has_more: false,
+            next_cursor: None,
+        })
+    }
+
+    async fn get_project(&self, id: &str) -> Result<Project> {
+        let list = self.fetch_list(id).await?;
+        let space = list.space.clone().unwrap_or_default();
+        let folder = list.folder.as_ref().filter(|f| !f.hidden).map(|f| f.name.clone());
+        Ok(to_project(list, &space, folder.as_deref()))
+    }
+
+    async fn create_project(&self, name: &str, description: Option<&str>) -> Result<Project> {
+        let space = self.space().await?;
+        let body = json!({ "name": name, "markdown_content": description });
+        let list: ClickUpList = self.send(Method::POST, &format!("/space/{}/list", space), &[], Some(body)).await?;
+        self.get_project(&list.id).await
+    }
+
+    async fn update_project(&self, id: &str, name: Option<&str>, description: Option<&str>) -> Result<Project> {
+        let mut body = Map::new();
+        if let Some(name) = name {
+            body.insert("name".to_string(), json!(name));
+        }
+        if let Some(description) = description {
+            body.insert("markdown_content".to_string(), json!(description));
+        }
+        self.send::<Value>(Method::PUT, &format!("/list/{}", id), &[], Some(Value::Object(body)))
+            .await?;
+        self.get_project(id).await
+    }
+
+    async fn archive_project(&self, _id: &str) -> Result<()> {
+        Err(Error::Unsupported("ClickUp lists cannot be archived through the API".to_string()))
+    }
+
+    async fn list_issues(&self, project_id: &str, filter: &IssueFilter, options: &ListOptions) -> Result<ListResult<Issue>> {
+        let mut query = Vec::new();
+        if let Some(assignee) = &filter.assignee_id {
+            query.push(("assignees[]", assignee.clone()));
+        }
+        if let Some(status) = &filter.status {
+            query.push(("statuses[]", status.clone()));
+        }
+        for label in &filter.labels {
+            query.push(("tags[]", label.clone()));
+        }
+
+        let (tasks, page, has_more) = self.task_page(&format!("/list/{}/task", project_id), query, options).await?;
+        Ok(ListResult {
+            items: tasks
+                .into_iter()
+                .map(to_issue)
+                .filter(|issue| matches_filter(issue, filter))
+                .collect(),
+            total: None,
+            has_more,
+            next_cursor: has_more.then(|| (page + 1).to_string()),
+        })
+    }
+
+    async fn get_issue(&self, id: &str) -> Result<Issue> {
+        Ok(to_issue(self.fetch_task(id).await?))
+    }
+
+    async fn create_issue(&self, project_id: &str, issue: &CreateIssue) -> Result<Issue> {
+        let mut body = Map::new();
+        body.insert("name".to_string(), json!(issue.title));
+        if let Some(description) = &issue.description {
+            body.insert("markdown_description".to_string(), json!(description));
+        }
+        if let Some(assignee) = &issue.assignee_id {
+            body.insert("assignees".to_string(), json!([user_id(assignee)?]));
+        }
+        if !issue.labels.is_empty() {
+            body.insert("tags".to_string(), json!(issue.labels));
+        }
+        if let Some(parent) = &issue.parent_id {
+            body.insert("parent".to_string(), json!(parent));
+        }
+        insert_common_fields(&mut body, issue.priority, issue.estimate, issue.due_date);
+
+        let task: ClickUpTask = self
+            .send(Method::POST, &format!("/list/{}/task", project_id), &[], Some(Value::Object(body)))
+            .await?;
+        self.get_issue(&task.id).await
+    }
+
+    async fn update_issue(&self, id: &str, update: &UpdateIssue) -> Result<Issue> {
+        let current = if update.assignee_id.is_some() || update.labels.is_some() {
+            Some(self.fetch_task(id).await?)
+        } else {
+            None
+        };
+
+        let mut body = Map::new();
+        if let Some(title) = &update.title {
+            body.insert("name".to_string(), json!(title));
+        }
+        if let Some(description) = &update.description {
+            body.insert("markdown_description".to_string(), json!(description));
+        }
+        if let Some(status) = &update.status {
+            body.insert("status".to_string(), json!(status));
+        }
+        if let (Some(assignee), Some(task)) = (&update.assignee_id, &current) {
+            let new_id = user_id(assignee)?;
+            let remove: Vec<u64> = task.assignees.iter().map(|a| a.id).filter(|id| *id != new_id).collect();
+            body.insert("assignees".to_string(), json!({ "add": [new_id], "rem": remove }));
+        }
+        insert_common_fields(&mut body, update.priority, update.estimate, update.due_date);
+
+        if !body.is_empty() {
+            self.send::<Value>(Method::PUT, &format!("/task/{}", id), &[], Some(Value::Object(body)))
+                .await?;
+        }
+
+        if let (Some(labels), Some(task)) = (&update.labels, &current) {
+            let existing: HashSet<String> = task.tags.iter().map(|t| t.name.to_lowercase()).collect();
+            let wanted: HashSet<String> = labels.iter().map(|l| l.to_lowercase()).collect();
+            for label in wanted.difference(&existing) {
+                self.send::<Value>(Method::POST, &format!("/task/{}/tag/{}", id, label), &[], None).await?;
+            }
+            for label in existing.difference(&wanted) {
+                self.send::<Value>(Method::DELETE, &format!("/task/{}/tag/{}", id, label), &[], None).await?;
+            }
+        }
+
+        self.get_issue(id).await
+    }
+
+    async fn delete_issue(&self, id: &str) -> Result<()> {
+        self.send::<Value>(Method::DELETE, &format!("/task/{}", id), &[], None).await?;
+        Ok(())
+    }
+
+    async fn search_issues(&self, query: &str, options: &ListOptions) -> Result<ListResult<Issue>> {
+        let team = self.team().await?;
+        let filter = IssueFilter {
+            search: Some(query.to_string()),
+            ..Default::default()
+        };
+
+        // The team task endpoint has no text search, so keep reading pages until
+        // a page's worth of tasks match or the workspace runs out.
+        let path = format!("/team/{}/task", team);
+        let wanted = options.per_page.unwrap_or(TASK_PAGE_SIZE) as usize;
+        let mut options = options.clone();
+        let mut items = Vec::new();
+        loop {
+            let (tasks, page, has_more) = self.task_page(&path, Vec::new(), &options).await?;
+            items.extend(tasks.into_iter().map(to_issue).filter(|issue| matches_filter(issue, &filter)));
+            let next_cursor = has_more.then(|| (page + 1).to_string());
+            if !has_more || items.len() >= wanted {
+                return Ok(ListResult {
+                    items,
+                    total: None,
+                    has_more,
+                    next_cursor,
+                });
+            }
+            options.cursor = next_cursor;
+        }
+    }
+
+    async fn list_labels(&self, project_id: &str) -> Result<Vec<Label>> {
+        let list = self.fetch_list(project_id).await?;
+        let space = list.space.map(|s| s.id).unwrap_or_default();
+        let tags: ClickUpTags = self.get(&format!("/space/{}/tag", space), &[]).await?;
+        Ok(tags.tags.into_iter().map(to_label).collect())
+    }
+
+    async fn create_label(&self, project_id: &str, name: &str, color: Option<&str>) -> Result<Label> {
+        let list = self.fetch_list(project_id).await?;
+        let space = list.space.map(|s| s.id).unwrap_or_default();
+        let tag = json!({ "name": name, "tag_bg": color, "tag_fg": "#ffffff" });
+        self.send::<Value>(Method::POST, &format!("/space/{}/tag", space), &[], Some(json!({ "tag": tag })))
+            .await?;
+        Ok(Label {
+            id: name.to_string(),
+            name: name.to_string(),
+            color: color.map(str::to_string),
+            description: None,
+        })
+    }
+
+    async fn list_workflow_states(&self, project_id: &str) -> Result<Vec<WorkflowState>> {
+        let list = self.fetch_list(project_id).await?;
+        let mut statuses = list.statuses;
+        statuses.sort_by_key(|s| s.orderindex);
+        Ok(statuses.into_iter().map(to_workflow_state).collect())
+    }
+
+    async fn list_comments(&self, issue_id: &str) -> Result<Vec<Comment>> {
+        let comments: ClickUpComments = self.get(&format!("/task/{}/comment", issue_id), &[]).await?;
+        let mut comments: Vec<Comment> = comments.comments.into_iter().map(|c| to_comment(c, issue_id)).collect();
+        comments.sort_by_key(|c| c.created_at);
+        Ok(comments)
+    }
+
+    async fn create_comment(&self, issue_id: &str, body: &str) -> Result<Comment> {
+        let created: ClickUpCreatedComment = self
+            .send(
+                Method::POST,
+                &format!("/task/{}/comment", issue_id),
+                &[],
+                Some(json!({ "comment_text": body, "notify_all": false })),
+            )
+            .await?;
+        let author = self.get_current_user().await?;
+        Ok(Comment {
+            id: created.id,
+            body: body.to_string(),
+            author_id: author.id,
+            issue_id: issue_id.to_string(),
+            created_at: created.date.unwrap_or_else(Utc::now),
+            updated_at: None,
+        })
+    }
+
+    async fn update_comment(&self, comment_id: &str, body: &str) -> Result<Comment> {
+        self.send::<Value>(
+            Method::PUT,
+            &format!("/comment/{}", comment_id),
+            &[],
+            Some(json!({ "comment_text": body })),
+        )
+        .await?;
+        let author = self.get_current_user().await?;
+        Ok(Comment {
+            id: comment_id.to_string(),
+            body: body.to_string(),
+            author_id: author.id,
+            issue_id: String::new(),
+            created_at: Utc::now(),
+            updated_at: Some(Utc::now()),
+        })
+    }
+
+    async fn delete_comment(&self, comment_id: &str) -> Result<()> {
+        self.send::<Value>(Method::DELETE, &format!("/comment/{}", comment_id), &[], None).await?;
+        Ok(())
+    }
+
+    async fn list_users(&self) -> Result<Vec<User>> {
+        let team = self.team().await?;
+        let teams: ClickUpTeams = self.get("/team", &[]).await?;
+        Ok(teams
+            .teams
+            .into_iter()
+            .filter(|t| t.id == team)
+            .flat_map(|t| t.members)
+            .map(|m| to_user(m.user))
+            .collect())
+    }
+
+    async fn get_current_user(&self) -> Result<User> {
+        let response: ClickUpUserResponse = self.get("/user", &[]).await?;
+        Ok(to_user(response.user))
+    }
+}
+
+#[async_trait]
+impl SprintProvider for ClickUpClient {
+    async fn list_sprints(&self, _project_id: &str) -> Result<Vec<Sprint>> {
+        Err(sprints_unsupported())
+    }
+
+    async fn get_active_sprint(&self, _project_id: &str) -> Result<Option<Sprint>> {
+        Err(sprints_unsupported())
+    }
+
+    async fn create_sprint(&self, _project_id: &str, _name: &str, _goal: Option<&str>, _start: Option<NaiveDate>, _end: Option<NaiveDate>) -> Result<Sprint> {
+        Err(sprints_unsupported())
+    }
+
+    async fn start_sprint(&self, _sprint_id: &str) -> Result<Sprint> {
+        Err(sprints_unsupported())
+    }
+
+    async fn complete_sprint(&self, _sprint_id: &str) -> Result<Sprint> {
+        Err(sprints_unsupported())
+    }
+}
+
+fn sprints_unsupported() -> Error {
+    Error::Unsupported("ClickUp Sprints are not exposed by the public API; use lists instead".to_string())
+}
+
+pub fn priority_from_value(value: i32) -> Priority {
+    match value {
+        1 => Priority::Urgent,
+        2 => Priority::High,
+        3 => Priority::Medium,
+        4 => Priority::Low,
+        _ => Priority::None,
+    }
+}
+
+fn priority_value(priority: Priority) -> Option<i32> {
+    match priority {
+        Priority::Urgent => Some(1),
+        Priority::High => Some(2),
+        Priority::Medium => Some(3),
+        Priority::Low => Some(4),
+        Priority::None => None,
+    }
+}
+
+fn user_id(value: &str) -> Result<u64> {
+    value
+        .parse()
+        .map_err(|_| Error::Validation(format!("Invalid ClickUp user id: {}", value)))
+}
+
+fn insert_common_fields(body: &mut Map<String, Value>, priority: Option<Priority>, estimate: Option<f64>, due_date: Option<NaiveDate>) {
+    if let Some(priority) = priority {
+        body.insert("priority".to_string(), json!(priority_value(priority)));
+    }
+    if let Some(estimate) = estimate {
+        body.insert("points".to_string(), json!(estimate));
+    }
+    if let Some(due) = due_date {
+        let millis = due.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc().timestamp_millis();
+        body.insert("due_date".to_string(), json!(millis));
+        body.insert("due_date_time".to_string(), json!(false));
+    }
+}
+
+fn matches_filter(issue: &Issue, filter: &IssueFilter) -> bool {
+    filter.priority.is_none_or(|p| issue.priority.unwrap_or(Priority::None) == p)
+        && filter.issue_type.is_none_or(|t| issue.issue_type == t)
+        && filter.search.as_ref().is_none_or(|query| {
+            let query = query.to_lowercase();
+            issue.title.to_lowercase().contains(&query)
+                || issue.description.as_ref().is_some_and(|d| d.to_lowercase().contains(&query))
+        })
+}
+
+fn status_type(status: &ClickUpStatus) -> StateType {
+    match status.status_type.as_str() {
+        "closed" | "done" => match state_type_from_name(&status.status) {
+            StateType::Canceled => StateType::Canceled,
+            _ => StateType::Completed,
+        },
+        "open" => match state_type_from_name(&status.status) {
+            StateType::Backlog => StateType::Backlog,
+            _ => StateType::Unstarted,
+        },
+        _ => match state_type_from_name(&status.status) {
+            StateType::Unstarted => StateType::Started,
+            other => other,
+        },
+    }
+}
+
+fn to_workflow_state(status: ClickUpStatus) -> WorkflowState {
+    WorkflowState {
+        id: status.id.clone().unwrap_or_else(|| status.status.clone()),
+        state_type: status_type(&status),
+        name: status.status,
+        color: status.color,
+        position: status.orderindex.map(|p| p as i32),
+    }
+}
+
+fn to_project(list: ClickUpList, space: &ClickUpRef, folder: Option<&str>) -> Project {
+    let mut extra = HashMap::new();
+    extra.insert("space_id".to_string(), json!(space.id));
+    if let Some(name) = &space.name {
+        extra.insert("space".to_string(), json!(name));
+    }
+    if let Some(folder) = folder {
+        extra.insert("folder".to_string(), json!(folder));
+    }
+    let name = match folder {
+        Some(folder) => format!("{} / {}", folder, list.name),
+        None => list.name,
+    };
+    Project {
+        id: list.id,
+        name,
+        key: None,
+        description: list.content.filter(|c| !c.is_empty()),
+        url: None,
+        lead_id: None,
+        status: Some(if list.archived { ProjectStatus::Archived } else { ProjectStatus::Active }),
+        created_at: None,
+        updated_at: None,
+        extra,
+    }
+}
+
+fn to_issue(task: ClickUpTask) -> Issue {
+    let mut extra = HashMap::new();
+    extra.insert("state_type".to_string(), json!(status_type(&task.status)));
+    if let Some(url) = &task.url {
+        extra.insert("url".to_string(), json!(url));
+    }
+
+    Issue {
+        key: task.custom_id,
+        title: task.name,
+        description: task
+            .markdown_description
+            .or(task.text_content)
+            .filter(|d| !d.is_empty()),
+        issue_type: if task.parent.is_some() { IssueType::Subtask } else { IssueType::Task },
+        status: task.status.status,
+        priority: task.priority.and_then(|p| p.id.parse().ok()).map(priority_from_value),
+        assignee_id: task.assignees.first().map(|a| a.id.to_string()),
+        reporter_id: task.creator.map(|c| c.id.to_string()),
+        project_id: task.list.map(|l| l.id).unwrap_or_default(),
+        parent_id: task.parent,
+        labels: task.tags.into_iter().map(|t| t.name).collect(),
+        estimate: task.points,
+        due_date: task.due_date.map(|d| d.date_naive()),
+        created_at: task.date_created.unwrap_or_default(),
+        updated_at: task.date_updated,
+        completed_at: task.date_closed,
+        extra,
+        id: task.id,
+    }
+}
+
+fn to_label(tag: ClickUpTag) -> Label {
+    Label {
+        id: tag.name.clone(),
+        name: tag.name,
+        color: tag.tag_bg,
+        description: None,
+    }
+}
+
+fn to_comment(comment: ClickUpComment, issue_id: &str) -> Comment {
+    Comment {
+        id: comment.id,
+        body: comment.comment_text,
+        author_id: comment.user.map(|u| u.id.to_string()).unwrap_or_default(),
+        issue_id: issue_id.to_string(),
+        created_at: comment.date.unwrap_or_default(),
+        updated_at: None,
+    }
+}
+
+fn to_user(user: ClickUpUser) -> User {
+    User {
+        id: user.id.to_string(),
+        name: user.username.unwrap_or_default(),
+        email: user.email,
+        avatar_url: user.profile_picture,
+    }
+}
+
+fn millis<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Option<DateTime<Utc>>, D::Error> {
+    let value = Option::<Value>::deserialize(deserializer)?;
+    let millis = match value {
+        Some(Value::String(s)) => s.parse::<i64>().ok(),
+        Some(Value::Number(n)) => n.as_i64(),
+        _ => None,
+    };
+    Ok(millis.and_then(DateTime::from_timestamp_millis))
+}
+
+#[derive(Debug, Deserialize)]
+struct ClickUpErrorResponse {
+    err: Option<String>,
+    #[serde(rename = "ECODE")]
+    ecode: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct ClickUpRef {
+    id: String,
+    name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ClickUpTeams {
+    teams: Vec<ClickUpTeam>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ClickUpTeam {
+    id: String,
+    #[serde(default)]
+    members: Vec<ClickUpMember>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ClickUpMember {
+    user: ClickUpUser,
+}
+
+#[derive(Debug, Deserialize)]
+struct ClickUpUserResponse {
+    user: ClickUpUser,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ClickUpUser {
+    id: u64,
+    username: Option<String>,
+    email: Option<String>,
+    profile_picture: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ClickUpSpaces {
+    spaces: Vec<ClickUpRef>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ClickUpFolders {
+    folders: Vec<ClickUpFolder>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ClickUpFolder {
+    name: String,
+    #[serde(default)]
+    hidden: bool,
+    #[serde(default)]
+    lists: Vec<ClickUpList>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ClickUpLists {
+    lists: Vec<ClickUpList>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ClickUpList {
+    id: String,
+    name: String,
+    content: Option<String>,
+    #[serde(default)]
+    archived: bool,
+    space: Option<ClickUpRef>,
+    folder: Option<ClickUpFolder>,
+    #[serde(default)]
+    statuses: Vec<ClickUpStatus>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ClickUpStatus {
+    id: Option<String>,
+    status: String,
+    #[serde(rename = "type", default)]
+    status_type: String,
+    color: Option<String>,
+    orderindex: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ClickUpTasks {
+    #[serde(default)]
+    tasks: Vec<ClickUpTask>,
+    last_page: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ClickUpTask {
+    id: String,
+    custom_id: Option<String>,
+    name: String,
+    text_content: Option<String>,
+    markdown_description: Option<String>,
+    status: ClickUpStatus,
+    priority: Option<ClickUpPriority>,
+    #[serde(default)]
+    assignees: Vec<ClickUpAssignee>,
+    creator: Option<ClickUpAssignee>,
+    list: Option<ClickUpRef>,
+    parent: Option<String>,
+    #[serde(default)]
+    tags: Vec<ClickUpTag>,
+    points: Option<f64>,
+    #[serde(default, deserialize_with = "millis")]
+    due_date: Option<DateTime<Utc>>,
+    #[serde(default, deserialize_with = "millis")]
+    date_created: Option<DateTime<Utc>>,
+    #[serde(default, deserialize_with = "millis")]
+    date_updated: Option<DateTime<Utc>>,
+    #[serde(default, deserialize_with = "millis")]
+    date_closed: Option<DateTime<Utc>>,
+    url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ClickUpPriority {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ClickUpAssignee {
+    id: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct ClickUpTags {
+    tags: Vec<ClickUpTag>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ClickUpTag {
+    name: String,
+    tag_bg: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ClickUpComments {
+    comments: Vec<ClickUpComment>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ClickUpComment {
+    id: String,
+    #[serde(default)]
+    comment_text: String,
+    user: Option<ClickUpAssignee>,
+    #[serde(default, deserialize_with = "millis")]
+    date: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ClickUpCreatedComment {
+    #[serde(deserialize_with = "string_or_number")]
+    id: String,
+    #[serde(default, deserialize_with = "millis")]
+    date: Option<DateTime<Utc>>,
+}
+
+fn string_or_number<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<String, D::Error> {
+    match Value::deserialize(deserializer)? {
+        Value::String(s) => Ok(s),
+        Value::Number(n) => Ok(n.to_string()),
+        other => Err(serde::de::Error::custom(format!("expected string or number, got {}", other))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_custom_statuses_map_to_state_types() {
+        let status = |name: &str, kind: &str| ClickUpStatus {
+            id: None,
+            status: name.to_string(),
+            status_type: kind.to_string(),
+            color: None,
+            orderindex: None,
+        };
+        assert_eq!(status_type(&status("to do", "open")), StateType::Unstarted);
+        assert_eq!(status_type(&status("backlog", "open")), StateType::Backlog);
+        assert_eq!(status_type(&status("qa", "custom")), StateType::Started);
+        assert_eq!(status_type(&status("won't fix", "closed")), StateType::Canceled);
+        assert_eq!(status_type(&status("complete", "closed")), StateType::Completed);
+    }
+
+    #[test]
+    fn test_task_maps_to_issue() {
+        let task: ClickUpTask = serde_json::from_value(json!({
+            "id": "abc",
+            "custom_id": "DEV-9",
+            "name": "Add search",
+            "markdown_description": "**Spec**",
+            "status": { "status": "in review", "type": "custom" },
+            "priority": { "id": "2", "priority": "high" },
+            "assignees": [{ "id": 183 }],
+            "list": { "id": "901" },
+            "parent": "root",
+            "tags": [{ "name": "api" }],
+            "due_date": "1709640000000",
+            "date_created": "1704067200000"
+        }))
+        .unwrap();
+
+        let issue = to_issue(task);
+        assert_eq!(issue.key.as_deref(), Some("DEV-9"));
+        assert_eq!(issue.description.as_deref(), Some("**Spec**"));
+        assert_eq!(issue.issue_type, IssueType::Subtask);
+        assert_eq!(issue.priority, Some(Priority::High));
+        assert_eq!(issue.assignee_id.as_deref(), Some("183"));
+        assert_eq!(issue.project_id, "901");
+        assert_eq!(issue.due_date, NaiveDate::from_ymd_opt(2024, 3, 5));
+        assert_eq!(issue.created_at.to_rfc3339(), "2024-01-01T00:00:00+00:00");
+        assert_eq!(issue.extra["state_type"], "started");
+    }
+}
@@ -20,6 +20,9 @@ pub enum Error {
     #[error("Validation error: {0}")]
     Validation(String),
 
+    #[error("Unsupported: {0}")]
+    Unsupported(String),
+
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
 }
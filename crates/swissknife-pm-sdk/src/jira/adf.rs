@@ -0,0 +1,435 @@
+use serde_json::{json, Map, Value};
+
+pub fn markdown_to_adf(markdown: &str) -> Value {
+    let lines: Vec<&str> = markdown.lines().collect();
+    let mut content = Vec::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i];
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() {
+            flush_paragraph(&mut paragraph, &mut content);
+            i += 1;
+            continue;
+        }
+
+        if let Some(language) = trimmed.strip_prefix("```") {
+            flush_paragraph(&mut paragraph, &mut content);
+            let mut code = Vec::new();
+            i += 1;
+            while i < lines.len() && !lines[i].trim_start().starts_with("```") {
+                code.push(lines[i]);
+                i += 1;
+            }
+            i += 1;
+            let mut block = json!({ "type": "codeBlock" });
+            if !language.trim().is_empty() {
+                block["attrs"] = json!({ "language": language.trim() });
+            }
+            if !code.is_empty() {
+                block["content"] = json!([{ "type": "text", "text": code.join("\n") }]);
+            }
+            content.push(block);
+            continue;
+        }
+
+        if let Some((level, title)) = heading(trimmed) {
+            flush_paragraph(&mut paragraph, &mut content);
+            content.push(json!({
+                "type": "heading",
+                "attrs": { "level": level },
+                "content": inline_to_adf(title),
+            }));
+            i += 1;
+            continue;
+        }
+
+        if is_rule(trimmed) {
+            flush_paragraph(&mut paragraph, &mut content);
+            content.push(json!({ "type": "rule" }));
+            i += 1;
+            continue;
+        }
+
+        if trimmed.starts_with('>') {
+            flush_paragraph(&mut paragraph, &mut content);
+            let mut quoted = Vec::new();
+            while i < lines.len() && lines[i].trim().starts_with('>') {
+                let inner = lines[i].trim().trim_start_matches('>');
+                quoted.push(inner.strip_prefix(' ').unwrap_or(inner));
+                i += 1;
+            }
+            let inner = markdown_to_adf(&quoted.join("\n"));
+            content.push(json!({ "type": "blockquote", "content": inner["content"] }));
+            continue;
+        }
+
+        if let Some((ordered, _)) = list_item(trimmed) {
+            flush_paragraph(&mut paragraph, &mut content);
+            let mut items = Vec::new();
+            while i < lines.len() {
+                match list_item(lines[i].trim()) {
+                    Some((item_ordered, text)) if item_ordered == ordered => {
+                        items.push(json!({
+                            "type": "listItem",
+                            "content": [{ "type": "paragraph", "content": inline_to_adf(text) }],
+                        }));
+                        i += 1;
+                    }
+                    _ => break,
+                }
+            }
+            let list_type = if ordered { "orderedList" } else { "bulletList" };
+            content.push(json!({ "type": list_type, "content": items }));
+            continue;
+        }
+
+        paragraph.push(trimmed);
+        i += 1;
+    }
+    flush_paragraph(&mut paragraph, &mut content);
+
+    json!({ "type": "doc", "version": 1, "content": content })
+}
+
+pub fn adf_to_markdown(document: &Value) -> String {
+    let mut out = String::new();
+    write_blocks(document.get("content"), "", &mut out);
+    out.trim_end().to_string()
+}
+
+fn flush_paragraph(paragraph: &mut Vec<&str>, content: &mut Vec<Value>) {
+    if paragraph.is_empty() {
+        return;
+    }
+    let mut inline = Vec::new();
+    for (index, line) in paragraph.drain(..).enumerate() {
+        if index > 0 {
+            inline.push(json!({ "type": "hardBreak" }));
+        }
+        inline.extend(inline_to_adf(line));
+    }
+    content.push(json!({ "type": "paragraph", "content": inline }));
+}
+
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    line[level..].strip_prefix(' ').map(|title| (level, title.trim()))
+}
+
+fn is_rule(line: &str) -> bool {
+    line.len() >= 3 && ['-', '*', '_'].iter().any(|c| line.chars().all(|ch| ch == *c))
+}
+
+fn list_item(line: &str) -> Option<(bool, &str)> {
+    if let Some(text) = line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+        return Some((false, text));
+    }
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits > 0 {
+        if let Some(text) = line[digits..].strip_prefix(". ") {
+            return Some((true, text));
+        }
+    }
+    None
+}
+
+fn inline_to_adf(text: &str) -> Vec<Value> {
+    let mut nodes = Vec::new();
+    parse_inline(text, &[], &mut nodes);
+    nodes
+}
+
+fn parse_inline(text: &str, marks: &[Value], nodes: &mut Vec<Value>) {
+    let mut plain = String::new();
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        let parsed = match c {
+            '`' => delimited(rest, "`").map(|(inner, after)| {
+                let mut code_marks = marks.to_vec();
+                code_marks.push(json!({ "type": "code" }));
+                (vec![text_node(inner, &code_marks)], after)
+            }),
+            '*' | '_' => {
+                let strong = if c == '*' { "**" } else { "__" };
+                let em = if c == '*' { "*" } else { "_" };
+                delimited(rest, strong)
+                    .map(|(inner, after)| (nested(inner, marks, json!({ "type": "strong" })), after))
+                    .or_else(|| {
+                        delimited(rest, em).map(|(inner, after)| (nested(inner, marks, json!({ "type": "em" })), after))
+                    })
+            }
+            '~' => delimited(rest, "~~")
+                .map(|(inner, after)| (nested(inner, marks, json!({ "type": "strike" })), after)),
+            '[' => link(rest).map(|(label, href, after)| {
+                (nested(label, marks, json!({ "type": "link", "attrs": { "href": href } })), after)
+            }),
+            _ => None,
+        };
+
+        match parsed {
+            Some((parsed_nodes, after)) => {
+                if !plain.is_empty() {
+                    nodes.push(text_node(&std::mem::take(&mut plain), marks));
+                }
+                nodes.extend(parsed_nodes);
+                rest = after;
+            }
+            None => {
+                plain.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    if !plain.is_empty() {
+        nodes.push(text_node(&plain, marks));
+    }
+}
+
+fn nested(inner: &str, marks: &[Value], mark: Value) -> Vec<Value> {
+    let mut marks = marks.to_vec();
+    marks.push(mark);
+    let mut nodes = Vec::new();
+    parse_inline(inner, &marks, &mut nodes);
+    nodes
+}
+
+fn delimited<'a>(text: &'a str, delimiter: &str) -> Option<(&'a str, &'a str)> {
+    let body = text.strip_prefix(delimiter)?;
+    let end = body.find(delimiter)?;
+    if end == 0 {
+        return None;
+    }
+    Some((&body[..end], &body[end + delimiter.len()..]))
+}
+
+fn link(text: &str) -> Option<(&str, &str, &str)> {
+    let close = text.find("](")?;
+    let label = &text[1..close];
+    let after_label = &text[close + 2..];
+    let end = after_label.find(')')?;
+    Some((label, &after_label[..end], &after_label[end + 1..]))
+}
+
+fn text_node(text: &str, marks: &[Value]) -> Value {
+    let mut node = json!({ "type": "text", "text": text });
+    if !marks.is_empty() {
+        node["marks"] = json!(marks);
+    }
+    node
+}
+
+fn write_blocks(blocks: Option<&Value>, indent: &str, out: &mut String) {
+    for block in blocks.and_then(Value::as_array).into_iter().flatten() {
+        write_block(block, indent, out);
+    }
+}
+
+fn write_block(block: &Value, indent: &str, out: &mut String) {
+    let attrs = block.get("attrs");
+    match node_type(block) {
+        "paragraph" => {
+            out.push_str(indent);
+            out.push_str(&inline_to_markdown(block.get("content"), indent));
+            out.push_str("\n\n");
+        }
+        "heading" => {
+            let level = attrs.and_then(|a| a.get("level")).and_then(Value::as_u64).unwrap_or(1) as usize;
+            out.push_str(&format!("{}{} {}\n\n", indent, "#".repeat(level), inline_to_markdown(block.get("content"), indent)));
+        }
+        "codeBlock" => {
+            let language = attrs.and_then(|a| a.get("language")).and_then(Value::as_str).unwrap_or("");
+            out.push_str(&format!("{}```{}\n", indent, language));
+            for line in plain_text(block).lines() {
+                out.push_str(indent);
+                out.push_str(line);
+                out.push('\n');
+            }
+            out.push_str(&format!("{}```\n\n", indent));
+        }
+        "blockquote" => {
+            let mut inner = String::new();
+            write_blocks(block.get("content"), "", &mut inner);
+            for line in inner.trim_end().lines() {
+                out.push_str(format!("{}> {}", indent, line).trim_end());
+                out.push('\n');
+            }
+            out.push('\n');
+        }
+        "bulletList" | "orderedList" => {
+            let ordered = node_type(block) == "orderedList";
+            write_list(block, ordered, indent, out);
+            if indent.is_empty() {
+                out.push('\n');
+            }
+        }
+        "rule" => {
+            out.push_str(indent);
+            out.push_str("---\n\n");
+        }
+        "mediaSingle" | "mediaGroup" => {}
+        _ => write_blocks(block.get("content"), indent, out),
+    }
+}
+
+fn write_list(list: &Value, ordered: bool, indent: &str, out: &mut String) {
+    let items = list.get("content").and_then(Value::as_array).into_iter().flatten();
+    for (index, item) in items.enumerate() {
+        let marker = if ordered { format!("{}. ", index + 1) } else { "- ".to_string() };
+        let child_indent = format!("{}{}", indent, " ".repeat(marker.len()));
+        let mut first = true;
+        for child in item.get("content").and_then(Value::as_array).into_iter().flatten() {
+            match node_type(child) {
+                "paragraph" if first => {
+                    out.push_str(&format!("{}{}{}\n", indent, marker, inline_to_markdown(child.get("content"), &child_indent)));
+                }
+                "bulletList" | "orderedList" => {
+                    if first {
+                        out.push_str(&format!("{}{}\n", indent, marker.trim_end()));
+                    }
+                    write_list(child, node_type(child) == "orderedList", &child_indent, out);
+                }
+                _ => {
+                    if first {
+                        out.push_str(&format!("{}{}\n", indent, marker.trim_end()));
+                    }
+                    let mut inner = String::new();
+                    write_block(child, &child_indent, &mut inner);
+                    out.push_str(inner.trim_end_matches('\n'));
+                    out.push('\n');
+                }
+            }
+            first = false;
+        }
+    }
+}
+
+fn inline_to_markdown(nodes: Option<&Value>, indent: &str) -> String {
+    let mut out = String::new();
+    for node in nodes.and_then(Value::as_array).into_iter().flatten() {
+        let attrs = node.get("attrs");
+        let attr = |key: &str| attrs.and_then(|a| a.get(key)).and_then(Value::as_str).unwrap_or("");
+        match node_type(node) {
+            "text" => out.push_str(&apply_marks(node.get("text").and_then(Value::as_str).unwrap_or(""), node.get("marks"))),
+            "hardBreak" => {
+                out.push('\n');
+                out.push_str(indent);
+            }
+            "mention" => out.push_str(attr("text")),
+            "emoji" => out.push_str(if attr("text").is_empty() { attr("shortName") } else { attr("text") }),
+            "inlineCard" => out.push_str(attr("url")),
+            "date" => out.push_str(attr("timestamp")),
+            _ => out.push_str(&inline_to_markdown(node.get("content"), indent)),
+        }
+    }
+    out
+}
+
+fn apply_marks(text: &str, marks: Option<&Value>) -> String {
+    let mut text = text.to_string();
+    let mut link = None;
+    for mark in marks.and_then(Value::as_array).into_iter().flatten() {
+        text = match node_type(mark) {
+            "strong" => format!("**{}**", text),
+            "em" => format!("*{}*", text),
+            "code" => format!("`{}`", text),
+            "strike" => format!("~~{}~~", text),
+            "link" => {
+                link = mark.get("attrs").and_then(|a| a.get("href")).and_then(Value::as_str).map(str::to_string);
+                text
+            }
+            _ => text,
+        };
+    }
+    match link {
+        Some(href) => format!("[{}]({})", text, href),
+        None => text,
+    }
+}
+
+fn plain_text(node: &Value) -> String {
+    if let Some(text) = node.get("text").and_then(Value::as_str) {
+        return text.to_string();
+    }
+    node.get("content")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .map(plain_text)
+        .collect()
+}
+
+fn node_type(node: &Value) -> &str {
+    node.get("type").and_then(Value::as_str).unwrap_or("")
+}
+
+pub(crate) fn adf_text(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(text) => Some(text.clone()),
+        Value::Object(map) if is_empty_doc(map) => None,
+        other => Some(adf_to_markdown(other)),
+    }
+}
+
+fn is_empty_doc(map: &Map<String, Value>) -> bool {
+    map.get("content").and_then(Value::as_array).is_none_or(|c| c.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_to_adf_blocks_and_marks() {
+        let adf = markdown_to_adf("# Title\n\nSome **bold** and `code` with [a link](https://x.dev).\n\n- one\n- two\n\n```rust\nfn main() {}\n```");
+        let content = adf["content"].as_array().unwrap();
+        assert_eq!(content[0]["type"], "heading");
+        assert_eq!(content[0]["attrs"]["level"], 1);
+        assert_eq!(content[1]["content"][1], json!({ "type": "text", "text": "bold", "marks": [{ "type": "strong" }] }));
+        assert_eq!(content[1]["content"][3]["marks"][0]["type"], "code");
+        assert_eq!(content[1]["content"][5]["marks"][0]["attrs"]["href"], "https://x.dev");
+        assert_eq!(content[2]["type"], "bulletList");
+        assert_eq!(content[2]["content"].as_array().unwrap().len(), 2);
+        assert_eq!(content[3]["attrs"]["language"], "rust");
+        assert_eq!(content[3]["content"][0]["text"], "fn main() {}");
+    }
+
+    #[test]
+    fn test_markdown_round_trip() {
+        let markdown = "## Steps\n\n1. Open *settings*\n2. Click ~~cancel~~ save\n\n> quoted line\n\nDone.";
+        assert_eq!(adf_to_markdown(&markdown_to_adf(markdown)), markdown);
+    }
+
+    #[test]
+    fn test_adf_to_markdown_nested_lists_and_mentions() {
+        let adf = json!({
+            "type": "doc",
+            "version": 1,
+            "content": [{
+                "type": "bulletList",
+                "content": [{
+                    "type": "listItem",
+                    "content": [
+                        { "type": "paragraph", "content": [
+                            { "type": "text", "text": "ask " },
+                            { "type": "mention", "attrs": { "id": "1", "text": "@ana" } }
+                        ]},
+                        { "type": "bulletList", "content": [{
+                            "type": "listItem",
+                            "content": [{ "type": "paragraph", "content": [{ "type": "text", "text": "nested" }] }]
+                        }]}
+                    ]
+                }]
+            }]
+        });
+        assert_eq!(adf_to_markdown(&adf), "- ask @ana\n  - nested");
+    }
+}
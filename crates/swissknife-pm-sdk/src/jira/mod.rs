@@ -0,0 +1,1055 @@
+mod adf;
+
+pub use adf::{adf_to_markdown, markdown_to_adf};
+
+use crate::{
+    Comment, CreateIssue, Error, Issue, IssueFilter, IssueType, Label, ListOptions, ListResult, Priority,
+    Project, ProjectManagementProvider, ProjectStatus, Result, Sprint, SprintProvider, SprintStatus, StateType,
+    UpdateIssue, User, WorkflowState,
+};
+use adf::adf_text;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use reqwest::{Method, RequestBuilder};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use std::collections::{HashMap, HashSet};
+
+const DEFAULT_PAGE_SIZE: u32 = 50;
+const MAX_SEARCH_RESULTS: u32 = 100;
+const DEFAULT_SPRINT_DAYS: i64 = 14;
+const ISSUE_FIELDS: [&str; 14] = [
+    "summary", "description", "issuetype", "status", "priority", "assignee", "reporter", "project",
+    "parent", "labels", "duedate", "created", "updated", "resolutiondate",
+];
+
+#[derive(Clone)]
+enum JiraAuth {
+    Basic { email: String, api_token: String },
+    Bearer(String),
+}
+
+#[derive(Clone)]
+pub struct JiraClient {
+    base_url: String,
+    auth: JiraAuth,
+    story_points_field: Option<String>,
+    http: reqwest::Client,
+}
+
+impl JiraClient {
+    pub fn new(base_url: impl Into<String>, email: impl Into<String>, api_token: impl Into<String>) -> Self {
+        Self::with_auth(
+            base_url.into(),
+            JiraAuth::Basic {
+                email: email.into(),
+                api_token: api_token.into(),
+            },
+        )
+    }
+
+    pub fn bearer(base_url: impl Into<String>, token: impl Into<String>) -> Self {
+        Self::with_auth(base_url.into(), JiraAuth::Bearer(token.into()))
+    }
+
+    fn with_auth(base_url: String, auth: JiraAuth) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            auth,
+            story_points_field: None,
+            http: reqwest::Client::new(),
+        }
+    }
+
+    pub fn with_story_points_field(mut self, field: impl Into<String>) -> Self {
+        self.story_points_field = Some(field.into());
+        self
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let request = self.http.request(method, format!("{}{}", self.base_url, path));
+        match &self.auth {
+            JiraAuth::Basic { email, api_token } => request.basic_auth(email, Some(api_token)),
+            JiraAuth::Bearer(token) => request.bearer_auth(token),
+        }
+    }
+
+    async fn send<T: DeserializeOwned>(&self, method: Method, path: &str, body: Option<Value>) -> Result<T> {
+        let mut request = self.request(method, path).header("Accept", "application/json");
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+
+        let response = request.send().await?;
+        let status = response.status().as_u16();
+        let text = response.text().await?;
+
+        if !(200..300).contains(&status) {
+            let message = serde_json::from_str::<JiraErrorResponse>(&text)
+                .ok()
+                .and_then(JiraErrorResponse::message)
+                .unwrap_or(text);
+            return Err(match status {
+                400 => Error::Validation(message),
+                401 | 403 => Error::Auth(message),
+                404 => Error::NotFound(message),
+                429 => Error::RateLimited,
+                _ => Error::Api {
+                    message,
+                    code: Some(status.to_string()),
+                },
+            });
+        }
+
+        if text.trim().is_empty() {
+            return Ok(serde_json::from_value(Value::Null)?);
+        }
+        Ok(serde_json::from_str(&text)?)
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        self.send(Method::GET, path, None).await
+    }
+
+    pub async fn search(&self, jql: &str, max_results: Option<u32>) -> Result<Vec<Issue>> {
+        let options = ListOptions {
+            per_page: max_results,
+            ..Default::default()
+        };
+        Ok(self.search_jql(jql, &options).await?.items)
+    }
+
+    pub async fn search_jql(&self, jql: &str, options: &ListOptions) -> Result<ListResult<Issue>> {
+        let mut body = json!({
+            "jql": jql,
+            "maxResults": options.per_page.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_SEARCH_RESULTS),
+            "fields": self.issue_fields(),
+        });
+        if let Some(token) = &options.cursor {
+            body["nextPageToken"] = json!(token);
+        }
+
+        let response: JiraSearchResponse = self.send(Method::POST, "/rest/api/3/search/jql", Some(body)).await?;
+        let has_more = !response.is_last.unwrap_or(response.next_page_token.is_none());
+        Ok(ListResult {
+            items: response.issues.into_iter().map(|i| self.to_issue(i)).collect(),
+            total: None,
+            has_more,
+            next_cursor: response.next_page_token.filter(|_| has_more),
+        })
+    }
+
+    pub async fn transition_issue(&self, issue_id: &str, transition_id: &str) -> Result<()> {
+        let body = json!({ "transition": { "id": transition_id } });
+        self.send(Method::POST, &format!("/rest/api/3/issue/{}/transitions", issue_id), Some(body))
+            .await
+    }
+
+    async fn transition_to_status(&self, issue_id: &str, status: &str) -> Result<()> {
+        let response: JiraTransitions = self.get(&format!("/rest/api/3/issue/{}/transitions", issue_id)).await?;
+        let transition = response
+            .transitions
+            .into_iter()
+            .find(|t| {
+                t.name.eq_ignore_ascii_case(status)
+                    || t.to.as_ref().is_some_and(|to| to.name.eq_ignore_ascii_case(status))
+            })
+            .ok_or_else(|| Error::Validation(format!("No transition to status '{}' for issue {}", status, issue_id)))?;
+        self.transition_issue(issue_id, &transition.id).await
+    }
+
+    async fn board_for_project(&self, project_id: &str) -> Result<u64> {
+        let path = format!("/rest/agile/1.0/board?type=scrum&projectKeyOrId={}", project_id);
+        let boards: JiraPage<JiraBoard> = self.get(&path).await?;
+        boards
+            .values
+            .into_iter()
+            .next()
+            .map(|b| b.id)
+            .ok_or_else(|| Error::NotFound(format!("No scrum board for project {}", project_id)))
+    }
+
+    async fn board_sprints(&self, board_id: u64, state: Option<&str>) -> Result<Vec<JiraSprint>> {
+        let mut sprints = Vec::new();
+        let mut start_at = 0;
+        loop {
+            let mut path = format!("/rest/agile/1.0/board/{}/sprint?startAt={}&maxResults={}", board_id, start_at, DEFAULT_PAGE_SIZE);
+            if let Some(state) = state {
+                path.push_str(&format!("&state={}", state));
+            }
+            let page: JiraPage<JiraSprint> = self.get(&path).await?;
+            start_at += page.values.len() as u64;
+            let done = page.values.is_empty() || page.is_last.unwrap_or(true);
+            sprints.extend(page.values);
+            if done {
+                return Ok(sprints);
+            }
+        }
+    }
+
+    async fn sprint_project(&self, sprint: &JiraSprint) -> Result<String> {
+        let Some(board_id) = sprint.origin_board_id else {
+            return Ok(String::new());
+        };
+        let board: JiraBoard = self.get(&format!("/rest/agile/1.0/board/{}", board_id)).await?;
+        Ok(board
+            .location
+            .and_then(|l| l.project_id.map(|id| id.to_string()))
+            .unwrap_or_default())
+    }
+
+    async fn update_sprint(&self, sprint_id: &str, body: Value) -> Result<Sprint> {
+        let sprint: JiraSprint = self
+            .send(Method::POST, &format!("/rest/agile/1.0/sprint/{}", sprint_id), Some(body))
+            .await?;
+        let project_id = self.sprint_project(&sprint).await?;
+        Ok(to_sprint(sprint, &project_id))
+    }
+
+    async fn comment_issue_id(&self, comment_id: &str) -> Result<String> {
+        let id: u64 = comment_id
+            .parse()
+            .map_err(|_| Error::Validation(format!("Invalid Jira comment id: {}", comment_id)))?;
+        let page: JiraPage<JiraComment> = self
+            .send(Method::POST, "/rest/api/3/comment/list", Some(json!({ "ids": [id] })))
+            .await?;
+        page.values
+            .into_iter()
+            .next()
+            .and_then(|c| c.issue_id())
+            .ok_or_else(|| Error::NotFound(format!("Comment {}", comment_id)))
+    }
+
+    fn issue_fields(&self) -> Vec<&str> {
+        let mut fields = ISSUE_FIELDS.to_vec();
+        if let Some(field) = &self.story_points_field {
+            fields.push(field);
+        }
+        fields
+    }
+
+    fn to_issue(&self, issue: JiraIssue) -> Issue {
+        let fields = &issue.fields;
+        let name = |key: &str| fields.get(key).and_then(|v| v.get("name")).and_then(Value::as_str);
+        let account = |key: &str| fields.get(key).and_then(|v| v.get("accountId")).and_then(Value::as_str).map(str::to_string);
+        let date_time = |key: &str| fields.get(key).and_then(Value::as_str).and_then(parse_datetime);
+
+        let subtask = fields
+            .get("issuetype")
+            .and_then(|t| t.get("subtask"))
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        let mut extra = HashMap::new();
+        extra.insert("url".to_string(), json!(format!("{}/browse/{}", self.base_url, issue.key)));
+        if let Some(category) = fields.get("status").and_then(|s| s.pointer("/statusCategory/key")) {
+            extra.insert("status_category".to_string(), category.clone());
+        }
+
+        Issue {
+            id: issue.id.clone(),
+            key: Some(issue.key.clone()),
+            title: fields.get("summary").and_then(Value::as_str).unwrap_or_default().to_string(),
+            description: fields.get("description").and_then(adf_text),
+            issue_type: if subtask { IssueType::Subtask } else { name("issuetype").map(issue_type_from_name).unwrap_or(IssueType::Other) },
+            status: name("status").unwrap_or_default().to_string(),
+            priority: name("priority").map(priority_from_name),
+            assignee_id: account("assignee"),
+            reporter_id: account("reporter"),
+            project_id: fields.get("project").and_then(|p| p.get("id")).and_then(Value::as_str).unwrap_or_default().to_string(),
+            parent_id: fields.get("parent").and_then(|p| p.get("id")).and_then(Value::as_str).map(str::to_string),
+            labels: fields
+                .get("labels")
+                .and_then(Value::as_array)
+                .map(|labels| labels.iter().filter_map(|l| l.as_str().map(str::to_string)).collect())
+                .unwrap_or_default(),
+            estimate: self.story_points_field.as_ref().and_then(|f| fields.get(f)).and_then(Value::as_f64),
+            due_date: fields
+                .get("duedate")
+                .and_then(Value::as_str)
+                .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok()),
+            created_at: date_time("created").unwrap_or_default(),
+            updated_at: date_time("updated"),
+            completed_at: date_time("resolutiondate"),
+            extra,
+        }
+    }
+
+    fn create_fields(&self, project_id: &str, issue: &CreateIssue) -> Map<String, Value> {
+        let issue_type = issue
+            .issue_type
+            .unwrap_or(if issue.parent_id.is_some() { IssueType::Subtask } else { IssueType::Task });
+
+        let mut fields = Map::new();
+        fields.insert("project".to_string(), id_or_key(project_id));
+        fields.insert("summary".to_string(), json!(issue.title));
+        fields.insert("issuetype".to_string(), json!({ "name": issue_type_name(issue_type) }));
+        if let Some(parent) = &issue.parent_id {
+            fields.insert("parent".to_string(), id_or_key(parent));
+        }
+        if !issue.labels.is_empty() {
+            fields.insert("labels".to_string(), json!(issue.labels));
+        }
+        self.insert_common_fields(
+            &mut fields,
+            issue.description.as_deref(),
+            issue.priority,
+            issue.assignee_id.as_deref(),
+            issue.estimate,
+            issue.due_date,
+        );
+        fields
+    }
+
+    fn update_fields(&self, update: &UpdateIssue) -> Map<String, Value> {
+        let mut fields = Map::new();
+        if let Some(title) = &update.title {
+            fields.insert("summary".to_string(), json!(title));
+        }
+        if let Some(labels) = &update.labels {
+            fields.insert("labels".to_string(), json!(labels));
+        }
+        self.insert_common_fields(
+            &mut fields,
+            update.description.as_deref(),
+            update.priority,
+            update.assignee_id.as_deref(),
+            update.estimate,
+            update.due_date,
+        );
+        fields
+    }
+
+    fn insert_common_fields(
+        &self,
+        fields: &mut Map<String, Value>,
+        description: Option<&str>,
+        priority: Option<Priority>,
+        assignee_id: Option<&str>,
+        estimate: Option<f64>,
+        due_date: Option<NaiveDate>,
+    ) {
+        if let Some(description) = description {
+            fields.insert("description".to_string(), markdown_to_adf(description));
+        }
+        if let Some(priority) = priority {
+            fields.insert("priority".to_string(), json!({ "name": priority_name(priority) }));
+        }
+        if let Some(assignee) = assignee_id {
+            fields.insert("assignee".to_string(), json!({ "accountId": assignee }));
+        }
+        if let (Some(field), Some(estimate)) = (&self.story_points_field, estimate) {
+            fields.insert(field.clone(), json!(estimate));
+        }
+        if let Some(due) = due_date {
+            fields.insert("duedate".to_string(), json!(due.format("%Y-%m-%d").to_string()));
+        }
+    }
+
+    fn to_project(&self, project: JiraProject) -> Project {
+        Project {
+            url: Some(format!("{}/browse/{}", self.base_url, project.key)),
+            id: project.id,
+            name: project.name,
+            key: Some(project.key),
+            description: project.description.filter(|d| !d.is_empty()),
+            lead_id: project.lead.map(|l| l.account_id),
+            status: Some(if project.archived.unwrap_or(false) { ProjectStatus::Archived } else { ProjectStatus::Active }),
+            created_at: None,
+            updated_at: None,
+            extra: HashMap::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl ProjectManagementProvider for JiraClient {
+    async fn list_projects(&self, options: &ListOptions) -> Result<ListResult<Project>> {
+        let (start_at, max_results) = page_window(options);
+        let path = format!("/rest/api/3/project/search?expand=description,lead&startAt={}&maxResults={}", start_at, max_results);
+        let page: JiraPage<JiraProject> = self.get(&path).await?;
+        Ok(page.into_list_result(start_at, |p| self.to_project(p)))
+    }
+
+    async fn get_project(&self, id: &str) -> Result<Project> {
+        let project: JiraProject = self.get(&format!("/rest/api/3/project/{}?expand=description,lead", id)).await?;
+        Ok(self.to_project(project))
+    }
+
+    async fn create_project(&self, name: &str, description: Option<&str>) -> Result<Project> {
+        let lead: JiraUser = self.get("/rest/api/3/myself").await?;
+        let mut body = json!({
+            "key": project_key(name),
+            "name": name,
+            "projectTypeKey": "software",
+            "leadAccountId": lead.account_id,
+        });
+        if let Some(description) = description {
+            body["description"] = json!(description);
+        }
+        let created: JiraCreated = self.send(Method::POST, "/rest/api/3/project", Some(body)).await?;
+        self.get_project(&created.id.to_string()).await
+    }
+
+    async fn update_project(&self, id: &str, name: Option<&str>, description: Option<&str>) -> Result<Project> {
+        let mut body = Map::new();
+        if let Some(name) = name {
+            body.insert("name".to_string(), json!(name));
+        }
+        if let Some(description) = description {
+            body.insert("description".to_string(), json!(description));
+        }
+        let project: JiraProject = self
+            .send(Method::PUT, &format!("/rest/api/3/project/{}?expand=description,lead", id), Some(Value::Object(body)))
+            .await?;
+        Ok(self.to_project(project))
+    }
+
+    async fn archive_project(&self, id: &str) -> Result<()> {
+        self.send(Method::POST, &format!("/rest/api/3/project/{}/archive", id), None).await
+    }
+
+    async fn list_issues(&self, project_id: &str, filter: &IssueFilter, options: &ListOptions) -> Result<ListResult<Issue>> {
+        self.search_jql(&build_jql(Some(project_id), filter), options).await
+    }
+
+    async fn get_issue(&self, id: &str) -> Result<Issue> {
+        let path = format!("/rest/api/3/issue/{}?fields={}", id, self.issue_fields().join(","));
+        let issue: JiraIssue = self.get(&path).await?;
+        Ok(self.to_issue(issue))
+    }
+
+    async fn create_issue(&self, project_id: &str, issue: &CreateIssue) -> Result<Issue> {
+        let body = json!({ "fields": self.create_fields(project_id, issue) });
+        let created: JiraCreated = self.send(Method::POST, "/rest/api/3/issue", Some(body)).await?;
+        self.get_issue(&created.id.to_string()).await
+    }
+
+    async fn update_issue(&self, id: &str, update: &UpdateIssue) -> Result<Issue> {
+        let fields = self.update_fields(update);
+        if !fields.is_empty() {
+            self.send::<()>(Method::PUT, &format!("/rest/api/3/issue/{}", id), Some(json!({ "fields": fields })))
+                .await?;
+        }
+        if let Some(status) = &update.status {
+            self.transition_to_status(id, status).await?;
+        }
+        self.get_issue(id).await
+    }
+
+    async fn delete_issue(&self, id: &str) -> Result<()> {
+        self.send(Method::DELETE, &format!("/rest/api/3/issue/{}", id), None).await
+    }
+
+    async fn search_issues(&self, query: &str, options: &ListOptions) -> Result<ListResult<Issue>> {
+        let filter = IssueFilter {
+            search: Some(query.to_string()),
+            ..Default::default()
+        };
+        self.search_jql(&build_jql(None, &filter), options).await
+    }
+
+    async fn list_labels(&self, _project_id: &str) -> Result<Vec<Label>> {
+        let mut labels = Vec::new();
+        let mut start_at = 0;
+        loop {
+            let path = format!("/rest/api/3/label?startAt={}&maxResults=1000", start_at);
+            let page: JiraPage<String> = self.get(&path).await?;
+            start_at += page.values.len() as u64;
+            let done = page.values.is_empty() || page.is_last.unwrap_or(true);
+            labels.extend(page.values.into_iter().map(|name| Label {
+                id: name.clone(),
+                name,
+                color: None,
+                description: None,
+            }));
+            if done {
+                return Ok(labels);
+            }
+        }
+    }
+
+    async fn create_label(&self, _project_id: &str, name: &str, _color: Option<&str>) -> Result<Label> {
+        if name.chars().any(char::is_whitespace) {
+            return Err(Error::Validation("Jira labels cannot contain spaces".to_string()));
+        }
+        Ok(Label {
+            id: name.to_string(),
+            name: name.to_string(),
+            color: None,
+            description: None,
+        })
+    }
+
+    async fn list_workflow_states(&self, project_id: &str) -> Result<Vec<WorkflowState>> {
+        let issue_types: Vec<JiraIssueTypeStatuses> = self.get(&format!("/rest/api/3/project/{}/statuses", project_id)).await?;
+        let mut seen = HashSet::new();
+        let mut states = Vec::new();
+        for status in issue_types.into_iter().flat_map(|t| t.statuses) {
+            if !seen.insert(status.id.clone()) {
+                continue;
+            }
+            let category = status.status_category.unwrap_or_default();
+            states.push(WorkflowState {
+                position: Some(states.len() as i32),
+                id: status.id,
+                name: status.name,
+                state_type: state_type_from_category(&category.key),
+                color: category.color_name,
+            });
+        }
+        Ok(states)
+    }
+
+    async fn list_comments(&self, issue_id: &str) -> Result<Vec<Comment>> {
+        let mut comments = Vec::new();
+        loop {
+            let path = format!("/rest/api/3/issue/{}/comment?startAt={}&maxResults=100", issue_id, comments.len());
+            let page: JiraComments = self.get(&path).await?;
+            let done = page.comments.is_empty() || comments.len() + page.comments.len() >= page.total as usize;
+            comments.extend(page.comments.into_iter().map(|c| to_comment(c, issue_id)));
+            if done {
+                return Ok(comments);
+            }
+        }
+    }
+
+    async fn create_comment(&self, issue_id: &str, body: &str) -> Result<Comment> {
+        let comment: JiraComment = self
+            .send(
+                Method::POST,
+                &format!("/rest/api/3/issue/{}/comment", issue_id),
+                Some(json!({ "body": markdown_to_adf(body) })),
+            )
+            .await?;
+        Ok(to_comment(comment, issue_id))
+    }
+
+    async fn update_comment(&self, comment_id: &str, body: &str) -> Result<Comment> {
+        let issue_id = self.comment_issue_id(comment_id).await?;
+        let comment: JiraComment = self
+            .send(
+                Method::PUT,
+                &format!("/rest/api/3/issue/{}/comment/{}", issue_id, comment_id),
+                Some(json!({ "body": markdown_to_adf(body) })),
+            )
+            .await?;
+        Ok(to_comment(comment, &issue_id))
+    }
+
+    async fn delete_comment(&self, comment_id: &str) -> Result<()> {
+        let issue_id = self.comment_issue_id(comment_id).await?;
+        self.send(Method::DELETE, &format!("/rest/api/3/issue/{}/comment/{}", issue_id, comment_id), None)
+            .await
+    }
+
+    async fn list_users(&self) -> Result<Vec<User>> {
+        let mut users = Vec::new();
+        let mut start_at = 0;
+        loop {
+            let path = format!("/rest/api/3/users/search?startAt={}&maxResults=1000", start_at);
+            let page: Vec<JiraUser> = self.get(&path).await?;
+            if page.is_empty() {
+                return Ok(users);
+            }
+            start_at += page.len();
+            users.extend(
+                page.into_iter()
+                    .filter(|u| u.account_type.as_deref().is_none_or(|t| t == "atlassian"))
+                    .map(to_user),
+            );
+        }
+    }
+
+    async fn get_current_user(&self) -> Result<User> {
+        let user: JiraUser = self.get("/rest/api/3/myself").await?;
+        Ok(to_user(user))
+    }
+}
+
+#[async_trait]
+impl SprintProvider for JiraClient {
+    async fn list_sprints(&self, project_id: &str) -> Result<Vec<Sprint>> {
+        let board_id = self.board_for_project(project_id).await?;
+        let sprints = self.board_sprints(board_id, None).await?;
+        Ok(sprints.into_iter().map(|s| to_sprint(s, project_id)).collect())
+    }
+
+    async fn get_active_sprint(&self, project_id: &str) -> Result<Option<Sprint>> {
+        let board_id = self.board_for_project(project_id).await?;
+        let sprints = self.board_sprints(board_id, Some("active")).await?;
+        Ok(sprints.into_iter().next().map(|s| to_sprint(s, project_id)))
+    }
+
+    async fn create_sprint(&self, project_id: &str, name: &str, goal: Option<&str>, start: Option<NaiveDate>, end: Option<NaiveDate>) -> Result<Sprint> {
+        let board_id = self.board_for_project(project_id).await?;
+        let mut body = json!({ "name": name, "originBoardId": board_id });
+        if let Some(goal) = goal {
+            body["goal"] = json!(goal);
+        }
+        if let Some(start) = start {
+            body["startDate"] = json!(date_to_datetime(start).to_rfc3339());
+        }
+        if let Some(end) = end {
+            body["endDate"] = json!(date_to_datetime(end).to_rfc3339());
+        }
+        let sprint: JiraSprint = self.send(Method::POST, "/rest/agile/1.0/sprint", Some(body)).await?;
+        Ok(to_sprint(sprint, project_id))
+    }
+
+    async fn start_sprint(&self, sprint_id: &str) -> Result<Sprint> {
+        let sprint: JiraSprint = self.get(&format!("/rest/agile/1.0/sprint/{}", sprint_id)).await?;
+        let start = sprint.start_date.as_deref().and_then(parse_datetime).unwrap_or_else(Utc::now);
+        let end = sprint
+            .end_date
+            .as_deref()
+            .and_then(parse_datetime)
+            .unwrap_or(start + Duration::days(DEFAULT_SPRINT_DAYS));
+        let body = json!({
+            "state": "active",
+            "startDate": start.to_rfc3339(),
+            "endDate": end.to_rfc3339(),
+        });
+        self.update_sprint(sprint_id, body).await
+    }
+
+    async fn complete_sprint(&self, sprint_id: &str) -> Result<Sprint> {
+        self.update_sprint(sprint_id, json!({ "state": "closed" })).await
+    }
+}
+
+pub fn build_jql(project_id: Option<&str>, filter: &IssueFilter) -> String {
+    let mut clauses = Vec::new();
+    if let Some(project) = project_id.or(filter.project_id.as_deref()) {
+        clauses.push(format!("project = {}", jql_quote(project)));
+    }
+    if let Some(assignee) = &filter.assignee_id {
+        clauses.push(format!("assignee = {}", jql_quote(assignee)));
+    }
+    if let Some(status) = &filter.status {
+        clauses.push(format!("status = {}", jql_quote(status)));
+    }
+    for label in &filter.labels {
+        clauses.push(format!("labels = {}", jql_quote(label)));
+    }
+    if let Some(issue_type) = filter.issue_type {
+        clauses.push(format!("issuetype = {}", jql_quote(issue_type_name(issue_type))));
+    }
+    match filter.priority {
+        Some(Priority::None) => clauses.push("priority is EMPTY".to_string()),
+        Some(priority) => clauses.push(format!("priority = {}", jql_quote(priority_name(priority)))),
+        None => {}
+    }
+    if let Some(search) = &filter.search {
+        clauses.push(format!("text ~ {}", jql_quote(search)));
+    }
+
+    let mut jql = clauses.join(" AND ");
+    if !jql.is_empty() {
+        jql.push(' ');
+    }
+    jql.push_str("ORDER BY created DESC");
+    jql
+}
+
+fn jql_quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn id_or_key(value: &str) -> Value {
+    if value.chars().all(|c| c.is_ascii_digit()) {
+        json!({ "id": value })
+    } else {
+        json!({ "key": value })
+    }
+}
+
+fn page_window(options: &ListOptions) -> (u64, u32) {
+    let per_page = options.per_page.unwrap_or(DEFAULT_PAGE_SIZE);
+    let start_at = options
+        .cursor
+        .as_deref()
+        .and_then(|c| c.parse().ok())
+        .or_else(|| options.page.map(|p| p.saturating_sub(1) as u64 * per_page as u64))
+        .unwrap_or(0);
+    (start_at, per_page)
+}
+
+fn project_key(name: &str) -> String {
+    let words: Vec<&str> = name.split(|c: char| !c.is_ascii_alphanumeric()).filter(|w| !w.is_empty()).collect();
+    let key: String = if words.len() > 1 {
+        words.iter().filter_map(|w| w.chars().next()).collect()
+    } else {
+        name.chars().filter(char::is_ascii_alphanumeric).take(4).collect()
+    };
+    let key: String = key.to_ascii_uppercase().trim_start_matches(|c: char| c.is_ascii_digit()).chars().take(10).collect();
+    if key.len() < 2 {
+        format!("{}PRJ", key).chars().take(10).collect()
+    } else {
+        key
+    }
+}
+
+fn parse_datetime(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .or_else(|_| DateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f%z"))
+        .ok()
+        .map(|d| d.with_timezone(&Utc))
+}
+
+fn date_to_datetime(date: NaiveDate) -> DateTime<Utc> {
+    date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc()
+}
+
+pub fn issue_type_from_name(name: &str) -> IssueType {
+    let name = name.to_ascii_lowercase();
+    match name.as_str() {
+        "bug" => IssueType::Bug,
+        "story" | "user story" => IssueType::Story,
+        "epic" => IssueType::Epic,
+        "task" => IssueType::Task,
+        "sub-task" | "subtask" => IssueType::Subtask,
+        "new feature" | "feature" => IssueType::Feature,
+        "improvement" => IssueType::Improvement,
+        _ => IssueType::Other,
+    }
+}
+
+fn issue_type_name(issue_type: IssueType) -> &'static str {
+    match issue_type {
+        IssueType::Bug => "Bug",
+        IssueType::Story => "Story",
+        IssueType::Epic => "Epic",
+        IssueType::Subtask => "Subtask",
+        IssueType::Feature => "New Feature",
+        IssueType::Improvement => "Improvement",
+        IssueType::Task | IssueType::Other => "Task",
+    }
+}
+
+pub fn priority_from_name(name: &str) -> Priority {
+    match name.to_ascii_lowercase().as_str() {
+        "highest" | "blocker" | "critical" => Priority::Urgent,
+        "high" | "major" => Priority::High,
+        "medium" => Priority::Medium,
+        "low" | "lowest" | "minor" | "trivial" => Priority::Low,
+        _ => Priority::None,
+    }
+}
+
+fn priority_name(priority: Priority) -> &'static str {
+    match priority {
+        Priority::Urgent => "Highest",
+        Priority::High => "High",
+        Priority::Medium => "Medium",
+        Priority::Low => "Low",
+        Priority::None => "Lowest",
+    }
+}
+
+fn state_type_from_category(key: &str) -> StateType {
+    match key {
+        "done" => StateType::Completed,
+        "indeterminate" => StateType::Started,
+        _ => StateType::Unstarted,
+    }
+}
+
+fn to_sprint(sprint: JiraSprint, project_id: &str) -> Sprint {
+    let date = |value: &Option<String>| value.as_deref().and_then(parse_datetime).map(|d| d.date_naive());
+    Sprint {
+        id: sprint.id.to_string(),
+        start_date: date(&sprint.start_date),
+        end_date: date(&sprint.end_date),
+        status: match sprint.state.as_str() {
+            "active" => SprintStatus::Active,
+            "closed" => SprintStatus::Completed,
+            _ => SprintStatus::Planned,
+        },
+        name: sprint.name,
+        goal: sprint.goal.filter(|g| !g.is_empty()),
+        project_id: project_id.to_string(),
+    }
+}
+
+fn to_comment(comment: JiraComment, issue_id: &str) -> Comment {
+    let issue_id = comment.issue_id().unwrap_or_else(|| issue_id.to_string());
+    Comment {
+        body: adf_text(&comment.body).unwrap_or_default(),
+        author_id: comment.author.map(|a| a.account_id).unwrap_or_default(),
+        issue_id,
+        created_at: comment.created.as_deref().and_then(parse_datetime).unwrap_or_default(),
+        updated_at: comment.updated.as_deref().and_then(parse_datetime),
+        id: comment.id,
+    }
+}
+
+fn to_user(user: JiraUser) -> User {
+    User {
+        id: user.account_id,
+        name: user.display_name.unwrap_or_default(),
+        email: user.email_address,
+        avatar_url: user.avatar_urls.and_then(|urls| urls.get("48x48").cloned()),
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JiraErrorResponse {
+    #[serde(default)]
+    error_messages: Vec<String>,
+    #[serde(default)]
+    errors: HashMap<String, String>,
+}
+
+impl JiraErrorResponse {
+    fn message(self) -> Option<String> {
+        let mut messages = self.error_messages;
+        let mut errors: Vec<_> = self.errors.into_iter().collect();
+        errors.sort();
+        messages.extend(errors.into_iter().map(|(field, message)| format!("{}: {}", field, message)));
+        (!messages.is_empty()).then(|| messages.join("; "))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JiraPage<T> {
+    #[serde(default = "Vec::new")]
+    values: Vec<T>,
+    total: Option<u64>,
+    is_last: Option<bool>,
+}
+
+impl<T> JiraPage<T> {
+    fn into_list_result<U>(self, start_at: u64, map: impl Fn(T) -> U) -> ListResult<U> {
+        let next = start_at + self.values.len() as u64;
+        let has_more = !self.values.is_empty()
+            && match (self.is_last, self.total) {
+                (Some(is_last), _) => !is_last,
+                (None, Some(total)) => next < total,
+                (None, None) => false,
+            };
+        ListResult {
+            items: self.values.into_iter().map(map).collect(),
+            total: self.total,
+            has_more,
+            next_cursor: has_more.then(|| next.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JiraSearchResponse {
+    #[serde(default)]
+    issues: Vec<JiraIssue>,
+    next_page_token: Option<String>,
+    is_last: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraIssue {
+    id: String,
+    key: String,
+    #[serde(default)]
+    fields: Map<String, Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraCreated {
+    #[serde(deserialize_with = "string_or_number")]
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JiraProject {
+    id: String,
+    key: String,
+    name: String,
+    description: Option<String>,
+    lead: Option<JiraUser>,
+    archived: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JiraUser {
+    account_id: String,
+    display_name: Option<String>,
+    email_address: Option<String>,
+    avatar_urls: Option<HashMap<String, String>>,
+    account_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JiraComment {
+    id: String,
+    #[serde(default)]
+    body: Value,
+    author: Option<JiraUser>,
+    created: Option<String>,
+    updated: Option<String>,
+    #[serde(rename = "self")]
+    self_url: Option<String>,
+}
+
+impl JiraComment {
+    fn issue_id(&self) -> Option<String> {
+        let url = self.self_url.as_deref()?;
+        let after = &url[url.find("/issue/")? + "/issue/".len()..];
+        after.split('/').next().map(str::to_string)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraComments {
+    #[serde(default)]
+    comments: Vec<JiraComment>,
+    #[serde(default)]
+    total: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraTransitions {
+    transitions: Vec<JiraTransition>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraTransition {
+    id: String,
+    name: String,
+    to: Option<JiraTransitionTarget>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraTransitionTarget {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraIssueTypeStatuses {
+    #[serde(default)]
+    statuses: Vec<JiraStatus>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JiraStatus {
+    id: String,
+    name: String,
+    status_category: Option<JiraStatusCategory>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JiraStatusCategory {
+    #[serde(default)]
+    key: String,
+    color_name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraBoard {
+    id: u64,
+    location: Option<JiraBoardLocation>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JiraBoardLocation {
+    project_id: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JiraSprint {
+    id: u64,
+    name: String,
+    state: String,
+    goal: Option<String>,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    origin_board_id: Option<u64>,
+}
+
+fn string_or_number<'de, D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<String, D::Error> {
+    match Value::deserialize(deserializer)? {
+        Value::String(s) => Ok(s),
+        Value::Number(n) => Ok(n.to_string()),
+        other => Err(serde::de::Error::custom(format!("expected string or number, got {}", other))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_jql_from_filter() {
+        let filter = IssueFilter {
+            assignee_id: Some("abc".into()),
+            status: Some("In Progress".into()),
+            labels: vec!["backend".into(), "urgent".into()],
+            issue_type: Some(IssueType::Bug),
+            priority: Some(Priority::Urgent),
+            search: Some("say \"hi\"".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            build_jql(Some("ENG"), &filter),
+            r#"project = "ENG" AND assignee = "abc" AND status = "In Progress" AND labels = "backend" AND labels = "urgent" AND issuetype = "Bug" AND priority = "Highest" AND text ~ "say \"hi\"" ORDER BY created DESC"#
+        );
+        assert_eq!(build_jql(None, &IssueFilter::default()), "ORDER BY created DESC");
+    }
+
+    #[test]
+    fn test_to_issue_maps_fields() {
+        let client = JiraClient::new("https://acme.atlassian.net/", "me@acme.dev", "token").with_story_points_field("customfield_10016");
+        let issue: JiraIssue = serde_json::from_value(json!({
+            "id": "10001",
+            "key": "ENG-7",
+            "fields": {
+                "summary": "Crash on save",
+                "description": { "type": "doc", "version": 1, "content": [
+                    { "type": "paragraph", "content": [{ "type": "text", "text": "Steps", "marks": [{ "type": "strong" }] }] }
+                ]},
+                "issuetype": { "name": "Bug", "subtask": false },
+                "status": { "name": "To Do", "statusCategory": { "key": "new" } },
+                "priority": { "name": "High" },
+                "assignee": { "accountId": "u1" },
+                "project": { "id": "100", "key": "ENG" },
+                "labels": ["backend"],
+                "duedate": "2024-03-01",
+                "created": "2024-01-15T10:30:00.000+0000",
+                "customfield_10016": 3.0
+            }
+        }))
+        .unwrap();
+
+        let issue = client.to_issue(issue);
+        assert_eq!(issue.key.as_deref(), Some("ENG-7"));
+        assert_eq!(issue.description.as_deref(), Some("**Steps**"));
+        assert_eq!(issue.issue_type, IssueType::Bug);
+        assert_eq!(issue.priority, Some(Priority::High));
+        assert_eq!(issue.project_id, "100");
+        assert_eq!(issue.estimate, Some(3.0));
+        assert_eq!(issue.due_date, NaiveDate::from_ymd_opt(2024, 3, 1));
+        assert_eq!(issue.created_at.to_rfc3339(), "2024-01-15T10:30:00+00:00");
+        assert_eq!(issue.extra["url"], "https://acme.atlassian.net/browse/ENG-7");
+    }
+
+    #[test]
+    fn test_page_cursor_and_project_key() {
+        let page: JiraPage<u32> = serde_json::from_value(json!({ "values": [1, 2], "total": 5, "isLast": false })).unwrap();
+        let result = page.into_list_result(2, |v| v);
+        assert!(result.has_more);
+        assert_eq!(result.next_cursor.as_deref(), Some("4"));
+        assert_eq!(page_window(&ListOptions { cursor: Some("4".into()), ..Default::default() }), (4, DEFAULT_PAGE_SIZE));
+
+        assert_eq!(project_key("Mobile App Team"), "MAT");
+        assert_eq!(project_key("payments"), "PAYM");
+    }
+}
@@ -207,6 +207,28 @@ pub struct UpdateIssue {
     pub due_date: Option<NaiveDate>,
 }
 
+// Only whole names count, so "Not done", "Incomplete" or "Ideas in review"
+// stay Unstarted instead of matching on a fragment.
+#[cfg(any(feature = "asana", feature = "trello", feature = "clickup"))]
+pub(crate) fn state_type_from_name(name: &str) -> StateType {
+    let name = name
+        .trim_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase()
+        .replace(['-', '_'], " ")
+        .replace('\u{2019}', "'");
+    match name.split_whitespace().collect::<Vec<_>>().join(" ").as_str() {
+        "canceled" | "cancelled" | "won't do" | "wont do" | "won't fix" | "wont fix" | "duplicate" | "rejected"
+        | "abandoned" => StateType::Canceled,
+        "done" | "complete" | "completed" | "closed" | "shipped" | "resolved" | "finished" | "released" => {
+            StateType::Completed
+        }
+        "in progress" | "doing" | "started" | "in review" | "review" | "code review" | "in development" | "testing"
+        | "in testing" | "qa" => StateType::Started,
+        "backlog" | "icebox" | "ideas" | "idea" | "someday" | "triage" => StateType::Backlog,
+        _ => StateType::Unstarted,
+    }
+}
+
 #[async_trait]
 pub trait ProjectManagementProvider: Send + Sync {
     async fn list_projects(&self, options: &ListOptions) -> Result<ListResult<Project>>;
@@ -244,3 +266,21 @@ pub trait SprintProvider: Send + Sync {
     async fn start_sprint(&self, sprint_id: &str) -> Result<Sprint>;
     async fn complete_sprint(&self, sprint_id: &str) -> Result<Sprint>;
 }
+
+#[cfg(all(test, any(feature = "asana", feature = "trello", feature = "clickup")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state_type_from_name_matches_whole_names() {
+        assert_eq!(state_type_from_name("In Progress"), StateType::Started);
+        assert_eq!(state_type_from_name("  in-progress "), StateType::Started);
+        assert_eq!(state_type_from_name("✅ Done"), StateType::Completed);
+        assert_eq!(state_type_from_name("Won\u{2019}t Fix"), StateType::Canceled);
+        assert_eq!(state_type_from_name("Icebox"), StateType::Backlog);
+        assert_eq!(state_type_from_name("Not done"), StateType::Unstarted);
+        assert_eq!(state_type_from_name("Incomplete"), StateType::Unstarted);
+        assert_eq!(state_type_from_name("Ideas in review"), StateType::Unstarted);
+        assert_eq!(state_type_from_name("To Do"), StateType::Unstarted);
+    }
+}
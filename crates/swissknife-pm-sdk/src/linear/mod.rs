@@ -0,0 +1,944 @@
+use crate::{
+    Comment, CreateIssue, Error, Issue, IssueFilter, IssueType, Label, ListOptions, ListResult, Priority, Project,
+    ProjectManagementProvider, ProjectStatus, Result, Sprint, SprintProvider, SprintStatus, StateType, UpdateIssue,
+    User, WorkflowState,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+const API_URL: &str = "https://api.linear.app/graphql";
+const DEFAULT_PAGE_SIZE: u32 = 50;
+const MAX_PAGE_SIZE: u32 = 250;
+const DEFAULT_CYCLE_DAYS: i64 = 14;
+
+const TEAM_FIELDS: &str = "id name key description createdAt updatedAt archivedAt";
+const ISSUE_FIELDS: &str = "id identifier title description priority estimate dueDate createdAt updatedAt completedAt url \
+    state { name type } assignee { id } creator { id } team { id } parent { id } labels { nodes { name } }";
+const COMMENT_FIELDS: &str = "id body createdAt updatedAt user { id } issue { id }";
+const USER_FIELDS: &str = "id name email avatarUrl";
+const CYCLE_FIELDS: &str = "id number name description startsAt endsAt completedAt isActive isPast team { id }";
+const TYPE_LABELS: [(&str, IssueType); 5] = [
+    ("bug", IssueType::Bug),
+    ("feature", IssueType::Feature),
+    ("improvement", IssueType::Improvement),
+    ("story", IssueType::Story),
+    ("epic", IssueType::Epic),
+];
+
+#[derive(Clone)]
+pub struct LinearClient {
+    api_url: String,
+    authorization: String,
+    http: reqwest::Client,
+}
+
+impl LinearClient {
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self {
+            api_url: API_URL.to_string(),
+            authorization: api_key.into(),
+            http: reqwest::Client::new(),
+        }
+    }
+
+    pub fn oauth(access_token: impl Into<String>) -> Self {
+        Self::new(format!("Bearer {}", access_token.into()))
+    }
+
+    pub fn with_api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.api_url = api_url.into();
+        self
+    }
+
+    pub async fn graphql<T: DeserializeOwned>(&self, query: &str, variables: Value) -> Result<T> {
+        let response = self
+            .http
+            .post(&self.api_url)
+            .header("Authorization", &self.authorization)
+            .json(&json!({ "query": query, "variables": variables }))
+            .send()
+            .await?;
+        let status = response.status().as_u16();
+        let text = response.text().await?;
+
+        let body: GraphQlResponse<T> = match serde_json::from_str(&text) {
+            Ok(body) => body,
+            Err(_) => {
+                return Err(match status {
+                    401 | 403 => Error::Auth(text),
+                    429 => Error::RateLimited,
+                    _ => Error::Api {
+                        message: text,
+                        code: Some(status.to_string()),
+                    },
+                })
+            }
+        };
+
+        if let Some(error) = body.errors.into_iter().next() {
+            let code = error.extensions.and_then(|e| e.code);
+            return Err(match code.as_deref() {
+                Some("RATELIMITED") => Error::RateLimited,
+                Some("AUTHENTICATION_ERROR") | Some("FORBIDDEN") => Error::Auth(error.message),
+                _ if status == 429 => Error::RateLimited,
+                _ if status == 401 || status == 403 => Error::Auth(error.message),
+                _ if error.message.starts_with("Entity not found") => Error::NotFound(error.message),
+                _ => Error::Api {
+                    message: error.message,
+                    code,
+                },
+            });
+        }
+
+        body.data
+            .ok_or_else(|| Error::Api {
+                message: "GraphQL response contained no data".to_string(),
+                code: Some(status.to_string()),
+            })
+    }
+
+    async fn mutate(&self, query: &str, variables: Value, field: &str) -> Result<Value> {
+        let mut data: Map<String, Value> = self.graphql(query, variables).await?;
+        let payload = data.remove(field).unwrap_or_default();
+        if payload.get("success").and_then(Value::as_bool) == Some(false) {
+            return Err(Error::Api {
+                message: format!("{} was not successful", field),
+                code: None,
+            });
+        }
+        Ok(payload)
+    }
+
+    // Follows `pageInfo` until the connection at `pointer` (a JSON pointer into
+    // `data`) is exhausted. The query must take `$first` and `$after`.
+    async fn paginate<T: DeserializeOwned>(&self, query: &str, mut variables: Value, pointer: &str) -> Result<Vec<T>> {
+        let mut items = Vec::new();
+        variables["first"] = json!(MAX_PAGE_SIZE);
+        variables["after"] = Value::Null;
+        loop {
+            let mut data: Value = self.graphql(query, variables.clone()).await?;
+            let connection: Connection<T> =
+                serde_json::from_value(data.pointer_mut(pointer).map(Value::take).unwrap_or_default())?;
+            items.extend(connection.nodes);
+            match connection.page_info {
+                Some(PageInfo { has_next_page: true, end_cursor: Some(cursor) }) => variables["after"] = json!(cursor),
+                _ => return Ok(items),
+            }
+        }
+    }
+
+    async fn team_states(&self, team_id: &str) -> Result<Vec<LinearWorkflowState>> {
+        let query = "query($id: String!, $first: Int, $after: String) { team(id: $id) { states(first: $first, after: $after) { nodes { id name type color position } pageInfo { hasNextPage endCursor } } } }";
+        self.paginate(query, json!({ "id": team_id }), "/team/states").await
+    }
+
+    async fn resolve_labels(&self, team_id: &str, names: &[String], issue_type: Option<IssueType>) -> Result<Vec<String>> {
+        let type_label = issue_type.and_then(type_label_name);
+        let mut wanted: Vec<String> = names.to_vec();
+        wanted.extend(type_label.map(str::to_string));
+        if wanted.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let query = "query($names: [String!], $team: ID, $first: Int, $after: String) { issueLabels(first: $first, after: $after, filter: { name: { in: $names }, or: [{ team: { id: { eq: $team } } }, { team: { null: true } }] }) { nodes { id name } pageInfo { hasNextPage endCursor } } }";
+        let labels: Vec<NamedNode> = self
+            .paginate(query, json!({ "names": wanted, "team": team_id }), "/issueLabels")
+            .await?;
+        let by_name: HashMap<String, String> = labels
+            .into_iter()
+            .map(|l| (l.name.to_lowercase(), l.id))
+            .collect();
+
+        let mut ids = Vec::new();
+        for name in names {
+            let id = by_name
+                .get(&name.to_lowercase())
+                .ok_or_else(|| Error::Validation(format!("Unknown Linear label: {}", name)))?;
+            ids.push(id.clone());
+        }
+        if let Some(id) = type_label.and_then(|name| by_name.get(name)) {
+            if !ids.contains(id) {
+                ids.push(id.clone());
+            }
+        }
+        Ok(ids)
+    }
+
+    async fn issue_team(&self, issue_id: &str) -> Result<String> {
+        let query = "query($id: String!) { issue(id: $id) { team { id } } }";
+        let data: IssueTeamData = self.graphql(query, json!({ "id": issue_id })).await?;
+        Ok(data.issue.team.id)
+    }
+
+    async fn update_cycle(&self, cycle_id: &str, input: Value) -> Result<Sprint> {
+        let query = format!(
+            "mutation($id: String!, $input: CycleUpdateInput!) {{ cycleUpdate(id: $id, input: $input) {{ success cycle {{ {} }} }} }}",
+            CYCLE_FIELDS
+        );
+        let payload = self.mutate(&query, json!({ "id": cycle_id, "input": input }), "cycleUpdate").await?;
+        Ok(to_sprint(entity(payload, "cycle")?))
+    }
+}
+
+#[async_trait]
+impl ProjectManagementProvider for LinearClient {
+    async fn list_projects(&self, options: &ListOptions) -> Result<ListResult<Project>> {
+        let query = format!(
+            "query($first: Int, $after: String) {{ teams(first: $first, after: $after) {{ nodes {{ {} }} pageInfo {{ hasNextPage endCursor }} }} }}",
+            TEAM_FIELDS
+        );
+        let data: TeamsData = self.graphql(&query, page_variables(options)).await?;
+        Ok(data.teams.into_list_result(to_project))
+    }
+
+    async fn get_project(&self, id: &str) -> Result<Project> {
+        let query = format!("query($id: String!) {{ team(id: $id) {{ {} }} }}", TEAM_FIELDS);
+        let data: TeamData = self.graphql(&query, json!({ "id": id })).await?;
+        Ok(to_project(data.team))
+    }
+
+    async fn create_project(&self, name: &str, description: Option<&str>) -> Result<Project> {
+        let query = format!(
+            "mutation($input: TeamCreateInput!) {{ teamCreate(input: $input) {{ success team {{ {} }} }} }}",
+            TEAM_FIELDS
+        );
+        let input = json!({ "name": name, "description": description });
+        let payload = self.mutate(&query, json!({ "input": input }), "teamCreate").await?;
+        Ok(to_project(entity(payload, "team")?))
+    }
+
+    async fn update_project(&self, id: &str, name: Option<&str>, description: Option<&str>) -> Result<Project> {
+        let query = format!(
+            "mutation($id: String!, $input: TeamUpdateInput!) {{ teamUpdate(id: $id, input: $input) {{ success team {{ {} }} }} }}",
+            TEAM_FIELDS
+        );
+        let mut input = Map::new();
+        if let Some(name) = name {
+            input.insert("name".to_string(), json!(name));
+        }
+        if let Some(description) = description {
+            input.insert("description".to_string(), json!(description));
+        }
+        let payload = self.mutate(&query, json!({ "id": id, "input": input }), "teamUpdate").await?;
+        Ok(to_project(entity(payload, "team")?))
+    }
+
+    async fn archive_project(&self, id: &str) -> Result<()> {
+        let query = "mutation($id: String!) { teamDelete(id: $id) { success } }";
+        self.mutate(query, json!({ "id": id }), "teamDelete").await?;
+        Ok(())
+    }
+
+    async fn list_issues(&self, project_id: &str, filter: &IssueFilter, options: &ListOptions) -> Result<ListResult<Issue>> {
+        let query = format!(
+            "query($filter: IssueFilter, $first: Int, $after: String) {{ issues(filter: $filter, first: $first, after: $after) {{ nodes {{ {} }} pageInfo {{ hasNextPage endCursor }} }} }}",
+            ISSUE_FIELDS
+        );
+        let mut variables = page_variables(options);
+        variables["filter"] = build_issue_filter(Some(project_id), filter);
+        let data: IssuesData = self.graphql(&query, variables).await?;
+        Ok(data.issues.into_list_result(to_issue))
+    }
+
+    async fn get_issue(&self, id: &str) -> Result<Issue> {
+        let query = format!("query($id: String!) {{ issue(id: $id) {{ {} }} }}", ISSUE_FIELDS);
+        let data: IssueData = self.graphql(&query, json!({ "id": id })).await?;
+        Ok(to_issue(data.issue))
+    }
+
+    async fn create_issue(&self, project_id: &str, issue: &CreateIssue) -> Result<Issue> {
+        let query = format!(
+            "mutation($input: IssueCreateInput!) {{ issueCreate(input: $input) {{ success issue {{ {} }} }} }}",
+            ISSUE_FIELDS
+        );
+        let mut input = Map::new();
+        input.insert("teamId".to_string(), json!(project_id));
+        input.insert("title".to_string(), json!(issue.title));
+        let label_ids = self.resolve_labels(project_id, &issue.labels, issue.issue_type).await?;
+        if !label_ids.is_empty() {
+            input.insert("labelIds".to_string(), json!(label_ids));
+        }
+        if let Some(parent) = &issue.parent_id {
+            input.insert("parentId".to_string(), json!(parent));
+        }
+        insert_common_fields(
+            &mut input,
+            issue.description.as_deref(),
+            issue.priority,
+            issue.assignee_id.as_deref(),
+            issue.estimate,
+            issue.due_date,
+        );
+
+        let payload = self.mutate(&query, json!({ "input": input }), "issueCreate").await?;
+        Ok(to_issue(entity(payload, "issue")?))
+    }
+
+    async fn update_issue(&self, id: &str, update: &UpdateIssue) -> Result<Issue> {
+        let query = format!(
+            "mutation($id: String!, $input: IssueUpdateInput!) {{ issueUpdate(id: $id, input: $input) {{ success issue {{ {} }} }} }}",
+            ISSUE_FIELDS
+        );
+        let mut input = Map::new();
+        if let Some(title) = &update.title {
+            input.insert("title".to_string(), json!(title));
+        }
+        if update.status.is_some() || update.labels.is_some() {
+            let team_id = self.issue_team(id).await?;
+            if let Some(status) = &update.status {
+                let states = self.team_states(&team_id).await?;
+                let state = states
+                    .iter()
+                    .find(|s| s.name.eq_ignore_ascii_case(status))
+                    .ok_or_else(|| Error::Validation(format!("Unknown Linear workflow state: {}", status)))?;
+                input.insert("stateId".to_string(), json!(state.id));
+            }
+            if let Some(labels) = &update.labels {
+                input.insert("labelIds".to_string(), json!(self.resolve_labels(&team_id, labels, None).await?));
+            }
+        }
+        insert_common_fields(
+            &mut input,
+            update.description.as_deref(),
+            update.priority,
+            update.assignee_id.as_deref(),
+            update.estimate,
+            update.due_date,
+        );
+
+        let payload = self.mutate(&query, json!({ "id": id, "input": input }), "issueUpdate").await?;
+        Ok(to_issue(entity(payload, "issue")?))
+    }
+
+    async fn delete_issue(&self, id: &str) -> Result<()> {
+        let query = "mutation($id: String!) { issueDelete(id: $id) { success } }";
+        self.mutate(query, json!({ "id": id }), "issueDelete").await?;
+        Ok(())
+    }
+
+    async fn search_issues(&self, query: &str, options: &ListOptions) -> Result<ListResult<Issue>> {
+        let gql = format!(
+            "query($term: String!, $first: Int, $after: String) {{ searchIssues(term: $term, first: $first, after: $after) {{ nodes {{ {} }} pageInfo {{ hasNextPage endCursor }} totalCount }} }}",
+            ISSUE_FIELDS
+        );
+        let mut variables = page_variables(options);
+        variables["term"] = json!(query);
+        let data: SearchIssuesData = self.graphql(&gql, variables).await?;
+        Ok(data.search_issues.into_list_result(to_issue))
+    }
+
+    async fn list_labels(&self, project_id: &str) -> Result<Vec<Label>> {
+        let query = "query($id: String!, $first: Int, $after: String) { team(id: $id) { labels(first: $first, after: $after) { nodes { id name color description } pageInfo { hasNextPage endCursor } } } }";
+        self.paginate(query, json!({ "id": project_id }), "/team/labels").await
+    }
+
+    async fn create_label(&self, project_id: &str, name: &str, color: Option<&str>) -> Result<Label> {
+        let query = "mutation($input: IssueLabelCreateInput!) { issueLabelCreate(input: $input) { success issueLabel { id name color description } } }";
+        let input = json!({ "teamId": project_id, "name": name, "color": color });
+        let payload = self.mutate(query, json!({ "input": input }), "issueLabelCreate").await?;
+        entity(payload, "issueLabel")
+    }
+
+    async fn list_workflow_states(&self, project_id: &str) -> Result<Vec<WorkflowState>> {
+        let mut states = self.team_states(project_id).await?;
+        states.sort_by(|a, b| a.position.total_cmp(&b.position));
+        Ok(states
+            .into_iter()
+            .map(|s| WorkflowState {
+                id: s.id,
+                name: s.name,
+                state_type: state_type(&s.state_type),
+                color: s.color,
+                position: Some(s.position as i32),
+            })
+            .collect())
+    }
+
+    async fn list_comments(&self, issue_id: &str) -> Result<Vec<Comment>> {
+        let query = format!(
+            "query($id: String!, $first: Int, $after: String) {{ issue(id: $id) {{ comments(first: $first, after: $after) {{ nodes {{ {} }} pageInfo {{ hasNextPage endCursor }} }} }} }}",
+            COMMENT_FIELDS
+        );
+        let comments: Vec<LinearComment> = self.paginate(&query, json!({ "id": issue_id }), "/issue/comments").await?;
+        let mut comments: Vec<Comment> = comments.into_iter().map(to_comment).collect();
+        comments.sort_by_key(|c| c.created_at);
+        Ok(comments)
+    }
+
+    async fn create_comment(&self, issue_id: &str, body: &str) -> Result<Comment> {
+        let query = format!(
+            "mutation($input: CommentCreateInput!) {{ commentCreate(input: $input) {{ success comment {{ {} }} }} }}",
+            COMMENT_FIELDS
+        );
+        let input = json!({ "issueId": issue_id, "body": body });
+        let payload = self.mutate(&query, json!({ "input": input }), "commentCreate").await?;
+        Ok(to_comment(entity(payload, "comment")?))
+    }
+
+    async fn update_comment(&self, comment_id: &str, body: &str) -> Result<Comment> {
+        let query = format!(
+            "mutation($id: String!, $input: CommentUpdateInput!) {{ commentUpdate(id: $id, input: $input) {{ success comment {{ {} }} }} }}",
+            COMMENT_FIELDS
+        );
+        let variables = json!({ "id": comment_id, "input": { "body": body } });
+        let payload = self.mutate(&query, variables, "commentUpdate").await?;
+        Ok(to_comment(entity(payload, "comment")?))
+    }
+
+    async fn delete_comment(&self, comment_id: &str) -> Result<()> {
+        let query = "mutation($id: String!) { commentDelete(id: $id) { success } }";
+        self.mutate(query, json!({ "id": comment_id }), "commentDelete").await?;
+        Ok(())
+    }
+
+    async fn list_users(&self) -> Result<Vec<User>> {
+        let query = format!(
+            "query($first: Int, $after: String) {{ users(first: $first, after: $after) {{ nodes {{ {} }} pageInfo {{ hasNextPage endCursor }} }} }}",
+            USER_FIELDS
+        );
+        let users: Vec<LinearUser> = self.paginate(&query, json!({}), "/users").await?;
+        Ok(users.into_iter().map(to_user).collect())
+    }
+
+    async fn get_current_user(&self) -> Result<User> {
+        let query = format!("query {{ viewer {{ {} }} }}", USER_FIELDS);
+        let data: ViewerData = self.graphql(&query, json!({})).await?;
+        Ok(to_user(data.viewer))
+    }
+}
+
+#[async_trait]
+impl SprintProvider for LinearClient {
+    async fn list_sprints(&self, project_id: &str) -> Result<Vec<Sprint>> {
+        let query = format!(
+            "query($id: String!, $first: Int, $after: String) {{ team(id: $id) {{ cycles(first: $first, after: $after) {{ nodes {{ {} }} pageInfo {{ hasNextPage endCursor }} }} }} }}",
+            CYCLE_FIELDS
+        );
+        let cycles: Vec<LinearCycle> = self.paginate(&query, json!({ "id": project_id }), "/team/cycles").await?;
+        let mut sprints: Vec<Sprint> = cycles.into_iter().map(to_sprint).collect();
+        sprints.sort_by_key(|s| s.start_date);
+        Ok(sprints)
+    }
+
+    async fn get_active_sprint(&self, project_id: &str) -> Result<Option<Sprint>> {
+        let query = format!("query($id: String!) {{ team(id: $id) {{ activeCycle {{ {} }} }} }}", CYCLE_FIELDS);
+        let data: TeamActiveCycleData = self.graphql(&query, json!({ "id": project_id })).await?;
+        Ok(data.team.active_cycle.map(to_sprint))
+    }
+
+    async fn create_sprint(&self, project_id: &str, name: &str, goal: Option<&str>, start: Option<NaiveDate>, end: Option<NaiveDate>) -> Result<Sprint> {
+        let query = format!(
+            "mutation($input: CycleCreateInput!) {{ cycleCreate(input: $input) {{ success cycle {{ {} }} }} }}",
+            CYCLE_FIELDS
+        );
+        let starts_at = start.unwrap_or_else(|| Utc::now().date_naive());
+        let ends_at = end.unwrap_or(starts_at + Duration::days(DEFAULT_CYCLE_DAYS));
+        let input = json!({
+            "teamId": project_id,
+            "name": name,
+            "description": goal,
+            "startsAt": date_to_datetime(starts_at).to_rfc3339(),
+            "endsAt": date_to_datetime(ends_at).to_rfc3339(),
+        });
+        let payload = self.mutate(&query, json!({ "input": input }), "cycleCreate").await?;
+        Ok(to_sprint(entity(payload, "cycle")?))
+    }
+
+    async fn start_sprint(&self, sprint_id: &str) -> Result<Sprint> {
+        self.update_cycle(sprint_id, json!({ "startsAt": Utc::now().to_rfc3339() })).await
+    }
+
+    async fn complete_sprint(&self, sprint_id: &str) -> Result<Sprint> {
+        self.update_cycle(sprint_id, json!({ "endsAt": Utc::now().to_rfc3339() })).await
+    }
+}
+
+pub fn build_issue_filter(team_id: Option<&str>, filter: &IssueFilter) -> Value {
+    let mut result = Map::new();
+    if let Some(team) = team_id.or(filter.project_id.as_deref()) {
+        result.insert("team".to_string(), json!({ "id": { "eq": team } }));
+    }
+    if let Some(assignee) = &filter.assignee_id {
+        result.insert("assignee".to_string(), json!({ "id": { "eq": assignee } }));
+    }
+    if let Some(status) = &filter.status {
+        result.insert("state".to_string(), json!({ "name": { "eqIgnoreCase": status } }));
+    }
+    if let Some(priority) = filter.priority {
+        result.insert("priority".to_string(), json!({ "eq": priority_value(priority) }));
+    }
+    if let Some(search) = &filter.search {
+        result.insert("searchableContent".to_string(), json!({ "contains": search }));
+    }
+
+    let mut labels: Vec<&str> = filter.labels.iter().map(String::as_str).collect();
+    match filter.issue_type {
+        Some(IssueType::Subtask) => {
+            result.insert("parent".to_string(), json!({ "null": false }));
+        }
+        Some(issue_type) => labels.extend(type_label_name(issue_type)),
+        None => {}
+    }
+    if !labels.is_empty() {
+        let clauses: Vec<Value> = labels
+            .into_iter()
+            .map(|name| json!({ "labels": { "some": { "name": { "eqIgnoreCase": name } } } }))
+            .collect();
+        result.insert("and".to_string(), json!(clauses));
+    }
+
+    Value::Object(result)
+}
+
+fn insert_common_fields(
+    input: &mut Map<String, Value>,
+    description: Option<&str>,
+    priority: Option<Priority>,
+    assignee_id: Option<&str>,
+    estimate: Option<f64>,
+    due_date: Option<NaiveDate>,
+) {
+    if let Some(description) = description {
+        input.insert("description".to_string(), json!(description));
+    }
+    if let Some(priority) = priority {
+        input.insert("priority".to_string(), json!(priority_value(priority)));
+    }
+    if let Some(assignee) = assignee_id {
+        input.insert("assigneeId".to_string(), json!(assignee));
+    }
+    if let Some(estimate) = estimate {
+        input.insert("estimate".to_string(), json!(estimate.round() as i64));
+    }
+    if let Some(due) = due_date {
+        input.insert("dueDate".to_string(), json!(due.format("%Y-%m-%d").to_string()));
+    }
+}
+
+fn page_variables(options: &ListOptions) -> Value {
+    json!({
+        "first": options.per_page.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE),
+        "after": options.cursor,
+    })
+}
+
+fn entity<T: DeserializeOwned>(mut payload: Value, field: &str) -> Result<T> {
+    match payload.get_mut(field).map(Value::take) {
+        Some(value) if !value.is_null() => Ok(serde_json::from_value(value)?),
+        _ => Err(Error::Api {
+            message: format!("Response did not include {}", field),
+            code: None,
+        }),
+    }
+}
+
+fn date_to_datetime(date: NaiveDate) -> DateTime<Utc> {
+    date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc()
+}
+
+fn priority_value(priority: Priority) -> i32 {
+    match priority {
+        Priority::None => 0,
+        Priority::Urgent => 1,
+        Priority::High => 2,
+        Priority::Medium => 3,
+        Priority::Low => 4,
+    }
+}
+
+pub fn priority_from_value(value: i32) -> Priority {
+    match value {
+        1 => Priority::Urgent,
+        2 => Priority::High,
+        3 => Priority::Medium,
+        4 => Priority::Low,
+        _ => Priority::None,
+    }
+}
+
+fn type_label_name(issue_type: IssueType) -> Option<&'static str> {
+    TYPE_LABELS.iter().find(|(_, t)| *t == issue_type).map(|(name, _)| *name)
+}
+
+fn state_type(value: &str) -> StateType {
+    match value {
+        "backlog" | "triage" => StateType::Backlog,
+        "started" => StateType::Started,
+        "completed" => StateType::Completed,
+        "canceled" => StateType::Canceled,
+        _ => StateType::Unstarted,
+    }
+}
+
+fn to_project(team: LinearTeam) -> Project {
+    Project {
+        id: team.id,
+        name: team.name,
+        key: Some(team.key),
+        description: team.description,
+        url: None,
+        lead_id: None,
+        status: Some(if team.archived_at.is_some() { ProjectStatus::Archived } else { ProjectStatus::Active }),
+        created_at: team.created_at,
+        updated_at: team.updated_at,
+        extra: HashMap::new(),
+    }
+}
+
+fn to_issue(issue: LinearIssue) -> Issue {
+    let labels: Vec<String> = issue.labels.map(|l| l.nodes.into_iter().map(|l| l.name).collect()).unwrap_or_default();
+    let labeled_type = labels.iter().find_map(|label| {
+        TYPE_LABELS
+            .iter()
+            .find(|(name, _)| label.eq_ignore_ascii_case(name))
+            .map(|(_, issue_type)| *issue_type)
+    });
+    let issue_type = labeled_type.unwrap_or(if issue.parent.is_some() { IssueType::Subtask } else { IssueType::Task });
+
+    let mut extra = HashMap::new();
+    if let Some(url) = issue.url {
+        extra.insert("url".to_string(), json!(url));
+    }
+    if let Some(state) = &issue.state {
+        extra.insert("state_type".to_string(), json!(state.state_type));
+    }
+
+    Issue {
+        id: issue.id,
+        key: Some(issue.identifier),
+        title: issue.title,
+        description: issue.description,
+        issue_type,
+        status: issue.state.map(|s| s.name).unwrap_or_default(),
+        priority: issue.priority.map(|p| priority_from_value(p as i32)),
+        assignee_id: issue.assignee.map(|u| u.id),
+        reporter_id: issue.creator.map(|u| u.id),
+        project_id: issue.team.map(|t| t.id).unwrap_or_default(),
+        parent_id: issue.parent.map(|p| p.id),
+        labels,
+        estimate: issue.estimate,
+        due_date: issue.due_date.and_then(|d| NaiveDate::parse_from_str(&d, "%Y-%m-%d").ok()),
+        created_at: issue.created_at,
+        updated_at: issue.updated_at,
+        completed_at: issue.completed_at,
+        extra,
+    }
+}
+
+fn to_comment(comment: LinearComment) -> Comment {
+    Comment {
+        id: comment.id,
+        body: comment.body,
+        author_id: comment.user.map(|u| u.id).unwrap_or_default(),
+        issue_id: comment.issue.map(|i| i.id).unwrap_or_default(),
+        created_at: comment.created_at,
+        updated_at: comment.updated_at,
+    }
+}
+
+fn to_user(user: LinearUser) -> User {
+    User {
+        id: user.id,
+        name: user.name,
+        email: user.email,
+        avatar_url: user.avatar_url,
+    }
+}
+
+fn to_sprint(cycle: LinearCycle) -> Sprint {
+    let status = if cycle.completed_at.is_some() || cycle.is_past {
+        SprintStatus::Completed
+    } else if cycle.is_active {
+        SprintStatus::Active
+    } else {
+        SprintStatus::Planned
+    };
+    Sprint {
+        id: cycle.id,
+        name: cycle.name.filter(|n| !n.is_empty()).unwrap_or_else(|| format!("Cycle {}", cycle.number)),
+        goal: cycle.description,
+        status,
+        start_date: cycle.starts_at.map(|d| d.date_naive()),
+        end_date: cycle.ends_at.map(|d| d.date_naive()),
+        project_id: cycle.team.map(|t| t.id).unwrap_or_default(),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlResponse<T> {
+    data: Option<T>,
+    #[serde(default)]
+    errors: Vec<GraphQlError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlError {
+    message: String,
+    extensions: Option<GraphQlErrorExtensions>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlErrorExtensions {
+    code: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PageInfo {
+    has_next_page: bool,
+    end_cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Connection<T> {
+    nodes: Vec<T>,
+    page_info: Option<PageInfo>,
+    total_count: Option<u64>,
+}
+
+impl<T> Connection<T> {
+    fn into_list_result<U>(self, map: impl Fn(T) -> U) -> ListResult<U> {
+        let (has_more, next_cursor) = match self.page_info {
+            Some(PageInfo { has_next_page, end_cursor }) => (has_next_page, end_cursor.filter(|_| has_next_page)),
+            None => (false, None),
+        };
+        ListResult {
+            items: self.nodes.into_iter().map(map).collect(),
+            total: self.total_count,
+            has_more,
+            next_cursor,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Node {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct NamedNode {
+    id: String,
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct LabelName {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LinearTeam {
+    id: String,
+    name: String,
+    key: String,
+    description: Option<String>,
+    created_at: Option<DateTime<Utc>>,
+    updated_at: Option<DateTime<Utc>>,
+    archived_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LinearState {
+    name: String,
+    #[serde(rename = "type")]
+    state_type: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LinearIssue {
+    id: String,
+    identifier: String,
+    title: String,
+    description: Option<String>,
+    priority: Option<f64>,
+    estimate: Option<f64>,
+    due_date: Option<String>,
+    created_at: DateTime<Utc>,
+    updated_at: Option<DateTime<Utc>>,
+    completed_at: Option<DateTime<Utc>>,
+    url: Option<String>,
+    state: Option<LinearState>,
+    assignee: Option<Node>,
+    creator: Option<Node>,
+    team: Option<Node>,
+    parent: Option<Node>,
+    labels: Option<Connection<LabelName>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LinearComment {
+    id: String,
+    body: String,
+    created_at: DateTime<Utc>,
+    updated_at: Option<DateTime<Utc>>,
+    user: Option<Node>,
+    issue: Option<Node>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LinearUser {
+    id: String,
+    name: String,
+    email: Option<String>,
+    avatar_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LinearWorkflowState {
+    id: String,
+    name: String,
+    #[serde(rename = "type")]
+    state_type: String,
+    color: Option<String>,
+    position: f64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LinearCycle {
+    id: String,
+    number: f64,
+    name: Option<String>,
+    description: Option<String>,
+    starts_at: Option<DateTime<Utc>>,
+    ends_at: Option<DateTime<Utc>>,
+    completed_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    is_active: bool,
+    #[serde(default)]
+    is_past: bool,
+    team: Option<Node>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TeamsData {
+    teams: Connection<LinearTeam>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TeamData {
+    team: LinearTeam,
+}
+
+#[derive(Debug, Deserialize)]
+struct IssuesData {
+    issues: Connection<LinearIssue>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SearchIssuesData {
+    search_issues: Connection<LinearIssue>,
+}
+
+#[derive(Debug, Deserialize)]
+struct IssueData {
+    issue: LinearIssue,
+}
+
+#[derive(Debug, Deserialize)]
+struct IssueTeamData {
+    issue: IssueTeamWrapper,
+}
+
+#[derive(Debug, Deserialize)]
+struct IssueTeamWrapper {
+    team: Node,
+}
+
+#[derive(Debug, Deserialize)]
+struct ViewerData {
+    viewer: LinearUser,
+}
+
+#[derive(Debug, Deserialize)]
+struct TeamActiveCycleData {
+    team: TeamActiveCycle,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TeamActiveCycle {
+    active_cycle: Option<LinearCycle>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_issue_filter() {
+        let filter = IssueFilter {
+            assignee_id: Some("u1".into()),
+            status: Some("In Progress".into()),
+            labels: vec!["backend".into()],
+            issue_type: Some(IssueType::Bug),
+            priority: Some(Priority::High),
+            ..Default::default()
+        };
+        assert_eq!(
+            build_issue_filter(Some("team-1"), &filter),
+            json!({
+                "team": { "id": { "eq": "team-1" } },
+                "assignee": { "id": { "eq": "u1" } },
+                "state": { "name": { "eqIgnoreCase": "In Progress" } },
+                "priority": { "eq": 2 },
+                "and": [
+                    { "labels": { "some": { "name": { "eqIgnoreCase": "backend" } } } },
+                    { "labels": { "some": { "name": { "eqIgnoreCase": "bug" } } } }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn test_to_issue_and_cycle_mapping() {
+        let issue: LinearIssue = serde_json::from_value(json!({
+            "id": "i1",
+            "identifier": "ENG-12",
+            "title": "Fix login",
+            "description": "Use **markdown**",
+            "priority": 1,
+            "estimate": 3,
+            "dueDate": "2024-05-01",
+            "createdAt": "2024-04-01T12:00:00.000Z",
+            "state": { "name": "Todo", "type": "unstarted" },
+            "team": { "id": "team-1" },
+            "labels": { "nodes": [{ "name": "Bug" }] }
+        }))
+        .unwrap();
+        let issue = to_issue(issue);
+        assert_eq!(issue.key.as_deref(), Some("ENG-12"));
+        assert_eq!(issue.issue_type, IssueType::Bug);
+        assert_eq!(issue.priority, Some(Priority::Urgent));
+        assert_eq!(issue.project_id, "team-1");
+        assert_eq!(issue.due_date, NaiveDate::from_ymd_opt(2024, 5, 1));
+
+        let cycle: LinearCycle = serde_json::from_value(json!({
+            "id": "c1",
+            "number": 4,
+            "name": null,
+            "startsAt": "2024-04-01T00:00:00.000Z",
+            "endsAt": "2024-04-15T00:00:00.000Z",
+            "isActive": true,
+            "team": { "id": "team-1" }
+        }))
+        .unwrap();
+        let sprint = to_sprint(cycle);
+        assert_eq!(sprint.name, "Cycle 4");
+        assert_eq!(sprint.status, SprintStatus::Active);
+        assert_eq!(sprint.end_date, NaiveDate::from_ymd_opt(2024, 4, 15));
+    }
+}
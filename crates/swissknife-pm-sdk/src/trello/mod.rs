@@ -0,0 +1,646 @@
+use crate::{
+    state_type_from_name, Comment, CreateIssue, Error, Issue, IssueFilter, IssueType, Label, ListOptions, ListResult,
+    Project, ProjectManagementProvider, ProjectStatus, Result, Sprint, SprintProvider, UpdateIssue, User,
+    WorkflowState,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use reqwest::Method;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+const API_BASE: &str = "https://api.trello.com/1";
+const DEFAULT_PAGE_SIZE: u32 = 50;
+const MAX_PAGE_SIZE: u32 = 1000;
+const BOARD_FIELDS: &str = "name,desc,url,closed,dateLastActivity";
+const CARD_FIELDS: &str = "idShort,name,desc,idList,idBoard,idMembers,labels,due,dueComplete,dateLastActivity,shortUrl,closed";
+
+#[derive(Clone)]
+pub struct TrelloClient {
+    api_key: String,
+    token: String,
+    http: reqwest::Client,
+}
+
+impl TrelloClient {
+    pub fn new(api_key: impl Into<String>, token: impl Into<String>) -> Self {
+        Self {
+            api_key: api_key.into(),
+            token: token.into(),
+            http: reqwest::Client::new(),
+        }
+    }
+
+    async fn send<T: DeserializeOwned>(&self, method: Method, path: &str, params: &[(&str, String)]) -> Result<T> {
+        let response = self
+            .http
+            .request(method, format!("{}{}", API_BASE, path))
+            .query(&[("key", self.api_key.as_str()), ("token", self.token.as_str())])
+            .query(params)
+            .send()
+            .await?;
+        let status = response.status().as_u16();
+        let text = response.text().await?;
+
+        if !(200..300).contains(&status) {
+            let message = serde_json::from_str::<TrelloErrorResponse>(&text)
+                .ok()
+                .and_then(|e| e.message)
+                .unwrap_or(text);
+            return Err(match status {
+                400 => Error::Validation(message),
+                401 | 403 => Error::Auth(message),
+                404 => Error::NotFound(message),
+                429 => Error::RateLimited,
+                _ => Error::Api {
+                    message,
+                    code: Some(status.to_string()),
+                },
+            });
+        }
+
+        if text.trim().is_empty() {
+            return Ok(serde_json::from_value(Value::Null)?);
+        }
+        Ok(serde_json::from_str(&text)?)
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str, params: &[(&str, String)]) -> Result<T> {
+        self.send(Method::GET, path, params).await
+    }
+
+    pub async fn create_card(&self, list_id: &str, name: &str, description: Option<&str>, due: Option<&str>) -> Result<Issue> {
+        let mut params = vec![("idList", list_id.to_string()), ("name", name.to_string())];
+        if let Some(description) = description {
+            params.push(("desc", description.to_string()));
+        }
+        if let Some(due) = due {
+            params.push(("due", due.to_string()));
+        }
+        let card: TrelloCard = self.send(Method::POST, "/cards", &params).await?;
+        self.get_issue(&card.id).await
+    }
+
+    pub async fn list_cards(&self, list_id: &str) -> Result<Vec<Issue>> {
+        let list: TrelloList = self.get(&format!("/lists/{}", list_id), &[("fields", "name".to_string())]).await?;
+        let cards: Vec<TrelloCard> = self
+            .get(&format!("/lists/{}/cards", list_id), &[("fields", CARD_FIELDS.to_string())])
+            .await?;
+        let lists = HashMap::from([(list.id, list.name)]);
+        Ok(cards.into_iter().map(|c| to_issue(c, &lists)).collect())
+    }
+
+    async fn board_lists(&self, board_id: &str) -> Result<Vec<TrelloList>> {
+        self.get(&format!("/boards/{}/lists", board_id), &[("filter", "open".to_string()), ("fields", "name,pos".to_string())])
+            .await
+    }
+
+    async fn list_names(&self, board_id: &str) -> Result<HashMap<String, String>> {
+        Ok(self.board_lists(board_id).await?.into_iter().map(|l| (l.id, l.name)).collect())
+    }
+
+    async fn label_ids(&self, board_id: &str, names: &[String]) -> Result<Vec<String>> {
+        if names.is_empty() {
+            return Ok(Vec::new());
+        }
+        let labels: Vec<TrelloLabel> = self.get(&format!("/boards/{}/labels", board_id), &[]).await?;
+        names
+            .iter()
+            .map(|name| {
+                labels
+                    .iter()
+                    .find(|l| l.name.eq_ignore_ascii_case(name))
+                    .map(|l| l.id.clone())
+                    .ok_or_else(|| Error::Validation(format!("Unknown Trello label: {}", name)))
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl ProjectManagementProvider for TrelloClient {
+    async fn list_projects(&self, _options: &ListOptions) -> Result<ListResult<Project>> {
+        let boards: Vec<TrelloBoard> = self
+            .get("/members/me/boards", &[("fields", BOARD_FIELDS.to_string())])
+            .await?;
+        Ok(ListResult {
+            total: Some(boards.len() as u64),
+            items: boards.into_iter().map(to_project).collect(),
+            has_more: false,
+            next_cursor: None,
+        })
+    }
+
+    async fn get_project(&self, id: &str) -> Result<Project> {
+        let board: TrelloBoard = self.get(&format!("/boards/{}", id), &[("fields", BOARD_FIELDS.to_string())]).await?;
+        Ok(to_project(board))
+    }
+
+    async fn create_project(&self, name: &str, description: Option<&str>) -> Result<Project> {
+        let mut params = vec![("name", name.to_string()), ("defaultLists", "true".to_string())];
+        if let Some(description) = description {
+            params.push(("desc", description.to_string()));
+        }
+        let board: TrelloBoard = self.send(Method::POST, "/boards", &params).await?;
+        Ok(to_project(board))
+    }
+
+    async fn update_project(&self, id: &str, name: Option<&str>, description: Option<&str>) -> Result<Project> {
+        let mut params = Vec::new();
+        if let Some(name) = name {
+            params.push(("name", name.to_string()));
+        }
+        if let Some(description) = description {
+            params.push(("desc", description.to_string()));
+        }
+        let board: TrelloBoard = self.send(Method::PUT, &format!("/boards/{}", id), &params).await?;
+        Ok(to_project(board))
+    }
+
+    async fn archive_project(&self, id: &str) -> Result<()> {
+        self.send::<Value>(Method::PUT, &format!("/boards/{}", id), &[("closed", "true".to_string())])
+            .await?;
+        Ok(())
+    }
+
+    async fn list_issues(&self, project_id: &str, filter: &IssueFilter, options: &ListOptions) -> Result<ListResult<Issue>> {
+        let lists = self.list_names(project_id).await?;
+        let limit = options.per_page.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE);
+        let mut params = vec![("fields", CARD_FIELDS.to_string()), ("limit", limit.to_string())];
+        if let Some(before) = &options.cursor {
+            params.push(("before", before.clone()));
+        }
+
+        let cards: Vec<TrelloCard> = self.get(&format!("/boards/{}/cards", project_id), &params).await?;
+        let has_more = cards.len() as u32 >= limit;
+        let next_cursor = cards.iter().map(|c| c.id.clone()).min().filter(|_| has_more);
+        Ok(ListResult {
+            items: cards
+                .into_iter()
+                .map(|c| to_issue(c, &lists))
+                .filter(|issue| matches_filter(issue, filter))
+                .collect(),
+            total: None,
+            has_more,
+            next_cursor,
+        })
+    }
+
+    async fn get_issue(&self, id: &str) -> Result<Issue> {
+        let card: TrelloCard = self
+            .get(&format!("/cards/{}", id), &[("fields", CARD_FIELDS.to_string()), ("list", "true".to_string())])
+            .await?;
+        let lists: HashMap<String, String> = card.list.iter().map(|l| (l.id.clone(), l.name.clone())).collect();
+        Ok(to_issue(card, &lists))
+    }
+
+    async fn create_issue(&self, project_id: &str, issue: &CreateIssue) -> Result<Issue> {
+        let list = self
+            .board_lists(project_id)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| Error::Validation(format!("Board {} has no open lists", project_id)))?;
+
+        let mut params = vec![("idList", list.id), ("name", issue.title.clone())];
+        if let Some(description) = &issue.description {
+            params.push(("desc", description.clone()));
+        }
+        if let Some(due) = issue.due_date {
+            params.push(("due", due.format("%Y-%m-%d").to_string()));
+        }
+        if let Some(assignee) = &issue.assignee_id {
+            params.push(("idMembers", assignee.clone()));
+        }
+        let labels = self.label_ids(project_id, &issue.labels).await?;
+        if !labels.is_empty() {
+            params.push(("idLabels", labels.join(",")));
+        }
+
+        let card: TrelloCard = self.send(Method::POST, "/cards", &params).await?;
+        self.get_issue(&card.id).await
+    }
+
+    async fn update_issue(&self, id: &str, update: &UpdateIssue) -> Result<Issue> {
+        let mut params = Vec::new();
+        if let Some(title) = &update.title {
+            params.push(("name", title.clone()));
+        }
+        if let Some(description) = &update.description {
+            params.push(("desc", description.clone()));
+        }
+        if let Some(due) = update.due_date {
+            params.push(("due", due.format("%Y-%m-%d").to_string()));
+        }
+        if let Some(assignee) = &update.assignee_id {
+            params.push(("idMembers", assignee.clone()));
+        }
+
+        if update.status.is_some() || update.labels.is_some() {
+            let card: TrelloCard = self.get(&format!("/cards/{}", id), &[("fields", "idBoard".to_string())]).await?;
+            if let Some(status) = &update.status {
+                let list = self
+                    .board_lists(&card.id_board)
+                    .await?
+                    .into_iter()
+                    .find(|l| l.name.eq_ignore_ascii_case(status))
+                    .ok_or_else(|| Error::Validation(format!("Unknown Trello list: {}", status)))?;
+                params.push(("idList", list.id));
+            }
+            if let Some(labels) = &update.labels {
+                params.push(("idLabels", self.label_ids(&card.id_board, labels).await?.join(",")));
+            }
+        }
+
+        if !params.is_empty() {
+            self.send::<Value>(Method::PUT, &format!("/cards/{}", id), &params).await?;
+        }
+        self.get_issue(id).await
+    }
+
+    async fn delete_issue(&self, id: &str) -> Result<()> {
+        self.send::<Value>(Method::DELETE, &format!("/cards/{}", id), &[]).await?;
+        Ok(())
+    }
+
+    async fn search_issues(&self, query: &str, options: &ListOptions) -> Result<ListResult<Issue>> {
+        let limit = options.per_page.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE);
+        let page: u32 = options
+            .cursor
+            .as_deref()
+            .and_then(|c| c.parse().ok())
+            .or(options.page)
+            .unwrap_or(0);
+        let params = [
+            ("query", query.to_string()),
+            ("modelTypes", "cards".to_string()),
+            ("card_fields", CARD_FIELDS.to_string()),
+            ("card_list", "true".to_string()),
+            ("cards_limit", limit.to_string()),
+            ("cards_page", page.to_string()),
+        ];
+        let response: TrelloSearchResponse = self.get("/search", &params).await?;
+        let has_more = response.cards.len() as u32 >= limit;
+        Ok(ListResult {
+            items: response
+                .cards
+                .into_iter()
+                .map(|card| {
+                    let lists: HashMap<String, String> = card.list.iter().map(|l| (l.id.clone(), l.name.clone())).collect();
+                    to_issue(card, &lists)
+                })
+                .collect(),
+            total: None,
+            has_more,
+            next_cursor: has_more.then(|| (page + 1).to_string()),
+        })
+    }
+
+    async fn list_labels(&self, project_id: &str) -> Result<Vec<Label>> {
+        let labels: Vec<TrelloLabel> = self.get(&format!("/boards/{}/labels", project_id), &[]).await?;
+        Ok(labels.into_iter().map(to_label).collect())
+    }
+
+    async fn create_label(&self, project_id: &str, name: &str, color: Option<&str>) -> Result<Label> {
+        let params = [
+            ("idBoard", project_id.to_string()),
+            ("name", name.to_string()),
+            ("color", color.unwrap_or("null").to_string()),
+        ];
+        let label: TrelloLabel = self.send(Method::POST, "/labels", &params).await?;
+        Ok(to_label(label))
+    }
+
+    async fn list_workflow_states(&self, project_id: &str) -> Result<Vec<WorkflowState>> {
+        let lists = self.board_lists(project_id).await?;
+        Ok(lists
+            .into_iter()
+            .enumerate()
+            .map(|(position, list)| WorkflowState {
+                state_type: state_type_from_name(&list.name),
+                id: list.id,
+                name: list.name,
+                color: None,
+                position: Some(position as i32),
+            })
+            .collect())
+    }
+
+    async fn list_comments(&self, issue_id: &str) -> Result<Vec<Comment>> {
+        let actions: Vec<TrelloAction> = self
+            .get(&format!("/cards/{}/actions", issue_id), &[("filter", "commentCard".to_string())])
+            .await?;
+        let mut comments: Vec<Comment> = actions.into_iter().map(|a| to_comment(a, issue_id)).collect();
+        comments.sort_by_key(|c| c.created_at);
+        Ok(comments)
+    }
+
+    async fn create_comment(&self, issue_id: &str, body: &str) -> Result<Comment> {
+        let action: TrelloAction = self
+            .send(Method::POST, &format!("/cards/{}/actions/comments", issue_id), &[("text", body.to_string())])
+            .await?;
+        Ok(to_comment(action, issue_id))
+    }
+
+    async fn update_comment(&self, comment_id: &str, body: &str) -> Result<Comment> {
+        let action: TrelloAction = self
+            .send(Method::PUT, &format!("/actions/{}", comment_id), &[("text", body.to_string())])
+            .await?;
+        Ok(to_comment(action, ""))
+    }
+
+    async fn delete_comment(&self, comment_id: &str) -> Result<()> {
+        self.send::<Value>(Method::DELETE, &format!("/actions/{}", comment_id), &[]).await?;
+        Ok(())
+    }
+
+    async fn list_users(&self) -> Result<Vec<User>> {
+        let params = [
+            ("fields", "id".to_string()),
+            ("members", "all".to_string()),
+            ("member_fields", "fullName,username,avatarUrl".to_string()),
+        ];
+        let boards: Vec<TrelloBoardMembers> = self.get("/members/me/boards", &params).await?;
+        let mut users: HashMap<String, User> = HashMap::new();
+        for member in boards.into_iter().flat_map(|b| b.members) {
+            users.entry(member.id.clone()).or_insert_with(|| to_user(member));
+        }
+        let mut users: Vec<User> = users.into_values().collect();
+        users.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(users)
+    }
+
+    async fn get_current_user(&self) -> Result<User> {
+        let member: TrelloMember = self
+            .get("/members/me", &[("fields", "fullName,username,email,avatarUrl".to_string())])
+            .await?;
+        Ok(to_user(member))
+    }
+}
+
+#[async_trait]
+impl SprintProvider for TrelloClient {
+    async fn list_sprints(&self, _project_id: &str) -> Result<Vec<Sprint>> {
+        Err(sprints_unsupported())
+    }
+
+    async fn get_active_sprint(&self, _project_id: &str) -> Result<Option<Sprint>> {
+        Err(sprints_unsupported())
+    }
+
+    async fn create_sprint(&self, _project_id: &str, _name: &str, _goal: Option<&str>, _start: Option<NaiveDate>, _end: Option<NaiveDate>) -> Result<Sprint> {
+        Err(sprints_unsupported())
+    }
+
+    async fn start_sprint(&self, _sprint_id: &str) -> Result<Sprint> {
+        Err(sprints_unsupported())
+    }
+
+    async fn complete_sprint(&self, _sprint_id: &str) -> Result<Sprint> {
+        Err(sprints_unsupported())
+    }
+}
+
+fn sprints_unsupported() -> Error {
+    Error::Unsupported("Trello boards have no sprints; use lists to model iterations instead".to_string())
+}
+
+fn matches_filter(issue: &Issue, filter: &IssueFilter) -> bool {
+    let assigned = |id: &String| {
+        issue
+            .extra
+            .get("member_ids")
+            .and_then(Value::as_array)
+            .is_some_and(|members| members.iter().any(|m| m.as_str() == Some(id)))
+    };
+    filter.assignee_id.as_ref().is_none_or(assigned)
+        && filter.status.as_ref().is_none_or(|s| issue.status.eq_ignore_ascii_case(s))
+        && filter
+            .labels
+            .iter()
+            .all(|label| issue.labels.iter().any(|l| l.eq_ignore_ascii_case(label)))
+        && filter.search.as_ref().is_none_or(|query| {
+            let query = query.to_lowercase();
+            issue.title.to_lowercase().contains(&query)
+                || issue.description.as_ref().is_some_and(|d| d.to_lowercase().contains(&query))
+        })
+}
+
+fn created_from_id(id: &str) -> Option<DateTime<Utc>> {
+    let seconds = i64::from_str_radix(id.get(..8)?, 16).ok()?;
+    DateTime::from_timestamp(seconds, 0)
+}
+
+fn to_project(board: TrelloBoard) -> Project {
+    Project {
+        created_at: created_from_id(&board.id),
+        id: board.id,
+        name: board.name,
+        key: None,
+        description: board.desc.filter(|d| !d.is_empty()),
+        url: board.url,
+        lead_id: None,
+        status: Some(if board.closed { ProjectStatus::Archived } else { ProjectStatus::Active }),
+        updated_at: board.date_last_activity,
+        extra: HashMap::new(),
+    }
+}
+
+fn to_issue(card: TrelloCard, lists: &HashMap<String, String>) -> Issue {
+    let mut extra = HashMap::new();
+    extra.insert("member_ids".to_string(), json!(card.id_members));
+    extra.insert("list_id".to_string(), json!(card.id_list));
+    extra.insert("closed".to_string(), json!(card.closed));
+    if let Some(url) = &card.short_url {
+        extra.insert("url".to_string(), json!(url));
+    }
+
+    Issue {
+        created_at: created_from_id(&card.id).unwrap_or_default(),
+        key: card.id_short.map(|n| n.to_string()),
+        title: card.name,
+        description: card.desc.filter(|d| !d.is_empty()),
+        issue_type: IssueType::Task,
+        status: lists.get(&card.id_list).cloned().unwrap_or_default(),
+        priority: None,
+        assignee_id: card.id_members.first().cloned(),
+        reporter_id: None,
+        project_id: card.id_board,
+        parent_id: None,
+        labels: card.labels.into_iter().map(|l| l.name).filter(|n| !n.is_empty()).collect(),
+        estimate: None,
+        due_date: card.due.map(|d| d.date_naive()),
+        updated_at: card.date_last_activity,
+        completed_at: None,
+        extra,
+        id: card.id,
+    }
+}
+
+fn to_label(label: TrelloLabel) -> Label {
+    Label {
+        id: label.id,
+        name: label.name,
+        color: label.color,
+        description: None,
+    }
+}
+
+fn to_comment(action: TrelloAction, issue_id: &str) -> Comment {
+    Comment {
+        id: action.id,
+        body: action.data.text.unwrap_or_default(),
+        author_id: action.id_member_creator.unwrap_or_default(),
+        issue_id: action.data.card.map(|c| c.id).unwrap_or_else(|| issue_id.to_string()),
+        created_at: action.date.unwrap_or_default(),
+        updated_at: action.data.date_last_edited,
+    }
+}
+
+fn to_user(member: TrelloMember) -> User {
+    User {
+        name: member.full_name.or(member.username).unwrap_or_default(),
+        email: member.email,
+        avatar_url: member.avatar_url.map(|url| format!("{}/50.png", url)),
+        id: member.id,
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct TrelloErrorResponse {
+    message: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TrelloBoard {
+    id: String,
+    name: String,
+    desc: Option<String>,
+    url: Option<String>,
+    #[serde(default)]
+    closed: bool,
+    date_last_activity: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TrelloBoardMembers {
+    #[serde(default)]
+    members: Vec<TrelloMember>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TrelloList {
+    id: String,
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TrelloCard {
+    id: String,
+    id_short: Option<u64>,
+    #[serde(default)]
+    name: String,
+    desc: Option<String>,
+    #[serde(default)]
+    id_list: String,
+    #[serde(default)]
+    id_board: String,
+    #[serde(default)]
+    id_members: Vec<String>,
+    #[serde(default)]
+    labels: Vec<TrelloLabel>,
+    due: Option<DateTime<Utc>>,
+    date_last_activity: Option<DateTime<Utc>>,
+    short_url: Option<String>,
+    #[serde(default)]
+    closed: bool,
+    list: Option<TrelloList>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TrelloLabel {
+    id: String,
+    #[serde(default)]
+    name: String,
+    color: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TrelloAction {
+    id: String,
+    id_member_creator: Option<String>,
+    date: Option<DateTime<Utc>>,
+    data: TrelloActionData,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TrelloActionData {
+    text: Option<String>,
+    card: Option<TrelloCardRef>,
+    date_last_edited: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TrelloCardRef {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TrelloSearchResponse {
+    #[serde(default)]
+    cards: Vec<TrelloCard>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TrelloMember {
+    id: String,
+    full_name: Option<String>,
+    username: Option<String>,
+    email: Option<String>,
+    avatar_url: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_card_maps_to_issue_with_list_status() {
+        let card: TrelloCard = serde_json::from_value(json!({
+            "id": "65a0f1c2d3e4f5a6b7c8d9e0",
+            "idShort": 42,
+            "name": "Ship it",
+            "desc": "",
+            "idList": "list-2",
+            "idBoard": "board-1",
+            "idMembers": ["m1", "m2"],
+            "labels": [{ "id": "l1", "name": "release", "color": "green" }],
+            "due": "2024-03-05T12:00:00.000Z"
+        }))
+        .unwrap();
+        let lists = HashMap::from([("list-2".to_string(), "Doing".to_string())]);
+
+        let issue = to_issue(card, &lists);
+        assert_eq!(issue.status, "Doing");
+        assert_eq!(issue.key.as_deref(), Some("42"));
+        assert_eq!(issue.project_id, "board-1");
+        assert_eq!(issue.assignee_id.as_deref(), Some("m1"));
+        assert_eq!(issue.due_date, NaiveDate::from_ymd_opt(2024, 3, 5));
+        assert_eq!(issue.created_at.timestamp(), 0x65a0f1c2);
+
+        let filter = IssueFilter {
+            assignee_id: Some("m2".into()),
+            labels: vec!["Release".into()],
+            ..Default::default()
+        };
+        assert!(matches_filter(&issue, &filter));
+        assert!(!matches_filter(&issue, &IssueFilter { status: Some("Done".into()), ..Default::default() }));
+    }
+}
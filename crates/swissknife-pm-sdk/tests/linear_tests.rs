@@ -0,0 +1,123 @@
+#![cfg(feature = "linear")]
+
+use serde_json::json;
+use swissknife_pm_sdk::linear::LinearClient;
+use swissknife_pm_sdk::{ProjectManagementProvider, StateType, UpdateIssue};
+use wiremock::matchers::{body_partial_json, body_string_contains, method};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn page(path: &[&str], nodes: serde_json::Value, next: Option<&str>) -> ResponseTemplate {
+    let mut connection = json!({
+        "nodes": nodes,
+        "pageInfo": { "hasNextPage": next.is_some(), "endCursor": next },
+    });
+    for key in path.iter().rev() {
+        connection = json!({ *key: connection });
+    }
+    ResponseTemplate::new(200).set_body_json(json!({ "data": connection }))
+}
+
+async fn mount_pages(server: &MockServer, marker: &str, path: &[&str], first: serde_json::Value, second: serde_json::Value) {
+    Mock::given(method("POST"))
+        .and(body_string_contains(marker))
+        .and(body_partial_json(json!({ "variables": { "after": null } })))
+        .respond_with(page(path, first, Some("cursor-1")))
+        .mount(server)
+        .await;
+    Mock::given(method("POST"))
+        .and(body_string_contains(marker))
+        .and(body_partial_json(json!({ "variables": { "after": "cursor-1" } })))
+        .respond_with(page(path, second, None))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_list_labels_follows_page_info() {
+    let server = MockServer::start().await;
+    mount_pages(
+        &server,
+        "labels(first: $first",
+        &["team", "labels"],
+        json!([{ "id": "l1", "name": "bug" }]),
+        json!([{ "id": "l2", "name": "backend" }]),
+    )
+    .await;
+
+    let client = LinearClient::new("lin_key").with_api_url(server.uri());
+    let labels = client.list_labels("team-1").await.unwrap();
+
+    let names: Vec<&str> = labels.iter().map(|l| l.name.as_str()).collect();
+    assert_eq!(names, vec!["bug", "backend"]);
+}
+
+#[tokio::test]
+async fn test_list_comments_follows_page_info() {
+    let server = MockServer::start().await;
+    mount_pages(
+        &server,
+        "comments(first: $first",
+        &["issue", "comments"],
+        json!([{ "id": "c2", "body": "second", "createdAt": "2024-04-02T00:00:00.000Z" }]),
+        json!([{ "id": "c1", "body": "first", "createdAt": "2024-04-01T00:00:00.000Z" }]),
+    )
+    .await;
+
+    let client = LinearClient::new("lin_key").with_api_url(server.uri());
+    let comments = client.list_comments("issue-1").await.unwrap();
+
+    let ids: Vec<&str> = comments.iter().map(|c| c.id.as_str()).collect();
+    assert_eq!(ids, vec!["c1", "c2"]);
+}
+
+#[tokio::test]
+async fn test_workflow_states_follow_page_info() {
+    let server = MockServer::start().await;
+    mount_pages(
+        &server,
+        "states(first: $first",
+        &["team", "states"],
+        json!([{ "id": "s2", "name": "Done", "type": "completed", "position": 2.0 }]),
+        json!([{ "id": "s1", "name": "Todo", "type": "unstarted", "position": 1.0 }]),
+    )
+    .await;
+    Mock::given(method("POST"))
+        .and(body_string_contains("issue(id: $id) { team { id } }"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": { "issue": { "team": { "id": "team-1" } } }
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(body_string_contains("issueUpdate"))
+        .and(body_partial_json(json!({ "variables": { "input": { "stateId": "s1" } } })))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "data": {
+                "issueUpdate": {
+                    "success": true,
+                    "issue": {
+                        "id": "issue-1",
+                        "identifier": "ENG-1",
+                        "title": "Fix login",
+                        "createdAt": "2024-04-01T00:00:00.000Z",
+                        "state": { "name": "Todo", "type": "unstarted" },
+                    }
+                }
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = LinearClient::new("lin_key").with_api_url(server.uri());
+    let states = client.list_workflow_states("team-1").await.unwrap();
+    assert_eq!(states.iter().map(|s| s.id.as_str()).collect::<Vec<_>>(), vec!["s1", "s2"]);
+    assert_eq!(states[1].state_type, StateType::Completed);
+
+    let update = UpdateIssue {
+        status: Some("todo".to_string()),
+        ..Default::default()
+    };
+    let issue = client.update_issue("issue-1", &update).await.unwrap();
+    assert_eq!(issue.status, "Todo");
+}
@@ -0,0 +1,600 @@
+use crate::filter::{parse_filter, FilterExpr, FilterOp};
+use crate::{
+    Collection, DeleteOptions, DistanceMetric, Error, QueryOptions, QueryResult, Result, UpsertOptions, UpsertResult,
+    Vector, VectorDatabaseProvider,
+};
+use async_trait::async_trait;
+use reqwest::{Method, RequestBuilder};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+const DEFAULT_URL: &str = "http://localhost:8000";
+const DEFAULT_TENANT: &str = "default_tenant";
+const DEFAULT_DATABASE: &str = "default_database";
+const UPSERT_BATCH_SIZE: usize = 500;
+const SPACE_KEY: &str = "hnsw:space";
+const DIMENSION_KEY: &str = "swissknife:dimension";
+const PARENT_KEY: &str = "swissknife:parent";
+const NAMESPACE_SEPARATOR: &str = "--";
+
+type Metadata = HashMap<String, Value>;
+
+#[derive(Clone)]
+struct CachedCollection {
+    id: String,
+    metric: DistanceMetric,
+}
+
+#[derive(Clone)]
+pub struct ChromaClient {
+    base_url: String,
+    tenant: String,
+    database: String,
+    api_key: Option<String>,
+    collections: Arc<Mutex<HashMap<String, CachedCollection>>>,
+    http: reqwest::Client,
+}
+
+impl ChromaClient {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            tenant: DEFAULT_TENANT.to_string(),
+            database: DEFAULT_DATABASE.to_string(),
+            api_key: None,
+            collections: Arc::new(Mutex::new(HashMap::new())),
+            http: reqwest::Client::new(),
+        }
+    }
+
+    pub fn local() -> Self {
+        Self::new(DEFAULT_URL)
+    }
+
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    pub fn with_database(mut self, tenant: impl Into<String>, database: impl Into<String>) -> Self {
+        self.tenant = tenant.into();
+        self.database = database.into();
+        self
+    }
+
+    pub async fn add(
+        &self,
+        collection: &str,
+        ids: &[String],
+        embeddings: &[Vec<f32>],
+        documents: Option<&[String]>,
+        metadatas: Option<&[Value]>,
+    ) -> Result<()> {
+        let target = self.cached(collection).await?;
+        let mut body = json!({ "ids": ids, "embeddings": embeddings });
+        if let Some(documents) = documents {
+            body["documents"] = json!(documents);
+        }
+        if let Some(metadatas) = metadatas {
+            body["metadatas"] = json!(metadatas);
+        }
+
+        let _: Value = self
+            .send(Method::POST, &format!("{}/{}/add", self.collections_path(), target.id), Some(body), collection)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn query_embeddings(
+        &self,
+        collection: &str,
+        embeddings: &[Vec<f32>],
+        n_results: u32,
+    ) -> Result<Vec<Vec<QueryResult>>> {
+        let options = QueryOptions {
+            top_k: n_results,
+            include_metadata: true,
+            ..Default::default()
+        };
+        let target = self.cached(collection).await?;
+        self.query_collection(&target, collection, embeddings, &options).await
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let request = self.http.request(method, format!("{}{}", self.base_url, path));
+        match &self.api_key {
+            Some(key) => request.header("x-chroma-token", key),
+            None => request,
+        }
+    }
+
+    async fn send<T: for<'de> Deserialize<'de>>(&self, method: Method, path: &str, body: Option<Value>, collection: &str) -> Result<T> {
+        let mut request = self.request(method, path);
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+
+        let response = request.send().await?;
+        let status = response.status().as_u16();
+
+        if !response.status().is_success() {
+            let body = response.text().await.unwrap_or_default();
+            let message = serde_json::from_str::<ChromaError>(&body)
+                .ok()
+                .and_then(|e| e.message.or(e.error))
+                .unwrap_or(body);
+            return Err(match status {
+                401 | 403 => Error::Auth(message),
+                404 => Error::IndexNotFound(collection.to_string()),
+                429 => Error::RateLimited,
+                _ if message.contains("does not exist") => Error::IndexNotFound(collection.to_string()),
+                _ => Error::Api {
+                    message,
+                    code: Some(status.to_string()),
+                },
+            });
+        }
+
+        let text = response.text().await?;
+        Ok(serde_json::from_str(if text.trim().is_empty() { "null" } else { &text })?)
+    }
+
+    fn collections_path(&self) -> String {
+        format!("/api/v2/tenants/{}/databases/{}/collections", self.tenant, self.database)
+    }
+
+    async fn get_collection(&self, name: &str) -> Result<ChromaCollection> {
+        let collection: ChromaCollection = self
+            .send(Method::GET, &format!("{}/{}", self.collections_path(), name), None, name)
+            .await?;
+        self.collections.lock().unwrap().insert(name.to_string(), collection.cached());
+        Ok(collection)
+    }
+
+    async fn cached(&self, name: &str) -> Result<CachedCollection> {
+        if let Some(cached) = self.collections.lock().unwrap().get(name) {
+            return Ok(cached.clone());
+        }
+        Ok(self.get_collection(name).await?.cached())
+    }
+
+    async fn list_all(&self) -> Result<Vec<ChromaCollection>> {
+        self.send(Method::GET, &self.collections_path(), None, "").await
+    }
+
+    async fn namespaces(&self, collection: &str) -> Result<Vec<ChromaCollection>> {
+        Ok(self
+            .list_all()
+            .await?
+            .into_iter()
+            .filter(|c| c.parent() == Some(collection))
+            .collect())
+    }
+
+    async fn namespace_collection(&self, collection: &str, namespace: Option<&str>, create: bool) -> Result<Option<CachedCollection>> {
+        let Some(namespace) = namespace.filter(|ns| !ns.is_empty()) else {
+            return self.cached(collection).await.map(Some);
+        };
+
+        let name = namespace_name(collection, namespace);
+        if let Some(cached) = self.collections.lock().unwrap().get(&name) {
+            return Ok(Some(cached.clone()));
+        }
+        if !create {
+            return match self.get_collection(&name).await {
+                Ok(found) => Ok(Some(found.cached())),
+                Err(Error::IndexNotFound(_)) => Ok(None),
+                Err(e) => Err(e),
+            };
+        }
+
+        let parent = self.get_collection(collection).await?;
+        let mut metadata = parent.metadata.unwrap_or_default();
+        metadata.insert(PARENT_KEY.to_string(), json!(collection));
+        let body = json!({ "name": name, "metadata": metadata, "get_or_create": true });
+        let created: ChromaCollection = self
+            .send(Method::POST, &self.collections_path(), Some(body), collection)
+            .await?;
+        let cached = created.cached();
+        self.collections.lock().unwrap().insert(name, cached.clone());
+        Ok(Some(cached))
+    }
+
+    async fn count(&self, id: &str, collection: &str) -> Result<u64> {
+        self.send(Method::GET, &format!("{}/{}/count", self.collections_path(), id), None, collection)
+            .await
+    }
+
+    async fn get_records(&self, id: &str, collection: &str, body: Value) -> Result<GetResponse> {
+        self.send(Method::POST, &format!("{}/{}/get", self.collections_path(), id), Some(body), collection)
+            .await
+    }
+
+    async fn query_collection(
+        &self,
+        target: &CachedCollection,
+        collection: &str,
+        embeddings: &[Vec<f32>],
+        options: &QueryOptions,
+    ) -> Result<Vec<Vec<QueryResult>>> {
+        let mut include = vec!["metadatas", "distances"];
+        if options.include_values {
+            include.push("embeddings");
+        }
+        let mut body = json!({
+            "query_embeddings": embeddings,
+            "n_results": options.top_k.max(1),
+            "include": include,
+        });
+        if let Some(filter) = where_clause(options.filter.as_ref())? {
+            body["where"] = filter;
+        }
+
+        let response: QueryResponse = self
+            .send(Method::POST, &format!("{}/{}/query", self.collections_path(), target.id), Some(body), collection)
+            .await?;
+
+        let mut distances = response.distances.unwrap_or_default().into_iter();
+        let mut metadatas = response.metadatas.unwrap_or_default().into_iter();
+        let mut values = response.embeddings.unwrap_or_default().into_iter();
+        Ok(response
+            .ids
+            .into_iter()
+            .map(|ids| {
+                let mut distances = distances.next().unwrap_or_default().into_iter();
+                let mut metadatas = metadatas.next().unwrap_or_default().into_iter();
+                let mut values = values.next().unwrap_or_default().into_iter();
+                ids.into_iter()
+                    .map(|id| QueryResult {
+                        id,
+                        score: score_from_distance(target.metric, distances.next().flatten().unwrap_or_default()),
+                        values: values.next().flatten().filter(|_| options.include_values),
+                        metadata: metadatas.next().flatten().filter(|_| options.include_metadata),
+                    })
+                    .collect()
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl VectorDatabaseProvider for ChromaClient {
+    async fn list_collections(&self) -> Result<Vec<Collection>> {
+        Ok(self
+            .list_all()
+            .await?
+            .into_iter()
+            .filter(|c| c.parent().is_none())
+            .map(|c| c.into_collection(None))
+            .collect())
+    }
+
+    async fn create_collection(&self, name: &str, dimension: u32, metric: DistanceMetric) -> Result<Collection> {
+        let body = json!({
+            "name": name,
+            "metadata": {
+                SPACE_KEY: space_name(metric),
+                DIMENSION_KEY: dimension,
+            },
+        });
+        let created: ChromaCollection = self
+            .send(Method::POST, &self.collections_path(), Some(body), name)
+            .await?;
+        self.collections.lock().unwrap().insert(name.to_string(), created.cached());
+        Ok(created.into_collection(Some(0)))
+    }
+
+    async fn delete_collection(&self, name: &str) -> Result<()> {
+        let mut names: Vec<String> = self.namespaces(name).await?.into_iter().map(|c| c.name).collect();
+        names.push(name.to_string());
+
+        for collection in names {
+            let _: Value = self
+                .send(Method::DELETE, &format!("{}/{}", self.collections_path(), collection), None, name)
+                .await?;
+            self.collections.lock().unwrap().remove(&collection);
+        }
+        Ok(())
+    }
+
+    async fn describe_collection(&self, name: &str) -> Result<Collection> {
+        let collection = self.get_collection(name).await?;
+        let mut vector_count = self.count(&collection.id, name).await?;
+        for namespace in self.namespaces(name).await? {
+            vector_count += self.count(&namespace.id, name).await?;
+        }
+        Ok(collection.into_collection(Some(vector_count)))
+    }
+
+    async fn upsert(&self, collection: &str, vectors: &[Vector], options: &UpsertOptions) -> Result<UpsertResult> {
+        for vector in vectors {
+            check_vector(vector)?;
+        }
+        let Some(target) = self
+            .namespace_collection(collection, options.namespace.as_deref(), true)
+            .await?
+        else {
+            return Err(Error::IndexNotFound(collection.to_string()));
+        };
+
+        let path = format!("{}/{}/upsert", self.collections_path(), target.id);
+        for batch in vectors.chunks(UPSERT_BATCH_SIZE) {
+            let body = json!({
+                "ids": batch.iter().map(|v| v.id.as_str()).collect::<Vec<_>>(),
+                "embeddings": batch.iter().map(|v| &v.values).collect::<Vec<_>>(),
+                "metadatas": batch.iter().map(|v| v.metadata.as_ref().filter(|m| !m.is_empty())).collect::<Vec<_>>(),
+            });
+            let _: Value = self.send(Method::POST, &path, Some(body), collection).await?;
+        }
+
+        Ok(UpsertResult {
+            upserted_count: vectors.len() as u64,
+        })
+    }
+
+    async fn query(&self, collection: &str, vector: &[f32], options: &QueryOptions) -> Result<Vec<QueryResult>> {
+        let Some(target) = self
+            .namespace_collection(collection, options.namespace.as_deref(), false)
+            .await?
+        else {
+            return Ok(Vec::new());
+        };
+        let mut results = self
+            .query_collection(&target, collection, &[vector.to_vec()], options)
+            .await?;
+        Ok(results.pop().unwrap_or_default())
+    }
+
+    async fn fetch(&self, collection: &str, ids: &[&str], namespace: Option<&str>) -> Result<Vec<Vector>> {
+        let Some(target) = self.namespace_collection(collection, namespace, false).await? else {
+            return Ok(Vec::new());
+        };
+        let response = self
+            .get_records(&target.id, collection, json!({ "ids": ids, "include": ["embeddings", "metadatas"] }))
+            .await?;
+
+        let mut fetched = response.into_vectors();
+        Ok(ids.iter().filter_map(|id| fetched.remove(*id)).collect())
+    }
+
+    async fn delete(&self, collection: &str, ids: &[&str], options: &DeleteOptions) -> Result<()> {
+        let namespace = options.namespace.as_deref().filter(|ns| !ns.is_empty());
+        let Some(target) = self.namespace_collection(collection, namespace, false).await? else {
+            return Ok(());
+        };
+
+        let body = if options.delete_all {
+            if let Some(namespace) = namespace {
+                let name = namespace_name(collection, namespace);
+                let _: Value = self
+                    .send(Method::DELETE, &format!("{}/{}", self.collections_path(), name), None, collection)
+                    .await?;
+                self.collections.lock().unwrap().remove(&name);
+                return Ok(());
+            }
+            let all = self.get_records(&target.id, collection, json!({ "include": [] })).await?;
+            if all.ids.is_empty() {
+                return Ok(());
+            }
+            json!({ "ids": all.ids })
+        } else if let Some(filter) = where_clause(options.filter.as_ref())? {
+            json!({ "where": filter })
+        } else if ids.is_empty() {
+            return Ok(());
+        } else {
+            json!({ "ids": ids })
+        };
+
+        let _: Value = self
+            .send(Method::POST, &format!("{}/{}/delete", self.collections_path(), target.id), Some(body), collection)
+            .await?;
+        Ok(())
+    }
+
+    async fn update(
+        &self,
+        collection: &str,
+        id: &str,
+        values: Option<&[f32]>,
+        metadata: Option<HashMap<String, Value>>,
+    ) -> Result<()> {
+        if let Some(metadata) = &metadata {
+            check_metadata(metadata)?;
+        }
+
+        let mut targets = vec![self.cached(collection).await?];
+        targets.extend(self.namespaces(collection).await?.into_iter().map(|c| c.cached()));
+
+        for target in targets {
+            let found = self
+                .get_records(&target.id, collection, json!({ "ids": [id], "include": [] }))
+                .await?;
+            if found.ids.is_empty() {
+                continue;
+            }
+
+            let mut body = json!({ "ids": [id] });
+            if let Some(values) = values {
+                body["embeddings"] = json!([values]);
+            }
+            if let Some(metadata) = metadata {
+                body["metadatas"] = json!([metadata]);
+            }
+            let _: Value = self
+                .send(Method::POST, &format!("{}/{}/update", self.collections_path(), target.id), Some(body), collection)
+                .await?;
+            return Ok(());
+        }
+
+        Err(Error::Api {
+            message: format!("Vector {} not found in {}", id, collection),
+            code: Some("not_found".to_string()),
+        })
+    }
+}
+
+fn namespace_name(collection: &str, namespace: &str) -> String {
+    format!("{}{}{}", collection, NAMESPACE_SEPARATOR, namespace)
+}
+
+fn check_metadata(metadata: &HashMap<String, Value>) -> Result<()> {
+    match metadata
+        .iter()
+        .find(|(_, v)| !(v.is_string() || v.is_number() || v.is_boolean()))
+    {
+        Some((key, _)) => Err(Error::Unsupported(format!(
+            "Chroma metadata values must be strings, numbers or booleans ({})",
+            key
+        ))),
+        None => Ok(()),
+    }
+}
+
+fn check_vector(vector: &Vector) -> Result<()> {
+    if vector.sparse_values.is_some() {
+        return Err(Error::Unsupported("Chroma does not support sparse vectors".to_string()));
+    }
+    match &vector.metadata {
+        Some(metadata) => check_metadata(metadata),
+        None => Ok(()),
+    }
+}
+
+fn space_name(metric: DistanceMetric) -> &'static str {
+    match metric {
+        DistanceMetric::Cosine => "cosine",
+        DistanceMetric::Euclidean => "l2",
+        DistanceMetric::DotProduct => "ip",
+    }
+}
+
+fn parse_space(space: Option<&str>) -> DistanceMetric {
+    match space {
+        Some("cosine") => DistanceMetric::Cosine,
+        Some("ip") => DistanceMetric::DotProduct,
+        _ => DistanceMetric::Euclidean,
+    }
+}
+
+fn score_from_distance(metric: DistanceMetric, distance: f32) -> f32 {
+    match metric {
+        DistanceMetric::Cosine | DistanceMetric::DotProduct => 1.0 - distance,
+        DistanceMetric::Euclidean => distance.max(0.0).sqrt(),
+    }
+}
+
+fn where_clause(filter: Option<&HashMap<String, Value>>) -> Result<Option<Value>> {
+    let Some(filter) = filter else {
+        return Ok(None);
+    };
+    Ok(match parse_filter(filter)? {
+        FilterExpr::And(items) if items.is_empty() => None,
+        expr => Some(translate(&expr)),
+    })
+}
+
+fn translate(expr: &FilterExpr) -> Value {
+    match expr {
+        FilterExpr::And(items) | FilterExpr::Or(items) if items.len() == 1 => translate(&items[0]),
+        FilterExpr::And(items) => json!({ "$and": items.iter().map(translate).collect::<Vec<_>>() }),
+        FilterExpr::Or(items) => json!({ "$or": items.iter().map(translate).collect::<Vec<_>>() }),
+        FilterExpr::Field { key, op, value } => {
+            let op = match op {
+                FilterOp::Eq => "$eq",
+                FilterOp::Ne => "$ne",
+                FilterOp::Gt => "$gt",
+                FilterOp::Gte => "$gte",
+                FilterOp::Lt => "$lt",
+                FilterOp::Lte => "$lte",
+                FilterOp::In => "$in",
+                FilterOp::Nin => "$nin",
+            };
+            json!({ key: { op: value } })
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ChromaError {
+    error: Option<String>,
+    message: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ChromaCollection {
+    id: String,
+    name: String,
+    metadata: Option<HashMap<String, Value>>,
+    dimension: Option<u32>,
+}
+
+impl ChromaCollection {
+    fn metadata_value(&self, key: &str) -> Option<&Value> {
+        self.metadata.as_ref().and_then(|m| m.get(key))
+    }
+
+    fn metric(&self) -> DistanceMetric {
+        parse_space(self.metadata_value(SPACE_KEY).and_then(|v| v.as_str()))
+    }
+
+    fn parent(&self) -> Option<&str> {
+        self.metadata_value(PARENT_KEY).and_then(|v| v.as_str())
+    }
+
+    fn cached(&self) -> CachedCollection {
+        CachedCollection {
+            id: self.id.clone(),
+            metric: self.metric(),
+        }
+    }
+
+    fn into_collection(self, vector_count: Option<u64>) -> Collection {
+        Collection {
+            dimension: self
+                .dimension
+                .or_else(|| self.metadata_value(DIMENSION_KEY).and_then(|v| v.as_u64()).map(|d| d as u32))
+                .unwrap_or_default(),
+            metric: self.metric(),
+            name: self.name,
+            vector_count,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct GetResponse {
+    #[serde(default)]
+    ids: Vec<String>,
+    embeddings: Option<Vec<Option<Vec<f32>>>>,
+    metadatas: Option<Vec<Option<Metadata>>>,
+}
+
+impl GetResponse {
+    fn into_vectors(self) -> HashMap<String, Vector> {
+        let mut embeddings = self.embeddings.unwrap_or_default().into_iter();
+        let mut metadatas = self.metadatas.unwrap_or_default().into_iter();
+        self.ids
+            .into_iter()
+            .map(|id| {
+                let vector = Vector {
+                    id: id.clone(),
+                    values: embeddings.next().flatten().unwrap_or_default(),
+                    metadata: metadatas.next().flatten(),
+                    sparse_values: None,
+                };
+                (id, vector)
+            })
+            .collect()
+    }
+}
+
+#[derive(Deserialize)]
+struct QueryResponse {
+    #[serde(default)]
+    ids: Vec<Vec<String>>,
+    distances: Option<Vec<Vec<Option<f32>>>>,
+    metadatas: Option<Vec<Vec<Option<Metadata>>>>,
+    embeddings: Option<Vec<Vec<Option<Vec<f32>>>>>,
+}
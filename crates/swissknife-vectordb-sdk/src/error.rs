@@ -17,11 +17,20 @@ pub enum Error {
     #[error("Dimension mismatch: expected {expected}, got {actual}")]
     DimensionMismatch { expected: usize, actual: usize },
 
+    #[error("Invalid filter: {0}")]
+    InvalidFilter(String),
+
+    #[error("Unsupported: {0}")]
+    Unsupported(String),
+
     #[error("Rate limited")]
     RateLimited,
 
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
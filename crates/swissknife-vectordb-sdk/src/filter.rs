@@ -0,0 +1,152 @@
+use crate::{Error, Result};
+use serde_json::Value;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FilterOp {
+    Eq,
+    Ne,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    In,
+    Nin,
+}
+
+impl FilterOp {
+    fn parse(op: &str) -> Result<Self> {
+        match op {
+            "$eq" => Ok(Self::Eq),
+            "$ne" => Ok(Self::Ne),
+            "$gt" => Ok(Self::Gt),
+            "$gte" => Ok(Self::Gte),
+            "$lt" => Ok(Self::Lt),
+            "$lte" => Ok(Self::Lte),
+            "$in" => Ok(Self::In),
+            "$nin" => Ok(Self::Nin),
+            other => Err(Error::InvalidFilter(format!("Unknown operator {}", other))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum FilterExpr {
+    And(Vec<FilterExpr>),
+    Or(Vec<FilterExpr>),
+    Field { key: String, op: FilterOp, value: Value },
+}
+
+pub(crate) fn parse_filter(filter: &HashMap<String, Value>) -> Result<FilterExpr> {
+    let mut keys: Vec<&String> = filter.keys().collect();
+    keys.sort();
+
+    let mut clauses = Vec::new();
+    for key in keys {
+        clauses.extend(parse_entry(key, &filter[key])?);
+    }
+
+    Ok(match clauses.len() {
+        1 => clauses.remove(0),
+        _ => FilterExpr::And(clauses),
+    })
+}
+
+fn parse_object(object: &serde_json::Map<String, Value>) -> Result<FilterExpr> {
+    let map: HashMap<String, Value> = object.iter().map(|(k, v)| (k.clone(), v.clone())).collect();
+    parse_filter(&map)
+}
+
+fn parse_entry(key: &str, value: &Value) -> Result<Vec<FilterExpr>> {
+    match key {
+        "$and" | "$or" => {
+            let items = value
+                .as_array()
+                .ok_or_else(|| Error::InvalidFilter(format!("{} expects an array", key)))?;
+            let nested = items
+                .iter()
+                .map(|item| {
+                    item.as_object()
+                        .ok_or_else(|| Error::InvalidFilter(format!("{} entries must be objects", key)))
+                        .and_then(parse_object)
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(vec![if key == "$and" { FilterExpr::And(nested) } else { FilterExpr::Or(nested) }])
+        }
+        _ if key.starts_with('$') => Err(Error::InvalidFilter(format!("Unknown logical operator {}", key))),
+        _ => match value {
+            Value::Object(ops) if ops.keys().all(|k| k.starts_with('$')) && !ops.is_empty() => {
+                let mut ops: Vec<(&String, &Value)> = ops.iter().collect();
+                ops.sort_by(|a, b| a.0.cmp(b.0));
+                ops.into_iter()
+                    .map(|(op, operand)| {
+                        let op = FilterOp::parse(op)?;
+                        if matches!(op, FilterOp::In | FilterOp::Nin) && !operand.is_array() {
+                            return Err(Error::InvalidFilter(format!("Operator on {} expects an array", key)));
+                        }
+                        Ok(FilterExpr::Field {
+                            key: key.to_string(),
+                            op,
+                            value: operand.clone(),
+                        })
+                    })
+                    .collect()
+            }
+            _ => Ok(vec![FilterExpr::Field {
+                key: key.to_string(),
+                op: FilterOp::Eq,
+                value: value.clone(),
+            }]),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn filter(value: Value) -> HashMap<String, Value> {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_parse_bare_value_as_equality() {
+        let expr = parse_filter(&filter(json!({"genre": "drama"}))).unwrap();
+        assert_eq!(
+            expr,
+            FilterExpr::Field {
+                key: "genre".to_string(),
+                op: FilterOp::Eq,
+                value: json!("drama")
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_operators_and_logical_groups() {
+        let expr = parse_filter(&filter(json!({
+            "year": {"$gte": 2000, "$lt": 2010},
+            "$or": [{"genre": "drama"}, {"tags": {"$in": ["a", "b"]}}]
+        })))
+        .unwrap();
+
+        let FilterExpr::And(clauses) = expr else { panic!("expected And") };
+        assert_eq!(clauses.len(), 3);
+        assert!(matches!(&clauses[0], FilterExpr::Or(items) if items.len() == 2));
+        assert!(matches!(&clauses[1], FilterExpr::Field { op: FilterOp::Gte, .. }));
+        assert!(matches!(&clauses[2], FilterExpr::Field { op: FilterOp::Lt, .. }));
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_operator() {
+        assert!(matches!(
+            parse_filter(&filter(json!({"year": {"$between": [1, 2]}}))),
+            Err(Error::InvalidFilter(_))
+        ));
+        assert!(matches!(
+            parse_filter(&filter(json!({"tags": {"$in": "a"}}))),
+            Err(Error::InvalidFilter(_))
+        ));
+    }
+}
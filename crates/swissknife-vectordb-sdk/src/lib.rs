@@ -1,4 +1,13 @@
 mod error;
+#[cfg(any(
+    feature = "pinecone",
+    feature = "qdrant",
+    feature = "weaviate",
+    feature = "chroma",
+    feature = "milvus",
+    feature = "local"
+))]
+mod filter;
 
 pub use error::{Error, Result};
 
@@ -17,6 +26,9 @@ pub mod chroma;
 #[cfg(feature = "milvus")]
 pub mod milvus;
 
+#[cfg(feature = "local")]
+pub mod local;
+
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -0,0 +1,224 @@
+use crate::DistanceMetric;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashSet};
+
+#[derive(Debug, Clone, Copy)]
+pub struct HnswConfig {
+    pub m: usize,
+    pub ef_construction: usize,
+    pub ef_search: usize,
+}
+
+impl Default for HnswConfig {
+    fn default() -> Self {
+        Self {
+            m: 16,
+            ef_construction: 200,
+            ef_search: 64,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+struct Candidate {
+    distance: f32,
+    id: usize,
+}
+
+impl Eq for Candidate {}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance.total_cmp(&other.distance).then(self.id.cmp(&other.id))
+    }
+}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+pub(crate) fn distance(metric: DistanceMetric, a: &[f32], b: &[f32]) -> f32 {
+    match metric {
+        DistanceMetric::Cosine => 1.0 - cosine_similarity(a, b),
+        DistanceMetric::Euclidean => euclidean_distance(a, b),
+        DistanceMetric::DotProduct => -dot(a, b),
+    }
+}
+
+pub(crate) fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+pub(crate) fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let norm = dot(a, a).sqrt() * dot(b, b).sqrt();
+    if norm == 0.0 {
+        0.0
+    } else {
+        dot(a, b) / norm
+    }
+}
+
+pub(crate) fn euclidean_distance(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum::<f32>().sqrt()
+}
+
+pub(crate) struct Hnsw {
+    config: HnswConfig,
+    metric: DistanceMetric,
+    layers: Vec<Vec<Vec<usize>>>,
+    entry_point: Option<usize>,
+    max_level: usize,
+    level_multiplier: f64,
+    rng_state: u64,
+}
+
+impl Hnsw {
+    pub(crate) fn new(config: HnswConfig, metric: DistanceMetric) -> Self {
+        let m = config.m.max(2);
+        Self {
+            config: HnswConfig { m, ..config },
+            metric,
+            layers: Vec::new(),
+            entry_point: None,
+            max_level: 0,
+            level_multiplier: 1.0 / (m as f64).ln(),
+            rng_state: 0x9E37_79B9_7F4A_7C15,
+        }
+    }
+
+    fn random_level(&mut self) -> usize {
+        self.rng_state ^= self.rng_state << 13;
+        self.rng_state ^= self.rng_state >> 7;
+        self.rng_state ^= self.rng_state << 17;
+        let uniform = ((self.rng_state >> 11) as f64 + 1.0) / ((1u64 << 53) as f64 + 1.0);
+        (-uniform.ln() * self.level_multiplier).floor() as usize
+    }
+
+    fn max_connections(&self, layer: usize) -> usize {
+        if layer == 0 {
+            self.config.m * 2
+        } else {
+            self.config.m
+        }
+    }
+
+    pub(crate) fn insert<'a, F>(&mut self, id: usize, vector_of: F)
+    where
+        F: Fn(usize) -> &'a [f32],
+    {
+        let level = self.random_level();
+        if self.layers.len() <= id {
+            self.layers.resize(id + 1, Vec::new());
+        }
+        self.layers[id] = vec![Vec::new(); level + 1];
+
+        let Some(mut entry) = self.entry_point else {
+            self.entry_point = Some(id);
+            self.max_level = level;
+            return;
+        };
+
+        let query = vector_of(id);
+        for layer in (level + 1..=self.max_level).rev() {
+            entry = self.search_layer(query, &[entry], 1, layer, &vector_of)[0].id;
+        }
+
+        for layer in (0..=level.min(self.max_level)).rev() {
+            let candidates = self.search_layer(query, &[entry], self.config.ef_construction, layer, &vector_of);
+            let max_connections = self.max_connections(layer);
+            let neighbors: Vec<usize> = candidates.iter().take(self.config.m).map(|c| c.id).collect();
+            self.layers[id][layer] = neighbors.clone();
+
+            for neighbor in neighbors {
+                let links = &mut self.layers[neighbor][layer];
+                links.push(id);
+                if links.len() > max_connections {
+                    let base = vector_of(neighbor);
+                    let mut scored: Vec<Candidate> = links
+                        .iter()
+                        .map(|&n| Candidate {
+                            distance: distance(self.metric, base, vector_of(n)),
+                            id: n,
+                        })
+                        .collect();
+                    scored.sort();
+                    *links = scored.into_iter().take(max_connections).map(|c| c.id).collect();
+                }
+            }
+            entry = candidates[0].id;
+        }
+
+        if level > self.max_level {
+            self.max_level = level;
+            self.entry_point = Some(id);
+        }
+    }
+
+    pub(crate) fn search<'a, F>(&self, query: &[f32], k: usize, ef: usize, vector_of: F) -> Vec<(usize, f32)>
+    where
+        F: Fn(usize) -> &'a [f32],
+    {
+        let Some(mut entry) = self.entry_point else {
+            return Vec::new();
+        };
+
+        for layer in (1..=self.max_level).rev() {
+            entry = self.search_layer(query, &[entry], 1, layer, &vector_of)[0].id;
+        }
+
+        self.search_layer(query, &[entry], ef.max(k), 0, &vector_of)
+            .into_iter()
+            .map(|c| (c.id, c.distance))
+            .collect()
+    }
+
+    fn search_layer<'a, F>(&self, query: &[f32], entry_points: &[usize], ef: usize, layer: usize, vector_of: &F) -> Vec<Candidate>
+    where
+        F: Fn(usize) -> &'a [f32],
+    {
+        let mut visited: HashSet<usize> = entry_points.iter().copied().collect();
+        let mut candidates: BinaryHeap<Reverse<Candidate>> = BinaryHeap::new();
+        let mut nearest: BinaryHeap<Candidate> = BinaryHeap::new();
+
+        for &id in entry_points {
+            let candidate = Candidate {
+                distance: distance(self.metric, query, vector_of(id)),
+                id,
+            };
+            candidates.push(Reverse(candidate));
+            nearest.push(candidate);
+        }
+
+        while let Some(Reverse(current)) = candidates.pop() {
+            let furthest = nearest.peek().map(|c| c.distance).unwrap_or(f32::INFINITY);
+            if current.distance > furthest && nearest.len() >= ef {
+                break;
+            }
+
+            let Some(links) = self.layers[current.id].get(layer) else {
+                continue;
+            };
+            for &neighbor in links {
+                if !visited.insert(neighbor) {
+                    continue;
+                }
+                let candidate = Candidate {
+                    distance: distance(self.metric, query, vector_of(neighbor)),
+                    id: neighbor,
+                };
+                let furthest = nearest.peek().map(|c| c.distance).unwrap_or(f32::INFINITY);
+                if nearest.len() < ef || candidate.distance < furthest {
+                    candidates.push(Reverse(candidate));
+                    nearest.push(candidate);
+                    if nearest.len() > ef {
+                        nearest.pop();
+                    }
+                }
+            }
+        }
+
+        nearest.into_sorted_vec()
+    }
+}
@@ -0,0 +1,659 @@
+mod hnsw;
+mod persistence;
+
+pub use hnsw::HnswConfig;
+
+use crate::filter::{parse_filter, FilterExpr, FilterOp};
+use crate::{
+    Collection, DeleteOptions, DistanceMetric, Error, HybridSearchProvider, QueryOptions, QueryResult, Result,
+    SparseVector, UpsertOptions, UpsertResult, Vector, VectorDatabaseProvider,
+};
+use async_trait::async_trait;
+use hnsw::{distance, Hnsw};
+use persistence::{CollectionSnapshot, LogEntry, Snapshot, Storage};
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+
+const DEFAULT_SNAPSHOT_INTERVAL: usize = 10_000;
+const COMPACTION_MIN_DELETED: usize = 1_000;
+
+struct Entry {
+    vector: Vector,
+    deleted: bool,
+}
+
+#[derive(Default)]
+struct Namespace {
+    entries: Vec<Entry>,
+    ids: HashMap<String, usize>,
+    deleted: usize,
+    index: Option<Hnsw>,
+}
+
+impl Namespace {
+    fn live(&self) -> impl Iterator<Item = (usize, &Vector)> {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| !e.deleted)
+            .map(|(slot, e)| (slot, &e.vector))
+    }
+
+    fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    fn get(&self, id: &str) -> Option<&Vector> {
+        self.ids.get(id).map(|&slot| &self.entries[slot].vector)
+    }
+
+    fn upsert(&mut self, vector: Vector) {
+        if let Some(&slot) = self.ids.get(&vector.id) {
+            if self.index.is_none() {
+                self.entries[slot].vector = vector;
+                return;
+            }
+            self.entries[slot].deleted = true;
+            self.deleted += 1;
+        }
+
+        let slot = self.entries.len();
+        self.ids.insert(vector.id.clone(), slot);
+        self.entries.push(Entry { vector, deleted: false });
+
+        let entries = &self.entries;
+        if let Some(index) = self.index.as_mut() {
+            index.insert(slot, |i| entries[i].vector.values.as_slice());
+        }
+    }
+
+    fn remove(&mut self, id: &str) -> bool {
+        let Some(slot) = self.ids.remove(id) else {
+            return false;
+        };
+        if self.index.is_none() {
+            self.entries.swap_remove(slot);
+            if let Some(moved) = self.entries.get(slot) {
+                self.ids.insert(moved.vector.id.clone(), slot);
+            }
+        } else {
+            self.entries[slot].deleted = true;
+            self.deleted += 1;
+        }
+        true
+    }
+
+    fn rebuild(&mut self, config: Option<HnswConfig>, metric: DistanceMetric) {
+        let vectors: Vec<Vector> = std::mem::take(&mut self.entries)
+            .into_iter()
+            .filter(|e| !e.deleted)
+            .map(|e| e.vector)
+            .collect();
+        self.ids.clear();
+        self.deleted = 0;
+        self.index = config.map(|c| Hnsw::new(c, metric));
+        for vector in vectors {
+            self.upsert(vector);
+        }
+    }
+
+    fn compact_if_needed(&mut self, config: Option<HnswConfig>, metric: DistanceMetric) {
+        if self.deleted >= COMPACTION_MIN_DELETED && self.deleted > self.len() {
+            self.rebuild(config, metric);
+        }
+    }
+
+    fn nearest(&self, query: &[f32], metric: DistanceMetric, k: usize, ef_search: usize, filter: Option<&FilterExpr>) -> Vec<(usize, f32)> {
+        let accept = |slot: usize| {
+            let entry = &self.entries[slot];
+            !entry.deleted && filter.is_none_or(|f| matches(f, entry.vector.metadata.as_ref()))
+        };
+
+        if let Some(index) = &self.index {
+            let ef = if filter.is_some() { ef_search.max(k * 4) } else { ef_search.max(k) };
+            let found: Vec<(usize, f32)> = index
+                .search(query, k, ef, |i| self.entries[i].vector.values.as_slice())
+                .into_iter()
+                .filter(|(slot, _)| accept(*slot))
+                .take(k)
+                .collect();
+            if found.len() >= k.min(self.len()) {
+                return found;
+            }
+        }
+
+        let mut scored: Vec<(usize, f32)> = (0..self.entries.len())
+            .filter(|&slot| accept(slot))
+            .map(|slot| (slot, distance(metric, query, &self.entries[slot].vector.values)))
+            .collect();
+        scored.sort_by(|a, b| a.1.total_cmp(&b.1));
+        scored.truncate(k);
+        scored
+    }
+}
+
+struct LocalCollection {
+    dimension: u32,
+    metric: DistanceMetric,
+    namespaces: HashMap<String, Namespace>,
+}
+
+impl LocalCollection {
+    fn vector_count(&self) -> u64 {
+        self.namespaces.values().map(|ns| ns.len() as u64).sum()
+    }
+
+    fn describe(&self, name: &str) -> Collection {
+        Collection {
+            name: name.to_string(),
+            dimension: self.dimension,
+            metric: self.metric,
+            vector_count: Some(self.vector_count()),
+        }
+    }
+
+    fn check_dimension(&self, values: &[f32]) -> Result<()> {
+        if values.len() != self.dimension as usize {
+            return Err(Error::DimensionMismatch {
+                expected: self.dimension as usize,
+                actual: values.len(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct State {
+    collections: HashMap<String, LocalCollection>,
+    storage: Option<Storage>,
+}
+
+impl State {
+    fn collection(&self, name: &str) -> Result<&LocalCollection> {
+        self.collections
+            .get(name)
+            .ok_or_else(|| Error::IndexNotFound(name.to_string()))
+    }
+
+    fn apply(&mut self, entry: LogEntry, hnsw: Option<HnswConfig>) {
+        match entry {
+            LogEntry::CreateCollection { name, dimension, metric } => {
+                self.collections.entry(name).or_insert(LocalCollection {
+                    dimension,
+                    metric,
+                    namespaces: HashMap::new(),
+                });
+            }
+            LogEntry::DeleteCollection { name } => {
+                self.collections.remove(&name);
+            }
+            LogEntry::Upsert { collection, namespace, vectors } => {
+                if let Some(collection) = self.collections.get_mut(&collection) {
+                    let metric = collection.metric;
+                    let ns = collection.namespaces.entry(namespace).or_insert_with(|| Namespace {
+                        index: hnsw.map(|c| Hnsw::new(c, metric)),
+                        ..Default::default()
+                    });
+                    for vector in vectors {
+                        ns.upsert(vector);
+                    }
+                    ns.compact_if_needed(hnsw, metric);
+                }
+            }
+            LogEntry::Delete { collection, namespace, ids } => {
+                if let Some(collection) = self.collections.get_mut(&collection) {
+                    let metric = collection.metric;
+                    if let Some(ns) = collection.namespaces.get_mut(&namespace) {
+                        for id in &ids {
+                            ns.remove(id);
+                        }
+                        ns.compact_if_needed(hnsw, metric);
+                        if ns.len() == 0 {
+                            collection.namespaces.remove(&namespace);
+                        }
+                    }
+                }
+            }
+            LogEntry::Update { collection, namespace, id, values, metadata } => {
+                let Some(collection) = self.collections.get_mut(&collection) else {
+                    return;
+                };
+                let metric = collection.metric;
+                let Some(ns) = collection.namespaces.get_mut(&namespace) else {
+                    return;
+                };
+                let Some(mut vector) = ns.get(&id).cloned() else {
+                    return;
+                };
+                if let Some(values) = values {
+                    vector.values = values;
+                }
+                if let Some(metadata) = metadata {
+                    vector.metadata.get_or_insert_with(HashMap::new).extend(metadata);
+                }
+                ns.upsert(vector);
+                ns.compact_if_needed(hnsw, metric);
+            }
+        }
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        let mut collections: Vec<CollectionSnapshot> = self
+            .collections
+            .iter()
+            .map(|(name, collection)| CollectionSnapshot {
+                name: name.clone(),
+                dimension: collection.dimension,
+                metric: collection.metric,
+                namespaces: collection
+                    .namespaces
+                    .iter()
+                    .map(|(ns, namespace)| (ns.clone(), namespace.live().map(|(_, v)| v.clone()).collect()))
+                    .collect(),
+            })
+            .collect();
+        collections.sort_by(|a, b| a.name.cmp(&b.name));
+        Snapshot { collections }
+    }
+}
+
+#[derive(Clone)]
+pub struct LocalVectorStore {
+    state: Arc<RwLock<State>>,
+    hnsw: Option<HnswConfig>,
+    snapshot_interval: usize,
+}
+
+impl LocalVectorStore {
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(RwLock::new(State::default())),
+            hnsw: None,
+            snapshot_interval: DEFAULT_SNAPSHOT_INTERVAL,
+        }
+    }
+
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self> {
+        let (storage, snapshot, entries) = Storage::open(dir.as_ref())?;
+        let mut state = State::default();
+
+        for collection in snapshot.collections {
+            state.apply(
+                LogEntry::CreateCollection {
+                    name: collection.name.clone(),
+                    dimension: collection.dimension,
+                    metric: collection.metric,
+                },
+                None,
+            );
+            for (namespace, vectors) in collection.namespaces {
+                state.apply(
+                    LogEntry::Upsert {
+                        collection: collection.name.clone(),
+                        namespace,
+                        vectors,
+                    },
+                    None,
+                );
+            }
+        }
+        for entry in entries {
+            state.apply(entry, None);
+        }
+        state.storage = Some(storage);
+
+        Ok(Self {
+            state: Arc::new(RwLock::new(state)),
+            hnsw: None,
+            snapshot_interval: DEFAULT_SNAPSHOT_INTERVAL,
+        })
+    }
+
+    pub fn with_hnsw(mut self, config: HnswConfig) -> Self {
+        self.hnsw = Some(config);
+        self.rebuild_indexes();
+        self
+    }
+
+    pub fn with_snapshot_interval(mut self, entries: usize) -> Self {
+        self.snapshot_interval = entries.max(1);
+        self
+    }
+
+    pub fn snapshot(&self) -> Result<()> {
+        let mut state = self.state.write().unwrap();
+        let snapshot = state.snapshot();
+        match state.storage.as_mut() {
+            Some(storage) => storage.write_snapshot(&snapshot),
+            None => Ok(()),
+        }
+    }
+
+    fn rebuild_indexes(&self) {
+        let mut state = self.state.write().unwrap();
+        for collection in state.collections.values_mut() {
+            let metric = collection.metric;
+            for namespace in collection.namespaces.values_mut() {
+                namespace.rebuild(self.hnsw, metric);
+            }
+        }
+    }
+
+    fn commit(&self, state: &mut State, entry: LogEntry) -> Result<()> {
+        let pending = match state.storage.as_mut() {
+            Some(storage) => Some(storage.append(&entry)?),
+            None => None,
+        };
+        state.apply(entry, self.hnsw);
+
+        if pending.is_some_and(|p| p >= self.snapshot_interval) {
+            let snapshot = state.snapshot();
+            if let Some(storage) = state.storage.as_mut() {
+                storage.write_snapshot(&snapshot)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn ef_search(&self) -> usize {
+        self.hnsw.map(|c| c.ef_search).unwrap_or_default()
+    }
+}
+
+impl Default for LocalVectorStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl VectorDatabaseProvider for LocalVectorStore {
+    async fn list_collections(&self) -> Result<Vec<Collection>> {
+        let state = self.state.read().unwrap();
+        let mut collections: Vec<Collection> = state
+            .collections
+            .iter()
+            .map(|(name, collection)| collection.describe(name))
+            .collect();
+        collections.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(collections)
+    }
+
+    async fn create_collection(&self, name: &str, dimension: u32, metric: DistanceMetric) -> Result<Collection> {
+        let mut state = self.state.write().unwrap();
+        if state.collections.contains_key(name) {
+            return Err(Error::Api {
+                message: format!("Collection {} already exists", name),
+                code: Some("already_exists".to_string()),
+            });
+        }
+
+        self.commit(
+            &mut state,
+            LogEntry::CreateCollection {
+                name: name.to_string(),
+                dimension,
+                metric,
+            },
+        )?;
+        Ok(state.collection(name)?.describe(name))
+    }
+
+    async fn delete_collection(&self, name: &str) -> Result<()> {
+        let mut state = self.state.write().unwrap();
+        state.collection(name)?;
+        self.commit(&mut state, LogEntry::DeleteCollection { name: name.to_string() })
+    }
+
+    async fn describe_collection(&self, name: &str) -> Result<Collection> {
+        let state = self.state.read().unwrap();
+        Ok(state.collection(name)?.describe(name))
+    }
+
+    async fn upsert(&self, collection: &str, vectors: &[Vector], options: &UpsertOptions) -> Result<UpsertResult> {
+        let mut state = self.state.write().unwrap();
+        let target = state.collection(collection)?;
+        for vector in vectors {
+            target.check_dimension(&vector.values)?;
+        }
+
+        self.commit(
+            &mut state,
+            LogEntry::Upsert {
+                collection: collection.to_string(),
+                namespace: options.namespace.clone().unwrap_or_default(),
+                vectors: vectors.to_vec(),
+            },
+        )?;
+        Ok(UpsertResult {
+            upserted_count: vectors.len() as u64,
+        })
+    }
+
+    async fn query(&self, collection: &str, vector: &[f32], options: &QueryOptions) -> Result<Vec<QueryResult>> {
+        let filter = options.filter.as_ref().map(parse_filter).transpose()?;
+        let state = self.state.read().unwrap();
+        let target = state.collection(collection)?;
+        target.check_dimension(vector)?;
+
+        let Some(namespace) = target.namespaces.get(options.namespace.as_deref().unwrap_or_default()) else {
+            return Ok(Vec::new());
+        };
+
+        Ok(namespace
+            .nearest(vector, target.metric, options.top_k.max(1) as usize, self.ef_search(), filter.as_ref())
+            .into_iter()
+            .map(|(slot, distance)| {
+                to_result(&namespace.entries[slot].vector, score_from_distance(target.metric, distance), options)
+            })
+            .collect())
+    }
+
+    async fn fetch(&self, collection: &str, ids: &[&str], namespace: Option<&str>) -> Result<Vec<Vector>> {
+        let state = self.state.read().unwrap();
+        let target = state.collection(collection)?;
+        let Some(namespace) = target.namespaces.get(namespace.unwrap_or_default()) else {
+            return Ok(Vec::new());
+        };
+        Ok(ids.iter().filter_map(|id| namespace.get(id).cloned()).collect())
+    }
+
+    async fn delete(&self, collection: &str, ids: &[&str], options: &DeleteOptions) -> Result<()> {
+        let filter = options.filter.as_ref().map(parse_filter).transpose()?;
+        let mut state = self.state.write().unwrap();
+        let target = state.collection(collection)?;
+        let namespace_name = options.namespace.clone().unwrap_or_default();
+
+        let ids: Vec<String> = match target.namespaces.get(&namespace_name) {
+            None => Vec::new(),
+            Some(namespace) if options.delete_all || filter.is_some() => namespace
+                .live()
+                .filter(|(_, v)| filter.as_ref().is_none_or(|f| matches(f, v.metadata.as_ref())))
+                .map(|(_, v)| v.id.clone())
+                .collect(),
+            Some(namespace) => ids
+                .iter()
+                .filter(|id| namespace.get(id).is_some())
+                .map(|id| id.to_string())
+                .collect(),
+        };
+        if ids.is_empty() {
+            return Ok(());
+        }
+
+        self.commit(
+            &mut state,
+            LogEntry::Delete {
+                collection: collection.to_string(),
+                namespace: namespace_name,
+                ids,
+            },
+        )
+    }
+
+    async fn update(&self, collection: &str, id: &str, values: Option<&[f32]>, metadata: Option<HashMap<String, Value>>) -> Result<()> {
+        let mut state = self.state.write().unwrap();
+        let target = state.collection(collection)?;
+        if let Some(values) = values {
+            target.check_dimension(values)?;
+        }
+
+        let mut namespaces: Vec<&String> = target.namespaces.keys().collect();
+        namespaces.sort();
+        let namespace = namespaces
+            .into_iter()
+            .find(|ns| target.namespaces[*ns].get(id).is_some())
+            .cloned()
+            .ok_or_else(|| Error::Api {
+                message: format!("Vector {} not found in {}", id, collection),
+                code: Some("not_found".to_string()),
+            })?;
+
+        self.commit(
+            &mut state,
+            LogEntry::Update {
+                collection: collection.to_string(),
+                namespace,
+                id: id.to_string(),
+                values: values.map(|v| v.to_vec()),
+                metadata,
+            },
+        )
+    }
+}
+
+#[async_trait]
+impl HybridSearchProvider for LocalVectorStore {
+    async fn hybrid_query(
+        &self,
+        collection: &str,
+        dense_vector: &[f32],
+        sparse_vector: &SparseVector,
+        alpha: f32,
+        options: &QueryOptions,
+    ) -> Result<Vec<QueryResult>> {
+        let filter = options.filter.as_ref().map(parse_filter).transpose()?;
+        let alpha = alpha.clamp(0.0, 1.0);
+        let state = self.state.read().unwrap();
+        let target = state.collection(collection)?;
+        target.check_dimension(dense_vector)?;
+
+        let Some(namespace) = target.namespaces.get(options.namespace.as_deref().unwrap_or_default()) else {
+            return Ok(Vec::new());
+        };
+
+        let top_k = options.top_k.max(1) as usize;
+        let candidates: Vec<(usize, f32)> = match namespace.index {
+            Some(_) => namespace.nearest(dense_vector, target.metric, top_k * 4, self.ef_search(), filter.as_ref()),
+            None => namespace
+                .live()
+                .filter(|(_, v)| filter.as_ref().is_none_or(|f| matches(f, v.metadata.as_ref())))
+                .map(|(slot, v)| (slot, distance(target.metric, dense_vector, &v.values)))
+                .collect(),
+        };
+
+        let query_sparse: HashMap<u32, f32> = sparse_vector
+            .indices
+            .iter()
+            .copied()
+            .zip(sparse_vector.values.iter().copied())
+            .collect();
+
+        let mut scored: Vec<(usize, f32)> = candidates
+            .into_iter()
+            .map(|(slot, distance)| {
+                let sparse_score = namespace.entries[slot]
+                    .vector
+                    .sparse_values
+                    .as_ref()
+                    .map(|s| {
+                        s.indices
+                            .iter()
+                            .zip(&s.values)
+                            .filter_map(|(i, v)| query_sparse.get(i).map(|q| q * v))
+                            .sum::<f32>()
+                    })
+                    .unwrap_or_default();
+                (slot, alpha * score_from_distance(target.metric, distance) + (1.0 - alpha) * sparse_score)
+            })
+            .collect();
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scored.truncate(top_k);
+
+        Ok(scored
+            .into_iter()
+            .map(|(slot, score)| to_result(&namespace.entries[slot].vector, score, options))
+            .collect())
+    }
+}
+
+// Higher scores are always better, so Euclidean distances are negated.
+fn score_from_distance(metric: DistanceMetric, distance: f32) -> f32 {
+    match metric {
+        DistanceMetric::Cosine => 1.0 - distance,
+        DistanceMetric::Euclidean | DistanceMetric::DotProduct => -distance,
+    }
+}
+
+fn to_result(vector: &Vector, score: f32, options: &QueryOptions) -> QueryResult {
+    QueryResult {
+        id: vector.id.clone(),
+        score,
+        values: options.include_values.then(|| vector.values.clone()),
+        metadata: vector.metadata.clone().filter(|_| options.include_metadata),
+    }
+}
+
+fn matches(expr: &FilterExpr, metadata: Option<&HashMap<String, Value>>) -> bool {
+    match expr {
+        FilterExpr::And(items) => items.iter().all(|e| matches(e, metadata)),
+        FilterExpr::Or(items) => items.iter().any(|e| matches(e, metadata)),
+        FilterExpr::Field { key, op, value } => {
+            let field = metadata.and_then(|m| m.get(key));
+            let contains = |candidates: &Value| {
+                candidates
+                    .as_array()
+                    .is_some_and(|options| field.is_some_and(|f| options.iter().any(|o| values_equal(f, o))))
+            };
+            match op {
+                FilterOp::Eq => field.is_some_and(|f| values_equal(f, value)),
+                FilterOp::Ne => !field.is_some_and(|f| values_equal(f, value)),
+                FilterOp::In => contains(value),
+                FilterOp::Nin => !contains(value),
+                FilterOp::Gt | FilterOp::Gte | FilterOp::Lt | FilterOp::Lte => field
+                    .and_then(|f| compare(f, value))
+                    .is_some_and(|ordering| match op {
+                        FilterOp::Gt => ordering == Ordering::Greater,
+                        FilterOp::Gte => ordering != Ordering::Less,
+                        FilterOp::Lt => ordering == Ordering::Less,
+                        _ => ordering != Ordering::Greater,
+                    }),
+            }
+        }
+    }
+}
+
+fn values_equal(field: &Value, value: &Value) -> bool {
+    match field {
+        Value::Array(items) => items.iter().any(|item| scalar_equal(item, value)),
+        _ => scalar_equal(field, value),
+    }
+}
+
+fn scalar_equal(a: &Value, b: &Value) -> bool {
+    match (a.as_f64(), b.as_f64()) {
+        (Some(x), Some(y)) => x == y,
+        _ => a == b,
+    }
+}
+
+fn compare(a: &Value, b: &Value) -> Option<Ordering> {
+    match (a, b) {
+        (Value::String(x), Value::String(y)) => Some(x.cmp(y)),
+        _ => a.as_f64()?.partial_cmp(&b.as_f64()?),
+    }
+}
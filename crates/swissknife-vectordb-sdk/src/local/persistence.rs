@@ -0,0 +1,138 @@
+use crate::{DistanceMetric, Result, Vector};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufReader, BufWriter, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+const SNAPSHOT_FILE: &str = "snapshot.json";
+const LOG_FILE: &str = "wal.jsonl";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub(crate) enum LogEntry {
+    CreateCollection {
+        name: String,
+        dimension: u32,
+        metric: DistanceMetric,
+    },
+    DeleteCollection {
+        name: String,
+    },
+    Upsert {
+        collection: String,
+        namespace: String,
+        vectors: Vec<Vector>,
+    },
+    Delete {
+        collection: String,
+        namespace: String,
+        ids: Vec<String>,
+    },
+    Update {
+        collection: String,
+        namespace: String,
+        id: String,
+        values: Option<Vec<f32>>,
+        metadata: Option<HashMap<String, Value>>,
+    },
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct Snapshot {
+    pub collections: Vec<CollectionSnapshot>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct CollectionSnapshot {
+    pub name: String,
+    pub dimension: u32,
+    pub metric: DistanceMetric,
+    pub namespaces: HashMap<String, Vec<Vector>>,
+}
+
+pub(crate) struct Storage {
+    dir: PathBuf,
+    log: BufWriter<File>,
+    pending: usize,
+}
+
+impl Storage {
+    pub(crate) fn open(dir: &Path) -> Result<(Self, Snapshot, Vec<LogEntry>)> {
+        fs::create_dir_all(dir)?;
+
+        let snapshot_path = dir.join(SNAPSHOT_FILE);
+        let snapshot = if snapshot_path.exists() {
+            serde_json::from_reader(BufReader::new(File::open(&snapshot_path)?))?
+        } else {
+            Snapshot::default()
+        };
+
+        let log_path = dir.join(LOG_FILE);
+        let mut entries = Vec::new();
+        if log_path.exists() {
+            let data = fs::read(&log_path)?;
+            let mut valid_len = 0;
+            let mut lines = data.split_inclusive(|&b| b == b'\n').peekable();
+            while let Some(line) = lines.next() {
+                let line_end = valid_len + line.len();
+                let text = line.trim_ascii();
+                if !text.is_empty() {
+                    match serde_json::from_slice(text) {
+                        Ok(entry) => entries.push(entry),
+                        // A crash mid-append leaves a torn last line, possibly
+                        // cut inside a UTF-8 sequence.
+                        Err(_) if lines.peek().is_none() => break,
+                        Err(e) => return Err(e.into()),
+                    }
+                }
+                valid_len = line_end;
+            }
+
+            // Cut the torn line off and make sure the next append starts on
+            // a line of its own.
+            if valid_len < data.len() || data.last().is_some_and(|&b| b != b'\n') {
+                let mut log = OpenOptions::new().write(true).open(&log_path)?;
+                log.set_len(valid_len as u64)?;
+                if valid_len > 0 && data[valid_len - 1] != b'\n' {
+                    log.seek(SeekFrom::End(0))?;
+                    log.write_all(b"\n")?;
+                }
+                log.sync_all()?;
+            }
+        }
+
+        let log = OpenOptions::new().create(true).append(true).open(&log_path)?;
+        let storage = Self {
+            dir: dir.to_path_buf(),
+            log: BufWriter::new(log),
+            pending: entries.len(),
+        };
+        Ok((storage, snapshot, entries))
+    }
+
+    pub(crate) fn append(&mut self, entry: &LogEntry) -> Result<usize> {
+        serde_json::to_writer(&mut self.log, entry)?;
+        self.log.write_all(b"\n")?;
+        self.log.flush()?;
+        self.pending += 1;
+        Ok(self.pending)
+    }
+
+    pub(crate) fn write_snapshot(&mut self, snapshot: &Snapshot) -> Result<()> {
+        let tmp_path = self.dir.join(format!("{}.tmp", SNAPSHOT_FILE));
+        let mut writer = BufWriter::new(File::create(&tmp_path)?);
+        serde_json::to_writer(&mut writer, snapshot)?;
+        writer.flush()?;
+        writer.get_ref().sync_all()?;
+        drop(writer);
+        fs::rename(&tmp_path, self.dir.join(SNAPSHOT_FILE))?;
+
+        let log = File::create(self.dir.join(LOG_FILE))?;
+        log.sync_all()?;
+        self.log = BufWriter::new(OpenOptions::new().append(true).open(self.dir.join(LOG_FILE))?);
+        self.pending = 0;
+        Ok(())
+    }
+}
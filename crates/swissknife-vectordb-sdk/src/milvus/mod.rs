@@ -0,0 +1,698 @@
+use crate::filter::{parse_filter, FilterExpr, FilterOp};
+use crate::{
+    Collection, DeleteOptions, DistanceMetric, Error, HybridSearchProvider, QueryOptions, QueryResult, Result,
+    SparseVector, UpsertOptions, UpsertResult, Vector, VectorDatabaseProvider,
+};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+const DEFAULT_URL: &str = "http://localhost:19530";
+const UPSERT_BATCH_SIZE: usize = 100;
+const MAX_ID_LENGTH: u32 = 512;
+const MAX_PARTITION_NAME_LENGTH: usize = 255;
+const ID_FIELD: &str = "id";
+const VECTOR_FIELD: &str = "vector";
+const SPARSE_FIELD: &str = "sparse";
+const METADATA_FIELD: &str = "metadata";
+
+#[derive(Clone)]
+pub struct MilvusClient {
+    base_url: String,
+    token: Option<String>,
+    database: Option<String>,
+    metrics: Arc<Mutex<HashMap<String, DistanceMetric>>>,
+    partitions: Arc<Mutex<HashSet<(String, String)>>>,
+    http: reqwest::Client,
+}
+
+impl MilvusClient {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            token: None,
+            database: None,
+            metrics: Arc::new(Mutex::new(HashMap::new())),
+            partitions: Arc::new(Mutex::new(HashSet::new())),
+            http: reqwest::Client::new(),
+        }
+    }
+
+    pub fn local() -> Self {
+        Self::new(DEFAULT_URL)
+    }
+
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    pub fn with_database(mut self, database: impl Into<String>) -> Self {
+        self.database = Some(database.into());
+        self
+    }
+
+    async fn call<T: for<'de> Deserialize<'de>>(&self, path: &str, mut body: Value, collection: &str) -> Result<T> {
+        if let Some(database) = &self.database {
+            body["dbName"] = json!(database);
+        }
+
+        let mut request = self
+            .http
+            .post(format!("{}/v2/vectordb{}", self.base_url, path))
+            .json(&body);
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request.send().await?;
+        let status = response.status().as_u16();
+
+        if !response.status().is_success() {
+            let message = response.text().await.unwrap_or_default();
+            return Err(match status {
+                401 | 403 => Error::Auth(message),
+                404 => Error::IndexNotFound(collection.to_string()),
+                429 => Error::RateLimited,
+                _ => Error::Api {
+                    message,
+                    code: Some(status.to_string()),
+                },
+            });
+        }
+
+        let response: MilvusResponse = response.json().await?;
+        if response.code != 0 && response.code != 200 {
+            let message = response.message.unwrap_or_default();
+            return Err(match response.code {
+                1800 | 2200 => Error::Auth(message),
+                _ if message.contains("collection not found") || message.contains("can't find collection") => {
+                    Error::IndexNotFound(collection.to_string())
+                }
+                _ => Error::Api {
+                    message,
+                    code: Some(response.code.to_string()),
+                },
+            });
+        }
+
+        Ok(serde_json::from_value(response.data.unwrap_or(Value::Null))?)
+    }
+
+    async fn describe(&self, collection: &str) -> Result<CollectionDescription> {
+        let description: CollectionDescription = self
+            .call("/collections/describe", json!({ "collectionName": collection }), collection)
+            .await?;
+        self.metrics
+            .lock()
+            .unwrap()
+            .insert(collection.to_string(), description.metric());
+        Ok(description)
+    }
+
+    async fn metric(&self, collection: &str) -> Result<DistanceMetric> {
+        if let Some(metric) = self.metrics.lock().unwrap().get(collection) {
+            return Ok(*metric);
+        }
+        Ok(self.describe(collection).await?.metric())
+    }
+
+    async fn ensure_partition(&self, collection: &str, partition: &str) -> Result<()> {
+        let key = (collection.to_string(), partition.to_string());
+        if self.partitions.lock().unwrap().contains(&key) {
+            return Ok(());
+        }
+
+        let body = json!({ "collectionName": collection, "partitionName": partition });
+        let existing: HasPartition = self.call("/partitions/has", body.clone(), collection).await?;
+        if !existing.has {
+            let _: Value = self.call("/partitions/create", body, collection).await?;
+            let _: Value = self
+                .call(
+                    "/partitions/load",
+                    json!({ "collectionName": collection, "partitionNames": [partition] }),
+                    collection,
+                )
+                .await?;
+        }
+        self.partitions.lock().unwrap().insert(key);
+        Ok(())
+    }
+
+    async fn search(&self, collection: &str, body: Value, path: &str, rescore: bool) -> Result<Vec<QueryResult>> {
+        let metric = self.metric(collection).await?;
+        let rows: Vec<Map<String, Value>> = self.call(path, body, collection).await?;
+        Ok(rows
+            .into_iter()
+            .map(|mut row| {
+                let distance = row.remove("distance").and_then(|d| d.as_f64()).unwrap_or_default() as f32;
+                let vector = to_vector(row);
+                QueryResult {
+                    id: vector.id,
+                    score: if rescore { score_from_distance(metric, distance) } else { distance },
+                    values: Some(vector.values).filter(|v| !v.is_empty()),
+                    metadata: vector.metadata,
+                }
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl VectorDatabaseProvider for MilvusClient {
+    async fn list_collections(&self) -> Result<Vec<Collection>> {
+        let names: Vec<String> = self.call("/collections/list", json!({}), "").await?;
+        let mut collections = Vec::with_capacity(names.len());
+        for name in names {
+            collections.push(self.describe(&name).await?.into_collection(None));
+        }
+        Ok(collections)
+    }
+
+    async fn create_collection(&self, name: &str, dimension: u32, metric: DistanceMetric) -> Result<Collection> {
+        let body = json!({
+            "collectionName": name,
+            "schema": {
+                "autoId": false,
+                "enableDynamicField": false,
+                "fields": [
+                    {
+                        "fieldName": ID_FIELD,
+                        "dataType": "VarChar",
+                        "isPrimary": true,
+                        "elementTypeParams": { "max_length": MAX_ID_LENGTH }
+                    },
+                    {
+                        "fieldName": VECTOR_FIELD,
+                        "dataType": "FloatVector",
+                        "elementTypeParams": { "dim": dimension }
+                    },
+                    { "fieldName": SPARSE_FIELD, "dataType": "SparseFloatVector" },
+                    { "fieldName": METADATA_FIELD, "dataType": "JSON" }
+                ]
+            },
+            "indexParams": [
+                {
+                    "fieldName": VECTOR_FIELD,
+                    "indexName": VECTOR_FIELD,
+                    "metricType": metric_name(metric),
+                    "indexType": "AUTOINDEX"
+                },
+                {
+                    "fieldName": SPARSE_FIELD,
+                    "indexName": SPARSE_FIELD,
+                    "metricType": "IP",
+                    "indexType": "SPARSE_INVERTED_INDEX"
+                }
+            ]
+        });
+        let _: Value = self.call("/collections/create", body, name).await?;
+        self.metrics.lock().unwrap().insert(name.to_string(), metric);
+
+        Ok(Collection {
+            name: name.to_string(),
+            dimension,
+            metric,
+            vector_count: Some(0),
+        })
+    }
+
+    async fn delete_collection(&self, name: &str) -> Result<()> {
+        let _: Value = self
+            .call("/collections/drop", json!({ "collectionName": name }), name)
+            .await?;
+        self.metrics.lock().unwrap().remove(name);
+        self.partitions.lock().unwrap().retain(|(collection, _)| collection != name);
+        Ok(())
+    }
+
+    async fn describe_collection(&self, name: &str) -> Result<Collection> {
+        let description = self.describe(name).await?;
+        let stats: CollectionStats = self
+            .call("/collections/get_stats", json!({ "collectionName": name }), name)
+            .await?;
+        Ok(description.into_collection(Some(stats.row_count)))
+    }
+
+    async fn upsert(&self, collection: &str, vectors: &[Vector], options: &UpsertOptions) -> Result<UpsertResult> {
+        let partition = partition_name(options.namespace.as_deref())?;
+        vectors.iter().try_for_each(|v| check_id(&v.id))?;
+        if let Some(partition) = partition {
+            self.ensure_partition(collection, partition).await?;
+        }
+
+        let mut upserted_count = 0;
+        for batch in vectors.chunks(UPSERT_BATCH_SIZE) {
+            let mut body = json!({
+                "collectionName": collection,
+                "data": batch.iter().map(row_body).collect::<Vec<_>>(),
+            });
+            if let Some(partition) = partition {
+                body["partitionName"] = json!(partition);
+            }
+            let response: UpsertResponse = self.call("/entities/upsert", body, collection).await?;
+            upserted_count += response.upsert_count;
+        }
+
+        Ok(UpsertResult { upserted_count })
+    }
+
+    async fn query(&self, collection: &str, vector: &[f32], options: &QueryOptions) -> Result<Vec<QueryResult>> {
+        let mut body = json!({
+            "collectionName": collection,
+            "data": [vector],
+            "annsField": VECTOR_FIELD,
+            "limit": options.top_k.max(1),
+            "outputFields": output_fields(options),
+        });
+        if let Some(filter) = build_expr(options.filter.as_ref())? {
+            body["filter"] = json!(filter);
+        }
+        if let Some(namespace) = partition_name(options.namespace.as_deref())? {
+            body["partitionNames"] = json!([namespace]);
+        }
+
+        self.search(collection, body, "/entities/search", true).await
+    }
+
+    async fn fetch(&self, collection: &str, ids: &[&str], namespace: Option<&str>) -> Result<Vec<Vector>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut body = json!({
+            "collectionName": collection,
+            "id": ids,
+            "outputFields": [ID_FIELD, VECTOR_FIELD, SPARSE_FIELD, METADATA_FIELD],
+        });
+        if let Some(namespace) = partition_name(namespace)? {
+            body["partitionNames"] = json!([namespace]);
+        }
+
+        let rows: Vec<Map<String, Value>> = self.call("/entities/get", body, collection).await?;
+        let mut fetched: HashMap<String, Vector> = rows
+            .into_iter()
+            .map(to_vector)
+            .map(|v| (v.id.clone(), v))
+            .collect();
+        Ok(ids.iter().filter_map(|id| fetched.remove(*id)).collect())
+    }
+
+    async fn delete(&self, collection: &str, ids: &[&str], options: &DeleteOptions) -> Result<()> {
+        let filter = if options.delete_all {
+            format!("{} != \"\"", ID_FIELD)
+        } else if let Some(filter) = build_expr(options.filter.as_ref())? {
+            filter
+        } else if ids.is_empty() {
+            return Ok(());
+        } else {
+            format!("{} in {}", ID_FIELD, json!(ids))
+        };
+
+        let mut body = json!({ "collectionName": collection, "filter": filter });
+        if let Some(namespace) = partition_name(options.namespace.as_deref())? {
+            body["partitionName"] = json!(namespace);
+        }
+        let _: Value = self.call("/entities/delete", body, collection).await?;
+        Ok(())
+    }
+
+    async fn update(
+        &self,
+        collection: &str,
+        id: &str,
+        values: Option<&[f32]>,
+        metadata: Option<HashMap<String, Value>>,
+    ) -> Result<()> {
+        let partitions: Vec<String> = self
+            .call("/partitions/list", json!({ "collectionName": collection }), collection)
+            .await?;
+
+        for partition in partitions {
+            let body = json!({
+                "collectionName": collection,
+                "id": [id],
+                "outputFields": [ID_FIELD, VECTOR_FIELD, SPARSE_FIELD, METADATA_FIELD],
+                "partitionNames": [partition],
+            });
+            let rows: Vec<Map<String, Value>> = self.call("/entities/get", body, collection).await?;
+            let Some(row) = rows.into_iter().next() else {
+                continue;
+            };
+
+            let mut vector = to_vector(row);
+            if let Some(values) = values {
+                vector.values = values.to_vec();
+            }
+            if let Some(metadata) = metadata {
+                vector.metadata.get_or_insert_with(HashMap::new).extend(metadata);
+            }
+
+            let body = json!({
+                "collectionName": collection,
+                "partitionName": partition,
+                "data": [row_body(&vector)],
+            });
+            let _: Value = self.call("/entities/upsert", body, collection).await?;
+            return Ok(());
+        }
+
+        Err(Error::Api {
+            message: format!("Vector {} not found in {}", id, collection),
+            code: Some("not_found".to_string()),
+        })
+    }
+}
+
+#[async_trait]
+impl HybridSearchProvider for MilvusClient {
+    async fn hybrid_query(
+        &self,
+        collection: &str,
+        dense_vector: &[f32],
+        sparse_vector: &SparseVector,
+        alpha: f32,
+        options: &QueryOptions,
+    ) -> Result<Vec<QueryResult>> {
+        let alpha = alpha.clamp(0.0, 1.0);
+        let top_k = options.top_k.max(1);
+        let filter = build_expr(options.filter.as_ref())?;
+
+        let request = |data: Value, field: &str| {
+            let mut search = json!({ "data": [data], "annsField": field, "limit": top_k });
+            if let Some(filter) = &filter {
+                search["filter"] = json!(filter);
+            }
+            search
+        };
+
+        let mut body = json!({
+            "collectionName": collection,
+            "search": [
+                request(json!(dense_vector), VECTOR_FIELD),
+                request(sparse_body(Some(sparse_vector)), SPARSE_FIELD),
+            ],
+            "rerank": { "strategy": "weighted", "params": { "weights": [alpha, 1.0 - alpha] } },
+            "limit": top_k,
+            "outputFields": output_fields(options),
+        });
+        if let Some(namespace) = partition_name(options.namespace.as_deref())? {
+            body["partitionNames"] = json!([namespace]);
+        }
+
+        self.search(collection, body, "/entities/hybrid_search", false).await
+    }
+}
+
+fn output_fields(options: &QueryOptions) -> Vec<&'static str> {
+    let mut fields = vec![ID_FIELD];
+    if options.include_metadata {
+        fields.push(METADATA_FIELD);
+    }
+    if options.include_values {
+        fields.push(VECTOR_FIELD);
+    }
+    fields
+}
+
+fn sparse_body(sparse: Option<&SparseVector>) -> Value {
+    let entries: Map<String, Value> = sparse
+        .map(|s| {
+            s.indices
+                .iter()
+                .zip(&s.values)
+                .map(|(index, value)| (index.to_string(), json!(value)))
+                .collect()
+        })
+        .unwrap_or_default();
+    Value::Object(entries)
+}
+
+fn row_body(vector: &Vector) -> Value {
+    json!({
+        ID_FIELD: vector.id,
+        VECTOR_FIELD: vector.values,
+        SPARSE_FIELD: sparse_body(vector.sparse_values.as_ref()),
+        METADATA_FIELD: vector.metadata.clone().unwrap_or_default(),
+    })
+}
+
+fn to_vector(mut row: Map<String, Value>) -> Vector {
+    let id = match row.remove(ID_FIELD) {
+        Some(Value::String(id)) => id,
+        Some(other) => other.to_string(),
+        None => String::new(),
+    };
+    let values = row
+        .remove(VECTOR_FIELD)
+        .and_then(|v| v.as_array().cloned())
+        .map(|values| values.iter().filter_map(|v| v.as_f64()).map(|v| v as f32).collect())
+        .unwrap_or_default();
+    let sparse_values = match row.remove(SPARSE_FIELD) {
+        Some(Value::Object(entries)) if !entries.is_empty() => {
+            let mut pairs: Vec<(u32, f32)> = entries
+                .iter()
+                .filter_map(|(index, value)| Some((index.parse().ok()?, value.as_f64()? as f32)))
+                .collect();
+            pairs.sort_by_key(|(index, _)| *index);
+            Some(SparseVector {
+                indices: pairs.iter().map(|(i, _)| *i).collect(),
+                values: pairs.iter().map(|(_, v)| *v).collect(),
+            })
+        }
+        _ => None,
+    };
+    let metadata = match row.remove(METADATA_FIELD) {
+        Some(Value::Object(fields)) if !fields.is_empty() => Some(fields.into_iter().collect()),
+        _ => None,
+    };
+
+    Vector {
+        id,
+        values,
+        metadata,
+        sparse_values,
+    }
+}
+
+fn metric_name(metric: DistanceMetric) -> &'static str {
+    match metric {
+        DistanceMetric::Cosine => "COSINE",
+        DistanceMetric::Euclidean => "L2",
+        DistanceMetric::DotProduct => "IP",
+    }
+}
+
+fn parse_metric(metric: &str) -> DistanceMetric {
+    match metric {
+        "L2" => DistanceMetric::Euclidean,
+        "IP" => DistanceMetric::DotProduct,
+        _ => DistanceMetric::Cosine,
+    }
+}
+
+// COSINE and IP come back as similarities, L2 as a squared distance that is
+// negated so higher scores are always better.
+fn score_from_distance(metric: DistanceMetric, distance: f32) -> f32 {
+    match metric {
+        DistanceMetric::Cosine | DistanceMetric::DotProduct => distance,
+        DistanceMetric::Euclidean => -distance.max(0.0).sqrt(),
+    }
+}
+
+// Namespaces become partitions, whose names Milvus restricts to [_A-Za-z][_0-9A-Za-z]*.
+fn partition_name(namespace: Option<&str>) -> Result<Option<&str>> {
+    let Some(namespace) = namespace.filter(|ns| !ns.is_empty()) else {
+        return Ok(None);
+    };
+    let mut chars = namespace.chars();
+    let valid = namespace.len() <= MAX_PARTITION_NAME_LENGTH
+        && chars.next().is_some_and(|c| c == '_' || c.is_ascii_alphabetic())
+        && chars.all(|c| c == '_' || c.is_ascii_alphanumeric());
+    if !valid {
+        return Err(Error::Unsupported(format!(
+            "Milvus partition names must match [_A-Za-z][_0-9A-Za-z]* and be at most {} characters, got {}",
+            MAX_PARTITION_NAME_LENGTH, namespace
+        )));
+    }
+    Ok(Some(namespace))
+}
+
+fn check_id(id: &str) -> Result<()> {
+    if id.len() > MAX_ID_LENGTH as usize {
+        return Err(Error::Unsupported(format!(
+            "Milvus ids are limited to {} bytes, got {}",
+            MAX_ID_LENGTH,
+            id.len()
+        )));
+    }
+    Ok(())
+}
+
+fn build_expr(filter: Option<&HashMap<String, Value>>) -> Result<Option<String>> {
+    let Some(filter) = filter else {
+        return Ok(None);
+    };
+    Ok(match parse_filter(filter)? {
+        FilterExpr::And(items) if items.is_empty() => None,
+        expr => Some(translate(&expr)),
+    })
+}
+
+fn translate(expr: &FilterExpr) -> String {
+    let group = |items: &[FilterExpr], joiner: &str| {
+        items
+            .iter()
+            .map(|item| format!("({})", translate(item)))
+            .collect::<Vec<_>>()
+            .join(joiner)
+    };
+
+    match expr {
+        FilterExpr::And(items) => group(items, " and "),
+        FilterExpr::Or(items) => group(items, " or "),
+        FilterExpr::Field { key, op, value } => {
+            let op = match op {
+                FilterOp::Eq => "==",
+                FilterOp::Ne => "!=",
+                FilterOp::Gt => ">",
+                FilterOp::Gte => ">=",
+                FilterOp::Lt => "<",
+                FilterOp::Lte => "<=",
+                FilterOp::In => "in",
+                FilterOp::Nin => "not in",
+            };
+            format!("{}[{}] {} {}", METADATA_FIELD, json!(key), op, value)
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct MilvusResponse {
+    #[serde(default)]
+    code: i64,
+    message: Option<String>,
+    data: Option<Value>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CollectionDescription {
+    collection_name: String,
+    #[serde(default)]
+    fields: Vec<FieldDescription>,
+    #[serde(default)]
+    indexes: Vec<IndexDescription>,
+}
+
+impl CollectionDescription {
+    fn metric(&self) -> DistanceMetric {
+        self.indexes
+            .iter()
+            .find(|index| index.field_name == VECTOR_FIELD)
+            .or_else(|| self.indexes.first())
+            .map(|index| parse_metric(&index.metric_type))
+            .unwrap_or(DistanceMetric::Cosine)
+    }
+
+    fn dimension(&self) -> u32 {
+        self.fields
+            .iter()
+            .filter(|field| field.field_type == "FloatVector")
+            .flat_map(|field| &field.params)
+            .find(|param| param.key == "dim")
+            .and_then(|param| match &param.value {
+                Value::String(dim) => dim.parse().ok(),
+                other => other.as_u64().map(|d| d as u32),
+            })
+            .unwrap_or_default()
+    }
+
+    fn into_collection(self, vector_count: Option<u64>) -> Collection {
+        Collection {
+            dimension: self.dimension(),
+            metric: self.metric(),
+            name: self.collection_name,
+            vector_count,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct FieldDescription {
+    #[serde(rename = "type", default)]
+    field_type: String,
+    #[serde(default)]
+    params: Vec<FieldParam>,
+}
+
+#[derive(Deserialize)]
+struct FieldParam {
+    key: String,
+    value: Value,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IndexDescription {
+    field_name: String,
+    #[serde(default)]
+    metric_type: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CollectionStats {
+    #[serde(default)]
+    row_count: u64,
+}
+
+#[derive(Deserialize)]
+struct HasPartition {
+    #[serde(default)]
+    has: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpsertResponse {
+    #[serde(default)]
+    upsert_count: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expr(filter: Value) -> Option<String> {
+        build_expr(Some(&serde_json::from_value(filter).unwrap())).unwrap()
+    }
+
+    #[test]
+    fn test_translate_filter_to_boolean_expression() {
+        assert_eq!(expr(json!({"genre": "drama"})).unwrap(), r#"metadata["genre"] == "drama""#);
+        assert_eq!(
+            expr(json!({"year": {"$gte": 2000}, "$or": [{"genre": {"$in": ["a", "b"]}}, {"draft": false}]})).unwrap(),
+            r#"((metadata["genre"] in ["a","b"]) or (metadata["draft"] == false)) and (metadata["year"] >= 2000)"#
+        );
+        assert_eq!(expr(json!({})), None);
+    }
+
+    #[test]
+    fn test_to_vector_reads_sparse_and_metadata() {
+        let row = json!({
+            "id": "doc-1",
+            "vector": [0.5, 1.0],
+            "sparse": {"7": 0.25, "2": 1.5},
+            "metadata": {"genre": "drama"}
+        });
+        let vector = to_vector(row.as_object().unwrap().clone());
+        assert_eq!(vector.id, "doc-1");
+        assert_eq!(vector.values, vec![0.5, 1.0]);
+        let sparse = vector.sparse_values.unwrap();
+        assert_eq!(sparse.indices, vec![2, 7]);
+        assert_eq!(sparse.values, vec![1.5, 0.25]);
+        assert_eq!(vector.metadata.unwrap()["genre"], json!("drama"));
+    }
+}
@@ -0,0 +1,499 @@
+use crate::filter::{parse_filter, FilterExpr, FilterOp};
+use crate::{
+    Collection, DeleteOptions, DistanceMetric, Error, HybridSearchProvider, QueryOptions, QueryResult, Result,
+    SparseVector, UpsertOptions, UpsertResult, Vector, VectorDatabaseProvider,
+};
+use async_trait::async_trait;
+use reqwest::{Method, RequestBuilder};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+const CONTROL_PLANE_BASE: &str = "https://api.pinecone.io";
+const API_VERSION: &str = "2024-07";
+const UPSERT_BATCH_SIZE: usize = 100;
+const FETCH_BATCH_SIZE: usize = 1000;
+
+#[derive(Clone)]
+pub struct PineconeClient {
+    api_key: String,
+    control_plane_url: String,
+    cloud: String,
+    region: String,
+    hosts: Arc<Mutex<HashMap<String, String>>>,
+    http: reqwest::Client,
+}
+
+impl PineconeClient {
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self {
+            api_key: api_key.into(),
+            control_plane_url: CONTROL_PLANE_BASE.to_string(),
+            cloud: "aws".to_string(),
+            region: "us-east-1".to_string(),
+            hosts: Arc::new(Mutex::new(HashMap::new())),
+            http: reqwest::Client::new(),
+        }
+    }
+
+    pub fn with_control_plane_url(mut self, url: impl Into<String>) -> Self {
+        self.control_plane_url = url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn with_serverless_spec(mut self, cloud: impl Into<String>, region: impl Into<String>) -> Self {
+        self.cloud = cloud.into();
+        self.region = region.into();
+        self
+    }
+
+    pub fn with_index_host(self, index: impl Into<String>, host: impl Into<String>) -> Self {
+        self.hosts.lock().unwrap().insert(index.into(), normalize_host(&host.into()));
+        self
+    }
+
+    fn request(&self, method: Method, url: String) -> RequestBuilder {
+        self.http
+            .request(method, url)
+            .header("Api-Key", &self.api_key)
+            .header("X-Pinecone-API-Version", API_VERSION)
+    }
+
+    async fn send<T: for<'de> Deserialize<'de>>(&self, request: RequestBuilder, index: &str) -> Result<T> {
+        let response = request.send().await?;
+        let status = response.status().as_u16();
+
+        if !response.status().is_success() {
+            let body = response.text().await.unwrap_or_default();
+            let message = serde_json::from_str::<PineconeError>(&body)
+                .ok()
+                .and_then(|e| e.message())
+                .unwrap_or(body);
+            return Err(match status {
+                401 | 403 => Error::Auth(message),
+                404 => Error::IndexNotFound(index.to_string()),
+                429 => Error::RateLimited,
+                _ => Error::Api {
+                    message,
+                    code: Some(status.to_string()),
+                },
+            });
+        }
+
+        let text = response.text().await?;
+        Ok(serde_json::from_str(if text.trim().is_empty() { "null" } else { &text })?)
+    }
+
+    async fn control<T: for<'de> Deserialize<'de>>(&self, method: Method, path: &str, body: Option<Value>, index: &str) -> Result<T> {
+        let mut request = self.request(method, format!("{}{}", self.control_plane_url, path));
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+        self.send(request, index).await
+    }
+
+    async fn index_host(&self, index: &str) -> Result<String> {
+        if let Some(host) = self.hosts.lock().unwrap().get(index) {
+            return Ok(host.clone());
+        }
+
+        let description: IndexDescription = self.control(Method::GET, &format!("/indexes/{}", index), None, index).await?;
+        let host = normalize_host(&description.host);
+        self.hosts.lock().unwrap().insert(index.to_string(), host.clone());
+        Ok(host)
+    }
+
+    async fn data<T: for<'de> Deserialize<'de>>(&self, index: &str, path: &str, body: &impl Serialize) -> Result<T> {
+        let host = self.index_host(index).await?;
+        let request = self.request(Method::POST, format!("{}{}", host, path)).json(body);
+        self.send(request, index).await
+    }
+
+    async fn query_vectors(
+        &self,
+        index: &str,
+        vector: &[f32],
+        sparse_vector: Option<&SparseVector>,
+        options: &QueryOptions,
+    ) -> Result<Vec<QueryResult>> {
+        let request = QueryRequest {
+            vector,
+            sparse_vector: sparse_vector.map(PineconeSparseValues::from),
+            top_k: options.top_k.max(1),
+            include_values: options.include_values,
+            include_metadata: options.include_metadata,
+            filter: options.filter.as_ref().map(translate_filter).transpose()?,
+            namespace: options.namespace.as_deref(),
+        };
+
+        let response: QueryResponse = self.data(index, "/query", &request).await?;
+        Ok(response
+            .matches
+            .into_iter()
+            .map(|m| QueryResult {
+                id: m.id,
+                score: m.score,
+                values: m.values.filter(|v| !v.is_empty()),
+                metadata: m.metadata,
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl VectorDatabaseProvider for PineconeClient {
+    async fn list_collections(&self) -> Result<Vec<Collection>> {
+        let response: IndexList = self.control(Method::GET, "/indexes", None, "").await?;
+        let mut hosts = self.hosts.lock().unwrap();
+        Ok(response
+            .indexes
+            .into_iter()
+            .map(|index| {
+                hosts.insert(index.name.clone(), normalize_host(&index.host));
+                index.into_collection(None)
+            })
+            .collect())
+    }
+
+    async fn create_collection(&self, name: &str, dimension: u32, metric: DistanceMetric) -> Result<Collection> {
+        let body = json!({
+            "name": name,
+            "dimension": dimension,
+            "metric": metric_name(metric),
+            "spec": {
+                "serverless": {
+                    "cloud": self.cloud,
+                    "region": self.region,
+                }
+            }
+        });
+        let description: IndexDescription = self.control(Method::POST, "/indexes", Some(body), name).await?;
+        if !description.host.is_empty() {
+            self.hosts
+                .lock()
+                .unwrap()
+                .insert(name.to_string(), normalize_host(&description.host));
+        }
+        Ok(description.into_collection(Some(0)))
+    }
+
+    async fn delete_collection(&self, name: &str) -> Result<()> {
+        let _: Value = self.control(Method::DELETE, &format!("/indexes/{}", name), None, name).await?;
+        self.hosts.lock().unwrap().remove(name);
+        Ok(())
+    }
+
+    async fn describe_collection(&self, name: &str) -> Result<Collection> {
+        let description: IndexDescription = self.control(Method::GET, &format!("/indexes/{}", name), None, name).await?;
+        self.hosts
+            .lock()
+            .unwrap()
+            .insert(name.to_string(), normalize_host(&description.host));
+
+        let stats: IndexStats = self.data(name, "/describe_index_stats", &json!({})).await?;
+        Ok(description.into_collection(Some(stats.total_vector_count)))
+    }
+
+    async fn upsert(&self, collection: &str, vectors: &[Vector], options: &UpsertOptions) -> Result<UpsertResult> {
+        let mut upserted_count = 0;
+        for batch in vectors.chunks(UPSERT_BATCH_SIZE) {
+            let request = UpsertRequest {
+                vectors: batch.iter().map(PineconeVector::from).collect(),
+                namespace: options.namespace.as_deref(),
+            };
+            let response: UpsertResponse = self.data(collection, "/vectors/upsert", &request).await?;
+            upserted_count += response.upserted_count;
+        }
+        Ok(UpsertResult { upserted_count })
+    }
+
+    async fn query(&self, collection: &str, vector: &[f32], options: &QueryOptions) -> Result<Vec<QueryResult>> {
+        self.query_vectors(collection, vector, None, options).await
+    }
+
+    async fn fetch(&self, collection: &str, ids: &[&str], namespace: Option<&str>) -> Result<Vec<Vector>> {
+        let host = self.index_host(collection).await?;
+        let mut vectors = Vec::with_capacity(ids.len());
+
+        for batch in ids.chunks(FETCH_BATCH_SIZE) {
+            let mut query: Vec<(&str, &str)> = batch.iter().map(|id| ("ids", *id)).collect();
+            if let Some(namespace) = namespace {
+                query.push(("namespace", namespace));
+            }
+            let request = self
+                .request(Method::GET, format!("{}/vectors/fetch", host))
+                .query(&query);
+            let response: FetchResponse = self.send(request, collection).await?;
+
+            let mut fetched = response.vectors;
+            vectors.extend(batch.iter().filter_map(|id| fetched.remove(*id)).map(Vector::from));
+        }
+
+        Ok(vectors)
+    }
+
+    async fn delete(&self, collection: &str, ids: &[&str], options: &DeleteOptions) -> Result<()> {
+        let filter = options.filter.as_ref().map(translate_filter).transpose()?;
+        let mut body = json!({ "namespace": options.namespace.clone().unwrap_or_default() });
+
+        if options.delete_all {
+            body["deleteAll"] = json!(true);
+        } else if let Some(filter) = filter {
+            body["filter"] = filter;
+        } else {
+            if ids.is_empty() {
+                return Ok(());
+            }
+            body["ids"] = json!(ids);
+        }
+
+        let _: Value = self.data(collection, "/vectors/delete", &body).await?;
+        Ok(())
+    }
+
+    async fn update(
+        &self,
+        collection: &str,
+        id: &str,
+        values: Option<&[f32]>,
+        metadata: Option<HashMap<String, Value>>,
+    ) -> Result<()> {
+        let mut body = json!({ "id": id });
+        if let Some(values) = values {
+            body["values"] = json!(values);
+        }
+        if let Some(metadata) = metadata {
+            body["setMetadata"] = json!(metadata);
+        }
+
+        let _: Value = self.data(collection, "/vectors/update", &body).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl HybridSearchProvider for PineconeClient {
+    async fn hybrid_query(
+        &self,
+        collection: &str,
+        dense_vector: &[f32],
+        sparse_vector: &SparseVector,
+        alpha: f32,
+        options: &QueryOptions,
+    ) -> Result<Vec<QueryResult>> {
+        let alpha = alpha.clamp(0.0, 1.0);
+        let dense: Vec<f32> = dense_vector.iter().map(|v| v * alpha).collect();
+        let sparse = SparseVector {
+            indices: sparse_vector.indices.clone(),
+            values: sparse_vector.values.iter().map(|v| v * (1.0 - alpha)).collect(),
+        };
+
+        self.query_vectors(collection, &dense, Some(&sparse), options).await
+    }
+}
+
+fn normalize_host(host: &str) -> String {
+    let host = host.trim_end_matches('/');
+    if host.starts_with("http://") || host.starts_with("https://") {
+        host.to_string()
+    } else {
+        format!("https://{}", host)
+    }
+}
+
+fn metric_name(metric: DistanceMetric) -> &'static str {
+    match metric {
+        DistanceMetric::Cosine => "cosine",
+        DistanceMetric::Euclidean => "euclidean",
+        DistanceMetric::DotProduct => "dotproduct",
+    }
+}
+
+fn parse_metric(metric: &str) -> DistanceMetric {
+    match metric {
+        "euclidean" => DistanceMetric::Euclidean,
+        "dotproduct" => DistanceMetric::DotProduct,
+        _ => DistanceMetric::Cosine,
+    }
+}
+
+fn translate_filter(filter: &HashMap<String, Value>) -> Result<Value> {
+    Ok(filter_to_json(&parse_filter(filter)?))
+}
+
+fn filter_to_json(expr: &FilterExpr) -> Value {
+    match expr {
+        FilterExpr::And(items) => json!({ "$and": items.iter().map(filter_to_json).collect::<Vec<_>>() }),
+        FilterExpr::Or(items) => json!({ "$or": items.iter().map(filter_to_json).collect::<Vec<_>>() }),
+        FilterExpr::Field { key, op, value } => {
+            let op = match op {
+                FilterOp::Eq => "$eq",
+                FilterOp::Ne => "$ne",
+                FilterOp::Gt => "$gt",
+                FilterOp::Gte => "$gte",
+                FilterOp::Lt => "$lt",
+                FilterOp::Lte => "$lte",
+                FilterOp::In => "$in",
+                FilterOp::Nin => "$nin",
+            };
+            json!({ key: { op: value } })
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum PineconeError {
+    Nested { error: PineconeErrorBody },
+    Flat(PineconeErrorBody),
+}
+
+impl PineconeError {
+    fn message(self) -> Option<String> {
+        match self {
+            PineconeError::Nested { error } | PineconeError::Flat(error) => error.message,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct PineconeErrorBody {
+    message: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct IndexList {
+    #[serde(default)]
+    indexes: Vec<IndexDescription>,
+}
+
+#[derive(Deserialize)]
+struct IndexDescription {
+    name: String,
+    dimension: u32,
+    #[serde(default)]
+    metric: String,
+    #[serde(default)]
+    host: String,
+}
+
+impl IndexDescription {
+    fn into_collection(self, vector_count: Option<u64>) -> Collection {
+        Collection {
+            name: self.name,
+            dimension: self.dimension,
+            metric: parse_metric(&self.metric),
+            vector_count,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IndexStats {
+    #[serde(default)]
+    total_vector_count: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PineconeSparseValues {
+    indices: Vec<u32>,
+    values: Vec<f32>,
+}
+
+impl From<&SparseVector> for PineconeSparseValues {
+    fn from(sparse: &SparseVector) -> Self {
+        Self {
+            indices: sparse.indices.clone(),
+            values: sparse.values.clone(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PineconeVector {
+    id: String,
+    #[serde(default)]
+    values: Vec<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sparse_values: Option<PineconeSparseValues>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    metadata: Option<HashMap<String, Value>>,
+}
+
+impl From<&Vector> for PineconeVector {
+    fn from(vector: &Vector) -> Self {
+        Self {
+            id: vector.id.clone(),
+            values: vector.values.clone(),
+            sparse_values: vector.sparse_values.as_ref().map(PineconeSparseValues::from),
+            metadata: vector.metadata.clone(),
+        }
+    }
+}
+
+impl From<PineconeVector> for Vector {
+    fn from(vector: PineconeVector) -> Self {
+        Self {
+            id: vector.id,
+            values: vector.values,
+            metadata: vector.metadata,
+            sparse_values: vector.sparse_values.map(|s| SparseVector {
+                indices: s.indices,
+                values: s.values,
+            }),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct UpsertRequest<'a> {
+    vectors: Vec<PineconeVector>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    namespace: Option<&'a str>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpsertResponse {
+    #[serde(default)]
+    upserted_count: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct QueryRequest<'a> {
+    vector: &'a [f32],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sparse_vector: Option<PineconeSparseValues>,
+    top_k: u32,
+    include_values: bool,
+    include_metadata: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    filter: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    namespace: Option<&'a str>,
+}
+
+#[derive(Deserialize)]
+struct QueryResponse {
+    #[serde(default)]
+    matches: Vec<QueryMatch>,
+}
+
+#[derive(Deserialize)]
+struct QueryMatch {
+    id: String,
+    #[serde(default)]
+    score: f32,
+    values: Option<Vec<f32>>,
+    metadata: Option<HashMap<String, Value>>,
+}
+
+#[derive(Deserialize)]
+struct FetchResponse {
+    #[serde(default)]
+    vectors: HashMap<String, PineconeVector>,
+}
@@ -0,0 +1,553 @@
+use crate::filter::{parse_filter, FilterExpr, FilterOp};
+use crate::{
+    Collection, DeleteOptions, DistanceMetric, Error, HybridSearchProvider, QueryOptions, QueryResult, Result,
+    SparseVector, UpsertOptions, UpsertResult, Vector, VectorDatabaseProvider,
+};
+use async_trait::async_trait;
+use reqwest::{Method, RequestBuilder};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+const DEFAULT_URL: &str = "http://localhost:6333";
+const UPSERT_BATCH_SIZE: usize = 256;
+const ORIGINAL_ID_KEY: &str = "_original_id";
+const NAMESPACE_KEY: &str = "_namespace";
+
+#[derive(Clone)]
+pub struct QdrantClient {
+    base_url: String,
+    api_key: Option<String>,
+    dense_vector_name: String,
+    sparse_vector_name: String,
+    http: reqwest::Client,
+}
+
+impl QdrantClient {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            api_key: None,
+            dense_vector_name: "dense".to_string(),
+            sparse_vector_name: "sparse".to_string(),
+            http: reqwest::Client::new(),
+        }
+    }
+
+    pub fn local() -> Self {
+        Self::new(DEFAULT_URL)
+    }
+
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    pub fn with_vector_names(mut self, dense: impl Into<String>, sparse: impl Into<String>) -> Self {
+        self.dense_vector_name = dense.into();
+        self.sparse_vector_name = sparse.into();
+        self
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let request = self.http.request(method, format!("{}{}", self.base_url, path));
+        match &self.api_key {
+            Some(key) => request.header("api-key", key),
+            None => request,
+        }
+    }
+
+    async fn send<T: for<'de> Deserialize<'de>>(&self, method: Method, path: &str, body: Option<Value>, collection: &str) -> Result<T> {
+        let mut request = self.request(method, path);
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+
+        let response = request.send().await?;
+        let status = response.status().as_u16();
+
+        if !response.status().is_success() {
+            let body = response.text().await.unwrap_or_default();
+            let message = serde_json::from_str::<QdrantErrorResponse>(&body)
+                .ok()
+                .and_then(|r| r.status.error)
+                .unwrap_or(body);
+            return Err(match status {
+                401 | 403 => Error::Auth(message),
+                404 => Error::IndexNotFound(collection.to_string()),
+                429 => Error::RateLimited,
+                _ => Error::Api {
+                    message,
+                    code: Some(status.to_string()),
+                },
+            });
+        }
+
+        let response: QdrantResponse<T> = response.json().await?;
+        Ok(response.result)
+    }
+
+    fn query_filter(&self, options: &QueryOptions) -> Result<Option<Value>> {
+        build_filter(options.filter.as_ref(), options.namespace.as_deref())
+    }
+
+    fn point_body(&self, vector: &Vector, namespace: Option<&str>) -> Value {
+        let mut vectors = serde_json::Map::new();
+        if !vector.values.is_empty() {
+            vectors.insert(self.dense_vector_name.clone(), json!(vector.values));
+        }
+        if let Some(sparse) = &vector.sparse_values {
+            vectors.insert(
+                self.sparse_vector_name.clone(),
+                json!({ "indices": sparse.indices, "values": sparse.values }),
+            );
+        }
+
+        let mut payload = vector.metadata.clone().unwrap_or_default();
+        let id = point_id(&vector.id, namespace);
+        if id.as_str().is_some_and(|normalized| normalized != vector.id) {
+            payload.insert(ORIGINAL_ID_KEY.to_string(), json!(vector.id));
+        }
+        if let Some(namespace) = namespace {
+            payload.insert(NAMESPACE_KEY.to_string(), json!(namespace));
+        }
+
+        json!({ "id": id, "vector": vectors, "payload": payload })
+    }
+
+    fn to_vector(&self, point: Point) -> Vector {
+        let (id, metadata) = split_payload(point.id, point.payload);
+        let mut values = Vec::new();
+        let mut sparse_values = None;
+
+        match point.vector {
+            Some(Value::Array(dense)) => values = parse_floats(&dense),
+            Some(Value::Object(mut named)) => {
+                if let Some(Value::Array(dense)) = named.remove(&self.dense_vector_name) {
+                    values = parse_floats(&dense);
+                }
+                if let Some(sparse) = named.remove(&self.sparse_vector_name) {
+                    sparse_values = serde_json::from_value(sparse).ok();
+                }
+            }
+            _ => {}
+        }
+
+        Vector {
+            id,
+            values,
+            metadata,
+            sparse_values,
+        }
+    }
+
+    fn to_query_result(&self, point: Point, include_values: bool) -> QueryResult {
+        let score = point.score.unwrap_or_default();
+        let vector = self.to_vector(point);
+        QueryResult {
+            id: vector.id,
+            score,
+            values: Some(vector.values).filter(|v| include_values && !v.is_empty()),
+            metadata: vector.metadata,
+        }
+    }
+
+    async fn query_points(&self, collection: &str, query: Value, using: &str, options: &QueryOptions) -> Result<Vec<QueryResult>> {
+        let mut body = json!({
+            "query": query,
+            "using": using,
+            "limit": options.top_k.max(1),
+            "with_payload": true,
+            "with_vector": options.include_values,
+        });
+        if let Some(filter) = self.query_filter(options)? {
+            body["filter"] = filter;
+        }
+
+        let response: QueryPoints = self
+            .send(Method::POST, &format!("/collections/{}/points/query", collection), Some(body), collection)
+            .await?;
+
+        Ok(response
+            .points
+            .into_iter()
+            .map(|p| self.to_query_result(p, options.include_values))
+            .map(|mut r| {
+                if !options.include_metadata {
+                    r.metadata = None;
+                }
+                r
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl VectorDatabaseProvider for QdrantClient {
+    async fn list_collections(&self) -> Result<Vec<Collection>> {
+        let response: CollectionList = self.send(Method::GET, "/collections", None, "").await?;
+        let mut collections = Vec::with_capacity(response.collections.len());
+        for entry in response.collections {
+            collections.push(self.describe_collection(&entry.name).await?);
+        }
+        Ok(collections)
+    }
+
+    async fn create_collection(&self, name: &str, dimension: u32, metric: DistanceMetric) -> Result<Collection> {
+        let body = json!({
+            "vectors": {
+                self.dense_vector_name.as_str(): {
+                    "size": dimension,
+                    "distance": distance_name(metric),
+                }
+            },
+            "sparse_vectors": {
+                self.sparse_vector_name.as_str(): {}
+            }
+        });
+        let _: Value = self
+            .send(Method::PUT, &format!("/collections/{}", name), Some(body), name)
+            .await?;
+
+        Ok(Collection {
+            name: name.to_string(),
+            dimension,
+            metric,
+            vector_count: Some(0),
+        })
+    }
+
+    async fn delete_collection(&self, name: &str) -> Result<()> {
+        let _: Value = self
+            .send(Method::DELETE, &format!("/collections/{}", name), None, name)
+            .await?;
+        Ok(())
+    }
+
+    async fn describe_collection(&self, name: &str) -> Result<Collection> {
+        let info: CollectionInfo = self
+            .send(Method::GET, &format!("/collections/{}", name), None, name)
+            .await?;
+
+        let params = match info.config.params.vectors {
+            VectorsConfig::Single(params) => params,
+            VectorsConfig::Named(mut named) => named
+                .remove(&self.dense_vector_name)
+                .or_else(|| named.into_values().next())
+                .ok_or_else(|| Error::Api {
+                    message: format!("Collection {} has no dense vectors", name),
+                    code: None,
+                })?,
+        };
+
+        Ok(Collection {
+            name: name.to_string(),
+            dimension: params.size,
+            metric: parse_distance(&params.distance),
+            vector_count: info.points_count,
+        })
+    }
+
+    async fn upsert(&self, collection: &str, vectors: &[Vector], options: &UpsertOptions) -> Result<UpsertResult> {
+        let path = format!("/collections/{}/points?wait=true", collection);
+        for batch in vectors.chunks(UPSERT_BATCH_SIZE) {
+            let points: Vec<Value> = batch
+                .iter()
+                .map(|v| self.point_body(v, options.namespace.as_deref()))
+                .collect();
+            let _: Value = self
+                .send(Method::PUT, &path, Some(json!({ "points": points })), collection)
+                .await?;
+        }
+
+        Ok(UpsertResult {
+            upserted_count: vectors.len() as u64,
+        })
+    }
+
+    async fn query(&self, collection: &str, vector: &[f32], options: &QueryOptions) -> Result<Vec<QueryResult>> {
+        self.query_points(collection, json!(vector), &self.dense_vector_name, options)
+            .await
+    }
+
+    async fn fetch(&self, collection: &str, ids: &[&str], namespace: Option<&str>) -> Result<Vec<Vector>> {
+        let body = json!({
+            "ids": ids.iter().map(|id| point_id(id, namespace)).collect::<Vec<_>>(),
+            "with_payload": true,
+            "with_vector": true,
+        });
+        let points: Vec<Point> = self
+            .send(Method::POST, &format!("/collections/{}/points", collection), Some(body), collection)
+            .await?;
+
+        Ok(points
+            .into_iter()
+            .filter(|p| match namespace {
+                Some(ns) => p.payload.as_ref().and_then(|m| m.get(NAMESPACE_KEY)).and_then(|v| v.as_str()) == Some(ns),
+                None => true,
+            })
+            .map(|p| self.to_vector(p))
+            .collect())
+    }
+
+    async fn delete(&self, collection: &str, ids: &[&str], options: &DeleteOptions) -> Result<()> {
+        let body = if options.delete_all || options.filter.is_some() {
+            let filter = build_filter(options.filter.as_ref(), options.namespace.as_deref())?
+                .unwrap_or_else(|| json!({ "must": [] }));
+            json!({ "filter": filter })
+        } else if ids.is_empty() {
+            return Ok(());
+        } else {
+            // Point IDs are derived from the namespace, so these only match
+            // points in `options.namespace`.
+            let namespace = options.namespace.as_deref();
+            json!({ "points": ids.iter().map(|id| point_id(id, namespace)).collect::<Vec<_>>() })
+        };
+
+        let _: Value = self
+            .send(
+                Method::POST,
+                &format!("/collections/{}/points/delete?wait=true", collection),
+                Some(body),
+                collection,
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn update(
+        &self,
+        collection: &str,
+        id: &str,
+        values: Option<&[f32]>,
+        metadata: Option<HashMap<String, Value>>,
+    ) -> Result<()> {
+        let point = point_id(id, None);
+
+        if let Some(values) = values {
+            let body = json!({
+                "points": [{ "id": point, "vector": { self.dense_vector_name.as_str(): values } }]
+            });
+            let _: Value = self
+                .send(
+                    Method::PUT,
+                    &format!("/collections/{}/points/vectors?wait=true", collection),
+                    Some(body),
+                    collection,
+                )
+                .await?;
+        }
+
+        if let Some(metadata) = metadata {
+            let body = json!({ "payload": metadata, "points": [point] });
+            let _: Value = self
+                .send(
+                    Method::POST,
+                    &format!("/collections/{}/points/payload?wait=true", collection),
+                    Some(body),
+                    collection,
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl HybridSearchProvider for QdrantClient {
+    async fn hybrid_query(
+        &self,
+        collection: &str,
+        dense_vector: &[f32],
+        sparse_vector: &SparseVector,
+        alpha: f32,
+        options: &QueryOptions,
+    ) -> Result<Vec<QueryResult>> {
+        let alpha = alpha.clamp(0.0, 1.0);
+        let candidates = QueryOptions {
+            top_k: options.top_k.max(1) * 2,
+            ..options.clone()
+        };
+
+        let dense = self
+            .query_points(collection, json!(dense_vector), &self.dense_vector_name, &candidates)
+            .await?;
+        let sparse = self
+            .query_points(
+                collection,
+                json!({ "indices": sparse_vector.indices, "values": sparse_vector.values }),
+                &self.sparse_vector_name,
+                &candidates,
+            )
+            .await?;
+
+        let mut combined: HashMap<String, QueryResult> = HashMap::new();
+        for (results, weight) in [(dense, alpha), (sparse, 1.0 - alpha)] {
+            for result in results {
+                let score = result.score * weight;
+                combined
+                    .entry(result.id.clone())
+                    .and_modify(|existing| existing.score += score)
+                    .or_insert(QueryResult { score, ..result });
+            }
+        }
+
+        let mut results: Vec<QueryResult> = combined.into_values().collect();
+        results.sort_by(|a, b| b.score.total_cmp(&a.score));
+        results.truncate(options.top_k.max(1) as usize);
+        Ok(results)
+    }
+}
+
+// Qdrant IDs must be unsigned integers or UUIDs, and are unique per
+// collection, so namespaced IDs are hashed together with their namespace to
+// keep the same ID in two namespaces apart.
+fn point_id(id: &str, namespace: Option<&str>) -> Value {
+    if let Some(namespace) = namespace {
+        let key = format!("{}\0{}", namespace, id);
+        return json!(Uuid::new_v5(&Uuid::NAMESPACE_OID, key.as_bytes()).to_string());
+    }
+    if let Ok(number) = id.parse::<u64>() {
+        return json!(number);
+    }
+    match Uuid::parse_str(id) {
+        Ok(uuid) => json!(uuid.to_string()),
+        Err(_) => json!(Uuid::new_v5(&Uuid::NAMESPACE_OID, id.as_bytes()).to_string()),
+    }
+}
+
+fn split_payload(id: Value, payload: Option<HashMap<String, Value>>) -> (String, Option<HashMap<String, Value>>) {
+    let mut payload = payload.unwrap_or_default();
+    payload.remove(NAMESPACE_KEY);
+    let id = match payload.remove(ORIGINAL_ID_KEY) {
+        Some(Value::String(original)) => original,
+        _ => match id {
+            Value::String(s) => s,
+            other => other.to_string(),
+        },
+    };
+    (id, Some(payload).filter(|p| !p.is_empty()))
+}
+
+fn parse_floats(values: &[Value]) -> Vec<f32> {
+    values.iter().filter_map(|v| v.as_f64()).map(|v| v as f32).collect()
+}
+
+fn distance_name(metric: DistanceMetric) -> &'static str {
+    match metric {
+        DistanceMetric::Cosine => "Cosine",
+        DistanceMetric::Euclidean => "Euclid",
+        DistanceMetric::DotProduct => "Dot",
+    }
+}
+
+fn parse_distance(distance: &str) -> DistanceMetric {
+    match distance {
+        "Euclid" => DistanceMetric::Euclidean,
+        "Dot" => DistanceMetric::DotProduct,
+        _ => DistanceMetric::Cosine,
+    }
+}
+
+fn build_filter(filter: Option<&HashMap<String, Value>>, namespace: Option<&str>) -> Result<Option<Value>> {
+    let mut must = Vec::new();
+    if let Some(filter) = filter {
+        must.push(translate(&parse_filter(filter)?));
+    }
+    if let Some(namespace) = namespace {
+        must.push(json!({ "key": NAMESPACE_KEY, "match": { "value": namespace } }));
+    }
+
+    Ok(match must.len() {
+        0 => None,
+        _ => Some(json!({ "must": must })),
+    })
+}
+
+fn translate(expr: &FilterExpr) -> Value {
+    match expr {
+        FilterExpr::And(items) => json!({ "must": items.iter().map(translate).collect::<Vec<_>>() }),
+        FilterExpr::Or(items) => json!({ "should": items.iter().map(translate).collect::<Vec<_>>() }),
+        FilterExpr::Field { key, op, value } => match op {
+            FilterOp::Eq => json!({ "key": key, "match": { "value": value } }),
+            FilterOp::Ne => json!({ "must_not": [{ "key": key, "match": { "value": value } }] }),
+            FilterOp::In => json!({ "key": key, "match": { "any": value } }),
+            FilterOp::Nin => json!({ "key": key, "match": { "except": value } }),
+            FilterOp::Gt => json!({ "key": key, "range": { "gt": value } }),
+            FilterOp::Gte => json!({ "key": key, "range": { "gte": value } }),
+            FilterOp::Lt => json!({ "key": key, "range": { "lt": value } }),
+            FilterOp::Lte => json!({ "key": key, "range": { "lte": value } }),
+        },
+    }
+}
+
+#[derive(Deserialize)]
+struct QdrantResponse<T> {
+    result: T,
+}
+
+#[derive(Deserialize)]
+struct QdrantErrorResponse {
+    status: QdrantErrorStatus,
+}
+
+#[derive(Deserialize)]
+struct QdrantErrorStatus {
+    error: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CollectionList {
+    #[serde(default)]
+    collections: Vec<CollectionName>,
+}
+
+#[derive(Deserialize)]
+struct CollectionName {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct CollectionInfo {
+    points_count: Option<u64>,
+    config: CollectionConfig,
+}
+
+#[derive(Deserialize)]
+struct CollectionConfig {
+    params: CollectionParams,
+}
+
+#[derive(Deserialize)]
+struct CollectionParams {
+    vectors: VectorsConfig,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum VectorsConfig {
+    Single(VectorParams),
+    Named(HashMap<String, VectorParams>),
+}
+
+#[derive(Deserialize)]
+struct VectorParams {
+    size: u32,
+    distance: String,
+}
+
+#[derive(Deserialize)]
+struct QueryPoints {
+    #[serde(default)]
+    points: Vec<Point>,
+}
+
+#[derive(Deserialize)]
+struct Point {
+    id: Value,
+    score: Option<f32>,
+    payload: Option<HashMap<String, Value>>,
+    vector: Option<Value>,
+}
@@ -0,0 +1,747 @@
+use crate::filter::{parse_filter, FilterExpr, FilterOp};
+use crate::{
+    Collection, DeleteOptions, DistanceMetric, Error, QueryOptions, QueryResult, Result, UpsertOptions, UpsertResult,
+    Vector, VectorDatabaseProvider,
+};
+use async_trait::async_trait;
+use reqwest::{Method, RequestBuilder};
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+const DEFAULT_URL: &str = "http://localhost:8080";
+const DEFAULT_TENANT: &str = "default";
+const UPSERT_BATCH_SIZE: usize = 100;
+const ORIGINAL_ID_KEY: &str = "swissknifeId";
+const DIMENSION_PREFIX: &str = "swissknife:dimension=";
+
+#[derive(Clone)]
+pub struct WeaviateClient {
+    base_url: String,
+    api_key: Option<String>,
+    tenancy: Arc<Mutex<HashMap<String, bool>>>,
+    http: reqwest::Client,
+}
+
+impl WeaviateClient {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            api_key: None,
+            tenancy: Arc::new(Mutex::new(HashMap::new())),
+            http: reqwest::Client::new(),
+        }
+    }
+
+    pub fn local() -> Self {
+        Self::new(DEFAULT_URL)
+    }
+
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    pub async fn add_object(&self, class: &str, properties: Value, vector: Option<Vec<f32>>) -> Result<String> {
+        let class = class_name(class);
+        let mut body = json!({ "class": class, "properties": properties });
+        if let Some(vector) = vector {
+            body["vector"] = json!(vector);
+        }
+        if let Some(tenant) = self.tenant(&class, None).await? {
+            body["tenant"] = json!(tenant);
+        }
+
+        let object: WeaviateObject = self.call(Method::POST, "/v1/objects", Some(body), &class).await?;
+        Ok(object.id)
+    }
+
+    pub async fn search(&self, class: &str, query: &str, limit: u32) -> Result<Vec<QueryResult>> {
+        let options = QueryOptions {
+            top_k: limit,
+            include_metadata: true,
+            ..Default::default()
+        };
+        let near_text = format!("nearText: {{concepts: [{}]}}", json!(query));
+        self.get_objects(&class_name(class), near_text, &options).await
+    }
+
+    pub async fn vector_search(&self, class: &str, vector: &[f32], limit: u32) -> Result<Vec<QueryResult>> {
+        let options = QueryOptions {
+            top_k: limit,
+            include_metadata: true,
+            ..Default::default()
+        };
+        self.query(class, vector, &options).await
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let request = self.http.request(method, format!("{}{}", self.base_url, path));
+        match &self.api_key {
+            Some(key) => request.bearer_auth(key),
+            None => request,
+        }
+    }
+
+    async fn call<T: for<'de> Deserialize<'de>>(&self, method: Method, path: &str, body: Option<Value>, class: &str) -> Result<T> {
+        let mut request = self.request(method, path);
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+
+        let response = request.send().await?;
+        let status = response.status().as_u16();
+
+        if !response.status().is_success() {
+            let body = response.text().await.unwrap_or_default();
+            let message = serde_json::from_str::<WeaviateError>(&body)
+                .ok()
+                .and_then(|e| e.error.into_iter().next())
+                .map(|e| e.message)
+                .unwrap_or(body);
+            return Err(match status {
+                401 | 403 => Error::Auth(message),
+                404 => Error::IndexNotFound(class.to_string()),
+                429 => Error::RateLimited,
+                _ => Error::Api {
+                    message,
+                    code: Some(status.to_string()),
+                },
+            });
+        }
+
+        let text = response.text().await?;
+        Ok(serde_json::from_str(if text.trim().is_empty() { "null" } else { &text })?)
+    }
+
+    async fn graphql(&self, query: String, class: &str) -> Result<Value> {
+        let response: GraphqlResponse = self
+            .call(Method::POST, "/v1/graphql", Some(json!({ "query": query })), class)
+            .await?;
+        if let Some(error) = response.errors.into_iter().next() {
+            return Err(Error::Api {
+                message: error.message,
+                code: None,
+            });
+        }
+        Ok(response.data)
+    }
+
+    async fn class_schema(&self, class: &str) -> Result<ClassSchema> {
+        let schema: ClassSchema = self
+            .call(Method::GET, &format!("/v1/schema/{}", class), None, class)
+            .await?;
+        self.tenancy
+            .lock()
+            .unwrap()
+            .insert(class.to_string(), schema.multi_tenant());
+        Ok(schema)
+    }
+
+    async fn tenant(&self, class: &str, namespace: Option<&str>) -> Result<Option<String>> {
+        let cached = self.tenancy.lock().unwrap().get(class).copied();
+        let multi_tenant = match cached {
+            Some(multi_tenant) => multi_tenant,
+            None => self.class_schema(class).await?.multi_tenant(),
+        };
+        resolve_tenant(class, multi_tenant, namespace)
+    }
+
+    async fn tenants(&self, class: &str) -> Result<Vec<Option<String>>> {
+        if self.tenant(class, None).await?.is_none() {
+            return Ok(vec![None]);
+        }
+        let tenants: Vec<Tenant> = self
+            .call(Method::GET, &format!("/v1/schema/{}/tenants", class), None, class)
+            .await?;
+        Ok(tenants.into_iter().map(|t| Some(t.name)).collect())
+    }
+
+    async fn count(&self, class: &str, tenant: Option<&str>) -> Result<u64> {
+        let args = tenant.map(|t| format!("(tenant: {})", json!(t))).unwrap_or_default();
+        let data = self
+            .graphql(format!("{{ Aggregate {{ {}{} {{ meta {{ count }} }} }} }}", class, args), class)
+            .await?;
+        Ok(data["Aggregate"][class][0]["meta"]["count"].as_u64().unwrap_or_default())
+    }
+
+    async fn batch_delete(&self, class: &str, tenant: Option<&str>, condition: Value) -> Result<BatchDeleteResults> {
+        let mut path = "/v1/batch/objects".to_string();
+        if let Some(tenant) = tenant {
+            path.push_str(&format!("?tenant={}", tenant));
+        }
+        let body = json!({ "match": { "class": class, "where": condition }, "output": "minimal" });
+        let response: Option<BatchDeleteResponse> = self.call(Method::DELETE, &path, Some(body), class).await?;
+        Ok(response.map(|r| r.results).unwrap_or_default())
+    }
+
+    async fn get_objects(&self, class: &str, search: String, options: &QueryOptions) -> Result<Vec<QueryResult>> {
+        let schema = self.class_schema(class).await?;
+        let tenant = resolve_tenant(class, schema.multi_tenant(), options.namespace.as_deref())?;
+
+        let mut args = vec![search, format!("limit: {}", options.top_k.max(1))];
+        if let Some(filter) = options.filter.as_ref().map(translate_filter).transpose()? {
+            args.push(format!("where: {}", graphql_value(&filter)));
+        }
+        if let Some(tenant) = tenant {
+            args.push(format!("tenant: {}", json!(tenant)));
+        }
+        let additional = if options.include_values { "id distance vector" } else { "id distance" };
+        let query = format!(
+            "{{ Get {{ {}({}) {{ {} _additional {{ {} }} }} }} }}",
+            class,
+            args.join(", "),
+            schema.scalar_properties().join(" "),
+            additional
+        );
+
+        let data = self.graphql(query, class).await?;
+        let metric = schema.metric();
+        Ok(data["Get"][class]
+            .as_array()
+            .map(|hits| hits.iter().filter_map(|hit| hit.as_object().cloned()).collect::<Vec<_>>())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|hit| to_query_result(hit, metric, options))
+            .collect())
+    }
+}
+
+#[async_trait]
+impl VectorDatabaseProvider for WeaviateClient {
+    async fn list_collections(&self) -> Result<Vec<Collection>> {
+        let schema: Schema = self.call(Method::GET, "/v1/schema", None, "").await?;
+        let mut tenancy = self.tenancy.lock().unwrap();
+        Ok(schema
+            .classes
+            .into_iter()
+            .map(|class| {
+                tenancy.insert(class.class.clone(), class.multi_tenant());
+                class.into_collection(None)
+            })
+            .collect())
+    }
+
+    async fn create_collection(&self, name: &str, dimension: u32, metric: DistanceMetric) -> Result<Collection> {
+        let class = class_name(name);
+        let body = json!({
+            "class": class,
+            "description": format!("{}{}", DIMENSION_PREFIX, dimension),
+            "vectorizer": "none",
+            "vectorIndexConfig": { "distance": distance_name(metric) },
+            "multiTenancyConfig": { "enabled": true, "autoTenantCreation": true },
+            "properties": [{ "name": ORIGINAL_ID_KEY, "dataType": ["text"] }],
+        });
+        let _: Value = self.call(Method::POST, "/v1/schema", Some(body), &class).await?;
+        self.tenancy.lock().unwrap().insert(class.clone(), true);
+
+        Ok(Collection {
+            name: class,
+            dimension,
+            metric,
+            vector_count: Some(0),
+        })
+    }
+
+    async fn delete_collection(&self, name: &str) -> Result<()> {
+        let class = class_name(name);
+        let _: Value = self
+            .call(Method::DELETE, &format!("/v1/schema/{}", class), None, &class)
+            .await?;
+        self.tenancy.lock().unwrap().remove(&class);
+        Ok(())
+    }
+
+    async fn describe_collection(&self, name: &str) -> Result<Collection> {
+        let class = class_name(name);
+        let schema = self.class_schema(&class).await?;
+        let mut vector_count = 0;
+        for tenant in self.tenants(&class).await? {
+            vector_count += self.count(&class, tenant.as_deref()).await?;
+        }
+        Ok(schema.into_collection(Some(vector_count)))
+    }
+
+    async fn upsert(&self, collection: &str, vectors: &[Vector], options: &UpsertOptions) -> Result<UpsertResult> {
+        let class = class_name(collection);
+        let tenant = self.tenant(&class, options.namespace.as_deref()).await?;
+
+        for batch in vectors.chunks(UPSERT_BATCH_SIZE) {
+            let objects = batch
+                .iter()
+                .map(|v| object_body(&class, v, tenant.as_deref()))
+                .collect::<Result<Vec<_>>>()?;
+            let results: Vec<BatchObjectResult> = self
+                .call(Method::POST, "/v1/batch/objects", Some(json!({ "objects": objects })), &class)
+                .await?;
+            if let Some(error) = results
+                .into_iter()
+                .filter_map(|r| r.result.and_then(|r| r.errors))
+                .flat_map(|e| e.error)
+                .next()
+            {
+                return Err(Error::Api {
+                    message: error.message,
+                    code: None,
+                });
+            }
+        }
+
+        Ok(UpsertResult {
+            upserted_count: vectors.len() as u64,
+        })
+    }
+
+    async fn query(&self, collection: &str, vector: &[f32], options: &QueryOptions) -> Result<Vec<QueryResult>> {
+        let near_vector = format!("nearVector: {{vector: {}}}", json!(vector));
+        self.get_objects(&class_name(collection), near_vector, options).await
+    }
+
+    async fn fetch(&self, collection: &str, ids: &[&str], namespace: Option<&str>) -> Result<Vec<Vector>> {
+        let class = class_name(collection);
+        let tenant = self.tenant(&class, namespace).await?;
+        let mut vectors = Vec::with_capacity(ids.len());
+
+        for id in ids {
+            let mut path = format!("/v1/objects/{}/{}?include=vector", class, object_id(id));
+            if let Some(tenant) = &tenant {
+                path.push_str(&format!("&tenant={}", tenant));
+            }
+            match self.call::<WeaviateObject>(Method::GET, &path, None, &class).await {
+                Ok(object) => vectors.push(object.into_vector()),
+                Err(Error::IndexNotFound(_)) => continue,
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(vectors)
+    }
+
+    async fn delete(&self, collection: &str, ids: &[&str], options: &DeleteOptions) -> Result<()> {
+        let class = class_name(collection);
+        let tenant = self.tenant(&class, options.namespace.as_deref()).await?;
+        let filter = options.filter.as_ref().map(translate_filter).transpose()?;
+
+        let condition = if options.delete_all {
+            if let Some(tenant) = tenant {
+                let _: Value = self
+                    .call(
+                        Method::DELETE,
+                        &format!("/v1/schema/{}/tenants", class),
+                        Some(json!([tenant])),
+                        &class,
+                    )
+                    .await?;
+                return Ok(());
+            }
+            // No object has the nil UUID, so this matches the whole class. Each batch
+            // delete is capped at the server's query limit, so repeat until drained.
+            let everything = json!({ "path": ["id"], "operator": "NotEqual", "valueText": Uuid::nil().to_string() });
+            loop {
+                let results = self.batch_delete(&class, None, everything.clone()).await?;
+                if results.successful == 0 || results.matches < results.limit {
+                    return Ok(());
+                }
+            }
+        } else if let Some(filter) = filter {
+            filter
+        } else if ids.is_empty() {
+            return Ok(());
+        } else {
+            json!({
+                "operator": "Or",
+                "operands": ids
+                    .iter()
+                    .map(|id| json!({ "path": ["id"], "operator": "Equal", "valueText": object_id(id) }))
+                    .collect::<Vec<_>>(),
+            })
+        };
+
+        self.batch_delete(&class, tenant.as_deref(), condition).await?;
+        Ok(())
+    }
+
+    async fn update(
+        &self,
+        collection: &str,
+        id: &str,
+        values: Option<&[f32]>,
+        metadata: Option<HashMap<String, Value>>,
+    ) -> Result<()> {
+        let class = class_name(collection);
+        let properties = metadata.unwrap_or_default();
+        check_properties(&properties)?;
+
+        let path = format!("/v1/objects/{}/{}", class, object_id(id));
+        for tenant in self.tenants(&class).await? {
+            let mut body = json!({ "class": class, "properties": properties });
+            if let Some(values) = values {
+                body["vector"] = json!(values);
+            }
+            if let Some(tenant) = tenant {
+                body["tenant"] = json!(tenant);
+            }
+            match self.call::<Value>(Method::PATCH, &path, Some(body), &class).await {
+                Ok(_) => return Ok(()),
+                Err(Error::IndexNotFound(_)) => continue,
+                Err(e) => return Err(e),
+            }
+        }
+
+        Err(Error::Api {
+            message: format!("Vector {} not found in {}", id, collection),
+            code: Some("not_found".to_string()),
+        })
+    }
+}
+
+fn class_name(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn resolve_tenant(class: &str, multi_tenant: bool, namespace: Option<&str>) -> Result<Option<String>> {
+    match (multi_tenant, namespace) {
+        (true, namespace) => Ok(Some(namespace.unwrap_or(DEFAULT_TENANT).to_string())),
+        (false, None) => Ok(None),
+        (false, Some(_)) => Err(Error::Unsupported(format!(
+            "Weaviate class {} is not multi-tenant, so namespaces cannot be used",
+            class
+        ))),
+    }
+}
+
+fn object_id(id: &str) -> String {
+    match Uuid::parse_str(id) {
+        Ok(uuid) => uuid.to_string(),
+        Err(_) => Uuid::new_v5(&Uuid::NAMESPACE_OID, id.as_bytes()).to_string(),
+    }
+}
+
+fn check_properties(properties: &HashMap<String, Value>) -> Result<()> {
+    for (key, value) in properties {
+        let mut chars = key.chars();
+        let valid = chars.next().is_some_and(|c| c == '_' || c.is_ascii_alphabetic())
+            && chars.all(|c| c == '_' || c.is_ascii_alphanumeric());
+        if !valid {
+            return Err(Error::Unsupported(format!(
+                "Weaviate property names must match [_A-Za-z][_0-9A-Za-z]*, got {}",
+                key
+            )));
+        }
+        if value.is_object() {
+            return Err(Error::Unsupported(format!(
+                "Weaviate backend does not support nested metadata objects ({})",
+                key
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn object_body(class: &str, vector: &Vector, tenant: Option<&str>) -> Result<Value> {
+    if vector.sparse_values.is_some() {
+        return Err(Error::Unsupported("Weaviate does not support sparse vectors".to_string()));
+    }
+
+    let mut properties = vector.metadata.clone().unwrap_or_default();
+    check_properties(&properties)?;
+    let id = object_id(&vector.id);
+    if id != vector.id {
+        properties.insert(ORIGINAL_ID_KEY.to_string(), json!(vector.id));
+    }
+
+    let mut body = json!({ "class": class, "id": id, "properties": properties, "vector": vector.values });
+    if let Some(tenant) = tenant {
+        body["tenant"] = json!(tenant);
+    }
+    Ok(body)
+}
+
+fn split_properties(id: &str, properties: Map<String, Value>) -> (String, Option<HashMap<String, Value>>) {
+    let mut metadata: HashMap<String, Value> = properties.into_iter().filter(|(_, v)| !v.is_null()).collect();
+    let id = match metadata.remove(ORIGINAL_ID_KEY) {
+        Some(Value::String(original)) => original,
+        _ => id.to_string(),
+    };
+    (id, Some(metadata).filter(|m| !m.is_empty()))
+}
+
+fn parse_floats(value: &Value) -> Vec<f32> {
+    value
+        .as_array()
+        .map(|values| values.iter().filter_map(|v| v.as_f64()).map(|v| v as f32).collect())
+        .unwrap_or_default()
+}
+
+fn to_query_result(mut hit: Map<String, Value>, metric: DistanceMetric, options: &QueryOptions) -> QueryResult {
+    let additional = hit.remove("_additional").unwrap_or_default();
+    let distance = additional["distance"].as_f64().unwrap_or_default() as f32;
+    let (id, metadata) = split_properties(additional["id"].as_str().unwrap_or_default(), hit);
+
+    QueryResult {
+        id,
+        score: score_from_distance(metric, distance),
+        values: Some(parse_floats(&additional["vector"])).filter(|v| options.include_values && !v.is_empty()),
+        metadata: metadata.filter(|_| options.include_metadata),
+    }
+}
+
+fn distance_name(metric: DistanceMetric) -> &'static str {
+    match metric {
+        DistanceMetric::Cosine => "cosine",
+        DistanceMetric::Euclidean => "l2-squared",
+        DistanceMetric::DotProduct => "dot",
+    }
+}
+
+fn parse_distance(distance: &str) -> DistanceMetric {
+    match distance {
+        "l2-squared" => DistanceMetric::Euclidean,
+        "dot" => DistanceMetric::DotProduct,
+        _ => DistanceMetric::Cosine,
+    }
+}
+
+// Weaviate reports distances; flip them so higher scores are always better.
+fn score_from_distance(metric: DistanceMetric, distance: f32) -> f32 {
+    match metric {
+        DistanceMetric::Cosine => 1.0 - distance,
+        DistanceMetric::Euclidean => -distance.max(0.0).sqrt(),
+        DistanceMetric::DotProduct => -distance,
+    }
+}
+
+fn translate_filter(filter: &HashMap<String, Value>) -> Result<Value> {
+    translate(&parse_filter(filter)?)
+}
+
+fn translate(expr: &FilterExpr) -> Result<Value> {
+    match expr {
+        FilterExpr::And(items) => Ok(json!({
+            "operator": "And",
+            "operands": items.iter().map(translate).collect::<Result<Vec<_>>>()?,
+        })),
+        FilterExpr::Or(items) => Ok(json!({
+            "operator": "Or",
+            "operands": items.iter().map(translate).collect::<Result<Vec<_>>>()?,
+        })),
+        FilterExpr::Field { key, op, value } => match op {
+            FilterOp::Eq => condition(key, "Equal", value),
+            FilterOp::Ne => condition(key, "NotEqual", value),
+            FilterOp::Gt => condition(key, "GreaterThan", value),
+            FilterOp::Gte => condition(key, "GreaterThanEqual", value),
+            FilterOp::Lt => condition(key, "LessThan", value),
+            FilterOp::Lte => condition(key, "LessThanEqual", value),
+            FilterOp::In => condition(key, "ContainsAny", value),
+            FilterOp::Nin => {
+                let items = value.as_array().cloned().unwrap_or_default();
+                Ok(json!({
+                    "operator": "And",
+                    "operands": items
+                        .iter()
+                        .map(|item| condition(key, "NotEqual", item))
+                        .collect::<Result<Vec<_>>>()?,
+                }))
+            }
+        },
+    }
+}
+
+fn condition(key: &str, operator: &str, value: &Value) -> Result<Value> {
+    let mut condition = json!({ "path": [key], "operator": operator });
+    condition[value_field(key, value)?] = value.clone();
+    Ok(condition)
+}
+
+fn value_field(key: &str, value: &Value) -> Result<&'static str> {
+    match value {
+        Value::String(_) => Ok("valueText"),
+        Value::Bool(_) => Ok("valueBoolean"),
+        Value::Number(n) if n.is_i64() || n.is_u64() => Ok("valueInt"),
+        Value::Number(_) => Ok("valueNumber"),
+        Value::Array(items) => {
+            let first = items
+                .first()
+                .ok_or_else(|| Error::InvalidFilter(format!("Operator on {} expects a non-empty array", key)))?;
+            Ok(match value_field(key, first)? {
+                "valueText" => "valueTextArray",
+                "valueBoolean" => "valueBooleanArray",
+                "valueInt" => "valueIntArray",
+                _ => "valueNumberArray",
+            })
+        }
+        _ => Err(Error::InvalidFilter(format!("Unsupported filter value for {}", key))),
+    }
+}
+
+fn graphql_value(value: &Value) -> String {
+    match value {
+        Value::Object(fields) => {
+            let fields: Vec<String> = fields
+                .iter()
+                .map(|(key, value)| match (key.as_str(), value) {
+                    ("operator", Value::String(operator)) => format!("{}: {}", key, operator),
+                    _ => format!("{}: {}", key, graphql_value(value)),
+                })
+                .collect();
+            format!("{{{}}}", fields.join(", "))
+        }
+        Value::Array(items) => format!("[{}]", items.iter().map(graphql_value).collect::<Vec<_>>().join(", ")),
+        other => other.to_string(),
+    }
+}
+
+#[derive(Deserialize)]
+struct WeaviateError {
+    #[serde(default)]
+    error: Vec<ErrorMessage>,
+}
+
+#[derive(Deserialize)]
+struct ErrorMessage {
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct GraphqlResponse {
+    #[serde(default)]
+    data: Value,
+    #[serde(default)]
+    errors: Vec<ErrorMessage>,
+}
+
+#[derive(Deserialize)]
+struct Schema {
+    #[serde(default)]
+    classes: Vec<ClassSchema>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ClassSchema {
+    class: String,
+    description: Option<String>,
+    vector_index_config: Option<VectorIndexConfig>,
+    multi_tenancy_config: Option<MultiTenancyConfig>,
+    #[serde(default)]
+    properties: Vec<Property>,
+}
+
+impl ClassSchema {
+    fn multi_tenant(&self) -> bool {
+        self.multi_tenancy_config.as_ref().is_some_and(|c| c.enabled)
+    }
+
+    fn metric(&self) -> DistanceMetric {
+        self.vector_index_config
+            .as_ref()
+            .and_then(|c| c.distance.as_deref())
+            .map(parse_distance)
+            .unwrap_or(DistanceMetric::Cosine)
+    }
+
+    fn scalar_properties(&self) -> Vec<&str> {
+        self.properties
+            .iter()
+            .filter(|p| {
+                p.data_type
+                    .first()
+                    .is_some_and(|t| t.starts_with(|c: char| c.is_ascii_lowercase()) && !t.starts_with("object"))
+            })
+            .map(|p| p.name.as_str())
+            .collect()
+    }
+
+    fn into_collection(self, vector_count: Option<u64>) -> Collection {
+        let metric = self.metric();
+        Collection {
+            dimension: self
+                .description
+                .as_deref()
+                .and_then(|d| d.strip_prefix(DIMENSION_PREFIX))
+                .and_then(|d| d.parse().ok())
+                .unwrap_or_default(),
+            name: self.class,
+            metric,
+            vector_count,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct VectorIndexConfig {
+    distance: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct MultiTenancyConfig {
+    #[serde(default)]
+    enabled: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Property {
+    name: String,
+    #[serde(default)]
+    data_type: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct Tenant {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct WeaviateObject {
+    id: String,
+    #[serde(default)]
+    properties: Map<String, Value>,
+    #[serde(default)]
+    vector: Value,
+}
+
+impl WeaviateObject {
+    fn into_vector(self) -> Vector {
+        let values = parse_floats(&self.vector);
+        let (id, metadata) = split_properties(&self.id, self.properties);
+        Vector {
+            id,
+            values,
+            metadata,
+            sparse_values: None,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct BatchObjectResult {
+    result: Option<BatchResultStatus>,
+}
+
+#[derive(Deserialize)]
+struct BatchResultStatus {
+    errors: Option<WeaviateError>,
+}
+
+#[derive(Deserialize)]
+struct BatchDeleteResponse {
+    #[serde(default)]
+    results: BatchDeleteResults,
+}
+
+#[derive(Default, Deserialize)]
+struct BatchDeleteResults {
+    #[serde(default)]
+    matches: u64,
+    #[serde(default)]
+    limit: u64,
+    #[serde(default)]
+    successful: u64,
+}
@@ -0,0 +1,1100 @@
+#[cfg(feature = "pinecone")]
+mod pinecone {
+    use serde_json::json;
+    use std::collections::HashMap;
+    use swissknife_vectordb_sdk::pinecone::PineconeClient;
+    use swissknife_vectordb_sdk::{DeleteOptions, Error, QueryOptions, UpsertOptions, Vector, VectorDatabaseProvider};
+    use wiremock::matchers::{body_json, body_partial_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn mock_client(server: &MockServer) -> PineconeClient {
+        PineconeClient::new("pc-key")
+            .with_control_plane_url(server.uri())
+            .with_index_host("docs", server.uri())
+    }
+
+    #[tokio::test]
+    async fn test_pinecone_upsert_batches_vectors() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/vectors/upsert"))
+            .and(header("Api-Key", "pc-key"))
+            .and(body_partial_json(json!({ "namespace": "prod" })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "upsertedCount": 100 })))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let vectors: Vec<Vector> = (0..150)
+            .map(|i| Vector {
+                id: format!("v{}", i),
+                values: vec![i as f32, 1.0],
+                metadata: None,
+                sparse_values: None,
+            })
+            .collect();
+        let options = UpsertOptions {
+            namespace: Some("prod".to_string()),
+        };
+
+        let result = mock_client(&server).upsert("docs", &vectors, &options).await.unwrap();
+
+        assert_eq!(result.upserted_count, 200);
+        let requests = server.received_requests().await.unwrap();
+        let first: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(first["vectors"].as_array().unwrap().len(), 100);
+        assert_eq!(first["vectors"][1], json!({ "id": "v1", "values": [1.0, 1.0] }));
+    }
+
+    #[tokio::test]
+    async fn test_pinecone_query_translates_filter() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/query"))
+            .and(body_json(json!({
+                "vector": [0.5, 0.5],
+                "topK": 2,
+                "includeValues": false,
+                "includeMetadata": true,
+                "filter": { "genre": { "$eq": "drama" } },
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "matches": [
+                    { "id": "a", "score": 0.9, "values": [], "metadata": { "genre": "drama" } },
+                    { "id": "b", "score": 0.4, "values": [] },
+                ]
+            })))
+            .mount(&server)
+            .await;
+
+        let options = QueryOptions {
+            top_k: 2,
+            include_metadata: true,
+            filter: Some(HashMap::from([("genre".to_string(), json!("drama"))])),
+            ..Default::default()
+        };
+
+        let results = mock_client(&server).query("docs", &[0.5, 0.5], &options).await.unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].id, "a");
+        assert_eq!(results[0].score, 0.9);
+        assert!(results[0].values.is_none());
+        assert_eq!(results[0].metadata.as_ref().unwrap()["genre"], "drama");
+        assert!(results[1].metadata.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_pinecone_delete_by_ids_and_all() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/vectors/delete"))
+            .and(body_json(json!({ "namespace": "", "ids": ["a", "b"] })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/vectors/delete"))
+            .and(body_json(json!({ "namespace": "prod", "deleteAll": true })))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+        client.delete("docs", &["a", "b"], &DeleteOptions::default()).await.unwrap();
+        let all = DeleteOptions {
+            namespace: Some("prod".to_string()),
+            delete_all: true,
+            ..Default::default()
+        };
+        client.delete("docs", &[], &all).await.unwrap();
+        client.delete("docs", &[], &DeleteOptions::default()).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_pinecone_resolves_index_host_once() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/indexes/movies"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "name": "movies",
+                "dimension": 2,
+                "metric": "cosine",
+                "host": server.uri(),
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/vectors/delete"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let client = PineconeClient::new("pc-key").with_control_plane_url(server.uri());
+        client.delete("movies", &["a"], &DeleteOptions::default()).await.unwrap();
+        client.delete("movies", &["b"], &DeleteOptions::default()).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_pinecone_error_mapping() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/indexes/missing"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+                "error": { "code": "NOT_FOUND", "message": "Resource missing not found" }
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/query"))
+            .respond_with(ResponseTemplate::new(401).set_body_json(json!({ "message": "Invalid API Key" })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/vectors/upsert"))
+            .respond_with(ResponseTemplate::new(429))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/vectors/update"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+                "error": { "code": "INVALID_ARGUMENT", "message": "Vector dimension 3 does not match 2" }
+            })))
+            .mount(&server)
+            .await;
+
+        let client = mock_client(&server);
+
+        let missing = client.describe_collection("missing").await.unwrap_err();
+        assert!(matches!(missing, Error::IndexNotFound(ref name) if name == "missing"));
+
+        let auth = client.query("docs", &[1.0, 0.0], &QueryOptions::default()).await.unwrap_err();
+        assert!(matches!(auth, Error::Auth(ref message) if message == "Invalid API Key"));
+
+        let vector = Vector {
+            id: "a".to_string(),
+            values: vec![1.0, 0.0],
+            metadata: None,
+            sparse_values: None,
+        };
+        let limited = client.upsert("docs", &[vector], &UpsertOptions::default()).await.unwrap_err();
+        assert!(matches!(limited, Error::RateLimited));
+
+        match client.update("docs", "a", Some(&[1.0, 2.0, 3.0]), None).await.unwrap_err() {
+            Error::Api { message, code } => {
+                assert_eq!(message, "Vector dimension 3 does not match 2");
+                assert_eq!(code.as_deref(), Some("400"));
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+}
+
+#[cfg(feature = "qdrant")]
+mod qdrant {
+    use serde_json::json;
+    use swissknife_vectordb_sdk::qdrant::QdrantClient;
+    use swissknife_vectordb_sdk::{DeleteOptions, Error, QueryOptions, UpsertOptions, Vector, VectorDatabaseProvider};
+    use wiremock::matchers::{body_json, header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn ok(result: serde_json::Value) -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_json(json!({ "result": result, "status": "ok", "time": 0.001 }))
+    }
+
+    #[tokio::test]
+    async fn test_qdrant_upsert_sends_named_vectors() {
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/collections/docs/points"))
+            .and(query_param("wait", "true"))
+            .and(header("api-key", "qd-key"))
+            .respond_with(ok(json!({ "operation_id": 1, "status": "completed" })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let vectors = vec![
+            Vector {
+                id: "42".to_string(),
+                values: vec![0.1, 0.2],
+                metadata: Some([("title".to_string(), json!("Dune"))].into()),
+                sparse_values: None,
+            },
+            Vector {
+                id: "doc-a".to_string(),
+                values: vec![0.3, 0.4],
+                metadata: None,
+                sparse_values: None,
+            },
+        ];
+        let options = UpsertOptions {
+            namespace: Some("prod".to_string()),
+        };
+
+        let client = QdrantClient::new(server.uri()).with_api_key("qd-key");
+        let result = client.upsert("docs", &vectors, &options).await.unwrap();
+
+        assert_eq!(result.upserted_count, 2);
+        let requests = server.received_requests().await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert!(body["points"][0]["id"].as_str().unwrap().contains('-'));
+        assert_eq!(body["points"][0]["vector"], json!({ "dense": [0.1f32, 0.2f32] }));
+        assert_eq!(
+            body["points"][0]["payload"],
+            json!({ "title": "Dune", "_namespace": "prod", "_original_id": "42" })
+        );
+        assert!(body["points"][1]["id"].as_str().unwrap().contains('-'));
+        assert_eq!(body["points"][1]["payload"]["_original_id"], "doc-a");
+    }
+
+    #[tokio::test]
+    async fn test_qdrant_query_restores_original_ids() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/collections/docs/points/query"))
+            .and(body_json(json!({
+                "query": [1.0, 0.0],
+                "using": "dense",
+                "limit": 2,
+                "with_payload": true,
+                "with_vector": false,
+                "filter": { "must": [{ "key": "_namespace", "match": { "value": "prod" } }] },
+            })))
+            .respond_with(ok(json!({
+                "points": [
+                    {
+                        "id": "0b7c4b1e-2d1f-5f3a-9a4e-6f0c2b3d4e5f",
+                        "score": 0.98,
+                        "payload": { "_original_id": "doc-a", "_namespace": "prod", "title": "Dune" },
+                    },
+                    { "id": 7, "score": 0.5, "payload": { "_namespace": "prod" } },
+                ]
+            })))
+            .mount(&server)
+            .await;
+
+        let options = QueryOptions {
+            top_k: 2,
+            include_metadata: true,
+            namespace: Some("prod".to_string()),
+            ..Default::default()
+        };
+
+        let results = QdrantClient::new(server.uri()).query("docs", &[1.0, 0.0], &options).await.unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].id, "doc-a");
+        assert_eq!(results[0].score, 0.98);
+        assert_eq!(results[0].metadata.as_ref().unwrap().len(), 1);
+        assert_eq!(results[0].metadata.as_ref().unwrap()["title"], "Dune");
+        assert_eq!(results[1].id, "7");
+        assert!(results[1].metadata.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_qdrant_delete_by_ids_and_namespace() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/collections/docs/points/delete"))
+            .and(body_json(json!({ "points": [42] })))
+            .respond_with(ok(json!({ "operation_id": 2, "status": "completed" })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/collections/docs/points/delete"))
+            .and(body_json(json!({
+                "filter": { "must": [{ "key": "_namespace", "match": { "value": "prod" } }] }
+            })))
+            .respond_with(ok(json!({ "operation_id": 3, "status": "completed" })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = QdrantClient::new(server.uri());
+        client.delete("docs", &["42"], &DeleteOptions::default()).await.unwrap();
+        let namespace = DeleteOptions {
+            namespace: Some("prod".to_string()),
+            delete_all: true,
+            ..Default::default()
+        };
+        client.delete("docs", &[], &namespace).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_qdrant_same_id_in_two_namespaces() {
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/collections/docs/points"))
+            .respond_with(ok(json!({ "operation_id": 1, "status": "completed" })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/collections/docs/points/delete"))
+            .respond_with(ok(json!({ "operation_id": 2, "status": "completed" })))
+            .mount(&server)
+            .await;
+
+        let client = QdrantClient::new(server.uri());
+        let vector = Vector {
+            id: "doc-a".to_string(),
+            values: vec![0.1, 0.2],
+            metadata: None,
+            sparse_values: None,
+        };
+        for namespace in ["alpha", "beta"] {
+            let options = UpsertOptions {
+                namespace: Some(namespace.to_string()),
+            };
+            client.upsert("docs", std::slice::from_ref(&vector), &options).await.unwrap();
+        }
+        let alpha = DeleteOptions {
+            namespace: Some("alpha".to_string()),
+            ..Default::default()
+        };
+        client.delete("docs", &["doc-a"], &alpha).await.unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        let bodies: Vec<serde_json::Value> = requests.iter().map(|r| serde_json::from_slice(&r.body).unwrap()).collect();
+        let alpha_id = &bodies[0]["points"][0]["id"];
+        let beta_id = &bodies[1]["points"][0]["id"];
+        assert_ne!(alpha_id, beta_id);
+        assert_eq!(bodies[0]["points"][0]["payload"]["_original_id"], "doc-a");
+        assert_eq!(bodies[2], json!({ "points": [alpha_id] }));
+    }
+
+    #[tokio::test]
+    async fn test_qdrant_error_mapping() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/collections/missing"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+                "status": { "error": "Not found: Collection `missing` doesn't exist!" },
+                "time": 0.0
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/collections/docs/points/query"))
+            .respond_with(ResponseTemplate::new(403).set_body_string("forbidden"))
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/collections/docs/points"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+                "status": { "error": "Wrong input: Vector dimension error: expected dim: 2, got 3" },
+                "time": 0.0
+            })))
+            .mount(&server)
+            .await;
+
+        let client = QdrantClient::new(server.uri());
+
+        let missing = client.describe_collection("missing").await.unwrap_err();
+        assert!(matches!(missing, Error::IndexNotFound(ref name) if name == "missing"));
+
+        let auth = client.query("docs", &[1.0, 0.0], &QueryOptions::default()).await.unwrap_err();
+        assert!(matches!(auth, Error::Auth(ref message) if message == "forbidden"));
+
+        let vector = Vector {
+            id: "1".to_string(),
+            values: vec![1.0, 2.0, 3.0],
+            metadata: None,
+            sparse_values: None,
+        };
+        match client.upsert("docs", &[vector], &UpsertOptions::default()).await.unwrap_err() {
+            Error::Api { message, code } => {
+                assert_eq!(message, "Wrong input: Vector dimension error: expected dim: 2, got 3");
+                assert_eq!(code.as_deref(), Some("400"));
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+}
+
+#[cfg(feature = "weaviate")]
+mod weaviate {
+    use serde_json::json;
+    use swissknife_vectordb_sdk::weaviate::WeaviateClient;
+    use swissknife_vectordb_sdk::{DeleteOptions, Error, QueryOptions, UpsertOptions, Vector, VectorDatabaseProvider};
+    use wiremock::matchers::{body_json, body_partial_json, header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    async fn mount_schema(server: &MockServer, multi_tenant: bool) {
+        Mock::given(method("GET"))
+            .and(path("/v1/schema/Docs"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "class": "Docs",
+                "description": "swissknife:dimension=2",
+                "vectorIndexConfig": { "distance": "cosine" },
+                "multiTenancyConfig": { "enabled": multi_tenant },
+                "properties": [
+                    { "name": "swissknifeId", "dataType": ["text"] },
+                    { "name": "title", "dataType": ["text"] },
+                ],
+            })))
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_weaviate_upsert_sends_batch_with_tenant() {
+        let server = MockServer::start().await;
+        mount_schema(&server, true).await;
+        Mock::given(method("POST"))
+            .and(path("/v1/batch/objects"))
+            .and(header("authorization", "Bearer wv-key"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([{ "result": {} }])))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let vector = Vector {
+            id: "doc-a".to_string(),
+            values: vec![0.1, 0.2],
+            metadata: Some([("title".to_string(), json!("Dune"))].into()),
+            sparse_values: None,
+        };
+        let options = UpsertOptions {
+            namespace: Some("prod".to_string()),
+        };
+
+        let client = WeaviateClient::new(server.uri()).with_api_key("wv-key");
+        client.upsert("docs", &[vector], &options).await.unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&requests.last().unwrap().body).unwrap();
+        let object = &body["objects"][0];
+        assert_eq!(object["class"], "Docs");
+        assert_eq!(object["tenant"], "prod");
+        assert_eq!(object["properties"], json!({ "title": "Dune", "swissknifeId": "doc-a" }));
+        assert_ne!(object["id"], "doc-a");
+    }
+
+    #[tokio::test]
+    async fn test_weaviate_upsert_reports_object_errors() {
+        let server = MockServer::start().await;
+        mount_schema(&server, false).await;
+        Mock::given(method("POST"))
+            .and(path("/v1/batch/objects"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                { "result": { "errors": { "error": [{ "message": "vector lengths don't match" }] } } }
+            ])))
+            .mount(&server)
+            .await;
+
+        let vector = Vector {
+            id: "1".to_string(),
+            values: vec![0.1, 0.2, 0.3],
+            metadata: None,
+            sparse_values: None,
+        };
+        let error = WeaviateClient::new(server.uri())
+            .upsert("docs", &[vector], &UpsertOptions::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(error, Error::Api { ref message, .. } if message == "vector lengths don't match"));
+    }
+
+    #[tokio::test]
+    async fn test_weaviate_query_reads_graphql_hits() {
+        let server = MockServer::start().await;
+        mount_schema(&server, false).await;
+        Mock::given(method("POST"))
+            .and(path("/v1/graphql"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": {
+                    "Get": {
+                        "Docs": [
+                            { "title": "Dune", "swissknifeId": "doc-a", "_additional": { "id": "b1", "distance": 0.25 } },
+                            { "title": null, "swissknifeId": null, "_additional": { "id": "b2", "distance": 0.5 } },
+                        ]
+                    }
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let options = QueryOptions {
+            top_k: 2,
+            include_metadata: true,
+            ..Default::default()
+        };
+        let results = WeaviateClient::new(server.uri()).query("docs", &[1.0, 0.0], &options).await.unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].id, "doc-a");
+        assert_eq!(results[0].score, 0.75);
+        assert_eq!(results[0].metadata.as_ref().unwrap()["title"], "Dune");
+        assert_eq!(results[1].id, "b2");
+        assert!(results[1].metadata.is_none());
+
+        let requests = server.received_requests().await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&requests.last().unwrap().body).unwrap();
+        let query = body["query"].as_str().unwrap();
+        assert!(query.contains("Docs(nearVector: {vector: [1.0,0.0]}, limit: 2)"));
+        assert!(query.contains("swissknifeId title _additional { id distance }"));
+    }
+
+    #[tokio::test]
+    async fn test_weaviate_query_negates_euclidean_distance() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/schema/Docs"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "class": "Docs",
+                "description": "swissknife:dimension=2",
+                "vectorIndexConfig": { "distance": "l2-squared" },
+                "properties": [{ "name": "swissknifeId", "dataType": ["text"] }],
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/graphql"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": {
+                    "Get": {
+                        "Docs": [
+                            { "swissknifeId": "near", "_additional": { "id": "b1", "distance": 4.0 } },
+                            { "swissknifeId": "far", "_additional": { "id": "b2", "distance": 9.0 } },
+                        ]
+                    }
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let options = QueryOptions {
+            top_k: 2,
+            ..Default::default()
+        };
+        let results = WeaviateClient::new(server.uri()).query("docs", &[1.0, 0.0], &options).await.unwrap();
+
+        assert_eq!((results[0].id.as_str(), results[0].score), ("near", -2.0));
+        assert_eq!((results[1].id.as_str(), results[1].score), ("far", -3.0));
+    }
+
+    #[tokio::test]
+    async fn test_weaviate_delete_all_matches_every_object_until_drained() {
+        let server = MockServer::start().await;
+        mount_schema(&server, false).await;
+        let everything = json!({
+            "match": {
+                "class": "Docs",
+                "where": { "path": ["id"], "operator": "NotEqual", "valueText": "00000000-0000-0000-0000-000000000000" }
+            }
+        });
+        Mock::given(method("DELETE"))
+            .and(path("/v1/batch/objects"))
+            .and(body_partial_json(everything.clone()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "results": { "matches": 10000, "limit": 10000, "successful": 10000, "failed": 0 }
+            })))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/v1/batch/objects"))
+            .and(body_partial_json(everything))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "results": { "matches": 12, "limit": 10000, "successful": 12, "failed": 0 }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let options = DeleteOptions {
+            delete_all: true,
+            ..Default::default()
+        };
+        WeaviateClient::new(server.uri()).delete("docs", &[], &options).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_weaviate_delete_all_in_tenant_drops_tenant() {
+        let server = MockServer::start().await;
+        mount_schema(&server, true).await;
+        Mock::given(method("DELETE"))
+            .and(path("/v1/schema/Docs/tenants"))
+            .and(body_json(json!(["prod"])))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let options = DeleteOptions {
+            namespace: Some("prod".to_string()),
+            delete_all: true,
+            ..Default::default()
+        };
+        WeaviateClient::new(server.uri()).delete("docs", &[], &options).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_weaviate_delete_by_ids_uses_tenant() {
+        let server = MockServer::start().await;
+        mount_schema(&server, true).await;
+        Mock::given(method("DELETE"))
+            .and(path("/v1/batch/objects"))
+            .and(query_param("tenant", "default"))
+            .and(body_partial_json(json!({
+                "match": {
+                    "where": {
+                        "operator": "Or",
+                        "operands": [{
+                            "path": ["id"],
+                            "operator": "Equal",
+                            "valueText": "6a2f41a3-c54c-fce8-32d2-0324e1c32e22"
+                        }]
+                    }
+                }
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "results": { "matches": 1 } })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        WeaviateClient::new(server.uri())
+            .delete("docs", &["6a2f41a3-c54c-fce8-32d2-0324e1c32e22"], &DeleteOptions::default())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_weaviate_error_mapping() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/schema/Missing"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/schema"))
+            .respond_with(ResponseTemplate::new(401).set_body_json(json!({
+                "error": [{ "message": "anonymous access not enabled" }]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/schema"))
+            .respond_with(ResponseTemplate::new(422).set_body_json(json!({
+                "error": [{ "message": "class name Docs already exists" }]
+            })))
+            .mount(&server)
+            .await;
+
+        let client = WeaviateClient::new(server.uri());
+
+        let missing = client.describe_collection("missing").await.unwrap_err();
+        assert!(matches!(missing, Error::IndexNotFound(ref name) if name == "Missing"));
+
+        let auth = client.list_collections().await.unwrap_err();
+        assert!(matches!(auth, Error::Auth(ref message) if message == "anonymous access not enabled"));
+
+        match client
+            .create_collection("docs", 2, swissknife_vectordb_sdk::DistanceMetric::Cosine)
+            .await
+            .unwrap_err()
+        {
+            Error::Api { message, code } => {
+                assert_eq!(message, "class name Docs already exists");
+                assert_eq!(code.as_deref(), Some("422"));
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+}
+
+#[cfg(feature = "chroma")]
+mod chroma {
+    use serde_json::json;
+    use swissknife_vectordb_sdk::chroma::ChromaClient;
+    use swissknife_vectordb_sdk::{DeleteOptions, Error, QueryOptions, UpsertOptions, Vector, VectorDatabaseProvider};
+    use wiremock::matchers::{body_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const COLLECTIONS: &str = "/api/v2/tenants/default_tenant/databases/default_database/collections";
+
+    async fn mount_collection(server: &MockServer) {
+        Mock::given(method("GET"))
+            .and(path(format!("{}/docs", COLLECTIONS)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": "c-1",
+                "name": "docs",
+                "metadata": { "hnsw:space": "cosine", "swissknife:dimension": 2 },
+                "dimension": 2,
+            })))
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_chroma_upsert_posts_columns() {
+        let server = MockServer::start().await;
+        mount_collection(&server).await;
+        Mock::given(method("POST"))
+            .and(path(format!("{}/c-1/upsert", COLLECTIONS)))
+            .and(header("x-chroma-token", "ch-key"))
+            .and(body_json(json!({
+                "ids": ["a", "b"],
+                "embeddings": [[1.0, 0.0], [0.0, 1.0]],
+                "metadatas": [{ "genre": "drama" }, null],
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let vectors = vec![
+            Vector {
+                id: "a".to_string(),
+                values: vec![1.0, 0.0],
+                metadata: Some([("genre".to_string(), json!("drama"))].into()),
+                sparse_values: None,
+            },
+            Vector {
+                id: "b".to_string(),
+                values: vec![0.0, 1.0],
+                metadata: None,
+                sparse_values: None,
+            },
+        ];
+
+        let client = ChromaClient::new(server.uri()).with_api_key("ch-key");
+        let result = client.upsert("docs", &vectors, &UpsertOptions::default()).await.unwrap();
+        assert_eq!(result.upserted_count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_chroma_query_converts_distances() {
+        let server = MockServer::start().await;
+        mount_collection(&server).await;
+        Mock::given(method("POST"))
+            .and(path(format!("{}/c-1/query", COLLECTIONS)))
+            .and(body_json(json!({
+                "query_embeddings": [[1.0, 0.0]],
+                "n_results": 2,
+                "include": ["metadatas", "distances"],
+                "where": { "genre": { "$eq": "drama" } },
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "ids": [["a", "c"]],
+                "distances": [[0.0, 0.5]],
+                "metadatas": [[{ "genre": "drama" }, null]],
+            })))
+            .mount(&server)
+            .await;
+
+        let options = QueryOptions {
+            top_k: 2,
+            include_metadata: true,
+            filter: Some([("genre".to_string(), json!("drama"))].into()),
+            ..Default::default()
+        };
+        let results = ChromaClient::new(server.uri()).query("docs", &[1.0, 0.0], &options).await.unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!((results[0].id.as_str(), results[0].score), ("a", 1.0));
+        assert_eq!((results[1].id.as_str(), results[1].score), ("c", 0.5));
+        assert_eq!(results[0].metadata.as_ref().unwrap()["genre"], "drama");
+        assert!(results[1].metadata.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_chroma_delete_by_ids_and_all() {
+        let server = MockServer::start().await;
+        mount_collection(&server).await;
+        Mock::given(method("POST"))
+            .and(path(format!("{}/c-1/get", COLLECTIONS)))
+            .and(body_json(json!({ "include": [] })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "ids": ["a", "b", "c"] })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path(format!("{}/c-1/delete", COLLECTIONS)))
+            .and(body_json(json!({ "ids": ["a"] })))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path(format!("{}/c-1/delete", COLLECTIONS)))
+            .and(body_json(json!({ "ids": ["a", "b", "c"] })))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = ChromaClient::new(server.uri());
+        client.delete("docs", &["a"], &DeleteOptions::default()).await.unwrap();
+        let all = DeleteOptions {
+            delete_all: true,
+            ..Default::default()
+        };
+        client.delete("docs", &[], &all).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_chroma_error_mapping() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(format!("{}/missing", COLLECTIONS)))
+            .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+                "error": "InvalidCollection",
+                "message": "Collection missing does not exist."
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(COLLECTIONS))
+            .respond_with(ResponseTemplate::new(403).set_body_json(json!({ "error": "Forbidden" })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path(COLLECTIONS))
+            .respond_with(ResponseTemplate::new(409).set_body_json(json!({
+                "error": "UniqueConstraintError",
+                "message": "Collection docs already exists"
+            })))
+            .mount(&server)
+            .await;
+
+        let client = ChromaClient::new(server.uri());
+
+        let missing = client.describe_collection("missing").await.unwrap_err();
+        assert!(matches!(missing, Error::IndexNotFound(ref name) if name == "missing"));
+
+        let auth = client.list_collections().await.unwrap_err();
+        assert!(matches!(auth, Error::Auth(ref message) if message == "Forbidden"));
+
+        match client
+            .create_collection("docs", 2, swissknife_vectordb_sdk::DistanceMetric::Cosine)
+            .await
+            .unwrap_err()
+        {
+            Error::Api { message, code } => {
+                assert_eq!(message, "Collection docs already exists");
+                assert_eq!(code.as_deref(), Some("409"));
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+}
+
+#[cfg(feature = "milvus")]
+mod milvus {
+    use serde_json::json;
+    use swissknife_vectordb_sdk::milvus::MilvusClient;
+    use swissknife_vectordb_sdk::{DeleteOptions, Error, QueryOptions, UpsertOptions, Vector, VectorDatabaseProvider};
+    use wiremock::matchers::{body_json, body_partial_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn ok(data: serde_json::Value) -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_json(json!({ "code": 0, "data": data }))
+    }
+
+    #[tokio::test]
+    async fn test_milvus_upsert_sends_rows_to_partition() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v2/vectordb/partitions/has"))
+            .respond_with(ok(json!({ "has": true })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v2/vectordb/entities/upsert"))
+            .and(header("authorization", "Bearer root:Milvus"))
+            .and(body_json(json!({
+                "collectionName": "docs",
+                "partitionName": "prod",
+                "dbName": "search",
+                "data": [{ "id": "a", "vector": [1.0, 0.0], "sparse": {}, "metadata": { "genre": "drama" } }],
+            })))
+            .respond_with(ok(json!({ "upsertCount": 1, "upsertIds": ["a"] })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let vector = Vector {
+            id: "a".to_string(),
+            values: vec![1.0, 0.0],
+            metadata: Some([("genre".to_string(), json!("drama"))].into()),
+            sparse_values: None,
+        };
+        let options = UpsertOptions {
+            namespace: Some("prod".to_string()),
+        };
+
+        let client = MilvusClient::new(server.uri())
+            .with_token("root:Milvus")
+            .with_database("search");
+        let result = client.upsert("docs", &[vector], &options).await.unwrap();
+        assert_eq!(result.upserted_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_milvus_query_rescores_euclidean_distance() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v2/vectordb/collections/describe"))
+            .respond_with(ok(json!({
+                "collectionName": "docs",
+                "fields": [],
+                "indexes": [{ "fieldName": "vector", "metricType": "L2" }],
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v2/vectordb/entities/search"))
+            .and(body_partial_json(json!({
+                "annsField": "vector",
+                "limit": 2,
+                "outputFields": ["id", "metadata"],
+                "filter": "metadata[\"genre\"] == \"drama\"",
+            })))
+            .respond_with(ok(json!([
+                { "id": "a", "distance": 4.0, "metadata": { "genre": "drama" } },
+                { "id": "b", "distance": 9.0, "metadata": {} },
+            ])))
+            .mount(&server)
+            .await;
+
+        let options = QueryOptions {
+            top_k: 2,
+            include_metadata: true,
+            filter: Some([("genre".to_string(), json!("drama"))].into()),
+            ..Default::default()
+        };
+        let results = MilvusClient::new(server.uri()).query("docs", &[1.0, 0.0], &options).await.unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!((results[0].id.as_str(), results[0].score), ("a", -2.0));
+        assert_eq!((results[1].id.as_str(), results[1].score), ("b", -3.0));
+        assert_eq!(results[0].metadata.as_ref().unwrap()["genre"], "drama");
+        assert!(results[1].metadata.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_milvus_rejects_unsupported_partitions_and_ids() {
+        let server = MockServer::start().await;
+        let client = MilvusClient::new(server.uri());
+        let vector = |id: String| Vector {
+            id,
+            values: vec![1.0, 0.0],
+            metadata: None,
+            sparse_values: None,
+        };
+
+        let dashed = UpsertOptions {
+            namespace: Some("tenant-a".to_string()),
+        };
+        let error = client.upsert("docs", &[vector("a".to_string())], &dashed).await.unwrap_err();
+        assert!(matches!(error, Error::Unsupported(_)));
+
+        let long_id = "x".repeat(513);
+        let error = client
+            .upsert("docs", &[vector(long_id)], &UpsertOptions::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(error, Error::Unsupported(_)));
+
+        let options = QueryOptions {
+            top_k: 1,
+            namespace: Some("1st".to_string()),
+            ..Default::default()
+        };
+        let error = client.query("docs", &[1.0, 0.0], &options).await.unwrap_err();
+        assert!(matches!(error, Error::Unsupported(_)));
+
+        assert!(server.received_requests().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_milvus_delete_builds_filters() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v2/vectordb/entities/delete"))
+            .and(body_json(json!({ "collectionName": "docs", "filter": "id in [\"a\",\"b\"]" })))
+            .respond_with(ok(json!({})))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v2/vectordb/entities/delete"))
+            .and(body_json(json!({ "collectionName": "docs", "filter": "id != \"\"", "partitionName": "prod" })))
+            .respond_with(ok(json!({})))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = MilvusClient::new(server.uri());
+        client.delete("docs", &["a", "b"], &DeleteOptions::default()).await.unwrap();
+        let all = DeleteOptions {
+            namespace: Some("prod".to_string()),
+            delete_all: true,
+            ..Default::default()
+        };
+        client.delete("docs", &[], &all).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_milvus_error_mapping() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v2/vectordb/collections/describe"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "code": 100,
+                "message": "can't find collection[database=default][collection=missing]"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v2/vectordb/collections/list"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "code": 1800,
+                "message": "user hasn't authenticated"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v2/vectordb/entities/delete"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "code": 1100,
+                "message": "invalid parameter: cannot parse expression"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v2/vectordb/entities/get"))
+            .respond_with(ResponseTemplate::new(429))
+            .mount(&server)
+            .await;
+
+        let client = MilvusClient::new(server.uri());
+
+        let missing = client.describe_collection("missing").await.unwrap_err();
+        assert!(matches!(missing, Error::IndexNotFound(ref name) if name == "missing"));
+
+        let auth = client.list_collections().await.unwrap_err();
+        assert!(matches!(auth, Error::Auth(ref message) if message == "user hasn't authenticated"));
+
+        match client.delete("docs", &["a"], &DeleteOptions::default()).await.unwrap_err() {
+            Error::Api { message, code } => {
+                assert_eq!(message, "invalid parameter: cannot parse expression");
+                assert_eq!(code.as_deref(), Some("1100"));
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+
+        let limited = client.fetch("docs", &["a"], None).await.unwrap_err();
+        assert!(matches!(limited, Error::RateLimited));
+    }
+}
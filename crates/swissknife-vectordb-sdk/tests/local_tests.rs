@@ -0,0 +1,326 @@
+#![cfg(feature = "local")]
+
+use serde_json::json;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use swissknife_vectordb_sdk::local::{HnswConfig, LocalVectorStore};
+use swissknife_vectordb_sdk::{
+    DeleteOptions, DistanceMetric, Error, HybridSearchProvider, QueryOptions, SparseVector, UpsertOptions, Vector,
+    VectorDatabaseProvider,
+};
+
+fn vector(id: &str, values: Vec<f32>, metadata: serde_json::Value) -> Vector {
+    Vector {
+        id: id.to_string(),
+        values,
+        metadata: Some(serde_json::from_value(metadata).unwrap()),
+        sparse_values: None,
+    }
+}
+
+fn query(top_k: u32) -> QueryOptions {
+    QueryOptions {
+        top_k,
+        include_metadata: true,
+        ..Default::default()
+    }
+}
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("swissknife-local-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+async fn seeded(metric: DistanceMetric) -> LocalVectorStore {
+    let store = LocalVectorStore::new();
+    store.create_collection("docs", 2, metric).await.unwrap();
+    store
+        .upsert(
+            "docs",
+            &[
+                vector("a", vec![1.0, 0.0], json!({"genre": "drama", "year": 1999})),
+                vector("b", vec![0.0, 1.0], json!({"genre": "comedy", "year": 2005})),
+                vector("c", vec![3.0, 3.0], json!({"genre": "drama", "year": 2012, "tags": ["x", "y"]})),
+            ],
+            &UpsertOptions::default(),
+        )
+        .await
+        .unwrap();
+    store
+}
+
+fn ids(results: &[swissknife_vectordb_sdk::QueryResult]) -> Vec<&str> {
+    results.iter().map(|r| r.id.as_str()).collect()
+}
+
+#[tokio::test]
+async fn test_query_ranks_by_metric() {
+    let cosine = seeded(DistanceMetric::Cosine).await;
+    let results = cosine.query("docs", &[1.0, 0.1], &query(3)).await.unwrap();
+    assert_eq!(ids(&results), vec!["a", "c", "b"]);
+    assert!(results[0].score > results[1].score);
+
+    let euclidean = seeded(DistanceMetric::Euclidean).await;
+    let results = euclidean.query("docs", &[2.5, 2.5], &query(3)).await.unwrap();
+    assert_eq!(results[0].id, "c");
+    assert!(results[0].score > results[1].score);
+
+    let dot = seeded(DistanceMetric::DotProduct).await;
+    let results = dot.query("docs", &[1.0, 0.0], &query(1)).await.unwrap();
+    assert_eq!(ids(&results), vec!["c"]);
+    assert_eq!(results[0].score, 3.0);
+}
+
+#[tokio::test]
+async fn test_euclidean_scores_match_between_query_and_hybrid_query() {
+    let store = seeded(DistanceMetric::Euclidean).await;
+    let results = store.query("docs", &[1.0, 0.2], &query(3)).await.unwrap();
+    assert_eq!(ids(&results), vec!["a", "b", "c"]);
+    assert!((results[0].score + 0.2).abs() < 1e-6);
+    assert!(results.windows(2).all(|w| w[0].score > w[1].score));
+
+    let empty = SparseVector {
+        indices: Vec::new(),
+        values: Vec::new(),
+    };
+    let hybrid = store.hybrid_query("docs", &[1.0, 0.2], &empty, 1.0, &query(3)).await.unwrap();
+    assert_eq!(ids(&hybrid), ids(&results));
+    for (dense, blended) in results.iter().zip(&hybrid) {
+        assert!((dense.score - blended.score).abs() < 1e-6);
+    }
+}
+
+#[tokio::test]
+async fn test_query_applies_metadata_filter() {
+    let store = seeded(DistanceMetric::Cosine).await;
+    let mut options = query(10);
+
+    options.filter = Some(serde_json::from_value(json!({"genre": "drama", "year": {"$gt": 2000}})).unwrap());
+    let results = store.query("docs", &[1.0, 0.0], &options).await.unwrap();
+    assert_eq!(ids(&results), vec!["c"]);
+
+    options.filter = Some(serde_json::from_value(json!({"$or": [{"tags": "x"}, {"year": {"$lt": 2000}}]})).unwrap());
+    let results = store.query("docs", &[1.0, 0.0], &options).await.unwrap();
+    assert_eq!(ids(&results), vec!["a", "c"]);
+
+    options.filter = Some(serde_json::from_value(json!({"genre": {"$nin": ["drama"]}})).unwrap());
+    let results = store.query("docs", &[1.0, 0.0], &options).await.unwrap();
+    assert_eq!(ids(&results), vec!["b"]);
+
+    options.filter = Some(serde_json::from_value(json!({"year": {"$between": [1, 2]}})).unwrap());
+    assert!(matches!(
+        store.query("docs", &[1.0, 0.0], &options).await,
+        Err(Error::InvalidFilter(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_namespaces_are_isolated() {
+    let store = LocalVectorStore::new();
+    store.create_collection("docs", 2, DistanceMetric::Cosine).await.unwrap();
+    let tenant = UpsertOptions {
+        namespace: Some("tenant".to_string()),
+    };
+    store
+        .upsert("docs", &[vector("a", vec![1.0, 0.0], json!({}))], &UpsertOptions::default())
+        .await
+        .unwrap();
+    store
+        .upsert("docs", &[vector("b", vec![1.0, 0.0], json!({}))], &tenant)
+        .await
+        .unwrap();
+
+    let mut options = query(10);
+    options.namespace = Some("tenant".to_string());
+    let results = store.query("docs", &[1.0, 0.0], &options).await.unwrap();
+    assert_eq!(ids(&results), vec!["b"]);
+    assert!(store.fetch("docs", &["a"], Some("tenant")).await.unwrap().is_empty());
+    assert_eq!(store.describe_collection("docs").await.unwrap().vector_count, Some(2));
+
+    store
+        .delete(
+            "docs",
+            &[],
+            &DeleteOptions {
+                namespace: Some("tenant".to_string()),
+                delete_all: true,
+                filter: None,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(store.describe_collection("docs").await.unwrap().vector_count, Some(1));
+}
+
+#[tokio::test]
+async fn test_update_delete_and_dimension_checks() {
+    let store = seeded(DistanceMetric::Cosine).await;
+
+    let mut metadata = HashMap::new();
+    metadata.insert("year".to_string(), json!(2020));
+    store.update("docs", "a", Some(&[0.0, 2.0]), Some(metadata)).await.unwrap();
+    let fetched = store.fetch("docs", &["a"], None).await.unwrap();
+    assert_eq!(fetched[0].values, vec![0.0, 2.0]);
+    assert_eq!(fetched[0].metadata.as_ref().unwrap()["genre"], json!("drama"));
+    assert_eq!(fetched[0].metadata.as_ref().unwrap()["year"], json!(2020));
+
+    store
+        .delete(
+            "docs",
+            &[],
+            &DeleteOptions {
+                filter: Some(serde_json::from_value(json!({"genre": "comedy"})).unwrap()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert!(store.fetch("docs", &["b"], None).await.unwrap().is_empty());
+
+    assert!(matches!(
+        store.query("docs", &[1.0, 0.0, 0.0], &query(1)).await,
+        Err(Error::DimensionMismatch { expected: 2, actual: 3 })
+    ));
+    assert!(matches!(
+        store.describe_collection("missing").await,
+        Err(Error::IndexNotFound(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_hybrid_query_blends_sparse_scores() {
+    let store = LocalVectorStore::new();
+    store.create_collection("docs", 2, DistanceMetric::DotProduct).await.unwrap();
+    let mut a = vector("a", vec![1.0, 0.0], json!({}));
+    a.sparse_values = Some(SparseVector {
+        indices: vec![1],
+        values: vec![0.1],
+    });
+    let mut b = vector("b", vec![0.5, 0.0], json!({}));
+    b.sparse_values = Some(SparseVector {
+        indices: vec![7],
+        values: vec![5.0],
+    });
+    store.upsert("docs", &[a, b], &UpsertOptions::default()).await.unwrap();
+
+    let sparse = SparseVector {
+        indices: vec![7],
+        values: vec![1.0],
+    };
+    let dense_only = store.hybrid_query("docs", &[1.0, 0.0], &sparse, 1.0, &query(2)).await.unwrap();
+    assert_eq!(ids(&dense_only), vec!["a", "b"]);
+
+    let sparse_only = store.hybrid_query("docs", &[1.0, 0.0], &sparse, 0.0, &query(2)).await.unwrap();
+    assert_eq!(ids(&sparse_only), vec!["b", "a"]);
+}
+
+#[tokio::test]
+async fn test_hnsw_index_matches_exact_search() {
+    let exact = LocalVectorStore::new();
+    let indexed = LocalVectorStore::new().with_hnsw(HnswConfig::default());
+    let vectors: Vec<Vector> = (0..500)
+        .map(|i| {
+            let angle = i as f32 * 0.37;
+            vector(&i.to_string(), vec![angle.cos(), angle.sin(), (i % 7) as f32 / 7.0], json!({"even": i % 2 == 0}))
+        })
+        .collect();
+    for store in [&exact, &indexed] {
+        store.create_collection("docs", 3, DistanceMetric::Euclidean).await.unwrap();
+        store.upsert("docs", &vectors, &UpsertOptions::default()).await.unwrap();
+    }
+
+    let probe = [0.3, -0.8, 0.5];
+    let expected = exact.query("docs", &probe, &query(10)).await.unwrap();
+    let actual = indexed.query("docs", &probe, &query(10)).await.unwrap();
+    assert_eq!(ids(&actual), ids(&expected));
+
+    let mut options = query(5);
+    options.filter = Some(serde_json::from_value(json!({"even": true})).unwrap());
+    let filtered = indexed.query("docs", &probe, &options).await.unwrap();
+    assert_eq!(filtered.len(), 5);
+    assert!(filtered.iter().all(|r| r.id.parse::<u32>().unwrap() % 2 == 0));
+}
+
+#[tokio::test]
+async fn test_hnsw_repeated_upserts_and_updates_stay_searchable() {
+    let store = LocalVectorStore::new().with_hnsw(HnswConfig::default());
+    store.create_collection("docs", 2, DistanceMetric::Euclidean).await.unwrap();
+    for round in 0..120 {
+        let vectors: Vec<Vector> = (0..10)
+            .map(|i| vector(&i.to_string(), vec![i as f32, round as f32], json!({"round": round})))
+            .collect();
+        store.upsert("docs", &vectors, &UpsertOptions::default()).await.unwrap();
+    }
+    for _ in 0..2 {
+        store.update("docs", "3", Some(&[3.0, 500.0]), None).await.unwrap();
+    }
+
+    assert_eq!(store.describe_collection("docs").await.unwrap().vector_count, Some(10));
+    let results = store.query("docs", &[3.0, 500.0], &query(2)).await.unwrap();
+    assert_eq!(ids(&results), vec!["3", "2"]);
+    assert_eq!(results[0].score, 0.0);
+    assert_eq!(results[1].metadata.as_ref().unwrap()["round"], json!(119));
+}
+
+#[tokio::test]
+async fn test_persistence_replays_snapshot_and_log() {
+    let dir = temp_dir("persist");
+    {
+        let store = LocalVectorStore::open(&dir).unwrap().with_snapshot_interval(2);
+        store.create_collection("docs", 2, DistanceMetric::Cosine).await.unwrap();
+        store
+            .upsert("docs", &[vector("a", vec![1.0, 0.0], json!({"n": 1}))], &UpsertOptions::default())
+            .await
+            .unwrap();
+        store
+            .upsert("docs", &[vector("b", vec![0.0, 1.0], json!({"n": 2}))], &UpsertOptions::default())
+            .await
+            .unwrap();
+        store.delete("docs", &["a"], &DeleteOptions::default()).await.unwrap();
+    }
+    assert!(dir.join("snapshot.json").exists());
+
+    let reopened = LocalVectorStore::open(&dir).unwrap();
+    let collection = reopened.describe_collection("docs").await.unwrap();
+    assert_eq!(collection.dimension, 2);
+    assert_eq!(collection.vector_count, Some(1));
+    let fetched = reopened.fetch("docs", &["a", "b"], None).await.unwrap();
+    assert_eq!(fetched.len(), 1);
+    assert_eq!(fetched[0].metadata.as_ref().unwrap()["n"], json!(2));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_persistence_recovers_from_torn_log_tail() {
+    let dir = temp_dir("torn");
+    {
+        let store = LocalVectorStore::open(&dir).unwrap();
+        store.create_collection("docs", 2, DistanceMetric::Cosine).await.unwrap();
+        store
+            .upsert("docs", &[vector("a", vec![1.0, 0.0], json!({"n": 1}))], &UpsertOptions::default())
+            .await
+            .unwrap();
+    }
+
+    // Simulate a crash halfway through an append, cut inside a multi-byte character.
+    let mut log = std::fs::OpenOptions::new().append(true).open(dir.join("wal.jsonl")).unwrap();
+    std::io::Write::write_all(&mut log, b"{\"op\":\"upsert\",\"collection\":\"docs\",\"namespace\":\"\xc3").unwrap();
+    drop(log);
+
+    {
+        let store = LocalVectorStore::open(&dir).unwrap();
+        assert_eq!(store.fetch("docs", &["a"], None).await.unwrap().len(), 1);
+        store
+            .upsert("docs", &[vector("b", vec![0.0, 1.0], json!({"n": 2}))], &UpsertOptions::default())
+            .await
+            .unwrap();
+    }
+
+    let reopened = LocalVectorStore::open(&dir).unwrap();
+    let fetched = reopened.fetch("docs", &["a", "b"], None).await.unwrap();
+    assert_eq!(fetched.len(), 2);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,105 @@
+#[cfg(feature = "router")]
+mod router;
+#[cfg(feature = "router")]
+pub use router::{WebhookHandler, WebhookRouter};
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+pub use http::HeaderMap;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{0}")]
+pub struct VerificationError(pub String);
+
+// Implemented by each SDK's error type so the router can tell a forged request
+// (401) from one it could not parse (400).
+pub trait WebhookError: std::error::Error + From<VerificationError> + Send + Sync + 'static {
+    fn is_verification(&self) -> bool;
+}
+
+pub trait EventId: Send + 'static {
+    // The provider's delivery or event id, used to skip redeliveries of events
+    // that were already handled.
+    fn event_id(&self) -> Option<&str>;
+}
+
+pub trait WebhookSource: Send + Sync {
+    type Event: EventId;
+    type Error: WebhookError;
+
+    fn provider(&self) -> &'static str;
+
+    fn verify(&self, headers: &HeaderMap, body: &[u8]) -> Result<(), Self::Error>;
+
+    // A delivery can carry several events (Adyen batches notification items).
+    fn parse(&self, headers: &HeaderMap, body: &[u8]) -> Result<Vec<Self::Event>, Self::Error>;
+
+    // Body the provider expects in a successful response.
+    fn acknowledgement(&self) -> &'static str {
+        ""
+    }
+
+    fn receive(&self, headers: &HeaderMap, body: &[u8]) -> Result<Vec<Self::Event>, Self::Error> {
+        self.verify(headers, body)?;
+        self.parse(headers, body)
+    }
+}
+
+pub fn header<'a>(headers: &'a HeaderMap, name: &str) -> Result<&'a str, VerificationError> {
+    headers
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .ok_or_else(|| VerificationError(format!("Missing {} header", name)))
+}
+
+pub fn optional_header(headers: &HeaderMap, name: &str) -> Option<String> {
+    headers.get(name).and_then(|v| v.to_str().ok()).map(String::from)
+}
+
+pub fn verify_hmac_sha256(key: &[u8], parts: &[&[u8]], signature: &[u8]) -> Result<(), VerificationError> {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(key).map_err(|_| VerificationError("Invalid signing secret".into()))?;
+    for part in parts {
+        mac.update(part);
+    }
+    mac.verify_slice(signature)
+        .map_err(|_| VerificationError("Signature mismatch".into()))
+}
+
+pub fn decode_base64(value: &str) -> Result<Vec<u8>, VerificationError> {
+    use base64::{engine::general_purpose::STANDARD, Engine};
+
+    STANDARD
+        .decode(value.trim())
+        .map_err(|_| VerificationError("Signature is not valid base64".into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_hmac_sha256() {
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"secret").unwrap();
+        mac.update(b"t.body");
+        let signature = mac.finalize().into_bytes();
+
+        assert!(verify_hmac_sha256(b"secret", &[b"t", b".", b"body"], &signature).is_ok());
+        assert_eq!(
+            verify_hmac_sha256(b"other", &[b"t.body"], &signature),
+            Err(VerificationError("Signature mismatch".into()))
+        );
+    }
+
+    #[test]
+    fn test_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-topic", "orders/create".parse().unwrap());
+
+        assert_eq!(header(&headers, "x-topic"), Ok("orders/create"));
+        assert_eq!(header(&headers, "x-id"), Err(VerificationError("Missing x-id header".into())));
+        assert_eq!(optional_header(&headers, "x-id"), None);
+        assert!(decode_base64("not base64!").is_err());
+    }
+}
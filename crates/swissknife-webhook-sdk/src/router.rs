@@ -0,0 +1,248 @@
+use crate::{EventId, WebhookError, WebhookSource};
+use async_trait::async_trait;
+use axum::{
+    body::Bytes,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::post,
+    Router,
+};
+use std::collections::{HashSet, VecDeque};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+// How many handled event ids each router remembers to skip redeliveries.
+const HANDLED_CAPACITY: usize = 10_000;
+
+#[async_trait]
+pub trait WebhookHandler<E, Err>: Send + Sync {
+    async fn handle(&self, event: E) -> Result<(), Err>;
+}
+
+#[async_trait]
+impl<F, Fut, E, Err> WebhookHandler<E, Err> for F
+where
+    F: Fn(E) -> Fut + Send + Sync,
+    Fut: Future<Output = Result<(), Err>> + Send,
+    E: Send + 'static,
+{
+    async fn handle(&self, event: E) -> Result<(), Err> {
+        self(event).await
+    }
+}
+
+type Source<E, Err> = Arc<dyn WebhookSource<Event = E, Error = Err>>;
+
+// Serves each source on its own path and passes verified events to the
+// handler. Providers retry a whole delivery when the response is not 2xx, so
+// the router remembers which events it already handled and skips them on the
+// retry; only events that failed run again.
+pub struct WebhookRouter<E, Err> {
+    handler: Arc<dyn WebhookHandler<E, Err>>,
+    sources: Vec<(String, Source<E, Err>)>,
+    handled: Arc<Mutex<HandledEvents>>,
+}
+
+impl<E: EventId, Err: WebhookError> WebhookRouter<E, Err> {
+    pub fn new(handler: impl WebhookHandler<E, Err> + 'static) -> Self {
+        Self {
+            handler: Arc::new(handler),
+            sources: Vec::new(),
+            handled: Arc::new(Mutex::new(HandledEvents::default())),
+        }
+    }
+
+    pub fn with_source(
+        mut self,
+        path: impl Into<String>,
+        source: impl WebhookSource<Event = E, Error = Err> + 'static,
+    ) -> Self {
+        self.sources.push((path.into(), Arc::new(source)));
+        self
+    }
+
+    pub fn into_router(self) -> Router {
+        let handled = self.handled;
+        self.sources.into_iter().fold(Router::new(), |router, (path, source)| {
+            let handler = self.handler.clone();
+            let handled = handled.clone();
+            router.route(
+                &path,
+                post(move |headers: HeaderMap, body: Bytes| {
+                    let source = source.clone();
+                    let handler = handler.clone();
+                    let handled = handled.clone();
+                    async move { dispatch(source.as_ref(), handler.as_ref(), &handled, &headers, &body).await }
+                }),
+            )
+        })
+    }
+}
+
+#[derive(Default)]
+struct HandledEvents {
+    ids: HashSet<String>,
+    order: VecDeque<String>,
+}
+
+impl HandledEvents {
+    fn contains(&self, id: &str) -> bool {
+        self.ids.contains(id)
+    }
+
+    fn insert(&mut self, id: String) {
+        if self.ids.insert(id.clone()) {
+            self.order.push_back(id);
+        }
+        while self.order.len() > HANDLED_CAPACITY {
+            if let Some(oldest) = self.order.pop_front() {
+                self.ids.remove(&oldest);
+            }
+        }
+    }
+}
+
+async fn dispatch<E: EventId, Err: WebhookError>(
+    source: &dyn WebhookSource<Event = E, Error = Err>,
+    handler: &dyn WebhookHandler<E, Err>,
+    handled: &Mutex<HandledEvents>,
+    headers: &HeaderMap,
+    body: &[u8],
+) -> Response {
+    let events = match source.receive(headers, body) {
+        Ok(events) => events,
+        Err(e) if e.is_verification() => return (StatusCode::UNAUTHORIZED, e.to_string()).into_response(),
+        Err(e) => return (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    };
+
+    let mut failure = None;
+    for event in events {
+        let key = event.event_id().map(|id| format!("{}:{}", source.provider(), id));
+        if let Some(key) = &key {
+            if handled.lock().unwrap_or_else(|e| e.into_inner()).contains(key) {
+                continue;
+            }
+        }
+        match handler.handle(event).await {
+            Ok(()) => {
+                if let Some(key) = key {
+                    handled.lock().unwrap_or_else(|e| e.into_inner()).insert(key);
+                }
+            }
+            Err(e) => failure = failure.or(Some(e)),
+        }
+    }
+
+    match failure {
+        Some(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        None => (StatusCode::OK, source.acknowledgement()).into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::VerificationError;
+
+    #[derive(Debug, thiserror::Error)]
+    enum TestError {
+        #[error("{0}")]
+        Verification(#[from] VerificationError),
+        #[error("{0}")]
+        Handler(String),
+    }
+
+    impl WebhookError for TestError {
+        fn is_verification(&self) -> bool {
+            matches!(self, TestError::Verification(_))
+        }
+    }
+
+    struct Event(String);
+
+    impl EventId for Event {
+        fn event_id(&self) -> Option<&str> {
+            Some(&self.0)
+        }
+    }
+
+    // Each body line is one event id; "forged" fails verification.
+    struct LineSource;
+
+    impl WebhookSource for LineSource {
+        type Event = Event;
+        type Error = TestError;
+
+        fn provider(&self) -> &'static str {
+            "lines"
+        }
+
+        fn verify(&self, _headers: &HeaderMap, body: &[u8]) -> Result<(), TestError> {
+            if body == b"forged" {
+                return Err(VerificationError("Signature mismatch".into()).into());
+            }
+            Ok(())
+        }
+
+        fn parse(&self, _headers: &HeaderMap, body: &[u8]) -> Result<Vec<Event>, TestError> {
+            Ok(String::from_utf8_lossy(body).lines().map(|l| Event(l.to_string())).collect())
+        }
+
+        fn acknowledgement(&self) -> &'static str {
+            "[accepted]"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_redelivery_only_retries_failed_events() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let fail = Arc::new(Mutex::new(true));
+        let handler = {
+            let calls = calls.clone();
+            let fail = fail.clone();
+            move |event: Event| {
+                let calls = calls.clone();
+                let fail = fail.clone();
+                async move {
+                    calls.lock().unwrap().push(event.0.clone());
+                    if event.0 == "b" && *fail.lock().unwrap() {
+                        return Err(TestError::Handler("database down".into()));
+                    }
+                    Ok(())
+                }
+            }
+        };
+        let handled = Mutex::new(HandledEvents::default());
+        let headers = HeaderMap::new();
+
+        let response = dispatch(&LineSource, &handler, &handled, &headers, b"a\nb\nc").await;
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(*calls.lock().unwrap(), ["a", "b", "c"]);
+
+        *fail.lock().unwrap() = false;
+        calls.lock().unwrap().clear();
+        let response = dispatch(&LineSource, &handler, &handled, &headers, b"a\nb\nc").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(*calls.lock().unwrap(), ["b"]);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_unverified_requests() {
+        let handler = |_: Event| async { Ok::<_, TestError>(()) };
+        let handled = Mutex::new(HandledEvents::default());
+
+        let response = dispatch(&LineSource, &handler, &handled, &HeaderMap::new(), b"forged").await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[test]
+    fn test_handled_events_are_bounded() {
+        let mut handled = HandledEvents::default();
+        for i in 0..=HANDLED_CAPACITY {
+            handled.insert(i.to_string());
+        }
+        assert!(!handled.contains("0"));
+        assert!(handled.contains("1"));
+        assert_eq!(handled.ids.len(), HANDLED_CAPACITY);
+    }
+}